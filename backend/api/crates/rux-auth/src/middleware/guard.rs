@@ -202,6 +202,14 @@ pub async fn check_requirements<B: AuthBackend>(
         }
     }
 
+    // Check permission requirements
+    for permission in &requirements.permissions {
+        if !auth.backend().has_permission(user, permission).await? {
+            return Err(AuthError::new(AuthErrorCode::PermissionDenied)
+                .with_context("required_permission", permission));
+        }
+    }
+
     Ok(())
 }
 
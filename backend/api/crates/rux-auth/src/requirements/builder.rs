@@ -51,6 +51,9 @@ pub struct AuthRequirements {
     /// Minimum role level required
     pub(crate) min_role: Option<i32>,
 
+    /// Named permissions the user must all hold
+    pub(crate) permissions: Vec<String>,
+
     /// Ban cache duration (how long to trust cached ban status)
     pub(crate) ban_cache_duration: Duration,
 }
@@ -143,6 +146,16 @@ impl AuthRequirements {
         self
     }
 
+    /// Require a named permission (e.g. `"post.publish"`)
+    ///
+    /// Returns `PermissionDenied` error if the user doesn't hold it.
+    /// Permissions are resolved by your `AuthBackend::has_permission()` implementation.
+    /// Can be chained; every listed permission is required.
+    pub fn permission(mut self, permission: impl Into<String>) -> Self {
+        self.permissions.push(permission.into());
+        self
+    }
+
     /// Set how long to cache ban status checks
     ///
     /// Default is 5 minutes. Set lower for stricter checking.
@@ -190,6 +203,11 @@ impl AuthRequirements {
 /// let admin = auth_requirements()
 ///     .authenticated()
 ///     .role_min(3); // Admin = 3
+///
+/// // For a single capability - named permission
+/// let publish = auth_requirements()
+///     .authenticated()
+///     .permission("post.publish");
 /// ```
 pub fn auth_requirements() -> AuthRequirements {
     AuthRequirements::new()
@@ -247,6 +265,17 @@ mod tests {
         assert_eq!(conditional.totp_verified, Some(false));
     }
 
+    #[test]
+    fn test_permission_requirements() {
+        let req = auth_requirements()
+            .authenticated()
+            .permission("post.publish")
+            .permission(String::from("post.edit_any"));
+
+        assert_eq!(req.permissions, vec!["post.publish", "post.edit_any"]);
+        assert!(auth_requirements().permissions.is_empty());
+    }
+
     #[test]
     fn test_reauth_requirement() {
         let req = auth_requirements().reauth_within(Duration::minutes(5));
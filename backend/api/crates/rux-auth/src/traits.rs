@@ -96,6 +96,17 @@ pub trait AuthBackend: Clone + Send + Sync + 'static {
         password: &str,
     ) -> Result<bool, AuthError>;
 
+    /// Check whether a user holds a named permission
+    ///
+    /// Called when a `permission()` requirement is set. The default grants nothing.
+    async fn has_permission(
+        &self,
+        _user: &Self::User,
+        _permission: &str,
+    ) -> Result<bool, AuthError> {
+        Ok(false)
+    }
+
     /// Called after successful login (optional hook)
     async fn on_login(&self, _user: &Self::User) -> Result<(), AuthError> {
         Ok(())
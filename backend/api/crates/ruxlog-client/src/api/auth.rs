@@ -0,0 +1,90 @@
+use crate::dto::{
+    Message, Page, TwoFASetup, User, UserSession, V1ForgotPasswordVerifyPayload, V1GeneratePayload,
+    V1LoginPayload, V1RegisterPayload, V1ResetPayload, V1TwoFADisablePayload, V1TwoFAVerifyPayload,
+    V1VerifyPayload,
+};
+use crate::{Client, Result};
+
+/// `/auth/v1`
+pub struct Auth<'a> {
+    pub(crate) client: &'a Client,
+}
+
+impl Auth<'_> {
+    pub async fn register(&self, payload: &V1RegisterPayload) -> Result<User> {
+        self.client.post("/auth/v1/register", payload).await
+    }
+
+    /// Starts a session; the native client keeps its cookie
+    pub async fn log_in(&self, payload: &V1LoginPayload) -> Result<User> {
+        self.client.post("/auth/v1/log_in", payload).await
+    }
+
+    pub async fn log_out(&self) -> Result<Message> {
+        self.client.post_empty("/auth/v1/log_out").await
+    }
+
+    pub async fn twofa_setup(&self) -> Result<TwoFASetup> {
+        self.client.post_empty("/auth/v1/2fa/setup").await
+    }
+
+    pub async fn twofa_verify(&self, payload: &V1TwoFAVerifyPayload) -> Result<User> {
+        self.client.post("/auth/v1/2fa/verify", payload).await
+    }
+
+    pub async fn twofa_disable(&self, payload: &V1TwoFADisablePayload) -> Result<User> {
+        self.client.post("/auth/v1/2fa/disable", payload).await
+    }
+
+    pub async fn sessions(&self) -> Result<Page<UserSession>> {
+        self.client.post_empty("/auth/v1/sessions/list").await
+    }
+
+    pub async fn terminate_session(&self, session_id: i32) -> Result<Message> {
+        self.client
+            .post_empty(&format!("/auth/v1/sessions/terminate/{}", session_id))
+            .await
+    }
+}
+
+/// `/email_verification/v1`
+pub struct EmailVerification<'a> {
+    pub(crate) client: &'a Client,
+}
+
+impl EmailVerification<'_> {
+    pub async fn verify(&self, payload: &V1VerifyPayload) -> Result<Message> {
+        self.client
+            .post("/email_verification/v1/verify", payload)
+            .await
+    }
+
+    pub async fn resend(&self) -> Result<Message> {
+        self.client
+            .post_empty("/email_verification/v1/resend")
+            .await
+    }
+}
+
+/// `/forgot_password/v1`
+pub struct PasswordReset<'a> {
+    pub(crate) client: &'a Client,
+}
+
+impl PasswordReset<'_> {
+    pub async fn request(&self, payload: &V1GeneratePayload) -> Result<Message> {
+        self.client
+            .post("/forgot_password/v1/request", payload)
+            .await
+    }
+
+    pub async fn verify(&self, payload: &V1ForgotPasswordVerifyPayload) -> Result<Message> {
+        self.client
+            .post("/forgot_password/v1/verify", payload)
+            .await
+    }
+
+    pub async fn reset(&self, payload: &V1ResetPayload) -> Result<Message> {
+        self.client.post("/forgot_password/v1/reset", payload).await
+    }
+}
@@ -0,0 +1,58 @@
+use crate::dto::{
+    Category, CategoryNode, CategoryWithRelations, Message, Page, V1CategoryQueryParams,
+    V1CreateCategoryPayload, V1DeleteCategoryPayload, V1UpdateCategoryPayload,
+};
+use crate::{Client, Result};
+
+/// `/category/v1`
+pub struct Categories<'a> {
+    pub(crate) client: &'a Client,
+}
+
+impl Categories<'_> {
+    pub async fn create(&self, payload: &V1CreateCategoryPayload) -> Result<CategoryWithRelations> {
+        self.client.post("/category/v1/create", payload).await
+    }
+
+    pub async fn update(
+        &self,
+        category_id: i32,
+        payload: &V1UpdateCategoryPayload,
+    ) -> Result<CategoryWithRelations> {
+        self.client
+            .post(&format!("/category/v1/update/{}", category_id), payload)
+            .await
+    }
+
+    pub async fn delete(
+        &self,
+        category_id: i32,
+        payload: &V1DeleteCategoryPayload,
+    ) -> Result<Message> {
+        self.client
+            .post(&format!("/category/v1/delete/{}", category_id), payload)
+            .await
+    }
+
+    pub async fn view(&self, category_id: i32) -> Result<CategoryWithRelations> {
+        self.client
+            .get(&format!("/category/v1/view/{}", category_id))
+            .await
+    }
+
+    pub async fn list(&self) -> Result<Vec<Category>> {
+        self.client.get("/category/v1/list").await
+    }
+
+    pub async fn query(
+        &self,
+        query: &V1CategoryQueryParams,
+    ) -> Result<Page<CategoryWithRelations>> {
+        self.client.post("/category/v1/list/query", query).await
+    }
+
+    /// Root categories with their descendants
+    pub async fn tree(&self) -> Result<Vec<CategoryNode>> {
+        self.client.get("/category/v1/tree").await
+    }
+}
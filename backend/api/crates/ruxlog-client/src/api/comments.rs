@@ -0,0 +1,78 @@
+use crate::dto::{
+    CommentLikeResponse, CommentLikeStatus, CommentWithUser, Message, Page, PostComment,
+    V1AdminPostCommentListQuery, V1CommentLikeStatusPayload, V1CreatePostCommentPayload,
+    V1FlagCommentPayload, V1UpdatePostCommentPayload,
+};
+use crate::{Client, Result};
+
+/// `/post/comment/v1`
+pub struct Comments<'a> {
+    pub(crate) client: &'a Client,
+}
+
+impl Comments<'_> {
+    pub async fn create(&self, payload: &V1CreatePostCommentPayload) -> Result<PostComment> {
+        self.client.post("/post/comment/v1/create", payload).await
+    }
+
+    pub async fn update(
+        &self,
+        comment_id: i32,
+        payload: &V1UpdatePostCommentPayload,
+    ) -> Result<PostComment> {
+        self.client
+            .post(&format!("/post/comment/v1/update/{}", comment_id), payload)
+            .await
+    }
+
+    pub async fn delete(&self, comment_id: i32) -> Result<Message> {
+        self.client
+            .post_empty(&format!("/post/comment/v1/delete/{}", comment_id))
+            .await
+    }
+
+    pub async fn flag(
+        &self,
+        comment_id: i32,
+        payload: &V1FlagCommentPayload,
+    ) -> Result<serde_json::Value> {
+        self.client
+            .post(&format!("/post/comment/v1/flag/{}", comment_id), payload)
+            .await
+    }
+
+    /// The visible comments of a post
+    pub async fn for_post(&self, post_id: i32) -> Result<Vec<CommentWithUser>> {
+        self.client
+            .post_empty(&format!("/post/comment/v1/{}", post_id))
+            .await
+    }
+
+    pub async fn admin_list(
+        &self,
+        query: &V1AdminPostCommentListQuery,
+    ) -> Result<Page<CommentWithUser>> {
+        self.client.post("/post/comment/v1/admin/list", query).await
+    }
+
+    pub async fn like(&self, comment_id: i32) -> Result<CommentLikeResponse> {
+        self.client
+            .post_empty(&format!("/post/comment/v1/like/{}", comment_id))
+            .await
+    }
+
+    pub async fn unlike(&self, comment_id: i32) -> Result<CommentLikeResponse> {
+        self.client
+            .post_empty(&format!("/post/comment/v1/unlike/{}", comment_id))
+            .await
+    }
+
+    pub async fn like_status(
+        &self,
+        payload: &V1CommentLikeStatusPayload,
+    ) -> Result<CommentLikeStatus> {
+        self.client
+            .post("/post/comment/v1/like/status", payload)
+            .await
+    }
+}
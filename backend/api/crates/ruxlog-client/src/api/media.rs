@@ -0,0 +1,37 @@
+use crate::client::Upload;
+use crate::dto::{self, MediaUploadMetadata, MediaWithUsage, Message, Page, V1MediaListQuery};
+use crate::{Client, Result};
+
+/// `/media/v1`
+pub struct Media<'a> {
+    pub(crate) client: &'a Client,
+}
+
+impl Media<'_> {
+    /// Uploads one file as multipart form data
+    pub async fn upload(
+        &self,
+        upload: &Upload,
+        metadata: &MediaUploadMetadata,
+    ) -> Result<dto::Media> {
+        self.client
+            .post_multipart("/media/v1/create", upload, metadata.fields())
+            .await
+    }
+
+    pub async fn view(&self, media_id: i32) -> Result<MediaWithUsage> {
+        self.client
+            .post_empty(&format!("/media/v1/view/{}", media_id))
+            .await
+    }
+
+    pub async fn query(&self, query: &V1MediaListQuery) -> Result<Page<MediaWithUsage>> {
+        self.client.post("/media/v1/list/query", query).await
+    }
+
+    pub async fn delete(&self, media_id: i32) -> Result<Message> {
+        self.client
+            .post_empty(&format!("/media/v1/delete/{}", media_id))
+            .await
+    }
+}
@@ -0,0 +1,22 @@
+//! One handle per backend module, borrowed from a [`Client`](crate::Client)
+//! with `client.posts()`, `client.media()`, ...
+
+mod auth;
+mod categories;
+mod comments;
+mod media;
+mod newsletter;
+mod notifications;
+mod posts;
+mod tags;
+mod users;
+
+pub use auth::{Auth, EmailVerification, PasswordReset};
+pub use categories::Categories;
+pub use comments::Comments;
+pub use media::Media;
+pub use newsletter::Newsletter;
+pub use notifications::Notifications;
+pub use posts::Posts;
+pub use tags::Tags;
+pub use users::Users;
@@ -0,0 +1,24 @@
+use crate::dto::{Message, V1SubscribePayload, V1UnsubscribePayload};
+use crate::{Client, Result};
+
+/// `/newsletter/v1`
+pub struct Newsletter<'a> {
+    pub(crate) client: &'a Client,
+}
+
+impl Newsletter<'_> {
+    /// Sends a confirmation email; the address is subscribed once confirmed
+    pub async fn subscribe(&self, payload: &V1SubscribePayload) -> Result<Message> {
+        self.client.post("/newsletter/v1/subscribe", payload).await
+    }
+
+    pub async fn confirm(&self, payload: &V1UnsubscribePayload) -> Result<Message> {
+        self.client.post("/newsletter/v1/confirm", payload).await
+    }
+
+    pub async fn unsubscribe(&self, payload: &V1UnsubscribePayload) -> Result<Message> {
+        self.client
+            .post("/newsletter/v1/unsubscribe", payload)
+            .await
+    }
+}
@@ -0,0 +1,39 @@
+use crate::dto::{
+    MarkReadResponse, Notification, NotificationPreference, Page, UnreadCount, V1MarkReadPayload,
+    V1NotificationListQuery, V1UpdatePreferencesPayload,
+};
+use crate::{Client, Result};
+
+/// `/notification/v1`
+pub struct Notifications<'a> {
+    pub(crate) client: &'a Client,
+}
+
+impl Notifications<'_> {
+    pub async fn list(&self, query: &V1NotificationListQuery) -> Result<Page<Notification>> {
+        self.client.post("/notification/v1/list", query).await
+    }
+
+    pub async fn mark_read(&self, payload: &V1MarkReadPayload) -> Result<MarkReadResponse> {
+        self.client
+            .post("/notification/v1/mark_read", payload)
+            .await
+    }
+
+    pub async fn unread_count(&self) -> Result<UnreadCount> {
+        self.client.get("/notification/v1/unread_count").await
+    }
+
+    pub async fn preferences(&self) -> Result<NotificationPreference> {
+        self.client.get("/notification/v1/preferences").await
+    }
+
+    pub async fn update_preferences(
+        &self,
+        payload: &V1UpdatePreferencesPayload,
+    ) -> Result<NotificationPreference> {
+        self.client
+            .post("/notification/v1/preferences/update", payload)
+            .await
+    }
+}
@@ -0,0 +1,157 @@
+use crate::client::{encode, query_string};
+use crate::dto::{
+    LikeActionResponse, LikeStatus, LikeStatusBatchRequest, LikeStatusBatchResponse, Message, Page,
+    PostSeries, PostSeriesList, PostSitemap, PostWithRelations, RenderFormat, ScheduledPost,
+    V1AutosavePayload, V1CreatePostPayload, V1PostQueryParams, V1PostUnlockPayload,
+    V1SchedulePayload, V1SeriesCreatePayload, V1SeriesListQuery, V1SeriesUpdatePayload,
+    V1UpdatePostPayload,
+};
+use crate::{Client, Result};
+
+/// `/post/v1`
+pub struct Posts<'a> {
+    pub(crate) client: &'a Client,
+}
+
+impl Posts<'_> {
+    pub async fn create(&self, payload: &V1CreatePostPayload) -> Result<PostWithRelations> {
+        self.client.post("/post/v1/create", payload).await
+    }
+
+    pub async fn update(
+        &self,
+        post_id: i32,
+        payload: &V1UpdatePostPayload,
+    ) -> Result<PostWithRelations> {
+        self.client
+            .post(&format!("/post/v1/update/{}", post_id), payload)
+            .await
+    }
+
+    /// Saves the editor content and returns the revision it created
+    pub async fn autosave(&self, payload: &V1AutosavePayload) -> Result<serde_json::Value> {
+        self.client.post("/post/v1/autosave", payload).await
+    }
+
+    pub async fn delete(&self, post_id: i32) -> Result<Message> {
+        self.client
+            .post_empty(&format!("/post/v1/delete/{}", post_id))
+            .await
+    }
+
+    /// All posts the caller may see, drafts included
+    pub async fn query(&self, query: &V1PostQueryParams) -> Result<Page<PostWithRelations>> {
+        self.client.post("/post/v1/query", query).await
+    }
+
+    pub async fn published(&self, query: &V1PostQueryParams) -> Result<Page<PostWithRelations>> {
+        self.client.post("/post/v1/list/published", query).await
+    }
+
+    /// A post by id or slug, with no password
+    pub async fn view(&self, id_or_slug: &str) -> Result<PostWithRelations> {
+        self.view_with(id_or_slug, None, &V1PostUnlockPayload::default())
+            .await
+    }
+
+    /// A post by slug in `locale`, unlocked with `unlock.password`
+    pub async fn view_with(
+        &self,
+        id_or_slug: &str,
+        locale: Option<&str>,
+        unlock: &V1PostUnlockPayload,
+    ) -> Result<PostWithRelations> {
+        self.client
+            .post(&view_path(id_or_slug, None, locale), unlock)
+            .await
+    }
+
+    /// Only the content of a post, rendered as `format`
+    pub async fn render(
+        &self,
+        id_or_slug: &str,
+        format: RenderFormat,
+        locale: Option<&str>,
+        unlock: &V1PostUnlockPayload,
+    ) -> Result<String> {
+        self.client
+            .post_text(&view_path(id_or_slug, Some(format), locale), unlock)
+            .await
+    }
+
+    pub async fn track_view(&self, post_id: i32) -> Result<Message> {
+        self.client
+            .post_empty(&format!("/post/v1/track_view/{}", post_id))
+            .await
+    }
+
+    pub async fn sitemap(&self) -> Result<Vec<PostSitemap>> {
+        self.client.post_empty("/post/v1/sitemap").await
+    }
+
+    pub async fn schedule(&self, payload: &V1SchedulePayload) -> Result<ScheduledPost> {
+        self.client.post("/post/v1/schedule", payload).await
+    }
+
+    pub async fn like(&self, post_id: i32) -> Result<LikeActionResponse> {
+        self.client
+            .post_empty(&format!("/post/v1/like/{}", post_id))
+            .await
+    }
+
+    pub async fn unlike(&self, post_id: i32) -> Result<LikeActionResponse> {
+        self.client
+            .post_empty(&format!("/post/v1/unlike/{}", post_id))
+            .await
+    }
+
+    pub async fn like_status(&self, post_id: i32) -> Result<LikeStatus> {
+        self.client
+            .post_empty(&format!("/post/v1/like/status/{}", post_id))
+            .await
+    }
+
+    pub async fn like_status_batch(
+        &self,
+        request: &LikeStatusBatchRequest,
+    ) -> Result<LikeStatusBatchResponse> {
+        self.client
+            .post("/post/v1/like/status/batch", request)
+            .await
+    }
+
+    pub async fn series_create(&self, payload: &V1SeriesCreatePayload) -> Result<PostSeries> {
+        self.client.post("/post/v1/series/create", payload).await
+    }
+
+    pub async fn series_update(
+        &self,
+        series_id: i32,
+        payload: &V1SeriesUpdatePayload,
+    ) -> Result<PostSeries> {
+        self.client
+            .post(&format!("/post/v1/series/update/{}", series_id), payload)
+            .await
+    }
+
+    pub async fn series_delete(&self, series_id: i32) -> Result<Message> {
+        self.client
+            .post_empty(&format!("/post/v1/series/delete/{}", series_id))
+            .await
+    }
+
+    pub async fn series_list(&self, query: &V1SeriesListQuery) -> Result<PostSeriesList> {
+        self.client.post("/post/v1/series/list", query).await
+    }
+}
+
+fn view_path(id_or_slug: &str, format: Option<RenderFormat>, locale: Option<&str>) -> String {
+    format!(
+        "/post/v1/view/{}{}",
+        encode(id_or_slug),
+        query_string(&[
+            ("format", format.map(|format| format.as_str())),
+            ("locale", locale),
+        ])
+    )
+}
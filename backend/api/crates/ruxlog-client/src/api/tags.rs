@@ -0,0 +1,39 @@
+use crate::dto::{Message, Page, Tag, V1CreateTagPayload, V1TagQueryParams, V1UpdateTagPayload};
+use crate::{Client, Result};
+
+/// `/tag/v1`
+pub struct Tags<'a> {
+    pub(crate) client: &'a Client,
+}
+
+impl Tags<'_> {
+    pub async fn create(&self, payload: &V1CreateTagPayload) -> Result<Tag> {
+        self.client.post("/tag/v1/create", payload).await
+    }
+
+    pub async fn update(&self, tag_id: i32, payload: &V1UpdateTagPayload) -> Result<Tag> {
+        self.client
+            .post(&format!("/tag/v1/update/{}", tag_id), payload)
+            .await
+    }
+
+    pub async fn delete(&self, tag_id: i32) -> Result<Message> {
+        self.client
+            .post_empty(&format!("/tag/v1/delete/{}", tag_id))
+            .await
+    }
+
+    pub async fn view(&self, tag_id: i32) -> Result<Tag> {
+        self.client
+            .post_empty(&format!("/tag/v1/view/{}", tag_id))
+            .await
+    }
+
+    pub async fn list(&self) -> Result<Vec<Tag>> {
+        self.client.get("/tag/v1/list").await
+    }
+
+    pub async fn query(&self, query: &V1TagQueryParams) -> Result<Page<Tag>> {
+        self.client.post("/tag/v1/list/query", query).await
+    }
+}
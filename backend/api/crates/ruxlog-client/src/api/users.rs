@@ -0,0 +1,54 @@
+use crate::dto::{
+    Message, Page, User, UserWithRelations, V1AdminCreateUserPayload, V1AdminUpdateUserPayload,
+    V1AdminUserQueryParams, V1UpdateProfilePayload,
+};
+use crate::{Client, Result};
+
+/// `/user/v1`
+pub struct Users<'a> {
+    pub(crate) client: &'a Client,
+}
+
+impl Users<'_> {
+    /// The logged-in user
+    pub async fn get(&self) -> Result<User> {
+        self.client.get("/user/v1/get").await
+    }
+
+    pub async fn update(&self, payload: &V1UpdateProfilePayload) -> Result<User> {
+        self.client.post("/user/v1/update", payload).await
+    }
+
+    pub async fn admin_list(
+        &self,
+        query: &V1AdminUserQueryParams,
+    ) -> Result<Page<UserWithRelations>> {
+        self.client.post("/user/v1/admin/list", query).await
+    }
+
+    pub async fn admin_view(&self, user_id: i32) -> Result<UserWithRelations> {
+        self.client
+            .post_empty(&format!("/user/v1/admin/view/{}", user_id))
+            .await
+    }
+
+    pub async fn admin_create(&self, payload: &V1AdminCreateUserPayload) -> Result<User> {
+        self.client.post("/user/v1/admin/create", payload).await
+    }
+
+    pub async fn admin_update(
+        &self,
+        user_id: i32,
+        payload: &V1AdminUpdateUserPayload,
+    ) -> Result<User> {
+        self.client
+            .post(&format!("/user/v1/admin/update/{}", user_id), payload)
+            .await
+    }
+
+    pub async fn admin_delete(&self, user_id: i32) -> Result<Message> {
+        self.client
+            .post_empty(&format!("/user/v1/admin/delete/{}", user_id))
+            .await
+    }
+}
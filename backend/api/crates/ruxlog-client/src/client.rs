@@ -0,0 +1,211 @@
+use base64::prelude::*;
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::api::{
+    Auth, Categories, Comments, EmailVerification, Media, Newsletter, Notifications, PasswordReset,
+    Posts, Tags, Users,
+};
+use crate::error::{Error, Result};
+use crate::transport::Transport;
+
+#[derive(Clone, Copy)]
+pub(crate) enum Method {
+    Get,
+    Post,
+}
+
+pub(crate) enum Body<'a> {
+    /// Handlers that read no body still get `{}`, which also satisfies the
+    /// ones whose body is optional
+    Empty,
+    Json(serde_json::Value),
+    Multipart {
+        upload: &'a Upload,
+        fields: Vec<(&'static str, String)>,
+    },
+}
+
+/// A file to send as the `file` field of a multipart upload
+#[derive(Debug, Clone, PartialEq)]
+pub struct Upload {
+    pub file_name: String,
+    pub mime_type: String,
+    pub bytes: Vec<u8>,
+}
+
+impl Upload {
+    pub fn new(
+        file_name: impl Into<String>,
+        mime_type: impl Into<String>,
+        bytes: impl Into<Vec<u8>>,
+    ) -> Self {
+        Self {
+            file_name: file_name.into(),
+            mime_type: mime_type.into(),
+            bytes: bytes.into(),
+        }
+    }
+}
+
+/// Typed client for the Ruxlog API
+///
+/// On native targets it owns a `reqwest` client with a cookie store, so a
+/// `log_in` keeps the session for later calls. On wasm it sends through
+/// `gloo-net` with the browser's cookies.
+#[derive(Clone)]
+pub struct Client {
+    pub(crate) transport: Transport,
+}
+
+impl Client {
+    /// Send the server's `CSRF_KEY`, base64-encoded as the API expects
+    pub fn with_csrf_key(self, key: &str) -> Self {
+        self.with_csrf_token(BASE64_STANDARD.encode(key))
+    }
+
+    /// Send an already encoded `csrf-token` header value
+    pub fn with_csrf_token(mut self, token: impl Into<String>) -> Self {
+        self.transport.csrf_token = token.into();
+        self
+    }
+
+    pub fn auth(&self) -> Auth<'_> {
+        Auth { client: self }
+    }
+
+    pub fn email_verification(&self) -> EmailVerification<'_> {
+        EmailVerification { client: self }
+    }
+
+    pub fn password_reset(&self) -> PasswordReset<'_> {
+        PasswordReset { client: self }
+    }
+
+    pub fn users(&self) -> Users<'_> {
+        Users { client: self }
+    }
+
+    pub fn posts(&self) -> Posts<'_> {
+        Posts { client: self }
+    }
+
+    pub fn comments(&self) -> Comments<'_> {
+        Comments { client: self }
+    }
+
+    pub fn categories(&self) -> Categories<'_> {
+        Categories { client: self }
+    }
+
+    pub fn tags(&self) -> Tags<'_> {
+        Tags { client: self }
+    }
+
+    pub fn media(&self) -> Media<'_> {
+        Media { client: self }
+    }
+
+    pub fn newsletter(&self) -> Newsletter<'_> {
+        Newsletter { client: self }
+    }
+
+    pub fn notifications(&self) -> Notifications<'_> {
+        Notifications { client: self }
+    }
+
+    pub(crate) async fn get<T: DeserializeOwned>(&self, path: &str) -> Result<T> {
+        self.send(Method::Get, path, Body::Empty).await
+    }
+
+    pub(crate) async fn post<B, T>(&self, path: &str, body: &B) -> Result<T>
+    where
+        B: Serialize + ?Sized,
+        T: DeserializeOwned,
+    {
+        let body = serde_json::to_value(body).map_err(|err| Error::Decode(err.to_string()))?;
+        self.send(Method::Post, path, Body::Json(body)).await
+    }
+
+    /// A `POST` answered with a non-JSON body, returned as is
+    pub(crate) async fn post_text<B>(&self, path: &str, body: &B) -> Result<String>
+    where
+        B: Serialize + ?Sized,
+    {
+        let body = serde_json::to_value(body).map_err(|err| Error::Decode(err.to_string()))?;
+        self.send_raw(Method::Post, path, Body::Json(body)).await
+    }
+
+    pub(crate) async fn post_empty<T: DeserializeOwned>(&self, path: &str) -> Result<T> {
+        self.send(Method::Post, path, Body::Empty).await
+    }
+
+    pub(crate) async fn post_multipart<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        upload: &Upload,
+        fields: Vec<(&'static str, String)>,
+    ) -> Result<T> {
+        self.send(Method::Post, path, Body::Multipart { upload, fields })
+            .await
+    }
+
+    async fn send<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        path: &str,
+        body: Body<'_>,
+    ) -> Result<T> {
+        let text = self.send_raw(method, path, body).await?;
+        serde_json::from_str(&text).map_err(|err| Error::Decode(err.to_string()))
+    }
+
+    async fn send_raw(&self, method: Method, path: &str, body: Body<'_>) -> Result<String> {
+        let (status, text) = self.transport.send(method, path, body).await?;
+        if !(200..300).contains(&status) {
+            return Err(Error::from_response(status, text));
+        }
+        Ok(text)
+    }
+}
+
+/// `?a=1&b=2` from the pairs that are set, or `""` when none are
+pub(crate) fn query_string(pairs: &[(&str, Option<&str>)]) -> String {
+    let encoded: Vec<String> = pairs
+        .iter()
+        .filter_map(|(key, value)| value.map(|value| format!("{}={}", key, encode(value))))
+        .collect();
+    if encoded.is_empty() {
+        String::new()
+    } else {
+        format!("?{}", encoded.join("&"))
+    }
+}
+
+/// Percent-encode everything but RFC 3986 unreserved characters
+pub(crate) fn encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builds_query_strings() {
+        assert_eq!(query_string(&[("format", None)]), "");
+        assert_eq!(
+            query_string(&[("format", Some("html")), ("locale", Some("pt-BR"))]),
+            "?format=html&locale=pt-BR"
+        );
+        assert_eq!(encode("a b/ç"), "a%20b%2F%C3%A7");
+    }
+}
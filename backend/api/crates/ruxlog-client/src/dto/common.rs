@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+
+use super::{SortParam, SortParamOrder};
+
+impl SortParam {
+    pub fn asc(field: impl Into<String>) -> Self {
+        Self {
+            field: field.into(),
+            order: Some(SortParamOrder::Asc),
+        }
+    }
+
+    pub fn desc(field: impl Into<String>) -> Self {
+        Self {
+            field: field.into(),
+            order: Some(SortParamOrder::Desc),
+        }
+    }
+}
+
+/// A page of a paginated list; the backend's `{T}Page` schemas
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Page<T> {
+    pub data: Vec<T>,
+    pub total: u64,
+    pub per_page: u64,
+    pub page: u64,
+}
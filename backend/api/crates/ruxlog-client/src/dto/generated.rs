@@ -0,0 +1,1464 @@
+//! Generated from the backend's OpenAPI document by
+//! `cargo run --bin generate_client_dtos`; don't edit by hand.
+
+use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Serialize};
+
+use super::Page;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AuthorMedia {
+    pub file_url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub height: Option<i32>,
+    pub id: i32,
+    pub mime_type: String,
+    pub object_key: String,
+    pub size: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub width: Option<i32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Category {
+    pub color: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cover_id: Option<i32>,
+    pub created_at: DateTime<FixedOffset>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub id: i32,
+    pub is_active: bool,
+    /// `None` when shared by all locales.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub logo_id: Option<i32>,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<i32>,
+    pub slug: String,
+    pub text_color: String,
+    pub updated_at: DateTime<FixedOffset>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CategoryMedia {
+    pub file_url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub height: Option<i32>,
+    pub id: i32,
+    pub mime_type: String,
+    pub object_key: String,
+    pub size: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub width: Option<i32>,
+}
+
+/// A category with its children, for the tree endpoints.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CategoryNode {
+    pub children: Vec<CategoryNode>,
+    pub color: String,
+    pub depth: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub id: i32,
+    pub is_active: bool,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<i32>,
+    pub slug: String,
+    pub text_color: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CategoryWithRelations {
+    pub color: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cover: Option<CategoryMedia>,
+    pub created_at: DateTime<FixedOffset>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub id: i32,
+    pub is_active: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub logo: Option<CategoryMedia>,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<i32>,
+    pub slug: String,
+    pub text_color: String,
+    pub updated_at: DateTime<FixedOffset>,
+}
+
+/// `{data, total, per_page, page}` answered by the paginated list endpoints.
+pub type CategoryWithRelationsPage = Page<CategoryWithRelations>;
+
+/// What to do with the children of a deleted category.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChildPolicy {
+    /// Refuse to delete a category that has children.
+    Block,
+    /// Move the children up to the deleted category's parent.
+    Reparent,
+}
+
+impl ChildPolicy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChildPolicy::Block => "block",
+            ChildPolicy::Reparent => "reparent",
+        }
+    }
+}
+
+/// Response for comment like/unlike actions
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CommentLikeResponse {
+    pub comment_id: i32,
+    pub is_liked: bool,
+    pub likes_count: i32,
+    pub message: String,
+}
+
+/// Response of `/post/comment/v1/like/status`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CommentLikeStatus {
+    /// The subset of the requested ids the user has liked
+    pub liked: Vec<i32>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CommentUserMedia {
+    pub file_url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub height: Option<i32>,
+    pub id: i32,
+    pub mime_type: String,
+    pub object_key: String,
+    pub size: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub width: Option<i32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CommentWithUser {
+    pub content: String,
+    pub created_at: DateTime<FixedOffset>,
+    pub flags_count: i32,
+    pub hidden: bool,
+    pub id: i32,
+    pub likes_count: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<i32>,
+    pub post_id: i32,
+    pub updated_at: DateTime<FixedOffset>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_avatar: Option<CommentUserMedia>,
+    pub user_id: i32,
+    pub user_name: String,
+}
+
+/// `{data, total, per_page, page}` answered by the paginated list endpoints.
+pub type CommentWithUserPage = Page<CommentWithUser>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DigestFrequency {
+    Off,
+    Daily,
+    Weekly,
+}
+
+impl DigestFrequency {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DigestFrequency::Off => "off",
+            DigestFrequency::Daily => "daily",
+            DigestFrequency::Weekly => "weekly",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EditorJsBlock {
+    pub data: serde_json::Value,
+    pub r#type: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EditorJsDocument {
+    pub blocks: Vec<EditorJsBlock>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FlagFilter {
+    All,
+    Flagged,
+    NotFlagged,
+}
+
+impl FlagFilter {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FlagFilter::All => "all",
+            FlagFilter::Flagged => "flagged",
+            FlagFilter::NotFlagged => "not_flagged",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HiddenFilter {
+    All,
+    Hidden,
+    Visible,
+}
+
+impl HiddenFilter {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HiddenFilter::All => "all",
+            HiddenFilter::Hidden => "hidden",
+            HiddenFilter::Visible => "visible",
+        }
+    }
+}
+
+/// Response for like/unlike action
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LikeActionResponse {
+    pub is_liked: bool,
+    pub likes_count: i32,
+    pub message: String,
+    pub post_id: i32,
+}
+
+/// Response for like status check
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LikeStatus {
+    pub is_liked: bool,
+    pub likes_count: i32,
+    pub post_id: i32,
+}
+
+/// Request to check like status for multiple posts
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LikeStatusBatchRequest {
+    pub post_ids: Vec<i32>,
+}
+
+/// Response with like status for multiple posts
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LikeStatusBatchResponse {
+    pub statuses: Vec<LikeStatus>,
+}
+
+/// Why a viewer gets the teaser instead of the full post.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LockReason {
+    LoginRequired,
+    RoleRequired,
+    PasswordRequired,
+}
+
+impl LockReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LockReason::LoginRequired => "login_required",
+            LockReason::RoleRequired => "role_required",
+            LockReason::PasswordRequired => "password_required",
+        }
+    }
+}
+
+/// Response of `/notification/v1/mark_read`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MarkReadResponse {
+    pub updated: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Media {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_hash: Option<String>,
+    pub created_at: DateTime<FixedOffset>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extension: Option<String>,
+    pub file_url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub height: Option<i32>,
+    pub id: i32,
+    pub is_optimized: bool,
+    pub mime_type: String,
+    pub object_key: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub optimized_at: Option<DateTime<FixedOffset>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reference_type: Option<MediaReference>,
+    pub size: i64,
+    pub updated_at: DateTime<FixedOffset>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uploader_id: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub width: Option<i32>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MediaReference {
+    Category,
+    User,
+    Post,
+}
+
+impl MediaReference {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MediaReference::Category => "category",
+            MediaReference::User => "user",
+            MediaReference::Post => "post",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MediaUploadMetadata {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub height: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reference_type: Option<MediaReference>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub width: Option<i32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MediaWithUsage {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_hash: Option<String>,
+    pub created_at: DateTime<FixedOffset>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extension: Option<String>,
+    pub file_url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub height: Option<i32>,
+    pub id: i32,
+    pub is_optimized: bool,
+    pub mime_type: String,
+    pub object_key: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub optimized_at: Option<DateTime<FixedOffset>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reference_type: Option<MediaReference>,
+    pub size: i64,
+    pub updated_at: DateTime<FixedOffset>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uploader_id: Option<i32>,
+    pub usage_count: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub width: Option<i32>,
+}
+
+/// `{data, total, per_page, page}` answered by the paginated list endpoints.
+pub type MediaWithUsagePage = Page<MediaWithUsage>;
+
+/// `{message}` acknowledging an action.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Message {
+    pub message: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Notification {
+    /// Who caused the latest notification in the group.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub actor_id: Option<i32>,
+    /// How many notifications the group stands for.
+    pub count: i32,
+    pub created_at: DateTime<FixedOffset>,
+    pub id: i64,
+    pub payload: serde_json::Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub read_at: Option<DateTime<FixedOffset>>,
+    pub r#type: NotificationType,
+    pub updated_at: DateTime<FixedOffset>,
+    pub user_id: i32,
+}
+
+/// `{data, total, per_page, page}` answered by the paginated list endpoints.
+pub type NotificationPage = Page<Notification>;
+
+/// A user's notification channels. Users without a row get
+/// [`Model::defaults`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NotificationPreference {
+    /// Email unread notifications at this interval.
+    pub email_digest: DigestFrequency,
+    /// Show notifications in the notification center.
+    pub in_app: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_digest_at: Option<DateTime<FixedOffset>>,
+    /// Notification types the user doesn't want at all.
+    pub muted_types: Vec<String>,
+    pub updated_at: DateTime<FixedOffset>,
+    pub user_id: i32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationType {
+    /// Someone replied to the user's comment.
+    CommentReply,
+    /// Someone liked the user's comment.
+    CommentLike,
+    /// An author the user follows published a post.
+    AuthorPublished,
+}
+
+impl NotificationType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NotificationType::CommentReply => "comment_reply",
+            NotificationType::CommentLike => "comment_like",
+            NotificationType::AuthorPublished => "author_published",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostAlternate {
+    pub hreflang: String,
+    pub slug: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostAuthor {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub avatar: Option<AuthorMedia>,
+    pub email: String,
+    pub id: i32,
+    pub name: String,
+}
+
+/// Part a contributor played on a post.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PostAuthorRole {
+    /// Credited as an author; co-owns the post.
+    Author,
+    /// May edit the post but not delete it or change its credits.
+    Editor,
+    /// Read-only access to the draft, its revisions and lock state.
+    Reviewer,
+}
+
+impl PostAuthorRole {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PostAuthorRole::Author => "author",
+            PostAuthorRole::Editor => "editor",
+            PostAuthorRole::Reviewer => "reviewer",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostCategory {
+    pub color: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cover: Option<PostCategoryMedia>,
+    pub id: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub logo: Option<PostCategoryMedia>,
+    pub name: String,
+    pub slug: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostCategoryMedia {
+    pub file_url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub height: Option<i32>,
+    pub id: i32,
+    pub mime_type: String,
+    pub object_key: String,
+    pub size: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub width: Option<i32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PostComment {
+    pub content: String,
+    pub created_at: DateTime<FixedOffset>,
+    pub flags_count: i32,
+    pub hidden: bool,
+    pub id: i32,
+    pub likes_count: i32,
+    /// The comment this one replies to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<i32>,
+    pub post_id: i32,
+    pub updated_at: DateTime<FixedOffset>,
+    pub user_id: i32,
+}
+
+/// A credited user as shown alongside a post.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PostContributor {
+    pub name: String,
+    pub role: PostAuthorRole,
+    pub sort_order: i32,
+    pub user_id: i32,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostFeaturedImage {
+    pub file_url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub height: Option<i32>,
+    pub id: i32,
+    pub mime_type: String,
+    pub object_key: String,
+    pub size: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub width: Option<i32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PostSeries {
+    pub created_at: DateTime<FixedOffset>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub id: i32,
+    pub name: String,
+    pub slug: String,
+    pub updated_at: DateTime<FixedOffset>,
+}
+
+/// Response of `/post/v1/series/list`, which has no `per_page`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostSeriesList {
+    pub data: Vec<PostSeriesWithCount>,
+    pub page: u64,
+    pub total: u64,
+}
+
+/// Projection for listing series with post counts
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PostSeriesWithCount {
+    pub created_at: DateTime<FixedOffset>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub id: i32,
+    pub name: String,
+    pub posts_count: i64,
+    pub slug: String,
+    pub updated_at: DateTime<FixedOffset>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PostSitemap {
+    /// `hreflang` alternates: every published translation including this
+    /// post, plus `x-default` for the default-locale version.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alternates: Option<Vec<PostAlternate>>,
+    /// Names of the credited authors, in order.
+    #[serde(default)]
+    pub authors: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub canonical_url: Option<String>,
+    #[serde(default)]
+    pub locale: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub published_at: Option<DateTime<FixedOffset>>,
+    pub slug: String,
+    pub updated_at: DateTime<FixedOffset>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PostStatus {
+    Draft,
+    Published,
+    Archived,
+    /// Submitted for editorial review.
+    InReview,
+    /// A reviewer sent it back to its authors.
+    ChangesRequested,
+    /// Cleared for publishing.
+    Approved,
+}
+
+impl PostStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PostStatus::Draft => "Draft",
+            PostStatus::Published => "Published",
+            PostStatus::Archived => "Archived",
+            PostStatus::InReview => "InReview",
+            PostStatus::ChangesRequested => "ChangesRequested",
+            PostStatus::Approved => "Approved",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostTag {
+    pub color: String,
+    pub id: i32,
+    pub name: String,
+    pub slug: String,
+}
+
+/// Who may read a published post in full. Everyone else gets a teaser.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PostVisibility {
+    Public,
+    /// Any logged-in user.
+    Members,
+    /// Users with at least the post's `min_role`.
+    Role,
+    /// Anyone with the post's password.
+    Password,
+}
+
+impl PostVisibility {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PostVisibility::Public => "public",
+            PostVisibility::Members => "members",
+            PostVisibility::Role => "role",
+            PostVisibility::Password => "password",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PostWithRelations {
+    pub author: PostAuthor,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub canonical_url: Option<String>,
+    pub category: PostCategory,
+    pub comment_count: i64,
+    pub content: serde_json::Value,
+    /// Everyone credited on the post, in display order. `author` is the
+    /// first of them with the `author` role.
+    #[serde(default)]
+    pub contributors: Vec<PostContributor>,
+    pub created_at: DateTime<FixedOffset>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub excerpt: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub featured_image: Option<PostFeaturedImage>,
+    pub id: i32,
+    pub likes_count: i32,
+    #[serde(default)]
+    pub locale: String,
+    /// Set when the viewer only gets the teaser.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locked: Option<LockReason>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub meta_description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub meta_title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_role: Option<UserRole>,
+    #[serde(default)]
+    pub noindex: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub og_image_id: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub og_image_variant: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub published_at: Option<DateTime<FixedOffset>>,
+    pub slug: String,
+    pub status: PostStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<PostTag>>,
+    pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub translation_group_id: Option<i32>,
+    pub updated_at: DateTime<FixedOffset>,
+    pub view_count: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub visibility: Option<PostVisibility>,
+}
+
+/// `{data, total, per_page, page}` answered by the paginated list endpoints.
+pub type PostWithRelationsPage = Page<PostWithRelations>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RenderFormat {
+    Html,
+    Md,
+    Text,
+}
+
+impl RenderFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RenderFormat::Html => "html",
+            RenderFormat::Md => "md",
+            RenderFormat::Text => "text",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScheduledPost {
+    pub created_at: DateTime<FixedOffset>,
+    pub id: i32,
+    pub post_id: i32,
+    pub publish_at: DateTime<FixedOffset>,
+    pub status: ScheduledPostStatus,
+    pub updated_at: DateTime<FixedOffset>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ScheduledPostStatus {
+    Pending,
+    Published,
+    Canceled,
+    Failed,
+}
+
+impl ScheduledPostStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ScheduledPostStatus::Pending => "Pending",
+            ScheduledPostStatus::Published => "Published",
+            ScheduledPostStatus::Canceled => "Canceled",
+            ScheduledPostStatus::Failed => "Failed",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SortParam {
+    pub field: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub order: Option<SortParamOrder>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortParamOrder {
+    Asc,
+    Desc,
+}
+
+impl SortParamOrder {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SortParamOrder::Asc => "asc",
+            SortParamOrder::Desc => "desc",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Tag {
+    pub color: String,
+    pub created_at: DateTime<FixedOffset>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub id: i32,
+    pub is_active: bool,
+    /// `None` when shared by all locales.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+    pub name: String,
+    pub slug: String,
+    pub text_color: String,
+    pub updated_at: DateTime<FixedOffset>,
+}
+
+/// `{data, total, per_page, page}` answered by the paginated list endpoints.
+pub type TagPage = Page<Tag>;
+
+/// Response of `/auth/v1/2fa/setup`; the backup codes are only shown here
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TwoFASetup {
+    pub backup_codes: Vec<String>,
+    pub otpauth_url: String,
+    pub secret: String,
+}
+
+/// Response of `/notification/v1/unread_count`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct UnreadCount {
+    pub unread: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct User {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub avatar_id: Option<i32>,
+    pub created_at: DateTime<FixedOffset>,
+    pub email: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub google_id: Option<String>,
+    pub id: i32,
+    pub is_verified: bool,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub oauth_provider: Option<String>,
+    pub role: UserRole,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub two_fa_backup_codes: Option<serde_json::Value>,
+    pub two_fa_enabled: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub two_fa_secret: Option<String>,
+    pub updated_at: DateTime<FixedOffset>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct UserMedia {
+    pub file_url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub height: Option<i32>,
+    pub id: i32,
+    pub mime_type: String,
+    pub object_key: String,
+    pub size: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub width: Option<i32>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum UserRole {
+    SuperAdmin,
+    Admin,
+    Moderator,
+    Author,
+    User,
+}
+
+impl UserRole {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UserRole::SuperAdmin => "super-admin",
+            UserRole::Admin => "admin",
+            UserRole::Moderator => "moderator",
+            UserRole::Author => "author",
+            UserRole::User => "user",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UserSession {
+    /// Optional device info (e.g., "MacOS · Chrome 126")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub device: Option<String>,
+    pub id: i32,
+    /// Optional IPv4/IPv6 address string
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ip_address: Option<String>,
+    /// Last time this session was seen/used
+    pub last_seen: DateTime<FixedOffset>,
+    /// When the session was revoked (null if active)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revoked_at: Option<DateTime<FixedOffset>>,
+    pub user_id: i32,
+}
+
+/// `{data, total, per_page, page}` answered by the paginated list endpoints.
+pub type UserSessionPage = Page<UserSession>;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UserWithRelations {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub avatar: Option<UserMedia>,
+    pub created_at: DateTime<FixedOffset>,
+    pub email: String,
+    pub id: i32,
+    pub is_verified: bool,
+    pub name: String,
+    pub role: UserRole,
+    pub two_fa_enabled: bool,
+    pub updated_at: DateTime<FixedOffset>,
+}
+
+/// `{data, total, per_page, page}` answered by the paginated list endpoints.
+pub type UserWithRelationsPage = Page<UserWithRelations>;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct V1AdminCreateUserPayload {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub avatar_id: Option<i32>,
+    pub email: String,
+    #[serde(default)]
+    pub is_verified: bool,
+    pub name: String,
+    pub password: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub role: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct V1AdminPostCommentListQuery {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at_gt: Option<DateTime<FixedOffset>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at_lt: Option<DateTime<FixedOffset>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub flag_filter: Option<FlagFilter>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hidden_filter: Option<HiddenFilter>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_flags: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub page: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub post_id: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub search: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sorts: Option<Vec<SortParam>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at_gt: Option<DateTime<FixedOffset>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at_lt: Option<DateTime<FixedOffset>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<i32>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct V1AdminUpdateUserPayload {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub avatar_id: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub is_verified: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub role: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct V1AdminUserQueryParams {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at_gt: Option<DateTime<FixedOffset>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at_lt: Option<DateTime<FixedOffset>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub page: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub role: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sorts: Option<Vec<SortParam>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at_gt: Option<DateTime<FixedOffset>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at_lt: Option<DateTime<FixedOffset>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct V1AutosavePayload {
+    pub content: EditorJsDocument,
+    pub post_id: i32,
+    /// The post's `updated_at` as last seen by the editor (the previous
+    /// autosave's `post_updated_at`); a mismatch is a conflict.
+    pub updated_at: DateTime<FixedOffset>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct V1CategoryQueryParams {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at_gt: Option<DateTime<FixedOffset>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at_lt: Option<DateTime<FixedOffset>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub is_active: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub page: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub search: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sorts: Option<Vec<SortParam>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at_gt: Option<DateTime<FixedOffset>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at_lt: Option<DateTime<FixedOffset>>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct V1CommentLikeStatusPayload {
+    pub comment_ids: Vec<i32>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct V1CreateCategoryPayload {
+    pub color: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cover_id: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub is_active: Option<bool>,
+    /// Leave out to share the category across every locale.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub logo_id: Option<i32>,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<i32>,
+    pub slug: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text_color: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct V1CreatePostCommentPayload {
+    pub content: String,
+    /// Comment being replied to; must be on the same post.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<i32>,
+    pub post_id: i32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct V1CreatePostPayload {
+    /// Canonical URL when the post was first published elsewhere.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub canonical_url: Option<String>,
+    pub category_id: i32,
+    pub content: EditorJsDocument,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub excerpt: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub featured_image_id: Option<i32>,
+    #[serde(default)]
+    pub is_published: bool,
+    /// BCP 47 tag such as `en` or `pt-BR`; the default locale when omitted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+    /// Description for search results and link previews; the excerpt when
+    /// omitted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub meta_description: Option<String>,
+    /// Title for search results and link previews; `title` when omitted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub meta_title: Option<String>,
+    /// Required with `visibility: role`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_role: Option<UserRole>,
+    #[serde(default)]
+    pub noindex: bool,
+    /// Image for Open Graph/Twitter cards; the featured image when omitted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub og_image_id: Option<i32>,
+    /// Variant of the image to share (`1200w`, ...); picked by size when
+    /// omitted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub og_image_variant: Option<String>,
+    /// Required with `visibility: password`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub published_at: Option<DateTime<FixedOffset>>,
+    pub slug: String,
+    #[serde(default)]
+    pub tag_ids: Vec<i32>,
+    pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub visibility: Option<PostVisibility>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct V1CreateTagPayload {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub is_active: Option<bool>,
+    /// Leave out to share the tag across every locale.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+    pub name: String,
+    pub slug: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text_color: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct V1DeleteCategoryPayload {
+    /// `block` (default) refuses to delete a category with children;
+    /// `reparent` moves them up to its parent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub children: Option<ChildPolicy>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct V1FlagCommentPayload {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct V1ForgotPasswordVerifyPayload {
+    pub code: String,
+    pub email: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct V1GeneratePayload {
+    pub email: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct V1LoginPayload {
+    pub email: String,
+    pub password: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct V1MarkReadPayload {
+    /// Notifications to mark read; missing means all of them
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ids: Option<Vec<i64>>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct V1MediaListQuery {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at_gt: Option<DateTime<FixedOffset>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at_lt: Option<DateTime<FixedOffset>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extension: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub page: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reference_type: Option<MediaReference>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub search: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sorts: Option<Vec<SortParam>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at_gt: Option<DateTime<FixedOffset>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at_lt: Option<DateTime<FixedOffset>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uploader_id: Option<i32>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct V1NotificationListQuery {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub page: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub r#type: Option<NotificationType>,
+    #[serde(default)]
+    pub unread_only: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct V1PostQueryParams {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author_id: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category_id: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at_gt: Option<DateTime<FixedOffset>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at_lt: Option<DateTime<FixedOffset>>,
+    /// With `category_id`, also match posts in its subcategories.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub include_subcategories: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+    /// With `locale`, fill in default-locale posts that have no translation
+    /// in it (default true).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locale_fallback: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub page: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub published_at_gt: Option<DateTime<FixedOffset>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub published_at_lt: Option<DateTime<FixedOffset>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub search: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sorts: Option<Vec<SortParam>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<PostStatus>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tag_ids: Option<Vec<i32>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at_gt: Option<DateTime<FixedOffset>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at_lt: Option<DateTime<FixedOffset>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub visibility: Option<PostVisibility>,
+}
+
+/// Optional body of the post view: the password of a protected post.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct V1PostUnlockPayload {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct V1PostViewQuery {
+    /// Answer with the content rendered as `html`, `md` or `text`, served
+    /// with that format's `Content-Type`, instead of the JSON post.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<RenderFormat>,
+    /// Pick the post with this slug in the given locale.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct V1RegisterPayload {
+    pub email: String,
+    pub name: String,
+    pub password: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct V1ResetPayload {
+    pub code: String,
+    pub confirm_password: String,
+    pub email: String,
+    pub password: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct V1SchedulePayload {
+    pub post_id: i32,
+    pub publish_at: DateTime<FixedOffset>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct V1SeriesCreatePayload {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub name: String,
+    pub slug: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct V1SeriesListQuery {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub page: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub search: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct V1SeriesUpdatePayload {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub slug: Option<String>,
+}
+
+/// Subscribe to newsletter (double opt-in)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct V1SubscribePayload {
+    pub email: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct V1TagQueryParams {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at_gt: Option<DateTime<FixedOffset>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at_lt: Option<DateTime<FixedOffset>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub is_active: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub page: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub search: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sorts: Option<Vec<SortParam>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at_gt: Option<DateTime<FixedOffset>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at_lt: Option<DateTime<FixedOffset>>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct V1TwoFADisablePayload {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct V1TwoFAVerifyPayload {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub backup_code: Option<String>,
+    pub code: String,
+}
+
+/// Unsubscribe from newsletter
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct V1UnsubscribePayload {
+    pub email: String,
+    pub token: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct V1UpdateCategoryPayload {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cover_id: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub is_active: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub logo_id: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub slug: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text_color: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct V1UpdatePostCommentPayload {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct V1UpdatePostPayload {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub canonical_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category_id: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content: Option<EditorJsDocument>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub excerpt: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub featured_image_id: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub meta_description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub meta_title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_role: Option<UserRole>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub noindex: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub og_image_id: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub og_image_variant: Option<String>,
+    /// Sets a new password for `visibility: password`; required when the
+    /// post becomes password-protected. Any other visibility drops it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub published_at: Option<DateTime<FixedOffset>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub slug: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<PostStatus>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tag_ids: Option<Vec<i32>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// The post's `updated_at` as loaded by the editor; the update is
+    /// rejected with a conflict if the post has changed since.
+    pub updated_at: DateTime<FixedOffset>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub visibility: Option<PostVisibility>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct V1UpdatePreferencesPayload {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email_digest: Option<DigestFrequency>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub in_app: Option<bool>,
+    /// Notification types to stop receiving, e.g. `comment_like`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub muted_types: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct V1UpdateProfilePayload {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct V1UpdateTagPayload {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub is_active: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub slug: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text_color: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct V1VerifyPayload {
+    pub code: String,
+}
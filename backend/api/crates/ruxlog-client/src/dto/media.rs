@@ -0,0 +1,18 @@
+use super::MediaUploadMetadata;
+
+impl MediaUploadMetadata {
+    /// The metadata as multipart text fields, skipping unset ones
+    pub(crate) fn fields(&self) -> Vec<(&'static str, String)> {
+        let mut fields = Vec::new();
+        if let Some(reference_type) = self.reference_type {
+            fields.push(("reference_type", reference_type.as_str().to_string()));
+        }
+        if let Some(width) = self.width {
+            fields.push(("width", width.to_string()));
+        }
+        if let Some(height) = self.height {
+            fields.push(("height", height.to_string()));
+        }
+        fields
+    }
+}
@@ -0,0 +1,16 @@
+//! Request and response bodies
+//!
+//! `generated.rs` is generated from the backend's OpenAPI document (see
+//! `src/modules/docs_v1/client_codegen.rs` in the backend), so names follow
+//! the schema names (`V1CreatePostPayload`, `PostWithRelations`, ...) and can
+//! be looked up in `/docs`. The other modules only add helpers to those
+//! types, plus the generic [`Page`] the `{T}Page` schemas alias.
+
+mod common;
+#[rustfmt::skip]
+mod generated;
+mod media;
+mod post;
+
+pub use common::*;
+pub use generated::*;
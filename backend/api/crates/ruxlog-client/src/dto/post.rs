@@ -0,0 +1,31 @@
+use chrono::{DateTime, FixedOffset};
+
+use super::V1UpdatePostPayload;
+
+impl V1UpdatePostPayload {
+    /// An update that changes nothing yet, guarded by the post's `updated_at`
+    pub fn new(updated_at: DateTime<FixedOffset>) -> Self {
+        Self {
+            canonical_url: None,
+            category_id: None,
+            content: None,
+            excerpt: None,
+            featured_image_id: None,
+            locale: None,
+            meta_description: None,
+            meta_title: None,
+            min_role: None,
+            noindex: None,
+            og_image_id: None,
+            og_image_variant: None,
+            password: None,
+            published_at: None,
+            slug: None,
+            status: None,
+            tag_ids: None,
+            title: None,
+            updated_at,
+            visibility: None,
+        }
+    }
+}
@@ -0,0 +1,263 @@
+//! API errors as the client sees them
+//!
+//! The backend answers every failure with an `ErrorResponse` body whose
+//! `type` is a stable code such as `"AUTH_001"`. Those bodies decode into
+//! [`Error::Api`]; anything else (transport failures, non-JSON error pages,
+//! bodies that don't match the expected type) gets its own variant.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+
+macro_rules! error_codes {
+    ($($(#[$meta:meta])* $variant:ident = $code:literal,)*) => {
+        /// Error codes sent in the `type` field of an [`ErrorResponse`]
+        ///
+        /// Mirrors the backend's `ErrorCode`. Codes this client doesn't know
+        /// yet decode as [`ErrorCode::Unknown`] instead of failing.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum ErrorCode {
+            $($(#[$meta])* $variant,)*
+            /// A code added to the backend after this client was built
+            Unknown,
+        }
+
+        impl ErrorCode {
+            /// Every known code, in declaration order
+            pub const ALL: &'static [ErrorCode] = &[$(ErrorCode::$variant,)*];
+
+            /// The wire form, e.g. `"AUTH_001"`
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    $(ErrorCode::$variant => $code,)*
+                    ErrorCode::Unknown => "UNKNOWN",
+                }
+            }
+
+            /// Parse a wire code; unrecognised codes map to `Unknown`
+            pub fn from_code(code: &str) -> Self {
+                match code {
+                    $($code => ErrorCode::$variant,)*
+                    _ => ErrorCode::Unknown,
+                }
+            }
+        }
+    };
+}
+
+error_codes! {
+    // Authentication errors
+    InvalidCredentials = "AUTH_001",
+    UserNotFound = "AUTH_002",
+    SessionExpired = "AUTH_003",
+    Unauthorized = "AUTH_004",
+    PasswordResetRequired = "AUTH_005",
+    AccountLocked = "AUTH_006",
+    TooManyAttempts = "AUTH_007",
+    EmailVerificationRequired = "AUTH_008",
+    InvalidToken = "AUTH_009",
+
+    // Validation errors
+    InvalidInput = "VAL_001",
+    MissingRequiredField = "VAL_002",
+    InvalidFormat = "VAL_003",
+    InvalidLength = "VAL_004",
+    InvalidValue = "VAL_005",
+    ValidationError = "VAL_006",
+
+    // Database errors
+    DatabaseConnectionError = "DB_001",
+    RecordNotFound = "DB_002",
+    DuplicateEntry = "DB_003",
+    QueryError = "DB_004",
+    TransactionError = "DB_005",
+    RelationshipError = "DB_006",
+    IntegrityError = "DB_007",
+
+    // Server errors
+    InternalServerError = "SRV_001",
+    ServiceUnavailable = "SRV_002",
+    Timeout = "SRV_003",
+    RateLimited = "SRV_004",
+    ConfigurationError = "SRV_005",
+
+    // Business logic errors
+    OperationNotAllowed = "BIZ_001",
+    ResourceConflict = "BIZ_002",
+    BusinessRuleViolation = "BIZ_003",
+    DependencyExists = "BIZ_004",
+
+    // External service errors
+    ExternalServiceError = "EXT_001",
+    ExternalServiceTimeout = "EXT_002",
+    ExternalServiceUnavailable = "EXT_003",
+
+    // Asset/file errors
+    FileUploadError = "AST_001",
+    FileNotFound = "AST_002",
+    FileTooLarge = "AST_003",
+    InvalidFileType = "AST_004",
+    StorageError = "AST_005",
+    FileDeletionError = "AST_006",
+    AssetMetadataError = "AST_007",
+
+    // Email errors
+    EmailSendingError = "EML_001",
+    InvalidEmailFormat = "EML_002",
+    EmailDeliveryError = "EML_003",
+
+    // Post errors
+    PostNotFound = "PST_001",
+    InvalidPostStatus = "PST_002",
+    PostAlreadyPublished = "PST_003",
+    SlugAlreadyExists = "PST_004",
+
+    // Category errors
+    CategoryNotFound = "CAT_001",
+    CategoryInUse = "CAT_002",
+    InvalidCategoryParent = "CAT_003",
+
+    // Tag errors
+    TagNotFound = "TAG_001",
+    TagAlreadyExists = "TAG_002",
+
+    // Newsletter errors
+    SubscriberNotFound = "NWS_001",
+}
+
+impl fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Serialize for ErrorCode {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for ErrorCode {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let code = String::deserialize(deserializer)?;
+        Ok(ErrorCode::from_code(&code))
+    }
+}
+
+/// The backend's standard error body
+///
+/// `message` and `details` are only sent by debug builds of the API, so
+/// both are optional here.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ErrorResponse {
+    #[serde(rename = "type")]
+    pub code: ErrorCode,
+    #[serde(default)]
+    pub message: Option<String>,
+    pub status: u16,
+    #[serde(default)]
+    pub details: Option<String>,
+    #[serde(default)]
+    pub context: Option<serde_json::Value>,
+    #[serde(default)]
+    pub retry_after: Option<u64>,
+    #[serde(default)]
+    pub request_id: Option<String>,
+}
+
+impl fmt::Display for ErrorResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.message {
+            Some(message) => write!(f, "{} ({}): {}", self.code, self.status, message),
+            None => write!(f, "{} ({})", self.code, self.status),
+        }
+    }
+}
+
+/// Everything a client call can fail with
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum Error {
+    /// The API rejected the request with a standard error body
+    #[error("{0}")]
+    Api(ErrorResponse),
+    /// A non-2xx response whose body isn't an `ErrorResponse`, e.g. a proxy
+    /// error page
+    #[error("unexpected {status} response: {body}")]
+    Status { status: u16, body: String },
+    /// The request never got a response
+    #[error("request failed: {0}")]
+    Transport(String),
+    /// A 2xx response whose body doesn't match the expected type
+    #[error("could not decode response: {0}")]
+    Decode(String),
+}
+
+impl Error {
+    /// The API error code, if the server sent one
+    pub fn code(&self) -> Option<ErrorCode> {
+        match self {
+            Error::Api(response) => Some(response.code),
+            _ => None,
+        }
+    }
+
+    /// The HTTP status, if a response was received
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            Error::Api(response) => Some(response.status),
+            Error::Status { status, .. } => Some(*status),
+            Error::Transport(_) | Error::Decode(_) => None,
+        }
+    }
+
+    /// Turn a non-2xx response into an error, decoding the standard body
+    /// when there is one
+    pub(crate) fn from_response(status: u16, body: String) -> Self {
+        match serde_json::from_str::<ErrorResponse>(&body) {
+            Ok(response) => Error::Api(response),
+            Err(_) => Error::Status { status, body },
+        }
+    }
+}
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_production_error_body() {
+        let body = r#"{"type":"AUTH_004","status":401,"requestId":"abc"}"#;
+        let error = Error::from_response(401, body.to_string());
+        assert_eq!(error.code(), Some(ErrorCode::Unauthorized));
+        assert_eq!(error.status(), Some(401));
+        match error {
+            Error::Api(response) => {
+                assert_eq!(response.message, None);
+                assert_eq!(response.request_id.as_deref(), Some("abc"));
+            }
+            other => panic!("expected Api error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn unknown_codes_and_foreign_bodies() {
+        let body = r#"{"type":"NEW_999","status":418}"#;
+        assert_eq!(
+            Error::from_response(418, body.to_string()).code(),
+            Some(ErrorCode::Unknown)
+        );
+
+        let error = Error::from_response(502, "<html>Bad Gateway</html>".to_string());
+        assert!(matches!(error, Error::Status { status: 502, .. }));
+        assert_eq!(error.code(), None);
+    }
+
+    #[test]
+    fn codes_round_trip() {
+        for code in ErrorCode::ALL {
+            assert_eq!(ErrorCode::from_code(code.as_str()), *code);
+        }
+    }
+}
@@ -0,0 +1,43 @@
+//! ruxlog-client: typed async client for the Ruxlog API
+//!
+//! Provides:
+//! - The request and response bodies of the API ([`dto`]), generated from
+//!   the backend's OpenAPI document
+//! - One handle per backend module (`client.posts()`, `client.media()`, ...)
+//! - [`Error`], which decodes the API's `ErrorResponse` and [`ErrorCode`]
+//!
+//! Runs natively over `reqwest` (integration tests, tools) and in the browser
+//! over `gloo-net`.
+//!
+//! # Quick Start
+//!
+//! ```ignore
+//! use ruxlog_client::{dto::*, Client, Error, ErrorCode, Upload};
+//!
+//! let client = Client::new("http://localhost:8888").with_csrf_key("ultra-instinct-goku");
+//!
+//! client
+//!     .auth()
+//!     .log_in(&V1LoginPayload { email: "me@example.com".into(), password: "secret".into() })
+//!     .await?;
+//!
+//! let page = client
+//!     .posts()
+//!     .query(&V1PostQueryParams { search: Some("rust".into()), ..Default::default() })
+//!     .await?;
+//!
+//! let upload = Upload::new("cover.png", "image/png", bytes);
+//! match client.media().upload(&upload, &MediaUploadMetadata::default()).await {
+//!     Err(Error::Api(err)) if err.code == ErrorCode::FileTooLarge => { /* ... */ }
+//!     result => { result?; }
+//! }
+//! ```
+
+pub mod api;
+mod client;
+pub mod dto;
+pub mod error;
+mod transport;
+
+pub use client::{Client, Upload};
+pub use error::{Error, ErrorCode, ErrorResponse, Result};
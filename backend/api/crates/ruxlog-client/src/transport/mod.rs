@@ -0,0 +1,12 @@
+//! Platform-specific request sending; both return the status and body text
+//! and leave decoding to [`Client`](crate::Client).
+
+#[cfg(not(target_arch = "wasm32"))]
+mod native;
+#[cfg(target_arch = "wasm32")]
+mod wasm;
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) use native::Transport;
+#[cfg(target_arch = "wasm32")]
+pub(crate) use wasm::Transport;
@@ -0,0 +1,84 @@
+use reqwest::multipart::{Form, Part};
+
+use crate::client::{Body, Method};
+use crate::error::{Error, Result};
+use crate::Client;
+
+#[derive(Clone)]
+pub(crate) struct Transport {
+    http: reqwest::Client,
+    base_url: String,
+    pub(crate) csrf_token: String,
+}
+
+impl Client {
+    /// A client for the API at `base_url`, e.g. `http://localhost:8888`
+    ///
+    /// Every request needs a CSRF token; set one with
+    /// [`with_csrf_key`](Self::with_csrf_key) or
+    /// [`with_csrf_token`](Self::with_csrf_token).
+    pub fn new(base_url: impl Into<String>) -> Self {
+        let http = reqwest::Client::builder()
+            .cookie_store(true)
+            .build()
+            .expect("failed to build HTTP client");
+        Self::with_http_client(http, base_url)
+    }
+
+    /// Use a preconfigured `reqwest` client; enable its cookie store to keep
+    /// the session between calls
+    pub fn with_http_client(http: reqwest::Client, base_url: impl Into<String>) -> Self {
+        Self {
+            transport: Transport {
+                http,
+                base_url: base_url.into().trim_end_matches('/').to_string(),
+                csrf_token: String::new(),
+            },
+        }
+    }
+}
+
+impl Transport {
+    pub(crate) async fn send(
+        &self,
+        method: Method,
+        path: &str,
+        body: Body<'_>,
+    ) -> Result<(u16, String)> {
+        let url = format!("{}{}", self.base_url, path);
+        let mut request = match method {
+            Method::Get => self.http.get(url),
+            Method::Post => self.http.post(url),
+        };
+        if !self.csrf_token.is_empty() {
+            request = request.header("csrf-token", &self.csrf_token);
+        }
+
+        let request = match (method, body) {
+            (Method::Get, _) => request,
+            (Method::Post, Body::Empty) => request.json(&serde_json::json!({})),
+            (Method::Post, Body::Json(body)) => request.json(&body),
+            (Method::Post, Body::Multipart { upload, fields }) => {
+                let file = Part::bytes(upload.bytes.clone())
+                    .file_name(upload.file_name.clone())
+                    .mime_str(&upload.mime_type)
+                    .map_err(transport_error)?;
+                let form = fields
+                    .into_iter()
+                    .fold(Form::new().part("file", file), |form, (name, value)| {
+                        form.text(name, value)
+                    });
+                request.multipart(form)
+            }
+        };
+
+        let response = request.send().await.map_err(transport_error)?;
+        let status = response.status().as_u16();
+        let text = response.text().await.map_err(transport_error)?;
+        Ok((status, text))
+    }
+}
+
+fn transport_error(err: reqwest::Error) -> Error {
+    Error::Transport(err.to_string())
+}
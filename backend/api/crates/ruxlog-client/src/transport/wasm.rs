@@ -0,0 +1,88 @@
+use gloo_net::http::Request;
+use web_sys::RequestCredentials;
+
+use crate::client::{Body, Method, Upload};
+use crate::error::{Error, Result};
+use crate::Client;
+
+#[derive(Clone)]
+pub(crate) struct Transport {
+    base_url: String,
+    pub(crate) csrf_token: String,
+}
+
+impl Client {
+    /// A client for the API at `base_url`, sending the browser's cookies
+    ///
+    /// Every request needs a CSRF token; set one with
+    /// [`with_csrf_key`](Self::with_csrf_key) or
+    /// [`with_csrf_token`](Self::with_csrf_token).
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {
+            transport: Transport {
+                base_url: base_url.into().trim_end_matches('/').to_string(),
+                csrf_token: String::new(),
+            },
+        }
+    }
+}
+
+impl Transport {
+    pub(crate) async fn send(
+        &self,
+        method: Method,
+        path: &str,
+        body: Body<'_>,
+    ) -> Result<(u16, String)> {
+        let url = format!("{}{}", self.base_url, path);
+        let mut request = match method {
+            Method::Get => Request::get(&url),
+            Method::Post => Request::post(&url),
+        }
+        .credentials(RequestCredentials::Include);
+        if !self.csrf_token.is_empty() {
+            request = request.header("csrf-token", &self.csrf_token);
+        }
+
+        // The browser sets the multipart `Content-Type` with its boundary
+        let request = match (method, body) {
+            (Method::Get, _) => request.build(),
+            (Method::Post, Body::Empty) => request.json(&serde_json::json!({})),
+            (Method::Post, Body::Json(body)) => request.json(&body),
+            (Method::Post, Body::Multipart { upload, fields }) => {
+                request.body(form_data(upload, &fields)?)
+            }
+        }
+        .map_err(transport_error)?;
+
+        let response = request.send().await.map_err(transport_error)?;
+        let status = response.status();
+        let text = response.text().await.map_err(transport_error)?;
+        Ok((status, text))
+    }
+}
+
+fn transport_error(err: gloo_net::Error) -> Error {
+    Error::Transport(err.to_string())
+}
+
+fn form_data(upload: &Upload, fields: &[(&'static str, String)]) -> Result<web_sys::FormData> {
+    let js_error = |err: wasm_bindgen::JsValue| Error::Transport(format!("{:?}", err));
+
+    let bytes = js_sys::Uint8Array::from(upload.bytes.as_slice());
+    let options = web_sys::BlobPropertyBag::new();
+    options.set_type(&upload.mime_type);
+    let blob = web_sys::Blob::new_with_u8_array_sequence_and_options(
+        &js_sys::Array::of1(&bytes),
+        &options,
+    )
+    .map_err(js_error)?;
+
+    let form = web_sys::FormData::new().map_err(js_error)?;
+    form.append_with_blob_and_filename("file", &blob, &upload.file_name)
+        .map_err(js_error)?;
+    for (name, value) in fields {
+        form.append_with_str(name, value).map_err(js_error)?;
+    }
+    Ok(form)
+}
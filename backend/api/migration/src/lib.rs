@@ -31,13 +31,30 @@ mod m20251116_000022_alter_media_add_hash;
 mod m20251117_000023_create_media_usages_table;
 mod m20251118_000024_alter_user_add_avatar_id;
 mod m20251119_000028_alter_posts_featured_image;
+mod m20251125_000034_create_post_likes_table;
 mod m20251201_000029_alter_user_add_oauth_fields;
 mod m20251202_000030_create_seed_runs_table;
 mod m20251203_000031_alter_email_and_forgot_password_add_updated_at;
 mod m20251204_000032_rename_media_variant_table;
 mod m20251205_000033_create_app_constants_table;
-mod m20251125_000034_create_post_likes_table;
 mod m20251220_000035_create_user_bans_table;
+mod m20251222_000036_create_backup_tables;
+mod m20251223_000037_create_import_tables;
+mod m20251224_000038_alter_post_revisions_add_snapshot;
+mod m20251226_000039_create_slug_redirects_table;
+mod m20251227_000040_create_post_authors_table;
+mod m20251228_000041_create_post_review_tables;
+mod m20251229_000042_alter_posts_add_visibility;
+mod m20251230_000043_add_locales_and_translation_groups;
+mod m20251231_000044_alter_posts_add_seo_fields;
+mod m20260101_000045_create_permission_tables;
+mod m20260102_000046_alter_app_constants_add_encryption_and_history;
+mod m20260103_000047_alter_route_status_add_rule_fields;
+mod m20260104_000048_create_audit_logs_table;
+mod m20260105_000049_create_jobs_tables;
+mod m20260106_000050_create_webhooks_tables;
+mod m20260107_000051_create_notifications_tables;
+mod m20260108_000052_alter_slug_redirects_add_locale;
 
 pub struct Migrator;
 
@@ -78,13 +95,47 @@ impl MigratorTrait for Migrator {
             Box::new(m20251119_000028_alter_posts_featured_image::Migration),
             Box::new(m20251201_000029_alter_user_add_oauth_fields::Migration),
             Box::new(m20251202_000030_create_seed_runs_table::Migration),
-            Box::new(
-                m20251203_000031_alter_email_and_forgot_password_add_updated_at::Migration,
-            ),
+            Box::new(m20251203_000031_alter_email_and_forgot_password_add_updated_at::Migration),
             Box::new(m20251204_000032_rename_media_variant_table::Migration),
             Box::new(m20251205_000033_create_app_constants_table::Migration),
             Box::new(m20251125_000034_create_post_likes_table::Migration),
             Box::new(m20251220_000035_create_user_bans_table::Migration),
+            Box::new(m20251222_000036_create_backup_tables::Migration),
+            Box::new(m20251223_000037_create_import_tables::Migration),
+            Box::new(m20251224_000038_alter_post_revisions_add_snapshot::Migration),
+            Box::new(m20251226_000039_create_slug_redirects_table::Migration),
+            Box::new(m20251227_000040_create_post_authors_table::Migration),
+            Box::new(m20251228_000041_create_post_review_tables::Migration),
+            Box::new(m20251229_000042_alter_posts_add_visibility::Migration),
+            Box::new(m20251230_000043_add_locales_and_translation_groups::Migration),
+            Box::new(m20251231_000044_alter_posts_add_seo_fields::Migration),
+            Box::new(m20260101_000045_create_permission_tables::Migration),
+            Box::new(m20260102_000046_alter_app_constants_add_encryption_and_history::Migration),
+            Box::new(m20260103_000047_alter_route_status_add_rule_fields::Migration),
+            Box::new(m20260104_000048_create_audit_logs_table::Migration),
+            Box::new(m20260105_000049_create_jobs_tables::Migration),
+            Box::new(m20260106_000050_create_webhooks_tables::Migration),
+            Box::new(m20260107_000051_create_notifications_tables::Migration),
+            Box::new(m20260108_000052_alter_slug_redirects_add_locale::Migration),
         ]
     }
 }
+
+/// Locale of content saved without one, read the way the app reads it
+/// (`utils::default_locale`): the `DEFAULT_LOCALE` setting, `en` when unset
+/// or not a locale tag.
+pub(crate) fn default_locale() -> String {
+    std::env::var("DEFAULT_LOCALE")
+        .ok()
+        .map(|value| value.trim().replace('_', "-").to_ascii_lowercase())
+        .filter(|tag| {
+            let language = tag.split('-').next().unwrap_or_default();
+            tag.len() <= 16
+                && (2..=3).contains(&language.len())
+                && language.chars().all(|c| c.is_ascii_lowercase())
+                && tag.split('-').skip(1).all(|part| {
+                    (2..=8).contains(&part.len()) && part.chars().all(|c| c.is_ascii_alphanumeric())
+                })
+        })
+        .unwrap_or_else(|| "en".to_string())
+}
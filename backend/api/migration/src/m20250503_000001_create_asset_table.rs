@@ -67,4 +67,4 @@ enum Assets {
 enum Users {
     Table,
     Id,
-}
\ No newline at end of file
+}
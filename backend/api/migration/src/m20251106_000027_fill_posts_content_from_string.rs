@@ -38,4 +38,3 @@ impl MigrationTrait for Migration {
         Ok(())
     }
 }
-
@@ -46,4 +46,3 @@ enum SeedRuns {
     RanAt,
     Ranges,
 }
-
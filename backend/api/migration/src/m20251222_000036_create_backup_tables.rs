@@ -0,0 +1,223 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_query::extension::postgres::Type;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Creates the backup tables:
+/// - `backup_jobs`: one row per export or restore run, with the stored archive
+///   location (object key), checksum and a JSON report.
+///
+/// Indexes:
+/// - idx_backup_jobs_kind_status (kind, status)
+/// - idx_backup_jobs_created_at (created_at)
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(BackupJobKind::Table)
+                    .values(vec![BackupJobKind::Export, BackupJobKind::Restore])
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(BackupJobStatus::Table)
+                    .values(vec![
+                        BackupJobStatus::Pending,
+                        BackupJobStatus::Running,
+                        BackupJobStatus::Completed,
+                        BackupJobStatus::Failed,
+                    ])
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(BackupJobs::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(BackupJobs::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(BackupJobs::Kind)
+                            .enumeration(
+                                BackupJobKind::Table,
+                                [BackupJobKind::Export, BackupJobKind::Restore],
+                            )
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(BackupJobs::Status)
+                            .enumeration(
+                                BackupJobStatus::Table,
+                                [
+                                    BackupJobStatus::Pending,
+                                    BackupJobStatus::Running,
+                                    BackupJobStatus::Completed,
+                                    BackupJobStatus::Failed,
+                                ],
+                            )
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(BackupJobs::IncludeMedia)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .col(
+                        ColumnDef::new(BackupJobs::Encrypted)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .col(
+                        ColumnDef::new(BackupJobs::Scheduled)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .col(
+                        ColumnDef::new(BackupJobs::FormatVersion)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(BackupJobs::ObjectKey).string())
+                    .col(ColumnDef::new(BackupJobs::SizeBytes).big_integer())
+                    .col(ColumnDef::new(BackupJobs::Checksum).string())
+                    .col(ColumnDef::new(BackupJobs::SourceJobId).integer())
+                    .col(ColumnDef::new(BackupJobs::Report).json_binary())
+                    .col(ColumnDef::new(BackupJobs::Error).text())
+                    .col(ColumnDef::new(BackupJobs::RequestedBy).integer())
+                    .col(
+                        ColumnDef::new(BackupJobs::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(ColumnDef::new(BackupJobs::StartedAt).timestamp_with_time_zone())
+                    .col(ColumnDef::new(BackupJobs::CompletedAt).timestamp_with_time_zone())
+                    .col(
+                        ColumnDef::new(BackupJobs::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_backup_jobs_requested_by")
+                            .from(BackupJobs::Table, BackupJobs::RequestedBy)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_backup_jobs_source_job_id")
+                            .from(BackupJobs::Table, BackupJobs::SourceJobId)
+                            .to(BackupJobs::Table, BackupJobs::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_backup_jobs_kind_status")
+                    .table(BackupJobs::Table)
+                    .col(BackupJobs::Kind)
+                    .col(BackupJobs::Status)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_backup_jobs_created_at")
+                    .table(BackupJobs::Table)
+                    .col(BackupJobs::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(BackupJobs::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_type(Type::drop().name(BackupJobStatus::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_type(Type::drop().name(BackupJobKind::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum BackupJobs {
+    Table,
+    Id,
+    Kind,
+    Status,
+    IncludeMedia,
+    Encrypted,
+    Scheduled,
+    FormatVersion,
+    ObjectKey,
+    SizeBytes,
+    Checksum,
+    SourceJobId,
+    Report,
+    Error,
+    RequestedBy,
+    CreatedAt,
+    StartedAt,
+    CompletedAt,
+    UpdatedAt,
+}
+
+#[derive(Iden)]
+enum Users {
+    Table,
+    Id,
+}
+
+#[derive(Iden)]
+enum BackupJobKind {
+    Table,
+    #[iden = "export"]
+    Export,
+    #[iden = "restore"]
+    Restore,
+}
+
+#[derive(Iden)]
+enum BackupJobStatus {
+    Table,
+    #[iden = "pending"]
+    Pending,
+    #[iden = "running"]
+    Running,
+    #[iden = "completed"]
+    Completed,
+    #[iden = "failed"]
+    Failed,
+}
@@ -0,0 +1,265 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_query::extension::postgres::Type;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Creates the blog import tables:
+/// - `import_jobs`: one row per WordPress/Ghost import run (or dry run), with
+///   its options and JSON report.
+/// - `import_mappings`: source item -> local row, so re-running an import
+///   skips what was already brought in.
+///
+/// Indexes:
+/// - idx_import_jobs_created_at (created_at)
+/// - uniq_import_mappings_source_item (source, scope, entity, source_id)
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(ImportSource::Table)
+                    .values(vec![ImportSource::Wordpress, ImportSource::Ghost])
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(ImportJobStatus::Table)
+                    .values(vec![
+                        ImportJobStatus::Pending,
+                        ImportJobStatus::Running,
+                        ImportJobStatus::Completed,
+                        ImportJobStatus::Failed,
+                    ])
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(ImportJobs::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ImportJobs::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(ImportJobs::Source)
+                            .enumeration(
+                                ImportSource::Table,
+                                [ImportSource::Wordpress, ImportSource::Ghost],
+                            )
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ImportJobs::Status)
+                            .enumeration(
+                                ImportJobStatus::Table,
+                                [
+                                    ImportJobStatus::Pending,
+                                    ImportJobStatus::Running,
+                                    ImportJobStatus::Completed,
+                                    ImportJobStatus::Failed,
+                                ],
+                            )
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ImportJobs::DryRun)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .col(ColumnDef::new(ImportJobs::FileName).string())
+                    .col(ColumnDef::new(ImportJobs::Options).json_binary())
+                    .col(ColumnDef::new(ImportJobs::Report).json_binary())
+                    .col(ColumnDef::new(ImportJobs::Error).text())
+                    .col(ColumnDef::new(ImportJobs::RequestedBy).integer())
+                    .col(
+                        ColumnDef::new(ImportJobs::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(ColumnDef::new(ImportJobs::StartedAt).timestamp_with_time_zone())
+                    .col(ColumnDef::new(ImportJobs::CompletedAt).timestamp_with_time_zone())
+                    .col(
+                        ColumnDef::new(ImportJobs::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_import_jobs_requested_by")
+                            .from(ImportJobs::Table, ImportJobs::RequestedBy)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_import_jobs_created_at")
+                    .table(ImportJobs::Table)
+                    .col(ImportJobs::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(ImportMappings::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ImportMappings::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(ImportMappings::Source)
+                            .enumeration(
+                                ImportSource::Table,
+                                [ImportSource::Wordpress, ImportSource::Ghost],
+                            )
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ImportMappings::Scope)
+                            .string()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(ColumnDef::new(ImportMappings::Entity).string().not_null())
+                    .col(ColumnDef::new(ImportMappings::SourceId).string().not_null())
+                    .col(
+                        ColumnDef::new(ImportMappings::TargetId)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(ImportMappings::JobId).integer())
+                    .col(
+                        ColumnDef::new(ImportMappings::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_import_mappings_job_id")
+                            .from(ImportMappings::Table, ImportMappings::JobId)
+                            .to(ImportJobs::Table, ImportJobs::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("uniq_import_mappings_source_item")
+                    .table(ImportMappings::Table)
+                    .col(ImportMappings::Source)
+                    .col(ImportMappings::Scope)
+                    .col(ImportMappings::Entity)
+                    .col(ImportMappings::SourceId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ImportMappings::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(ImportJobs::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_type(Type::drop().name(ImportJobStatus::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_type(Type::drop().name(ImportSource::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum ImportJobs {
+    Table,
+    Id,
+    Source,
+    Status,
+    DryRun,
+    FileName,
+    Options,
+    Report,
+    Error,
+    RequestedBy,
+    CreatedAt,
+    StartedAt,
+    CompletedAt,
+    UpdatedAt,
+}
+
+#[derive(Iden)]
+enum ImportMappings {
+    Table,
+    Id,
+    Source,
+    Scope,
+    Entity,
+    SourceId,
+    TargetId,
+    JobId,
+    CreatedAt,
+}
+
+#[derive(Iden)]
+enum Users {
+    Table,
+    Id,
+}
+
+#[derive(Iden)]
+enum ImportSource {
+    Table,
+    #[iden = "wordpress"]
+    Wordpress,
+    #[iden = "ghost"]
+    Ghost,
+}
+
+#[derive(Iden)]
+enum ImportJobStatus {
+    Table,
+    #[iden = "pending"]
+    Pending,
+    #[iden = "running"]
+    Running,
+    #[iden = "completed"]
+    Completed,
+    #[iden = "failed"]
+    Failed,
+}
@@ -0,0 +1,70 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Adds full-field snapshots to `post_revisions`:
+/// - fields (jsonb, nullable): title, slug, excerpt, category, tags and
+///   featured image at the time of the revision; NULL for content-only
+///   revisions recorded before this migration
+/// - created_by -> users.id (FK, set null on delete): who made the change
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(PostRevisions::Table)
+                    .add_column(ColumnDef::new(PostRevisions::Fields).json_binary().null())
+                    .add_column(ColumnDef::new(PostRevisions::CreatedBy).integer().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_foreign_key(
+                ForeignKey::create()
+                    .name("fk_post_revisions_created_by")
+                    .from(PostRevisions::Table, PostRevisions::CreatedBy)
+                    .to(Users::Table, Users::Id)
+                    .on_delete(ForeignKeyAction::SetNull)
+                    .on_update(ForeignKeyAction::Cascade)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_foreign_key(
+                ForeignKey::drop()
+                    .name("fk_post_revisions_created_by")
+                    .table(PostRevisions::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(PostRevisions::Table)
+                    .drop_column(PostRevisions::Fields)
+                    .drop_column(PostRevisions::CreatedBy)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum PostRevisions {
+    Table,
+    Fields,
+    CreatedBy,
+}
+
+#[derive(Iden)]
+enum Users {
+    Table,
+    Id,
+}
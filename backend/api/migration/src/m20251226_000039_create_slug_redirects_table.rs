@@ -0,0 +1,182 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_query::extension::postgres::Type;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Creates `slug_redirects`: old slugs of posts, categories, tags and series,
+/// pointing at the row that now owns the content. Rows are written
+/// automatically when a slug changes (`auto`) or by admins (`manual`).
+///
+/// The target is an id rather than a slug, so renaming the target again
+/// never leaves a chain of redirects behind.
+///
+/// Indexes:
+/// - uniq_slug_redirects_entity_from_slug (entity, from_slug)
+/// - idx_slug_redirects_target (entity, target_id)
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(SlugRedirectEntity::Table)
+                    .values(vec![
+                        SlugRedirectEntity::Post,
+                        SlugRedirectEntity::Category,
+                        SlugRedirectEntity::Tag,
+                        SlugRedirectEntity::Series,
+                    ])
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(SlugRedirectKind::Table)
+                    .values(vec![SlugRedirectKind::Auto, SlugRedirectKind::Manual])
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(SlugRedirects::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(SlugRedirects::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(SlugRedirects::Entity)
+                            .enumeration(
+                                SlugRedirectEntity::Table,
+                                [
+                                    SlugRedirectEntity::Post,
+                                    SlugRedirectEntity::Category,
+                                    SlugRedirectEntity::Tag,
+                                    SlugRedirectEntity::Series,
+                                ],
+                            )
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(SlugRedirects::FromSlug).string().not_null())
+                    .col(ColumnDef::new(SlugRedirects::TargetId).integer().not_null())
+                    .col(
+                        ColumnDef::new(SlugRedirects::Kind)
+                            .enumeration(
+                                SlugRedirectKind::Table,
+                                [SlugRedirectKind::Auto, SlugRedirectKind::Manual],
+                            )
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(SlugRedirects::CreatedBy).integer())
+                    .col(
+                        ColumnDef::new(SlugRedirects::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(SlugRedirects::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_slug_redirects_created_by")
+                            .from(SlugRedirects::Table, SlugRedirects::CreatedBy)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("uniq_slug_redirects_entity_from_slug")
+                    .table(SlugRedirects::Table)
+                    .col(SlugRedirects::Entity)
+                    .col(SlugRedirects::FromSlug)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_slug_redirects_target")
+                    .table(SlugRedirects::Table)
+                    .col(SlugRedirects::Entity)
+                    .col(SlugRedirects::TargetId)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(SlugRedirects::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_type(Type::drop().name(SlugRedirectKind::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_type(Type::drop().name(SlugRedirectEntity::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum SlugRedirects {
+    Table,
+    Id,
+    Entity,
+    FromSlug,
+    TargetId,
+    Kind,
+    CreatedBy,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(Iden)]
+enum SlugRedirectEntity {
+    Table,
+    #[iden = "post"]
+    Post,
+    #[iden = "category"]
+    Category,
+    #[iden = "tag"]
+    Tag,
+    #[iden = "series"]
+    Series,
+}
+
+#[derive(Iden)]
+enum SlugRedirectKind {
+    Table,
+    #[iden = "auto"]
+    Auto,
+    #[iden = "manual"]
+    Manual,
+}
+
+#[derive(Iden)]
+enum Users {
+    Table,
+    Id,
+}
@@ -0,0 +1,168 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_query::extension::postgres::Type;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Creates `post_authors`: everyone credited on a post, in display order,
+/// with the part they played (`author`, `editor`, `reviewer`).
+///
+/// `posts.author_id` stays as the primary author (the first `author` row)
+/// so existing joins keep working. Existing posts are backfilled with their
+/// current author.
+///
+/// Indexes:
+/// - uniq_post_authors_post_user (post_id, user_id)
+/// - idx_post_authors_user (user_id)
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(PostAuthorRole::Table)
+                    .values(vec![
+                        PostAuthorRole::Author,
+                        PostAuthorRole::Editor,
+                        PostAuthorRole::Reviewer,
+                    ])
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(PostAuthors::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(PostAuthors::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(PostAuthors::PostId).integer().not_null())
+                    .col(ColumnDef::new(PostAuthors::UserId).integer().not_null())
+                    .col(
+                        ColumnDef::new(PostAuthors::Role)
+                            .enumeration(
+                                PostAuthorRole::Table,
+                                [
+                                    PostAuthorRole::Author,
+                                    PostAuthorRole::Editor,
+                                    PostAuthorRole::Reviewer,
+                                ],
+                            )
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(PostAuthors::SortOrder)
+                            .integer()
+                            .not_null()
+                            .default(1),
+                    )
+                    .col(
+                        ColumnDef::new(PostAuthors::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_post_authors_post")
+                            .from(PostAuthors::Table, PostAuthors::PostId)
+                            .to(Posts::Table, Posts::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_post_authors_user")
+                            .from(PostAuthors::Table, PostAuthors::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("uniq_post_authors_post_user")
+                    .table(PostAuthors::Table)
+                    .col(PostAuthors::PostId)
+                    .col(PostAuthors::UserId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_post_authors_user")
+                    .table(PostAuthors::Table)
+                    .col(PostAuthors::UserId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                INSERT INTO "post_authors" ("post_id", "user_id", "role", "sort_order")
+                SELECT "id", "author_id", 'author', 1 FROM "posts"
+                ON CONFLICT DO NOTHING;
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(PostAuthors::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_type(Type::drop().name(PostAuthorRole::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum PostAuthors {
+    Table,
+    Id,
+    PostId,
+    UserId,
+    Role,
+    SortOrder,
+    CreatedAt,
+}
+
+#[derive(Iden)]
+enum PostAuthorRole {
+    Table,
+    #[iden = "author"]
+    Author,
+    #[iden = "editor"]
+    Editor,
+    #[iden = "reviewer"]
+    Reviewer,
+}
+
+#[derive(Iden)]
+enum Posts {
+    Table,
+    Id,
+}
+
+#[derive(Iden)]
+enum Users {
+    Table,
+    Id,
+}
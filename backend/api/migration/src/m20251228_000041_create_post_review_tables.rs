@@ -0,0 +1,233 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Editorial review:
+/// - `post_status` gains `in_review`, `changes_requested` and `approved`
+/// - `post_review_notes`: reviewer notes, optionally anchored to an Editor.js
+///   block ID
+/// - `post_status_transitions`: every status change with who made it
+///
+/// Indexes:
+/// - idx_post_review_notes_post (post_id)
+/// - idx_post_status_transitions_post (post_id, created_at)
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        for value in ["in_review", "changes_requested", "approved"] {
+            db.execute_unprepared(&format!(
+                r#"ALTER TYPE "post_status" ADD VALUE IF NOT EXISTS '{}';"#,
+                value
+            ))
+            .await?;
+        }
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(PostReviewNotes::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(PostReviewNotes::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(PostReviewNotes::PostId).integer().not_null())
+                    .col(ColumnDef::new(PostReviewNotes::BlockId).string_len(64))
+                    .col(ColumnDef::new(PostReviewNotes::Body).text().not_null())
+                    .col(ColumnDef::new(PostReviewNotes::AuthorId).integer())
+                    .col(ColumnDef::new(PostReviewNotes::ResolvedAt).timestamp_with_time_zone())
+                    .col(ColumnDef::new(PostReviewNotes::ResolvedBy).integer())
+                    .col(
+                        ColumnDef::new(PostReviewNotes::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(PostReviewNotes::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_post_review_notes_post")
+                            .from(PostReviewNotes::Table, PostReviewNotes::PostId)
+                            .to(Posts::Table, Posts::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_post_review_notes_author")
+                            .from(PostReviewNotes::Table, PostReviewNotes::AuthorId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_post_review_notes_resolved_by")
+                            .from(PostReviewNotes::Table, PostReviewNotes::ResolvedBy)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_post_review_notes_post")
+                    .table(PostReviewNotes::Table)
+                    .col(PostReviewNotes::PostId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(PostStatusTransitions::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(PostStatusTransitions::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(PostStatusTransitions::PostId)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(PostStatusTransitions::FromStatus)
+                            .custom(PostStatus::PostStatus)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(PostStatusTransitions::ToStatus)
+                            .custom(PostStatus::PostStatus)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(PostStatusTransitions::ActorId).integer())
+                    .col(ColumnDef::new(PostStatusTransitions::Note).text())
+                    .col(
+                        ColumnDef::new(PostStatusTransitions::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_post_status_transitions_post")
+                            .from(PostStatusTransitions::Table, PostStatusTransitions::PostId)
+                            .to(Posts::Table, Posts::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_post_status_transitions_actor")
+                            .from(PostStatusTransitions::Table, PostStatusTransitions::ActorId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_post_status_transitions_post")
+                    .table(PostStatusTransitions::Table)
+                    .col(PostStatusTransitions::PostId)
+                    .col(PostStatusTransitions::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(PostStatusTransitions::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(PostReviewNotes::Table).to_owned())
+            .await?;
+
+        // Postgres can't drop enum values; fold review states back into
+        // drafts and rebuild the type.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                UPDATE "posts" SET "status" = 'draft'
+                WHERE "status" IN ('in_review', 'changes_requested', 'approved');
+                ALTER TYPE "post_status" RENAME TO "post_status_old";
+                CREATE TYPE "post_status" AS ENUM ('draft', 'published', 'archived');
+                ALTER TABLE "posts" ALTER COLUMN "status" DROP DEFAULT;
+                ALTER TABLE "posts" ALTER COLUMN "status" TYPE "post_status"
+                    USING "status"::text::"post_status";
+                ALTER TABLE "posts" ALTER COLUMN "status" SET DEFAULT 'draft';
+                DROP TYPE "post_status_old";
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+enum PostReviewNotes {
+    Table,
+    Id,
+    PostId,
+    BlockId,
+    Body,
+    AuthorId,
+    ResolvedAt,
+    ResolvedBy,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(Iden)]
+enum PostStatusTransitions {
+    Table,
+    Id,
+    PostId,
+    FromStatus,
+    ToStatus,
+    ActorId,
+    Note,
+    CreatedAt,
+}
+
+#[derive(Iden)]
+enum PostStatus {
+    #[iden = "post_status"]
+    PostStatus,
+}
+
+#[derive(Iden)]
+enum Posts {
+    Table,
+    Id,
+}
+
+#[derive(Iden)]
+enum Users {
+    Table,
+    Id,
+}
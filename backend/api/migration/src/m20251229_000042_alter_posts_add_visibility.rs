@@ -0,0 +1,115 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_query::extension::postgres::Type;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Per-post visibility on `posts`:
+/// - visibility (`post_visibility`, default `public`): `public`, `members`
+///   (any logged-in user), `role` (at least `min_role`) or `password`
+/// - min_role (`user_role`, nullable): minimum role for `role` posts
+/// - password_hash (nullable): hash of the password for `password` posts
+///
+/// Indexes:
+/// - idx_posts_visibility (visibility)
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(PostVisibility::Table)
+                    .values(vec![
+                        PostVisibility::Public,
+                        PostVisibility::Members,
+                        PostVisibility::Role,
+                        PostVisibility::Password,
+                    ])
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Posts::Table)
+                    .add_column(
+                        ColumnDef::new(Posts::Visibility)
+                            .custom(PostVisibility::Table)
+                            .not_null()
+                            .default(Expr::cust("'public'::post_visibility")),
+                    )
+                    .add_column(
+                        ColumnDef::new(Posts::MinRole)
+                            .custom(UserRole::Table)
+                            .null(),
+                    )
+                    .add_column(ColumnDef::new(Posts::PasswordHash).string().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_posts_visibility")
+                    .table(Posts::Table)
+                    .col(Posts::Visibility)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_posts_visibility")
+                    .table(Posts::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Posts::Table)
+                    .drop_column(Posts::Visibility)
+                    .drop_column(Posts::MinRole)
+                    .drop_column(Posts::PasswordHash)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_type(Type::drop().name(PostVisibility::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum Posts {
+    Table,
+    Visibility,
+    MinRole,
+    PasswordHash,
+}
+
+#[derive(Iden)]
+enum PostVisibility {
+    Table,
+    #[iden = "public"]
+    Public,
+    #[iden = "members"]
+    Members,
+    #[iden = "role"]
+    Role,
+    #[iden = "password"]
+    Password,
+}
+
+#[derive(Iden)]
+enum UserRole {
+    #[iden = "user_role"]
+    Table,
+}
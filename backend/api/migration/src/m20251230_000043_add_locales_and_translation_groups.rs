@@ -0,0 +1,197 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Multilingual content:
+/// - `post_translation_groups`: one row per set of equivalent posts
+/// - posts.locale (varchar(16), default the `DEFAULT_LOCALE` setting) and
+///   posts.translation_group_id -> post_translation_groups.id (FK, set null
+///   on delete)
+/// - categories.locale / tags.locale (nullable): NULL means shared by all
+///   locales
+/// - Post slugs become unique per locale instead of globally
+///
+/// Indexes:
+/// - uniq_posts_locale_slug (locale, slug)
+/// - uniq_posts_translation_group_locale (translation_group_id, locale)
+/// - idx_posts_locale (locale)
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(PostTranslationGroups::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(PostTranslationGroups::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(PostTranslationGroups::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Posts::Table)
+                    .add_column(
+                        ColumnDef::new(Posts::Locale)
+                            .string_len(16)
+                            .not_null()
+                            .default(crate::default_locale()),
+                    )
+                    .add_column(ColumnDef::new(Posts::TranslationGroupId).integer().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_foreign_key(
+                ForeignKey::create()
+                    .name("fk_posts_translation_group")
+                    .from(Posts::Table, Posts::TranslationGroupId)
+                    .to(PostTranslationGroups::Table, PostTranslationGroups::Id)
+                    .on_delete(ForeignKeyAction::SetNull)
+                    .to_owned(),
+            )
+            .await?;
+
+        for table in [Categories::Table.into_iden(), Tags::Table.into_iden()] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(table)
+                        .add_column(ColumnDef::new(Alias::new("locale")).string_len(16).null())
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        let db = manager.get_connection();
+        db.execute_unprepared(r#"ALTER TABLE "posts" DROP CONSTRAINT IF EXISTS "posts_slug_key";"#)
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("uniq_posts_locale_slug")
+                    .table(Posts::Table)
+                    .col(Posts::Locale)
+                    .col(Posts::Slug)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("uniq_posts_translation_group_locale")
+                    .table(Posts::Table)
+                    .col(Posts::TranslationGroupId)
+                    .col(Posts::Locale)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_posts_locale")
+                    .table(Posts::Table)
+                    .col(Posts::Locale)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for name in [
+            "idx_posts_locale",
+            "uniq_posts_translation_group_locale",
+            "uniq_posts_locale_slug",
+        ] {
+            manager
+                .drop_index(Index::drop().name(name).table(Posts::Table).to_owned())
+                .await?;
+        }
+
+        // Fails when two locales share a slug; resolve those first.
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            r#"ALTER TABLE "posts" ADD CONSTRAINT "posts_slug_key" UNIQUE ("slug");"#,
+        )
+        .await?;
+
+        for table in [Categories::Table.into_iden(), Tags::Table.into_iden()] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(table)
+                        .drop_column(Alias::new("locale"))
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        manager
+            .drop_foreign_key(
+                ForeignKey::drop()
+                    .name("fk_posts_translation_group")
+                    .table(Posts::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Posts::Table)
+                    .drop_column(Posts::Locale)
+                    .drop_column(Posts::TranslationGroupId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(PostTranslationGroups::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum PostTranslationGroups {
+    Table,
+    Id,
+    CreatedAt,
+}
+
+#[derive(Iden)]
+enum Posts {
+    Table,
+    Slug,
+    Locale,
+    TranslationGroupId,
+}
+
+#[derive(Iden)]
+enum Categories {
+    Table,
+}
+
+#[derive(Iden)]
+enum Tags {
+    Table,
+}
@@ -0,0 +1,94 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// SEO metadata on `posts`:
+/// - meta_title, meta_description (nullable): override title and excerpt
+///   in search results and link previews
+/// - canonical_url (nullable): canonical URL when it isn't the post's own
+/// - noindex (default false): keep the post out of search engines and the
+///   sitemap
+/// - og_image_id (nullable, FK media, SET NULL): Open Graph/Twitter image;
+///   the featured image when unset
+/// - og_image_variant (nullable): `media_variants.variant_type` to use for it
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Posts::Table)
+                    .add_column(ColumnDef::new(Posts::MetaTitle).string_len(255).null())
+                    .add_column(
+                        ColumnDef::new(Posts::MetaDescription)
+                            .string_len(500)
+                            .null(),
+                    )
+                    .add_column(ColumnDef::new(Posts::CanonicalUrl).text().null())
+                    .add_column(
+                        ColumnDef::new(Posts::Noindex)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .add_column(ColumnDef::new(Posts::OgImageId).integer().null())
+                    .add_column(ColumnDef::new(Posts::OgImageVariant).string_len(32).null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_foreign_key(
+                ForeignKey::create()
+                    .name("fk_posts_og_image")
+                    .from(Posts::Table, Posts::OgImageId)
+                    .to(Media::Table, Media::Id)
+                    .on_delete(ForeignKeyAction::SetNull)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_foreign_key(
+                ForeignKey::drop()
+                    .name("fk_posts_og_image")
+                    .table(Posts::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Posts::Table)
+                    .drop_column(Posts::MetaTitle)
+                    .drop_column(Posts::MetaDescription)
+                    .drop_column(Posts::CanonicalUrl)
+                    .drop_column(Posts::Noindex)
+                    .drop_column(Posts::OgImageId)
+                    .drop_column(Posts::OgImageVariant)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum Posts {
+    Table,
+    MetaTitle,
+    MetaDescription,
+    CanonicalUrl,
+    Noindex,
+    OgImageId,
+    OgImageVariant,
+}
+
+#[derive(Iden)]
+enum Media {
+    Table,
+    Id,
+}
@@ -0,0 +1,221 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Permission-based access control:
+/// - `role_permissions`: the permissions each role holds, seeded with what
+///   the fixed role levels allowed until now
+/// - `user_permissions`: per-user grants (`granted = true`) and revocations
+///   (`granted = false`) on top of the user's role
+///
+/// Indexes:
+/// - idx_role_permissions_role_permission (role, permission), unique
+/// - idx_user_permissions_user_permission (user_id, permission), unique
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(RolePermissions::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(RolePermissions::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(RolePermissions::Role)
+                            .custom(UserRole::Table)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(RolePermissions::Permission)
+                            .string_len(64)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(RolePermissions::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_role_permissions_role_permission")
+                    .table(RolePermissions::Table)
+                    .col(RolePermissions::Role)
+                    .col(RolePermissions::Permission)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(UserPermissions::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(UserPermissions::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(UserPermissions::UserId).integer().not_null())
+                    .col(
+                        ColumnDef::new(UserPermissions::Permission)
+                            .string_len(64)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(UserPermissions::Granted)
+                            .boolean()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(UserPermissions::UpdatedBy).integer())
+                    .col(
+                        ColumnDef::new(UserPermissions::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(UserPermissions::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_user_permissions_user")
+                            .from(UserPermissions::Table, UserPermissions::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_user_permissions_updated_by")
+                            .from(UserPermissions::Table, UserPermissions::UpdatedBy)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_user_permissions_user_permission")
+                    .table(UserPermissions::Table)
+                    .col(UserPermissions::UserId)
+                    .col(UserPermissions::Permission)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        // Today's defaults: each permission goes to its minimum role and
+        // every role above it.
+        let values: Vec<String> = DEFAULTS
+            .iter()
+            .flat_map(|(permission, min_role)| {
+                let min_level = level(min_role);
+                ROLES
+                    .iter()
+                    .filter(move |role| level(role) >= min_level)
+                    .map(move |role| format!("('{}', '{}')", role, permission))
+            })
+            .collect();
+        manager
+            .get_connection()
+            .execute_unprepared(&format!(
+                r#"INSERT INTO "role_permissions" ("role", "permission") VALUES {}
+                ON CONFLICT DO NOTHING;"#,
+                values.join(", ")
+            ))
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(UserPermissions::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(RolePermissions::Table).to_owned())
+            .await
+    }
+}
+
+/// Roles from lowest to highest.
+const ROLES: [&str; 5] = ["user", "author", "moderator", "admin", "super-admin"];
+
+/// Permission and the lowest role that held it under the fixed role levels.
+const DEFAULTS: [(&str, &str); 16] = [
+    ("post.view_any", "moderator"),
+    ("post.edit_any", "moderator"),
+    ("post.publish", "moderator"),
+    ("post.review", "moderator"),
+    ("comment.moderate", "admin"),
+    ("media.delete_any", "admin"),
+    ("taxonomy.manage", "admin"),
+    ("user.manage", "admin"),
+    ("newsletter.manage", "admin"),
+    ("analytics.view", "admin"),
+    ("redirect.manage", "admin"),
+    ("route.manage", "admin"),
+    ("import.run", "admin"),
+    ("backup.manage", "super-admin"),
+    ("acl.manage", "super-admin"),
+    ("permission.manage", "super-admin"),
+];
+
+fn level(role: &str) -> usize {
+    ROLES.iter().position(|r| *r == role).unwrap_or(0)
+}
+
+#[derive(Iden)]
+enum RolePermissions {
+    Table,
+    Id,
+    Role,
+    Permission,
+    CreatedAt,
+}
+
+#[derive(Iden)]
+enum UserPermissions {
+    Table,
+    Id,
+    UserId,
+    Permission,
+    Granted,
+    UpdatedBy,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(Iden)]
+enum Users {
+    Table,
+    Id,
+}
+
+#[derive(Iden)]
+enum UserRole {
+    #[iden = "user_role"]
+    Table,
+}
@@ -0,0 +1,130 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Settings hardening:
+/// - `app_constants.is_encrypted` (default false): the value is an envelope
+///   encrypted blob, see `services::settings::crypto`
+/// - `app_constant_changes`: who changed which constant and when, with the
+///   old and new values for non-sensitive keys
+///
+/// Indexes:
+/// - idx_app_constant_changes_key (key, created_at)
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(AppConstants::Table)
+                    .add_column(
+                        ColumnDef::new(AppConstants::IsEncrypted)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(AppConstantChanges::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(AppConstantChanges::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(AppConstantChanges::Key)
+                            .string_len(191)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(AppConstantChanges::Action)
+                            .string_len(16)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(AppConstantChanges::OldValue).text())
+                    .col(ColumnDef::new(AppConstantChanges::NewValue).text())
+                    .col(
+                        ColumnDef::new(AppConstantChanges::IsSensitive)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .col(ColumnDef::new(AppConstantChanges::ChangedBy).integer())
+                    .col(
+                        ColumnDef::new(AppConstantChanges::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_app_constant_changes_changed_by")
+                            .from(AppConstantChanges::Table, AppConstantChanges::ChangedBy)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_app_constant_changes_key")
+                    .table(AppConstantChanges::Table)
+                    .col(AppConstantChanges::Key)
+                    .col(AppConstantChanges::CreatedAt)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(AppConstantChanges::Table).to_owned())
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(AppConstants::Table)
+                    .drop_column(AppConstants::IsEncrypted)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum AppConstants {
+    Table,
+    IsEncrypted,
+}
+
+#[derive(DeriveIden)]
+enum AppConstantChanges {
+    Table,
+    Id,
+    Key,
+    Action,
+    OldValue,
+    NewValue,
+    IsSensitive,
+    ChangedBy,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+}
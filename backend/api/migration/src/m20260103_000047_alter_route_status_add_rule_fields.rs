@@ -0,0 +1,78 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Route blocker rules on `route_status`:
+/// - `match_type`: `exact` (default, the old behavior), `prefix` or `glob`
+/// - `methods`: HTTP methods the rule applies to; empty means all
+/// - `exempt_role`: lowest role that bypasses the rule
+/// - `active_from` / `active_until`: optional window the rule is in effect
+/// - `status_code`, `message`, `retry_after_secs`: what blocked callers get
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(RouteStatus::Table)
+                    .add_column(
+                        ColumnDef::new(RouteStatus::MatchType)
+                            .string_len(16)
+                            .not_null()
+                            .default("exact"),
+                    )
+                    .add_column(
+                        ColumnDef::new(RouteStatus::Methods)
+                            .array(ColumnType::Text)
+                            .not_null()
+                            .default(Expr::cust("'{}'")),
+                    )
+                    .add_column(ColumnDef::new(RouteStatus::ExemptRole).custom(UserRole::Table))
+                    .add_column(ColumnDef::new(RouteStatus::ActiveFrom).timestamp_with_time_zone())
+                    .add_column(ColumnDef::new(RouteStatus::ActiveUntil).timestamp_with_time_zone())
+                    .add_column(ColumnDef::new(RouteStatus::StatusCode).integer())
+                    .add_column(ColumnDef::new(RouteStatus::Message).string_len(500))
+                    .add_column(ColumnDef::new(RouteStatus::RetryAfterSecs).integer())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(RouteStatus::Table)
+                    .drop_column(RouteStatus::MatchType)
+                    .drop_column(RouteStatus::Methods)
+                    .drop_column(RouteStatus::ExemptRole)
+                    .drop_column(RouteStatus::ActiveFrom)
+                    .drop_column(RouteStatus::ActiveUntil)
+                    .drop_column(RouteStatus::StatusCode)
+                    .drop_column(RouteStatus::Message)
+                    .drop_column(RouteStatus::RetryAfterSecs)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum RouteStatus {
+    Table,
+    MatchType,
+    Methods,
+    ExemptRole,
+    ActiveFrom,
+    ActiveUntil,
+    StatusCode,
+    Message,
+    RetryAfterSecs,
+}
+
+#[derive(Iden)]
+enum UserRole {
+    #[iden = "user_role"]
+    Table,
+}
@@ -0,0 +1,160 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Creates `audit_logs`: one row per privileged action, with the actor, the
+/// target entity, before/after snapshots and their diff, and the IP and
+/// request ID the action came from.
+///
+/// Also grants the new `audit.view` permission to super admins.
+///
+/// Indexes:
+/// - idx_audit_logs_created_at (created_at)
+/// - idx_audit_logs_actor (actor_id)
+/// - idx_audit_logs_entity (entity_type, entity_id)
+/// - idx_audit_logs_action (action)
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(AuditLogs::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(AuditLogs::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(AuditLogs::ActorId).integer())
+                    .col(ColumnDef::new(AuditLogs::ActorRole).custom(UserRole::Table))
+                    .col(ColumnDef::new(AuditLogs::Action).string_len(128).not_null())
+                    .col(ColumnDef::new(AuditLogs::EntityType).string_len(64))
+                    .col(ColumnDef::new(AuditLogs::EntityId).string_len(255))
+                    .col(ColumnDef::new(AuditLogs::Before).json_binary())
+                    .col(ColumnDef::new(AuditLogs::After).json_binary())
+                    .col(ColumnDef::new(AuditLogs::Diff).json_binary())
+                    .col(ColumnDef::new(AuditLogs::Method).string_len(16).not_null())
+                    .col(ColumnDef::new(AuditLogs::Path).string_len(512).not_null())
+                    .col(ColumnDef::new(AuditLogs::Status).small_integer().not_null())
+                    .col(ColumnDef::new(AuditLogs::Ip).string_len(64))
+                    .col(ColumnDef::new(AuditLogs::RequestId).string_len(128))
+                    .col(
+                        ColumnDef::new(AuditLogs::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_audit_logs_actor")
+                            .from(AuditLogs::Table, AuditLogs::ActorId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_audit_logs_created_at")
+                    .table(AuditLogs::Table)
+                    .col(AuditLogs::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_audit_logs_actor")
+                    .table(AuditLogs::Table)
+                    .col(AuditLogs::ActorId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_audit_logs_entity")
+                    .table(AuditLogs::Table)
+                    .col(AuditLogs::EntityType)
+                    .col(AuditLogs::EntityId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_audit_logs_action")
+                    .table(AuditLogs::Table)
+                    .col(AuditLogs::Action)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"INSERT INTO "role_permissions" ("role", "permission")
+                VALUES ('super-admin', 'audit.view')
+                ON CONFLICT DO NOTHING;"#,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"DELETE FROM "role_permissions" WHERE "permission" = 'audit.view';
+                DELETE FROM "user_permissions" WHERE "permission" = 'audit.view';"#,
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(AuditLogs::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum AuditLogs {
+    Table,
+    Id,
+    ActorId,
+    ActorRole,
+    Action,
+    EntityType,
+    EntityId,
+    Before,
+    After,
+    Diff,
+    Method,
+    Path,
+    Status,
+    Ip,
+    RequestId,
+    CreatedAt,
+}
+
+#[derive(Iden)]
+enum Users {
+    Table,
+    Id,
+}
+
+#[derive(Iden)]
+enum UserRole {
+    #[iden = "user_role"]
+    Table,
+}
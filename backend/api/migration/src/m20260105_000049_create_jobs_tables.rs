@@ -0,0 +1,321 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_query::extension::postgres::Type;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Background job queue:
+/// - `jobs`: queued work, claimed by workers with `FOR UPDATE SKIP LOCKED`.
+///   Failed jobs go back to `pending` with a later `run_at` until
+///   `max_attempts` is used up, then to `dead`
+/// - `job_schedules`: recurring jobs with a cron expression, enqueued by
+///   whichever instance claims the row first
+///
+/// Also grants the new `job.manage` permission to admins and super admins.
+///
+/// Indexes:
+/// - idx_jobs_claim (queue, status, run_at)
+/// - idx_jobs_kind (kind)
+/// - uniq_jobs_active_unique_key (unique_key) where status is pending or running
+/// - uniq_job_schedules_name (name)
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(JobStatus::Table)
+                    .values(vec![
+                        JobStatus::Pending,
+                        JobStatus::Running,
+                        JobStatus::Succeeded,
+                        JobStatus::Dead,
+                        JobStatus::Cancelled,
+                    ])
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(Jobs::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Jobs::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Jobs::Queue).string_len(64).not_null())
+                    .col(ColumnDef::new(Jobs::Kind).string_len(128).not_null())
+                    .col(
+                        ColumnDef::new(Jobs::Payload)
+                            .json_binary()
+                            .not_null()
+                            .default(Expr::cust("'{}'::jsonb")),
+                    )
+                    .col(
+                        ColumnDef::new(Jobs::Status)
+                            .enumeration(
+                                JobStatus::Table,
+                                [
+                                    JobStatus::Pending,
+                                    JobStatus::Running,
+                                    JobStatus::Succeeded,
+                                    JobStatus::Dead,
+                                    JobStatus::Cancelled,
+                                ],
+                            )
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(Jobs::Attempts)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(Jobs::MaxAttempts)
+                            .integer()
+                            .not_null()
+                            .default(5),
+                    )
+                    .col(ColumnDef::new(Jobs::UniqueKey).string_len(255))
+                    .col(
+                        ColumnDef::new(Jobs::RunAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(ColumnDef::new(Jobs::LockedBy).string_len(128))
+                    .col(ColumnDef::new(Jobs::LockedAt).timestamp_with_time_zone())
+                    .col(ColumnDef::new(Jobs::LastError).text())
+                    .col(ColumnDef::new(Jobs::Result).json_binary())
+                    .col(ColumnDef::new(Jobs::CreatedBy).integer())
+                    .col(
+                        ColumnDef::new(Jobs::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(Jobs::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(ColumnDef::new(Jobs::FinishedAt).timestamp_with_time_zone())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_jobs_created_by")
+                            .from(Jobs::Table, Jobs::CreatedBy)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_jobs_claim")
+                    .table(Jobs::Table)
+                    .col(Jobs::Queue)
+                    .col(Jobs::Status)
+                    .col(Jobs::RunAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_jobs_kind")
+                    .table(Jobs::Table)
+                    .col(Jobs::Kind)
+                    .to_owned(),
+            )
+            .await?;
+
+        // Partial unique index; sea-query can't express the WHERE clause.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"CREATE UNIQUE INDEX IF NOT EXISTS "uniq_jobs_active_unique_key"
+                ON "jobs" ("unique_key")
+                WHERE "unique_key" IS NOT NULL AND "status" IN ('pending', 'running');"#,
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(JobSchedules::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(JobSchedules::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(JobSchedules::Name)
+                            .string_len(128)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(JobSchedules::Kind)
+                            .string_len(128)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(JobSchedules::Queue)
+                            .string_len(64)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(JobSchedules::Payload)
+                            .json_binary()
+                            .not_null()
+                            .default(Expr::cust("'{}'::jsonb")),
+                    )
+                    .col(
+                        ColumnDef::new(JobSchedules::Cron)
+                            .string_len(128)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(JobSchedules::Enabled)
+                            .boolean()
+                            .not_null()
+                            .default(true),
+                    )
+                    .col(ColumnDef::new(JobSchedules::LastRunAt).timestamp_with_time_zone())
+                    .col(ColumnDef::new(JobSchedules::NextRunAt).timestamp_with_time_zone())
+                    .col(ColumnDef::new(JobSchedules::LastJobId).big_integer())
+                    .col(
+                        ColumnDef::new(JobSchedules::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(JobSchedules::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("uniq_job_schedules_name")
+                    .table(JobSchedules::Table)
+                    .col(JobSchedules::Name)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"INSERT INTO "role_permissions" ("role", "permission")
+                VALUES ('admin', 'job.manage'), ('super-admin', 'job.manage')
+                ON CONFLICT DO NOTHING;"#,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"DELETE FROM "role_permissions" WHERE "permission" = 'job.manage';
+                DELETE FROM "user_permissions" WHERE "permission" = 'job.manage';"#,
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(JobSchedules::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(Jobs::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_type(Type::drop().name(JobStatus::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum Jobs {
+    Table,
+    Id,
+    Queue,
+    Kind,
+    Payload,
+    Status,
+    Attempts,
+    MaxAttempts,
+    UniqueKey,
+    RunAt,
+    LockedBy,
+    LockedAt,
+    LastError,
+    Result,
+    CreatedBy,
+    CreatedAt,
+    UpdatedAt,
+    FinishedAt,
+}
+
+#[derive(Iden)]
+enum JobSchedules {
+    Table,
+    Id,
+    Name,
+    Kind,
+    Queue,
+    Payload,
+    Cron,
+    Enabled,
+    LastRunAt,
+    NextRunAt,
+    LastJobId,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(Iden)]
+enum JobStatus {
+    #[iden = "job_status"]
+    Table,
+    #[iden = "pending"]
+    Pending,
+    #[iden = "running"]
+    Running,
+    #[iden = "succeeded"]
+    Succeeded,
+    #[iden = "dead"]
+    Dead,
+    #[iden = "cancelled"]
+    Cancelled,
+}
+
+#[derive(Iden)]
+enum Users {
+    Table,
+    Id,
+}
@@ -0,0 +1,299 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_query::extension::postgres::Type;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Outbound webhooks:
+/// - `webhooks`: admin-managed endpoints with their signing secret and the
+///   events they subscribe to. `consecutive_failures` counts deliveries that
+///   failed for good in a row; the endpoint is disabled past the limit
+/// - `webhook_deliveries`: one row per event sent to an endpoint, with the
+///   signed body and the last response
+///
+/// Also grants the new `webhook.manage` permission to admins and super admins.
+///
+/// Indexes:
+/// - idx_webhook_deliveries_webhook (webhook_id, created_at)
+/// - idx_webhook_deliveries_event (event)
+/// - idx_webhook_deliveries_status (status)
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(WebhookDeliveryStatus::Table)
+                    .values(vec![
+                        WebhookDeliveryStatus::Pending,
+                        WebhookDeliveryStatus::Succeeded,
+                        WebhookDeliveryStatus::Failed,
+                    ])
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(Webhooks::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Webhooks::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Webhooks::Name).string_len(128).not_null())
+                    .col(ColumnDef::new(Webhooks::Url).string_len(2048).not_null())
+                    .col(ColumnDef::new(Webhooks::Secret).string_len(128).not_null())
+                    .col(
+                        ColumnDef::new(Webhooks::Events)
+                            .array(ColumnType::Text)
+                            .not_null()
+                            .default(Expr::cust("'{}'::text[]")),
+                    )
+                    .col(
+                        ColumnDef::new(Webhooks::Enabled)
+                            .boolean()
+                            .not_null()
+                            .default(true),
+                    )
+                    .col(
+                        ColumnDef::new(Webhooks::ConsecutiveFailures)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(ColumnDef::new(Webhooks::DisabledAt).timestamp_with_time_zone())
+                    .col(ColumnDef::new(Webhooks::DisabledReason).text())
+                    .col(ColumnDef::new(Webhooks::LastDeliveryAt).timestamp_with_time_zone())
+                    .col(ColumnDef::new(Webhooks::LastSuccessAt).timestamp_with_time_zone())
+                    .col(ColumnDef::new(Webhooks::CreatedBy).integer())
+                    .col(
+                        ColumnDef::new(Webhooks::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(Webhooks::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_webhooks_created_by")
+                            .from(Webhooks::Table, Webhooks::CreatedBy)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(WebhookDeliveries::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(WebhookDeliveries::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(WebhookDeliveries::WebhookId)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(WebhookDeliveries::Event)
+                            .string_len(64)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(WebhookDeliveries::EventId)
+                            .string_len(64)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(WebhookDeliveries::Payload)
+                            .json_binary()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(WebhookDeliveries::Status)
+                            .enumeration(
+                                WebhookDeliveryStatus::Table,
+                                [
+                                    WebhookDeliveryStatus::Pending,
+                                    WebhookDeliveryStatus::Succeeded,
+                                    WebhookDeliveryStatus::Failed,
+                                ],
+                            )
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(WebhookDeliveries::Attempts)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(ColumnDef::new(WebhookDeliveries::ResponseStatus).small_integer())
+                    .col(ColumnDef::new(WebhookDeliveries::ResponseBody).text())
+                    .col(ColumnDef::new(WebhookDeliveries::Error).text())
+                    .col(ColumnDef::new(WebhookDeliveries::DurationMs).integer())
+                    .col(ColumnDef::new(WebhookDeliveries::JobId).big_integer())
+                    .col(ColumnDef::new(WebhookDeliveries::RedeliveryOf).big_integer())
+                    .col(
+                        ColumnDef::new(WebhookDeliveries::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(WebhookDeliveries::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(ColumnDef::new(WebhookDeliveries::DeliveredAt).timestamp_with_time_zone())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_webhook_deliveries_webhook")
+                            .from(WebhookDeliveries::Table, WebhookDeliveries::WebhookId)
+                            .to(Webhooks::Table, Webhooks::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_webhook_deliveries_webhook")
+                    .table(WebhookDeliveries::Table)
+                    .col(WebhookDeliveries::WebhookId)
+                    .col(WebhookDeliveries::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_webhook_deliveries_event")
+                    .table(WebhookDeliveries::Table)
+                    .col(WebhookDeliveries::Event)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_webhook_deliveries_status")
+                    .table(WebhookDeliveries::Table)
+                    .col(WebhookDeliveries::Status)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"INSERT INTO "role_permissions" ("role", "permission")
+                VALUES ('admin', 'webhook.manage'), ('super-admin', 'webhook.manage')
+                ON CONFLICT DO NOTHING;"#,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"DELETE FROM "role_permissions" WHERE "permission" = 'webhook.manage';
+                DELETE FROM "user_permissions" WHERE "permission" = 'webhook.manage';"#,
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(WebhookDeliveries::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(Webhooks::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_type(Type::drop().name(WebhookDeliveryStatus::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum Webhooks {
+    Table,
+    Id,
+    Name,
+    Url,
+    Secret,
+    Events,
+    Enabled,
+    ConsecutiveFailures,
+    DisabledAt,
+    DisabledReason,
+    LastDeliveryAt,
+    LastSuccessAt,
+    CreatedBy,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(Iden)]
+enum WebhookDeliveries {
+    Table,
+    Id,
+    WebhookId,
+    Event,
+    EventId,
+    Payload,
+    Status,
+    Attempts,
+    ResponseStatus,
+    ResponseBody,
+    Error,
+    DurationMs,
+    JobId,
+    RedeliveryOf,
+    CreatedAt,
+    UpdatedAt,
+    DeliveredAt,
+}
+
+#[derive(Iden)]
+enum WebhookDeliveryStatus {
+    #[iden = "webhook_delivery_status"]
+    Table,
+    #[iden = "pending"]
+    Pending,
+    #[iden = "succeeded"]
+    Succeeded,
+    #[iden = "failed"]
+    Failed,
+}
+
+#[derive(Iden)]
+enum Users {
+    Table,
+    Id,
+}
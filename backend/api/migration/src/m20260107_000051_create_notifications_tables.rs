@@ -0,0 +1,482 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_query::extension::postgres::Type;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// In-app notifications and what feeds them:
+/// - `post_comments.parent_id`: the comment being replied to
+/// - `comment_likes`: one row per user liking a comment
+/// - `user_follows`: readers following an author
+/// - `notifications`: one row per recipient and group. While unread, a
+///   notification with the same `group_key` (e.g. more likes on the same
+///   comment) is bumped instead of added; `count` says how many it stands for
+/// - `notification_preferences`: per-user channels (in-app, email digest)
+///   and muted types; users without a row get the defaults
+///
+/// Indexes:
+/// - idx_post_comments_parent (parent_id)
+/// - uniq_comment_likes_comment_user (comment_id, user_id)
+/// - idx_comment_likes_user (user_id)
+/// - uniq_user_follows_follower_followed (follower_id, followed_id)
+/// - idx_user_follows_followed (followed_id)
+/// - idx_notifications_user_updated (user_id, updated_at)
+/// - uniq_notifications_unread_group (user_id, group_key) WHERE read_at IS NULL
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(PostComments::Table)
+                    .add_column(ColumnDef::new(PostComments::ParentId).integer())
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk_post_comments_parent")
+                            .from_tbl(PostComments::Table)
+                            .from_col(PostComments::ParentId)
+                            .to_tbl(PostComments::Table)
+                            .to_col(PostComments::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_post_comments_parent")
+                    .table(PostComments::Table)
+                    .col(PostComments::ParentId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(CommentLikes::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(CommentLikes::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(CommentLikes::CommentId).integer().not_null())
+                    .col(ColumnDef::new(CommentLikes::UserId).integer().not_null())
+                    .col(
+                        ColumnDef::new(CommentLikes::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_comment_likes_comment")
+                            .from(CommentLikes::Table, CommentLikes::CommentId)
+                            .to(PostComments::Table, PostComments::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_comment_likes_user")
+                            .from(CommentLikes::Table, CommentLikes::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("uniq_comment_likes_comment_user")
+                    .table(CommentLikes::Table)
+                    .col(CommentLikes::CommentId)
+                    .col(CommentLikes::UserId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_comment_likes_user")
+                    .table(CommentLikes::Table)
+                    .col(CommentLikes::UserId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(UserFollows::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(UserFollows::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(UserFollows::FollowerId).integer().not_null())
+                    .col(ColumnDef::new(UserFollows::FollowedId).integer().not_null())
+                    .col(
+                        ColumnDef::new(UserFollows::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .check(
+                        Expr::col(UserFollows::FollowerId).ne(Expr::col(UserFollows::FollowedId)),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_user_follows_follower")
+                            .from(UserFollows::Table, UserFollows::FollowerId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_user_follows_followed")
+                            .from(UserFollows::Table, UserFollows::FollowedId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("uniq_user_follows_follower_followed")
+                    .table(UserFollows::Table)
+                    .col(UserFollows::FollowerId)
+                    .col(UserFollows::FollowedId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_user_follows_followed")
+                    .table(UserFollows::Table)
+                    .col(UserFollows::FollowedId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(NotificationType::Table)
+                    .values(vec![
+                        NotificationType::CommentReply,
+                        NotificationType::CommentLike,
+                        NotificationType::AuthorPublished,
+                    ])
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(NotificationDigest::Table)
+                    .values(vec![
+                        NotificationDigest::Off,
+                        NotificationDigest::Daily,
+                        NotificationDigest::Weekly,
+                    ])
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(Notifications::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Notifications::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Notifications::UserId).integer().not_null())
+                    .col(
+                        ColumnDef::new(Notifications::Type)
+                            .enumeration(
+                                NotificationType::Table,
+                                [
+                                    NotificationType::CommentReply,
+                                    NotificationType::CommentLike,
+                                    NotificationType::AuthorPublished,
+                                ],
+                            )
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(Notifications::ActorId).integer())
+                    .col(
+                        ColumnDef::new(Notifications::GroupKey)
+                            .string_len(128)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(Notifications::Count)
+                            .integer()
+                            .not_null()
+                            .default(1),
+                    )
+                    .col(
+                        ColumnDef::new(Notifications::Payload)
+                            .json_binary()
+                            .not_null()
+                            .default(Expr::cust("'{}'::jsonb")),
+                    )
+                    .col(ColumnDef::new(Notifications::ReadAt).timestamp_with_time_zone())
+                    .col(ColumnDef::new(Notifications::EmailedAt).timestamp_with_time_zone())
+                    .col(
+                        ColumnDef::new(Notifications::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(Notifications::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_notifications_user")
+                            .from(Notifications::Table, Notifications::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_notifications_actor")
+                            .from(Notifications::Table, Notifications::ActorId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_notifications_user_updated")
+                    .table(Notifications::Table)
+                    .col(Notifications::UserId)
+                    .col(Notifications::UpdatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        // Partial, so read notifications don't block a new group.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"CREATE UNIQUE INDEX "uniq_notifications_unread_group"
+                ON "notifications" ("user_id", "group_key") WHERE "read_at" IS NULL;"#,
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(NotificationPreferences::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(NotificationPreferences::UserId)
+                            .integer()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(NotificationPreferences::InApp)
+                            .boolean()
+                            .not_null()
+                            .default(true),
+                    )
+                    .col(
+                        ColumnDef::new(NotificationPreferences::EmailDigest)
+                            .enumeration(
+                                NotificationDigest::Table,
+                                [
+                                    NotificationDigest::Off,
+                                    NotificationDigest::Daily,
+                                    NotificationDigest::Weekly,
+                                ],
+                            )
+                            .not_null()
+                            .default(Expr::cust("'off'::notification_digest")),
+                    )
+                    .col(
+                        ColumnDef::new(NotificationPreferences::MutedTypes)
+                            .array(ColumnType::Text)
+                            .not_null()
+                            .default(Expr::cust("'{}'::text[]")),
+                    )
+                    .col(
+                        ColumnDef::new(NotificationPreferences::LastDigestAt)
+                            .timestamp_with_time_zone(),
+                    )
+                    .col(
+                        ColumnDef::new(NotificationPreferences::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_notification_preferences_user")
+                            .from(
+                                NotificationPreferences::Table,
+                                NotificationPreferences::UserId,
+                            )
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(NotificationPreferences::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(Notifications::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_type(Type::drop().name(NotificationDigest::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_type(Type::drop().name(NotificationType::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(UserFollows::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(CommentLikes::Table).to_owned())
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(PostComments::Table)
+                    .drop_foreign_key(Alias::new("fk_post_comments_parent"))
+                    .drop_column(PostComments::ParentId)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum PostComments {
+    Table,
+    Id,
+    ParentId,
+}
+
+#[derive(Iden)]
+enum CommentLikes {
+    Table,
+    Id,
+    CommentId,
+    UserId,
+    CreatedAt,
+}
+
+#[derive(Iden)]
+enum UserFollows {
+    Table,
+    Id,
+    FollowerId,
+    FollowedId,
+    CreatedAt,
+}
+
+#[derive(Iden)]
+enum Notifications {
+    Table,
+    Id,
+    UserId,
+    Type,
+    ActorId,
+    GroupKey,
+    Count,
+    Payload,
+    ReadAt,
+    EmailedAt,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(Iden)]
+enum NotificationPreferences {
+    Table,
+    UserId,
+    InApp,
+    EmailDigest,
+    MutedTypes,
+    LastDigestAt,
+    UpdatedAt,
+}
+
+#[derive(Iden)]
+enum NotificationType {
+    #[iden = "notification_type"]
+    Table,
+    #[iden = "comment_reply"]
+    CommentReply,
+    #[iden = "comment_like"]
+    CommentLike,
+    #[iden = "author_published"]
+    AuthorPublished,
+}
+
+#[derive(Iden)]
+enum NotificationDigest {
+    #[iden = "notification_digest"]
+    Table,
+    #[iden = "off"]
+    Off,
+    #[iden = "daily"]
+    Daily,
+    #[iden = "weekly"]
+    Weekly,
+}
+
+#[derive(Iden)]
+enum Users {
+    Table,
+    Id,
+}
@@ -0,0 +1,114 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Post slugs are unique per locale, so their redirects are too:
+/// - slug_redirects.locale (varchar(16), default the `DEFAULT_LOCALE`
+///   setting): locale the old slug was served in. Post redirects are
+///   backfilled from their target; other entities share one slug namespace
+///   and keep the default
+///
+/// Indexes:
+/// - uniq_slug_redirects_entity_locale_from_slug (entity, locale, from_slug),
+///   replacing uniq_slug_redirects_entity_from_slug
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(SlugRedirects::Table)
+                    .add_column(
+                        ColumnDef::new(SlugRedirects::Locale)
+                            .string_len(16)
+                            .not_null()
+                            .default(crate::default_locale()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            r#"UPDATE "slug_redirects" SET "locale" = "posts"."locale"
+               FROM "posts"
+               WHERE "slug_redirects"."entity" = 'post'
+                 AND "posts"."id" = "slug_redirects"."target_id";"#,
+        )
+        .await?;
+
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("uniq_slug_redirects_entity_from_slug")
+                    .table(SlugRedirects::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("uniq_slug_redirects_entity_locale_from_slug")
+                    .table(SlugRedirects::Table)
+                    .col(SlugRedirects::Entity)
+                    .col(SlugRedirects::Locale)
+                    .col(SlugRedirects::FromSlug)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("uniq_slug_redirects_entity_locale_from_slug")
+                    .table(SlugRedirects::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        // Keep the newest redirect where locales shared an old slug
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            r#"DELETE FROM "slug_redirects" AS "older"
+               USING "slug_redirects" AS "newer"
+               WHERE "older"."entity" = "newer"."entity"
+                 AND "older"."from_slug" = "newer"."from_slug"
+                 AND ("older"."updated_at", "older"."id") < ("newer"."updated_at", "newer"."id");"#,
+        )
+        .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("uniq_slug_redirects_entity_from_slug")
+                    .table(SlugRedirects::Table)
+                    .col(SlugRedirects::Entity)
+                    .col(SlugRedirects::FromSlug)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(SlugRedirects::Table)
+                    .drop_column(SlugRedirects::Locale)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum SlugRedirects {
+    Table,
+    Entity,
+    FromSlug,
+    Locale,
+}
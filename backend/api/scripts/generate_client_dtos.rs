@@ -0,0 +1,14 @@
+//! Regenerates `crates/ruxlog-client/src/dto/generated.rs` from the OpenAPI
+//! document. Run it after changing a schema the client uses.
+
+use std::path::Path;
+
+use ruxlog::modules::docs_v1::{client_codegen, spec};
+
+fn main() {
+    let path =
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("crates/ruxlog-client/src/dto/generated.rs");
+    let source = client_codegen::client_dtos(&spec::document());
+    std::fs::write(&path, source).expect("write generated DTOs");
+    println!("wrote {}", path.display());
+}
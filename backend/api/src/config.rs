@@ -2,4 +2,7 @@ pub mod body_limits {
     pub const DEFAULT: usize = 64 * 1024; // 64 KiB
     pub const POST: usize = 256 * 1024; // 256 KiB
     pub const MEDIA: usize = 2 * 1024 * 1024; // 2 MiB
+    pub const IMPORT: usize = 20 * 1024 * 1024; // 20 MiB
+    pub const BLOG_IMPORT: usize = 64 * 1024 * 1024; // 64 MiB
+    pub const RESTORE: usize = 256 * 1024 * 1024; // 256 MiB
 }
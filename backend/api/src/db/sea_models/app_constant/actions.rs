@@ -10,31 +10,33 @@ impl Entity {
         Entity::find().filter(Column::Key.eq(key)).one(db).await
     }
 
+    /// Insert `write` unless the key already exists; returns the stored row
+    /// and whether it was created.
     pub async fn ensure_exists(
         db: &DatabaseConnection,
-        key: &str,
-        value: &str,
-        value_type: Option<String>,
-        is_sensitive: bool,
-        source: &str,
-    ) -> Result<Model, DbErr> {
-        if let Some(existing) = Self::find_by_key(db, key).await? {
-            return Ok(existing);
+        write: ConstantWrite,
+    ) -> Result<(Model, bool), DbErr> {
+        if let Some(existing) = Self::find_by_key(db, &write.key).await? {
+            return Ok((existing, false));
         }
 
+        let key = write.key.clone();
         let active = ActiveModel {
-            key: Set(key.to_string()),
-            value: Set(value.to_string()),
-            value_type: Set(value_type),
-            is_sensitive: Set(is_sensitive),
-            source: Set(source.to_string()),
+            key: Set(write.key),
+            value: Set(write.value),
+            value_type: Set(write.value_type),
+            description: Set(write.description),
+            is_sensitive: Set(write.is_sensitive),
+            is_encrypted: Set(write.is_encrypted),
+            source: Set(write.source),
+            updated_by: Set(write.updated_by),
             ..Default::default()
         };
 
         match active.insert(db).await {
-            Ok(model) => Ok(model),
-            Err(DbErr::Exec(exec_err)) => match Self::find_by_key(db, key).await? {
-                Some(existing) => Ok(existing),
+            Ok(model) => Ok((model, true)),
+            Err(DbErr::Exec(exec_err)) => match Self::find_by_key(db, &key).await? {
+                Some(existing) => Ok((existing, false)),
                 None => Err(DbErr::Exec(exec_err)),
             },
             Err(err) => Err(err),
@@ -43,39 +45,53 @@ impl Entity {
 
     pub async fn upsert_value(
         db: &DatabaseConnection,
-        key: &str,
-        value: &str,
-        value_type: Option<String>,
-        description: Option<String>,
-        is_sensitive: bool,
-        source: &str,
-        updated_by: Option<i32>,
+        write: ConstantWrite,
     ) -> Result<Model, DbErr> {
-        if let Some(existing) = Self::find_by_key(db, key).await? {
+        if let Some(existing) = Self::find_by_key(db, &write.key).await? {
             let mut active: ActiveModel = existing.into();
-            active.value = Set(value.to_string());
-            active.value_type = Set(value_type);
-            active.description = Set(description);
-            active.is_sensitive = Set(is_sensitive);
-            active.source = Set(source.to_string());
-            active.updated_by = Set(updated_by);
+            active.value = Set(write.value);
+            active.value_type = Set(write.value_type);
+            active.description = Set(write.description);
+            active.is_sensitive = Set(write.is_sensitive);
+            active.is_encrypted = Set(write.is_encrypted);
+            active.source = Set(write.source);
+            active.updated_by = Set(write.updated_by);
             active.updated_at = Set(chrono::Utc::now().fixed_offset());
             active.update(db).await
         } else {
             let active = ActiveModel {
-                key: Set(key.to_string()),
-                value: Set(value.to_string()),
-                value_type: Set(value_type),
-                description: Set(description),
-                is_sensitive: Set(is_sensitive),
-                source: Set(source.to_string()),
-                updated_by: Set(updated_by),
+                key: Set(write.key),
+                value: Set(write.value),
+                value_type: Set(write.value_type),
+                description: Set(write.description),
+                is_sensitive: Set(write.is_sensitive),
+                is_encrypted: Set(write.is_encrypted),
+                source: Set(write.source),
+                updated_by: Set(write.updated_by),
                 ..Default::default()
             };
             active.insert(db).await
         }
     }
 
+    /// Replace the stored value only, e.g. when sealing or rewrapping it.
+    /// `updated_at` is left alone since the setting itself didn't change.
+    pub async fn replace_stored_value(
+        db: &DatabaseConnection,
+        existing: Model,
+        value: String,
+        is_encrypted: bool,
+    ) -> Result<Model, DbErr> {
+        let mut active: ActiveModel = existing.into();
+        active.value = Set(value);
+        active.is_encrypted = Set(is_encrypted);
+        active.update(db).await
+    }
+
+    pub async fn all(db: &DatabaseConnection) -> Result<Vec<Model>, DbErr> {
+        Entity::find().order_by_asc(Column::Key).all(db).await
+    }
+
     pub async fn delete_by_key(db: &DatabaseConnection, key: &str) -> Result<u64, DbErr> {
         let res = Entity::delete_many()
             .filter(Column::Key.eq(key))
@@ -84,6 +100,16 @@ impl Entity {
         Ok(res.rows_affected)
     }
 
+    /// Delete rows copied from the environment whose key isn't in `keep`.
+    pub async fn delete_env_copies(db: &DatabaseConnection, keep: &[&str]) -> Result<u64, DbErr> {
+        let res = Entity::delete_many()
+            .filter(Column::Source.eq("env"))
+            .filter(Column::Key.is_not_in(keep.iter().copied()))
+            .exec(db)
+            .await?;
+        Ok(res.rows_affected)
+    }
+
     pub async fn list(
         db: &DatabaseConnection,
         page: u64,
@@ -138,6 +164,7 @@ impl Entity {
             let meta = serde_json::json!({
                 "value_type": item.value_type,
                 "is_sensitive": item.is_sensitive,
+                "is_encrypted": item.is_encrypted,
                 "updated_at": item.updated_at,
             })
             .to_string();
@@ -1,4 +1,6 @@
 pub mod actions;
 pub mod model;
+pub mod slice;
 
 pub use model::*;
+pub use slice::*;
@@ -1,9 +1,11 @@
+use schemars::JsonSchema;
 use sea_orm::entity::prelude::*;
 use sea_orm::Set;
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, JsonSchema)]
 #[sea_orm(table_name = "app_constants")]
+#[schemars(rename = "AppConstant")]
 pub struct Model {
     #[sea_orm(primary_key)]
     pub id: i32,
@@ -21,6 +23,10 @@ pub struct Model {
 
     pub is_sensitive: bool,
 
+    /// `value` is sealed with `services::settings::crypto`.
+    #[serde(default)]
+    pub is_encrypted: bool,
+
     pub source: String,
 
     #[sea_orm(nullable)]
@@ -33,6 +39,18 @@ pub struct Model {
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {}
 
+impl Model {
+    /// The constant with its value masked when sensitive, for API responses.
+    pub fn redacted(mut self) -> Self {
+        if self.is_sensitive {
+            self.value = REDACTED.to_string();
+        }
+        self
+    }
+}
+
+pub const REDACTED: &str = "********";
+
 impl ActiveModelBehavior for ActiveModel {
     fn new() -> Self {
         Self {
@@ -0,0 +1,16 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// A constant as written to `app_constants`; `value` is already sealed when
+/// `is_encrypted` is set.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ConstantWrite {
+    pub key: String,
+    pub value: String,
+    pub value_type: Option<String>,
+    pub description: Option<String>,
+    pub is_sensitive: bool,
+    pub is_encrypted: bool,
+    pub source: String,
+    pub updated_by: Option<i32>,
+}
@@ -0,0 +1,68 @@
+use sea_orm::{entity::prelude::*, QueryOrder, QuerySelect, Set};
+
+use crate::error::DbResult;
+
+use super::super::user;
+use super::*;
+
+/// Actions for the app constant change log:
+/// - Record a change, dropping values of sensitive keys
+/// - List changes, newest first, optionally for one key
+impl Entity {
+    pub async fn record(conn: &DbConn, change: NewConstantChange) -> DbResult<Model> {
+        let (old_value, new_value) = if change.is_sensitive {
+            (None, None)
+        } else {
+            (change.old_value, change.new_value)
+        };
+        let active = ActiveModel {
+            key: Set(change.key),
+            action: Set(change.action.as_str().to_string()),
+            old_value: Set(old_value),
+            new_value: Set(new_value),
+            is_sensitive: Set(change.is_sensitive),
+            changed_by: Set(change.changed_by),
+            created_at: Set(chrono::Utc::now().fixed_offset()),
+            ..Default::default()
+        };
+        Ok(active.insert(conn).await?)
+    }
+
+    pub async fn list(
+        conn: &DbConn,
+        key: Option<String>,
+        page: u64,
+        per_page: u64,
+    ) -> DbResult<(Vec<ConstantChangeEntry>, u64)> {
+        let mut query = Entity::find();
+        if let Some(key) = key {
+            query = query.filter(Column::Key.eq(key));
+        }
+
+        let total = query.clone().count(conn).await?;
+        let rows = query
+            .find_also_related(user::Entity)
+            .order_by_desc(Column::CreatedAt)
+            .order_by_desc(Column::Id)
+            .offset(page.saturating_sub(1) * per_page)
+            .limit(per_page)
+            .all(conn)
+            .await?;
+
+        let entries = rows
+            .into_iter()
+            .map(|(change, user)| ConstantChangeEntry {
+                id: change.id,
+                key: change.key,
+                action: change.action,
+                old_value: change.old_value,
+                new_value: change.new_value,
+                is_sensitive: change.is_sensitive,
+                changed_by: change.changed_by,
+                changed_by_name: user.map(|u| u.name),
+                created_at: change.created_at,
+            })
+            .collect();
+        Ok((entries, total))
+    }
+}
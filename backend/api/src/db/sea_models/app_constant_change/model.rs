@@ -0,0 +1,40 @@
+use schemars::JsonSchema;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// One change to an app constant. Values are only kept for non-sensitive
+/// keys.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize, JsonSchema)]
+#[sea_orm(table_name = "app_constant_changes")]
+#[schemars(rename = "AppConstantChange")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub key: String,
+    /// `create`, `update`, `delete` or `import`.
+    pub action: String,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+    pub is_sensitive: bool,
+    pub changed_by: Option<i32>,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::super::user::Entity",
+        from = "Column::ChangedBy",
+        to = "super::super::user::Column::Id",
+        on_delete = "SetNull"
+    )]
+    ChangedBy,
+}
+
+impl Related<super::super::user::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ChangedBy.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
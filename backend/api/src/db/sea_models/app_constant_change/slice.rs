@@ -0,0 +1,47 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ConstantAction {
+    Create,
+    Update,
+    Delete,
+    /// Copied from the environment at startup.
+    Import,
+}
+
+impl ConstantAction {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Create => "create",
+            Self::Update => "update",
+            Self::Delete => "delete",
+            Self::Import => "import",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct NewConstantChange {
+    pub key: String,
+    pub action: ConstantAction,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+    pub is_sensitive: bool,
+    pub changed_by: Option<i32>,
+}
+
+/// A change as listed, with the name of whoever made it.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ConstantChangeEntry {
+    pub id: i32,
+    pub key: String,
+    pub action: String,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+    pub is_sensitive: bool,
+    pub changed_by: Option<i32>,
+    pub changed_by_name: Option<String>,
+    pub created_at: sea_orm::prelude::DateTimeWithTimeZone,
+}
@@ -0,0 +1,130 @@
+use sea_orm::{entity::prelude::*, Condition, QueryOrder, QuerySelect, Select, Set};
+
+use crate::error::DbResult;
+
+use super::super::user;
+use super::*;
+
+/// Actions for the audit log:
+/// - Record an entry
+/// - List, view and export entries, newest first
+/// - Purge entries past the retention period
+impl Entity {
+    pub const PER_PAGE: u64 = 50;
+
+    pub async fn record(conn: &DbConn, entry: NewAuditLog) -> DbResult<Model> {
+        let active = ActiveModel {
+            actor_id: Set(entry.actor_id),
+            actor_role: Set(entry.actor_role),
+            action: Set(entry.action),
+            entity_type: Set(entry.entity_type),
+            entity_id: Set(entry.entity_id),
+            before: Set(entry.before),
+            after: Set(entry.after),
+            diff: Set(entry.diff),
+            method: Set(entry.method),
+            path: Set(entry.path),
+            status: Set(entry.status),
+            ip: Set(entry.ip),
+            request_id: Set(entry.request_id),
+            created_at: Set(chrono::Utc::now().fixed_offset()),
+            ..Default::default()
+        };
+        Ok(active.insert(conn).await?)
+    }
+
+    pub async fn find_with_query(
+        conn: &DbConn,
+        query: AuditLogQuery,
+    ) -> DbResult<(Vec<AuditLogEntry>, u64)> {
+        let select = Self::filtered(&query);
+        let total = select.clone().count(conn).await?;
+
+        let page = match query.page {
+            Some(p) if p > 0 => p,
+            _ => 1,
+        };
+        let rows = select
+            .find_also_related(user::Entity)
+            .offset((page - 1) * Self::PER_PAGE)
+            .limit(Self::PER_PAGE)
+            .all(conn)
+            .await?;
+        Ok((rows.into_iter().map(into_entry).collect(), total))
+    }
+
+    pub async fn find_entry(conn: &DbConn, id: i64) -> DbResult<Option<AuditLogEntry>> {
+        let row = Entity::find_by_id(id)
+            .find_also_related(user::Entity)
+            .one(conn)
+            .await?;
+        Ok(row.map(into_entry))
+    }
+
+    /// Every entry matching `query` (its page is ignored), up to `limit`.
+    pub async fn export(
+        conn: &DbConn,
+        query: AuditLogQuery,
+        limit: u64,
+    ) -> DbResult<Vec<AuditLogEntry>> {
+        let rows = Self::filtered(&query)
+            .find_also_related(user::Entity)
+            .limit(limit)
+            .all(conn)
+            .await?;
+        Ok(rows.into_iter().map(into_entry).collect())
+    }
+
+    /// Delete entries older than `cutoff`; returns how many went.
+    pub async fn purge_before(conn: &DbConn, cutoff: DateTimeWithTimeZone) -> DbResult<u64> {
+        let result = Entity::delete_many()
+            .filter(Column::CreatedAt.lt(cutoff))
+            .exec(conn)
+            .await?;
+        Ok(result.rows_affected)
+    }
+
+    fn filtered(query: &AuditLogQuery) -> Select<Entity> {
+        let mut condition = Condition::all();
+        if let Some(actor_id) = query.actor_id {
+            condition = condition.add(Column::ActorId.eq(actor_id));
+        }
+        if let Some(action) = &query.action {
+            condition = if action.ends_with('.') {
+                condition.add(Column::Action.starts_with(action))
+            } else {
+                condition.add(Column::Action.eq(action))
+            };
+        }
+        if let Some(entity_type) = &query.entity_type {
+            condition = condition.add(Column::EntityType.eq(entity_type));
+        }
+        if let Some(entity_id) = &query.entity_id {
+            condition = condition.add(Column::EntityId.eq(entity_id));
+        }
+        if let Some(request_id) = &query.request_id {
+            condition = condition.add(Column::RequestId.eq(request_id));
+        }
+        if let Some(ip) = &query.ip {
+            condition = condition.add(Column::Ip.eq(ip));
+        }
+        if let Some(after) = query.created_at_gt {
+            condition = condition.add(Column::CreatedAt.gt(after));
+        }
+        if let Some(before) = query.created_at_lt {
+            condition = condition.add(Column::CreatedAt.lt(before));
+        }
+
+        Entity::find()
+            .filter(condition)
+            .order_by_desc(Column::CreatedAt)
+            .order_by_desc(Column::Id)
+    }
+}
+
+fn into_entry((log, actor): (Model, Option<user::Model>)) -> AuditLogEntry {
+    AuditLogEntry {
+        log,
+        actor_name: actor.map(|u| u.name),
+    }
+}
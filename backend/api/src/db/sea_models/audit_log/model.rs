@@ -0,0 +1,55 @@
+use schemars::JsonSchema;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::db::sea_models::user::UserRole;
+
+/// One privileged action, written by the `audit` middleware.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize, JsonSchema)]
+#[sea_orm(table_name = "audit_logs")]
+#[schemars(rename = "AuditLog")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i64,
+    /// `None` once the actor's account is deleted.
+    pub actor_id: Option<i32>,
+    /// Role the actor held at the time.
+    pub actor_role: Option<UserRole>,
+    /// Dotted name such as `user.update`, or `METHOD /matched/path` when the
+    /// handler didn't name it.
+    pub action: String,
+    pub entity_type: Option<String>,
+    pub entity_id: Option<String>,
+    #[sea_orm(column_type = "JsonBinary", nullable)]
+    pub before: Option<Json>,
+    #[sea_orm(column_type = "JsonBinary", nullable)]
+    pub after: Option<Json>,
+    /// Changed fields as `{ "field": { "before": .., "after": .. } }`.
+    #[sea_orm(column_type = "JsonBinary", nullable)]
+    pub diff: Option<Json>,
+    pub method: String,
+    pub path: String,
+    pub status: i16,
+    pub ip: Option<String>,
+    pub request_id: Option<String>,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::super::user::Entity",
+        from = "Column::ActorId",
+        to = "super::super::user::Column::Id",
+        on_delete = "SetNull"
+    )]
+    Actor,
+}
+
+impl Related<super::super::user::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Actor.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,46 @@
+use schemars::JsonSchema;
+use sea_orm::prelude::{DateTimeWithTimeZone, Json};
+use serde::{Deserialize, Serialize};
+
+use crate::db::sea_models::user::UserRole;
+
+/// Entry to write; see `audit_log::Model`.
+#[derive(Clone, Debug)]
+pub struct NewAuditLog {
+    pub actor_id: Option<i32>,
+    pub actor_role: Option<UserRole>,
+    pub action: String,
+    pub entity_type: Option<String>,
+    pub entity_id: Option<String>,
+    pub before: Option<Json>,
+    pub after: Option<Json>,
+    pub diff: Option<Json>,
+    pub method: String,
+    pub path: String,
+    pub status: i16,
+    pub ip: Option<String>,
+    pub request_id: Option<String>,
+}
+
+/// Filters for listing and exporting entries
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema)]
+pub struct AuditLogQuery {
+    pub page: Option<u64>,
+    pub actor_id: Option<i32>,
+    /// Exact action, or a prefix when it ends with `.` (`user.`)
+    pub action: Option<String>,
+    pub entity_type: Option<String>,
+    pub entity_id: Option<String>,
+    pub request_id: Option<String>,
+    pub ip: Option<String>,
+    pub created_at_gt: Option<DateTimeWithTimeZone>,
+    pub created_at_lt: Option<DateTimeWithTimeZone>,
+}
+
+/// An entry as listed, with the actor's name.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct AuditLogEntry {
+    #[serde(flatten)]
+    pub log: super::Model,
+    pub actor_name: Option<String>,
+}
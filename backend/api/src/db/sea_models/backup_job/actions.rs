@@ -0,0 +1,152 @@
+use sea_orm::{
+    entity::prelude::*, ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder,
+    QuerySelect, Set,
+};
+
+use crate::error::DbResult;
+
+use super::*;
+
+/// Actions for backup jobs:
+/// - Create a pending job and move it through running -> completed/failed
+/// - Paginated listing and retention helpers for scheduled exports
+impl Entity {
+    pub const PER_PAGE: u64 = 20;
+
+    pub async fn create<C: ConnectionTrait>(conn: &C, new_job: NewBackupJob) -> DbResult<Model> {
+        let now = chrono::Utc::now().fixed_offset();
+
+        let active = ActiveModel {
+            kind: Set(new_job.kind),
+            status: Set(BackupJobStatus::Pending),
+            include_media: Set(new_job.include_media),
+            encrypted: Set(new_job.encrypted),
+            scheduled: Set(new_job.scheduled),
+            format_version: Set(new_job.format_version),
+            source_job_id: Set(new_job.source_job_id),
+            requested_by: Set(new_job.requested_by),
+            created_at: Set(now),
+            updated_at: Set(now),
+            ..Default::default()
+        };
+
+        let model = active.insert(conn).await?;
+        Ok(model)
+    }
+
+    pub async fn find_by_id(conn: &DbConn, job_id: i32) -> DbResult<Option<Model>> {
+        let model = <Self as EntityTrait>::find_by_id(job_id).one(conn).await?;
+        Ok(model)
+    }
+
+    pub async fn mark_running(conn: &DbConn, job_id: i32) -> DbResult<()> {
+        let now = chrono::Utc::now().fixed_offset();
+        Entity::update_many()
+            .col_expr(Column::Status, Expr::value(BackupJobStatus::Running))
+            .col_expr(Column::StartedAt, Expr::value(now))
+            .col_expr(Column::UpdatedAt, Expr::value(now))
+            .filter(Column::Id.eq(job_id))
+            .exec(conn)
+            .await?;
+        Ok(())
+    }
+
+    /// Record a finished export (archive location, size and checksum).
+    pub async fn mark_exported(
+        conn: &DbConn,
+        job_id: i32,
+        export: CompletedExport,
+    ) -> DbResult<Model> {
+        let now = chrono::Utc::now().fixed_offset();
+        let active = ActiveModel {
+            id: Set(job_id),
+            status: Set(BackupJobStatus::Completed),
+            object_key: Set(Some(export.object_key)),
+            size_bytes: Set(Some(export.size_bytes)),
+            checksum: Set(Some(export.checksum)),
+            report: Set(Some(export.report)),
+            completed_at: Set(Some(now)),
+            updated_at: Set(now),
+            ..Default::default()
+        };
+        let model = active.update(conn).await?;
+        Ok(model)
+    }
+
+    /// Record a finished restore along with its report.
+    pub async fn mark_restored(
+        conn: &DbConn,
+        job_id: i32,
+        report: serde_json::Value,
+    ) -> DbResult<Model> {
+        let now = chrono::Utc::now().fixed_offset();
+        let active = ActiveModel {
+            id: Set(job_id),
+            status: Set(BackupJobStatus::Completed),
+            report: Set(Some(report)),
+            completed_at: Set(Some(now)),
+            updated_at: Set(now),
+            ..Default::default()
+        };
+        let model = active.update(conn).await?;
+        Ok(model)
+    }
+
+    pub async fn mark_failed(conn: &DbConn, job_id: i32, error: String) -> DbResult<()> {
+        let now = chrono::Utc::now().fixed_offset();
+        Entity::update_many()
+            .col_expr(Column::Status, Expr::value(BackupJobStatus::Failed))
+            .col_expr(Column::Error, Expr::value(error))
+            .col_expr(Column::CompletedAt, Expr::value(now))
+            .col_expr(Column::UpdatedAt, Expr::value(now))
+            .filter(Column::Id.eq(job_id))
+            .exec(conn)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn list(conn: &DbConn, query: BackupJobQuery) -> DbResult<(Vec<Model>, u64)> {
+        let page = match query.page {
+            Some(p) if p > 0 => p,
+            _ => 1,
+        };
+
+        let mut select = Entity::find();
+        if let Some(kind) = query.kind {
+            select = select.filter(Column::Kind.eq(kind));
+        }
+        if let Some(status) = query.status {
+            select = select.filter(Column::Status.eq(status));
+        }
+
+        let paginator = select
+            .order_by_desc(Column::CreatedAt)
+            .order_by_desc(Column::Id)
+            .paginate(conn, Self::PER_PAGE);
+        let total = paginator.num_items().await?;
+        let items = paginator.fetch_page(page - 1).await?;
+
+        Ok((items, total))
+    }
+
+    /// Completed scheduled exports beyond the newest `retain`, oldest first.
+    pub async fn expired_scheduled_exports(conn: &DbConn, retain: u64) -> DbResult<Vec<Model>> {
+        let items = Entity::find()
+            .filter(Column::Kind.eq(BackupJobKind::Export))
+            .filter(Column::Scheduled.eq(true))
+            .filter(Column::Status.eq(BackupJobStatus::Completed))
+            .order_by_desc(Column::CreatedAt)
+            .order_by_desc(Column::Id)
+            .offset(retain)
+            .all(conn)
+            .await?;
+        Ok(items)
+    }
+
+    pub async fn delete_by_id(conn: &DbConn, job_id: i32) -> DbResult<()> {
+        <Self as EntityTrait>::delete_by_id(job_id)
+            .exec(conn)
+            .await?;
+        Ok(())
+    }
+}
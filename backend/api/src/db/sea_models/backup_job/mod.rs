@@ -0,0 +1,6 @@
+pub mod actions;
+pub mod model;
+pub mod slice;
+
+pub use model::{ActiveModel, BackupJobKind, BackupJobStatus, Column, Entity, Model, Relation};
+pub use slice::*;
@@ -0,0 +1,119 @@
+use schemars::JsonSchema;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    EnumIter,
+    DeriveActiveEnum,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "backup_job_kind")]
+#[serde(rename_all = "lowercase")]
+pub enum BackupJobKind {
+    #[sea_orm(string_value = "export")]
+    Export,
+    #[sea_orm(string_value = "restore")]
+    Restore,
+}
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    EnumIter,
+    DeriveActiveEnum,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "backup_job_status")]
+#[serde(rename_all = "lowercase")]
+pub enum BackupJobStatus {
+    #[sea_orm(string_value = "pending")]
+    Pending,
+    #[sea_orm(string_value = "running")]
+    Running,
+    #[sea_orm(string_value = "completed")]
+    Completed,
+    #[sea_orm(string_value = "failed")]
+    Failed,
+}
+
+impl fmt::Display for BackupJobStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::Pending => "pending",
+            Self::Running => "running",
+            Self::Completed => "completed",
+            Self::Failed => "failed",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize, JsonSchema)]
+#[sea_orm(table_name = "backup_jobs")]
+#[schemars(rename = "BackupJob")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+
+    pub kind: BackupJobKind,
+    pub status: BackupJobStatus,
+
+    pub include_media: bool,
+    pub encrypted: bool,
+    /// Created by the backup schedule rather than a user request.
+    pub scheduled: bool,
+    pub format_version: i32,
+
+    /// Location of the archive in object storage (exports only).
+    #[serde(skip_serializing)]
+    #[schemars(skip)]
+    pub object_key: Option<String>,
+    pub size_bytes: Option<i64>,
+    /// SHA-256 of the stored archive bytes.
+    pub checksum: Option<String>,
+    /// Export job a restore was started from, if any.
+    pub source_job_id: Option<i32>,
+
+    #[sea_orm(column_type = "JsonBinary", nullable)]
+    pub report: Option<Json>,
+    pub error: Option<String>,
+
+    pub requested_by: Option<i32>,
+
+    pub created_at: DateTimeWithTimeZone,
+    pub started_at: Option<DateTimeWithTimeZone>,
+    pub completed_at: Option<DateTimeWithTimeZone>,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::super::user::Entity",
+        from = "Column::RequestedBy",
+        to = "super::super::user::Column::Id",
+        on_delete = "SetNull"
+    )]
+    User,
+}
+
+impl Related<super::super::user::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
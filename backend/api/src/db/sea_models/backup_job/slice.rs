@@ -0,0 +1,32 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::model::{BackupJobKind, BackupJobStatus};
+
+/// Payload to create a backup job row before the work is spawned.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct NewBackupJob {
+    pub kind: BackupJobKind,
+    pub include_media: bool,
+    pub encrypted: bool,
+    pub scheduled: bool,
+    pub format_version: i32,
+    pub source_job_id: Option<i32>,
+    pub requested_by: Option<i32>,
+}
+
+/// Result of a finished export, recorded on the job row.
+#[derive(Clone, Debug)]
+pub struct CompletedExport {
+    pub object_key: String,
+    pub size_bytes: i64,
+    pub checksum: String,
+    pub report: serde_json::Value,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema)]
+pub struct BackupJobQuery {
+    pub page: Option<u64>,
+    pub kind: Option<BackupJobKind>,
+    pub status: Option<BackupJobStatus>,
+}
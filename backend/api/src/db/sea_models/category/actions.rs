@@ -1,11 +1,13 @@
 use crate::error::{DbResult, ErrorCode, ErrorResponse};
 use sea_orm::{
-    entity::prelude::*, prelude::Expr, sea_query::Alias, Condition, JoinType, Order, QueryOrder,
-    QuerySelect, Set, TransactionTrait,
+    entity::prelude::*, prelude::Expr, sea_query::Alias, Condition, DatabaseBackend,
+    FromQueryResult, JoinType, Order, PaginatorTrait, QueryOrder, QuerySelect, Set, Statement,
+    TransactionTrait,
 };
 
 use super::*;
 use crate::db::sea_models::media_usage::EntityType;
+use crate::db::sea_models::slug_redirect::{self, RedirectEntity};
 use crate::utils::color::{derive_text_color, DEFAULT_BG_COLOR};
 
 impl Entity {
@@ -65,6 +67,7 @@ impl Entity {
                 color: cat.color,
                 text_color: cat.text_color,
                 is_active: cat.is_active,
+                locale: cat.locale,
                 created_at: cat.created_at,
                 updated_at: cat.updated_at,
                 cover: cat.cover_id.and_then(|id| media_map.get(&id).cloned()),
@@ -79,6 +82,10 @@ impl Entity {
         conn: &DbConn,
         new_category: NewCategory,
     ) -> DbResult<CategoryWithRelations> {
+        if let Some(parent_id) = new_category.parent_id {
+            Self::validate_parent(conn, None, parent_id).await?;
+        }
+
         let txn = conn.begin().await?;
 
         let now = chrono::Utc::now().fixed_offset();
@@ -101,6 +108,7 @@ impl Entity {
             color: Set(color),
             text_color: Set(text_color),
             is_active: Set(is_active),
+            locale: Set(new_category.locale),
             created_at: Set(now),
             updated_at: Set(now),
             ..Default::default()
@@ -150,8 +158,13 @@ impl Entity {
         };
 
         if let Some(category_model) = category {
+            if let Some(Some(parent_id)) = update_category.parent_id {
+                Self::validate_parent(conn, Some(category_id), parent_id).await?;
+            }
+
             let txn = conn.begin().await?;
 
+            let old_slug = category_model.slug.clone();
             let old_cover_id = category_model.cover_id;
             let old_logo_id = category_model.logo_id;
 
@@ -201,9 +214,24 @@ impl Entity {
                 category_active.is_active = Set(is_active);
             }
 
+            if let Some(locale) = update_category.locale {
+                category_active.locale = Set(locale);
+            }
+
             category_active.updated_at = Set(chrono::Utc::now().fixed_offset());
 
-            category_active.update(&txn).await?;
+            let updated = category_active.update(&txn).await?;
+
+            slug_redirect::Entity::record_slug_change(
+                &txn,
+                RedirectEntity::Category,
+                category_id,
+                None,
+                &old_slug,
+                None,
+                &updated.slug,
+            )
+            .await?;
 
             if update_category.cover_id.is_some() && old_cover_id != new_cover_id {
                 super::super::media_usage::Entity::update_usage(
@@ -238,9 +266,43 @@ impl Entity {
         }
     }
 
-    pub async fn delete(conn: &DbConn, category_id: i32) -> DbResult<u64> {
+    /// Delete a category; `children` decides what happens to its child
+    /// categories.
+    pub async fn delete(conn: &DbConn, category_id: i32, children: ChildPolicy) -> DbResult<u64> {
         let txn = conn.begin().await?;
 
+        let Some(category) = Self::find_by_id(category_id).one(&txn).await? else {
+            return Ok(0);
+        };
+
+        let child_count = Self::find()
+            .filter(Column::ParentId.eq(category_id))
+            .count(&txn)
+            .await?;
+        if child_count > 0 {
+            match children {
+                ChildPolicy::Block => {
+                    return Err(ErrorResponse::new(ErrorCode::ResourceConflict)
+                        .with_message("Category has child categories")
+                        .with_context(serde_json::json!({
+                            "category_id": category_id,
+                            "children": child_count,
+                        })));
+                }
+                ChildPolicy::Reparent => {
+                    Self::update_many()
+                        .col_expr(Column::ParentId, Expr::value(category.parent_id))
+                        .col_expr(
+                            Column::UpdatedAt,
+                            Expr::value(chrono::Utc::now().fixed_offset()),
+                        )
+                        .filter(Column::ParentId.eq(category_id))
+                        .exec(&txn)
+                        .await?;
+                }
+            }
+        }
+
         super::super::media_usage::Entity::delete_by_entity(
             &txn,
             EntityType::Category,
@@ -248,6 +310,9 @@ impl Entity {
         )
         .await?;
 
+        slug_redirect::Entity::delete_for_target(&txn, RedirectEntity::Category, category_id)
+            .await?;
+
         let result = Self::delete_by_id(category_id).exec(&txn).await?;
 
         txn.commit().await?;
@@ -278,6 +343,7 @@ impl Entity {
                 Column::Color,
                 Column::TextColor,
                 Column::IsActive,
+                Column::Locale,
                 Column::CreatedAt,
                 Column::UpdatedAt,
             ])
@@ -369,7 +435,7 @@ impl Entity {
 
         q = if let Some(id) = category_id {
             q.filter(Column::Id.eq(id))
-        } else if let Some(slug) = category_slug {
+        } else if let Some(slug) = category_slug.clone() {
             q.filter(Column::Slug.eq(slug))
         } else {
             q
@@ -377,6 +443,23 @@ impl Entity {
 
         let row = q.into_model::<CategoryWithJoinedData>().one(conn).await?;
 
+        // Old slug: answer with the category that owns it now
+        if row.is_none() && category_id.is_none() {
+            if let Some(slug) = category_slug.as_deref() {
+                if let Some(redirect) =
+                    slug_redirect::Entity::resolve(conn, RedirectEntity::Category, None, slug)
+                        .await?
+                {
+                    return Box::pin(Self::find_by_id_or_slug(
+                        conn,
+                        Some(redirect.target_id),
+                        None,
+                    ))
+                    .await;
+                }
+            }
+        }
+
         Ok(row.map(|r| r.into_relation()))
     }
 
@@ -408,6 +491,7 @@ impl Entity {
                 Column::Color,
                 Column::TextColor,
                 Column::IsActive,
+                Column::Locale,
                 Column::CreatedAt,
                 Column::UpdatedAt,
             ])
@@ -514,6 +598,14 @@ impl Entity {
             category_query = category_query.filter(Column::IsActive.eq(active));
         }
 
+        if let Some(locale) = query.locale {
+            category_query = category_query.filter(
+                Condition::any()
+                    .add(Column::Locale.eq(locale))
+                    .add(Column::Locale.is_null()),
+            );
+        }
+
         if let Some(ts) = query.created_at_gt {
             category_query = category_query.filter(Column::CreatedAt.gt(ts));
         }
@@ -569,4 +661,159 @@ impl Entity {
 
         Ok((results, total))
     }
+
+    /// Find a category by slug.
+    pub async fn find_by_slug(conn: &DbConn, slug: String) -> DbResult<Option<Model>> {
+        let model = Entity::find()
+            .filter(Column::Slug.eq(slug))
+            .one(conn)
+            .await?;
+        Ok(model)
+    }
+}
+
+const TREE_COLUMNS: &str =
+    "c.id, c.name, c.slug, c.parent_id, c.description, c.color, c.text_color, c.is_active";
+
+/// Tree queries (recursive CTEs) and parent validation.
+impl Entity {
+    /// Deepest allowed nesting; root categories are level 1.
+    pub const MAX_DEPTH: usize = 5;
+
+    /// Categories below `root_id` (included, depth 0), or the whole forest
+    /// when `None`. Ordered by depth, then name. Rows that sit on an existing
+    /// cycle are visited once.
+    pub async fn tree_rows<C: ConnectionTrait>(
+        conn: &C,
+        root_id: Option<i32>,
+    ) -> DbResult<Vec<CategoryTreeRow>> {
+        let (anchor, values) = match root_id {
+            Some(id) => ("id = $1", vec![id.into()]),
+            None => ("parent_id IS NULL", vec![]),
+        };
+        let sql = format!(
+            r#"
+            WITH RECURSIVE tree AS (
+                SELECT id, 0 AS depth, ARRAY[id] AS path
+                FROM categories
+                WHERE {anchor}
+                UNION ALL
+                SELECT c.id, tree.depth + 1, tree.path || c.id
+                FROM categories c
+                JOIN tree ON c.parent_id = tree.id
+                WHERE NOT c.id = ANY(tree.path)
+            )
+            SELECT {TREE_COLUMNS}, tree.depth
+            FROM tree
+            JOIN categories c ON c.id = tree.id
+            ORDER BY tree.depth, c.name, c.id
+            "#
+        );
+
+        let rows = CategoryTreeRow::find_by_statement(Statement::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            sql,
+            values,
+        ))
+        .all(conn)
+        .await?;
+        Ok(rows)
+    }
+
+    /// The category and its ancestors, root first; `depth` is the level below
+    /// the root. Empty when the category doesn't exist.
+    pub async fn ancestors<C: ConnectionTrait>(
+        conn: &C,
+        category_id: i32,
+    ) -> DbResult<Vec<CategoryTreeRow>> {
+        let sql = format!(
+            r#"
+            WITH RECURSIVE chain AS (
+                SELECT id, parent_id, 0 AS up, ARRAY[id] AS seen
+                FROM categories
+                WHERE id = $1
+                UNION ALL
+                SELECT c.id, c.parent_id, chain.up + 1, chain.seen || c.id
+                FROM categories c
+                JOIN chain ON c.id = chain.parent_id
+                WHERE NOT c.id = ANY(chain.seen)
+            )
+            SELECT {TREE_COLUMNS}, (MAX(chain.up) OVER () - chain.up) AS depth
+            FROM chain
+            JOIN categories c ON c.id = chain.id
+            ORDER BY chain.up DESC
+            "#
+        );
+
+        let rows = CategoryTreeRow::find_by_statement(Statement::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            sql,
+            vec![category_id.into()],
+        ))
+        .all(conn)
+        .await?;
+        Ok(rows)
+    }
+
+    /// The category and every category below it.
+    pub async fn descendant_ids<C: ConnectionTrait>(
+        conn: &C,
+        category_id: i32,
+    ) -> DbResult<Vec<i32>> {
+        let rows = Self::tree_rows(conn, Some(category_id)).await?;
+        Ok(rows.into_iter().map(|row| row.id).collect())
+    }
+
+    /// Check that `parent_id` exists and that hanging `category_id` (a new
+    /// category when `None`) under it neither creates a cycle nor nests
+    /// deeper than `MAX_DEPTH`.
+    pub async fn validate_parent<C: ConnectionTrait>(
+        conn: &C,
+        category_id: Option<i32>,
+        parent_id: i32,
+    ) -> DbResult<()> {
+        let chain: Vec<i32> = Self::ancestors(conn, parent_id)
+            .await?
+            .into_iter()
+            .rev()
+            .map(|row| row.id)
+            .collect();
+        if chain.is_empty() {
+            return Err(ErrorResponse::new(ErrorCode::RecordNotFound)
+                .with_message("Parent category not found")
+                .with_context(serde_json::json!({ "parent_id": parent_id })));
+        }
+
+        let subtree_height = match category_id {
+            Some(id) => Self::tree_rows(conn, Some(id))
+                .await?
+                .iter()
+                .map(|row| row.depth.max(0) as usize)
+                .max()
+                .unwrap_or(0),
+            None => 0,
+        };
+
+        match check_placement(category_id, &chain, subtree_height, Self::MAX_DEPTH) {
+            Ok(()) => Ok(()),
+            Err(TreeViolation::Cycle) => Err(ErrorResponse::new(ErrorCode::InvalidInput)
+                .with_message("A category can't be placed under itself or one of its descendants")
+                .with_context(serde_json::json!({
+                    "category_id": category_id,
+                    "parent_id": parent_id,
+                }))),
+            Err(TreeViolation::TooDeep { levels }) => {
+                Err(ErrorResponse::new(ErrorCode::InvalidInput)
+                    .with_message(format!(
+                        "Categories can be nested at most {} levels deep",
+                        Self::MAX_DEPTH
+                    ))
+                    .with_context(serde_json::json!({
+                        "parent_id": parent_id,
+                        "levels": levels,
+                        "max_depth": Self::MAX_DEPTH,
+                    })))
+            }
+        }
+    }
 }
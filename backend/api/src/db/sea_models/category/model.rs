@@ -1,10 +1,12 @@
+use schemars::JsonSchema;
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 
 use crate::db::sea_models::media;
 
-#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize, JsonSchema)]
 #[sea_orm(table_name = "categories")]
+#[schemars(rename = "Category")]
 pub struct Model {
     #[sea_orm(primary_key)]
     pub id: i32,
@@ -17,6 +19,8 @@ pub struct Model {
     pub color: String,
     pub text_color: String,
     pub is_active: bool,
+    /// `None` when shared by all locales.
+    pub locale: Option<String>,
     pub created_at: DateTimeWithTimeZone,
     pub updated_at: DateTimeWithTimeZone,
 }
@@ -1,4 +1,5 @@
 use chrono::{DateTime, FixedOffset};
+use schemars::JsonSchema;
 use sea_orm::prelude::DateTimeWithTimeZone;
 use sea_orm::FromQueryResult;
 use serde::{Deserialize, Serialize};
@@ -16,6 +17,8 @@ pub struct NewCategory {
     pub color: Option<String>,
     pub text_color: Option<String>,
     pub is_active: Option<bool>,
+    /// `None` makes the category shared by every locale.
+    pub locale: Option<String>,
 }
 
 #[derive(Deserialize, Debug)]
@@ -29,23 +32,26 @@ pub struct UpdateCategory {
     pub color: Option<String>,
     pub text_color: Option<String>,
     pub is_active: Option<bool>,
+    pub locale: Option<Option<String>>,
     pub updated_at: DateTimeWithTimeZone,
 }
 
-#[derive(Clone, Debug, Serialize, PartialEq)]
+#[derive(Clone, Debug, Serialize, PartialEq, JsonSchema)]
 pub struct CategoryQuery {
     pub page: Option<u64>,
     pub search: Option<String>,
     pub sorts: Option<Vec<SortParam>>,
     pub parent_id: Option<i32>,
     pub is_active: Option<bool>,
+    /// Categories in this locale plus the ones shared by every locale.
+    pub locale: Option<String>,
     pub created_at_gt: Option<DateTimeWithTimeZone>,
     pub created_at_lt: Option<DateTimeWithTimeZone>,
     pub updated_at_gt: Option<DateTimeWithTimeZone>,
     pub updated_at_lt: Option<DateTimeWithTimeZone>,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 pub struct CategoryMedia {
     pub id: i32,
     pub object_key: String,
@@ -56,7 +62,7 @@ pub struct CategoryMedia {
     pub size: i64,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 pub struct CategoryWithRelations {
     pub id: i32,
     pub name: String,
@@ -66,6 +72,7 @@ pub struct CategoryWithRelations {
     pub color: String,
     pub text_color: String,
     pub is_active: bool,
+    pub locale: Option<String>,
     pub created_at: DateTimeWithTimeZone,
     pub updated_at: DateTimeWithTimeZone,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -74,7 +81,7 @@ pub struct CategoryWithRelations {
     pub logo: Option<CategoryMedia>,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize, FromQueryResult)]
+#[derive(Clone, Debug, Serialize, Deserialize, FromQueryResult, JsonSchema)]
 pub struct CategoryWithJoinedData {
     // Category fields
     pub id: i32,
@@ -87,6 +94,7 @@ pub struct CategoryWithJoinedData {
     pub color: String,
     pub text_color: String,
     pub is_active: bool,
+    pub locale: Option<String>,
     pub created_at: DateTime<FixedOffset>,
     pub updated_at: DateTime<FixedOffset>,
 
@@ -158,6 +166,7 @@ impl CategoryWithJoinedData {
             color: self.color,
             text_color: self.text_color,
             is_active: self.is_active,
+            locale: self.locale,
             created_at: self.created_at,
             updated_at: self.updated_at,
             cover,
@@ -165,3 +174,167 @@ impl CategoryWithJoinedData {
         }
     }
 }
+
+/// One category row from a tree query, with its depth below the query root.
+#[derive(Clone, Debug, Serialize, Deserialize, FromQueryResult, JsonSchema)]
+pub struct CategoryTreeRow {
+    pub id: i32,
+    pub name: String,
+    pub slug: String,
+    pub parent_id: Option<i32>,
+    pub description: Option<String>,
+    pub color: String,
+    pub text_color: String,
+    pub is_active: bool,
+    pub depth: i32,
+}
+
+/// A category with its children, for the tree endpoints.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct CategoryNode {
+    pub id: i32,
+    pub name: String,
+    pub slug: String,
+    pub parent_id: Option<i32>,
+    pub description: Option<String>,
+    pub color: String,
+    pub text_color: String,
+    pub is_active: bool,
+    pub depth: i32,
+    pub children: Vec<CategoryNode>,
+}
+
+impl From<CategoryTreeRow> for CategoryNode {
+    fn from(row: CategoryTreeRow) -> Self {
+        Self {
+            id: row.id,
+            name: row.name,
+            slug: row.slug,
+            parent_id: row.parent_id,
+            description: row.description,
+            color: row.color,
+            text_color: row.text_color,
+            is_active: row.is_active,
+            depth: row.depth,
+            children: Vec::new(),
+        }
+    }
+}
+
+/// Nest flat tree rows under their parents. Rows whose parent is not in
+/// `rows` become roots; sibling order is kept.
+pub fn build_tree(rows: Vec<CategoryTreeRow>) -> Vec<CategoryNode> {
+    use std::collections::HashMap;
+
+    let ids: std::collections::HashSet<i32> = rows.iter().map(|r| r.id).collect();
+    let mut children: HashMap<i32, Vec<CategoryTreeRow>> = HashMap::new();
+    let mut roots = Vec::new();
+    for row in rows {
+        match row.parent_id {
+            Some(parent_id) if ids.contains(&parent_id) && parent_id != row.id => {
+                children.entry(parent_id).or_default().push(row)
+            }
+            _ => roots.push(row),
+        }
+    }
+
+    fn attach(
+        row: CategoryTreeRow,
+        children: &mut HashMap<i32, Vec<CategoryTreeRow>>,
+    ) -> CategoryNode {
+        let kids = children.remove(&row.id).unwrap_or_default();
+        let mut node = CategoryNode::from(row);
+        node.children = kids.into_iter().map(|kid| attach(kid, children)).collect();
+        node
+    }
+
+    roots
+        .into_iter()
+        .map(|row| attach(row, &mut children))
+        .collect()
+}
+
+/// What to do with the children of a deleted category.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ChildPolicy {
+    /// Refuse to delete a category that has children.
+    #[default]
+    Block,
+    /// Move the children up to the deleted category's parent.
+    Reparent,
+}
+
+/// Why a parent assignment was refused.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TreeViolation {
+    /// The new parent is the category itself or one of its descendants.
+    Cycle,
+    /// The deepest category would end up `levels` levels deep.
+    TooDeep { levels: usize },
+}
+
+/// Check placing a category (with a subtree `subtree_height` levels below
+/// it) under a parent whose ancestor chain, parent first, is `parent_chain`.
+pub fn check_placement(
+    category_id: Option<i32>,
+    parent_chain: &[i32],
+    subtree_height: usize,
+    max_depth: usize,
+) -> Result<(), TreeViolation> {
+    if category_id.is_some_and(|id| parent_chain.contains(&id)) {
+        return Err(TreeViolation::Cycle);
+    }
+    let levels = parent_chain.len() + 1 + subtree_height;
+    if levels > max_depth {
+        return Err(TreeViolation::TooDeep { levels });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(id: i32, parent_id: Option<i32>, depth: i32) -> CategoryTreeRow {
+        CategoryTreeRow {
+            id,
+            name: format!("c{}", id),
+            slug: format!("c{}", id),
+            parent_id,
+            description: None,
+            color: "#000000".into(),
+            text_color: "#ffffff".into(),
+            is_active: true,
+            depth,
+        }
+    }
+
+    #[test]
+    fn builds_nested_tree() {
+        let tree = build_tree(vec![
+            row(1, None, 0),
+            row(4, None, 0),
+            row(2, Some(1), 1),
+            row(3, Some(2), 2),
+        ]);
+        assert_eq!(tree.len(), 2);
+        assert_eq!(tree[0].children[0].id, 2);
+        assert_eq!(tree[0].children[0].children[0].id, 3);
+        assert!(tree[1].children.is_empty());
+    }
+
+    #[test]
+    fn placement_rejects_cycles_and_depth() {
+        // 3 -> 2 -> 1 (root)
+        assert_eq!(
+            check_placement(Some(1), &[3, 2, 1], 0, 5),
+            Err(TreeViolation::Cycle)
+        );
+        assert_eq!(
+            check_placement(Some(9), &[3, 2, 1], 2, 5),
+            Err(TreeViolation::TooDeep { levels: 6 })
+        );
+        assert!(check_placement(None, &[3, 2, 1], 0, 5).is_ok());
+    }
+}
@@ -1,8 +1,10 @@
+use schemars::JsonSchema;
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize, JsonSchema)]
 #[sea_orm(table_name = "comment_flags")]
+#[schemars(rename = "CommentFlag")]
 pub struct Model {
     #[sea_orm(primary_key)]
     pub id: i32,
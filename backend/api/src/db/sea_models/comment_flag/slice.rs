@@ -1,7 +1,8 @@
+use schemars::JsonSchema;
 use sea_orm::{prelude::DateTimeWithTimeZone, FromQueryResult};
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, JsonSchema)]
 pub struct FlagUserMedia {
     pub id: i32,
     pub object_key: String,
@@ -13,7 +14,7 @@ pub struct FlagUserMedia {
 }
 
 /// New flag to be created for a comment
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 pub struct NewCommentFlag {
     pub comment_id: i32,
     pub user_id: i32,
@@ -21,7 +22,7 @@ pub struct NewCommentFlag {
 }
 
 /// Query parameters for listing comment flags
-#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, JsonSchema)]
 pub struct CommentFlagQuery {
     pub page_no: Option<u64>,
     pub comment_id: Option<i32>,
@@ -48,7 +49,7 @@ impl Default for CommentFlagQuery {
 }
 
 /// Single flag row joined with reporting user fields from query
-#[derive(Clone, Debug, Serialize, Deserialize, FromQueryResult, PartialEq)]
+#[derive(Clone, Debug, Serialize, Deserialize, FromQueryResult, PartialEq, JsonSchema)]
 pub struct FlagWithUserJoined {
     pub id: i32,
     pub comment_id: i32,
@@ -102,7 +103,7 @@ impl FlagWithUserJoined {
 }
 
 /// Single flag row joined with reporting user fields
-#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, JsonSchema)]
 pub struct FlagWithUser {
     pub id: i32,
     pub comment_id: i32,
@@ -116,7 +117,7 @@ pub struct FlagWithUser {
 }
 
 /// Aggregate summary for flags on a comment
-#[derive(Clone, Debug, Serialize, Deserialize, FromQueryResult, PartialEq)]
+#[derive(Clone, Debug, Serialize, Deserialize, FromQueryResult, PartialEq, JsonSchema)]
 pub struct FlagsSummary {
     pub comment_id: i32,
     pub flags_count: i64,
@@ -0,0 +1,120 @@
+use sea_orm::{entity::prelude::*, sea_query::OnConflict, Set, TransactionTrait};
+use tracing::{info, instrument};
+
+use crate::{
+    db::sea_models::{notification, post_comment},
+    error::DbResult,
+};
+
+use super::*;
+
+impl Entity {
+    /// Like a comment and bump its `likes_count`; the comment's author is
+    /// notified. Returns (newly liked, likes_count).
+    #[instrument(skip(conn), fields(comment_id, user_id))]
+    pub async fn like_comment(
+        conn: &DbConn,
+        comment_id: i32,
+        user_id: i32,
+    ) -> DbResult<(bool, i32)> {
+        let txn = conn.begin().await?;
+        let Some(comment) = post_comment::Entity::find_by_id(comment_id)
+            .one(&txn)
+            .await?
+        else {
+            return Err(DbErr::RecordNotFound("Comment not found".to_string()).into());
+        };
+
+        let inserted = Entity::insert(ActiveModel {
+            comment_id: Set(comment_id),
+            user_id: Set(user_id),
+            created_at: Set(chrono::Utc::now().fixed_offset()),
+            ..Default::default()
+        })
+        .on_conflict(
+            OnConflict::columns([Column::CommentId, Column::UserId])
+                .do_nothing()
+                .to_owned(),
+        )
+        .exec_without_returning(&txn)
+        .await?;
+        if inserted == 0 {
+            txn.rollback().await?;
+            return Ok((false, comment.likes_count));
+        }
+
+        let likes_count = Self::adjust_count(&txn, comment_id, 1).await?;
+        txn.commit().await?;
+        info!(comment_id, user_id, likes_count, "Comment liked");
+
+        if comment.user_id != user_id {
+            notification::Entity::comment_liked(conn, &comment, user_id).await;
+        }
+        Ok((true, likes_count))
+    }
+
+    /// Remove a like. Returns (was liked, likes_count).
+    #[instrument(skip(conn), fields(comment_id, user_id))]
+    pub async fn unlike_comment(
+        conn: &DbConn,
+        comment_id: i32,
+        user_id: i32,
+    ) -> DbResult<(bool, i32)> {
+        let txn = conn.begin().await?;
+        let Some(comment) = post_comment::Entity::find_by_id(comment_id)
+            .one(&txn)
+            .await?
+        else {
+            return Err(DbErr::RecordNotFound("Comment not found".to_string()).into());
+        };
+
+        let deleted = Entity::delete_many()
+            .filter(Column::CommentId.eq(comment_id))
+            .filter(Column::UserId.eq(user_id))
+            .exec(&txn)
+            .await?;
+        if deleted.rows_affected == 0 {
+            txn.rollback().await?;
+            return Ok((false, comment.likes_count));
+        }
+
+        let likes_count = Self::adjust_count(&txn, comment_id, -1).await?;
+        txn.commit().await?;
+        info!(comment_id, user_id, likes_count, "Comment unliked");
+        Ok((true, likes_count))
+    }
+
+    /// Comment ids among `comment_ids` that `user_id` has liked.
+    pub async fn liked_ids(
+        conn: &DbConn,
+        user_id: i32,
+        comment_ids: Vec<i32>,
+    ) -> DbResult<Vec<i32>> {
+        let likes = Entity::find()
+            .filter(Column::UserId.eq(user_id))
+            .filter(Column::CommentId.is_in(comment_ids))
+            .all(conn)
+            .await?;
+        Ok(likes.into_iter().map(|like| like.comment_id).collect())
+    }
+
+    /// Add `delta` to the comment's `likes_count`, never going below 0.
+    async fn adjust_count<C: ConnectionTrait>(
+        conn: &C,
+        comment_id: i32,
+        delta: i32,
+    ) -> DbResult<i32> {
+        post_comment::Entity::update_many()
+            .col_expr(
+                post_comment::Column::LikesCount,
+                Expr::cust_with_values("GREATEST(likes_count + $1, 0)", [delta]),
+            )
+            .filter(post_comment::Column::Id.eq(comment_id))
+            .exec(conn)
+            .await?;
+        let comment = post_comment::Entity::find_by_id(comment_id)
+            .one(conn)
+            .await?;
+        Ok(comment.map(|comment| comment.likes_count).unwrap_or(0))
+    }
+}
@@ -0,0 +1,18 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Response for comment like/unlike actions
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+pub struct CommentLikeResponse {
+    pub comment_id: i32,
+    pub is_liked: bool,
+    pub likes_count: i32,
+    pub message: String,
+}
+
+/// Response of `/post/comment/v1/like/status`
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+pub struct CommentLikeStatus {
+    /// The subset of the requested ids the user has liked
+    pub liked: Vec<i32>,
+}
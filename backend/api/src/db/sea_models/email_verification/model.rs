@@ -1,10 +1,12 @@
 use chrono::{Duration, Utc};
 use rand::{distr::Alphanumeric, Rng};
+use schemars::JsonSchema;
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize, JsonSchema)]
 #[sea_orm(table_name = "email_verifications")]
+#[schemars(rename = "EmailVerification")]
 pub struct Model {
     #[sea_orm(primary_key)]
     pub id: i32,
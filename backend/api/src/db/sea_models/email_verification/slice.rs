@@ -1,5 +1,6 @@
 use super::Entity;
 use chrono::Utc;
+use schemars::JsonSchema;
 use sea_orm::prelude::DateTimeWithTimeZone;
 use serde::{Deserialize, Serialize};
 
@@ -9,14 +10,14 @@ pub struct UpdateEmailVerification {
     pub updated_at: DateTimeWithTimeZone,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 pub struct RegenerateEmailVerification {
     pub user_id: i32,
     pub code: String,
     pub updated_at: DateTimeWithTimeZone,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 pub struct AdminEmailVerificationQuery {
     pub page_no: Option<i64>,
     pub user_id: Option<i32>,
@@ -1,5 +1,6 @@
 use super::Entity;
 use chrono::Utc;
+use schemars::JsonSchema;
 use sea_orm::prelude::DateTimeWithTimeZone;
 use serde::{Deserialize, Serialize};
 
@@ -15,14 +16,14 @@ pub struct UpdateForgotPassword {
     pub updated_at: DateTimeWithTimeZone,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 pub struct RegenerateForgotPassword {
     pub user_id: i32,
     pub code: String,
     pub updated_at: DateTimeWithTimeZone,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 pub struct AdminForgotPasswordQuery {
     pub page_no: Option<i64>,
     pub user_id: Option<i32>,
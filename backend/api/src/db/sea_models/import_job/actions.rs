@@ -0,0 +1,105 @@
+use sea_orm::{
+    entity::prelude::*, ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder, Set,
+};
+
+use crate::error::DbResult;
+
+use super::*;
+
+/// Actions for import jobs:
+/// - Create a pending job and move it through running -> completed/failed
+/// - Paginated listing
+impl Entity {
+    pub const PER_PAGE: u64 = 20;
+
+    pub async fn create<C: ConnectionTrait>(conn: &C, new_job: NewImportJob) -> DbResult<Model> {
+        let now = chrono::Utc::now().fixed_offset();
+
+        let active = ActiveModel {
+            source: Set(new_job.source),
+            status: Set(ImportJobStatus::Pending),
+            dry_run: Set(new_job.dry_run),
+            file_name: Set(new_job.file_name),
+            options: Set(Some(new_job.options)),
+            requested_by: Set(new_job.requested_by),
+            created_at: Set(now),
+            updated_at: Set(now),
+            ..Default::default()
+        };
+
+        let model = active.insert(conn).await?;
+        Ok(model)
+    }
+
+    pub async fn find_by_id(conn: &DbConn, job_id: i32) -> DbResult<Option<Model>> {
+        let model = <Self as EntityTrait>::find_by_id(job_id).one(conn).await?;
+        Ok(model)
+    }
+
+    pub async fn mark_running(conn: &DbConn, job_id: i32) -> DbResult<()> {
+        let now = chrono::Utc::now().fixed_offset();
+        Entity::update_many()
+            .col_expr(Column::Status, Expr::value(ImportJobStatus::Running))
+            .col_expr(Column::StartedAt, Expr::value(now))
+            .col_expr(Column::UpdatedAt, Expr::value(now))
+            .filter(Column::Id.eq(job_id))
+            .exec(conn)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn mark_completed(
+        conn: &DbConn,
+        job_id: i32,
+        report: serde_json::Value,
+    ) -> DbResult<Model> {
+        let now = chrono::Utc::now().fixed_offset();
+        let active = ActiveModel {
+            id: Set(job_id),
+            status: Set(ImportJobStatus::Completed),
+            report: Set(Some(report)),
+            completed_at: Set(Some(now)),
+            updated_at: Set(now),
+            ..Default::default()
+        };
+        let model = active.update(conn).await?;
+        Ok(model)
+    }
+
+    pub async fn mark_failed(conn: &DbConn, job_id: i32, error: String) -> DbResult<()> {
+        let now = chrono::Utc::now().fixed_offset();
+        Entity::update_many()
+            .col_expr(Column::Status, Expr::value(ImportJobStatus::Failed))
+            .col_expr(Column::Error, Expr::value(error))
+            .col_expr(Column::CompletedAt, Expr::value(now))
+            .col_expr(Column::UpdatedAt, Expr::value(now))
+            .filter(Column::Id.eq(job_id))
+            .exec(conn)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn list(conn: &DbConn, query: ImportJobQuery) -> DbResult<(Vec<Model>, u64)> {
+        let page = match query.page {
+            Some(p) if p > 0 => p,
+            _ => 1,
+        };
+
+        let mut select = Entity::find();
+        if let Some(source) = query.source {
+            select = select.filter(Column::Source.eq(source));
+        }
+        if let Some(status) = query.status {
+            select = select.filter(Column::Status.eq(status));
+        }
+
+        let paginator = select
+            .order_by_desc(Column::CreatedAt)
+            .order_by_desc(Column::Id)
+            .paginate(conn, Self::PER_PAGE);
+        let total = paginator.num_items().await?;
+        let items = paginator.fetch_page(page - 1).await?;
+
+        Ok((items, total))
+    }
+}
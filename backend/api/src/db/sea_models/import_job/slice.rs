@@ -0,0 +1,21 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::model::{ImportJobStatus, ImportSource};
+
+/// Payload to create an import job row before the work is spawned.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct NewImportJob {
+    pub source: ImportSource,
+    pub dry_run: bool,
+    pub file_name: Option<String>,
+    pub options: serde_json::Value,
+    pub requested_by: Option<i32>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema)]
+pub struct ImportJobQuery {
+    pub page: Option<u64>,
+    pub source: Option<ImportSource>,
+    pub status: Option<ImportJobStatus>,
+}
@@ -0,0 +1,56 @@
+use sea_orm::{
+    entity::prelude::*, sea_query::OnConflict, ColumnTrait, EntityTrait, QueryFilter, Set,
+};
+
+use crate::error::DbResult;
+
+use super::*;
+
+/// Actions for import mappings:
+/// - Look up the local row created for a source item
+/// - Record (or repoint) a mapping after an item is imported
+impl Entity {
+    pub async fn find_target(conn: &DbConn, item: &SourceItem) -> DbResult<Option<Model>> {
+        let model = Entity::find()
+            .filter(Column::Source.eq(item.source))
+            .filter(Column::Scope.eq(item.scope.as_str()))
+            .filter(Column::Entity.eq(item.entity.as_str()))
+            .filter(Column::SourceId.eq(item.source_id.as_str()))
+            .one(conn)
+            .await?;
+        Ok(model)
+    }
+
+    pub async fn record<C: ConnectionTrait>(
+        conn: &C,
+        item: SourceItem,
+        target_id: i32,
+        job_id: Option<i32>,
+    ) -> DbResult<()> {
+        let active = ActiveModel {
+            source: Set(item.source),
+            scope: Set(item.scope),
+            entity: Set(item.entity),
+            source_id: Set(item.source_id),
+            target_id: Set(target_id),
+            job_id: Set(job_id),
+            created_at: Set(chrono::Utc::now().fixed_offset()),
+            ..Default::default()
+        };
+
+        Entity::insert(active)
+            .on_conflict(
+                OnConflict::columns([
+                    Column::Source,
+                    Column::Scope,
+                    Column::Entity,
+                    Column::SourceId,
+                ])
+                .update_columns([Column::TargetId, Column::JobId, Column::CreatedAt])
+                .to_owned(),
+            )
+            .exec(conn)
+            .await?;
+        Ok(())
+    }
+}
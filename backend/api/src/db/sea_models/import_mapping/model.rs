@@ -0,0 +1,45 @@
+use schemars::JsonSchema;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::super::import_job::ImportSource;
+
+/// Links an item from an imported blog to the local row created for it.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize, JsonSchema)]
+#[sea_orm(table_name = "import_mappings")]
+#[schemars(rename = "ImportMapping")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+
+    pub source: ImportSource,
+    /// Distinguishes several blogs of the same kind (WordPress base site URL,
+    /// empty for Ghost).
+    pub scope: String,
+    /// `author`, `category`, `tag`, `post`, `comment` or `media`.
+    pub entity: String,
+    pub source_id: String,
+    pub target_id: i32,
+    pub job_id: Option<i32>,
+
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::super::import_job::Entity",
+        from = "Column::JobId",
+        to = "super::super::import_job::Column::Id",
+        on_delete = "SetNull"
+    )]
+    ImportJob,
+}
+
+impl Related<super::super::import_job::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ImportJob.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
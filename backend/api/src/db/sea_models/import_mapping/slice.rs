@@ -0,0 +1,13 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::super::import_job::ImportSource;
+
+/// Identifies one item of an imported blog.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+pub struct SourceItem {
+    pub source: ImportSource,
+    pub scope: String,
+    pub entity: String,
+    pub source_id: String,
+}
@@ -0,0 +1,290 @@
+use sea_orm::{
+    entity::prelude::*, sea_query::Expr, Condition, DatabaseBackend, FromQueryResult, QueryOrder,
+    QuerySelect, Select, Statement,
+};
+
+use crate::error::DbResult;
+
+use super::*;
+
+/// Actions for the job queue:
+/// - Enqueue jobs, skipping duplicates of an active unique key
+/// - Claim the next due job with `FOR UPDATE SKIP LOCKED`
+/// - Record the outcome of an attempt
+/// - Admin retry, cancel, listing and counts
+/// - Recover jobs left running by a dead worker and purge old ones
+impl Entity {
+    pub const PER_PAGE: u64 = 50;
+
+    /// Insert the job; `None` when an active job already holds its unique key.
+    pub async fn enqueue<C: ConnectionTrait>(conn: &C, job: NewJob) -> DbResult<Option<Model>> {
+        let now = chrono::Utc::now().fixed_offset();
+        let row = conn
+            .query_one(Statement::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                r#"
+                INSERT INTO jobs (queue, kind, payload, status, max_attempts, unique_key, run_at, created_by, created_at, updated_at)
+                VALUES ($1, $2, $3, 'pending', $4, $5, $6, $7, $8, $8)
+                ON CONFLICT DO NOTHING
+                RETURNING id
+                "#,
+                vec![
+                    job.queue.into(),
+                    job.kind.into(),
+                    job.payload.into(),
+                    job.max_attempts.into(),
+                    job.unique_key.into(),
+                    job.run_at.unwrap_or(now).into(),
+                    job.created_by.into(),
+                    now.into(),
+                ],
+            ))
+            .await?;
+
+        match row {
+            Some(row) => {
+                let id: i64 = row.try_get("", "id")?;
+                Ok(Entity::find_by_id(id).one(conn).await?)
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Lock the oldest due job on `queue` for `worker` and count the attempt.
+    pub async fn claim(conn: &DbConn, queue: &str, worker: &str) -> DbResult<Option<Model>> {
+        let row = conn
+            .query_one(Statement::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                r#"
+                UPDATE jobs
+                SET status = 'running', attempts = attempts + 1,
+                    locked_by = $2, locked_at = now(), updated_at = now()
+                WHERE id = (
+                    SELECT id FROM jobs
+                    WHERE queue = $1 AND status = 'pending' AND run_at <= now()
+                    ORDER BY run_at, id
+                    FOR UPDATE SKIP LOCKED
+                    LIMIT 1
+                )
+                RETURNING id
+                "#,
+                vec![queue.into(), worker.into()],
+            ))
+            .await?;
+
+        match row {
+            Some(row) => {
+                let id: i64 = row.try_get("", "id")?;
+                Ok(Entity::find_by_id(id).one(conn).await?)
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Refresh the lock `worker` holds on a running job so it isn't taken
+    /// for stale. `false` once the job is no longer the worker's.
+    pub async fn heartbeat(conn: &DbConn, id: i64, worker: &str) -> DbResult<bool> {
+        let now = chrono::Utc::now().fixed_offset();
+        let result = Entity::update_many()
+            .col_expr(Column::LockedAt, Expr::value(now))
+            .filter(Column::Id.eq(id))
+            .filter(Column::Status.eq(JobStatus::Running))
+            .filter(Column::LockedBy.eq(worker))
+            .exec(conn)
+            .await?;
+        Ok(result.rows_affected > 0)
+    }
+
+    /// Only applies while `worker` still holds the job.
+    pub async fn complete(
+        conn: &DbConn,
+        id: i64,
+        worker: &str,
+        result: Option<Json>,
+    ) -> DbResult<()> {
+        let now = chrono::Utc::now().fixed_offset();
+        Entity::update_many()
+            .col_expr(Column::Status, Expr::value(JobStatus::Succeeded))
+            .col_expr(Column::Result, Expr::value(result))
+            .col_expr(Column::LastError, Expr::value(Option::<String>::None))
+            .col_expr(Column::LockedBy, Expr::value(Option::<String>::None))
+            .col_expr(Column::FinishedAt, Expr::value(now))
+            .col_expr(Column::UpdatedAt, Expr::value(now))
+            .filter(Column::Id.eq(id))
+            .filter(Column::Status.eq(JobStatus::Running))
+            .filter(Column::LockedBy.eq(worker))
+            .exec(conn)
+            .await?;
+        Ok(())
+    }
+
+    /// Record a failed attempt: back to `pending` until `retry_at` when
+    /// given, otherwise `dead`. Only applies while `worker` still holds the
+    /// job.
+    pub async fn fail(
+        conn: &DbConn,
+        id: i64,
+        worker: &str,
+        error: &str,
+        retry_at: Option<DateTimeWithTimeZone>,
+    ) -> DbResult<()> {
+        let now = chrono::Utc::now().fixed_offset();
+        let mut update = Entity::update_many()
+            .col_expr(Column::LastError, Expr::value(error))
+            .col_expr(Column::LockedBy, Expr::value(Option::<String>::None))
+            .col_expr(Column::UpdatedAt, Expr::value(now));
+        update = match retry_at {
+            Some(at) => update
+                .col_expr(Column::Status, Expr::value(JobStatus::Pending))
+                .col_expr(Column::RunAt, Expr::value(at)),
+            None => update
+                .col_expr(Column::Status, Expr::value(JobStatus::Dead))
+                .col_expr(Column::FinishedAt, Expr::value(now)),
+        };
+        update
+            .filter(Column::Id.eq(id))
+            .filter(Column::Status.eq(JobStatus::Running))
+            .filter(Column::LockedBy.eq(worker))
+            .exec(conn)
+            .await?;
+        Ok(())
+    }
+
+    /// Requeue a dead or cancelled job with a fresh set of attempts.
+    pub async fn retry(conn: &DbConn, id: i64) -> DbResult<Option<Model>> {
+        let now = chrono::Utc::now().fixed_offset();
+        let result = Entity::update_many()
+            .col_expr(Column::Status, Expr::value(JobStatus::Pending))
+            .col_expr(Column::Attempts, Expr::value(0))
+            .col_expr(Column::RunAt, Expr::value(now))
+            .col_expr(
+                Column::FinishedAt,
+                Expr::value(Option::<DateTimeWithTimeZone>::None),
+            )
+            .col_expr(Column::UpdatedAt, Expr::value(now))
+            .filter(Column::Id.eq(id))
+            .filter(Column::Status.is_in([JobStatus::Dead, JobStatus::Cancelled]))
+            .exec(conn)
+            .await?;
+        if result.rows_affected == 0 {
+            return Ok(None);
+        }
+        Ok(Entity::find_by_id(id).one(conn).await?)
+    }
+
+    /// Cancel a job that hasn't started; running jobs can't be interrupted.
+    pub async fn cancel(conn: &DbConn, id: i64) -> DbResult<Option<Model>> {
+        let now = chrono::Utc::now().fixed_offset();
+        let result = Entity::update_many()
+            .col_expr(Column::Status, Expr::value(JobStatus::Cancelled))
+            .col_expr(Column::FinishedAt, Expr::value(now))
+            .col_expr(Column::UpdatedAt, Expr::value(now))
+            .filter(Column::Id.eq(id))
+            .filter(Column::Status.eq(JobStatus::Pending))
+            .exec(conn)
+            .await?;
+        if result.rows_affected == 0 {
+            return Ok(None);
+        }
+        Ok(Entity::find_by_id(id).one(conn).await?)
+    }
+
+    /// Hand running jobs back to the queue without counting the attempt:
+    /// those of `worker` when given, otherwise any locked before `cutoff`.
+    pub async fn release(
+        conn: &DbConn,
+        worker: Option<&str>,
+        cutoff: Option<DateTimeWithTimeZone>,
+    ) -> DbResult<u64> {
+        let mut condition = Condition::all().add(Column::Status.eq(JobStatus::Running));
+        if let Some(worker) = worker {
+            condition = condition.add(Column::LockedBy.eq(worker));
+        }
+        if let Some(cutoff) = cutoff {
+            condition = condition.add(Column::LockedAt.lt(cutoff));
+        }
+        let result = Entity::update_many()
+            .col_expr(Column::Status, Expr::value(JobStatus::Pending))
+            .col_expr(
+                Column::Attempts,
+                Expr::cust("GREATEST(\"attempts\" - 1, 0)"),
+            )
+            .col_expr(Column::LockedBy, Expr::value(Option::<String>::None))
+            .col_expr(
+                Column::UpdatedAt,
+                Expr::value(chrono::Utc::now().fixed_offset()),
+            )
+            .filter(condition)
+            .exec(conn)
+            .await?;
+        Ok(result.rows_affected)
+    }
+
+    /// Delete succeeded and cancelled jobs that finished before `cutoff`.
+    pub async fn purge_finished_before(
+        conn: &DbConn,
+        cutoff: DateTimeWithTimeZone,
+    ) -> DbResult<u64> {
+        let result = Entity::delete_many()
+            .filter(Column::Status.is_in([JobStatus::Succeeded, JobStatus::Cancelled]))
+            .filter(Column::FinishedAt.lt(cutoff))
+            .exec(conn)
+            .await?;
+        Ok(result.rows_affected)
+    }
+
+    pub async fn find_with_query(conn: &DbConn, query: JobQuery) -> DbResult<(Vec<Model>, u64)> {
+        let select = Self::filtered(&query);
+        let total = select.clone().count(conn).await?;
+
+        let page = match query.page {
+            Some(p) if p > 0 => p,
+            _ => 1,
+        };
+        let rows = select
+            .offset((page - 1) * Self::PER_PAGE)
+            .limit(Self::PER_PAGE)
+            .all(conn)
+            .await?;
+        Ok((rows, total))
+    }
+
+    pub async fn counts(conn: &DbConn) -> DbResult<Vec<JobCount>> {
+        let rows = JobCount::find_by_statement(Statement::from_string(
+            DatabaseBackend::Postgres,
+            r#"
+            SELECT queue, status::text AS status, COUNT(*) AS count
+            FROM jobs
+            GROUP BY queue, status
+            ORDER BY queue, status
+            "#,
+        ))
+        .all(conn)
+        .await?;
+        Ok(rows)
+    }
+
+    fn filtered(query: &JobQuery) -> Select<Entity> {
+        let mut condition = Condition::all();
+        if let Some(queue) = &query.queue {
+            condition = condition.add(Column::Queue.eq(queue));
+        }
+        if let Some(kind) = &query.kind {
+            condition = condition.add(Column::Kind.eq(kind));
+        }
+        if let Some(status) = query.status {
+            condition = condition.add(Column::Status.eq(status));
+        }
+        if let Some(after) = query.created_at_gt {
+            condition = condition.add(Column::CreatedAt.gt(after));
+        }
+        if let Some(before) = query.created_at_lt {
+            condition = condition.add(Column::CreatedAt.lt(before));
+        }
+
+        Entity::find()
+            .filter(condition)
+            .order_by_desc(Column::CreatedAt)
+            .order_by_desc(Column::Id)
+    }
+}
@@ -0,0 +1,55 @@
+use schemars::JsonSchema;
+use sea_orm::{prelude::DateTimeWithTimeZone, FromQueryResult};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::model::JobStatus;
+
+/// Job to enqueue; see `job::Model`.
+#[derive(Clone, Debug)]
+pub struct NewJob {
+    pub queue: String,
+    pub kind: String,
+    pub payload: Value,
+    pub max_attempts: i32,
+    pub unique_key: Option<String>,
+    pub run_at: Option<DateTimeWithTimeZone>,
+    pub created_by: Option<i32>,
+}
+
+impl NewJob {
+    /// Skip the insert while another pending or running job has this key.
+    pub fn unique(mut self, key: impl Into<String>) -> Self {
+        self.unique_key = Some(key.into());
+        self
+    }
+
+    pub fn run_at(mut self, at: DateTimeWithTimeZone) -> Self {
+        self.run_at = Some(at);
+        self
+    }
+
+    pub fn created_by(mut self, user_id: i32) -> Self {
+        self.created_by = Some(user_id);
+        self
+    }
+}
+
+/// Filters for listing jobs
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema)]
+pub struct JobQuery {
+    pub page: Option<u64>,
+    pub queue: Option<String>,
+    pub kind: Option<String>,
+    pub status: Option<JobStatus>,
+    pub created_at_gt: Option<DateTimeWithTimeZone>,
+    pub created_at_lt: Option<DateTimeWithTimeZone>,
+}
+
+/// Job count for one queue and status.
+#[derive(Clone, Debug, Serialize, Deserialize, FromQueryResult, JsonSchema)]
+pub struct JobCount {
+    pub queue: String,
+    pub status: String,
+    pub count: i64,
+}
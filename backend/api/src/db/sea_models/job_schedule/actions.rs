@@ -0,0 +1,165 @@
+use sea_orm::{
+    entity::prelude::*, sea_query::OnConflict, DatabaseBackend, DatabaseTransaction, QueryOrder,
+    Set, Statement, TransactionTrait,
+};
+
+use crate::error::DbResult;
+
+use super::*;
+
+/// Actions for recurring job schedules:
+/// - Register built-in schedules without touching admin edits
+/// - Claim a due schedule with `FOR UPDATE SKIP LOCKED`
+/// - List and update schedules, or replace one a module configures
+impl Entity {
+    /// Insert the schedule, or refresh its kind and queue when it exists.
+    /// Cron, payload and `enabled` stay as an admin left them.
+    pub async fn ensure(
+        conn: &DbConn,
+        schedule: NewJobSchedule,
+        next_run_at: DateTimeWithTimeZone,
+    ) -> DbResult<()> {
+        let now = chrono::Utc::now().fixed_offset();
+        let active = ActiveModel {
+            name: Set(schedule.name),
+            kind: Set(schedule.kind),
+            queue: Set(schedule.queue),
+            payload: Set(schedule.payload),
+            cron: Set(schedule.cron),
+            enabled: Set(true),
+            next_run_at: Set(Some(next_run_at)),
+            created_at: Set(now),
+            updated_at: Set(now),
+            ..Default::default()
+        };
+        Entity::insert(active)
+            .on_conflict(
+                OnConflict::column(Column::Name)
+                    .update_columns([Column::Kind, Column::Queue])
+                    .to_owned(),
+            )
+            .exec_without_returning(conn)
+            .await?;
+        Ok(())
+    }
+
+    /// Open a transaction holding the lock on one enabled schedule that is
+    /// due. Commit it after enqueueing the run so other instances skip it.
+    pub async fn claim_due(conn: &DbConn) -> DbResult<Option<(DatabaseTransaction, Model)>> {
+        let txn = conn.begin().await?;
+        let row = txn
+            .query_one(Statement::from_string(
+                DatabaseBackend::Postgres,
+                r#"
+                SELECT id FROM job_schedules
+                WHERE enabled AND next_run_at <= now()
+                ORDER BY next_run_at
+                FOR UPDATE SKIP LOCKED
+                LIMIT 1
+                "#,
+            ))
+            .await?;
+        let Some(row) = row else {
+            txn.rollback().await?;
+            return Ok(None);
+        };
+
+        let id: i32 = row.try_get("", "id")?;
+        match Entity::find_by_id(id).one(&txn).await? {
+            Some(schedule) => Ok(Some((txn, schedule))),
+            None => {
+                txn.rollback().await?;
+                Ok(None)
+            }
+        }
+    }
+
+    pub async fn mark_run<C: ConnectionTrait>(
+        conn: &C,
+        schedule: Model,
+        job_id: Option<i64>,
+        next_run_at: Option<DateTimeWithTimeZone>,
+    ) -> DbResult<Model> {
+        let now = chrono::Utc::now().fixed_offset();
+        let last_job_id = job_id.or(schedule.last_job_id);
+        let mut active: ActiveModel = schedule.into();
+        active.last_run_at = Set(Some(now));
+        active.last_job_id = Set(last_job_id);
+        active.next_run_at = Set(next_run_at);
+        active.updated_at = Set(now);
+        Ok(active.update(conn).await?)
+    }
+
+    pub async fn list(conn: &DbConn) -> DbResult<Vec<Model>> {
+        Ok(Entity::find().order_by_asc(Column::Name).all(conn).await?)
+    }
+
+    pub async fn get_by_id(conn: &DbConn, id: i32) -> DbResult<Option<Model>> {
+        Ok(Entity::find_by_id(id).one(conn).await?)
+    }
+
+    pub async fn get_by_name(conn: &DbConn, name: &str) -> DbResult<Option<Model>> {
+        Ok(Entity::find()
+            .filter(Column::Name.eq(name))
+            .one(conn)
+            .await?)
+    }
+
+    /// Insert the schedule, or overwrite its cron, payload and `enabled`.
+    /// For schedules a module configures rather than built-in ones.
+    pub async fn upsert(
+        conn: &DbConn,
+        schedule: NewJobSchedule,
+        enabled: bool,
+        next_run_at: Option<DateTimeWithTimeZone>,
+    ) -> DbResult<Model> {
+        let now = chrono::Utc::now().fixed_offset();
+        let active = ActiveModel {
+            name: Set(schedule.name),
+            kind: Set(schedule.kind),
+            queue: Set(schedule.queue),
+            payload: Set(schedule.payload),
+            cron: Set(schedule.cron),
+            enabled: Set(enabled),
+            next_run_at: Set(next_run_at),
+            created_at: Set(now),
+            updated_at: Set(now),
+            ..Default::default()
+        };
+        let model = Entity::insert(active)
+            .on_conflict(
+                OnConflict::column(Column::Name)
+                    .update_columns([
+                        Column::Kind,
+                        Column::Queue,
+                        Column::Payload,
+                        Column::Cron,
+                        Column::Enabled,
+                        Column::NextRunAt,
+                        Column::UpdatedAt,
+                    ])
+                    .to_owned(),
+            )
+            .exec_with_returning(conn)
+            .await?;
+        Ok(model)
+    }
+
+    pub async fn update(
+        conn: &DbConn,
+        schedule: Model,
+        changes: JobScheduleUpdate,
+        next_run_at: Option<DateTimeWithTimeZone>,
+    ) -> DbResult<Model> {
+        let mut active: ActiveModel = schedule.into();
+        if let Some(cron) = changes.cron {
+            active.cron = Set(cron);
+        }
+        if let Some(enabled) = changes.enabled {
+            active.enabled = Set(enabled);
+        }
+        active.next_run_at = Set(next_run_at);
+        active.updated_at = Set(chrono::Utc::now().fixed_offset());
+        Ok(active.update(conn).await?)
+    }
+}
@@ -0,0 +1,33 @@
+use schemars::JsonSchema;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize, JsonSchema)]
+#[sea_orm(table_name = "job_schedules")]
+#[schemars(rename = "JobSchedule")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+
+    pub name: String,
+    pub kind: String,
+    pub queue: String,
+    #[sea_orm(column_type = "JsonBinary")]
+    pub payload: Json,
+    /// Cron expression, seconds optional.
+    pub cron: String,
+    pub enabled: bool,
+
+    pub last_run_at: Option<DateTimeWithTimeZone>,
+    pub next_run_at: Option<DateTimeWithTimeZone>,
+    /// Job enqueued by the last run.
+    pub last_job_id: Option<i64>,
+
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,20 @@
+use schemars::JsonSchema;
+use sea_orm::prelude::Json;
+use serde::{Deserialize, Serialize};
+
+/// Built-in schedule registered at startup; see `job_schedule::Model`.
+#[derive(Clone, Debug)]
+pub struct NewJobSchedule {
+    pub name: String,
+    pub kind: String,
+    pub queue: String,
+    pub payload: Json,
+    pub cron: String,
+}
+
+/// Admin edits; unset fields are left alone.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema)]
+pub struct JobScheduleUpdate {
+    pub cron: Option<String>,
+    pub enabled: Option<bool>,
+}
@@ -1,10 +1,11 @@
 use crate::utils::SortParam;
+use schemars::JsonSchema;
 use sea_orm::prelude::DateTimeWithTimeZone;
 use serde::{Deserialize, Serialize};
 
 use super::MediaReference;
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
 pub struct NewMedia {
     pub object_key: String,
     pub file_url: String,
@@ -20,12 +21,12 @@ pub struct NewMedia {
     pub optimized_at: Option<DateTimeWithTimeZone>,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
 pub struct MediaDeletion {
     pub id: i32,
 }
 
-#[derive(Clone, Debug, Serialize, PartialEq)]
+#[derive(Clone, Debug, Serialize, PartialEq, JsonSchema)]
 pub struct MediaQuery {
     pub page: Option<u64>,
     pub search: Option<String>,
@@ -40,7 +41,7 @@ pub struct MediaQuery {
     pub updated_at_lt: Option<DateTimeWithTimeZone>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct MediaWithUsage {
     #[serde(flatten)]
     pub media: super::model::Model,
@@ -1,10 +1,12 @@
+use schemars::JsonSchema;
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 
 use super::super::media;
 
-#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize, JsonSchema)]
 #[sea_orm(table_name = "media_variants")]
+#[schemars(rename = "MediaVariant")]
 pub struct Model {
     #[sea_orm(primary_key)]
     pub id: i32,
@@ -1,27 +1,47 @@
 pub mod category;
 pub mod comment_flag;
+pub mod comment_like;
 pub mod email_verification;
 pub mod forgot_password;
 pub mod newsletter_subscriber;
 
 pub mod app_constant;
+pub mod app_constant_change;
+pub mod audit_log;
+pub mod backup_job;
+pub mod import_job;
+pub mod import_mapping;
+pub mod job;
+pub mod job_schedule;
 pub mod media;
 pub mod media_usage;
 pub mod media_variant;
+pub mod notification;
+pub mod notification_preference;
 pub mod pagination;
 pub mod post;
+pub mod post_author;
 pub mod post_comment;
 pub mod post_like;
+pub mod post_review_note;
 pub mod post_revision;
 pub mod post_series;
 pub mod post_series_post;
+pub mod post_status_transition;
+pub mod post_translation_group;
 pub mod post_view;
+pub mod role_permission;
 pub mod route_status;
 pub mod scheduled_post;
 pub mod seed_run;
+pub mod slug_redirect;
 pub mod tag;
 pub mod user;
 pub mod user_ban;
+pub mod user_follow;
+pub mod user_permission;
 pub mod user_session;
+pub mod webhook;
+pub mod webhook_delivery;
 
 pub use crate::utils::color as color_utils;
@@ -1,5 +1,9 @@
-use crate::error::{DbResult, ErrorCode, ErrorResponse};
+use crate::{
+    db::sea_models::webhook::{self, events},
+    error::{DbResult, ErrorCode, ErrorResponse},
+};
 use sea_orm::{entity::prelude::*, Condition, Order, QueryOrder, QuerySelect, Set};
+use serde_json::json;
 use tracing::{error, info, instrument, warn};
 
 use super::{
@@ -10,6 +14,16 @@ use super::{
 impl Entity {
     pub const PER_PAGE: u64 = 20;
 
+    /// Subscriber sent with `subscriber.*` webhook events; never the token.
+    fn webhook_data(subscriber: &Model) -> serde_json::Value {
+        json!({
+            "id": subscriber.id,
+            "email": subscriber.email,
+            "status": subscriber.status,
+            "created_at": subscriber.created_at,
+        })
+    }
+
     #[instrument(skip(conn, new_subscriber), fields(subscriber_id, email = %new_subscriber.email))]
     pub async fn create(conn: &DbConn, new_subscriber: NewSubscriber) -> DbResult<Model> {
         let now = chrono::Utc::now().fixed_offset();
@@ -29,6 +43,12 @@ impl Entity {
                     Ok(updated) => {
                         tracing::Span::current().record("subscriber_id", updated.id);
                         info!(subscriber_id = updated.id, "Newsletter subscriber updated");
+                        webhook::Entity::emit(
+                            conn,
+                            events::SUBSCRIBER_CREATED,
+                            Self::webhook_data(&updated),
+                        )
+                        .await;
                         Ok(updated)
                     }
                     Err(err) => {
@@ -50,6 +70,12 @@ impl Entity {
                     Ok(inserted) => {
                         tracing::Span::current().record("subscriber_id", inserted.id);
                         info!(subscriber_id = inserted.id, "Newsletter subscriber created");
+                        webhook::Entity::emit(
+                            conn,
+                            events::SUBSCRIBER_CREATED,
+                            Self::webhook_data(&inserted),
+                        )
+                        .await;
                         Ok(inserted)
                     }
                     Err(err) => {
@@ -76,10 +102,19 @@ impl Entity {
         if let Some(model) = sub {
             // Only confirm if token matches and not already unsubscribed
             if model.token == token && model.status != SubscriberStatus::Unsubscribed {
+                let was_confirmed = model.status == SubscriberStatus::Confirmed;
                 let mut am: ActiveModel = model.into();
                 am.status = Set(SubscriberStatus::Confirmed);
                 am.updated_at = Set(now);
                 let updated = am.update(conn).await?;
+                if !was_confirmed {
+                    webhook::Entity::emit(
+                        conn,
+                        events::SUBSCRIBER_CONFIRMED,
+                        Self::webhook_data(&updated),
+                    )
+                    .await;
+                }
                 Ok(Some(updated))
             } else {
                 Ok(None)
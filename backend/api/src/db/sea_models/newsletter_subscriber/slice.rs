@@ -1,3 +1,4 @@
+use schemars::JsonSchema;
 use sea_orm::prelude::DateTimeWithTimeZone;
 use sea_orm::FromQueryResult;
 use serde::{Deserialize, Serialize};
@@ -6,7 +7,7 @@ use super::SubscriberStatus;
 use crate::utils::SortParam;
 
 /// New subscriber DTO for insertion
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 pub struct NewSubscriber {
     pub email: String,
     pub status: SubscriberStatus,
@@ -14,7 +15,7 @@ pub struct NewSubscriber {
 }
 
 /// Update subscriber DTO for partial updates (e.g., confirm/unsubscribe)
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 pub struct UpdateSubscriber {
     pub status: Option<SubscriberStatus>,
     pub token: Option<String>,
@@ -22,7 +23,7 @@ pub struct UpdateSubscriber {
 }
 
 /// Query parameters for searching/paginating subscribers
-#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, JsonSchema)]
 pub struct SubscriberQuery {
     pub page: Option<u64>,
     pub search: Option<String>,
@@ -35,7 +36,7 @@ pub struct SubscriberQuery {
 }
 
 /// Lightweight subscriber list item for admin listings
-#[derive(Clone, Debug, Serialize, Deserialize, FromQueryResult)]
+#[derive(Clone, Debug, Serialize, Deserialize, FromQueryResult, JsonSchema)]
 pub struct SubscriberListItem {
     pub id: i32,
     pub email: String,
@@ -0,0 +1,273 @@
+use sea_orm::{
+    entity::prelude::*, DatabaseBackend, PaginatorTrait, QueryOrder, QuerySelect, Statement,
+};
+use serde_json::json;
+use tracing::error;
+
+use crate::{
+    db::sea_models::{post, post_comment, user},
+    error::DbResult,
+};
+
+use super::*;
+
+/// Recipients of a notification, as a subquery yielding `"id"`.
+enum Recipients {
+    User(i32),
+    FollowersOf(i32),
+}
+
+impl Recipients {
+    fn source(&self) -> (&'static str, i32) {
+        match self {
+            Self::User(id) => (r#"SELECT "id" FROM "users" WHERE "id" = $1"#, *id),
+            Self::FollowersOf(id) => (
+                r#"SELECT "follower_id" AS "id" FROM "user_follows" WHERE "followed_id" = $1"#,
+                *id,
+            ),
+        }
+    }
+}
+
+/// Actions for in-app notifications:
+/// - Record notifications from the comment, like and publish flows,
+///   honouring each recipient's preferences and folding unread repeats
+/// - List, count and mark a user's notifications read
+/// - Pick and mark the notifications that go into email digests
+impl Entity {
+    pub const PER_PAGE: u64 = 20;
+
+    /// Insert `notification` for every recipient who hasn't muted its type
+    /// or turned off every channel. A recipient with an unread notification
+    /// in the same group gets that one bumped instead.
+    async fn record<C: ConnectionTrait>(
+        conn: &C,
+        recipients: Recipients,
+        notification: NewNotification,
+    ) -> DbResult<u64> {
+        let (source, recipient) = recipients.source();
+        let sql = format!(
+            r#"INSERT INTO "notifications" ("user_id", "type", "actor_id", "group_key", "payload")
+            SELECT r."id", $2::text::notification_type, $3, $4, $5
+            FROM ({}) r
+            LEFT JOIN "notification_preferences" p ON p."user_id" = r."id"
+            WHERE p."user_id" IS NULL
+               OR (NOT ($2::text = ANY(p."muted_types"))
+                   AND (p."in_app" OR p."email_digest" <> 'off'))
+            ON CONFLICT ("user_id", "group_key") WHERE "read_at" IS NULL
+            DO UPDATE SET
+                "count" = "notifications"."count" + 1,
+                "actor_id" = EXCLUDED."actor_id",
+                "payload" = EXCLUDED."payload",
+                "emailed_at" = NULL,
+                "updated_at" = now()"#,
+            source
+        );
+        let result = conn
+            .execute(Statement::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                sql,
+                [
+                    recipient.into(),
+                    notification.kind.as_str().into(),
+                    notification.actor_id.into(),
+                    notification.group_key.into(),
+                    notification.payload.into(),
+                ],
+            ))
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Post and actor details shared by every payload.
+    async fn context<C: ConnectionTrait>(
+        conn: &C,
+        post_id: i32,
+        actor_id: i32,
+    ) -> DbResult<serde_json::Value> {
+        let post = post::Entity::find_by_id(post_id).one(conn).await?;
+        let actor = user::Entity::find_by_id(actor_id).one(conn).await?;
+        Ok(json!({
+            "post_id": post_id,
+            "post_title": post.as_ref().map(|post| post.title.clone()),
+            "post_slug": post.as_ref().map(|post| post.slug.clone()),
+            "post_locale": post.as_ref().map(|post| post.locale.clone()),
+            "actor_name": actor.map(|actor| actor.name),
+        }))
+    }
+
+    /// Tell the parent comment's author about `reply`. Failures are logged.
+    pub async fn comment_replied<C: ConnectionTrait>(
+        conn: &C,
+        reply: &post_comment::Model,
+        parent: &post_comment::Model,
+    ) {
+        if reply.user_id == parent.user_id {
+            return;
+        }
+        let recorded = async {
+            let mut payload = Self::context(conn, reply.post_id, reply.user_id).await?;
+            payload["comment_id"] = json!(reply.id);
+            payload["parent_id"] = json!(parent.id);
+            payload["excerpt"] = json!(excerpt(&reply.content));
+            Self::record(
+                conn,
+                Recipients::User(parent.user_id),
+                NewNotification {
+                    kind: NotificationType::CommentReply,
+                    actor_id: Some(reply.user_id),
+                    group_key: format!("comment_reply:{}", parent.id),
+                    payload,
+                },
+            )
+            .await
+        }
+        .await;
+        if let Err(err) = recorded {
+            error!(comment_id = reply.id, error = ?err, "Failed to record reply notification");
+        }
+    }
+
+    /// Tell the comment's author that `actor_id` liked it. Failures are
+    /// logged.
+    pub async fn comment_liked<C: ConnectionTrait>(
+        conn: &C,
+        comment: &post_comment::Model,
+        actor_id: i32,
+    ) {
+        let recorded = async {
+            let mut payload = Self::context(conn, comment.post_id, actor_id).await?;
+            payload["comment_id"] = json!(comment.id);
+            payload["excerpt"] = json!(excerpt(&comment.content));
+            Self::record(
+                conn,
+                Recipients::User(comment.user_id),
+                NewNotification {
+                    kind: NotificationType::CommentLike,
+                    actor_id: Some(actor_id),
+                    group_key: format!("comment_like:{}", comment.id),
+                    payload,
+                },
+            )
+            .await
+        }
+        .await;
+        if let Err(err) = recorded {
+            error!(comment_id = comment.id, error = ?err, "Failed to record like notification");
+        }
+    }
+
+    /// Tell the author's followers that `post` is out, unless it's behind a
+    /// role or password. Failures are logged.
+    pub async fn author_published<C: ConnectionTrait>(conn: &C, post: &post::Model) {
+        if !matches!(
+            post.visibility,
+            post::PostVisibility::Public | post::PostVisibility::Members
+        ) {
+            return;
+        }
+        let recorded = async {
+            let mut payload = Self::context(conn, post.id, post.author_id).await?;
+            payload["excerpt"] = json!(post.excerpt.as_deref().map(excerpt));
+            Self::record(
+                conn,
+                Recipients::FollowersOf(post.author_id),
+                NewNotification {
+                    kind: NotificationType::AuthorPublished,
+                    actor_id: Some(post.author_id),
+                    group_key: format!("author_published:{}", post.id),
+                    payload,
+                },
+            )
+            .await
+        }
+        .await;
+        if let Err(err) = recorded {
+            error!(post_id = post.id, error = ?err, "Failed to record publish notifications");
+        }
+    }
+
+    /// A user's notifications, most recently active first.
+    pub async fn list(
+        conn: &DbConn,
+        user_id: i32,
+        query: NotificationQuery,
+    ) -> DbResult<(Vec<Model>, u64)> {
+        let mut select = Entity::find().filter(Column::UserId.eq(user_id));
+        if query.unread_only {
+            select = select.filter(Column::ReadAt.is_null());
+        }
+        if let Some(kind) = query.kind {
+            select = select.filter(Column::Kind.eq(kind));
+        }
+
+        let page = query.page.filter(|page| *page > 0).unwrap_or(1);
+        let paginator = select
+            .order_by_desc(Column::UpdatedAt)
+            .order_by_desc(Column::Id)
+            .paginate(conn, Self::PER_PAGE);
+        let total = paginator.num_items().await?;
+        let items = paginator.fetch_page(page - 1).await?;
+        Ok((items, total))
+    }
+
+    pub async fn unread_count(conn: &DbConn, user_id: i32) -> DbResult<u64> {
+        Ok(Entity::find()
+            .filter(Column::UserId.eq(user_id))
+            .filter(Column::ReadAt.is_null())
+            .count(conn)
+            .await?)
+    }
+
+    /// Mark the user's notifications in `ids` read, or all of them when
+    /// `ids` is `None`. Returns how many changed.
+    pub async fn mark_read(conn: &DbConn, user_id: i32, ids: Option<Vec<i64>>) -> DbResult<u64> {
+        let mut update = Entity::update_many()
+            .col_expr(
+                Column::ReadAt,
+                Expr::value(chrono::Utc::now().fixed_offset()),
+            )
+            .filter(Column::UserId.eq(user_id))
+            .filter(Column::ReadAt.is_null());
+        if let Some(ids) = ids {
+            update = update.filter(Column::Id.is_in(ids));
+        }
+        Ok(update.exec(conn).await?.rows_affected)
+    }
+
+    /// Unread notifications not yet emailed, newest first.
+    pub async fn pending_digest(conn: &DbConn, user_id: i32, limit: u64) -> DbResult<Vec<Model>> {
+        Ok(Entity::find()
+            .filter(Column::UserId.eq(user_id))
+            .filter(Column::ReadAt.is_null())
+            .filter(Column::EmailedAt.is_null())
+            .order_by_desc(Column::UpdatedAt)
+            .limit(limit)
+            .all(conn)
+            .await?)
+    }
+
+    /// Record that `ids` went out in a digest; `mark_read` too when email is
+    /// the user's only channel.
+    pub async fn mark_emailed(conn: &DbConn, ids: Vec<i64>, mark_read: bool) -> DbResult<u64> {
+        let now = chrono::Utc::now().fixed_offset();
+        let mut update = Entity::update_many()
+            .col_expr(Column::EmailedAt, Expr::value(now))
+            .filter(Column::Id.is_in(ids));
+        if mark_read {
+            update = update.col_expr(Column::ReadAt, Expr::value(now));
+        }
+        Ok(update.exec(conn).await?.rows_affected)
+    }
+}
+
+/// First 140 characters of `text`.
+fn excerpt(text: &str) -> String {
+    const LEN: usize = 140;
+    let mut chars = text.trim().chars();
+    let mut excerpt: String = chars.by_ref().take(LEN).collect();
+    if chars.next().is_some() {
+        excerpt.push('…');
+    }
+    excerpt
+}
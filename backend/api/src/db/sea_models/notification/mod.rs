@@ -0,0 +1,6 @@
+pub mod actions;
+pub mod model;
+pub mod slice;
+
+pub use model::{ActiveModel, Column, Entity, Model, NotificationType, Relation};
+pub use slice::*;
@@ -0,0 +1,101 @@
+use schemars::JsonSchema;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    EnumIter,
+    DeriveActiveEnum,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "notification_type")]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationType {
+    /// Someone replied to the user's comment.
+    #[sea_orm(string_value = "comment_reply")]
+    CommentReply,
+    /// Someone liked the user's comment.
+    #[sea_orm(string_value = "comment_like")]
+    CommentLike,
+    /// An author the user follows published a post.
+    #[sea_orm(string_value = "author_published")]
+    AuthorPublished,
+}
+
+impl NotificationType {
+    pub const ALL: [Self; 3] = [Self::CommentReply, Self::CommentLike, Self::AuthorPublished];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::CommentReply => "comment_reply",
+            Self::CommentLike => "comment_like",
+            Self::AuthorPublished => "author_published",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.as_str() == value)
+    }
+}
+
+impl fmt::Display for NotificationType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize, JsonSchema)]
+#[sea_orm(table_name = "notifications")]
+#[schemars(rename = "Notification")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i64,
+
+    pub user_id: i32,
+    #[sea_orm(column_name = "type")]
+    #[serde(rename = "type")]
+    pub kind: NotificationType,
+    /// Who caused the latest notification in the group.
+    pub actor_id: Option<i32>,
+    /// Unread notifications with the same key are folded into one.
+    #[serde(skip_serializing)]
+    #[schemars(skip)]
+    pub group_key: String,
+    /// How many notifications the group stands for.
+    pub count: i32,
+    pub payload: Json,
+
+    pub read_at: Option<DateTimeWithTimeZone>,
+    #[serde(skip_serializing)]
+    #[schemars(skip)]
+    pub emailed_at: Option<DateTimeWithTimeZone>,
+
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::super::user::Entity",
+        from = "Column::UserId",
+        to = "super::super::user::Column::Id",
+        on_delete = "Cascade"
+    )]
+    User,
+}
+
+impl Related<super::super::user::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,32 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::NotificationType;
+
+/// A notification for one or more recipients; see `Entity::record`.
+#[derive(Clone, Debug)]
+pub struct NewNotification {
+    pub kind: NotificationType,
+    pub actor_id: Option<i32>,
+    pub group_key: String,
+    pub payload: serde_json::Value,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema)]
+pub struct NotificationQuery {
+    pub page: Option<u64>,
+    pub unread_only: bool,
+    pub kind: Option<NotificationType>,
+}
+
+/// Response of `/notification/v1/mark_read`
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct MarkReadResponse {
+    pub updated: u64,
+}
+
+/// Response of `/notification/v1/unread_count`
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct UnreadCount {
+    pub unread: u64,
+}
@@ -0,0 +1,80 @@
+use sea_orm::{entity::prelude::*, Condition, Set};
+
+use crate::error::DbResult;
+
+use super::*;
+
+/// Actions for notification preferences:
+/// - Read a user's preferences, falling back to the defaults
+/// - Save changes, creating the row on first use
+/// - Find users due an email digest
+impl Entity {
+    pub async fn for_user(conn: &DbConn, user_id: i32) -> DbResult<Model> {
+        Ok(Entity::find_by_id(user_id)
+            .one(conn)
+            .await?
+            .unwrap_or_else(|| Model::defaults(user_id)))
+    }
+
+    pub async fn save(conn: &DbConn, user_id: i32, changes: PreferenceUpdate) -> DbResult<Model> {
+        let existing = Entity::find_by_id(user_id).one(conn).await?;
+        let is_new = existing.is_none();
+        let current = existing.unwrap_or_else(|| Model::defaults(user_id));
+        let was_off = current.email_digest == DigestFrequency::Off;
+
+        let mut active: ActiveModel = current.into();
+        if let Some(in_app) = changes.in_app {
+            active.in_app = Set(in_app);
+        }
+        if let Some(email_digest) = changes.email_digest {
+            active.email_digest = Set(email_digest);
+            // The first digest covers what arrives from now on.
+            if was_off && email_digest != DigestFrequency::Off {
+                active.last_digest_at = Set(Some(chrono::Utc::now().fixed_offset()));
+            }
+        }
+        if let Some(muted_types) = changes.muted_types {
+            active.muted_types = Set(muted_types);
+        }
+        active.updated_at = Set(chrono::Utc::now().fixed_offset());
+
+        if is_new {
+            Ok(active.insert(conn).await?)
+        } else {
+            Ok(active.update(conn).await?)
+        }
+    }
+
+    /// Users whose digest period has passed since their last one.
+    pub async fn due_for_digest(conn: &DbConn, now: DateTimeWithTimeZone) -> DbResult<Vec<Model>> {
+        let due = |frequency: DigestFrequency| {
+            let cutoff = now - frequency.period().unwrap_or_default();
+            Condition::all().add(Column::EmailDigest.eq(frequency)).add(
+                Condition::any()
+                    .add(Column::LastDigestAt.is_null())
+                    .add(Column::LastDigestAt.lte(cutoff)),
+            )
+        };
+        Ok(Entity::find()
+            .filter(
+                Condition::any()
+                    .add(due(DigestFrequency::Daily))
+                    .add(due(DigestFrequency::Weekly)),
+            )
+            .all(conn)
+            .await?)
+    }
+
+    pub async fn set_last_digest_at(
+        conn: &DbConn,
+        user_id: i32,
+        at: DateTimeWithTimeZone,
+    ) -> DbResult<()> {
+        Entity::update_many()
+            .col_expr(Column::LastDigestAt, Expr::value(at))
+            .filter(Column::UserId.eq(user_id))
+            .exec(conn)
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,107 @@
+use schemars::JsonSchema;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    EnumIter,
+    DeriveActiveEnum,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+)]
+#[sea_orm(
+    rs_type = "String",
+    db_type = "Enum",
+    enum_name = "notification_digest"
+)]
+#[serde(rename_all = "lowercase")]
+pub enum DigestFrequency {
+    #[default]
+    #[sea_orm(string_value = "off")]
+    Off,
+    #[sea_orm(string_value = "daily")]
+    Daily,
+    #[sea_orm(string_value = "weekly")]
+    Weekly,
+}
+
+impl DigestFrequency {
+    /// Time between two digests.
+    pub fn period(self) -> Option<chrono::Duration> {
+        match self {
+            Self::Off => None,
+            Self::Daily => Some(chrono::Duration::days(1)),
+            Self::Weekly => Some(chrono::Duration::days(7)),
+        }
+    }
+}
+
+impl fmt::Display for DigestFrequency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::Off => "off",
+            Self::Daily => "daily",
+            Self::Weekly => "weekly",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// A user's notification channels. Users without a row get
+/// [`Model::defaults`].
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize, JsonSchema)]
+#[sea_orm(table_name = "notification_preferences")]
+#[schemars(rename = "NotificationPreference")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub user_id: i32,
+
+    /// Show notifications in the notification center.
+    pub in_app: bool,
+    /// Email unread notifications at this interval.
+    pub email_digest: DigestFrequency,
+    /// Notification types the user doesn't want at all.
+    pub muted_types: Vec<String>,
+    pub last_digest_at: Option<DateTimeWithTimeZone>,
+
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+impl Model {
+    pub fn defaults(user_id: i32) -> Self {
+        Self {
+            user_id,
+            in_app: true,
+            email_digest: DigestFrequency::Off,
+            muted_types: Vec::new(),
+            last_digest_at: None,
+            updated_at: chrono::Utc::now().fixed_offset(),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::super::user::Entity",
+        from = "Column::UserId",
+        to = "super::super::user::Column::Id",
+        on_delete = "Cascade"
+    )]
+    User,
+}
+
+impl Related<super::super::user::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
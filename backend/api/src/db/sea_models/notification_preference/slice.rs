@@ -0,0 +1,11 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::DigestFrequency;
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema)]
+pub struct PreferenceUpdate {
+    pub in_app: Option<bool>,
+    pub email_digest: Option<DigestFrequency>,
+    pub muted_types: Option<Vec<String>>,
+}
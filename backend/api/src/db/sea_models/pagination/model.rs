@@ -1,14 +1,15 @@
+use schemars::JsonSchema;
 use sea_orm::{DbConn, DbErr, EntityTrait, PaginatorTrait, Select};
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct PagedResult<Model> {
     // Changed T to Model for clarity, and data type to Vec<Model>
     pub data: Vec<Model>,
     pub page: Page,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Page {
     pub page_number: u64,
     pub page_size: u64,
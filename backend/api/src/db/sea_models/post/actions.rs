@@ -1,10 +1,25 @@
 use std::collections::HashSet;
 
-use crate::{db::sea_models::tag, error::DbResult};
+use crate::{
+    db::sea_models::{
+        notification,
+        post_author::{self, PostAuthorRole},
+        slug_redirect::{self, RedirectEntity},
+        tag,
+        webhook::{self, events},
+    },
+    error::{DbResult, ErrorCode, ErrorResponse},
+    utils::default_locale,
+};
 use sea_orm::{
-    entity::prelude::*, prelude::Expr, sea_query::Alias, Condition, JoinType, Order, QueryOrder,
-    QuerySelect, Set, TransactionTrait,
+    entity::prelude::*,
+    prelude::Expr,
+    sea_query::{Alias, SimpleExpr},
+    Condition, JoinType, Order, QueryOrder, QuerySelect, Set, TransactionTrait,
 };
+use serde_json::json;
+use std::collections::HashMap;
+use tokio::task;
 use tracing::{error, info, instrument, warn};
 
 use super::*;
@@ -224,7 +239,10 @@ impl Entity {
             )
     }
 
-    async fn sanitized_tag_ids(conn: &DbConn, tag_ids: Vec<i32>) -> DbResult<Vec<i32>> {
+    async fn sanitized_tag_ids<C: ConnectionTrait>(
+        conn: &C,
+        tag_ids: Vec<i32>,
+    ) -> DbResult<Vec<i32>> {
         let mut sanitized_ids = Vec::new();
         tag::Entity::find()
             .filter(tag::Column::Id.is_in(tag_ids))
@@ -237,11 +255,42 @@ impl Entity {
         Ok(sanitized_ids)
     }
 
+    /// Post summary sent with `post.*` webhook events.
+    fn webhook_data(post: &Model) -> serde_json::Value {
+        json!({
+            "id": post.id,
+            "title": post.title,
+            "slug": post.slug,
+            "excerpt": post.excerpt,
+            "status": post.status,
+            "visibility": post.visibility,
+            "locale": post.locale,
+            "author_id": post.author_id,
+            "category_id": post.category_id,
+            "tag_ids": post.tag_ids,
+            "published_at": post.published_at,
+            "updated_at": post.updated_at,
+        })
+    }
+
+    async fn hash_password(password: String) -> DbResult<String> {
+        task::spawn_blocking(move || password_auth::generate_hash(password))
+            .await
+            .map_err(|_| {
+                ErrorResponse::new(ErrorCode::InternalServerError)
+                    .with_message("Failed to generate password hash")
+            })
+    }
+
     #[instrument(skip(conn, new_post), fields(post_id, author_id = new_post.author_id, slug = %new_post.slug))]
     pub async fn create(conn: &DbConn, new_post: NewPost) -> DbResult<PostWithRelations> {
         let now = chrono::Utc::now().fixed_offset();
 
         let sanitized_tag_ids = Self::sanitized_tag_ids(conn, new_post.tag_ids).await?;
+        let password_hash = match new_post.password {
+            Some(password) => Some(Self::hash_password(password).await?),
+            None => None,
+        };
 
         let post = ActiveModel {
             title: Set(new_post.title),
@@ -256,6 +305,18 @@ impl Entity {
             view_count: Set(new_post.view_count),
             likes_count: Set(new_post.likes_count),
             tag_ids: Set(sanitized_tag_ids),
+            visibility: Set(new_post.visibility),
+            min_role: Set(new_post.min_role),
+            password_hash: Set(password_hash),
+            locale: Set(new_post
+                .locale
+                .unwrap_or_else(|| default_locale().to_string())),
+            meta_title: Set(new_post.meta_title),
+            meta_description: Set(new_post.meta_description),
+            canonical_url: Set(new_post.canonical_url),
+            noindex: Set(new_post.noindex),
+            og_image_id: Set(new_post.og_image_id),
+            og_image_variant: Set(new_post.og_image_variant),
             created_at: Set(now),
             updated_at: Set(now),
             ..Default::default()
@@ -264,11 +325,18 @@ impl Entity {
         match post.insert(conn).await {
             Ok(model) => {
                 tracing::Span::current().record("post_id", model.id);
+                post_author::Entity::add_owner(conn, model.id, model.author_id).await?;
                 info!(
                     post_id = model.id,
                     author_id = model.author_id,
                     "Post created"
                 );
+                webhook::Entity::emit(conn, events::POST_CREATED, Self::webhook_data(&model)).await;
+                if model.status == PostStatus::Published {
+                    webhook::Entity::emit(conn, events::POST_PUBLISHED, Self::webhook_data(&model))
+                        .await;
+                    notification::Entity::author_published(conn, &model).await;
+                }
                 Self::find_by_id_or_slug(conn, Some(model.id), None)
                     .await?
                     .ok_or_else(|| {
@@ -285,82 +353,202 @@ impl Entity {
         }
     }
 
-    #[instrument(skip(conn, update_post), fields(post_id))]
-    pub async fn update(
-        conn: &DbConn,
-        post_id: i32,
+    /// Apply `update_post` to a loaded post.
+    async fn apply_update<C: ConnectionTrait>(
+        conn: &C,
+        post_model: Model,
         update_post: UpdatePost,
-    ) -> DbResult<Option<PostWithRelations>> {
-        let post: Option<Model> = Self::find_by_id(post_id).one(conn).await?;
+    ) -> DbResult<Model> {
+        let post_id = post_model.id;
+        let old_slug = post_model.slug.clone();
+        let old_locale = post_model.locale.clone();
+        let old_status = post_model.status;
+        let mut post_active: ActiveModel = post_model.into();
 
-        if let Some(post_model) = post {
-            let mut post_active: ActiveModel = post_model.into();
+        if let Some(title) = update_post.title {
+            post_active.title = Set(title);
+        }
 
-            if let Some(title) = update_post.title {
-                post_active.title = Set(title);
-            }
+        if let Some(slug) = update_post.slug {
+            post_active.slug = Set(slug);
+        }
 
-            if let Some(slug) = update_post.slug {
-                post_active.slug = Set(slug);
-            }
+        if let Some(content) = update_post.content {
+            post_active.content = Set(content);
+        }
 
-            if let Some(content) = update_post.content {
-                post_active.content = Set(content);
-            }
+        if let Some(excerpt) = update_post.excerpt {
+            post_active.excerpt = Set(Some(excerpt));
+        }
 
-            if let Some(excerpt) = update_post.excerpt {
-                post_active.excerpt = Set(Some(excerpt));
-            }
+        if let Some(featured_image_id) = update_post.featured_image_id {
+            post_active.featured_image_id = Set(featured_image_id);
+        }
 
-            if let Some(featured_image_id) = update_post.featured_image_id {
-                post_active.featured_image_id = Set(featured_image_id);
-            }
+        if let Some(status) = update_post.status {
+            post_active.status = Set(status);
+        }
 
-            if let Some(status) = update_post.status {
-                post_active.status = Set(status);
-            }
+        if let Some(published_at) = update_post.published_at {
+            post_active.published_at = Set(Some(published_at));
+        }
 
-            if let Some(published_at) = update_post.published_at {
-                post_active.published_at = Set(Some(published_at));
-            }
+        if let Some(category_id) = update_post.category_id {
+            post_active.category_id = Set(category_id);
+        }
 
-            if let Some(category_id) = update_post.category_id {
-                post_active.category_id = Set(category_id);
-            }
+        if let Some(view_count) = update_post.view_count {
+            post_active.view_count = Set(view_count);
+        }
 
-            if let Some(view_count) = update_post.view_count {
-                post_active.view_count = Set(view_count);
-            }
+        if let Some(likes_count) = update_post.likes_count {
+            post_active.likes_count = Set(likes_count);
+        }
 
-            if let Some(likes_count) = update_post.likes_count {
-                post_active.likes_count = Set(likes_count);
-            }
+        if let Some(tag_ids) = update_post.tag_ids {
+            let sanitized_tag_ids = Self::sanitized_tag_ids(conn, tag_ids).await?;
+            post_active.tag_ids = Set(sanitized_tag_ids);
+        }
 
-            if let Some(tag_ids) = update_post.tag_ids {
-                let sanitized_tag_ids = Self::sanitized_tag_ids(conn, tag_ids).await?;
-                post_active.tag_ids = Set(sanitized_tag_ids);
+        if let Some(visibility) = update_post.visibility {
+            post_active.visibility = Set(visibility);
+        }
+
+        if let Some(min_role) = update_post.min_role {
+            post_active.min_role = Set(min_role);
+        }
+
+        match update_post.password {
+            Some(Some(password)) => {
+                post_active.password_hash = Set(Some(Self::hash_password(password).await?));
             }
+            Some(None) => post_active.password_hash = Set(None),
+            None => {}
+        }
 
-            post_active.updated_at = Set(update_post.updated_at);
+        if let Some(locale) = update_post.locale {
+            post_active.locale = Set(locale);
+        }
 
-            match post_active.update(conn).await {
-                Ok(updated_post) => {
-                    info!(post_id, "Post updated");
-                    Self::find_by_id_or_slug(conn, Some(updated_post.id), None).await
-                }
-                Err(err) => {
-                    error!(post_id, "Failed to update post: {}", err);
-                    Err(err.into())
+        if let Some(meta_title) = update_post.meta_title {
+            post_active.meta_title = Set(meta_title);
+        }
+        if let Some(meta_description) = update_post.meta_description {
+            post_active.meta_description = Set(meta_description);
+        }
+        if let Some(canonical_url) = update_post.canonical_url {
+            post_active.canonical_url = Set(canonical_url);
+        }
+        if let Some(noindex) = update_post.noindex {
+            post_active.noindex = Set(noindex);
+        }
+        if let Some(og_image_id) = update_post.og_image_id {
+            post_active.og_image_id = Set(og_image_id);
+        }
+        if let Some(og_image_variant) = update_post.og_image_variant {
+            post_active.og_image_variant = Set(og_image_variant);
+        }
+
+        post_active.updated_at = Set(update_post.updated_at);
+
+        match post_active.update(conn).await {
+            Ok(updated_post) => {
+                info!(post_id, "Post updated");
+                slug_redirect::Entity::record_slug_change(
+                    conn,
+                    RedirectEntity::Post,
+                    post_id,
+                    Some(&old_locale),
+                    &old_slug,
+                    Some(&updated_post.locale),
+                    &updated_post.slug,
+                )
+                .await?;
+                webhook::Entity::emit(
+                    conn,
+                    events::POST_UPDATED,
+                    Self::webhook_data(&updated_post),
+                )
+                .await;
+                if updated_post.status == PostStatus::Published
+                    && old_status != PostStatus::Published
+                {
+                    webhook::Entity::emit(
+                        conn,
+                        events::POST_PUBLISHED,
+                        Self::webhook_data(&updated_post),
+                    )
+                    .await;
+                    notification::Entity::author_published(conn, &updated_post).await;
                 }
+                Ok(updated_post)
             }
+            Err(err) => {
+                error!(post_id, "Failed to update post: {}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    #[instrument(skip(conn, update_post), fields(post_id))]
+    pub async fn update(
+        conn: &DbConn,
+        post_id: i32,
+        update_post: UpdatePost,
+    ) -> DbResult<Option<PostWithRelations>> {
+        let post: Option<Model> = Self::find_by_id(post_id).one(conn).await?;
+
+        if let Some(post_model) = post {
+            let updated_post = Self::apply_update(conn, post_model, update_post).await?;
+            Self::find_by_id_or_slug(conn, Some(updated_post.id), None).await
         } else {
             warn!(post_id, "Post not found for update");
             Ok(None)
         }
     }
 
+    /// Optimistic-concurrency update: applies `update_post` only if the stored
+    /// `updated_at` still equals `expected_updated_at` (the version the editor
+    /// started from). The row is locked while comparing, so two concurrent
+    /// saves from the same version can't both succeed.
+    #[instrument(skip(conn, update_post), fields(post_id))]
+    pub async fn update_if_unmodified(
+        conn: &DbConn,
+        post_id: i32,
+        expected_updated_at: DateTimeWithTimeZone,
+        update_post: UpdatePost,
+    ) -> DbResult<UpdateOutcome> {
+        let txn = conn.begin().await?;
+
+        let Some(post_model) = Self::find_by_id(post_id).lock_exclusive().one(&txn).await? else {
+            warn!(post_id, "Post not found for update");
+            return Ok(UpdateOutcome::NotFound);
+        };
+
+        let post_model = match UpdateOutcome::check_unmodified(post_model, expected_updated_at) {
+            Ok(post_model) => post_model,
+            Err(conflict) => {
+                info!(post_id, "Stale post update rejected");
+                return Ok(conflict);
+            }
+        };
+
+        let updated_post = Self::apply_update(&txn, post_model, update_post).await?;
+        txn.commit().await?;
+
+        Ok(
+            match Self::find_by_id_or_slug(conn, Some(updated_post.id), None).await? {
+                Some(post) => UpdateOutcome::Updated(Box::new(post)),
+                None => UpdateOutcome::NotFound,
+            },
+        )
+    }
+
     #[instrument(skip(conn), fields(post_id))]
     pub async fn delete(conn: &DbConn, post_id: i32) -> DbResult<u64> {
+        let existing = Self::find_by_id(post_id).one(conn).await?;
+        slug_redirect::Entity::delete_for_target(conn, RedirectEntity::Post, post_id).await?;
+
         match Self::delete_by_id(post_id).exec(conn).await {
             Ok(result) => {
                 info!(
@@ -368,6 +556,14 @@ impl Entity {
                     rows_affected = result.rows_affected,
                     "Post deleted"
                 );
+                if let (Some(post), true) = (existing, result.rows_affected > 0) {
+                    webhook::Entity::emit(
+                        conn,
+                        events::POST_DELETED,
+                        json!({ "id": post.id, "slug": post.slug, "title": post.title }),
+                    )
+                    .await;
+                }
                 Ok(result.rows_affected)
             }
             Err(err) => {
@@ -387,12 +583,32 @@ impl Entity {
 
         query = match (post_id, post_slug.clone()) {
             (Some(id), _) => query.filter(Column::Id.eq(id)),
-            (_, Some(slug)) => query.filter(Column::Slug.eq(slug)),
+            // Slugs are unique per locale; prefer the default-locale post
+            (_, Some(slug)) => query.filter(Column::Slug.eq(slug)).order_by(
+                Expr::col((Entity, Column::Locale)).eq(default_locale()),
+                Order::Desc,
+            ),
             _ => return Ok(None),
         };
 
         let post_result = query.into_model::<PostWithJoinedData>().one(conn).await?;
 
+        // Old slug: answer with the post that owns it now
+        if post_result.is_none() && post_id.is_none() {
+            if let Some(slug) = post_slug.as_deref() {
+                if let Some(redirect) =
+                    slug_redirect::Entity::resolve(conn, RedirectEntity::Post, None, slug).await?
+                {
+                    return Box::pin(Self::find_by_id_or_slug(
+                        conn,
+                        Some(redirect.target_id),
+                        None,
+                    ))
+                    .await;
+                }
+            }
+        }
+
         if let Some(post_data) = post_result {
             let mut tags = Vec::new();
             if !post_data.tag_ids.is_empty() {
@@ -411,12 +627,57 @@ impl Entity {
                 }
             }
 
-            return Ok(Some(post_data.into_relation(tags)));
+            let mut post = post_data.into_relation(tags);
+            post.contributors = post_author::Entity::list_for_post(conn, post.id).await?;
+            return Ok(Some(post));
         }
 
         Ok(None)
     }
 
+    /// The post with `slug` in `locale`, or the one an old slug of that
+    /// locale redirects to; whichever post has the slug when the locale has
+    /// neither.
+    pub async fn find_localized(
+        conn: &DbConn,
+        slug: String,
+        locale: &str,
+    ) -> DbResult<Option<PostWithRelations>> {
+        let localized = Self::find()
+            .filter(Column::Slug.eq(slug.as_str()))
+            .filter(Column::Locale.eq(locale))
+            .one(conn)
+            .await?;
+        if let Some(post) = localized {
+            return Self::find_by_id_or_slug(conn, Some(post.id), None).await;
+        }
+        match slug_redirect::Entity::resolve(conn, RedirectEntity::Post, Some(locale), &slug)
+            .await?
+        {
+            Some(redirect) => Self::find_by_id_or_slug(conn, Some(redirect.target_id), None).await,
+            None => Self::find_by_id_or_slug(conn, None, Some(slug)).await,
+        }
+    }
+
+    /// Posts in `locale`. With `fallback`, also default-locale posts that
+    /// have no published translation in `locale`. `locale` must be
+    /// normalized.
+    pub fn locale_filter(locale: &str, fallback: bool) -> SimpleExpr {
+        let default = default_locale();
+        if !fallback || locale == default {
+            return Expr::col((Entity, Column::Locale)).eq(locale);
+        }
+        Expr::cust_with_values(
+            r#"("posts"."locale" = $1 OR ("posts"."locale" = $2 AND NOT EXISTS (
+                SELECT 1 FROM "posts" AS "translation"
+                WHERE "translation"."translation_group_id" = "posts"."translation_group_id"
+                  AND "translation"."locale" = $1
+                  AND "translation"."status" = 'published'
+            )))"#,
+            [locale, default],
+        )
+    }
+
     // Search posts with query parameters and optionally load relations
     pub async fn search(
         conn: &DbConn,
@@ -433,8 +694,30 @@ impl Entity {
             post_query = post_query.filter(Column::Status.eq(status_filter));
         }
 
+        if let Some(visibility) = query.visibility {
+            post_query = post_query.filter(Column::Visibility.eq(visibility));
+        }
+
+        if let Some(locale) = query.locale.as_deref() {
+            post_query = post_query.filter(Self::locale_filter(
+                locale,
+                query.locale_fallback.unwrap_or(true),
+            ));
+        }
+
         if let Some(author_id_filter) = query.author_id {
-            post_query = post_query.filter(Column::AuthorId.eq(author_id_filter));
+            post_query = post_query.filter(Expr::cust(format!(
+                "posts.id IN (SELECT post_id FROM post_authors WHERE user_id = {} AND role = '{}')",
+                author_id_filter,
+                PostAuthorRole::Author
+            )));
+        }
+
+        if let Some(contributor_id) = query.contributor_id {
+            post_query = post_query.filter(Expr::cust(format!(
+                "posts.id IN (SELECT post_id FROM post_authors WHERE user_id = {})",
+                contributor_id
+            )));
         }
 
         // Date range filters
@@ -458,7 +741,14 @@ impl Entity {
         }
 
         if let Some(category_id_filter) = query.category_id {
-            post_query = post_query.filter(Column::CategoryId.eq(category_id_filter));
+            if query.include_subcategories.unwrap_or(false) {
+                let category_ids =
+                    super::super::category::Entity::descendant_ids(conn, category_id_filter)
+                        .await?;
+                post_query = post_query.filter(Column::CategoryId.is_in(category_ids));
+            } else {
+                post_query = post_query.filter(Column::CategoryId.eq(category_id_filter));
+            }
         }
 
         if let Some(search_term) = &query.search {
@@ -548,6 +838,9 @@ impl Entity {
             std::collections::HashMap::new()
         };
 
+        let post_ids: Vec<i32> = posts_joined.iter().map(|p| p.id).collect();
+        let mut contributors = post_author::Entity::list_for_posts(conn, &post_ids).await?;
+
         // Map joined data to PostWithRelations
         let posts_with_relations: Vec<PostWithRelations> = posts_joined
             .into_iter()
@@ -559,7 +852,9 @@ impl Entity {
                     .collect::<Vec<PostTag>>();
 
                 // Convert joined data to PostWithRelations
-                joined_data.into_relation(post_tags)
+                let mut post = joined_data.into_relation(post_tags);
+                post.contributors = contributors.remove(&post.id).unwrap_or_default();
+                post
             })
             .collect();
 
@@ -575,13 +870,18 @@ impl Entity {
             status: Some(PostStatus::Published),
             title: None,
             author_id: query.author_id,
+            contributor_id: None,
             sorts: Some(vec![crate::utils::SortParam {
                 field: "updated_at".to_string(),
                 order: sea_orm::Order::Desc,
             }]),
             category_id: query.category_id,
+            include_subcategories: query.include_subcategories,
             search: None,
             tag_ids: query.tag_ids,
+            visibility: query.visibility,
+            locale: query.locale,
+            locale_fallback: query.locale_fallback,
             created_at_gt: None,
             created_at_lt: None,
             updated_at_gt: None,
@@ -593,17 +893,100 @@ impl Entity {
         Self::search(conn, query).await
     }
 
+    /// Posts that belong in the sitemap: published, public and indexable.
+    fn sitemap_query() -> Select<Entity> {
+        Self::find()
+            .filter(Column::Status.eq(PostStatus::Published))
+            .filter(Column::Visibility.eq(PostVisibility::Public))
+            .filter(Column::Noindex.eq(false))
+    }
+
+    pub async fn sitemap_count(conn: &DbConn) -> DbResult<u64> {
+        Ok(Self::sitemap_query().count(conn).await?)
+    }
+
     // Sitemap data for published posts
     pub async fn sitemap(conn: &DbConn) -> DbResult<Vec<PostSitemap>> {
-        let sitemaps = Self::find()
+        Self::load_sitemap(conn, None).await
+    }
+
+    /// `limit` sitemap entries from `offset`, in ID order.
+    pub async fn sitemap_chunk(
+        conn: &DbConn,
+        offset: u64,
+        limit: u64,
+    ) -> DbResult<Vec<PostSitemap>> {
+        Self::load_sitemap(conn, Some((offset, limit))).await
+    }
+
+    async fn load_sitemap(conn: &DbConn, window: Option<(u64, u64)>) -> DbResult<Vec<PostSitemap>> {
+        let columns = vec![
+            Column::Id,
+            Column::Slug,
+            Column::Locale,
+            Column::TranslationGroupId,
+            Column::CanonicalUrl,
+            Column::UpdatedAt,
+            Column::PublishedAt,
+        ];
+        let mut query = Self::sitemap_query()
             .select_only()
-            .columns(vec![Column::Slug, Column::UpdatedAt, Column::PublishedAt])
-            .filter(Column::Status.eq(PostStatus::Published))
-            .into_model::<PostSitemap>()
-            .all(conn)
-            .await?;
+            .columns(columns.clone())
+            .order_by_asc(Column::Id);
+        if let Some((offset, limit)) = window {
+            query = query.offset(offset).limit(limit);
+        }
+        let rows = query.into_model::<PostSitemapRow>().all(conn).await?;
 
-        Ok(sitemaps)
+        let post_ids: Vec<i32> = rows.iter().map(|r| r.id).collect();
+        let mut contributors = post_author::Entity::list_for_posts(conn, &post_ids).await?;
+
+        // Translations may sit in other chunks; load every member of the
+        // groups in this one.
+        let group_ids: HashSet<i32> = rows.iter().filter_map(|r| r.translation_group_id).collect();
+        let members = if group_ids.is_empty() {
+            Vec::new()
+        } else {
+            Self::sitemap_query()
+                .select_only()
+                .columns(columns)
+                .filter(Column::TranslationGroupId.is_in(group_ids))
+                .into_model::<PostSitemapRow>()
+                .all(conn)
+                .await?
+        };
+        let mut groups: HashMap<i32, Vec<(String, String)>> = HashMap::new();
+        for member in &members {
+            if let Some(group_id) = member.translation_group_id {
+                groups
+                    .entry(group_id)
+                    .or_default()
+                    .push((member.locale.clone(), member.slug.clone()));
+            }
+        }
+
+        Ok(rows
+            .into_iter()
+            .map(|row| PostSitemap {
+                alternates: row
+                    .translation_group_id
+                    .and_then(|group_id| groups.get(&group_id))
+                    .map(|group| sitemap_alternates(group, default_locale()))
+                    .unwrap_or_default(),
+                locale: row.locale,
+                canonical_url: row.canonical_url,
+                authors: contributors
+                    .remove(&row.id)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter(|c| c.role == PostAuthorRole::Author)
+                    .map(|c| c.name)
+                    .collect(),
+                slug: row.slug,
+                updated_at: row.updated_at,
+                published_at: row.published_at,
+            })
+            .collect())
     }
 
     pub async fn increment_view_count(
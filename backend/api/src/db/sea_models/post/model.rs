@@ -1,8 +1,26 @@
+use schemars::JsonSchema;
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+use super::super::user::UserRole;
+
+fn default_locale() -> String {
+    crate::utils::default_locale().to_string()
+}
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    EnumIter,
+    DeriveActiveEnum,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+)]
 #[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "post_status")]
 pub enum PostStatus {
     #[sea_orm(string_value = "draft")]
@@ -11,6 +29,15 @@ pub enum PostStatus {
     Published,
     #[sea_orm(string_value = "archived")]
     Archived,
+    /// Submitted for editorial review.
+    #[sea_orm(string_value = "in_review")]
+    InReview,
+    /// A reviewer sent it back to its authors.
+    #[sea_orm(string_value = "changes_requested")]
+    ChangesRequested,
+    /// Cleared for publishing.
+    #[sea_orm(string_value = "approved")]
+    Approved,
 }
 
 impl fmt::Display for PostStatus {
@@ -19,12 +46,58 @@ impl fmt::Display for PostStatus {
             Self::Draft => write!(f, "draft"),
             Self::Published => write!(f, "published"),
             Self::Archived => write!(f, "archived"),
+            Self::InReview => write!(f, "in_review"),
+            Self::ChangesRequested => write!(f, "changes_requested"),
+            Self::Approved => write!(f, "approved"),
+        }
+    }
+}
+
+/// Who may read a published post in full. Everyone else gets a teaser.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    EnumIter,
+    DeriveActiveEnum,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "post_visibility")]
+#[serde(rename_all = "lowercase")]
+pub enum PostVisibility {
+    #[default]
+    #[sea_orm(string_value = "public")]
+    Public,
+    /// Any logged-in user.
+    #[sea_orm(string_value = "members")]
+    Members,
+    /// Users with at least the post's `min_role`.
+    #[sea_orm(string_value = "role")]
+    Role,
+    /// Anyone with the post's password.
+    #[sea_orm(string_value = "password")]
+    Password,
+}
+
+impl fmt::Display for PostVisibility {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Public => write!(f, "public"),
+            Self::Members => write!(f, "members"),
+            Self::Role => write!(f, "role"),
+            Self::Password => write!(f, "password"),
         }
     }
 }
 
-#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize, JsonSchema)]
 #[sea_orm(table_name = "posts")]
+#[schemars(rename = "Post")]
 pub struct Model {
     #[sea_orm(primary_key)]
     pub id: i32,
@@ -43,6 +116,26 @@ pub struct Model {
     pub likes_count: i32,
     pub tag_ids: Vec<i32>,
 
+    #[serde(default)]
+    pub visibility: PostVisibility,
+    pub min_role: Option<UserRole>,
+    #[serde(skip_serializing)]
+    #[schemars(skip)]
+    pub password_hash: Option<String>,
+
+    #[serde(default = "default_locale")]
+    pub locale: String,
+    pub translation_group_id: Option<i32>,
+
+    pub meta_title: Option<String>,
+    pub meta_description: Option<String>,
+    pub canonical_url: Option<String>,
+    #[serde(default)]
+    pub noindex: bool,
+    pub og_image_id: Option<i32>,
+    /// `media_variants.variant_type` of `og_image_id` to share.
+    pub og_image_variant: Option<String>,
+
     pub created_at: DateTimeWithTimeZone,
     pub updated_at: DateTimeWithTimeZone,
 }
@@ -71,6 +164,13 @@ pub enum Relation {
         to = "super::super::media::Column::Id"
     )]
     FeaturedImage,
+    #[sea_orm(
+        belongs_to = "super::super::post_translation_group::Entity",
+        from = "Column::TranslationGroupId",
+        to = "super::super::post_translation_group::Column::Id",
+        on_delete = "SetNull"
+    )]
+    TranslationGroup,
     // We're using a tag_ids array directly in the Post model for now
     // but for now, just removing this relation
 }
@@ -1,10 +1,13 @@
-use super::PostStatus;
+use super::super::post_author::PostContributor;
+use super::super::user::{self, UserRole};
+use super::{Model, PostStatus, PostVisibility};
 use chrono::{DateTime, FixedOffset};
+use schemars::JsonSchema;
 use sea_orm::prelude::{DateTimeWithTimeZone, Json};
 use sea_orm::FromQueryResult;
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 pub struct AuthorMedia {
     pub id: i32,
     pub object_key: String,
@@ -15,7 +18,8 @@ pub struct AuthorMedia {
     pub size: i64,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[schemars(rename = "PostCategoryMedia")]
 pub struct CategoryMedia {
     pub id: i32,
     pub object_key: String,
@@ -40,6 +44,18 @@ pub struct NewPost {
     pub view_count: i32,
     pub likes_count: i32,
     pub tag_ids: Vec<i32>,
+    pub visibility: PostVisibility,
+    pub min_role: Option<UserRole>,
+    /// Plain text; hashed before it is stored.
+    pub password: Option<String>,
+    /// Normalized tag; the default locale when `None`.
+    pub locale: Option<String>,
+    pub meta_title: Option<String>,
+    pub meta_description: Option<String>,
+    pub canonical_url: Option<String>,
+    pub noindex: bool,
+    pub og_image_id: Option<i32>,
+    pub og_image_variant: Option<String>,
 }
 
 #[derive(Deserialize, Debug)]
@@ -56,18 +72,66 @@ pub struct UpdatePost {
     pub view_count: Option<i32>,
     pub likes_count: Option<i32>,
     pub tag_ids: Option<Vec<i32>>,
+    pub visibility: Option<PostVisibility>,
+    pub min_role: Option<Option<UserRole>>,
+    /// Plain text; hashed before it is stored. `Some(None)` drops the
+    /// stored hash.
+    pub password: Option<Option<String>>,
+    pub locale: Option<String>,
+    pub meta_title: Option<Option<String>>,
+    pub meta_description: Option<Option<String>>,
+    pub canonical_url: Option<Option<String>>,
+    pub noindex: Option<bool>,
+    pub og_image_id: Option<Option<i32>>,
+    pub og_image_variant: Option<Option<String>>,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+/// Result of `Entity::update_if_unmodified`.
+#[derive(Debug)]
+pub enum UpdateOutcome {
+    Updated(Box<PostWithRelations>),
+    /// The post changed since the editor loaded it; carries the stored row.
+    Conflict(Box<Model>),
+    NotFound,
+}
+
+impl UpdateOutcome {
+    /// Hands `current` back unless it moved on from the `updated_at` the
+    /// editor loaded, in which case the save is a `Conflict` carrying it.
+    /// Postgres keeps microseconds, so the comparison stops there.
+    pub fn check_unmodified(
+        current: Model,
+        expected_updated_at: DateTimeWithTimeZone,
+    ) -> Result<Model, Self> {
+        if current.updated_at.timestamp_micros() == expected_updated_at.timestamp_micros() {
+            Ok(current)
+        } else {
+            Err(Self::Conflict(Box::new(current)))
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, JsonSchema)]
 pub struct PostQuery {
     pub page_no: Option<u64>,
     pub title: Option<String>,
     pub status: Option<PostStatus>,
+    /// Posts crediting this user as an author.
     pub author_id: Option<i32>,
+    /// Posts crediting this user in any role; scopes author-level users to
+    /// the posts they work on.
+    pub contributor_id: Option<i32>,
     pub sorts: Option<Vec<crate::utils::SortParam>>,
     pub category_id: Option<i32>,
+    /// Also match posts in categories below `category_id`.
+    pub include_subcategories: Option<bool>,
     pub search: Option<String>,
     pub tag_ids: Option<Vec<i32>>,
+    pub visibility: Option<PostVisibility>,
+    pub locale: Option<String>,
+    /// With `locale`, also list default-locale posts that have no
+    /// translation in it (default true).
+    pub locale_fallback: Option<bool>,
     // Date range filters
     pub created_at_gt: Option<DateTimeWithTimeZone>,
     pub created_at_lt: Option<DateTimeWithTimeZone>,
@@ -77,7 +141,7 @@ pub struct PostQuery {
     pub published_at_lt: Option<DateTimeWithTimeZone>,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 pub struct PostCategory {
     pub id: i32,
     pub name: String,
@@ -89,7 +153,7 @@ pub struct PostCategory {
     pub logo: Option<CategoryMedia>,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 pub struct PostTag {
     pub id: i32,
     pub name: String,
@@ -97,7 +161,7 @@ pub struct PostTag {
     pub color: String,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 pub struct PostAuthor {
     pub id: i32,
     pub name: String,
@@ -106,7 +170,7 @@ pub struct PostAuthor {
     pub avatar: Option<AuthorMedia>,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 pub struct PostFeaturedImage {
     pub id: i32,
     pub object_key: String,
@@ -117,7 +181,7 @@ pub struct PostFeaturedImage {
     pub size: i64,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 pub struct PostWithRelations {
     // Core post data
     pub id: i32,
@@ -133,23 +197,210 @@ pub struct PostWithRelations {
     pub updated_at: DateTimeWithTimeZone,
     pub view_count: i32,
     pub likes_count: i32,
+    #[serde(default)]
+    pub visibility: PostVisibility,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_role: Option<UserRole>,
+    #[serde(skip)]
+    #[schemars(skip)]
+    pub password_hash: Option<String>,
+    #[serde(default)]
+    pub locale: String,
+    pub translation_group_id: Option<i32>,
+    pub meta_title: Option<String>,
+    pub meta_description: Option<String>,
+    pub canonical_url: Option<String>,
+    #[serde(default)]
+    pub noindex: bool,
+    pub og_image_id: Option<i32>,
+    pub og_image_variant: Option<String>,
+    /// Set when the viewer only gets the teaser.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub locked: Option<LockReason>,
     pub category: PostCategory,
-    #[serde(skip_serializing_if = "Vec::is_empty", default = "Vec::new")]
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub tags: Vec<PostTag>,
     pub author: PostAuthor,
+    /// Everyone credited on the post, in display order. `author` is the
+    /// first of them with the `author` role.
+    #[serde(default)]
+    pub contributors: Vec<PostContributor>,
 
     pub comment_count: i64,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize, FromQueryResult)]
+/// Blocks of a protected post shown to viewers who may not read it.
+pub const TEASER_BLOCKS: usize = 3;
+
+/// Why a `visibility` setting can't be saved: a `role` post needs a
+/// minimum role, a `password` post a password.
+pub fn visibility_problem(
+    visibility: PostVisibility,
+    min_role: Option<UserRole>,
+    has_password: bool,
+) -> Option<&'static str> {
+    match visibility {
+        PostVisibility::Role if min_role.is_none() => Some("Role visibility needs a min_role"),
+        PostVisibility::Password if !has_password => Some("Password visibility needs a password"),
+        _ => None,
+    }
+}
+
+/// Why a viewer gets the teaser instead of the full post.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum LockReason {
+    LoginRequired,
+    RoleRequired,
+    PasswordRequired,
+}
+
+/// Check a viewer against a post's visibility. `role` is `None` for
+/// visitors who aren't logged in; `password_ok` is whether they gave the
+/// post's password.
+pub fn visibility_lock(
+    visibility: PostVisibility,
+    min_role: Option<UserRole>,
+    role: Option<UserRole>,
+    password_ok: bool,
+) -> Option<LockReason> {
+    match visibility {
+        PostVisibility::Public => None,
+        PostVisibility::Members => role.is_none().then_some(LockReason::LoginRequired),
+        PostVisibility::Role => match role {
+            None => Some(LockReason::LoginRequired),
+            Some(role) if role.to_i32() >= min_role.unwrap_or(UserRole::User).to_i32() => None,
+            Some(_) => Some(LockReason::RoleRequired),
+        },
+        PostVisibility::Password => (!password_ok).then_some(LockReason::PasswordRequired),
+    }
+}
+
+/// The first `blocks` Editor.js blocks of `content`.
+pub fn teaser_content(content: &Json, blocks: usize) -> Json {
+    let mut teaser = content.clone();
+    match teaser.get_mut("blocks").and_then(|b| b.as_array_mut()) {
+        Some(list) => list.truncate(blocks),
+        None => teaser = serde_json::json!({ "blocks": [] }),
+    }
+    teaser
+}
+
+impl PostWithRelations {
+    /// Moderators, admins and the post's contributors read every post whole.
+    fn exempt(&self, viewer: Option<&user::Model>) -> bool {
+        viewer.is_some_and(|viewer| {
+            viewer.is_moderator() || self.contributors.iter().any(|c| c.user_id == viewer.id)
+        })
+    }
+
+    /// Whether a password from `viewer` would be checked at all: the post is
+    /// password-protected, has a password, and `viewer` isn't exempt.
+    pub fn asks_password(&self, viewer: Option<&user::Model>) -> bool {
+        self.visibility == PostVisibility::Password
+            && self.password_hash.is_some()
+            && !self.exempt(viewer)
+    }
+
+    /// Check `password` against the post's hash, off the async runtime since
+    /// hashing is deliberately slow.
+    pub async fn password_matches(&self, password: String) -> bool {
+        let Some(hash) = self.password_hash.clone() else {
+            return false;
+        };
+        tokio::task::spawn_blocking(move || password_auth::verify_password(password, &hash).is_ok())
+            .await
+            .unwrap_or(false)
+    }
+
+    /// Why `viewer` can't read the full post, if they can't. `password_ok`
+    /// is whether they gave the post's password.
+    pub fn lock_reason(
+        &self,
+        viewer: Option<&user::Model>,
+        password_ok: bool,
+    ) -> Option<LockReason> {
+        if self.exempt(viewer) {
+            return None;
+        }
+        visibility_lock(
+            self.visibility,
+            self.min_role,
+            viewer.map(|v| v.get_role()),
+            self.visibility == PostVisibility::Password && password_ok,
+        )
+    }
+
+    /// The post as `viewer` may see it: cut down to the teaser, with
+    /// `locked` set, when they can't read all of it. The excerpt is kept.
+    pub fn for_viewer(mut self, viewer: Option<&user::Model>, password_ok: bool) -> Self {
+        self.locked = self.lock_reason(viewer, password_ok);
+        if self.locked.is_some() {
+            self.content = teaser_content(&self.content, TEASER_BLOCKS);
+        }
+        self
+    }
+}
+
+#[derive(Clone, Debug, FromQueryResult)]
+pub struct PostSitemapRow {
+    pub id: i32,
+    pub slug: String,
+    pub locale: String,
+    pub translation_group_id: Option<i32>,
+    pub canonical_url: Option<String>,
+    pub updated_at: DateTimeWithTimeZone,
+    pub published_at: Option<DateTimeWithTimeZone>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 pub struct PostSitemap {
     pub slug: String,
     pub updated_at: DateTimeWithTimeZone,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub published_at: Option<DateTimeWithTimeZone>,
+    /// Names of the credited authors, in order.
+    #[serde(default)]
+    pub authors: Vec<String>,
+    #[serde(default)]
+    pub locale: String,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub canonical_url: Option<String>,
+    /// `hreflang` alternates: every published translation including this
+    /// post, plus `x-default` for the default-locale version.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub alternates: Vec<PostAlternate>,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct PostAlternate {
+    pub hreflang: String,
+    pub slug: String,
+}
+
+/// `hreflang` alternates for a translation group given as `(locale, slug)`
+/// pairs. Empty for posts without translations.
+pub fn sitemap_alternates(group: &[(String, String)], default_locale: &str) -> Vec<PostAlternate> {
+    if group.len() < 2 {
+        return Vec::new();
+    }
+    let mut alternates: Vec<PostAlternate> = group
+        .iter()
+        .map(|(locale, slug)| PostAlternate {
+            hreflang: locale.clone(),
+            slug: slug.clone(),
+        })
+        .collect();
+    if let Some((_, slug)) = group.iter().find(|(locale, _)| locale == default_locale) {
+        alternates.push(PostAlternate {
+            hreflang: "x-default".to_string(),
+            slug: slug.clone(),
+        });
+    }
+    alternates
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum PostSortBy {
     Title,
@@ -159,7 +410,7 @@ pub enum PostSortBy {
     LikesCount,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize, FromQueryResult)]
+#[derive(Clone, Debug, Serialize, Deserialize, FromQueryResult, JsonSchema)]
 pub struct PostWithJoinedData {
     // Post fields
     pub id: i32,
@@ -177,6 +428,17 @@ pub struct PostWithJoinedData {
     pub likes_count: i32,
     pub tag_ids: Vec<i32>,
     pub category_id: i32,
+    pub visibility: PostVisibility,
+    pub min_role: Option<UserRole>,
+    pub password_hash: Option<String>,
+    pub locale: String,
+    pub translation_group_id: Option<i32>,
+    pub meta_title: Option<String>,
+    pub meta_description: Option<String>,
+    pub canonical_url: Option<String>,
+    pub noindex: bool,
+    pub og_image_id: Option<i32>,
+    pub og_image_variant: Option<String>,
 
     // Author fields from join
     pub author_name: String,
@@ -320,6 +582,18 @@ impl PostWithJoinedData {
             updated_at: self.updated_at,
             view_count: self.view_count,
             likes_count: self.likes_count,
+            visibility: self.visibility,
+            min_role: self.min_role,
+            password_hash: self.password_hash.clone(),
+            locale: self.locale.clone(),
+            translation_group_id: self.translation_group_id,
+            meta_title: self.meta_title.clone(),
+            meta_description: self.meta_description.clone(),
+            canonical_url: self.canonical_url.clone(),
+            noindex: self.noindex,
+            og_image_id: self.og_image_id,
+            og_image_variant: self.og_image_variant.clone(),
+            locked: None,
             category: PostCategory {
                 id: self.category_id,
                 name: self.category_name.clone(),
@@ -335,7 +609,72 @@ impl PostWithJoinedData {
                 email: self.author_email.clone(),
                 avatar,
             },
+            contributors: Vec::new(),
             comment_count: self.comment_count,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn visibility_lock_checks_login_role_and_password() {
+        use PostVisibility::*;
+
+        assert_eq!(visibility_lock(Public, None, None, false), None);
+        assert_eq!(
+            visibility_lock(Members, None, None, false),
+            Some(LockReason::LoginRequired)
+        );
+        assert_eq!(
+            visibility_lock(Members, None, Some(UserRole::User), false),
+            None
+        );
+        assert_eq!(
+            visibility_lock(Role, Some(UserRole::Author), Some(UserRole::User), false),
+            Some(LockReason::RoleRequired)
+        );
+        assert_eq!(
+            visibility_lock(Role, Some(UserRole::Author), Some(UserRole::Admin), false),
+            None
+        );
+        assert_eq!(
+            visibility_lock(Password, None, Some(UserRole::Admin), false),
+            Some(LockReason::PasswordRequired)
+        );
+        assert_eq!(visibility_lock(Password, None, None, true), None);
+    }
+
+    #[test]
+    fn teaser_keeps_the_first_blocks() {
+        let content = json!({
+            "time": 1,
+            "blocks": [
+                { "type": "paragraph", "data": { "text": "1" } },
+                { "type": "paragraph", "data": { "text": "2" } },
+                { "type": "paragraph", "data": { "text": "3" } },
+                { "type": "paragraph", "data": { "text": "4" } }
+            ]
+        });
+        let teaser = teaser_content(&content, 2);
+        assert_eq!(teaser["blocks"].as_array().unwrap().len(), 2);
+        assert_eq!(teaser["time"], 1);
+        assert_eq!(teaser_content(&json!("legacy"), 2), json!({ "blocks": [] }));
+    }
+
+    #[test]
+    fn sitemap_alternates_add_x_default_for_the_default_locale() {
+        let group = vec![
+            ("en".to_string(), "hello".to_string()),
+            ("de".to_string(), "hallo".to_string()),
+        ];
+        let alternates = sitemap_alternates(&group, "en");
+        assert_eq!(alternates.len(), 3);
+        assert_eq!(alternates[2].hreflang, "x-default");
+        assert_eq!(alternates[2].slug, "hello");
+        assert!(sitemap_alternates(&group[..1], "en").is_empty());
+    }
+}
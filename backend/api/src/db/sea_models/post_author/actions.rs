@@ -0,0 +1,198 @@
+use std::collections::HashMap;
+
+use sea_orm::{
+    entity::prelude::*, sea_query::OnConflict, JoinType, PaginatorTrait, QueryOrder, QuerySelect,
+    Set, TransactionTrait,
+};
+use serde_json::json;
+use tracing::info;
+
+use crate::error::{DbResult, ErrorCode, ErrorResponse};
+
+use super::super::{post, user};
+use super::*;
+
+/// Actions for post contributors:
+/// - Credit the creator of a new post
+/// - Look up a user's role on a post (for access checks)
+/// - Load contributors for one or many posts
+/// - Replace the full contributor list, keeping `posts.author_id` in sync
+impl Entity {
+    /// Credit `user_id` as the author of a freshly created post.
+    pub async fn add_owner<C: ConnectionTrait>(
+        conn: &C,
+        post_id: i32,
+        user_id: i32,
+    ) -> DbResult<()> {
+        let active = ActiveModel {
+            post_id: Set(post_id),
+            user_id: Set(user_id),
+            role: Set(PostAuthorRole::Author),
+            sort_order: Set(1),
+            created_at: Set(chrono::Utc::now().fixed_offset()),
+            ..Default::default()
+        };
+
+        Entity::insert(active)
+            .on_conflict(
+                OnConflict::columns([Column::PostId, Column::UserId])
+                    .do_nothing()
+                    .to_owned(),
+            )
+            .exec_without_returning(conn)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn role_of<C: ConnectionTrait>(
+        conn: &C,
+        post_id: i32,
+        user_id: i32,
+    ) -> DbResult<Option<PostAuthorRole>> {
+        let model = Entity::find()
+            .filter(Column::PostId.eq(post_id))
+            .filter(Column::UserId.eq(user_id))
+            .one(conn)
+            .await?;
+        Ok(model.map(|m| m.role))
+    }
+
+    pub async fn list_for_post<C: ConnectionTrait>(
+        conn: &C,
+        post_id: i32,
+    ) -> DbResult<Vec<PostContributor>> {
+        let mut by_post = Self::list_for_posts(conn, &[post_id]).await?;
+        Ok(by_post.remove(&post_id).unwrap_or_default())
+    }
+
+    /// Contributors of each post, in display order.
+    pub async fn list_for_posts<C: ConnectionTrait>(
+        conn: &C,
+        post_ids: &[i32],
+    ) -> DbResult<HashMap<i32, Vec<PostContributor>>> {
+        let mut by_post: HashMap<i32, Vec<PostContributor>> = HashMap::new();
+        if post_ids.is_empty() {
+            return Ok(by_post);
+        }
+
+        let rows = Entity::find()
+            .filter(Column::PostId.is_in(post_ids.to_vec()))
+            .find_also_related(user::Entity)
+            .order_by_asc(Column::PostId)
+            .order_by_asc(Column::SortOrder)
+            .order_by_asc(Column::Id)
+            .all(conn)
+            .await?;
+
+        for (row, user) in rows {
+            let Some(user) = user else { continue };
+            by_post
+                .entry(row.post_id)
+                .or_default()
+                .push(PostContributor {
+                    user_id: row.user_id,
+                    name: user.name,
+                    role: row.role,
+                    sort_order: row.sort_order,
+                });
+        }
+        Ok(by_post)
+    }
+
+    /// Published posts crediting `user_id` as an author.
+    pub async fn published_count(conn: &DbConn, user_id: i32) -> DbResult<u64> {
+        let count = Entity::find()
+            .join(JoinType::InnerJoin, Relation::Post.def())
+            .filter(Column::UserId.eq(user_id))
+            .filter(Column::Role.eq(PostAuthorRole::Author))
+            .filter(post::Column::Status.eq(post::PostStatus::Published))
+            .count(conn)
+            .await?;
+        Ok(count)
+    }
+
+    /// Replace everyone credited on `post_id` with `entries`, in order.
+    ///
+    /// The first `author` becomes `posts.author_id`. Contributors must be
+    /// existing users with at least the author role.
+    pub async fn replace(
+        conn: &DbConn,
+        post_id: i32,
+        entries: Vec<NewContributor>,
+    ) -> DbResult<Vec<PostContributor>> {
+        match check_contributors(&entries) {
+            Some(ContributorViolation::NoAuthor) => {
+                return Err(ErrorResponse::new(ErrorCode::InvalidInput)
+                    .with_message("At least one contributor must have the `author` role"));
+            }
+            Some(ContributorViolation::DuplicateUser(user_id)) => {
+                return Err(ErrorResponse::new(ErrorCode::InvalidInput)
+                    .with_message("A user can only be credited once per post")
+                    .with_context(json!({ "user_id": user_id })));
+            }
+            None => {}
+        }
+
+        let user_ids: Vec<i32> = entries.iter().map(|e| e.user_id).collect();
+        let eligible: Vec<i32> = user::Entity::find()
+            .filter(user::Column::Id.is_in(user_ids.clone()))
+            .all(conn)
+            .await?
+            .into_iter()
+            .filter(|u| u.is_author())
+            .map(|u| u.id)
+            .collect();
+        let rejected: Vec<i32> = user_ids
+            .iter()
+            .copied()
+            .filter(|id| !eligible.contains(id))
+            .collect();
+        if !rejected.is_empty() {
+            return Err(ErrorResponse::new(ErrorCode::InvalidInput)
+                .with_message("Contributors must be existing users with the author role or above")
+                .with_context(json!({ "user_ids": rejected })));
+        }
+
+        let primary = entries
+            .iter()
+            .find(|e| e.role == PostAuthorRole::Author)
+            .map(|e| e.user_id)
+            .unwrap_or(user_ids[0]);
+
+        let txn = conn.begin().await?;
+
+        Entity::delete_many()
+            .filter(Column::PostId.eq(post_id))
+            .exec(&txn)
+            .await?;
+
+        let now = chrono::Utc::now().fixed_offset();
+        let rows = entries.iter().enumerate().map(|(i, e)| ActiveModel {
+            post_id: Set(post_id),
+            user_id: Set(e.user_id),
+            role: Set(e.role),
+            sort_order: Set(i as i32 + 1),
+            created_at: Set(now),
+            ..Default::default()
+        });
+        Entity::insert_many(rows)
+            .exec_without_returning(&txn)
+            .await?;
+
+        post::Entity::update_many()
+            .col_expr(post::Column::AuthorId, Expr::value(primary))
+            .filter(post::Column::Id.eq(post_id))
+            .exec(&txn)
+            .await?;
+
+        txn.commit().await?;
+
+        info!(
+            post_id,
+            primary,
+            count = entries.len(),
+            "Post contributors replaced"
+        );
+        Self::list_for_post(conn, post_id).await
+    }
+}
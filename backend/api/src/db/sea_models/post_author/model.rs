@@ -0,0 +1,91 @@
+use schemars::JsonSchema;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Part a contributor played on a post.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    EnumIter,
+    DeriveActiveEnum,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "post_author_role")]
+#[serde(rename_all = "lowercase")]
+pub enum PostAuthorRole {
+    /// Credited as an author; co-owns the post.
+    #[sea_orm(string_value = "author")]
+    Author,
+    /// May edit the post but not delete it or change its credits.
+    #[sea_orm(string_value = "editor")]
+    Editor,
+    /// Read-only access to the draft, its revisions and lock state.
+    #[sea_orm(string_value = "reviewer")]
+    Reviewer,
+}
+
+impl fmt::Display for PostAuthorRole {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::Author => "author",
+            Self::Editor => "editor",
+            Self::Reviewer => "reviewer",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// A user credited on a post, in display order.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize, JsonSchema)]
+#[sea_orm(table_name = "post_authors")]
+#[schemars(rename = "PostAuthor")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+
+    pub post_id: i32,
+    pub user_id: i32,
+    pub role: PostAuthorRole,
+    pub sort_order: i32,
+
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::super::post::Entity",
+        from = "Column::PostId",
+        to = "super::super::post::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Post,
+    #[sea_orm(
+        belongs_to = "super::super::user::Entity",
+        from = "Column::UserId",
+        to = "super::super::user::Column::Id",
+        on_delete = "Cascade"
+    )]
+    User,
+}
+
+impl Related<super::super::post::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Post.def()
+    }
+}
+
+impl Related<super::super::user::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
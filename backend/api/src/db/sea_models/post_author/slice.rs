@@ -0,0 +1,109 @@
+use std::collections::HashSet;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::PostAuthorRole;
+
+/// A credited user as shown alongside a post.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct PostContributor {
+    pub user_id: i32,
+    pub name: String,
+    pub role: PostAuthorRole,
+    pub sort_order: i32,
+}
+
+/// One entry of the full contributor list sent by the editor.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct NewContributor {
+    pub user_id: i32,
+    pub role: PostAuthorRole,
+}
+
+/// What an author-level user wants to do with a post.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PostAccess {
+    /// Read the draft, its revisions and lock state.
+    View,
+    /// Change content, schedule, series membership.
+    Edit,
+    /// Delete the post or change who is credited.
+    Manage,
+}
+
+impl PostAuthorRole {
+    pub fn allows(self, access: PostAccess) -> bool {
+        match access {
+            PostAccess::View => true,
+            PostAccess::Edit => matches!(self, Self::Author | Self::Editor),
+            PostAccess::Manage => self == Self::Author,
+        }
+    }
+}
+
+/// Why a contributor list was rejected.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ContributorViolation {
+    /// Every post needs at least one `author`.
+    NoAuthor,
+    DuplicateUser(i32),
+}
+
+/// Check a full contributor list before it replaces the stored one.
+pub fn check_contributors(entries: &[NewContributor]) -> Option<ContributorViolation> {
+    let mut seen = HashSet::new();
+    for entry in entries {
+        if !seen.insert(entry.user_id) {
+            return Some(ContributorViolation::DuplicateUser(entry.user_id));
+        }
+    }
+    if !entries.iter().any(|e| e.role == PostAuthorRole::Author) {
+        return Some(ContributorViolation::NoAuthor);
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(user_id: i32, role: PostAuthorRole) -> NewContributor {
+        NewContributor { user_id, role }
+    }
+
+    #[test]
+    fn contributor_list_needs_an_author_and_unique_users() {
+        assert_eq!(
+            check_contributors(&[]),
+            Some(ContributorViolation::NoAuthor)
+        );
+        assert_eq!(
+            check_contributors(&[entry(1, PostAuthorRole::Editor)]),
+            Some(ContributorViolation::NoAuthor)
+        );
+        assert_eq!(
+            check_contributors(&[
+                entry(1, PostAuthorRole::Author),
+                entry(1, PostAuthorRole::Reviewer)
+            ]),
+            Some(ContributorViolation::DuplicateUser(1))
+        );
+        assert_eq!(
+            check_contributors(&[
+                entry(2, PostAuthorRole::Editor),
+                entry(1, PostAuthorRole::Author)
+            ]),
+            None
+        );
+    }
+
+    #[test]
+    fn roles_grant_increasing_access() {
+        assert!(PostAuthorRole::Reviewer.allows(PostAccess::View));
+        assert!(!PostAuthorRole::Reviewer.allows(PostAccess::Edit));
+        assert!(PostAuthorRole::Editor.allows(PostAccess::Edit));
+        assert!(!PostAuthorRole::Editor.allows(PostAccess::Manage));
+        assert!(PostAuthorRole::Author.allows(PostAccess::Manage));
+    }
+}
@@ -1,5 +1,12 @@
-use crate::error::DbResult;
+use crate::{
+    db::sea_models::{
+        notification,
+        webhook::{self, events},
+    },
+    error::{DbResult, ErrorCode, ErrorResponse},
+};
 use sea_orm::{entity::prelude::*, Order, QueryOrder, Set};
+use serde_json::json;
 use tracing::{error, info, instrument, warn};
 
 use super::*;
@@ -9,10 +16,23 @@ impl Entity {
 
     #[instrument(skip(conn, new_comment), fields(comment_id, post_id = new_comment.post_id, user_id = new_comment.user_id))]
     pub async fn create(conn: &DbConn, new_comment: NewComment) -> DbResult<Model> {
+        let parent = match new_comment.parent_id {
+            Some(parent_id) => match Self::find_by_id(parent_id).one(conn).await? {
+                Some(parent) if parent.post_id == new_comment.post_id => Some(parent),
+                _ => {
+                    return Err(ErrorResponse::new(ErrorCode::InvalidValue)
+                        .with_message("Parent comment not found on this post")
+                        .with_context(json!({ "parent_id": parent_id })))
+                }
+            },
+            None => None,
+        };
+
         let now = chrono::Utc::now().fixed_offset();
         let comment = ActiveModel {
             post_id: Set(new_comment.post_id),
             user_id: Set(new_comment.user_id),
+            parent_id: Set(new_comment.parent_id),
             content: Set(new_comment.content),
             likes_count: Set(new_comment.likes_count.unwrap_or(0)),
             created_at: Set(now),
@@ -29,6 +49,22 @@ impl Entity {
                     user_id = model.user_id,
                     "Comment created"
                 );
+                webhook::Entity::emit(
+                    conn,
+                    events::COMMENT_CREATED,
+                    json!({
+                        "id": model.id,
+                        "post_id": model.post_id,
+                        "user_id": model.user_id,
+                        "parent_id": model.parent_id,
+                        "content": model.content,
+                        "created_at": model.created_at,
+                    }),
+                )
+                .await;
+                if let Some(parent) = parent {
+                    notification::Entity::comment_replied(conn, &model, &parent).await;
+                }
                 Ok(model)
             }
             Err(err) => {
@@ -43,6 +79,28 @@ impl Entity {
         }
     }
 
+    #[instrument(skip(conn, comment), fields(comment_id, post_id = comment.post_id, user_id = comment.user_id))]
+    pub async fn create_imported<C: ConnectionTrait>(
+        conn: &C,
+        comment: ImportedComment,
+    ) -> DbResult<Model> {
+        let model = ActiveModel {
+            post_id: Set(comment.post_id),
+            user_id: Set(comment.user_id),
+            content: Set(comment.content),
+            likes_count: Set(0),
+            hidden: Set(comment.hidden),
+            created_at: Set(comment.created_at),
+            updated_at: Set(comment.created_at),
+            ..Default::default()
+        }
+        .insert(conn)
+        .await?;
+
+        tracing::Span::current().record("comment_id", model.id);
+        Ok(model)
+    }
+
     #[instrument(skip(conn, update_comment), fields(comment_id, user_id))]
     pub async fn update(
         conn: &DbConn,
@@ -116,6 +174,7 @@ impl Entity {
             .column(Column::Id)
             .column(Column::PostId)
             .column(Column::UserId)
+            .column(Column::ParentId)
             .column(Column::Content)
             .column(Column::LikesCount)
             .column(Column::Hidden)
@@ -202,6 +261,7 @@ impl Entity {
             .column(Column::Id)
             .column(Column::PostId)
             .column(Column::UserId)
+            .column(Column::ParentId)
             .column(Column::Content)
             .column(Column::LikesCount)
             .column(Column::Hidden)
@@ -356,6 +416,10 @@ impl Entity {
         Ok(count as i64)
     }
 
+    pub async fn get_by_id(conn: &DbConn, comment_id: i32) -> DbResult<Option<Model>> {
+        Ok(Self::find_by_id(comment_id).one(conn).await?)
+    }
+
     pub async fn admin_hide(conn: &DbConn, comment_id: i32) -> DbResult<Option<Model>> {
         let existing = Self::find_by_id(comment_id).one(conn).await?;
         if let Some(model) = existing {
@@ -1,13 +1,18 @@
+use schemars::JsonSchema;
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize, JsonSchema)]
 #[sea_orm(table_name = "post_comments")]
+#[schemars(rename = "PostComment")]
 pub struct Model {
     #[sea_orm(primary_key)]
     pub id: i32,
     pub post_id: i32,
     pub user_id: i32,
+    /// The comment this one replies to.
+    #[serde(default)]
+    pub parent_id: Option<i32>,
     pub content: String,
     pub likes_count: i32,
     pub hidden: bool,
@@ -1,7 +1,8 @@
+use schemars::JsonSchema;
 use sea_orm::{prelude::DateTimeWithTimeZone, FromQueryResult};
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum HiddenFilter {
     All,
@@ -16,7 +17,7 @@ impl HiddenFilter {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum FlagFilter {
     All,
@@ -31,7 +32,7 @@ impl FlagFilter {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 pub struct CommentUserMedia {
     pub id: i32,
     pub object_key: String,
@@ -46,17 +47,29 @@ pub struct CommentUserMedia {
 pub struct NewComment {
     pub post_id: i32,
     pub user_id: i32,
+    pub parent_id: Option<i32>,
     pub content: String,
     pub likes_count: Option<i32>,
 }
 
+/// A comment carried over from another platform, keeping its moderation
+/// state and original timestamp.
+#[derive(Deserialize, Debug)]
+pub struct ImportedComment {
+    pub post_id: i32,
+    pub user_id: i32,
+    pub content: String,
+    pub hidden: bool,
+    pub created_at: DateTimeWithTimeZone,
+}
+
 #[derive(Deserialize, Debug)]
 pub struct UpdateComment {
     pub content: Option<String>,
     pub updated_at: DateTimeWithTimeZone,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, JsonSchema)]
 pub struct CommentQuery {
     pub page_no: Option<u64>,
     pub post_id: Option<i32>,
@@ -92,11 +105,12 @@ impl Default for CommentQuery {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize, FromQueryResult)]
+#[derive(Clone, Debug, Serialize, Deserialize, FromQueryResult, JsonSchema)]
 pub struct CommentWithUserJoined {
     pub id: i32,
     pub post_id: i32,
     pub user_id: i32,
+    pub parent_id: Option<i32>,
     pub content: String,
     pub likes_count: i32,
     pub hidden: bool,
@@ -141,6 +155,7 @@ impl CommentWithUserJoined {
             id: self.id,
             post_id: self.post_id,
             user_id: self.user_id,
+            parent_id: self.parent_id,
             content: self.content,
             likes_count: self.likes_count,
             hidden: self.hidden,
@@ -153,11 +168,12 @@ impl CommentWithUserJoined {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 pub struct CommentWithUser {
     pub id: i32,
     pub post_id: i32,
     pub user_id: i32,
+    pub parent_id: Option<i32>,
     pub content: String,
     pub likes_count: i32,
     pub hidden: bool,
@@ -169,7 +185,7 @@ pub struct CommentWithUser {
     pub user_avatar: Option<CommentUserMedia>,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 pub struct CommentTree {
     pub comment: CommentWithUser,
     pub replies: Vec<CommentWithUser>,
@@ -1,8 +1,9 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use validator::{Validate, ValidationError, ValidationErrors};
 
 /// Response for like status check
-#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 pub struct LikeStatus {
     pub post_id: i32,
     pub is_liked: bool,
@@ -10,7 +11,7 @@ pub struct LikeStatus {
 }
 
 /// Response for like/unlike action
-#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 pub struct LikeActionResponse {
     pub post_id: i32,
     pub is_liked: bool,
@@ -19,7 +20,7 @@ pub struct LikeActionResponse {
 }
 
 /// Request to check like status for multiple posts
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 pub struct LikeStatusBatchRequest {
     pub post_ids: Vec<i32>,
 }
@@ -31,8 +32,7 @@ impl Validate for LikeStatusBatchRequest {
         if self.post_ids.is_empty() {
             errors.add(
                 "post_ids",
-                ValidationError::new("length")
-                    .with_message("post_ids must not be empty".into()),
+                ValidationError::new("length").with_message("post_ids must not be empty".into()),
             );
         }
 
@@ -53,7 +53,7 @@ impl Validate for LikeStatusBatchRequest {
 }
 
 /// Response with like status for multiple posts
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 pub struct LikeStatusBatchResponse {
     pub statuses: Vec<LikeStatus>,
 }
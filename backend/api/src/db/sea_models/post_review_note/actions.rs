@@ -0,0 +1,129 @@
+use std::collections::HashSet;
+
+use sea_orm::{entity::prelude::*, QueryOrder, Set};
+use tracing::info;
+
+use crate::error::DbResult;
+
+use super::super::user;
+use super::*;
+
+/// Actions for reviewer notes:
+/// - Create, edit, resolve/reopen and delete a note
+/// - List the notes of a post with their anchoring state
+impl Entity {
+    pub async fn create(conn: &DbConn, new_note: NewReviewNote) -> DbResult<Model> {
+        let now = chrono::Utc::now().fixed_offset();
+        let active = ActiveModel {
+            post_id: Set(new_note.post_id),
+            block_id: Set(new_note.block_id),
+            body: Set(new_note.body),
+            author_id: Set(Some(new_note.author_id)),
+            resolved_at: Set(None),
+            resolved_by: Set(None),
+            created_at: Set(now),
+            updated_at: Set(now),
+            ..Default::default()
+        };
+        let model = active.insert(conn).await?;
+        info!(
+            note_id = model.id,
+            post_id = model.post_id,
+            "Review note created"
+        );
+        Ok(model)
+    }
+
+    pub async fn find_for_post(
+        conn: &DbConn,
+        post_id: i32,
+        note_id: i32,
+    ) -> DbResult<Option<Model>> {
+        let model = Entity::find_by_id(note_id)
+            .filter(Column::PostId.eq(post_id))
+            .one(conn)
+            .await?;
+        Ok(model)
+    }
+
+    pub async fn update(
+        conn: &DbConn,
+        note: Model,
+        changes: UpdateReviewNote,
+        actor_id: i32,
+    ) -> DbResult<Model> {
+        let now = chrono::Utc::now().fixed_offset();
+        let was_resolved = note.resolved_at.is_some();
+        let mut active: ActiveModel = note.into();
+
+        if let Some(body) = changes.body {
+            active.body = Set(body);
+        }
+        match changes.resolved {
+            Some(true) if !was_resolved => {
+                active.resolved_at = Set(Some(now));
+                active.resolved_by = Set(Some(actor_id));
+            }
+            Some(false) => {
+                active.resolved_at = Set(None);
+                active.resolved_by = Set(None);
+            }
+            _ => {}
+        }
+        active.updated_at = Set(now);
+
+        let model = active.update(conn).await?;
+        info!(
+            note_id = model.id,
+            post_id = model.post_id,
+            "Review note updated"
+        );
+        Ok(model)
+    }
+
+    pub async fn delete(conn: &DbConn, note_id: i32) -> DbResult<u64> {
+        let result = Entity::delete_by_id(note_id).exec(conn).await?;
+        info!(note_id, "Review note deleted");
+        Ok(result.rows_affected)
+    }
+
+    /// Notes of a post, oldest first. `blocks` are the block IDs currently in
+    /// the post, used to flag notes whose block is gone.
+    pub async fn list_for_post(
+        conn: &DbConn,
+        post_id: i32,
+        include_resolved: bool,
+        blocks: &HashSet<String>,
+    ) -> DbResult<Vec<ReviewNoteEntry>> {
+        let mut query = Entity::find().filter(Column::PostId.eq(post_id));
+        if !include_resolved {
+            query = query.filter(Column::ResolvedAt.is_null());
+        }
+
+        let rows = query
+            .find_also_related(user::Entity)
+            .order_by_asc(Column::CreatedAt)
+            .order_by_asc(Column::Id)
+            .all(conn)
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(note, author)| ReviewNoteEntry {
+                id: note.id,
+                anchored: note
+                    .block_id
+                    .as_ref()
+                    .is_none_or(|block_id| blocks.contains(block_id)),
+                block_id: note.block_id,
+                body: note.body,
+                author_id: note.author_id,
+                author_name: author.map(|u| u.name),
+                resolved_at: note.resolved_at,
+                resolved_by: note.resolved_by,
+                created_at: note.created_at,
+                updated_at: note.updated_at,
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,56 @@
+use schemars::JsonSchema;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// A reviewer's note on a post, optionally pinned to one Editor.js block.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize, JsonSchema)]
+#[sea_orm(table_name = "post_review_notes")]
+#[schemars(rename = "PostReviewNote")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+
+    pub post_id: i32,
+    /// Editor.js block `id`; `None` for a note on the whole post.
+    pub block_id: Option<String>,
+    pub body: String,
+    pub author_id: Option<i32>,
+
+    pub resolved_at: Option<DateTimeWithTimeZone>,
+    pub resolved_by: Option<i32>,
+
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::super::post::Entity",
+        from = "Column::PostId",
+        to = "super::super::post::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Post,
+    #[sea_orm(
+        belongs_to = "super::super::user::Entity",
+        from = "Column::AuthorId",
+        to = "super::super::user::Column::Id",
+        on_delete = "SetNull"
+    )]
+    Author,
+}
+
+impl Related<super::super::post::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Post.def()
+    }
+}
+
+impl Related<super::super::user::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Author.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
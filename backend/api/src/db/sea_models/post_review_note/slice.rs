@@ -0,0 +1,72 @@
+use std::collections::HashSet;
+
+use schemars::JsonSchema;
+use sea_orm::prelude::{DateTimeWithTimeZone, Json};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct NewReviewNote {
+    pub post_id: i32,
+    pub block_id: Option<String>,
+    pub body: String,
+    pub author_id: i32,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct UpdateReviewNote {
+    pub body: Option<String>,
+    /// `true` resolves the note, `false` reopens it.
+    pub resolved: Option<bool>,
+}
+
+/// A note as listed for the editor.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ReviewNoteEntry {
+    pub id: i32,
+    pub block_id: Option<String>,
+    /// False when the block the note points at has since been removed.
+    pub anchored: bool,
+    pub body: String,
+    pub author_id: Option<i32>,
+    pub author_name: Option<String>,
+    pub resolved_at: Option<DateTimeWithTimeZone>,
+    pub resolved_by: Option<i32>,
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+/// IDs of the Editor.js blocks in a post's content.
+pub fn block_ids(content: &Json) -> HashSet<String> {
+    content
+        .get("blocks")
+        .and_then(|blocks| blocks.as_array())
+        .map(|blocks| {
+            blocks
+                .iter()
+                .filter_map(|block| block.get("id").and_then(|id| id.as_str()))
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn block_ids_skip_blocks_without_an_id() {
+        let content = json!({
+            "blocks": [
+                { "id": "a1", "type": "paragraph", "data": { "text": "One" } },
+                { "type": "paragraph", "data": { "text": "Two" } },
+                { "id": "c3", "type": "header", "data": { "text": "Three", "level": 2 } }
+            ]
+        });
+        let ids = block_ids(&content);
+        assert_eq!(ids.len(), 2);
+        assert!(ids.contains("a1") && ids.contains("c3"));
+        assert!(block_ids(&json!("plain text")).is_empty());
+    }
+}
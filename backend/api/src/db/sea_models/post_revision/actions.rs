@@ -3,34 +3,46 @@ use sea_orm::{
     QuerySelect, Set, TransactionTrait,
 };
 
+use crate::db::sea_models::{
+    post,
+    slug_redirect::{self, RedirectEntity},
+    tag,
+};
 use crate::error::DbResult;
 
 use super::*;
 
 /// Actions for post revisions:
-/// - Create a revision
+/// - Snapshot a post into a revision
+/// - Restore selected fields from a revision
 /// - List revisions (newest first) with pagination
 /// - Enforce a maximum number of revisions per post by trimming oldest
 impl Entity {
     pub const PER_PAGE: u64 = 10;
     pub const MAX_REVISIONS_PER_POST: u64 = 10;
 
-    /// Create a new revision entry for a post.
+    /// Record the current state of a post (content and all editable fields)
+    /// as a new revision made by `created_by`.
     /// This will also enforce the maximum revisions cap (keep newest N).
-    pub async fn create(
-        conn: &DbConn,
-        post_id: i32,
-        content: String,
+    pub async fn snapshot<C>(
+        conn: &C,
+        post: &post::Model,
+        created_by: Option<i32>,
         metadata: Option<serde_json::Value>,
-    ) -> DbResult<Model> {
+    ) -> DbResult<Model>
+    where
+        C: ConnectionTrait + TransactionTrait,
+    {
         let now = chrono::Utc::now().fixed_offset();
 
         let txn = conn.begin().await?;
 
         let active = ActiveModel {
-            post_id: Set(post_id),
-            content: Set(content),
+            post_id: Set(post.id),
+            content: Set(post.content.to_string()),
             metadata: Set(metadata),
+            fields: Set(serde_json::to_value(RevisionFields::from(post)).ok()),
+            created_by: Set(created_by),
             created_at: Set(now),
             ..Default::default()
         };
@@ -38,12 +50,128 @@ impl Entity {
         let created = active.insert(&txn).await?;
 
         // Trim older revisions beyond the cap
-        Self::enforce_max_inner(&txn, post_id, Self::MAX_REVISIONS_PER_POST).await?;
+        Self::enforce_max_inner(&txn, post.id, Self::MAX_REVISIONS_PER_POST).await?;
 
         txn.commit().await?;
         Ok(created)
     }
 
+    /// Load the post by id and snapshot it; `None` if the post does not exist.
+    pub async fn snapshot_post(
+        conn: &DbConn,
+        post_id: i32,
+        created_by: Option<i32>,
+        metadata: Option<serde_json::Value>,
+    ) -> DbResult<Option<Model>> {
+        match post::Entity::find_by_id(post_id).one(conn).await? {
+            Some(post) => Ok(Some(
+                Self::snapshot(conn, &post, created_by, metadata).await?,
+            )),
+            None => Ok(None),
+        }
+    }
+
+    /// Find a revision, only if it belongs to `post_id`.
+    pub async fn find_for_post(
+        conn: &DbConn,
+        post_id: i32,
+        revision_id: i32,
+    ) -> DbResult<Option<Model>> {
+        Entity::find_by_id(revision_id)
+            .filter(Column::PostId.eq(post_id))
+            .one(conn)
+            .await
+            .map_err(Into::into)
+    }
+
+    /// The newest revision of `post_id`, if it has any.
+    pub async fn latest_for_post(conn: &DbConn, post_id: i32) -> DbResult<Option<Model>> {
+        Entity::find()
+            .filter(Column::PostId.eq(post_id))
+            .order_by_desc(Column::CreatedAt)
+            .order_by_desc(Column::Id)
+            .one(conn)
+            .await
+            .map_err(Into::into)
+    }
+
+    /// Copy the selected fields of `revision` back onto its post, moving it
+    /// to `status` if given, and record the result as a new revision.
+    /// Returns `None` if the post is gone.
+    ///
+    /// Callers must make sure the revision has a field snapshot when
+    /// restoring anything other than content.
+    pub async fn restore(
+        conn: &DbConn,
+        revision: &Model,
+        fields: &[RevisionField],
+        status: Option<post::PostStatus>,
+        restored_by: Option<i32>,
+    ) -> DbResult<Option<(post::Model, Model)>> {
+        let txn = conn.begin().await?;
+
+        let Some(post) = post::Entity::find_by_id(revision.post_id).one(&txn).await? else {
+            return Ok(None);
+        };
+        let snapshot = revision.snapshot_fields();
+        let old_slug = post.slug.clone();
+        let mut active: post::ActiveModel = post.into();
+
+        for field in fields {
+            match (field, &snapshot) {
+                (RevisionField::Content, _) => active.content = Set(revision.content_json()),
+                (RevisionField::Title, Some(s)) => active.title = Set(s.title.clone()),
+                (RevisionField::Slug, Some(s)) => active.slug = Set(s.slug.clone()),
+                (RevisionField::Excerpt, Some(s)) => active.excerpt = Set(s.excerpt.clone()),
+                (RevisionField::FeaturedImage, Some(s)) => {
+                    active.featured_image_id = Set(s.featured_image_id)
+                }
+                (RevisionField::Category, Some(s)) => active.category_id = Set(s.category_id),
+                (RevisionField::Tags, Some(s)) => {
+                    // Tags deleted since the revision are dropped
+                    let existing: Vec<i32> = tag::Entity::find()
+                        .filter(tag::Column::Id.is_in(s.tag_ids.clone()))
+                        .all(&txn)
+                        .await?
+                        .into_iter()
+                        .map(|t| t.id)
+                        .collect();
+                    active.tag_ids = Set(s
+                        .tag_ids
+                        .iter()
+                        .copied()
+                        .filter(|id| existing.contains(id))
+                        .collect());
+                }
+                (_, None) => {}
+            }
+        }
+        if let Some(status) = status {
+            active.status = Set(status);
+        }
+        active.updated_at = Set(chrono::Utc::now().fixed_offset());
+        let post = active.update(&txn).await?;
+        slug_redirect::Entity::record_slug_change(
+            &txn,
+            RedirectEntity::Post,
+            post.id,
+            Some(&post.locale),
+            &old_slug,
+            Some(&post.locale),
+            &post.slug,
+        )
+        .await?;
+
+        let metadata = serde_json::json!({
+            "restored_from_revision_id": revision.id,
+            "restored_fields": fields,
+        });
+        let created = Self::snapshot(&txn, &post, restored_by, Some(metadata)).await?;
+
+        txn.commit().await?;
+        Ok(Some((post, created)))
+    }
+
     /// List revisions for a post (newest first) with pagination.
     /// Returns (revisions, total_count).
     pub async fn list_by_post(
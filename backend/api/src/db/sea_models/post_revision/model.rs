@@ -1,8 +1,10 @@
+use schemars::JsonSchema;
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize, JsonSchema)]
 #[sea_orm(table_name = "post_revisions")]
+#[schemars(rename = "PostRevision")]
 pub struct Model {
     #[sea_orm(primary_key)]
     pub id: i32,
@@ -14,6 +16,13 @@ pub struct Model {
     #[sea_orm(column_type = "JsonBinary", nullable)]
     pub metadata: Option<serde_json::Value>,
 
+    /// Snapshot of the other editable fields (`RevisionFields`); `None` for
+    /// content-only revisions recorded before snapshots existed.
+    #[sea_orm(column_type = "JsonBinary", nullable)]
+    pub fields: Option<serde_json::Value>,
+
+    pub created_by: Option<i32>,
+
     pub created_at: DateTimeWithTimeZone,
 }
 
@@ -27,6 +36,14 @@ pub enum Relation {
         on_delete = "Cascade"
     )]
     Post,
+    #[sea_orm(
+        belongs_to = "super::super::user::Entity",
+        from = "Column::CreatedBy",
+        to = "super::super::user::Column::Id",
+        on_update = "Cascade",
+        on_delete = "SetNull"
+    )]
+    CreatedBy,
 }
 
 impl Related<super::super::post::Entity> for Entity {
@@ -35,4 +52,23 @@ impl Related<super::super::post::Entity> for Entity {
     }
 }
 
+impl Related<super::super::user::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::CreatedBy.def()
+    }
+}
+
+impl Model {
+    /// Post content stored in this revision as an Editor.js document.
+    pub fn content_json(&self) -> serde_json::Value {
+        serde_json::from_str(&self.content).unwrap_or_else(|_| serde_json::json!({}))
+    }
+
+    pub fn snapshot_fields(&self) -> Option<super::RevisionFields> {
+        self.fields
+            .clone()
+            .and_then(|value| serde_json::from_value(value).ok())
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
@@ -1,9 +1,10 @@
+use schemars::JsonSchema;
 use sea_orm::prelude::DateTimeWithTimeZone;
 use sea_orm::FromQueryResult;
 use serde::{Deserialize, Serialize};
 
 /// Payload to create a new post revision
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 pub struct CreatePostRevision {
     pub post_id: i32,
     pub content: String,
@@ -12,7 +13,7 @@ pub struct CreatePostRevision {
 }
 
 /// Query parameters for listing revisions
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 pub struct PostRevisionListQuery {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub page: Option<u64>,
@@ -21,7 +22,7 @@ pub struct PostRevisionListQuery {
 }
 
 /// Lightweight projection for listing revisions
-#[derive(Clone, Debug, Serialize, Deserialize, FromQueryResult)]
+#[derive(Clone, Debug, Serialize, Deserialize, FromQueryResult, JsonSchema)]
 pub struct PostRevisionSummary {
     pub id: i32,
     pub post_id: i32,
@@ -29,8 +30,57 @@ pub struct PostRevisionSummary {
 }
 
 /// Payload to restore a specific revision into a post
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 pub struct RestorePostRevision {
     pub post_id: i32,
     pub revision_id: i32,
 }
+
+/// Editable post fields captured alongside the content of a revision.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct RevisionFields {
+    pub title: String,
+    pub slug: String,
+    pub excerpt: Option<String>,
+    pub featured_image_id: Option<i32>,
+    pub category_id: i32,
+    pub tag_ids: Vec<i32>,
+}
+
+impl From<&crate::db::sea_models::post::Model> for RevisionFields {
+    fn from(post: &crate::db::sea_models::post::Model) -> Self {
+        Self {
+            title: post.title.clone(),
+            slug: post.slug.clone(),
+            excerpt: post.excerpt.clone(),
+            featured_image_id: post.featured_image_id,
+            category_id: post.category_id,
+            tag_ids: post.tag_ids.clone(),
+        }
+    }
+}
+
+/// A post field that can be restored on its own from a revision.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RevisionField {
+    Content,
+    Title,
+    Slug,
+    Excerpt,
+    FeaturedImage,
+    Category,
+    Tags,
+}
+
+impl RevisionField {
+    pub const ALL: [RevisionField; 7] = [
+        RevisionField::Content,
+        RevisionField::Title,
+        RevisionField::Slug,
+        RevisionField::Excerpt,
+        RevisionField::FeaturedImage,
+        RevisionField::Category,
+        RevisionField::Tags,
+    ];
+}
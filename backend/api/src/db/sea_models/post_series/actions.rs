@@ -1,8 +1,10 @@
 use sea_orm::{
     entity::prelude::*, ColumnTrait, Condition, EntityTrait, PaginatorTrait, QueryFilter,
-    QueryOrder, Set,
+    QueryOrder, QuerySelect, Set,
 };
 
+use crate::db::sea_models::slug_redirect::{self, RedirectEntity};
+use crate::db::sea_models::{post, post_series_post};
 use crate::error::DbResult;
 
 use super::*;
@@ -13,6 +15,7 @@ use super::*;
 /// - delete
 /// - list (with optional search + pagination)
 /// - find_by_slug
+/// - published posts in order, and series navigation for a post
 impl Entity {
     pub const PER_PAGE: u64 = 10;
 
@@ -39,6 +42,7 @@ impl Entity {
     }
 
     /// Update a post series by id. Returns Ok(None) if the series doesn't exist.
+    /// A changed slug is kept as a redirect to the series.
     ///
     /// Note: `description` cannot be cleared to NULL via this method (consistent with other update patterns);
     /// it can only be set when provided (Some), otherwise left unchanged (None).
@@ -50,6 +54,7 @@ impl Entity {
         description: Option<String>,
     ) -> DbResult<Option<Model>> {
         if let Some(existing) = Entity::find_by_id(series_id).one(conn).await? {
+            let old_slug = existing.slug.clone();
             let mut active: ActiveModel = existing.into();
 
             if let Some(n) = name {
@@ -65,6 +70,16 @@ impl Entity {
             active.updated_at = Set(chrono::Utc::now().fixed_offset());
 
             let updated = active.update(conn).await?;
+            slug_redirect::Entity::record_slug_change(
+                conn,
+                RedirectEntity::Series,
+                series_id,
+                None,
+                &old_slug,
+                None,
+                &updated.slug,
+            )
+            .await?;
             Ok(Some(updated))
         } else {
             Ok(None)
@@ -73,6 +88,7 @@ impl Entity {
 
     /// Delete a post series by id. Returns number of rows affected.
     pub async fn delete(conn: &DbConn, series_id: i32) -> DbResult<u64> {
+        slug_redirect::Entity::delete_for_target(conn, RedirectEntity::Series, series_id).await?;
         let res = Entity::delete_by_id(series_id).exec(conn).await?;
         Ok(res.rows_affected)
     }
@@ -123,4 +139,49 @@ impl Entity {
             .await?;
         Ok(model)
     }
+
+    /// Published posts of a series in series order.
+    pub async fn published_posts(conn: &DbConn, series_id: i32) -> DbResult<Vec<SeriesPostEntry>> {
+        let entries = post_series_post::Entity::find()
+            .select_only()
+            .column(post_series_post::Column::PostId)
+            .column(post_series_post::Column::SortOrder)
+            .column(post::Column::Title)
+            .column(post::Column::Slug)
+            .column(post::Column::Excerpt)
+            .column(post::Column::FeaturedImageId)
+            .column(post::Column::PublishedAt)
+            .inner_join(post::Entity)
+            .filter(post_series_post::Column::SeriesId.eq(series_id))
+            .filter(post::Column::Status.eq(post::PostStatus::Published))
+            .order_by_asc(post_series_post::Column::SortOrder)
+            .order_by_asc(post_series_post::Column::Id)
+            .into_model::<SeriesPostEntry>()
+            .all(conn)
+            .await?;
+        Ok(entries)
+    }
+
+    /// Position and prev/next links of a post in each series it belongs to,
+    /// counting published posts only.
+    pub async fn navigation_for_post(
+        conn: &DbConn,
+        post_id: i32,
+    ) -> DbResult<Vec<SeriesNavigation>> {
+        let series = Entity::find()
+            .inner_join(post_series_post::Entity)
+            .filter(post_series_post::Column::PostId.eq(post_id))
+            .order_by_asc(Column::Id)
+            .all(conn)
+            .await?;
+
+        let mut navigation = Vec::with_capacity(series.len());
+        for s in series {
+            let entries = Self::published_posts(conn, s.id).await?;
+            if let Some(nav) = SeriesNavigation::locate(&s, &entries, post_id) {
+                navigation.push(nav);
+            }
+        }
+        Ok(navigation)
+    }
 }
@@ -1,9 +1,10 @@
+use schemars::JsonSchema;
 use sea_orm::prelude::DateTimeWithTimeZone;
 use sea_orm::FromQueryResult;
 use serde::{Deserialize, Serialize};
 
 /// Payload to create a new post series
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 pub struct NewPostSeries {
     pub name: String,
     pub slug: String,
@@ -12,7 +13,7 @@ pub struct NewPostSeries {
 }
 
 /// Payload to update an existing post series
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 pub struct UpdatePostSeries {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
@@ -23,7 +24,7 @@ pub struct UpdatePostSeries {
 }
 
 /// Query parameters for listing series with pagination and optional search
-#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, JsonSchema)]
 pub struct PostSeriesListQuery {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub page: Option<u64>,
@@ -34,7 +35,7 @@ pub struct PostSeriesListQuery {
 }
 
 /// Projection for listing series with post counts
-#[derive(Clone, Debug, Serialize, Deserialize, FromQueryResult)]
+#[derive(Clone, Debug, Serialize, Deserialize, FromQueryResult, JsonSchema)]
 pub struct PostSeriesWithCount {
     pub id: i32,
     pub name: String,
@@ -45,3 +46,117 @@ pub struct PostSeriesWithCount {
     pub updated_at: DateTimeWithTimeZone,
     pub posts_count: i64,
 }
+
+/// Response of `/post/v1/series/list`, which has no `per_page`
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct PostSeriesList {
+    pub data: Vec<PostSeriesWithCount>,
+    pub total: u64,
+    pub page: u64,
+}
+
+/// A post of a series, in series order.
+#[derive(Clone, Debug, Serialize, Deserialize, FromQueryResult, JsonSchema)]
+pub struct SeriesPostEntry {
+    pub post_id: i32,
+    pub sort_order: i32,
+    pub title: String,
+    pub slug: String,
+    pub excerpt: Option<String>,
+    pub featured_image_id: Option<i32>,
+    pub published_at: Option<DateTimeWithTimeZone>,
+}
+
+/// Neighbouring post in a series.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SeriesNavLink {
+    pub id: i32,
+    pub title: String,
+    pub slug: String,
+}
+
+impl From<&SeriesPostEntry> for SeriesNavLink {
+    fn from(entry: &SeriesPostEntry) -> Self {
+        Self {
+            id: entry.post_id,
+            title: entry.title.clone(),
+            slug: entry.slug.clone(),
+        }
+    }
+}
+
+/// Where a post sits in one of its series.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SeriesNavigation {
+    pub id: i32,
+    pub name: String,
+    pub slug: String,
+    /// 1-based position among the published posts of the series.
+    pub position: usize,
+    pub total: usize,
+    pub prev: Option<SeriesNavLink>,
+    pub next: Option<SeriesNavLink>,
+}
+
+impl SeriesNavigation {
+    /// Locate `post_id` in the ordered `entries`; `None` if it isn't there.
+    pub fn locate(
+        series: &super::Model,
+        entries: &[SeriesPostEntry],
+        post_id: i32,
+    ) -> Option<Self> {
+        let idx = entries.iter().position(|e| e.post_id == post_id)?;
+        Some(Self {
+            id: series.id,
+            name: series.name.clone(),
+            slug: series.slug.clone(),
+            position: idx + 1,
+            total: entries.len(),
+            prev: idx
+                .checked_sub(1)
+                .and_then(|i| entries.get(i))
+                .map(SeriesNavLink::from),
+            next: entries.get(idx + 1).map(SeriesNavLink::from),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(post_id: i32, sort_order: i32) -> SeriesPostEntry {
+        SeriesPostEntry {
+            post_id,
+            sort_order,
+            title: format!("Part {}", sort_order),
+            slug: format!("part-{}", sort_order),
+            excerpt: None,
+            featured_image_id: None,
+            published_at: None,
+        }
+    }
+
+    #[test]
+    fn locates_prev_and_next() {
+        let now = chrono::Utc::now().fixed_offset();
+        let series = super::super::Model {
+            id: 1,
+            name: "Intro".into(),
+            slug: "intro".into(),
+            description: None,
+            created_at: now,
+            updated_at: now,
+        };
+        let entries = vec![entry(10, 1), entry(20, 2), entry(30, 3)];
+
+        let nav = SeriesNavigation::locate(&series, &entries, 20).unwrap();
+        assert_eq!((nav.position, nav.total), (2, 3));
+        assert_eq!(nav.prev.map(|p| p.id), Some(10));
+        assert_eq!(nav.next.map(|n| n.id), Some(30));
+
+        let first = SeriesNavigation::locate(&series, &entries, 10).unwrap();
+        assert!(first.prev.is_none());
+        assert!(SeriesNavigation::locate(&series, &entries, 99).is_none());
+    }
+}
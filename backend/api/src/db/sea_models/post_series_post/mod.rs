@@ -1,9 +1,13 @@
 pub mod model {
+    use schemars::JsonSchema;
     use sea_orm::entity::prelude::*;
     use serde::{Deserialize, Serialize};
 
-    #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+    #[derive(
+        Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize, JsonSchema,
+    )]
     #[sea_orm(table_name = "post_series_posts")]
+    #[schemars(rename = "PostSeriesPost")]
     pub struct Model {
         #[sea_orm(primary_key)]
         pub id: i32,
@@ -79,6 +83,44 @@ pub mod slice {
         pub new_sort_order: i32,
     }
 
+    /// Why a full reorder list doesn't match the posts of a series
+    #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+    pub struct OrderMismatch {
+        /// Posts in the series missing from the list
+        pub missing: Vec<i32>,
+        /// Listed posts that aren't in the series
+        pub unknown: Vec<i32>,
+        /// Posts listed more than once
+        pub duplicates: Vec<i32>,
+    }
+
+    /// Compare a requested order against the posts currently in a series.
+    /// `None` when `requested` lists every post exactly once.
+    pub fn check_full_order(current: &[i32], requested: &[i32]) -> Option<OrderMismatch> {
+        let mut seen = std::collections::HashSet::new();
+        let mut mismatch = OrderMismatch::default();
+        for id in requested {
+            if !seen.insert(*id) {
+                if !mismatch.duplicates.contains(id) {
+                    mismatch.duplicates.push(*id);
+                }
+            } else if !current.contains(id) {
+                mismatch.unknown.push(*id);
+            }
+        }
+        mismatch.missing = current
+            .iter()
+            .copied()
+            .filter(|id| !seen.contains(id))
+            .collect();
+
+        if mismatch == OrderMismatch::default() {
+            None
+        } else {
+            Some(mismatch)
+        }
+    }
+
     /// Query parameters for listing mappings within a series
     #[derive(Clone, Debug, Serialize, Deserialize)]
     pub struct SeriesPostsListQuery {
@@ -91,10 +133,10 @@ pub mod slice {
 }
 
 pub mod actions {
-    use crate::error::DbResult;
+    use crate::error::{DbResult, ErrorCode, ErrorResponse};
     use sea_orm::{
-        entity::prelude::*, ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder, Set,
-        TransactionTrait,
+        entity::prelude::*, ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder,
+        QuerySelect, Set, TransactionTrait,
     };
 
     use super::model::*;
@@ -215,6 +257,53 @@ pub mod actions {
             }
         }
 
+        /// Replace the whole order of a series at once. `post_ids` must list
+        /// every post of the series exactly once, first to last.
+        pub async fn reorder_all(
+            conn: &DbConn,
+            series_id: i32,
+            post_ids: &[i32],
+        ) -> DbResult<Vec<Model>> {
+            let txn = conn.begin().await?;
+
+            let items = Entity::find()
+                .filter(Column::SeriesId.eq(series_id))
+                .order_by_asc(Column::SortOrder)
+                .order_by_asc(Column::Id)
+                .lock_exclusive()
+                .all(&txn)
+                .await?;
+
+            let current: Vec<i32> = items.iter().map(|m| m.post_id).collect();
+            if let Some(mismatch) = check_full_order(&current, post_ids) {
+                return Err(ErrorResponse::new(ErrorCode::InvalidInput)
+                    .with_message("The order must list every post of the series exactly once")
+                    .with_context(serde_json::json!(mismatch)));
+            }
+
+            let now = chrono::Utc::now().fixed_offset();
+            let mut updated = Vec::with_capacity(items.len());
+            for m in items {
+                let desired = post_ids
+                    .iter()
+                    .position(|id| *id == m.post_id)
+                    .map(|idx| idx as i32 + 1)
+                    .unwrap_or(m.sort_order);
+                if m.sort_order == desired {
+                    updated.push(m);
+                    continue;
+                }
+                let mut active: ActiveModel = m.into();
+                active.sort_order = Set(desired);
+                active.updated_at = Set(now);
+                updated.push(active.update(&txn).await?);
+            }
+
+            txn.commit().await?;
+            updated.sort_by_key(|m| m.sort_order);
+            Ok(updated)
+        }
+
         /// List mappings for a series ordered by sort_order asc, id asc.
         /// Returns (items, total_count)
         pub async fn list_by_series(
@@ -300,3 +389,21 @@ pub mod actions {
 // Re-exports for convenience and consistency with other modules
 pub use model::{ActiveModel, Column, Entity, Model, Relation};
 pub use slice::*;
+
+#[cfg(test)]
+mod tests {
+    use super::slice::{check_full_order, OrderMismatch};
+
+    #[test]
+    fn full_order_must_list_each_post_once() {
+        assert_eq!(check_full_order(&[1, 2, 3], &[3, 1, 2]), None);
+        assert_eq!(
+            check_full_order(&[1, 2, 3], &[3, 3, 4]),
+            Some(OrderMismatch {
+                missing: vec![1, 2],
+                unknown: vec![4],
+                duplicates: vec![3],
+            })
+        );
+    }
+}
@@ -0,0 +1,57 @@
+use sea_orm::{entity::prelude::*, QueryOrder, Set};
+use tracing::info;
+
+use crate::error::DbResult;
+
+use super::super::{post::PostStatus, user};
+use super::*;
+
+/// Actions for post status history:
+/// - Record a status change
+/// - List the history of a post, oldest first
+impl Entity {
+    pub async fn record<C: ConnectionTrait>(
+        conn: &C,
+        post_id: i32,
+        from_status: PostStatus,
+        to_status: PostStatus,
+        actor_id: Option<i32>,
+        note: Option<String>,
+    ) -> DbResult<Model> {
+        let active = ActiveModel {
+            post_id: Set(post_id),
+            from_status: Set(from_status),
+            to_status: Set(to_status),
+            actor_id: Set(actor_id),
+            note: Set(note),
+            created_at: Set(chrono::Utc::now().fixed_offset()),
+            ..Default::default()
+        };
+        let model = active.insert(conn).await?;
+        info!(post_id, %from_status, %to_status, ?actor_id, "Post status transition recorded");
+        Ok(model)
+    }
+
+    pub async fn list_for_post(conn: &DbConn, post_id: i32) -> DbResult<Vec<TransitionEntry>> {
+        let rows = Entity::find()
+            .filter(Column::PostId.eq(post_id))
+            .find_also_related(user::Entity)
+            .order_by_asc(Column::CreatedAt)
+            .order_by_asc(Column::Id)
+            .all(conn)
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(row, actor)| TransitionEntry {
+                id: row.id,
+                from_status: row.from_status,
+                to_status: row.to_status,
+                actor_id: row.actor_id,
+                actor_name: actor.map(|u| u.name),
+                note: row.note,
+                created_at: row.created_at,
+            })
+            .collect())
+    }
+}
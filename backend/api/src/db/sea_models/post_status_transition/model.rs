@@ -0,0 +1,54 @@
+use schemars::JsonSchema;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::super::post::PostStatus;
+
+/// One status change of a post.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize, JsonSchema)]
+#[sea_orm(table_name = "post_status_transitions")]
+#[schemars(rename = "PostStatusTransition")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+
+    pub post_id: i32,
+    pub from_status: PostStatus,
+    pub to_status: PostStatus,
+    pub actor_id: Option<i32>,
+    pub note: Option<String>,
+
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::super::post::Entity",
+        from = "Column::PostId",
+        to = "super::super::post::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Post,
+    #[sea_orm(
+        belongs_to = "super::super::user::Entity",
+        from = "Column::ActorId",
+        to = "super::super::user::Column::Id",
+        on_delete = "SetNull"
+    )]
+    Actor,
+}
+
+impl Related<super::super::post::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Post.def()
+    }
+}
+
+impl Related<super::super::user::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Actor.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,103 @@
+use schemars::JsonSchema;
+use sea_orm::prelude::DateTimeWithTimeZone;
+use serde::{Deserialize, Serialize};
+
+use super::super::post::PostStatus;
+
+/// A history entry with the name of whoever made the change.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct TransitionEntry {
+    pub id: i32,
+    pub from_status: PostStatus,
+    pub to_status: PostStatus,
+    pub actor_id: Option<i32>,
+    pub actor_name: Option<String>,
+    pub note: Option<String>,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+/// Why a status change was refused.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TransitionDenied {
+    /// Only moderators and admins approve posts or request changes.
+    ModeratorOnly,
+    /// Publishing needs an approved review first.
+    NeedsApproval,
+}
+
+/// Check a status change made by a post's contributor.
+///
+/// Moderators and admins may move a post to any status. Everyone else can
+/// submit, withdraw, archive and (re)draft freely; publishing needs the post
+/// to be `approved` first when `approval_required` is on.
+pub fn check_transition(
+    from: PostStatus,
+    to: PostStatus,
+    is_moderator: bool,
+    approval_required: bool,
+) -> Option<TransitionDenied> {
+    if from == to || is_moderator {
+        return None;
+    }
+    match to {
+        PostStatus::Approved | PostStatus::ChangesRequested => {
+            Some(TransitionDenied::ModeratorOnly)
+        }
+        PostStatus::Published if approval_required && from != PostStatus::Approved => {
+            Some(TransitionDenied::NeedsApproval)
+        }
+        _ => None,
+    }
+}
+
+/// The status an edit that doesn't ask for one (an update without a
+/// status, an autosave, a revision restore) leaves a post in: an approved
+/// post goes back to review unless the editor may publish directly, since
+/// the approval was for the content before the edit.
+pub fn status_after_unrequested_edit(
+    from: PostStatus,
+    may_publish_directly: bool,
+) -> Option<PostStatus> {
+    (from == PostStatus::Approved && !may_publish_directly).then_some(PostStatus::InReview)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn authors_need_approval_to_publish_when_required() {
+        use PostStatus::*;
+
+        assert_eq!(check_transition(Draft, InReview, false, true), None);
+        assert_eq!(
+            check_transition(InReview, Approved, false, true),
+            Some(TransitionDenied::ModeratorOnly)
+        );
+        assert_eq!(
+            check_transition(InReview, ChangesRequested, false, false),
+            Some(TransitionDenied::ModeratorOnly)
+        );
+        assert_eq!(
+            check_transition(Draft, Published, false, true),
+            Some(TransitionDenied::NeedsApproval)
+        );
+        assert_eq!(check_transition(Approved, Published, false, true), None);
+        assert_eq!(check_transition(Draft, Published, false, false), None);
+        assert_eq!(check_transition(InReview, Approved, true, true), None);
+        assert_eq!(check_transition(Draft, Published, true, true), None);
+    }
+
+    #[test]
+    fn edits_and_restores_send_approved_posts_back_to_review() {
+        use PostStatus::*;
+
+        assert_eq!(
+            status_after_unrequested_edit(Approved, false),
+            Some(InReview)
+        );
+        assert_eq!(status_after_unrequested_edit(Approved, true), None);
+        assert_eq!(status_after_unrequested_edit(Draft, false), None);
+        assert_eq!(status_after_unrequested_edit(Published, false), None);
+    }
+}
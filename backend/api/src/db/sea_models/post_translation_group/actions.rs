@@ -0,0 +1,158 @@
+use sea_orm::{entity::prelude::*, prelude::Expr, QueryOrder, Set, TransactionTrait};
+use tracing::info;
+
+use crate::error::{DbResult, ErrorCode, ErrorResponse};
+
+use super::super::post::{self, PostStatus};
+use super::*;
+
+async fn find_post<C: ConnectionTrait>(conn: &C, post_id: i32) -> DbResult<post::Model> {
+    post::Entity::find_by_id(post_id)
+        .one(conn)
+        .await?
+        .ok_or_else(|| {
+            ErrorResponse::new(ErrorCode::RecordNotFound).with_message("Post does not exist")
+        })
+}
+
+/// `post` and everything in its group.
+async fn group_of<C: ConnectionTrait>(conn: &C, post: &post::Model) -> DbResult<Vec<post::Model>> {
+    match post.translation_group_id {
+        Some(group_id) => Ok(post::Entity::find()
+            .filter(post::Column::TranslationGroupId.eq(group_id))
+            .all(conn)
+            .await?),
+        None => Ok(vec![post.clone()]),
+    }
+}
+
+/// Actions for translation groups:
+/// - List the posts of a group or the translations of a post
+/// - Link two posts (merging their groups) and unlink a post
+impl Entity {
+    pub async fn create<C: ConnectionTrait>(conn: &C) -> DbResult<Model> {
+        let active = ActiveModel {
+            created_at: Set(chrono::Utc::now().fixed_offset()),
+            ..Default::default()
+        };
+        Ok(active.insert(conn).await?)
+    }
+
+    /// Posts of a group, by locale.
+    pub async fn members<C: ConnectionTrait>(
+        conn: &C,
+        group_id: i32,
+    ) -> DbResult<Vec<PostTranslation>> {
+        let rows = post::Entity::find()
+            .filter(post::Column::TranslationGroupId.eq(group_id))
+            .order_by_asc(post::Column::Locale)
+            .all(conn)
+            .await?;
+        Ok(rows.into_iter().map(PostTranslation::from).collect())
+    }
+
+    /// Translations of a post, itself excluded.
+    pub async fn translations_of(
+        conn: &DbConn,
+        post_id: i32,
+        group_id: Option<i32>,
+        published_only: bool,
+    ) -> DbResult<Vec<PostTranslation>> {
+        let Some(group_id) = group_id else {
+            return Ok(Vec::new());
+        };
+        Ok(Self::members(conn, group_id)
+            .await?
+            .into_iter()
+            .filter(|t| t.id != post_id && (!published_only || t.status == PostStatus::Published))
+            .collect())
+    }
+
+    /// Make two posts translations of each other. Their groups are merged;
+    /// fails when that would put two posts of one locale in the group.
+    pub async fn link(
+        conn: &DbConn,
+        post_id: i32,
+        other_id: i32,
+    ) -> DbResult<Vec<PostTranslation>> {
+        let txn = conn.begin().await?;
+        let post = find_post(&txn, post_id).await?;
+        let other = find_post(&txn, other_id).await?;
+
+        let current = group_of(&txn, &post).await?;
+        if current.iter().any(|p| p.id == other.id) {
+            txn.commit().await?;
+            return Self::members(conn, post.translation_group_id.unwrap_or_default()).await;
+        }
+        let incoming = group_of(&txn, &other).await?;
+        if let Some(locale) = locale_clash(
+            current.iter().map(|p| p.locale.as_str()),
+            incoming.iter().map(|p| p.locale.as_str()),
+        ) {
+            return Err(ErrorResponse::new(ErrorCode::InvalidInput)
+                .with_message(format!("The group already has a '{}' translation", locale))
+                .with_context(serde_json::json!({ "locale": locale })));
+        }
+
+        let group_id = match post.translation_group_id.or(other.translation_group_id) {
+            Some(id) => id,
+            None => Self::create(&txn).await?.id,
+        };
+        let ids: Vec<i32> = current
+            .iter()
+            .chain(incoming.iter())
+            .map(|p| p.id)
+            .collect();
+        post::Entity::update_many()
+            .col_expr(post::Column::TranslationGroupId, Expr::value(group_id))
+            .filter(post::Column::Id.is_in(ids))
+            .exec(&txn)
+            .await?;
+
+        // The other post's group was merged in and is now empty
+        for old in [post.translation_group_id, other.translation_group_id]
+            .into_iter()
+            .flatten()
+            .filter(|id| *id != group_id)
+        {
+            Entity::delete_by_id(old).exec(&txn).await?;
+        }
+        txn.commit().await?;
+
+        info!(post_id, other_id, group_id, "Posts linked as translations");
+        Self::members(conn, group_id).await
+    }
+
+    /// Take a post out of its group. A group left with one post is removed.
+    pub async fn unlink(conn: &DbConn, post_id: i32) -> DbResult<()> {
+        let txn = conn.begin().await?;
+        let post = find_post(&txn, post_id).await?;
+        let Some(group_id) = post.translation_group_id else {
+            return Ok(());
+        };
+
+        let mut active: post::ActiveModel = post.into();
+        active.translation_group_id = Set(None);
+        active.update(&txn).await?;
+
+        let remaining = post::Entity::find()
+            .filter(post::Column::TranslationGroupId.eq(group_id))
+            .count(&txn)
+            .await?;
+        if remaining <= 1 {
+            post::Entity::update_many()
+                .col_expr(
+                    post::Column::TranslationGroupId,
+                    Expr::value(Option::<i32>::None),
+                )
+                .filter(post::Column::TranslationGroupId.eq(group_id))
+                .exec(&txn)
+                .await?;
+            Entity::delete_by_id(group_id).exec(&txn).await?;
+        }
+        txn.commit().await?;
+
+        info!(post_id, group_id, "Post unlinked from its translations");
+        Ok(())
+    }
+}
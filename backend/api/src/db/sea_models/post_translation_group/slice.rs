@@ -0,0 +1,53 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::super::post::{self, PostStatus};
+
+/// A post as listed among the translations of another.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct PostTranslation {
+    pub id: i32,
+    pub locale: String,
+    pub slug: String,
+    pub title: String,
+    pub status: PostStatus,
+}
+
+impl From<post::Model> for PostTranslation {
+    fn from(post: post::Model) -> Self {
+        Self {
+            id: post.id,
+            locale: post.locale,
+            slug: post.slug,
+            title: post.title,
+            status: post.status,
+        }
+    }
+}
+
+/// A locale present in both sets, which would leave a merged group with two
+/// posts in that locale.
+pub fn locale_clash<'a>(
+    current: impl IntoIterator<Item = &'a str>,
+    incoming: impl IntoIterator<Item = &'a str>,
+) -> Option<String> {
+    let current: Vec<&str> = current.into_iter().collect();
+    incoming
+        .into_iter()
+        .find(|locale| current.contains(locale))
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locale_clash_finds_shared_locales() {
+        assert_eq!(locale_clash(["en", "de"], ["fr", "pt-br"]), None);
+        assert_eq!(
+            locale_clash(["en", "de"], ["fr", "de"]).as_deref(),
+            Some("de")
+        );
+    }
+}
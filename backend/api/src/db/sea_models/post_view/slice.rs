@@ -1,3 +1,4 @@
+use schemars::JsonSchema;
 use sea_orm::prelude::DateTimeWithTimeZone;
 use serde::{Deserialize, Serialize};
 
@@ -9,7 +10,7 @@ pub struct NewPostView {
     pub user_id: Option<i32>,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, JsonSchema)]
 pub struct PostViewQuery {
     pub page_no: Option<u64>,
     pub post_id: i32,
@@ -0,0 +1,92 @@
+use sea_orm::{entity::prelude::*, QueryOrder, Set, TransactionTrait};
+use tracing::info;
+
+use crate::error::DbResult;
+
+use super::super::user::UserRole;
+use super::*;
+
+/// Actions for the role/permission matrix:
+/// - Read the permissions of one role or of every role
+/// - Replace a role's permissions, or the whole matrix
+impl Entity {
+    pub async fn for_role(conn: &DbConn, role: UserRole) -> DbResult<Vec<String>> {
+        let rows = Entity::find()
+            .filter(Column::Role.eq(role))
+            .order_by_asc(Column::Permission)
+            .all(conn)
+            .await?;
+        Ok(rows.into_iter().map(|row| row.permission).collect())
+    }
+
+    pub async fn matrix(conn: &DbConn) -> DbResult<Vec<Model>> {
+        Ok(Entity::find()
+            .order_by_asc(Column::Role)
+            .order_by_asc(Column::Permission)
+            .all(conn)
+            .await?)
+    }
+
+    /// Roles holding `permission`.
+    pub async fn roles_with(conn: &DbConn, permission: &str) -> DbResult<Vec<UserRole>> {
+        let rows = Entity::find()
+            .filter(Column::Permission.eq(permission))
+            .all(conn)
+            .await?;
+        Ok(rows.into_iter().map(|row| row.role).collect())
+    }
+
+    /// Replace the permissions of `role`.
+    pub async fn set_for_role(
+        conn: &DbConn,
+        role: UserRole,
+        permissions: &[String],
+    ) -> DbResult<Vec<String>> {
+        let txn = conn.begin().await?;
+        Self::replace(&txn, role, permissions).await?;
+        txn.commit().await?;
+
+        info!(role = %role.to_string(), count = permissions.len(), "Role permissions replaced");
+        Self::for_role(conn, role).await
+    }
+
+    /// Replace the whole matrix, one role at a time, in one transaction.
+    pub async fn set_matrix(conn: &DbConn, matrix: &[(UserRole, Vec<String>)]) -> DbResult<()> {
+        let txn = conn.begin().await?;
+        for (role, permissions) in matrix {
+            Self::replace(&txn, *role, permissions).await?;
+        }
+        txn.commit().await?;
+
+        info!(roles = matrix.len(), "Permission matrix replaced");
+        Ok(())
+    }
+
+    async fn replace<C: ConnectionTrait>(
+        conn: &C,
+        role: UserRole,
+        permissions: &[String],
+    ) -> DbResult<()> {
+        Entity::delete_many()
+            .filter(Column::Role.eq(role))
+            .exec(conn)
+            .await?;
+
+        let now = chrono::Utc::now().fixed_offset();
+        let mut seen = std::collections::BTreeSet::new();
+        let rows: Vec<ActiveModel> = permissions
+            .iter()
+            .filter(|permission| seen.insert(permission.as_str()))
+            .map(|permission| ActiveModel {
+                role: Set(role),
+                permission: Set(permission.clone()),
+                created_at: Set(now),
+                ..Default::default()
+            })
+            .collect();
+        if !rows.is_empty() {
+            Entity::insert_many(rows).exec(conn).await?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,24 @@
+use schemars::JsonSchema;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::super::user::UserRole;
+
+/// A permission held by every user of a role, unless revoked for a user in
+/// `user_permissions`.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize, JsonSchema)]
+#[sea_orm(table_name = "role_permissions")]
+#[schemars(rename = "RolePermission")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub role: UserRole,
+    /// Permission name, e.g. `post.publish`.
+    pub permission: String,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -1,9 +1,8 @@
 use super::*;
 use sea_orm::{
     ActiveModelTrait, ColumnTrait, DatabaseConnection, DbErr, DeleteResult, EntityTrait,
-    PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, Set,
+    PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, Set, TryIntoModel,
 };
-use tower_sessions_redis_store::fred::interfaces::{KeysInterface, SetsInterface};
 
 impl Entity {
     pub const PER_PAGE: u64 = 20;
@@ -25,27 +24,48 @@ impl Entity {
             .await
     }
 
+    /// Every row a request can be checked against: blocked rules and
+    /// recorded route patterns.
+    pub async fn all(db: &DatabaseConnection) -> Result<Vec<Model>, DbErr> {
+        Entity::find()
+            .order_by_asc(Column::RoutePattern)
+            .all(db)
+            .await
+    }
+
+    /// Set the block flag and reason on `route_pattern`, creating the row if
+    /// needed. Rule settings are replaced when `rule` is given and left as
+    /// they are otherwise.
     pub async fn create_or_update(
         db: &DatabaseConnection,
         route_pattern: String,
         is_blocked: bool,
         reason: Option<String>,
+        rule: Option<RouteRule>,
     ) -> Result<Model, DbErr> {
-        if let Some(existing) = Self::find_by_pattern(db, &route_pattern).await? {
-            let mut active_model: ActiveModel = existing.into();
-            active_model.is_blocked = Set(is_blocked);
-            active_model.reason = Set(reason);
-            active_model.updated_at = Set(chrono::Utc::now().fixed_offset());
-            active_model.update(db).await
-        } else {
-            let new_route = ActiveModel {
+        let mut active_model: ActiveModel = match Self::find_by_pattern(db, &route_pattern).await? {
+            Some(existing) => existing.into(),
+            None => ActiveModel {
                 route_pattern: Set(route_pattern),
-                is_blocked: Set(is_blocked),
-                reason: Set(reason),
                 ..Default::default()
-            };
-            new_route.insert(db).await
+            },
+        };
+        active_model.is_blocked = Set(is_blocked);
+        active_model.reason = Set(reason);
+        active_model.updated_at = Set(chrono::Utc::now().fixed_offset());
+
+        if let Some(rule) = rule {
+            active_model.match_type = Set(rule.match_type.as_str().to_string());
+            active_model.methods = Set(rule.methods);
+            active_model.exempt_role = Set(rule.exempt_role);
+            active_model.active_from = Set(rule.active_from);
+            active_model.active_until = Set(rule.active_until);
+            active_model.status_code = Set(rule.status_code);
+            active_model.message = Set(rule.message);
+            active_model.retry_after_secs = Set(rule.retry_after_secs);
         }
+
+        active_model.save(db).await?.try_into_model()
     }
 
     pub async fn ensure_exists(
@@ -149,33 +169,4 @@ impl Entity {
             .exec(db)
             .await
     }
-
-    pub async fn sync_all_to_redis(
-        db: &DatabaseConnection,
-        redis_pool: tower_sessions_redis_store::fred::prelude::Pool,
-        known_routes_key: &str,
-        blocked_routes_key: &str,
-    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        let routes = Entity::find()
-            .order_by_asc(Column::RoutePattern)
-            .all(db)
-            .await?;
-
-        redis_pool.del::<(), _>(known_routes_key).await?;
-        redis_pool.del::<(), _>(blocked_routes_key).await?;
-
-        for route in routes {
-            redis_pool
-                .sadd::<(), _, _>(known_routes_key, route.route_pattern.clone())
-                .await?;
-
-            if route.is_blocked {
-                redis_pool
-                    .sadd::<(), _, _>(blocked_routes_key, route.route_pattern.clone())
-                    .await?;
-            }
-        }
-
-        Ok(())
-    }
 }
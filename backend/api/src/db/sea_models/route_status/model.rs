@@ -1,9 +1,13 @@
+use schemars::JsonSchema;
 use sea_orm::entity::prelude::*;
 use sea_orm::Set;
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+use crate::db::sea_models::user::UserRole;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, JsonSchema)]
 #[sea_orm(table_name = "route_status")]
+#[schemars(rename = "RouteStatus")]
 pub struct Model {
     #[sea_orm(primary_key)]
     pub id: i32,
@@ -13,9 +17,37 @@ pub struct Model {
 
     pub is_blocked: bool,
 
+    /// Admin note; not shown to blocked callers.
     #[sea_orm(nullable)]
     pub reason: Option<String>,
 
+    /// `exact`, `prefix` or `glob`, see [`super::RouteMatch`].
+    pub match_type: String,
+
+    /// Upper-case HTTP methods the rule applies to; empty means all.
+    pub methods: Vec<String>,
+
+    /// Users with this role or above aren't blocked.
+    #[sea_orm(nullable)]
+    pub exempt_role: Option<UserRole>,
+
+    #[sea_orm(nullable)]
+    pub active_from: Option<DateTimeWithTimeZone>,
+
+    #[sea_orm(nullable)]
+    pub active_until: Option<DateTimeWithTimeZone>,
+
+    /// Response status for blocked requests; 403 when unset.
+    #[sea_orm(nullable)]
+    pub status_code: Option<i32>,
+
+    /// Message shown to blocked callers.
+    #[sea_orm(nullable)]
+    pub message: Option<String>,
+
+    #[sea_orm(nullable)]
+    pub retry_after_secs: Option<i32>,
+
     pub created_at: DateTimeWithTimeZone,
     pub updated_at: DateTimeWithTimeZone,
 }
@@ -28,6 +60,8 @@ impl ActiveModelBehavior for ActiveModel {
         Self {
             created_at: Set(chrono::Utc::now().fixed_offset()),
             updated_at: Set(chrono::Utc::now().fixed_offset()),
+            match_type: Set(super::RouteMatch::Exact.as_str().to_string()),
+            methods: Set(Vec::new()),
             ..ActiveModelTrait::default()
         }
     }
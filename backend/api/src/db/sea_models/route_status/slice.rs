@@ -1,9 +1,10 @@
+use schemars::JsonSchema;
 use sea_orm::prelude::DateTimeWithTimeZone;
 use serde::{Deserialize, Serialize};
 
-use crate::utils::sort::SortParam;
+use crate::{db::sea_models::user::UserRole, utils::sort::SortParam};
 
-#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum BlockFilter {
     All,
@@ -17,7 +18,7 @@ impl BlockFilter {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 pub struct RouteStatusQuery {
     pub page: Option<u64>,
     pub block_filter: Option<BlockFilter>,
@@ -28,3 +29,46 @@ pub struct RouteStatusQuery {
     pub updated_at_gt: Option<DateTimeWithTimeZone>,
     pub updated_at_lt: Option<DateTimeWithTimeZone>,
 }
+
+/// How a rule's pattern is compared with the request.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RouteMatch {
+    /// The route's matched pattern (`/post/v1/view/{id}`) or the literal path.
+    Exact,
+    /// Any path starting with the pattern.
+    Prefix,
+    /// `*` matches within one path segment, `**` across segments.
+    Glob,
+}
+
+impl RouteMatch {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Exact => "exact",
+            Self::Prefix => "prefix",
+            Self::Glob => "glob",
+        }
+    }
+
+    pub fn parse(value: &str) -> Self {
+        match value {
+            "prefix" => Self::Prefix,
+            "glob" => Self::Glob,
+            _ => Self::Exact,
+        }
+    }
+}
+
+/// Rule settings written alongside a block; see `route_status::Model`.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct RouteRule {
+    pub match_type: RouteMatch,
+    pub methods: Vec<String>,
+    pub exempt_role: Option<UserRole>,
+    pub active_from: Option<DateTimeWithTimeZone>,
+    pub active_until: Option<DateTimeWithTimeZone>,
+    pub status_code: Option<i32>,
+    pub message: Option<String>,
+    pub retry_after_secs: Option<i32>,
+}
@@ -13,6 +13,7 @@ use super::model::ScheduledPostStatus;
 /// - Create a schedule
 /// - Upsert (create or update) a schedule for a post
 /// - Query helpers (find by post, list pending due items, list by status)
+/// - Status changes made by the scheduled publish job
 impl Entity {
     pub const PER_PAGE: u64 = 10;
 
@@ -113,6 +114,27 @@ impl Entity {
         Ok(items)
     }
 
+    /// Move a schedule from `from` to `to`. Returns false when it wasn't in
+    /// `from` any more, e.g. because another worker got to it first.
+    pub async fn set_status(
+        conn: &DbConn,
+        id: i32,
+        from: ScheduledPostStatus,
+        to: ScheduledPostStatus,
+    ) -> DbResult<bool> {
+        let result = Entity::update_many()
+            .col_expr(Column::Status, Expr::value(to))
+            .col_expr(
+                Column::UpdatedAt,
+                Expr::value(chrono::Utc::now().fixed_offset()),
+            )
+            .filter(Column::Id.eq(id))
+            .filter(Column::Status.eq(from))
+            .exec(conn)
+            .await?;
+        Ok(result.rows_affected > 0)
+    }
+
     /// List scheduled posts by status with pagination (page starts at 1).
     /// Returns (items, total_count).
     pub async fn list_by_status(
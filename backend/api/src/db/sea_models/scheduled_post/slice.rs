@@ -1,10 +1,11 @@
+use schemars::JsonSchema;
 use sea_orm::prelude::DateTimeWithTimeZone;
 use serde::{Deserialize, Serialize};
 
 use super::model::ScheduledPostStatus;
 
 /// Payload to create a new scheduled post entry.
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 pub struct CreateScheduledPost {
     pub post_id: i32,
     pub publish_at: DateTimeWithTimeZone,
@@ -13,14 +14,14 @@ pub struct CreateScheduledPost {
 }
 
 /// Payload to upsert (create or update) a scheduled post for a given post.
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 pub struct UpsertScheduledPost {
     pub post_id: i32,
     pub publish_at: DateTimeWithTimeZone,
 }
 
 /// Query parameters for listing scheduled posts by status with pagination.
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 pub struct ScheduledPostStatusQuery {
     pub status: ScheduledPostStatus,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -30,7 +31,7 @@ pub struct ScheduledPostStatusQuery {
 }
 
 /// Query parameters to fetch pending schedules due before or at a specific time.
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 pub struct ScheduledPostDueQuery {
     pub until: DateTimeWithTimeZone,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -0,0 +1,431 @@
+use sea_orm::{
+    entity::prelude::*,
+    sea_query::{Expr, OnConflict},
+    ColumnTrait, EntityTrait, Order, PaginatorTrait, QueryFilter, QueryOrder, Set,
+};
+use tracing::info;
+
+use crate::error::{DbResult, ErrorCode, ErrorResponse};
+use crate::utils::default_locale;
+
+use super::super::{category, post, post_series, tag};
+use super::*;
+
+/// Actions for slug redirects:
+/// - Resolve an old slug to the row that now owns it, per locale for posts
+/// - Record the old slug automatically when a slug changes
+/// - Manual redirect CRUD (admin), with loop detection
+impl Entity {
+    pub const PER_PAGE: u64 = 20;
+    /// Redirects followed when collapsing a chain before giving up on it
+    const MAX_CHAIN: usize = 8;
+
+    /// The redirect for `slug` in `locale`. Without a locale (entities that
+    /// share slugs across locales, or a post looked up without one) any
+    /// locale matches, the default one first.
+    pub async fn resolve<C: ConnectionTrait>(
+        conn: &C,
+        entity: RedirectEntity,
+        locale: Option<&str>,
+        slug: &str,
+    ) -> DbResult<Option<Model>> {
+        let mut select = Entity::find()
+            .filter(Column::Entity.eq(entity))
+            .filter(Column::FromSlug.eq(slug));
+        select = match locale {
+            Some(locale) => select.filter(Column::Locale.eq(locale)),
+            None => select.order_by(Expr::col(Column::Locale).eq(default_locale()), Order::Desc),
+        };
+        Ok(select.one(conn).await?)
+    }
+
+    /// Remember `old_slug` in `old_locale` for `target_id` after it moved to
+    /// `new_slug` in `new_locale`. Locales are the post's, `None` for the
+    /// other entities.
+    ///
+    /// A redirect for `new_slug` is dropped: the slug is live again, and
+    /// keeping it would point the target at itself.
+    pub async fn record_slug_change<C: ConnectionTrait>(
+        conn: &C,
+        entity: RedirectEntity,
+        target_id: i32,
+        old_locale: Option<&str>,
+        old_slug: &str,
+        new_locale: Option<&str>,
+        new_slug: &str,
+    ) -> DbResult<()> {
+        let old = (old_locale.unwrap_or(default_locale()), old_slug);
+        let new = (new_locale.unwrap_or(default_locale()), new_slug);
+        let Some((locale, old_slug)) = moved_from(old, new) else {
+            return Ok(());
+        };
+
+        Entity::delete_many()
+            .filter(Column::Entity.eq(entity))
+            .filter(Column::Locale.eq(new.0))
+            .filter(Column::FromSlug.eq(new_slug))
+            .exec(conn)
+            .await?;
+
+        let now = chrono::Utc::now().fixed_offset();
+        let active = ActiveModel {
+            entity: Set(entity),
+            locale: Set(locale.to_string()),
+            from_slug: Set(old_slug.to_string()),
+            target_id: Set(target_id),
+            kind: Set(RedirectKind::Auto),
+            created_by: Set(None),
+            created_at: Set(now),
+            updated_at: Set(now),
+            ..Default::default()
+        };
+
+        Entity::insert(active)
+            .on_conflict(
+                OnConflict::columns([Column::Entity, Column::Locale, Column::FromSlug])
+                    .update_columns([Column::TargetId, Column::Kind, Column::UpdatedAt])
+                    .to_owned(),
+            )
+            .exec(conn)
+            .await?;
+
+        info!(%entity, target_id, locale, old_slug, new_locale = new.0, new_slug, "Slug redirect recorded");
+        Ok(())
+    }
+
+    /// Drop every redirect pointing at a deleted row.
+    pub async fn delete_for_target<C: ConnectionTrait>(
+        conn: &C,
+        entity: RedirectEntity,
+        target_id: i32,
+    ) -> DbResult<u64> {
+        let result = Entity::delete_many()
+            .filter(Column::Entity.eq(entity))
+            .filter(Column::TargetId.eq(target_id))
+            .exec(conn)
+            .await?;
+        Ok(result.rows_affected)
+    }
+
+    /// Current slug of the target row and the locale its redirects live in,
+    /// if it exists.
+    pub async fn current_slug<C: ConnectionTrait>(
+        conn: &C,
+        entity: RedirectEntity,
+        target_id: i32,
+    ) -> DbResult<Option<(String, String)>> {
+        let shared = |slug: String| (slug, default_locale().to_string());
+        let slug = match entity {
+            RedirectEntity::Post => post::Entity::find_by_id(target_id)
+                .one(conn)
+                .await?
+                .map(|m| (m.slug, m.locale)),
+            RedirectEntity::Category => category::Entity::find_by_id(target_id)
+                .one(conn)
+                .await?
+                .map(|m| shared(m.slug)),
+            RedirectEntity::Tag => tag::Entity::find_by_id(target_id)
+                .one(conn)
+                .await?
+                .map(|m| shared(m.slug)),
+            RedirectEntity::Series => post_series::Entity::find_by_id(target_id)
+                .one(conn)
+                .await?
+                .map(|m| shared(m.slug)),
+        };
+        Ok(slug)
+    }
+
+    async fn slug_in_use<C: ConnectionTrait>(
+        conn: &C,
+        entity: RedirectEntity,
+        locale: &str,
+        slug: &str,
+    ) -> DbResult<bool> {
+        let count = match entity {
+            RedirectEntity::Post => {
+                post::Entity::find()
+                    .filter(post::Column::Slug.eq(slug))
+                    .filter(post::Column::Locale.eq(locale))
+                    .count(conn)
+                    .await?
+            }
+            RedirectEntity::Category => {
+                category::Entity::find()
+                    .filter(category::Column::Slug.eq(slug))
+                    .count(conn)
+                    .await?
+            }
+            RedirectEntity::Tag => {
+                tag::Entity::find()
+                    .filter(tag::Column::Slug.eq(slug))
+                    .count(conn)
+                    .await?
+            }
+            RedirectEntity::Series => {
+                post_series::Entity::find()
+                    .filter(post_series::Column::Slug.eq(slug))
+                    .count(conn)
+                    .await?
+            }
+        };
+        Ok(count > 0)
+    }
+
+    /// Check a manual redirect before saving it and return where it belongs:
+    /// the target's locale, and the target at the end of any chain of
+    /// redirects starting from the target's slug. `existing_id` is the
+    /// redirect being edited, if any.
+    async fn validate_manual(
+        conn: &DbConn,
+        entity: RedirectEntity,
+        from_slug: &str,
+        target_id: i32,
+        existing_id: Option<i32>,
+    ) -> DbResult<(String, i32)> {
+        let (target_slug, locale) = Self::current_slug(conn, entity, target_id)
+            .await?
+            .ok_or_else(|| {
+                ErrorResponse::new(ErrorCode::RecordNotFound)
+                    .with_message(format!("Target {} not found", entity))
+            })?;
+
+        let mut hops: Vec<(i32, String)> = Vec::new();
+        let mut hop = (target_slug.clone(), locale.clone());
+        while hops.len() < Self::MAX_CHAIN {
+            let Some(redirect) = Self::resolve(conn, entity, Some(&hop.1), &hop.0).await? else {
+                break;
+            };
+            // The redirect being edited no longer leaves from its old slug
+            if Some(redirect.id) == existing_id {
+                break;
+            }
+            let Some(next) = Self::current_slug(conn, entity, redirect.target_id).await? else {
+                break;
+            };
+            let seen = next.0 == from_slug
+                || next.0 == target_slug
+                || hops.iter().any(|(_, slug)| *slug == next.0);
+            hops.push((redirect.target_id, next.0.clone()));
+            if seen {
+                break;
+            }
+            hop = next;
+        }
+
+        let target_id =
+            collapse_chain(from_slug, target_id, &target_slug, &hops).map_err(|path| {
+                ErrorResponse::new(ErrorCode::InvalidInput)
+                    .with_message(format!(
+                        "Redirect from '{}' would loop: {}",
+                        from_slug,
+                        path.join(" -> ")
+                    ))
+                    .with_context(serde_json::json!({ "from_slug": from_slug, "path": path }))
+            })?;
+
+        if Self::slug_in_use(conn, entity, &locale, from_slug).await? {
+            return Err(
+                ErrorResponse::new(ErrorCode::ResourceConflict).with_message(format!(
+                    "A {} already uses the slug '{}'; a redirect would never be reached",
+                    entity, from_slug
+                )),
+            );
+        }
+
+        if let Some(other) = Self::resolve(conn, entity, Some(&locale), from_slug).await? {
+            if Some(other.id) != existing_id {
+                return Err(ErrorResponse::new(ErrorCode::ResourceConflict)
+                    .with_message(format!("A redirect from '{}' already exists", from_slug))
+                    .with_context(serde_json::json!({ "redirect": other })));
+            }
+        }
+        Ok((locale, target_id))
+    }
+
+    pub async fn create_manual(conn: &DbConn, new_redirect: NewSlugRedirect) -> DbResult<Model> {
+        let (locale, target_id) = Self::validate_manual(
+            conn,
+            new_redirect.entity,
+            &new_redirect.from_slug,
+            new_redirect.target_id,
+            None,
+        )
+        .await?;
+
+        let now = chrono::Utc::now().fixed_offset();
+        let active = ActiveModel {
+            entity: Set(new_redirect.entity),
+            locale: Set(locale),
+            from_slug: Set(new_redirect.from_slug),
+            target_id: Set(target_id),
+            kind: Set(RedirectKind::Manual),
+            created_by: Set(new_redirect.created_by),
+            created_at: Set(now),
+            updated_at: Set(now),
+            ..Default::default()
+        };
+        Ok(active.insert(conn).await?)
+    }
+
+    pub async fn update(
+        conn: &DbConn,
+        redirect_id: i32,
+        update: UpdateSlugRedirect,
+    ) -> DbResult<Option<Model>> {
+        let Some(existing) = Entity::find_by_id(redirect_id).one(conn).await? else {
+            return Ok(None);
+        };
+
+        let from_slug = update
+            .from_slug
+            .unwrap_or_else(|| existing.from_slug.clone());
+        let target_id = update.target_id.unwrap_or(existing.target_id);
+        let (locale, target_id) = Self::validate_manual(
+            conn,
+            existing.entity,
+            &from_slug,
+            target_id,
+            Some(existing.id),
+        )
+        .await?;
+
+        let mut active: ActiveModel = existing.into();
+        active.locale = Set(locale);
+        active.from_slug = Set(from_slug);
+        active.target_id = Set(target_id);
+        // An edited redirect is owned by whoever edited it
+        active.kind = Set(RedirectKind::Manual);
+        active.updated_at = Set(chrono::Utc::now().fixed_offset());
+        Ok(Some(active.update(conn).await?))
+    }
+
+    pub async fn delete(conn: &DbConn, redirect_id: i32) -> DbResult<u64> {
+        let result = Entity::delete_by_id(redirect_id).exec(conn).await?;
+        Ok(result.rows_affected)
+    }
+
+    pub async fn find_with_query(
+        conn: &DbConn,
+        query: SlugRedirectQuery,
+    ) -> DbResult<(Vec<Model>, u64)> {
+        let mut select = Entity::find()
+            .order_by_desc(Column::UpdatedAt)
+            .order_by_desc(Column::Id);
+
+        if let Some(entity) = query.entity {
+            select = select.filter(Column::Entity.eq(entity));
+        }
+        if let Some(locale) = query.locale {
+            select = select.filter(Column::Locale.eq(locale));
+        }
+        if let Some(kind) = query.kind {
+            select = select.filter(Column::Kind.eq(kind));
+        }
+        if let Some(target_id) = query.target_id {
+            select = select.filter(Column::TargetId.eq(target_id));
+        }
+        if let Some(search) = query.search {
+            select = select.filter(Column::FromSlug.contains(&search));
+        }
+
+        let page = match query.page {
+            Some(p) if p > 0 => p,
+            _ => 1,
+        };
+        let paginator = select.paginate(conn, Self::PER_PAGE);
+        let total = paginator.num_items().await?;
+        let items = paginator.fetch_page(page - 1).await?;
+        Ok((items, total))
+    }
+}
+
+/// The (locale, slug) a row was served at, if changing its slug or its
+/// locale moved it away from there.
+fn moved_from<'a>(old: (&'a str, &'a str), new: (&str, &str)) -> Option<(&'a str, &'a str)> {
+    (old != new).then_some(old)
+}
+
+/// Follow a redirect from `from_slug` to `target_slug` (owned by
+/// `target_id`) along `hops`, the (target, slug) pairs reached by the
+/// redirects that continue from there, and return the target the chain ends
+/// at. A chain that comes back to a slug already on it is a loop; the error
+/// is the path up to that point.
+fn collapse_chain(
+    from_slug: &str,
+    target_id: i32,
+    target_slug: &str,
+    hops: &[(i32, String)],
+) -> Result<i32, Vec<String>> {
+    let mut path = vec![from_slug.to_string()];
+    let mut end = target_id;
+    for (id, slug) in std::iter::once((target_id, target_slug))
+        .chain(hops.iter().map(|(id, slug)| (*id, slug.as_str())))
+    {
+        let repeated = path.iter().any(|seen| seen == slug);
+        path.push(slug.to_string());
+        if repeated {
+            return Err(path);
+        }
+        end = id;
+    }
+    Ok(end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hops(items: &[(i32, &str)]) -> Vec<(i32, String)> {
+        items
+            .iter()
+            .map(|(id, slug)| (*id, slug.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn slug_or_locale_changes_leave_a_redirect_behind() {
+        assert_eq!(
+            moved_from(("en", "hello"), ("en", "hello-world")),
+            Some(("en", "hello"))
+        );
+        // Moving a post to another locale keeps its old URL working
+        assert_eq!(
+            moved_from(("en", "hello"), ("de", "hello")),
+            Some(("en", "hello"))
+        );
+        assert_eq!(
+            moved_from(("en", "hello"), ("de", "hallo")),
+            Some(("en", "hello"))
+        );
+        assert_eq!(moved_from(("en", "hello"), ("en", "hello")), None);
+    }
+
+    #[test]
+    fn redirect_to_own_slug_is_a_loop() {
+        assert_eq!(
+            collapse_chain("hello", 1, "hello", &[]),
+            Err(vec!["hello".to_string(), "hello".to_string()])
+        );
+        assert_eq!(collapse_chain("hello-old", 1, "hello", &[]), Ok(1));
+    }
+
+    #[test]
+    fn redirect_back_to_its_own_slug_is_a_loop() {
+        // a -> b, and b already redirects back to a
+        assert_eq!(
+            collapse_chain("a", 1, "b", &hops(&[(2, "a")])),
+            Err(vec!["a".to_string(), "b".to_string(), "a".to_string()])
+        );
+        // A cycle further down the chain is caught as well
+        assert!(collapse_chain("a", 1, "b", &hops(&[(2, "c"), (1, "b")])).is_err());
+    }
+
+    #[test]
+    fn chains_collapse_onto_the_last_target() {
+        assert_eq!(
+            collapse_chain("a", 1, "b", &hops(&[(2, "c"), (3, "d")])),
+            Ok(3)
+        );
+    }
+}
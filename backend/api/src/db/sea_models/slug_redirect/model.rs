@@ -0,0 +1,112 @@
+use schemars::JsonSchema;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Kind of row an old slug belonged to.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    EnumIter,
+    DeriveActiveEnum,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+)]
+#[sea_orm(
+    rs_type = "String",
+    db_type = "Enum",
+    enum_name = "slug_redirect_entity"
+)]
+#[serde(rename_all = "lowercase")]
+pub enum RedirectEntity {
+    #[sea_orm(string_value = "post")]
+    Post,
+    #[sea_orm(string_value = "category")]
+    Category,
+    #[sea_orm(string_value = "tag")]
+    Tag,
+    #[sea_orm(string_value = "series")]
+    Series,
+}
+
+impl fmt::Display for RedirectEntity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::Post => "post",
+            Self::Category => "category",
+            Self::Tag => "tag",
+            Self::Series => "series",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    EnumIter,
+    DeriveActiveEnum,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "slug_redirect_kind")]
+#[serde(rename_all = "lowercase")]
+pub enum RedirectKind {
+    /// Recorded when the slug of the target changed.
+    #[sea_orm(string_value = "auto")]
+    Auto,
+    /// Added by an admin.
+    #[sea_orm(string_value = "manual")]
+    Manual,
+}
+
+/// An old slug and the row that now answers for it.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize, JsonSchema)]
+#[sea_orm(table_name = "slug_redirects")]
+#[schemars(rename = "SlugRedirect")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+
+    pub entity: RedirectEntity,
+    /// Locale the old slug was served in. Post slugs are unique per locale;
+    /// categories, tags and series share one namespace and use the default.
+    pub locale: String,
+    pub from_slug: String,
+    /// Id of the post / category / tag / series; its current slug is the
+    /// canonical one.
+    pub target_id: i32,
+    pub kind: RedirectKind,
+    pub created_by: Option<i32>,
+
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::super::user::Entity",
+        from = "Column::CreatedBy",
+        to = "super::super::user::Column::Id",
+        on_delete = "SetNull"
+    )]
+    CreatedBy,
+}
+
+impl Related<super::super::user::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::CreatedBy.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
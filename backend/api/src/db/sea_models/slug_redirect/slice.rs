@@ -0,0 +1,53 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::model::{RedirectEntity, RedirectKind};
+
+/// Manual redirect to create
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct NewSlugRedirect {
+    pub entity: RedirectEntity,
+    pub from_slug: String,
+    pub target_id: i32,
+    pub created_by: Option<i32>,
+}
+
+/// Changes to an existing redirect
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct UpdateSlugRedirect {
+    pub from_slug: Option<String>,
+    pub target_id: Option<i32>,
+}
+
+/// Query params for listing redirects
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct SlugRedirectQuery {
+    pub page: Option<u64>,
+    pub entity: Option<RedirectEntity>,
+    pub locale: Option<String>,
+    pub kind: Option<RedirectKind>,
+    pub target_id: Option<i32>,
+    /// Substring of `from_slug`
+    pub search: Option<String>,
+}
+
+/// A slug lookup answered through a redirect.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SlugRedirectHit {
+    /// Slug that was requested.
+    pub from: String,
+    /// Current slug of the target; clients should 301 to it.
+    pub to: String,
+}
+
+/// Redirect info for a lookup by `requested` that found a row whose slug is
+/// `canonical`; `None` for id lookups and exact slug matches.
+pub fn redirect_hit(requested: &str, canonical: &str) -> Option<SlugRedirectHit> {
+    if requested.parse::<i32>().is_ok() || requested == canonical {
+        return None;
+    }
+    Some(SlugRedirectHit {
+        from: requested.to_string(),
+        to: canonical.to_string(),
+    })
+}
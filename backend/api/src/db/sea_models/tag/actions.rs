@@ -2,6 +2,7 @@ use crate::error::{DbResult, ErrorCode, ErrorResponse};
 use sea_orm::{entity::prelude::*, Condition, Order, QueryOrder, Set};
 
 use super::*;
+use crate::db::sea_models::slug_redirect::{self, RedirectEntity};
 use crate::utils::color::{derive_text_color, DEFAULT_BG_COLOR};
 
 impl Entity {
@@ -21,6 +22,7 @@ impl Entity {
             color: Set(color),
             text_color: Set(text_color),
             is_active: Set(is_active),
+            locale: Set(new_tag.locale),
             created_at: Set(now),
             updated_at: Set(now),
             ..Default::default()
@@ -43,6 +45,7 @@ impl Entity {
         };
 
         if let Some(tag_model) = tag {
+            let old_slug = tag_model.slug.clone();
             let mut tag_active: ActiveModel = tag_model.into();
 
             if let Some(name) = update_tag.name {
@@ -73,18 +76,32 @@ impl Entity {
                 tag_active.is_active = Set(is_active);
             }
 
+            if let Some(locale) = update_tag.locale {
+                tag_active.locale = Set(locale);
+            }
+
             tag_active.updated_at = Set(chrono::Utc::now().fixed_offset());
 
-            match tag_active.update(conn).await {
-                Ok(updated_tag) => Ok(Some(updated_tag)),
-                Err(err) => Err(err.into()),
-            }
+            let updated_tag = tag_active.update(conn).await?;
+            slug_redirect::Entity::record_slug_change(
+                conn,
+                RedirectEntity::Tag,
+                tag_id,
+                None,
+                &old_slug,
+                None,
+                &updated_tag.slug,
+            )
+            .await?;
+            Ok(Some(updated_tag))
         } else {
             Ok(None)
         }
     }
 
     pub async fn delete(conn: &DbConn, tag_id: i32) -> DbResult<u64> {
+        slug_redirect::Entity::delete_for_target(conn, RedirectEntity::Tag, tag_id).await?;
+
         match Self::delete_by_id(tag_id).exec(conn).await {
             Ok(result) => Ok(result.rows_affected),
             Err(err) => Err(err.into()),
@@ -135,6 +152,15 @@ impl Entity {
             tag_query = tag_query.filter(Column::IsActive.eq(active));
         }
 
+        // Optional locale filter; shared tags always match
+        if let Some(locale) = query.locale {
+            tag_query = tag_query.filter(
+                Condition::any()
+                    .add(Column::Locale.eq(locale))
+                    .add(Column::Locale.is_null()),
+            );
+        }
+
         // Optional created_at/updated_at range filters
         if let Some(ts) = query.created_at_gt {
             tag_query = tag_query.filter(Column::CreatedAt.gt(ts));
@@ -183,4 +209,13 @@ impl Entity {
             Err(err) => Err(err.into()),
         }
     }
+
+    /// Find a tag by slug.
+    pub async fn find_by_slug(conn: &DbConn, slug: String) -> DbResult<Option<Model>> {
+        let model = Entity::find()
+            .filter(Column::Slug.eq(slug))
+            .one(conn)
+            .await?;
+        Ok(model)
+    }
 }
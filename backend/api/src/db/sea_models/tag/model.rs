@@ -1,8 +1,10 @@
+use schemars::JsonSchema;
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize, JsonSchema)]
 #[sea_orm(table_name = "tags")]
+#[schemars(rename = "Tag")]
 pub struct Model {
     #[sea_orm(primary_key)]
     pub id: i32,
@@ -12,6 +14,8 @@ pub struct Model {
     pub color: String,
     pub text_color: String,
     pub is_active: bool,
+    /// `None` when shared by all locales.
+    pub locale: Option<String>,
     pub created_at: DateTimeWithTimeZone,
     pub updated_at: DateTimeWithTimeZone,
 }
@@ -1,4 +1,5 @@
 use crate::utils::SortParam;
+use schemars::JsonSchema;
 use sea_orm::prelude::DateTimeWithTimeZone;
 use serde::{Deserialize, Serialize};
 
@@ -10,6 +11,8 @@ pub struct NewTag {
     pub color: Option<String>,
     pub text_color: Option<String>,
     pub is_active: Option<bool>,
+    /// `None` makes the tag shared by every locale.
+    pub locale: Option<String>,
 }
 
 #[derive(Deserialize, Debug)]
@@ -20,10 +23,11 @@ pub struct UpdateTag {
     pub color: Option<String>,
     pub text_color: Option<String>,
     pub is_active: Option<bool>,
+    pub locale: Option<Option<String>>,
     pub updated_at: DateTimeWithTimeZone,
 }
 
-#[derive(Clone, Debug, Serialize, PartialEq)]
+#[derive(Clone, Debug, Serialize, PartialEq, JsonSchema)]
 pub struct TagQuery {
     pub page: Option<u64>,
     pub search: Option<String>,
@@ -31,6 +35,8 @@ pub struct TagQuery {
     pub sorts: Option<Vec<SortParam>>,
     // Optional filter for active state
     pub is_active: Option<bool>,
+    // Tags in this locale plus the ones shared by every locale
+    pub locale: Option<String>,
     // Optional created_at/updated_at range filters
     pub created_at_gt: Option<DateTimeWithTimeZone>,
     pub created_at_lt: Option<DateTimeWithTimeZone>,
@@ -1,11 +1,15 @@
 use crate::{
-    db::sea_models::email_verification,
+    db::sea_models::{
+        email_verification,
+        webhook::{self, events},
+    },
     error::{DbResult, ErrorCode, ErrorResponse},
 };
 use sea_orm::{
     entity::prelude::*, prelude::Expr, sea_query::Alias, JoinType, Order, QueryOrder, QuerySelect,
     Set, TransactionTrait,
 };
+use serde_json::json;
 use tokio::task;
 use tracing::{error, info, instrument, warn};
 
@@ -15,6 +19,19 @@ use crate::db::sea_models::media_usage::EntityType;
 impl Entity {
     pub const PER_PAGE: u64 = 20;
 
+    /// User summary sent with `user.*` webhook events.
+    fn webhook_data(user: &Model) -> serde_json::Value {
+        json!({
+            "id": user.id,
+            "name": user.name,
+            "email": user.email,
+            "role": user.role,
+            "is_verified": user.is_verified,
+            "oauth_provider": user.oauth_provider,
+            "created_at": user.created_at,
+        })
+    }
+
     #[allow(dead_code)]
     async fn load_media_for_users(
         conn: &DbConn,
@@ -99,6 +116,7 @@ impl Entity {
                         .with_message("Failed to commit transaction")
                 })?;
                 info!(user_id = model.id, email = %model.email, "User created");
+                webhook::Entity::emit(conn, events::USER_CREATED, Self::webhook_data(&model)).await;
                 Ok(model)
             }
             Err(err) => {
@@ -112,6 +130,34 @@ impl Entity {
         }
     }
 
+    /// Create an account for someone who hasn't signed up yet (e.g. an author
+    /// brought in by a blog import). It has no password and stays unverified
+    /// until the person claims it through the password reset flow.
+    #[instrument(skip(conn), fields(user_id))]
+    pub async fn create_invited(
+        conn: &DbConn,
+        name: String,
+        email: String,
+        role: UserRole,
+    ) -> DbResult<Model> {
+        let now = chrono::Utc::now().fixed_offset();
+        let user = ActiveModel {
+            name: Set(name),
+            email: Set(email),
+            password: Set(None),
+            role: Set(role),
+            is_verified: Set(false),
+            created_at: Set(now),
+            updated_at: Set(now),
+            ..Default::default()
+        };
+
+        let model = user.insert(conn).await?;
+        tracing::Span::current().record("user_id", model.id);
+        info!(user_id = model.id, email = %model.email, "Invited user created");
+        Ok(model)
+    }
+
     #[instrument(skip(conn, update_user), fields(user_id))]
     pub async fn update(
         conn: &DbConn,
@@ -273,6 +319,7 @@ impl Entity {
             Ok(model) => {
                 tracing::Span::current().record("user_id", model.id);
                 info!(user_id = model.id, email = %model.email, "User created from Google");
+                webhook::Entity::emit(conn, events::USER_CREATED, Self::webhook_data(&model)).await;
                 Ok(model)
             }
             Err(err) => {
@@ -359,6 +406,7 @@ impl Entity {
         }
 
         txn.commit().await?;
+        webhook::Entity::emit(conn, events::USER_CREATED, Self::webhook_data(&model)).await;
 
         Self::find_by_id_with_relations(conn, model.id).await
     }
@@ -434,6 +482,7 @@ impl Entity {
     }
 
     pub async fn admin_delete(conn: &DbConn, user_id: i32) -> DbResult<u64> {
+        let existing = Self::find_by_id(user_id).one(conn).await?;
         let txn = conn.begin().await?;
 
         super::super::media_usage::Entity::delete_by_entity(&txn, EntityType::User, user_id)
@@ -443,6 +492,15 @@ impl Entity {
 
         txn.commit().await?;
 
+        if let (Some(user), true) = (existing, result.rows_affected > 0) {
+            webhook::Entity::emit(
+                conn,
+                events::USER_DELETED,
+                json!({ "id": user.id, "email": user.email }),
+            )
+            .await;
+        }
+
         Ok(result.rows_affected)
     }
 
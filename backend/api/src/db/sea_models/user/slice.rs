@@ -1,10 +1,11 @@
 use super::UserRole;
 use chrono::{DateTime, FixedOffset};
+use schemars::JsonSchema;
 use sea_orm::prelude::DateTimeWithTimeZone;
 use sea_orm::FromQueryResult;
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 pub struct UserMedia {
     pub id: i32,
     pub object_key: String,
@@ -15,7 +16,7 @@ pub struct UserMedia {
     pub size: i64,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 pub struct UserWithRelations {
     pub id: i32,
     pub name: String,
@@ -29,7 +30,7 @@ pub struct UserWithRelations {
     pub avatar: Option<UserMedia>,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize, FromQueryResult)]
+#[derive(Clone, Debug, Serialize, Deserialize, FromQueryResult, JsonSchema)]
 pub struct UserWithJoinedData {
     // User fields
     pub id: i32,
@@ -114,7 +115,7 @@ pub struct VerifiedUser {
     pub updated_at: DateTimeWithTimeZone,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, JsonSchema)]
 pub struct AdminUserQuery {
     pub page: Option<u64>,
     pub email: Option<String>,
@@ -148,3 +149,11 @@ pub struct AdminUpdateUser {
     pub is_verified: Option<bool>,
     pub updated_at: DateTimeWithTimeZone,
 }
+
+/// Response of `/auth/v1/2fa/setup`; the backup codes are only shown here
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct TwoFASetup {
+    pub secret: String,
+    pub otpauth_url: String,
+    pub backup_codes: Vec<String>,
+}
@@ -1,8 +1,9 @@
+use schemars::JsonSchema;
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 
 /// New ban record to be created
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct NewUserBan {
     pub user_id: i32,
     pub reason: Option<String>,
@@ -44,7 +45,7 @@ impl NewUserBan {
 }
 
 /// Query params for listing bans
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 pub struct UserBanQuery {
     pub page_no: Option<i64>,
     pub user_id: Option<i32>,
@@ -0,0 +1,61 @@
+use sea_orm::{entity::prelude::*, sea_query::OnConflict, PaginatorTrait, QueryOrder, Set};
+use tracing::{info, instrument};
+
+use crate::error::DbResult;
+
+use super::*;
+
+impl Entity {
+    /// Follow `followed_id`. Returns false when already following.
+    #[instrument(skip(conn))]
+    pub async fn follow(conn: &DbConn, follower_id: i32, followed_id: i32) -> DbResult<bool> {
+        let inserted = Entity::insert(ActiveModel {
+            follower_id: Set(follower_id),
+            followed_id: Set(followed_id),
+            created_at: Set(chrono::Utc::now().fixed_offset()),
+            ..Default::default()
+        })
+        .on_conflict(
+            OnConflict::columns([Column::FollowerId, Column::FollowedId])
+                .do_nothing()
+                .to_owned(),
+        )
+        .exec_without_returning(conn)
+        .await?;
+        if inserted > 0 {
+            info!(follower_id, followed_id, "User followed");
+        }
+        Ok(inserted > 0)
+    }
+
+    /// Stop following. Returns false when not following.
+    #[instrument(skip(conn))]
+    pub async fn unfollow(conn: &DbConn, follower_id: i32, followed_id: i32) -> DbResult<bool> {
+        let deleted = Entity::delete_many()
+            .filter(Column::FollowerId.eq(follower_id))
+            .filter(Column::FollowedId.eq(followed_id))
+            .exec(conn)
+            .await?;
+        Ok(deleted.rows_affected > 0)
+    }
+
+    /// Ids of the users `follower_id` follows, most recent first.
+    pub async fn following(conn: &DbConn, follower_id: i32) -> DbResult<Vec<i32>> {
+        let follows = Entity::find()
+            .filter(Column::FollowerId.eq(follower_id))
+            .order_by_desc(Column::CreatedAt)
+            .all(conn)
+            .await?;
+        Ok(follows
+            .into_iter()
+            .map(|follow| follow.followed_id)
+            .collect())
+    }
+
+    pub async fn followers_count(conn: &DbConn, followed_id: i32) -> DbResult<u64> {
+        Ok(Entity::find()
+            .filter(Column::FollowedId.eq(followed_id))
+            .count(conn)
+            .await?)
+    }
+}
@@ -0,0 +1,35 @@
+use schemars::JsonSchema;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// A reader following an author; they're notified when the author publishes.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize, JsonSchema)]
+#[sea_orm(table_name = "user_follows")]
+#[schemars(rename = "UserFollow")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub follower_id: i32,
+    pub followed_id: i32,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::super::user::Entity",
+        from = "Column::FollowerId",
+        to = "super::super::user::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Follower,
+    #[sea_orm(
+        belongs_to = "super::super::user::Entity",
+        from = "Column::FollowedId",
+        to = "super::super::user::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Followed,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,67 @@
+use sea_orm::{entity::prelude::*, sea_query::OnConflict, QueryOrder, Set};
+use tracing::info;
+
+use crate::error::DbResult;
+
+use super::*;
+
+/// Actions for per-user permission overrides:
+/// - List a user's overrides, or the users granted a permission
+/// - Set and remove an override
+impl Entity {
+    pub async fn for_user(conn: &DbConn, user_id: i32) -> DbResult<Vec<Model>> {
+        Ok(Entity::find()
+            .filter(Column::UserId.eq(user_id))
+            .order_by_asc(Column::Permission)
+            .all(conn)
+            .await?)
+    }
+
+    /// Overrides on `permission`, granting and revoking.
+    pub async fn for_permission(conn: &DbConn, permission: &str) -> DbResult<Vec<Model>> {
+        Ok(Entity::find()
+            .filter(Column::Permission.eq(permission))
+            .all(conn)
+            .await?)
+    }
+
+    pub async fn set(
+        conn: &DbConn,
+        user_id: i32,
+        permission: &str,
+        granted: bool,
+        updated_by: Option<i32>,
+    ) -> DbResult<()> {
+        let now = chrono::Utc::now().fixed_offset();
+        let active = ActiveModel {
+            user_id: Set(user_id),
+            permission: Set(permission.to_string()),
+            granted: Set(granted),
+            updated_by: Set(updated_by),
+            created_at: Set(now),
+            updated_at: Set(now),
+            ..Default::default()
+        };
+        Entity::insert(active)
+            .on_conflict(
+                OnConflict::columns([Column::UserId, Column::Permission])
+                    .update_columns([Column::Granted, Column::UpdatedBy, Column::UpdatedAt])
+                    .to_owned(),
+            )
+            .exec(conn)
+            .await?;
+
+        info!(user_id, permission, granted, "User permission override set");
+        Ok(())
+    }
+
+    pub async fn remove(conn: &DbConn, user_id: i32, permission: &str) -> DbResult<u64> {
+        let result = Entity::delete_many()
+            .filter(Column::UserId.eq(user_id))
+            .filter(Column::Permission.eq(permission))
+            .exec(conn)
+            .await?;
+        info!(user_id, permission, "User permission override removed");
+        Ok(result.rows_affected)
+    }
+}
@@ -0,0 +1,4 @@
+pub mod actions;
+pub mod model;
+
+pub use model::{ActiveModel, Column, Entity, Model, Relation};
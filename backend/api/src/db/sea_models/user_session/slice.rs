@@ -1,8 +1,9 @@
+use schemars::JsonSchema;
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 
 /// New session record to be created when a user logs in (or a device is registered).
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct NewUserSession {
     pub user_id: i32,
     pub device: Option<String>,
@@ -20,7 +21,7 @@ impl NewUserSession {
 }
 
 /// Update payload for a session. Used to touch last_seen or revoke a session.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct UpdateUserSession {
     pub last_seen: Option<DateTimeWithTimeZone>,
     pub revoked_at: Option<DateTimeWithTimeZone>,
@@ -47,7 +48,7 @@ impl UpdateUserSession {
 }
 
 /// Admin query for listing/filtering user sessions.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct AdminUserSessionQuery {
     pub page_no: Option<i64>,
     pub user_id: Option<i32>,
@@ -0,0 +1,202 @@
+use sea_orm::{entity::prelude::*, DatabaseBackend, QueryOrder, Set, Statement};
+use serde_json::{json, Value};
+use tracing::error;
+
+use crate::{
+    db::sea_models::webhook_delivery::{self, NewDelivery},
+    error::DbResult,
+};
+
+use super::*;
+
+/// Actions for outbound webhooks:
+/// - Admin create, update, secret rotation and delete
+/// - Fan an event out to the subscribed webhooks as queued deliveries
+/// - Track consecutive failures and disable failing endpoints
+impl Entity {
+    pub async fn create(conn: &DbConn, webhook: NewWebhook) -> DbResult<Model> {
+        let now = chrono::Utc::now().fixed_offset();
+        let model = ActiveModel {
+            name: Set(webhook.name),
+            url: Set(webhook.url),
+            secret: Set(webhook.secret),
+            events: Set(webhook.events),
+            enabled: Set(webhook.enabled),
+            consecutive_failures: Set(0),
+            created_by: Set(webhook.created_by),
+            created_at: Set(now),
+            updated_at: Set(now),
+            ..Default::default()
+        };
+        Ok(model.insert(conn).await?)
+    }
+
+    /// Apply admin edits. Turning a webhook back on clears its failure count
+    /// and the reason it was disabled.
+    pub async fn update(conn: &DbConn, webhook: Model, changes: WebhookUpdate) -> DbResult<Model> {
+        let now = chrono::Utc::now().fixed_offset();
+        let was_enabled = webhook.enabled;
+        let mut active: ActiveModel = webhook.into();
+        if let Some(name) = changes.name {
+            active.name = Set(name);
+        }
+        if let Some(url) = changes.url {
+            active.url = Set(url);
+        }
+        if let Some(events) = changes.events {
+            active.events = Set(events);
+        }
+        match changes.enabled {
+            Some(true) if !was_enabled => {
+                active.enabled = Set(true);
+                active.consecutive_failures = Set(0);
+                active.disabled_at = Set(None);
+                active.disabled_reason = Set(None);
+            }
+            Some(false) if was_enabled => {
+                active.enabled = Set(false);
+                active.disabled_at = Set(Some(now));
+                active.disabled_reason = Set(Some("Disabled by an admin".to_string()));
+            }
+            _ => {}
+        }
+        active.updated_at = Set(now);
+        Ok(active.update(conn).await?)
+    }
+
+    pub async fn rotate_secret(conn: &DbConn, webhook: Model, secret: String) -> DbResult<Model> {
+        let mut active: ActiveModel = webhook.into();
+        active.secret = Set(secret);
+        active.updated_at = Set(chrono::Utc::now().fixed_offset());
+        Ok(active.update(conn).await?)
+    }
+
+    /// Delete the webhook and its delivery log.
+    pub async fn delete(conn: &DbConn, id: i32) -> DbResult<u64> {
+        let result = Entity::delete_by_id(id).exec(conn).await?;
+        Ok(result.rows_affected)
+    }
+
+    pub async fn list(conn: &DbConn) -> DbResult<Vec<Model>> {
+        Ok(Entity::find().order_by_asc(Column::Id).all(conn).await?)
+    }
+
+    pub async fn get_by_id(conn: &DbConn, id: i32) -> DbResult<Option<Model>> {
+        Ok(Entity::find_by_id(id).one(conn).await?)
+    }
+
+    /// A delivery went through: reset the failure count.
+    pub async fn record_success(conn: &DbConn, id: i32) -> DbResult<()> {
+        conn.execute(Statement::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            r#"
+            UPDATE webhooks
+            SET consecutive_failures = 0, last_delivery_at = now(), last_success_at = now()
+            WHERE id = $1
+            "#,
+            vec![id.into()],
+        ))
+        .await?;
+        Ok(())
+    }
+
+    /// A delivery failed on every attempt: count it and disable the webhook
+    /// once `disable_after` deliveries in a row have failed (0 never does).
+    pub async fn record_failure(
+        conn: &DbConn,
+        id: i32,
+        disable_after: i32,
+    ) -> DbResult<Option<Model>> {
+        conn.execute(Statement::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            r#"
+            UPDATE webhooks
+            SET consecutive_failures = consecutive_failures + 1,
+                last_delivery_at = now(),
+                enabled = enabled AND NOT ($2 > 0 AND consecutive_failures + 1 >= $2),
+                disabled_at = CASE
+                    WHEN enabled AND $2 > 0 AND consecutive_failures + 1 >= $2 THEN now()
+                    ELSE disabled_at END,
+                disabled_reason = CASE
+                    WHEN enabled AND $2 > 0 AND consecutive_failures + 1 >= $2
+                    THEN 'Disabled after ' || (consecutive_failures + 1) || ' failed deliveries in a row'
+                    ELSE disabled_reason END
+            WHERE id = $1
+            "#,
+            vec![id.into(), disable_after.into()],
+        ))
+        .await?;
+        Ok(Entity::find_by_id(id).one(conn).await?)
+    }
+
+    /// Queue a delivery of `event` to every enabled webhook subscribed to
+    /// it. Failures are logged rather than returned so the action that
+    /// fired the event isn't undone by its webhooks.
+    pub async fn emit<C: ConnectionTrait>(conn: &C, event: &str, data: Value) {
+        if let Err(err) = Self::try_emit(conn, event, data).await {
+            error!(event, error = ?err, "Failed to queue webhook deliveries");
+        }
+    }
+
+    async fn try_emit<C: ConnectionTrait>(conn: &C, event: &str, data: Value) -> DbResult<()> {
+        let webhooks: Vec<Model> = Entity::find()
+            .filter(Column::Enabled.eq(true))
+            .all(conn)
+            .await?
+            .into_iter()
+            .filter(|webhook| subscribes(&webhook.events, event))
+            .collect();
+        if webhooks.is_empty() {
+            return Ok(());
+        }
+
+        let (event_id, payload) = envelope(event, data);
+        for webhook in webhooks {
+            webhook_delivery::Entity::queue(
+                conn,
+                NewDelivery {
+                    webhook_id: webhook.id,
+                    event: event.to_string(),
+                    event_id: event_id.clone(),
+                    payload: payload.clone(),
+                    redelivery_of: None,
+                },
+            )
+            .await?;
+        }
+        Ok(())
+    }
+
+    /// Queue a delivery of `event` to this webhook only, subscribed or not.
+    pub async fn send_to(
+        conn: &DbConn,
+        webhook: &Model,
+        event: &str,
+        data: Value,
+    ) -> DbResult<webhook_delivery::Model> {
+        let (event_id, payload) = envelope(event, data);
+        webhook_delivery::Entity::queue(
+            conn,
+            NewDelivery {
+                webhook_id: webhook.id,
+                event: event.to_string(),
+                event_id,
+                payload,
+                redelivery_of: None,
+            },
+        )
+        .await
+    }
+}
+
+/// The body receivers get: a new event id, the event name and its data.
+fn envelope(event: &str, data: Value) -> (String, Value) {
+    let event_id = uuid::Uuid::new_v4().to_string();
+    let payload = json!({
+        "id": event_id,
+        "event": event,
+        "created_at": chrono::Utc::now().fixed_offset(),
+        "data": data,
+    });
+    (event_id, payload)
+}
@@ -0,0 +1,60 @@
+use schemars::JsonSchema;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize, JsonSchema)]
+#[sea_orm(table_name = "webhooks")]
+#[schemars(rename = "Webhook")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+
+    pub name: String,
+    pub url: String,
+    /// HMAC key for the signature header; only shown on create and rotate.
+    #[serde(skip_serializing)]
+    #[schemars(skip)]
+    pub secret: String,
+    /// Event names or `prefix.*` patterns; empty means every event.
+    pub events: Vec<String>,
+    pub enabled: bool,
+
+    /// Deliveries in a row that failed on every attempt.
+    pub consecutive_failures: i32,
+    pub disabled_at: Option<DateTimeWithTimeZone>,
+    pub disabled_reason: Option<String>,
+    pub last_delivery_at: Option<DateTimeWithTimeZone>,
+    pub last_success_at: Option<DateTimeWithTimeZone>,
+
+    pub created_by: Option<i32>,
+
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::super::user::Entity",
+        from = "Column::CreatedBy",
+        to = "super::super::user::Column::Id",
+        on_delete = "SetNull"
+    )]
+    User,
+    #[sea_orm(has_many = "super::super::webhook_delivery::Entity")]
+    Delivery,
+}
+
+impl Related<super::super::user::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+
+impl Related<super::super::webhook_delivery::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Delivery.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,76 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Event names sent to webhooks.
+pub mod events {
+    pub const POST_CREATED: &str = "post.created";
+    pub const POST_UPDATED: &str = "post.updated";
+    pub const POST_PUBLISHED: &str = "post.published";
+    pub const POST_DELETED: &str = "post.deleted";
+    pub const COMMENT_CREATED: &str = "comment.created";
+    pub const SUBSCRIBER_CREATED: &str = "subscriber.created";
+    pub const SUBSCRIBER_CONFIRMED: &str = "subscriber.confirmed";
+    pub const USER_CREATED: &str = "user.created";
+    pub const USER_DELETED: &str = "user.deleted";
+    /// Only sent by the admin test endpoint, whatever the subscription.
+    pub const PING: &str = "webhook.ping";
+}
+
+/// Events a webhook can subscribe to, with what fires them.
+pub const EVENTS: &[(&str, &str)] = &[
+    (events::POST_CREATED, "A post was created"),
+    (events::POST_UPDATED, "A post was edited"),
+    (events::POST_PUBLISHED, "A post became published"),
+    (events::POST_DELETED, "A post was deleted"),
+    (events::COMMENT_CREATED, "A comment was posted"),
+    (
+        events::SUBSCRIBER_CREATED,
+        "Someone subscribed to the newsletter",
+    ),
+    (
+        events::SUBSCRIBER_CONFIRMED,
+        "A newsletter subscription was confirmed",
+    ),
+    (events::USER_CREATED, "A user account was created"),
+    (events::USER_DELETED, "A user account was deleted"),
+];
+
+/// An event name from [`EVENTS`], or a `prefix.*` pattern matching one.
+pub fn is_valid_pattern(pattern: &str) -> bool {
+    EVENTS
+        .iter()
+        .any(|(name, _)| subscribes(&[pattern.to_string()], name))
+}
+
+/// Whether `patterns` (event names or `prefix.*`) cover `event`. No
+/// patterns means every event.
+pub fn subscribes(patterns: &[String], event: &str) -> bool {
+    patterns.is_empty()
+        || patterns
+            .iter()
+            .any(|pattern| match pattern.strip_suffix(".*") {
+                Some(prefix) => event
+                    .strip_prefix(prefix)
+                    .is_some_and(|rest| rest.starts_with('.')),
+                None => pattern == "*" || pattern == event,
+            })
+}
+
+#[derive(Clone, Debug)]
+pub struct NewWebhook {
+    pub name: String,
+    pub url: String,
+    pub secret: String,
+    pub events: Vec<String>,
+    pub enabled: bool,
+    pub created_by: Option<i32>,
+}
+
+/// Admin edits; unset fields are left alone.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema)]
+pub struct WebhookUpdate {
+    pub name: Option<String>,
+    pub url: Option<String>,
+    pub events: Option<Vec<String>>,
+    pub enabled: Option<bool>,
+}
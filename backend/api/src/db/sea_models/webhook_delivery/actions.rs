@@ -0,0 +1,127 @@
+use sea_orm::{
+    entity::prelude::*, sea_query::Expr, Condition, QueryOrder, QuerySelect, Select, Set,
+};
+use serde_json::json;
+
+use crate::{
+    db::sea_models::job::{self, NewJob},
+    error::DbResult,
+};
+
+use super::*;
+
+/// Actions for webhook deliveries:
+/// - Queue a delivery together with the job that sends it
+/// - Record the outcome of an attempt
+/// - Admin listing
+impl Entity {
+    pub const PER_PAGE: u64 = 50;
+
+    /// Insert a pending delivery and enqueue its job on the same connection,
+    /// so inside a transaction neither exists unless it commits.
+    pub async fn queue<C: ConnectionTrait>(conn: &C, delivery: NewDelivery) -> DbResult<Model> {
+        let now = chrono::Utc::now().fixed_offset();
+        let model = ActiveModel {
+            webhook_id: Set(delivery.webhook_id),
+            event: Set(delivery.event),
+            event_id: Set(delivery.event_id),
+            payload: Set(delivery.payload),
+            status: Set(DeliveryStatus::Pending),
+            attempts: Set(0),
+            redelivery_of: Set(delivery.redelivery_of),
+            created_at: Set(now),
+            updated_at: Set(now),
+            ..Default::default()
+        }
+        .insert(conn)
+        .await?;
+
+        let queued = job::Entity::enqueue(
+            conn,
+            NewJob {
+                queue: DELIVER_JOB_QUEUE.to_string(),
+                kind: DELIVER_JOB_KIND.to_string(),
+                payload: json!({ "delivery_id": model.id }),
+                max_attempts: DELIVER_MAX_ATTEMPTS,
+                unique_key: None,
+                run_at: None,
+                created_by: None,
+            },
+        )
+        .await?;
+
+        let mut active: ActiveModel = model.into();
+        active.job_id = Set(queued.map(|job| job.id));
+        Ok(active.update(conn).await?)
+    }
+
+    /// Store the result of an attempt; `delivered_at` is set on success.
+    pub async fn record_attempt(
+        conn: &DbConn,
+        id: i64,
+        attempt: DeliveryAttempt,
+        status: DeliveryStatus,
+    ) -> DbResult<()> {
+        let now = chrono::Utc::now().fixed_offset();
+        let mut update = Entity::update_many()
+            .col_expr(Column::Status, Expr::value(status))
+            .col_expr(Column::Attempts, Expr::col(Column::Attempts).add(1))
+            .col_expr(Column::ResponseStatus, Expr::value(attempt.response_status))
+            .col_expr(Column::ResponseBody, Expr::value(attempt.response_body))
+            .col_expr(Column::Error, Expr::value(attempt.error))
+            .col_expr(Column::DurationMs, Expr::value(attempt.duration_ms))
+            .col_expr(Column::UpdatedAt, Expr::value(now));
+        if status == DeliveryStatus::Succeeded {
+            update = update.col_expr(Column::DeliveredAt, Expr::value(now));
+        }
+        update.filter(Column::Id.eq(id)).exec(conn).await?;
+        Ok(())
+    }
+
+    pub async fn get_by_id(conn: &DbConn, id: i64) -> DbResult<Option<Model>> {
+        Ok(Entity::find_by_id(id).one(conn).await?)
+    }
+
+    pub async fn find_with_query(
+        conn: &DbConn,
+        query: WebhookDeliveryQuery,
+    ) -> DbResult<(Vec<Model>, u64)> {
+        let select = Self::filtered(&query);
+        let total = select.clone().count(conn).await?;
+
+        let page = match query.page {
+            Some(p) if p > 0 => p,
+            _ => 1,
+        };
+        let rows = select
+            .offset((page - 1) * Self::PER_PAGE)
+            .limit(Self::PER_PAGE)
+            .all(conn)
+            .await?;
+        Ok((rows, total))
+    }
+
+    fn filtered(query: &WebhookDeliveryQuery) -> Select<Entity> {
+        let mut condition = Condition::all();
+        if let Some(webhook_id) = query.webhook_id {
+            condition = condition.add(Column::WebhookId.eq(webhook_id));
+        }
+        if let Some(event) = &query.event {
+            condition = condition.add(Column::Event.eq(event));
+        }
+        if let Some(status) = query.status {
+            condition = condition.add(Column::Status.eq(status));
+        }
+        if let Some(after) = query.created_at_gt {
+            condition = condition.add(Column::CreatedAt.gt(after));
+        }
+        if let Some(before) = query.created_at_lt {
+            condition = condition.add(Column::CreatedAt.lt(before));
+        }
+
+        Entity::find()
+            .filter(condition)
+            .order_by_desc(Column::CreatedAt)
+            .order_by_desc(Column::Id)
+    }
+}
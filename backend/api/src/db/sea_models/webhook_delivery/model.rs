@@ -0,0 +1,96 @@
+use schemars::JsonSchema;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    EnumIter,
+    DeriveActiveEnum,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+)]
+#[sea_orm(
+    rs_type = "String",
+    db_type = "Enum",
+    enum_name = "webhook_delivery_status"
+)]
+#[serde(rename_all = "lowercase")]
+pub enum DeliveryStatus {
+    /// Queued or waiting for a retry.
+    #[sea_orm(string_value = "pending")]
+    Pending,
+    #[sea_orm(string_value = "succeeded")]
+    Succeeded,
+    /// Failed on every attempt.
+    #[sea_orm(string_value = "failed")]
+    Failed,
+}
+
+impl fmt::Display for DeliveryStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::Pending => "pending",
+            Self::Succeeded => "succeeded",
+            Self::Failed => "failed",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize, JsonSchema)]
+#[sea_orm(table_name = "webhook_deliveries")]
+#[schemars(rename = "WebhookDelivery")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i64,
+
+    pub webhook_id: i32,
+    /// Event name, e.g. `post.published`.
+    pub event: String,
+    /// Shared by every delivery of one event, so receivers can dedupe.
+    pub event_id: String,
+    /// Body sent to the endpoint.
+    #[sea_orm(column_type = "JsonBinary")]
+    pub payload: Json,
+    pub status: DeliveryStatus,
+    pub attempts: i32,
+
+    /// Result of the last attempt.
+    pub response_status: Option<i16>,
+    pub response_body: Option<String>,
+    pub error: Option<String>,
+    pub duration_ms: Option<i32>,
+
+    pub job_id: Option<i64>,
+    /// Delivery this one was resent from.
+    pub redelivery_of: Option<i64>,
+
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+    pub delivered_at: Option<DateTimeWithTimeZone>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::super::webhook::Entity",
+        from = "Column::WebhookId",
+        to = "super::super::webhook::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Webhook,
+}
+
+impl Related<super::super::webhook::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Webhook.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,44 @@
+use schemars::JsonSchema;
+use sea_orm::prelude::{DateTimeWithTimeZone, Json};
+use serde::{Deserialize, Serialize};
+
+use super::model::DeliveryStatus;
+
+/// Job kind and queue that send a delivery; the job's payload is
+/// `{ "delivery_id": .. }`.
+pub const DELIVER_JOB_KIND: &str = "webhook.deliver";
+pub const DELIVER_JOB_QUEUE: &str = "webhooks";
+/// With the job backoff the last attempt is about 20 minutes after the first.
+pub const DELIVER_MAX_ATTEMPTS: i32 = 8;
+
+/// Responses are cut to this many bytes before they're stored.
+pub const RESPONSE_BODY_LIMIT: usize = 2048;
+
+#[derive(Clone, Debug)]
+pub struct NewDelivery {
+    pub webhook_id: i32,
+    pub event: String,
+    pub event_id: String,
+    pub payload: Json,
+    pub redelivery_of: Option<i64>,
+}
+
+/// Outcome of one attempt.
+#[derive(Clone, Debug, Default)]
+pub struct DeliveryAttempt {
+    pub response_status: Option<i16>,
+    pub response_body: Option<String>,
+    pub error: Option<String>,
+    pub duration_ms: Option<i32>,
+}
+
+/// Filters for listing deliveries
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema)]
+pub struct WebhookDeliveryQuery {
+    pub page: Option<u64>,
+    pub webhook_id: Option<i32>,
+    pub event: Option<String>,
+    pub status: Option<DeliveryStatus>,
+    pub created_at_gt: Option<DateTimeWithTimeZone>,
+    pub created_at_lt: Option<DateTimeWithTimeZone>,
+}
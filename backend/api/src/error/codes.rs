@@ -3,6 +3,7 @@
 //! This module defines standard error codes that can be used throughout the application.
 //! Each error code has a unique string identifier that can be used for translation on the client.
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
@@ -15,7 +16,7 @@ use std::fmt;
 ///
 /// When sent to clients, these are serialized to strings like "AUTH_001" which can be
 /// used for translation lookup on the client side.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum ErrorCode {
     #[serde(rename = "AUTH_001")]
@@ -89,7 +89,17 @@ impl IntoResponse for CorsError {
 #[derive(Debug, thiserror::Error)]
 pub enum RouteBlockerError {
     #[error("Route blocked by admin policy: {path}")]
-    Blocked { path: String },
+    Blocked {
+        path: String,
+        status: u16,
+        message: Option<String>,
+        retry_after_secs: Option<u64>,
+    },
+    #[error("Site is in maintenance mode")]
+    Maintenance {
+        message: Option<String>,
+        retry_after_secs: Option<u64>,
+    },
     #[error("Failed to check route blocker status: {0}")]
     CheckFailed(String),
 }
@@ -97,9 +107,40 @@ pub enum RouteBlockerError {
 impl IntoErrorResponse for RouteBlockerError {
     fn into_error_response(self) -> ErrorResponse {
         match self {
-            Self::Blocked { path } => ErrorResponse::new(ErrorCode::OperationNotAllowed)
-                .with_message("This route is currently unavailable")
-                .with_context(json!({ "path": path })),
+            Self::Blocked {
+                path,
+                status,
+                message,
+                retry_after_secs,
+            } => {
+                let code = match status {
+                    404 => ErrorCode::RecordNotFound,
+                    429 => ErrorCode::RateLimited,
+                    503 => ErrorCode::ServiceUnavailable,
+                    _ => ErrorCode::OperationNotAllowed,
+                };
+                let response = ErrorResponse::new(code)
+                    .with_message(
+                        message.unwrap_or_else(|| "This route is currently unavailable".into()),
+                    )
+                    .with_context(json!({ "path": path }));
+                match retry_after_secs {
+                    Some(secs) => response.with_retry_after(secs),
+                    None => response,
+                }
+            }
+            Self::Maintenance {
+                message,
+                retry_after_secs,
+            } => {
+                let response = ErrorResponse::new(ErrorCode::ServiceUnavailable)
+                    .with_message(message.unwrap_or_else(|| "Down for maintenance".into()))
+                    .with_context(json!({ "maintenance": true }));
+                match retry_after_secs {
+                    Some(secs) => response.with_retry_after(secs),
+                    None => response,
+                }
+            }
             Self::CheckFailed(error) => ErrorResponse::new(ErrorCode::ServiceUnavailable)
                 .with_message("Failed to verify route availability")
                 .with_details(error),
@@ -4,6 +4,7 @@
 
 use super::codes::ErrorCode;
 use axum::{response::IntoResponse, Json};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
@@ -14,7 +15,7 @@ use std::fmt;
 /// - A human-readable message (which may be localized on the server if Accept-Language is used)
 /// - Optional detailed information for developers (only in development mode)
 /// - Optional additional fields for specific error types
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ErrorResponse {
     /// The error type - this will serialize to strings like "AUTH_001"
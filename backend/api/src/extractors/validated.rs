@@ -1,6 +1,6 @@
 use axum::extract::{
-    rejection::JsonRejection, rejection::QueryRejection, FromRequest, FromRequestParts, Query,
-    Request,
+    rejection::JsonRejection, rejection::QueryRejection, FromRequest, FromRequestParts,
+    OptionalFromRequest, Query, Request,
 };
 use axum::http::request::Parts;
 use axum::Json;
@@ -24,7 +24,7 @@ where
     type Rejection = ErrorResponse;
 
     async fn from_request(req: Request, state: &S) -> Result<Self, ErrorResponse> {
-        match Json::<T>::from_request(req, state).await {
+        match <Json<T> as FromRequest<S>>::from_request(req, state).await {
             Ok(json) => {
                 let data = json.0;
                 match data.validate() {
@@ -44,6 +44,38 @@ where
     }
 }
 
+/// `Option<ValidatedJson<T>>` is `None` when the request has no JSON body
+/// (no `application/json` content type); a JSON body must still be valid.
+impl<T, S> OptionalFromRequest<S> for ValidatedJson<T>
+where
+    T: DeserializeOwned + Validate + Send + Sync,
+    S: Send + Sync + 'static,
+    Json<T>: OptionalFromRequest<S, Rejection = JsonRejection>,
+{
+    type Rejection = ErrorResponse;
+
+    async fn from_request(req: Request, state: &S) -> Result<Option<Self>, ErrorResponse> {
+        match <Json<T> as OptionalFromRequest<S>>::from_request(req, state).await {
+            Ok(Some(json)) => {
+                let data = json.0;
+                match data.validate() {
+                    Ok(_) => Ok(Some(ValidatedJson(data))),
+                    Err(errors) => {
+                        use crate::error::{ErrorCode, ErrorResponse};
+                        warn!("JSON validation failed: {:?}", errors);
+                        let errors_json = serde_json::to_value(&errors).unwrap_or_default();
+                        Err(ErrorResponse::new(ErrorCode::InvalidInput)
+                            .with_message("Validation failed")
+                            .with_context(errors_json))
+                    }
+                }
+            }
+            Ok(None) => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+}
+
 impl<T> Deref for ValidatedJson<T> {
     type Target = T;
 
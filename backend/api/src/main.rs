@@ -7,14 +7,14 @@ use tower_http::{
     cors::{AllowOrigin, CorsLayer},
 };
 use tower_sessions::{cookie::Key, Expiry, SessionManagerLayer};
-use tower_sessions_redis_store::RedisStore;
+use tower_sessions_redis_store::{fred::prelude::ClientLike, RedisStore};
 
 use modules::csrf_v1;
 use ruxlog::utils::cors::get_allowed_origins;
 use ruxlog::{
     db, middlewares, modules, router,
     services::{
-        self, acl_service::AclService, redis::init_redis_store, route_blocker_config,
+        self, acl_service::AclService, jobs, redis::init_redis_store,
         route_blocker_service::RouteBlockerService,
     },
     state::{AppState, ObjectStorageConfig, OptimizerConfig},
@@ -32,6 +32,37 @@ fn hex_to_512bit_key(hex: &str) -> [u8; 64] {
     array
 }
 
+/// Resolves on Ctrl+C or SIGTERM.
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(err) = tokio::signal::ctrl_c().await {
+            tracing::error!(error = %err, "Failed to listen for Ctrl+C");
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(err) => {
+                tracing::error!(error = %err, "Failed to listen for SIGTERM");
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+    tracing::info!("Shutdown signal received");
+}
+
 fn env_bool(key: &str, default: bool) -> bool {
     env::var(key)
         .ok()
@@ -173,16 +204,14 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         meter: telemetry::global_meter(),
     };
 
-    // Bootstrap application constants from environment (only fills missing keys) and warm Redis.
+    // Import registered runtime settings from the environment (only fills missing keys), seal
+    // sensitive values and warm Redis.
     if let Err(err) = AclService::bootstrap_from_env(State(state.clone())).await {
         tracing::error!(error = %err, "Failed to bootstrap ACL constants from env");
     } else {
         tracing::info!("ACL constants bootstrapped from env");
     }
 
-    let sync_interval_secs = env_u64("ROUTE_BLOCKER_SYNC_INTERVAL_SECS", 60 * 30);
-    route_blocker_config::set_sync_interval_secs(sync_interval_secs);
-
     if let Err(err) = RouteBlockerService::initialize_redis_sync(&state).await {
         tracing::error!(
             error = %err,
@@ -192,62 +221,14 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         tracing::info!("Initial route blocker Redis sync completed successfully");
     }
 
-    let state_for_blocker = state.clone();
-    tokio::spawn(async move {
-        let notify = route_blocker_config::notifier();
-
-        // Set initial next sync time
-        route_blocker_config::set_next_sync_at(route_blocker_config::calculate_next_sync());
-
-        loop {
-            if route_blocker_config::is_paused() {
-                tokio::select! {
-                    _ = notify.notified() => {},
-                    _ = tokio::time::sleep(Duration::from_secs(5)) => {},
-                }
-                continue;
-            }
-
-            let force_sync = route_blocker_config::take_force_sync_flag();
-
-            if !force_sync {
-                let interval_secs = route_blocker_config::get_sync_interval_secs();
-                let next_sync = route_blocker_config::calculate_next_sync();
-                route_blocker_config::set_next_sync_at(next_sync);
-
-                let sleep = tokio::time::sleep(Duration::from_secs(interval_secs));
-                tokio::pin!(sleep);
-
-                tokio::select! {
-                    _ = &mut sleep => {},
-                    _ = notify.notified() => {
-                        // config change: restart loop to re-read state.
-                        continue;
-                    }
-                }
-            }
-
-            if route_blocker_config::is_paused() {
-                continue;
-            }
-
-            route_blocker_config::set_sync_running(true);
-            let sync_start = chrono::Utc::now();
-
-            if let Err(err) = RouteBlockerService::initialize_redis_sync(&state_for_blocker).await {
-                tracing::error!(
-                    error = %err,
-                    "Periodic route blocker Redis sync failed"
-                );
-            } else {
-                tracing::info!("Periodic route blocker Redis sync completed successfully");
-            }
-
-            route_blocker_config::set_last_sync_at(sync_start);
-            route_blocker_config::set_sync_running(false);
-            route_blocker_config::set_next_sync_at(route_blocker_config::calculate_next_sync());
+    let job_runner = jobs::start(state.clone());
+    let admin_events = match services::admin_events::start().await {
+        Ok(subscriber) => Some(subscriber),
+        Err(err) => {
+            tracing::error!(error = %err, "Admin event stream unavailable");
+            None
         }
-    });
+    };
 
     tracing::info!("Redis successfully established.");
     let session_store = RedisStore::new(redis_pool);
@@ -293,10 +274,18 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Clone the database connection for the Extension layer (used by auth middleware)
     let db_extension = Extension(state.sea_db.clone());
+    // Permission guards cache role permissions in Redis
+    let redis_for_shutdown = state.redis_pool.clone();
+    let redis_extension = Extension(state.redis_pool.clone());
 
     let app = router::router()
+        // Innermost so it runs after routing and sees the session.
+        .layer(middlewares::route_blocker::RouteBlockerLayer::new(
+            state.clone(),
+        ))
         .layer(ip_source.into_extension())
         .layer(db_extension)
+        .layer(redis_extension)
         .layer(session_layer)
         //     config: governor_conf,
         // })
@@ -314,9 +303,6 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             routing::post(csrf_v1::controller::generate),
         )
         .layer(cors)
-        .layer(middlewares::route_blocker::RouteBlockerLayer::new(
-            state.clone(),
-        ))
         .with_state(state);
 
     let host = env::var("HOST").unwrap_or_else(|_| "0.0.0.0".to_string());
@@ -329,8 +315,18 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         listener,
         app.into_make_service_with_connect_info::<SocketAddr>(),
     )
+    .with_graceful_shutdown(shutdown_signal())
     .await?;
 
+    job_runner.shutdown().await;
+    if let Some(subscriber) = admin_events {
+        if let Err(err) = subscriber.quit().await {
+            tracing::warn!(error = %err, "Failed to close the Redis subscriber");
+        }
+    }
+    if let Err(err) = redis_for_shutdown.quit().await {
+        tracing::warn!(error = %err, "Failed to close Redis connections");
+    }
     redis_connection.await??;
 
     Ok(())
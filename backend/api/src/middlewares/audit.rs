@@ -0,0 +1,100 @@
+//! Audit log middleware for admin routers.
+//!
+//! Add it as a `route_layer` after the router's auth guard so it wraps the
+//! guard, e.g. `.route_layer(middleware::from_fn(audit::audit))`. Successful
+//! write requests, and any request whose handler returned an
+//! [`AuditEvent`](crate::services::audit::AuditEvent), get an `audit_logs` row.
+
+use axum::{
+    extract::{FromRequestParts, MatchedPath, Request},
+    http::Method,
+    middleware::Next,
+    response::Response,
+    Extension,
+};
+use axum_client_ip::ClientIp;
+use rux_auth::AuthSession;
+use sea_orm::DatabaseConnection;
+use tower_sessions::Session;
+use tower_sessions_redis_store::fred::prelude::Pool as RedisPool;
+use tracing::error;
+
+use crate::{
+    db::sea_models::audit_log::{self, NewAuditLog},
+    middlewares::RequestId,
+    services::{
+        audit::{self, AuditEvent},
+        auth::AuthBackend,
+    },
+};
+
+pub async fn audit(
+    Extension(db): Extension<DatabaseConnection>,
+    redis: Option<Extension<RedisPool>>,
+    session: Session,
+    request: Request,
+    next: Next,
+) -> Response {
+    let (mut parts, body) = request.into_parts();
+    let ip = ClientIp::from_request_parts(&mut parts, &())
+        .await
+        .ok()
+        .map(|ClientIp(ip)| ip.to_string());
+    let request = Request::from_parts(parts, body);
+
+    let method = request.method().clone();
+    let path = request.uri().path().to_string();
+    let matched = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|matched| matched.as_str().to_string());
+    let request_id = request
+        .extensions()
+        .get::<RequestId>()
+        .map(|RequestId(id)| id.clone());
+
+    // Load the actor up front: the action may delete their account.
+    let mut backend = AuthBackend::new(&db);
+    if let Some(Extension(redis)) = &redis {
+        backend = backend.with_redis(redis);
+    }
+    let actor = AuthSession::new(backend, session).await.user;
+
+    let response = next.run(request).await;
+
+    let event = response.extensions().get::<AuditEvent>().cloned();
+    let is_write = !matches!(method, Method::GET | Method::HEAD | Method::OPTIONS);
+    if !response.status().is_success() || (event.is_none() && !is_write) {
+        return response;
+    }
+
+    let event = event.unwrap_or_else(|| {
+        AuditEvent::new(format!(
+            "{} {}",
+            method,
+            matched.as_deref().unwrap_or(&path)
+        ))
+    });
+    let before = event.before.map(audit::redact);
+    let after = event.after.map(audit::redact);
+    let entry = NewAuditLog {
+        actor_id: actor.as_ref().map(|user| user.id),
+        actor_role: actor.as_ref().map(|user| user.role),
+        action: event.action,
+        entity_type: event.entity_type,
+        entity_id: event.entity_id,
+        diff: audit::diff(before.as_ref(), after.as_ref()),
+        before,
+        after,
+        method: method.to_string(),
+        path,
+        status: response.status().as_u16() as i16,
+        ip,
+        request_id,
+    };
+
+    if let Err(err) = audit_log::Entity::record(&db, entry).await {
+        error!(error = %err, "Failed to write audit log entry");
+    }
+    response
+}
@@ -2,10 +2,13 @@
 //!
 //! Uses composable requirements - single middleware per route with chained checks.
 
+use std::{future::Future, pin::Pin};
+
 use axum::{extract::Request, middleware::Next, response::Response, Extension};
 use rux_auth::{auth_requirements, check_requirements, AuthError, AuthSession};
 use sea_orm::DatabaseConnection;
 use tower_sessions::Session;
+use tower_sessions_redis_store::fred::prelude::Pool as RedisPool;
 
 use crate::services::auth::AuthBackend;
 
@@ -58,11 +61,7 @@ pub async fn unverified(
     next: Next,
 ) -> Result<Response, AuthError> {
     let mut auth = make_auth_session(&db, session).await;
-    check_requirements(
-        &mut auth,
-        &auth_requirements().authenticated().unverified(),
-    )
-    .await?;
+    check_requirements(&mut auth, &auth_requirements().authenticated().unverified()).await?;
     Ok(next.run(request).await)
 }
 
@@ -78,11 +77,7 @@ pub async fn verified(
     next: Next,
 ) -> Result<Response, AuthError> {
     let mut auth = make_auth_session(&db, session).await;
-    check_requirements(
-        &mut auth,
-        &auth_requirements().authenticated().verified(),
-    )
-    .await?;
+    check_requirements(&mut auth, &auth_requirements().authenticated().verified()).await?;
     Ok(next.run(request).await)
 }
 
@@ -104,3 +99,43 @@ pub async fn verified_with_role<const LEVEL: i32>(
     .await?;
     Ok(next.run(request).await)
 }
+
+/// Guard future returned by [`verified_with_permission`]
+pub type GuardFuture = Pin<Box<dyn Future<Output = Result<Response, AuthError>> + Send>>;
+
+/// Require authenticated + verified + a named permission (single middleware)
+///
+/// Permissions are names like `post.publish`, see `services::permission_service`:
+/// `middleware::from_fn(auth_guard::verified_with_permission(permission_service::USER_MANAGE))`
+pub fn verified_with_permission(
+    permission: &'static str,
+) -> impl Fn(
+    Extension<DatabaseConnection>,
+    Option<Extension<RedisPool>>,
+    Session,
+    Request,
+    Next,
+) -> GuardFuture
+       + Clone
+       + Send
+       + Sync
+       + 'static {
+    move |Extension(db), redis, session, request, next| {
+        Box::pin(async move {
+            let mut backend = AuthBackend::new(&db);
+            if let Some(Extension(redis)) = redis {
+                backend = backend.with_redis(&redis);
+            }
+            let mut auth = AuthSession::new(backend, session).await;
+            check_requirements(
+                &mut auth,
+                &auth_requirements()
+                    .authenticated()
+                    .verified()
+                    .permission(permission),
+            )
+            .await?;
+            Ok(next.run(request).await)
+        })
+    }
+}
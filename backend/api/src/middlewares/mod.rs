@@ -1,3 +1,4 @@
+pub mod audit;
 pub mod auth_guard;
 pub mod cors;
 pub mod http_metrics;
@@ -1,25 +1,42 @@
 use crate::error::RouteBlockerError;
+use crate::services::auth::AuthBackend;
+use crate::services::route_blocker_config;
+use crate::services::route_blocker_rules::RouteRequest;
 use crate::services::route_blocker_service::RouteBlockerService;
+use crate::services::settings;
 use crate::state::AppState;
 use axum::{
-    extract::{MatchedPath, Request, State},
+    extract::{MatchedPath, Request},
     response::{IntoResponse, Response},
 };
-use std::env;
+use chrono::Utc;
+use rux_auth::AuthSession;
 use std::future::Future;
 use std::pin::Pin;
 use std::task::{Context, Poll};
 use tower::{Layer, Service};
-use tracing::{debug, error, info, warn};
+use tower_sessions::Session;
+use tracing::{debug, error, warn};
 
+/// Blocks requests matching the route blocker rules or maintenance mode.
+///
+/// Rules come from the in-process snapshot (`route_blocker_config::snapshot`),
+/// so allowed requests never touch Redis; once every
+/// `ROUTE_BLOCKER_REFRESH_SECS` one request checks for rules published by
+/// other instances. Must sit inside the session layer: role exemptions read
+/// the user from the session.
 #[derive(Clone)]
 pub struct RouteBlockerLayer {
     state: AppState,
+    refresh_secs: i64,
 }
 
 impl RouteBlockerLayer {
     pub fn new(state: AppState) -> Self {
-        Self { state }
+        Self {
+            state,
+            refresh_secs: settings::ROUTE_BLOCKER_REFRESH_SECS.env_int().max(1),
+        }
     }
 }
 
@@ -30,6 +47,7 @@ impl<S> Layer<S> for RouteBlockerLayer {
         RouteBlockerMiddleware {
             inner,
             state: self.state.clone(),
+            refresh_secs: self.refresh_secs,
         }
     }
 }
@@ -38,6 +56,7 @@ impl<S> Layer<S> for RouteBlockerLayer {
 pub struct RouteBlockerMiddleware<S> {
     inner: S,
     state: AppState,
+    refresh_secs: i64,
 }
 
 impl<S> Service<Request> for RouteBlockerMiddleware<S>
@@ -56,6 +75,7 @@ where
     fn call(&mut self, req: Request) -> Self::Future {
         let state = self.state.clone();
         let mut inner = self.inner.clone();
+        let refresh_secs = self.refresh_secs;
 
         Box::pin(async move {
             let path = req.uri().path().to_string();
@@ -63,47 +83,62 @@ where
                 .extensions()
                 .get::<MatchedPath>()
                 .map(|matched| matched.as_str().to_string());
-            let pattern = matched_pattern.clone().unwrap_or_else(|| path.clone());
-
-            let is_development = env::var("APP_ENV")
-                .unwrap_or_else(|_| "development".to_string())
-                == "development";
-
-            if is_development {
-                debug!(path, "Route blocker disabled in development mode");
-                return inner.call(req).await;
-            }
-
-            info!("ROUTER BLOCKER WORKING");
 
-            if let Some(_) = matched_pattern {
-                if let Err(err) = RouteBlockerService::record_route_pattern(&state, &pattern).await
-                {
+            if let Some(pattern) = &matched_pattern {
+                if let Err(err) = RouteBlockerService::record_route_pattern(&state, pattern).await {
                     error!(
                         pattern = %pattern,
                         error = %err,
-                        "Failed to record route pattern in cache"
+                        "Failed to record route pattern"
                     );
                 }
             }
 
-            match RouteBlockerService::is_route_blocked(State(state.clone()), &pattern).await {
-                Ok(true) => {
-                    warn!(path = %path, pattern = %pattern, "Route blocked by dynamic route_blocker middleware");
-                    let error_response: Response = RouteBlockerError::Blocked { path }.into_response();
-                    return Ok(error_response);
+            if route_blocker_config::take_refresh_slot(Utc::now().timestamp(), refresh_secs) {
+                if let Err(err) = RouteBlockerService::refresh(&state).await {
+                    warn!(error = %err, "Route blocker rule refresh failed");
                 }
-                Ok(false) => {
-                    debug!(path = %path, pattern = %pattern, "Route allowed");
-                }
-                Err(e) => {
-                    error!(error = %e, path = %path, pattern = %pattern, "Failed to check route status");
-                    let error_response: Response = RouteBlockerError::CheckFailed(e.to_string()).into_response();
-                    return Ok(error_response);
+            }
+
+            let snapshot = route_blocker_config::snapshot();
+            let block = snapshot.check(&RouteRequest {
+                method: req.method().as_str(),
+                path: &path,
+                matched: matched_pattern.as_deref(),
+                now: Utc::now(),
+            });
+            let Some(block) = block else {
+                return inner.call(req).await;
+            };
+
+            // Only blocked requests pay for loading the user.
+            if let Some(session) = req.extensions().get::<Session>().cloned() {
+                let auth = AuthSession::new(AuthBackend::new(&state.sea_db), session).await;
+                if auth.user.is_some_and(|user| block.exempts(user.role)) {
+                    debug!(path = %path, "Route block bypassed by exempt role");
+                    return inner.call(req).await;
                 }
             }
 
-            inner.call(req).await
+            warn!(
+                path = %path,
+                pattern = ?block.pattern,
+                status = block.status,
+                "Route blocked by route_blocker middleware"
+            );
+            let error = match block.pattern {
+                None => RouteBlockerError::Maintenance {
+                    message: block.message,
+                    retry_after_secs: block.retry_after_secs,
+                },
+                Some(_) => RouteBlockerError::Blocked {
+                    path,
+                    status: block.status,
+                    message: block.message,
+                    retry_after_secs: block.retry_after_secs,
+                },
+            };
+            Ok(error.into_response())
         })
     }
 }
@@ -1,12 +1,9 @@
-use std::env;
-
-use crate::error::CsrfError;
+use crate::{error::CsrfError, services::settings};
 use axum::{extract::Request, middleware::Next, response::Response};
 use tracing::{debug, instrument, warn};
 
 pub fn get_static_csrf_key() -> String {
-    let key = env::var("CSRF_KEY").unwrap_or_else(|_| "ultra-instinct-goku".to_string());
-    return key;
+    settings::CSRF_KEY.env_string()
 }
 
 #[instrument(skip(req, next), fields(token_present, decode_status, result, path))]
@@ -22,6 +19,13 @@ pub async fn csrf_guard(req: Request, next: Next) -> Result<Response, CsrfError>
         return Ok(next.run(req).await);
     }
 
+    // The API docs are read-only and opened straight from a browser
+    if path == "/docs" || path.starts_with("/docs/") {
+        debug!("Skipping CSRF check for API docs: {}", path);
+        tracing::Span::current().record("result", "docs_exempted");
+        return Ok(next.run(req).await);
+    }
+
     if let Some(token) = req.headers().get("csrf-token") {
         tracing::Span::current().record("token_present", true);
         debug!("CSRF token present in request");
@@ -9,12 +9,17 @@ use serde_json::json;
 use tracing::{error, info};
 
 use crate::{
-    error::ErrorResponse, extractors::ValidatedJson, services::acl_service::AclService,
-    services::acl_service::ConstantsListParams, services::acl_service::UpsertConstantPayload,
-    services::auth::AuthSession, AppState,
+    error::ErrorResponse,
+    extractors::{ValidatedJson, ValidatedQuery},
+    services::acl_service::AclService,
+    services::acl_service::ConstantsListParams,
+    services::acl_service::UpsertConstantPayload,
+    services::audit::AuditEvent,
+    services::auth::AuthSession,
+    AppState,
 };
 
-use super::validator::{ConstantsListQuery, UpsertConstantRequest};
+use super::validator::{ConstantHistoryQuery, ConstantsListQuery, UpsertConstantRequest};
 
 #[debug_handler]
 pub async fn import_env_constants(
@@ -72,22 +77,7 @@ pub async fn list_constants(
     match result {
         Ok((items, total)) => {
             info!(count = items.len(), "Listed ACL constants");
-            let sanitized: Vec<serde_json::Value> = items
-                .into_iter()
-                .map(|item| {
-                    json!({
-                        "key": item.key,
-                        "value": if item.is_sensitive { serde_json::Value::String("********".into()) } else { serde_json::Value::String(item.value) },
-                        "value_type": item.value_type,
-                        "description": item.description,
-                        "is_sensitive": item.is_sensitive,
-                        "source": item.source,
-                        "updated_at": item.updated_at,
-                        "created_at": item.created_at,
-                        "updated_by": item.updated_by,
-                    })
-                })
-                .collect();
+            let sanitized: Vec<serde_json::Value> = items.into_iter().map(sanitize).collect();
 
             Ok(Json(json!({
                 "data": sanitized,
@@ -108,21 +98,22 @@ pub async fn get_constant(
 ) -> Result<impl IntoResponse, ErrorResponse> {
     let result = AclService::get_constant(State(state), &key).await;
     match result {
-        Ok(item) => Ok(Json(json!({
-            "key": item.key,
-            "value": if item.is_sensitive { "********".to_string() } else { item.value },
-            "value_type": item.value_type,
-            "description": item.description,
-            "is_sensitive": item.is_sensitive,
-            "source": item.source,
-            "updated_at": item.updated_at,
-            "created_at": item.created_at,
-            "updated_by": item.updated_by,
-        }))),
+        Ok(item) => Ok(Json(sanitize(item))),
         Err(err) => Err(err),
     }
 }
 
+/// Audit event for `key` with its current value, masked like responses.
+async fn audit_constant(state: &AppState, action: &str, key: &str) -> AuditEvent {
+    let before = AclService::get_constant(State(state.clone()), key)
+        .await
+        .ok()
+        .map(sanitize);
+    AuditEvent::new(action)
+        .target("app_constant", key)
+        .before(&before)
+}
+
 #[debug_handler]
 pub async fn create_constant(
     State(state): State<AppState>,
@@ -139,10 +130,18 @@ pub async fn create_constant(
         source: Some("manual".to_string()),
     };
 
+    let audit = audit_constant(&state, "acl.create", body.key.trim()).await;
     let result = AclService::upsert_constant(State(state), body, auth.user.map(|u| u.id)).await;
 
     match result {
-        Ok(item) => Ok((StatusCode::CREATED, Json(json!({ "data": sanitize(item) })))),
+        Ok(item) => {
+            let item = sanitize(item);
+            Ok((
+                StatusCode::CREATED,
+                audit.after(&item),
+                Json(json!({ "data": item })),
+            ))
+        }
         Err(err) => Err(err),
     }
 }
@@ -164,10 +163,14 @@ pub async fn update_constant(
         source: Some("manual".to_string()),
     };
 
+    let audit = audit_constant(&state, "acl.update", key.trim()).await;
     let result = AclService::upsert_constant(State(state), body, auth.user.map(|u| u.id)).await;
 
     match result {
-        Ok(item) => Ok(Json(json!({ "data": sanitize(item) }))),
+        Ok(item) => {
+            let item = sanitize(item);
+            Ok((audit.after(&item), Json(json!({ "data": item }))))
+        }
         Err(err) => Err(err),
     }
 }
@@ -175,20 +178,51 @@ pub async fn update_constant(
 #[debug_handler]
 pub async fn delete_constant(
     State(state): State<AppState>,
-    _auth: AuthSession,
+    auth: AuthSession,
     Path(key): Path<String>,
 ) -> Result<impl IntoResponse, ErrorResponse> {
-    let result = AclService::delete_constant(State(state), key.clone()).await;
+    let audit = audit_constant(&state, "acl.delete", &key).await;
+    let result =
+        AclService::delete_constant(State(state), key.clone(), auth.user.map(|u| u.id)).await;
     match result {
-        Ok(_) => Ok(Json(json!({ "message": "Deleted", "key": key }))),
+        Ok(_) => Ok((audit, Json(json!({ "message": "Deleted", "key": key })))),
         Err(err) => Err(err),
     }
 }
 
+#[debug_handler]
+pub async fn registry(
+    State(state): State<AppState>,
+    _auth: AuthSession,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let items = AclService::registry(State(state)).await?;
+    Ok(Json(json!({ "data": items })))
+}
+
+#[debug_handler]
+pub async fn history(
+    State(state): State<AppState>,
+    _auth: AuthSession,
+    query: ValidatedQuery<ConstantHistoryQuery>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let query = query.0;
+    let page = query.page.unwrap_or(1);
+    let per_page = query.per_page.unwrap_or(20);
+    let (items, total) = AclService::history(State(state), query.key, page, per_page).await?;
+    Ok(Json(json!({
+        "data": items,
+        "total": total,
+        "page": page,
+        "per_page": per_page,
+    })))
+}
+
+/// Response shape for a constant; sensitive values are masked.
 fn sanitize(item: crate::db::sea_models::app_constant::Model) -> serde_json::Value {
+    let item = item.redacted();
     json!({
         "key": item.key,
-        "value": if item.is_sensitive { "********".to_string() } else { item.value },
+        "value": item.value,
         "value_type": item.value_type,
         "description": item.description,
         "is_sensitive": item.is_sensitive,
@@ -7,7 +7,11 @@ use axum::{
     Router,
 };
 
-use crate::{middlewares::auth_guard, AppState};
+use crate::{
+    middlewares::{audit, auth_guard},
+    services::permission_service,
+    AppState,
+};
 
 pub fn routes() -> Router<AppState> {
     Router::<AppState>::new()
@@ -18,5 +22,10 @@ pub fn routes() -> Router<AppState> {
         .route("/delete/{key}", delete(controller::delete_constant))
         .route("/sync", post(controller::sync_constants))
         .route("/import_env", post(controller::import_env_constants))
-        .route_layer(middleware::from_fn(auth_guard::verified_with_role::<{ auth_guard::ROLE_SUPER_ADMIN }>))
+        .route("/registry", get(controller::registry))
+        .route("/history", get(controller::history))
+        .route_layer(middleware::from_fn(auth_guard::verified_with_permission(
+            permission_service::ACL_MANAGE,
+        )))
+        .route_layer(middleware::from_fn(audit::audit))
 }
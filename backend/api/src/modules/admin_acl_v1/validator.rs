@@ -1,7 +1,8 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use validator::Validate;
 
-#[derive(Debug, Deserialize, Serialize, Validate)]
+#[derive(Debug, Deserialize, Serialize, Validate, JsonSchema)]
 pub struct UpsertConstantRequest {
     #[validate(length(min = 1, max = 191))]
     pub key: String,
@@ -14,7 +15,7 @@ pub struct UpsertConstantRequest {
     pub is_sensitive: Option<bool>,
 }
 
-#[derive(Debug, Deserialize, Serialize, Validate)]
+#[derive(Debug, Deserialize, Serialize, Validate, JsonSchema)]
 pub struct ConstantsListQuery {
     pub page: Option<u64>,
     pub per_page: Option<u64>,
@@ -22,3 +23,13 @@ pub struct ConstantsListQuery {
     pub is_sensitive: Option<bool>,
     pub value_type: Option<String>,
 }
+
+#[derive(Debug, Deserialize, Serialize, Validate, JsonSchema)]
+pub struct ConstantHistoryQuery {
+    #[validate(length(min = 1, max = 191))]
+    pub key: Option<String>,
+    #[validate(range(min = 1))]
+    pub page: Option<u64>,
+    #[validate(range(min = 1, max = 100))]
+    pub per_page: Option<u64>,
+}
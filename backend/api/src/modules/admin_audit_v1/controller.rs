@@ -0,0 +1,113 @@
+use axum::{
+    extract::{Path, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use axum_macros::debug_handler;
+use serde_json::json;
+use tracing::{error, info, instrument};
+
+use crate::{
+    db::sea_models::audit_log::Entity as AuditLog,
+    error::{ErrorCode, ErrorResponse},
+    extractors::ValidatedJson,
+    services::{
+        audit::{self, AuditEvent, AuditService},
+        settings,
+    },
+    AppState,
+};
+
+use super::validator::{V1AuditExportFormat, V1AuditExportPayload, V1AuditLogQueryParams};
+
+#[debug_handler]
+#[instrument(skip(state, payload))]
+pub async fn list(
+    State(state): State<AppState>,
+    payload: Option<ValidatedJson<V1AuditLogQueryParams>>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let query = payload.map(|p| p.0).unwrap_or_default().into_query();
+    let page = query.page.unwrap_or(1);
+
+    match AuditLog::find_with_query(&state.sea_db, query).await {
+        Ok((entries, total)) => Ok((
+            StatusCode::OK,
+            Json(json!({
+                "data": entries,
+                "total": total,
+                "per_page": AuditLog::PER_PAGE,
+                "page": page,
+            })),
+        )),
+        Err(err) => {
+            error!(error = ?err, "Failed to list audit log");
+            Err(err)
+        }
+    }
+}
+
+#[debug_handler]
+#[instrument(skip(state), fields(log_id))]
+pub async fn view(
+    State(state): State<AppState>,
+    Path(log_id): Path<i64>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    match AuditLog::find_entry(&state.sea_db, log_id).await? {
+        Some(entry) => Ok((StatusCode::OK, Json(json!(entry)))),
+        None => {
+            Err(ErrorResponse::new(ErrorCode::RecordNotFound)
+                .with_message("Audit log entry not found"))
+        }
+    }
+}
+
+/// Matching entries, newest first, capped at `AUDIT_EXPORT_MAX_ROWS`.
+#[debug_handler]
+#[instrument(skip(state, payload))]
+pub async fn export(
+    State(state): State<AppState>,
+    payload: Option<ValidatedJson<V1AuditExportPayload>>,
+) -> Result<Response, ErrorResponse> {
+    let payload = payload.map(|p| p.0).unwrap_or_default();
+    let limit = settings::AUDIT_EXPORT_MAX_ROWS.int(&state).await.max(1) as u64;
+    let entries = AuditLog::export(&state.sea_db, payload.filters.into_query(), limit).await?;
+    info!(count = entries.len(), format = ?payload.format, "Exported audit log");
+
+    let event = AuditEvent::new("audit.export");
+    let stamp = chrono::Utc::now().format("%Y%m%d%H%M%S");
+    Ok(match payload.format {
+        V1AuditExportFormat::Json => (
+            event,
+            [(
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"audit-{}.json\"", stamp),
+            )],
+            Json(json!({ "data": entries, "limit": limit })),
+        )
+            .into_response(),
+        V1AuditExportFormat::Csv => (
+            event,
+            [
+                (header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+                (
+                    header::CONTENT_DISPOSITION,
+                    format!("attachment; filename=\"audit-{}.csv\"", stamp),
+                ),
+            ],
+            audit::to_csv(&entries),
+        )
+            .into_response(),
+    })
+}
+
+/// Apply `AUDIT_RETENTION_DAYS` now instead of waiting for the next sweep.
+#[debug_handler]
+pub async fn purge(State(state): State<AppState>) -> Result<impl IntoResponse, ErrorResponse> {
+    let deleted = AuditService::purge_expired(&state).await?;
+    info!(deleted, "Purged audit log");
+    Ok((
+        AuditEvent::new("audit.purge").after(&json!({ "deleted": deleted })),
+        Json(json!({ "deleted": deleted })),
+    ))
+}
@@ -0,0 +1,26 @@
+pub mod controller;
+pub mod validator;
+
+use axum::{
+    middleware,
+    routing::{get, post},
+    Router,
+};
+
+use crate::{
+    middlewares::{audit, auth_guard},
+    services::permission_service,
+    AppState,
+};
+
+pub fn routes() -> Router<AppState> {
+    Router::<AppState>::new()
+        .route("/list", post(controller::list))
+        .route("/view/{log_id}", get(controller::view))
+        .route("/export", post(controller::export))
+        .route("/purge", post(controller::purge))
+        .route_layer(middleware::from_fn(auth_guard::verified_with_permission(
+            permission_service::AUDIT_VIEW,
+        )))
+        .route_layer(middleware::from_fn(audit::audit))
+}
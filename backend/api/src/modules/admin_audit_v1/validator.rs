@@ -0,0 +1,59 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::db::sea_models::audit_log::AuditLogQuery;
+
+#[derive(Debug, Default, Deserialize, Serialize, Validate, JsonSchema)]
+pub struct V1AuditLogQueryParams {
+    pub page: Option<u64>,
+    pub actor_id: Option<i32>,
+    /// Exact action, or a prefix ending in `.` such as `user.`
+    #[validate(length(max = 128))]
+    pub action: Option<String>,
+    #[validate(length(max = 64))]
+    pub entity_type: Option<String>,
+    #[validate(length(max = 255))]
+    pub entity_id: Option<String>,
+    #[validate(length(max = 128))]
+    pub request_id: Option<String>,
+    #[validate(length(max = 64))]
+    pub ip: Option<String>,
+    pub created_at_gt: Option<chrono::DateTime<chrono::FixedOffset>>,
+    pub created_at_lt: Option<chrono::DateTime<chrono::FixedOffset>>,
+}
+
+impl V1AuditLogQueryParams {
+    pub fn into_query(self) -> AuditLogQuery {
+        AuditLogQuery {
+            page: self.page,
+            actor_id: self.actor_id,
+            action: self.action,
+            entity_type: self.entity_type,
+            entity_id: self.entity_id,
+            request_id: self.request_id,
+            ip: self.ip,
+            created_at_gt: self.created_at_gt,
+            created_at_lt: self.created_at_lt,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum V1AuditExportFormat {
+    #[default]
+    Json,
+    Csv,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, Validate, JsonSchema)]
+pub struct V1AuditExportPayload {
+    #[serde(default)]
+    pub format: V1AuditExportFormat,
+
+    /// Same filters as `/list`; `page` is ignored.
+    #[serde(flatten)]
+    #[validate(nested)]
+    pub filters: V1AuditLogQueryParams,
+}
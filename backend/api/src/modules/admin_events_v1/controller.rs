@@ -0,0 +1,41 @@
+use std::time::Duration;
+
+use axum::{
+    extract::State,
+    http::HeaderMap,
+    response::{sse::KeepAlive, IntoResponse, Sse},
+};
+use axum_macros::debug_handler;
+use tracing::{info, instrument};
+
+use crate::{
+    error::ErrorResponse,
+    services::{admin_events, auth::AuthSession},
+    AppState,
+};
+
+const KEEP_ALIVE: Duration = Duration::from_secs(15);
+
+/// Server-sent admin events the user may see. A reconnecting client sends
+/// `Last-Event-ID` to first get the events it missed.
+#[debug_handler]
+#[instrument(skip(state, auth, headers))]
+pub async fn stream(
+    State(state): State<AppState>,
+    auth: AuthSession,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let user = auth.user.unwrap();
+    let last_event_id = headers
+        .get("last-event-id")
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+    info!(
+        user_id = user.id,
+        resumed = last_event_id.is_some(),
+        "Admin event stream opened"
+    );
+
+    let events = admin_events::stream(state, user, last_event_id);
+    Ok(Sse::new(events).keep_alive(KeepAlive::new().interval(KEEP_ALIVE)))
+}
@@ -0,0 +1,13 @@
+pub mod controller;
+
+use axum::{middleware, routing::get, Router};
+
+use crate::{middlewares::auth_guard, AppState};
+
+pub fn routes() -> Router<AppState> {
+    Router::<AppState>::new()
+        .route("/stream", get(controller::stream))
+        .route_layer(middleware::from_fn(
+            auth_guard::verified_with_role::<{ auth_guard::ROLE_MODERATOR }>,
+        ))
+}
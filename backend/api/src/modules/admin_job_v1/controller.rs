@@ -0,0 +1,219 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use axum_macros::debug_handler;
+use sea_orm::EntityTrait;
+use serde_json::json;
+use tracing::{error, info, instrument};
+
+use crate::{
+    db::sea_models::{job::Entity as Job, job_schedule::Entity as JobSchedule},
+    error::{ErrorCode, ErrorResponse},
+    extractors::ValidatedJson,
+    services::{audit::AuditEvent, jobs, settings},
+    AppState,
+};
+
+use super::validator::{V1JobQueryParams, V1UpdateSchedulePayload};
+
+fn job_not_found() -> ErrorResponse {
+    ErrorResponse::new(ErrorCode::RecordNotFound).with_message("Job not found")
+}
+
+fn schedule_not_found() -> ErrorResponse {
+    ErrorResponse::new(ErrorCode::RecordNotFound).with_message("Job schedule not found")
+}
+
+#[debug_handler]
+#[instrument(skip(state, payload))]
+pub async fn list(
+    State(state): State<AppState>,
+    payload: Option<ValidatedJson<V1JobQueryParams>>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let query = payload.map(|p| p.0).unwrap_or_default().into_query();
+    let page = query.page.unwrap_or(1);
+
+    match Job::find_with_query(&state.sea_db, query).await {
+        Ok((jobs, total)) => Ok((
+            StatusCode::OK,
+            Json(json!({
+                "data": jobs,
+                "total": total,
+                "per_page": Job::PER_PAGE,
+                "page": page,
+            })),
+        )),
+        Err(err) => {
+            error!(error = ?err, "Failed to list jobs");
+            Err(err)
+        }
+    }
+}
+
+#[debug_handler]
+#[instrument(skip(state), fields(job_id))]
+pub async fn view(
+    State(state): State<AppState>,
+    Path(job_id): Path<i64>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    match Job::find_by_id(job_id).one(&state.sea_db).await? {
+        Some(job) => Ok((StatusCode::OK, Json(json!(job)))),
+        None => Err(job_not_found()),
+    }
+}
+
+/// Requeue a dead or cancelled job with a fresh set of attempts.
+#[debug_handler]
+#[instrument(skip(state), fields(job_id))]
+pub async fn retry(
+    State(state): State<AppState>,
+    Path(job_id): Path<i64>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let Some(before) = Job::find_by_id(job_id).one(&state.sea_db).await? else {
+        return Err(job_not_found());
+    };
+    let Some(job) = Job::retry(&state.sea_db, job_id).await? else {
+        return Err(ErrorResponse::new(ErrorCode::ResourceConflict)
+            .with_message("Only dead or cancelled jobs can be retried")
+            .with_context(json!({ "status": before.status })));
+    };
+    info!(job_id, kind = %job.kind, "Job requeued");
+
+    Ok((
+        AuditEvent::new("job.retry")
+            .target("job", job_id)
+            .before(&before)
+            .after(&job),
+        Json(json!(job)),
+    ))
+}
+
+/// Cancel a job that hasn't started.
+#[debug_handler]
+#[instrument(skip(state), fields(job_id))]
+pub async fn cancel(
+    State(state): State<AppState>,
+    Path(job_id): Path<i64>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let Some(before) = Job::find_by_id(job_id).one(&state.sea_db).await? else {
+        return Err(job_not_found());
+    };
+    let Some(job) = Job::cancel(&state.sea_db, job_id).await? else {
+        return Err(ErrorResponse::new(ErrorCode::ResourceConflict)
+            .with_message("Only pending jobs can be cancelled")
+            .with_context(json!({ "status": before.status })));
+    };
+    info!(job_id, kind = %job.kind, "Job cancelled");
+
+    Ok((
+        AuditEvent::new("job.cancel")
+            .target("job", job_id)
+            .before(&before)
+            .after(&job),
+        Json(json!(job)),
+    ))
+}
+
+/// Job counts per queue and status, the registered job kinds and the
+/// queues this instance works on.
+#[debug_handler]
+pub async fn stats(State(state): State<AppState>) -> Result<impl IntoResponse, ErrorResponse> {
+    let counts = Job::counts(&state.sea_db).await?;
+    let kinds: Vec<_> = jobs::HANDLERS
+        .iter()
+        .map(|handler| {
+            json!({
+                "kind": handler.kind,
+                "queue": handler.queue,
+                "max_attempts": handler.max_attempts,
+            })
+        })
+        .collect();
+    let queues: Vec<_> =
+        jobs::parse_queues(&settings::parse_list(&settings::JOB_QUEUES.env_string()))
+            .into_iter()
+            .map(|(queue, concurrency)| json!({ "queue": queue, "concurrency": concurrency }))
+            .collect();
+
+    Ok(Json(json!({
+        "counts": counts,
+        "kinds": kinds,
+        "queues": queues,
+    })))
+}
+
+#[debug_handler]
+pub async fn schedules(State(state): State<AppState>) -> Result<impl IntoResponse, ErrorResponse> {
+    let schedules = JobSchedule::list(&state.sea_db).await?;
+    Ok(Json(json!({ "data": schedules })))
+}
+
+/// Change a schedule's cron or turn it on or off. The next run is worked
+/// out again from the (new) cron; disabled schedules have none.
+#[debug_handler]
+#[instrument(skip(state, payload), fields(schedule_id))]
+pub async fn update_schedule(
+    State(state): State<AppState>,
+    Path(schedule_id): Path<i32>,
+    payload: ValidatedJson<V1UpdateSchedulePayload>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let Some(before) = JobSchedule::get_by_id(&state.sea_db, schedule_id).await? else {
+        return Err(schedule_not_found());
+    };
+
+    let changes = payload.0.into_update();
+    let cron = changes.cron.clone().unwrap_or_else(|| before.cron.clone());
+    let next = jobs::next_run_at(&cron).map_err(|err| {
+        ErrorResponse::new(ErrorCode::InvalidValue)
+            .with_message("Invalid cron expression")
+            .with_context(json!({ "cron": cron, "error": err }))
+    })?;
+    let enabled = changes.enabled.unwrap_or(before.enabled);
+
+    let schedule = JobSchedule::update(
+        &state.sea_db,
+        before.clone(),
+        changes,
+        enabled.then_some(next),
+    )
+    .await?;
+    info!(name = %schedule.name, cron = %schedule.cron, enabled, "Job schedule updated");
+
+    Ok((
+        AuditEvent::new("job.schedule_update")
+            .target("job_schedule", &schedule.name)
+            .before(&before)
+            .after(&schedule),
+        Json(json!(schedule)),
+    ))
+}
+
+/// Enqueue a schedule's job now; its next cron run is unchanged.
+#[debug_handler]
+#[instrument(skip(state), fields(schedule_id))]
+pub async fn run_schedule(
+    State(state): State<AppState>,
+    Path(schedule_id): Path<i32>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let Some(schedule) = JobSchedule::get_by_id(&state.sea_db, schedule_id).await? else {
+        return Err(schedule_not_found());
+    };
+    let name = schedule.name.clone();
+
+    let Some(job) = jobs::run_schedule_now(&state, schedule).await? else {
+        return Err(ErrorResponse::new(ErrorCode::ResourceConflict)
+            .with_message("The schedule's previous job is still queued"));
+    };
+    info!(name = %name, job_id = job.id, "Job schedule run manually");
+
+    Ok((
+        StatusCode::ACCEPTED,
+        AuditEvent::new("job.schedule_run")
+            .target("job_schedule", &name)
+            .after(&job),
+        Json(json!(job)),
+    ))
+}
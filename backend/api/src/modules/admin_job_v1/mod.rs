@@ -0,0 +1,36 @@
+pub mod controller;
+pub mod validator;
+
+use axum::{
+    middleware,
+    routing::{get, post},
+    Router,
+};
+
+use crate::{
+    middlewares::{audit, auth_guard},
+    services::permission_service,
+    AppState,
+};
+
+pub fn routes() -> Router<AppState> {
+    Router::<AppState>::new()
+        .route("/list", post(controller::list))
+        .route("/view/{job_id}", get(controller::view))
+        .route("/retry/{job_id}", post(controller::retry))
+        .route("/cancel/{job_id}", post(controller::cancel))
+        .route("/stats", get(controller::stats))
+        .route("/schedules", get(controller::schedules))
+        .route(
+            "/schedules/update/{schedule_id}",
+            post(controller::update_schedule),
+        )
+        .route(
+            "/schedules/run/{schedule_id}",
+            post(controller::run_schedule),
+        )
+        .route_layer(middleware::from_fn(auth_guard::verified_with_permission(
+            permission_service::JOB_MANAGE,
+        )))
+        .route_layer(middleware::from_fn(audit::audit))
+}
@@ -0,0 +1,50 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::db::sea_models::{
+    job::{JobQuery, JobStatus},
+    job_schedule::JobScheduleUpdate,
+};
+
+#[derive(Debug, Default, Deserialize, Serialize, Validate, JsonSchema)]
+pub struct V1JobQueryParams {
+    pub page: Option<u64>,
+    #[validate(length(max = 64))]
+    pub queue: Option<String>,
+    #[validate(length(max = 128))]
+    pub kind: Option<String>,
+    pub status: Option<JobStatus>,
+    pub created_at_gt: Option<chrono::DateTime<chrono::FixedOffset>>,
+    pub created_at_lt: Option<chrono::DateTime<chrono::FixedOffset>>,
+}
+
+impl V1JobQueryParams {
+    pub fn into_query(self) -> JobQuery {
+        JobQuery {
+            page: self.page,
+            queue: self.queue,
+            kind: self.kind,
+            status: self.status,
+            created_at_gt: self.created_at_gt,
+            created_at_lt: self.created_at_lt,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Validate, JsonSchema)]
+pub struct V1UpdateSchedulePayload {
+    /// Cron expression, seconds optional (`0 15 */6 * * *`)
+    #[validate(length(min = 1, max = 128))]
+    pub cron: Option<String>,
+    pub enabled: Option<bool>,
+}
+
+impl V1UpdateSchedulePayload {
+    pub fn into_update(self) -> JobScheduleUpdate {
+        JobScheduleUpdate {
+            cron: self.cron.map(|cron| cron.trim().to_string()),
+            enabled: self.enabled,
+        }
+    }
+}
@@ -0,0 +1,202 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use axum_macros::debug_handler;
+use serde_json::json;
+use tracing::{info, instrument};
+
+use crate::{
+    db::sea_models::{
+        role_permission,
+        user::{self, UserRole},
+        user_permission,
+    },
+    error::{ErrorCode, ErrorResponse},
+    extractors::ValidatedJson,
+    services::{
+        audit::AuditEvent,
+        auth::AuthSession,
+        permission_service::{self, PermissionService, CATALOG, ROLES},
+    },
+    AppState,
+};
+
+use super::validator::{V1RolePermissionsPayload, V1UserPermissionPayload};
+
+async fn find_user(state: &AppState, user_id: i32) -> Result<user::Model, ErrorResponse> {
+    user::Entity::get_by_id(&state.sea_db, user_id)
+        .await?
+        .ok_or_else(|| ErrorResponse::new(ErrorCode::UserNotFound).with_message("User not found"))
+}
+
+/// Overrides keyed by permission, so audit diffs name what changed.
+fn overrides_map(rows: &[user_permission::Model]) -> BTreeMap<&str, bool> {
+    rows.iter()
+        .map(|row| (row.permission.as_str(), row.granted))
+        .collect()
+}
+
+#[debug_handler]
+pub async fn catalog() -> Result<impl IntoResponse, ErrorResponse> {
+    Ok((StatusCode::OK, Json(json!({ "data": CATALOG }))))
+}
+
+/// Stored permissions of every role. Super admins hold every permission
+/// regardless of their row.
+#[debug_handler]
+pub async fn matrix(State(state): State<AppState>) -> Result<impl IntoResponse, ErrorResponse> {
+    let mut roles: BTreeMap<String, Vec<String>> = ROLES
+        .iter()
+        .map(|role| (role.to_string(), Vec::new()))
+        .collect();
+    for row in role_permission::Entity::matrix(&state.sea_db).await? {
+        roles
+            .entry(row.role.to_string())
+            .or_default()
+            .push(row.permission);
+    }
+    Ok((
+        StatusCode::OK,
+        Json(json!({ "data": roles, "catalog": CATALOG })),
+    ))
+}
+
+#[debug_handler]
+#[instrument(skip(state, auth, payload), fields(role = %role))]
+pub async fn role_update(
+    State(state): State<AppState>,
+    auth: AuthSession,
+    Path(role): Path<String>,
+    payload: ValidatedJson<V1RolePermissionsPayload>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let user = auth.user.unwrap();
+    let role = UserRole::from_str(&role)
+        .map_err(|message| ErrorResponse::new(ErrorCode::InvalidInput).with_message(message))?;
+
+    let before = role_permission::Entity::for_role(&state.sea_db, role).await?;
+    let permissions =
+        role_permission::Entity::set_for_role(&state.sea_db, role, &payload.0.permissions).await?;
+    PermissionService::forget_role(&state, role).await;
+
+    info!(user_id = user.id, role = %role.to_string(), "Role permissions updated");
+    Ok((
+        StatusCode::OK,
+        AuditEvent::new("permission.role_update")
+            .target("role", role.to_string())
+            .before(&json!({ "permissions": before }))
+            .after(&json!({ "permissions": permissions })),
+        Json(json!({ "role": role, "permissions": permissions })),
+    ))
+}
+
+/// Put every role back to its default permissions. User overrides are kept.
+#[debug_handler]
+pub async fn reset(
+    State(state): State<AppState>,
+    auth: AuthSession,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let user = auth.user.unwrap();
+    let defaults: Vec<(UserRole, Vec<String>)> = ROLES
+        .iter()
+        .map(|role| (*role, permission_service::defaults_for(*role)))
+        .collect();
+
+    role_permission::Entity::set_matrix(&state.sea_db, &defaults).await?;
+    PermissionService::flush(&state).await;
+
+    info!(user_id = user.id, "Permission matrix reset to defaults");
+    Ok((
+        StatusCode::OK,
+        AuditEvent::new("permission.reset"),
+        Json(json!({ "message": "Role permissions reset to defaults" })),
+    ))
+}
+
+#[debug_handler]
+pub async fn user_view(
+    State(state): State<AppState>,
+    Path(user_id): Path<i32>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let user = find_user(&state, user_id).await?;
+    let role_permissions =
+        PermissionService::role_permissions(&state.sea_db, Some(&state.redis_pool), user.role)
+            .await?;
+    let overrides = user_permission::Entity::for_user(&state.sea_db, user_id).await?;
+    let effective: BTreeSet<String> =
+        PermissionService::effective_for(&state.sea_db, Some(&state.redis_pool), &user).await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "user_id": user.id,
+            "role": user.role,
+            "role_permissions": role_permissions,
+            "overrides": overrides,
+            "permissions": effective,
+        })),
+    ))
+}
+
+#[debug_handler]
+#[instrument(skip(state, auth, payload), fields(user_id))]
+pub async fn user_update(
+    State(state): State<AppState>,
+    auth: AuthSession,
+    Path(user_id): Path<i32>,
+    payload: ValidatedJson<V1UserPermissionPayload>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let admin = auth.user.unwrap();
+    let user = find_user(&state, user_id).await?;
+    let p = payload.0;
+    let before = user_permission::Entity::for_user(&state.sea_db, user.id).await?;
+
+    match p.granted {
+        Some(granted) => {
+            user_permission::Entity::set(
+                &state.sea_db,
+                user.id,
+                &p.permission,
+                granted,
+                Some(admin.id),
+            )
+            .await?
+        }
+        None => {
+            user_permission::Entity::remove(&state.sea_db, user.id, &p.permission).await?;
+        }
+    }
+    PermissionService::forget_user(&state, user.id).await;
+
+    let effective =
+        PermissionService::effective_for(&state.sea_db, Some(&state.redis_pool), &user).await?;
+    let overrides = user_permission::Entity::for_user(&state.sea_db, user.id).await?;
+    info!(admin_id = admin.id, user_id, permission = %p.permission, granted = ?p.granted, "User permission override changed");
+    Ok((
+        StatusCode::OK,
+        AuditEvent::new("permission.user_update")
+            .target("user", user.id)
+            .before(&json!({ "overrides": overrides_map(&before) }))
+            .after(&json!({ "overrides": overrides_map(&overrides) })),
+        Json(json!({
+            "user_id": user.id,
+            "overrides": overrides,
+            "permissions": effective,
+        })),
+    ))
+}
+
+/// Drop the Redis copies of role permissions and overrides, e.g. after
+/// editing the tables by hand.
+#[debug_handler]
+pub async fn sync(State(state): State<AppState>) -> Result<impl IntoResponse, ErrorResponse> {
+    PermissionService::flush(&state).await;
+    Ok((
+        StatusCode::OK,
+        Json(json!({ "message": "Permission cache cleared" })),
+    ))
+}
@@ -0,0 +1,29 @@
+pub mod controller;
+pub mod validator;
+
+use axum::{
+    middleware,
+    routing::{get, post},
+    Router,
+};
+
+use crate::{
+    middlewares::{audit, auth_guard},
+    services::permission_service,
+    AppState,
+};
+
+pub fn routes() -> Router<AppState> {
+    Router::<AppState>::new()
+        .route("/catalog", get(controller::catalog))
+        .route("/matrix", get(controller::matrix))
+        .route("/role/{role}", post(controller::role_update))
+        .route("/reset", post(controller::reset))
+        .route("/user/{user_id}", get(controller::user_view))
+        .route("/user/{user_id}", post(controller::user_update))
+        .route("/sync", post(controller::sync))
+        .route_layer(middleware::from_fn(auth_guard::verified_with_permission(
+            permission_service::PERMISSION_MANAGE,
+        )))
+        .route_layer(middleware::from_fn(audit::audit))
+}
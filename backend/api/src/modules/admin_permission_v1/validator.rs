@@ -0,0 +1,35 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use validator::{Validate, ValidationError};
+
+use crate::services::permission_service;
+
+fn validate_permission(permission: &str) -> Result<(), ValidationError> {
+    if permission_service::is_known(permission) {
+        Ok(())
+    } else {
+        Err(ValidationError::new("unknown_permission"))
+    }
+}
+
+fn validate_permissions(permissions: &[String]) -> Result<(), ValidationError> {
+    permissions
+        .iter()
+        .try_for_each(|permission| validate_permission(permission))
+}
+
+#[derive(Debug, Deserialize, Serialize, Validate, JsonSchema)]
+pub struct V1RolePermissionsPayload {
+    /// The role's complete permission set; replaces the current one.
+    #[validate(custom(function = "validate_permissions"))]
+    pub permissions: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Validate, JsonSchema)]
+pub struct V1UserPermissionPayload {
+    #[validate(custom(function = "validate_permission"))]
+    pub permission: String,
+    /// `true` grants, `false` revokes, `null` drops the override so the
+    /// role decides again.
+    pub granted: Option<bool>,
+}
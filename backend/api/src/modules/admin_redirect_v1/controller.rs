@@ -0,0 +1,112 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use axum_macros::debug_handler;
+use serde_json::json;
+use tracing::{error, info, instrument, warn};
+
+use crate::{
+    db::sea_models::slug_redirect::Entity as SlugRedirect,
+    error::{ErrorCode, ErrorResponse},
+    extractors::ValidatedJson,
+    services::auth::AuthSession,
+    AppState,
+};
+
+use super::validator::{V1CreateRedirectPayload, V1RedirectQueryParams, V1UpdateRedirectPayload};
+
+#[debug_handler]
+#[instrument(skip(state, auth, payload), fields(entity = %payload.entity, from_slug = %payload.from_slug))]
+pub async fn create(
+    State(state): State<AppState>,
+    auth: AuthSession,
+    payload: ValidatedJson<V1CreateRedirectPayload>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let user = auth.user.unwrap();
+
+    match SlugRedirect::create_manual(&state.sea_db, payload.0.into_new_redirect(user.id)).await {
+        Ok(redirect) => {
+            info!(redirect_id = redirect.id, "Slug redirect created");
+            Ok((StatusCode::CREATED, Json(json!(redirect))))
+        }
+        Err(err) => {
+            warn!(error = ?err, "Failed to create slug redirect");
+            Err(err)
+        }
+    }
+}
+
+#[debug_handler]
+#[instrument(skip(state, payload), fields(redirect_id))]
+pub async fn update(
+    State(state): State<AppState>,
+    Path(redirect_id): Path<i32>,
+    payload: ValidatedJson<V1UpdateRedirectPayload>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    match SlugRedirect::update(&state.sea_db, redirect_id, payload.0.into_update()).await {
+        Ok(Some(redirect)) => {
+            info!(redirect_id, "Slug redirect updated");
+            Ok((StatusCode::OK, Json(json!(redirect))))
+        }
+        Ok(None) => {
+            Err(ErrorResponse::new(ErrorCode::RecordNotFound).with_message("Redirect not found"))
+        }
+        Err(err) => {
+            warn!(redirect_id, error = ?err, "Failed to update slug redirect");
+            Err(err)
+        }
+    }
+}
+
+#[debug_handler]
+#[instrument(skip(state), fields(redirect_id))]
+pub async fn delete(
+    State(state): State<AppState>,
+    Path(redirect_id): Path<i32>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    match SlugRedirect::delete(&state.sea_db, redirect_id).await {
+        Ok(0) => {
+            Err(ErrorResponse::new(ErrorCode::RecordNotFound).with_message("Redirect not found"))
+        }
+        Ok(_) => {
+            info!(redirect_id, "Slug redirect deleted");
+            Ok((
+                StatusCode::OK,
+                Json(json!({ "message": "Redirect deleted successfully" })),
+            ))
+        }
+        Err(err) => {
+            error!(redirect_id, error = ?err, "Failed to delete slug redirect");
+            Err(err)
+        }
+    }
+}
+
+#[debug_handler]
+#[instrument(skip(state, payload))]
+pub async fn list(
+    State(state): State<AppState>,
+    payload: Option<ValidatedJson<V1RedirectQueryParams>>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let query = payload.map(|p| p.0).unwrap_or_default().into_query();
+    let page = query.page.unwrap_or(1);
+
+    match SlugRedirect::find_with_query(&state.sea_db, query).await {
+        Ok((redirects, total)) => Ok((
+            StatusCode::OK,
+            Json(json!({
+                "data": redirects,
+                "total": total,
+                "per_page": SlugRedirect::PER_PAGE,
+                "page": page,
+            })),
+        )),
+        Err(err) => {
+            error!(error = ?err, "Failed to list slug redirects");
+            Err(err)
+        }
+    }
+}
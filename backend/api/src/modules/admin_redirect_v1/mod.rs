@@ -0,0 +1,22 @@
+pub mod controller;
+pub mod validator;
+
+use axum::{middleware, routing::post, Router};
+
+use crate::{
+    middlewares::{audit, auth_guard},
+    services::permission_service,
+    AppState,
+};
+
+pub fn routes() -> Router<AppState> {
+    Router::<AppState>::new()
+        .route("/create", post(controller::create))
+        .route("/update/{redirect_id}", post(controller::update))
+        .route("/delete/{redirect_id}", post(controller::delete))
+        .route("/list", post(controller::list))
+        .route_layer(middleware::from_fn(auth_guard::verified_with_permission(
+            permission_service::REDIRECT_MANAGE,
+        )))
+        .route_layer(middleware::from_fn(audit::audit))
+}
@@ -0,0 +1,84 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::db::sea_models::slug_redirect::{
+    NewSlugRedirect, RedirectEntity, RedirectKind, SlugRedirectQuery, UpdateSlugRedirect,
+};
+use crate::utils::{normalize_locale, validate_locale};
+
+/// Old URLs arrive as path segments; tolerate a pasted `/old-slug/`.
+fn normalize_slug(slug: String) -> String {
+    slug.trim().trim_matches('/').to_string()
+}
+
+#[derive(Debug, Deserialize, Serialize, Validate, JsonSchema)]
+pub struct V1CreateRedirectPayload {
+    pub entity: RedirectEntity,
+    #[validate(length(
+        min = 1,
+        max = 255,
+        message = "Slug must be between 1 and 255 characters"
+    ))]
+    pub from_slug: String,
+    #[validate(range(min = 1))]
+    pub target_id: i32,
+}
+
+impl V1CreateRedirectPayload {
+    pub fn into_new_redirect(self, created_by: i32) -> NewSlugRedirect {
+        NewSlugRedirect {
+            entity: self.entity,
+            from_slug: normalize_slug(self.from_slug),
+            target_id: self.target_id,
+            created_by: Some(created_by),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Validate, JsonSchema)]
+pub struct V1UpdateRedirectPayload {
+    #[validate(length(
+        min = 1,
+        max = 255,
+        message = "Slug must be between 1 and 255 characters"
+    ))]
+    pub from_slug: Option<String>,
+    #[validate(range(min = 1))]
+    pub target_id: Option<i32>,
+}
+
+impl V1UpdateRedirectPayload {
+    pub fn into_update(self) -> UpdateSlugRedirect {
+        UpdateSlugRedirect {
+            from_slug: self.from_slug.map(normalize_slug),
+            target_id: self.target_id,
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, Validate, JsonSchema)]
+pub struct V1RedirectQueryParams {
+    pub page: Option<u64>,
+    pub entity: Option<RedirectEntity>,
+    /// Redirects of posts in this locale
+    #[validate(custom(function = "validate_locale"))]
+    pub locale: Option<String>,
+    pub kind: Option<RedirectKind>,
+    pub target_id: Option<i32>,
+    #[validate(length(max = 255))]
+    pub search: Option<String>,
+}
+
+impl V1RedirectQueryParams {
+    pub fn into_query(self) -> SlugRedirectQuery {
+        SlugRedirectQuery {
+            page: self.page,
+            entity: self.entity,
+            locale: self.locale.as_deref().and_then(normalize_locale),
+            kind: self.kind,
+            target_id: self.target_id,
+            search: self.search,
+        }
+    }
+}
@@ -1,22 +1,38 @@
 use axum::{extract::State, http::StatusCode, response::IntoResponse, Json};
 use axum_macros::debug_handler;
+use sea_orm::EntityTrait;
 use serde_json::json;
 use tracing::{error, info, instrument};
 
 use crate::{
-    db::sea_models::route_status::Entity as RouteStatus,
-    error::ErrorResponse,
+    db::sea_models::{
+        job::{Entity as Job, JobStatus},
+        job_schedule::{self, Entity as JobSchedule, JobScheduleUpdate},
+        route_status::Entity as RouteStatus,
+    },
+    error::{ErrorCode, ErrorResponse},
     extractors::ValidatedJson,
-    services::auth::AuthSession,
-    services::{route_blocker_config, route_blocker_service::RouteBlockerService},
+    services::{audit::AuditEvent, auth::AuthSession},
+    services::{jobs, route_blocker_config, route_blocker_service::RouteBlockerService, settings},
     AppState,
 };
 
 use super::validator::{
-    V1BlockRoutePayload, V1DeleteRoutePayload, V1RouteStatusQueryParams, V1UnblockRoutePayload,
-    V1UpdateRoutePayload, V1UpdateSyncIntervalPayload,
+    V1BlockRoutePayload, V1DeleteRoutePayload, V1MaintenancePayload, V1RouteStatusQueryParams,
+    V1UnblockRoutePayload, V1UpdateRoutePayload, V1UpdateSyncIntervalPayload,
 };
 
+async fn audit_route(
+    state: &AppState,
+    action: &str,
+    pattern: &str,
+) -> Result<AuditEvent, ErrorResponse> {
+    let before = RouteStatus::find_by_pattern(&state.sea_db, pattern).await?;
+    Ok(AuditEvent::new(action)
+        .target("route", pattern)
+        .before(&before))
+}
+
 #[debug_handler]
 #[instrument(skip(state, _auth, payload), fields(pattern))]
 pub async fn block_route(
@@ -24,20 +40,23 @@ pub async fn block_route(
     _auth: AuthSession,
     payload: ValidatedJson<V1BlockRoutePayload>,
 ) -> Result<impl IntoResponse, ErrorResponse> {
-    let pattern = &payload.pattern;
+    let payload = payload.0;
+    let pattern = payload.pattern.clone();
     tracing::Span::current().record("pattern", pattern.as_str());
 
+    let audit = audit_route(&state, "route.block", &pattern).await?;
     let result = RouteBlockerService::block_route(
         State(state),
-        payload.pattern.clone(),
-        payload.reason.clone(),
+        payload.pattern,
+        payload.reason,
+        Some(payload.rule.into_rule()),
     )
     .await;
 
     match result {
         Ok(route) => {
             info!(pattern = %pattern, "Route blocked successfully");
-            Ok((StatusCode::CREATED, Json(json!(route))))
+            Ok((StatusCode::CREATED, audit.after(&route), Json(json!(route))))
         }
         Err(err) => {
             error!(pattern = %pattern, error = %err, "Failed to block route");
@@ -56,12 +75,13 @@ pub async fn unblock_route(
     let pattern = &payload.pattern;
     tracing::Span::current().record("pattern", pattern.as_str());
 
+    let audit = audit_route(&state, "route.unblock", pattern).await?;
     let result = RouteBlockerService::unblock_route(State(state), pattern.clone()).await;
 
     match result {
         Ok(route) => {
             info!(pattern = %pattern, "Route unblocked successfully");
-            Ok(Json(json!(route)))
+            Ok((audit.after(&route), Json(json!(route))))
         }
         Err(err) => {
             error!(pattern = %pattern, error = %err, "Failed to unblock route");
@@ -77,29 +97,37 @@ pub async fn update_route_status(
     _auth: AuthSession,
     payload: ValidatedJson<V1UpdateRoutePayload>,
 ) -> Result<impl IntoResponse, ErrorResponse> {
-    let pattern = &payload.pattern;
+    let payload = payload.0;
+    let pattern = payload.pattern.clone();
+    let is_blocked = payload.is_blocked;
     tracing::Span::current().record("pattern", pattern.as_str());
 
-    let result = if payload.is_blocked {
-        RouteBlockerService::block_route(State(state), pattern.clone(), payload.reason.clone())
-            .await
+    let audit = audit_route(&state, "route.update", &pattern).await?;
+    let result = if is_blocked {
+        RouteBlockerService::block_route(
+            State(state),
+            payload.pattern,
+            payload.reason,
+            Some(payload.rule.into_rule()),
+        )
+        .await
     } else {
-        RouteBlockerService::unblock_route(State(state), pattern.clone()).await
+        RouteBlockerService::unblock_route(State(state), payload.pattern).await
     };
 
     match result {
         Ok(route) => {
             info!(
                 pattern = %pattern,
-                is_blocked = payload.is_blocked,
+                is_blocked,
                 "Route status updated successfully"
             );
-            Ok(Json(json!(route)))
+            Ok((audit.after(&route), Json(json!(route))))
         }
         Err(err) => {
             error!(
                 pattern = %pattern,
-                is_blocked = payload.is_blocked,
+                is_blocked,
                 error = %err,
                 "Failed to update route status"
             );
@@ -118,12 +146,13 @@ pub async fn delete_route(
     let pattern = &payload.pattern;
     tracing::Span::current().record("pattern", pattern.as_str());
 
+    let audit = audit_route(&state, "route.delete", pattern).await?;
     let result = RouteBlockerService::delete_route(State(state), pattern.clone()).await;
 
     match result {
         Ok(response) => {
             info!(pattern = %pattern, "Route deleted successfully");
-            Ok(Json(response))
+            Ok((audit, Json(response)))
         }
         Err(err) => {
             error!(pattern = %pattern, error = %err, "Failed to delete route");
@@ -182,74 +211,211 @@ pub async fn sync_routes_to_redis(
     }
 }
 
-fn get_sync_status_json() -> serde_json::Value {
-    let interval_secs = route_blocker_config::get_sync_interval_secs();
-    let paused = route_blocker_config::is_paused();
-    let is_running = route_blocker_config::is_sync_running();
-    let last_sync_at = route_blocker_config::get_last_sync_at();
-    let next_sync_at = route_blocker_config::get_next_sync_at();
+fn sync_schedule_not_found() -> ErrorResponse {
+    ErrorResponse::new(ErrorCode::RecordNotFound)
+        .with_message("Route blocker sync schedule not registered yet")
+}
+
+async fn sync_schedule(state: &AppState) -> Result<job_schedule::Model, ErrorResponse> {
+    JobSchedule::get_by_name(&state.sea_db, route_blocker_config::SYNC_SCHEDULE)
+        .await?
+        .ok_or_else(sync_schedule_not_found)
+}
 
-    let remaining_secs = if !paused && !is_running {
+/// Sync status from the `route_blocker.sync` schedule and its last job.
+async fn sync_status_json(
+    state: &AppState,
+    schedule: &job_schedule::Model,
+) -> Result<serde_json::Value, ErrorResponse> {
+    let last_job = match schedule.last_job_id {
+        Some(job_id) => Job::find_by_id(job_id).one(&state.sea_db).await?,
+        None => None,
+    };
+    let is_running = last_job
+        .as_ref()
+        .is_some_and(|job| job.status == JobStatus::Running);
+    let paused = !schedule.enabled;
+    let next_sync_at = schedule.next_run_at.filter(|_| !paused);
+    let snapshot = route_blocker_config::snapshot();
+
+    let remaining_secs = if !is_running {
         next_sync_at.map(|next| {
             let now = chrono::Utc::now();
-            (next - now).num_seconds().max(0) as u64
+            (next.to_utc() - now).num_seconds().max(0) as u64
         })
     } else {
         None
     };
 
-    json!({
-        "interval_secs": interval_secs,
+    Ok(json!({
+        "interval_secs": route_blocker_config::cron_interval_secs(&schedule.cron).unwrap_or(0),
+        "cron": schedule.cron,
         "paused": paused,
         "is_running": is_running,
-        "last_sync_at": last_sync_at,
+        "last_sync_at": schedule.last_run_at,
         "next_sync_at": next_sync_at,
-        "remaining_secs": remaining_secs
-    })
+        "remaining_secs": remaining_secs,
+        "rules_version": snapshot.version,
+        "active_rules": snapshot.rules.len(),
+        "maintenance": snapshot.maintenance.enabled,
+    }))
 }
 
-#[debug_handler(state = AppState)]
-#[instrument(skip(_auth))]
-pub async fn get_sync_interval(_auth: AuthSession) -> Result<impl IntoResponse, ErrorResponse> {
+/// Apply `changes` to the sync schedule and, with `run_now`, queue a sync
+/// right away.
+async fn change_sync_schedule(
+    state: &AppState,
+    changes: JobScheduleUpdate,
+    run_now: bool,
+) -> Result<job_schedule::Model, ErrorResponse> {
+    let before = sync_schedule(state).await?;
+    let cron = changes.cron.clone().unwrap_or_else(|| before.cron.clone());
+    let enabled = changes.enabled.unwrap_or(before.enabled);
+    let next = jobs::next_run_at(&cron).map_err(|err| {
+        ErrorResponse::new(ErrorCode::InvalidValue)
+            .with_message("Invalid cron expression")
+            .with_context(json!({ "cron": cron, "error": err }))
+    })?;
+
+    let schedule =
+        JobSchedule::update(&state.sea_db, before, changes, enabled.then_some(next)).await?;
+    if run_now {
+        // `None` only means a sync is already queued, which serves as well.
+        jobs::run_schedule_now(state, schedule.clone()).await?;
+    }
+    Ok(schedule)
+}
+
+#[debug_handler]
+#[instrument(skip(state, _auth))]
+pub async fn get_sync_interval(
+    State(state): State<AppState>,
+    _auth: AuthSession,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let schedule = sync_schedule(&state).await?;
     info!("Retrieved route blocker sync interval status");
-    Ok(Json(get_sync_status_json()))
+    Ok(Json(sync_status_json(&state, &schedule).await?))
 }
 
-#[debug_handler(state = AppState)]
-#[instrument(skip(_auth, payload))]
+/// Reschedule the sync to run every `interval_secs`, rounded to what cron can
+/// express, and run it now.
+#[debug_handler]
+#[instrument(skip(state, _auth, payload))]
 pub async fn update_sync_interval(
+    State(state): State<AppState>,
     _auth: AuthSession,
     payload: ValidatedJson<V1UpdateSyncIntervalPayload>,
 ) -> Result<impl IntoResponse, ErrorResponse> {
-    let interval_secs = payload.interval_secs;
-    route_blocker_config::set_sync_interval_secs(interval_secs);
-    route_blocker_config::request_immediate_sync();
-    info!(interval_secs, "Updated route blocker sync interval");
+    let (cron, interval_secs) = route_blocker_config::interval_cron(payload.interval_secs);
+    let changes = JobScheduleUpdate {
+        cron: Some(cron),
+        enabled: None,
+    };
+    let schedule = change_sync_schedule(&state, changes, true).await?;
+    info!(interval_secs, cron = %schedule.cron, "Updated route blocker sync interval");
 
-    Ok((StatusCode::OK, Json(get_sync_status_json())))
+    Ok((
+        StatusCode::OK,
+        Json(sync_status_json(&state, &schedule).await?),
+    ))
 }
 
-#[debug_handler(state = AppState)]
-#[instrument(skip(_auth))]
-pub async fn pause_sync_interval(_auth: AuthSession) -> Result<impl IntoResponse, ErrorResponse> {
-    route_blocker_config::pause_sync();
-    info!("Paused route blocker sync loop");
-    Ok((StatusCode::OK, Json(get_sync_status_json())))
+#[debug_handler]
+#[instrument(skip(state, _auth))]
+pub async fn pause_sync_interval(
+    State(state): State<AppState>,
+    _auth: AuthSession,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let changes = JobScheduleUpdate {
+        cron: None,
+        enabled: Some(false),
+    };
+    let schedule = change_sync_schedule(&state, changes, false).await?;
+    info!("Paused route blocker sync schedule");
+    Ok((
+        StatusCode::OK,
+        Json(sync_status_json(&state, &schedule).await?),
+    ))
 }
 
-#[debug_handler(state = AppState)]
-#[instrument(skip(_auth))]
-pub async fn resume_sync_interval(_auth: AuthSession) -> Result<impl IntoResponse, ErrorResponse> {
-    route_blocker_config::resume_sync();
-    info!("Resumed route blocker sync loop");
-    Ok((StatusCode::OK, Json(get_sync_status_json())))
+/// Re-enable the sync; it runs right away when it was paused.
+#[debug_handler]
+#[instrument(skip(state, _auth))]
+pub async fn resume_sync_interval(
+    State(state): State<AppState>,
+    _auth: AuthSession,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let was_paused = !sync_schedule(&state).await?.enabled;
+    let changes = JobScheduleUpdate {
+        cron: None,
+        enabled: Some(true),
+    };
+    let schedule = change_sync_schedule(&state, changes, was_paused).await?;
+    info!(was_paused, "Resumed route blocker sync schedule");
+    Ok((
+        StatusCode::OK,
+        Json(sync_status_json(&state, &schedule).await?),
+    ))
+}
+
+#[debug_handler]
+#[instrument(skip(state, _auth))]
+pub async fn restart_sync_interval(
+    State(state): State<AppState>,
+    _auth: AuthSession,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let changes = JobScheduleUpdate {
+        cron: None,
+        enabled: Some(true),
+    };
+    let schedule = change_sync_schedule(&state, changes, true).await?;
+    info!("Restarted route blocker sync schedule");
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(sync_status_json(&state, &schedule).await?),
+    ))
+}
+
+#[debug_handler]
+#[instrument(skip(state, _auth))]
+pub async fn get_maintenance(
+    State(state): State<AppState>,
+    _auth: AuthSession,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let maintenance = RouteBlockerService::maintenance(&state).await;
+    Ok(Json(json!(maintenance)))
 }
 
-#[debug_handler(state = AppState)]
-#[instrument(skip(_auth))]
-pub async fn restart_sync_interval(_auth: AuthSession) -> Result<impl IntoResponse, ErrorResponse> {
-    route_blocker_config::resume_sync();
-    route_blocker_config::request_immediate_sync();
-    info!("Restarted route blocker sync loop");
-    Ok((StatusCode::ACCEPTED, Json(get_sync_status_json())))
+#[debug_handler]
+#[instrument(skip(state, auth, payload), fields(enabled = payload.enabled))]
+pub async fn update_maintenance(
+    State(state): State<AppState>,
+    auth: AuthSession,
+    payload: ValidatedJson<V1MaintenancePayload>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let payload = payload.0;
+    let before = RouteBlockerService::maintenance(&state).await;
+    let mut updates = vec![(settings::MAINTENANCE_MODE, payload.enabled.to_string())];
+    if let Some(message) = payload.message {
+        updates.push((settings::MAINTENANCE_MESSAGE, message));
+    }
+    if let Some(secs) = payload.retry_after_secs {
+        updates.push((settings::MAINTENANCE_RETRY_AFTER, secs.to_string()));
+    }
+    if let Some(role) = payload.exempt_role {
+        updates.push((settings::MAINTENANCE_EXEMPT_ROLE, role.to_string()));
+    }
+    if let Some(allow) = payload.allow {
+        updates.push((settings::MAINTENANCE_ALLOW, allow.join(",")));
+    }
+
+    let maintenance =
+        RouteBlockerService::update_maintenance(&state, updates, auth.user.map(|u| u.id)).await?;
+    info!(enabled = maintenance.enabled, "Maintenance mode updated");
+    Ok((
+        AuditEvent::new("route.maintenance")
+            .before(&before)
+            .after(&maintenance),
+        Json(json!(maintenance)),
+    ))
 }
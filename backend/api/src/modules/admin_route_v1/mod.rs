@@ -7,7 +7,11 @@ use axum::{
     Router,
 };
 
-use crate::{middlewares::auth_guard, AppState};
+use crate::{
+    middlewares::{audit, auth_guard},
+    services::permission_service,
+    AppState,
+};
 
 pub fn routes() -> Router<AppState> {
     let admin = Router::<AppState>::new()
@@ -17,6 +21,10 @@ pub fn routes() -> Router<AppState> {
         .route("/delete", post(controller::delete_route))
         .route("/list", post(controller::list_routes))
         .route("/sync", get(controller::sync_routes_to_redis))
+        .route(
+            "/maintenance",
+            get(controller::get_maintenance).post(controller::update_maintenance),
+        )
         .route(
             "/sync_interval",
             get(controller::get_sync_interval).post(controller::update_sync_interval),
@@ -33,7 +41,10 @@ pub fn routes() -> Router<AppState> {
             "/sync_interval/restart",
             post(controller::restart_sync_interval),
         )
-        .route_layer(middleware::from_fn(auth_guard::verified_with_role::<{ auth_guard::ROLE_ADMIN }>));
+        .route_layer(middleware::from_fn(auth_guard::verified_with_permission(
+            permission_service::ROUTE_MANAGE,
+        )))
+        .route_layer(middleware::from_fn(audit::audit));
 
     admin
 }
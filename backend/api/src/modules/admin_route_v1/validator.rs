@@ -1,9 +1,88 @@
-use crate::db::sea_models::route_status::{BlockFilter, RouteStatusQuery};
+use crate::db::sea_models::route_status::{BlockFilter, RouteMatch, RouteRule, RouteStatusQuery};
+use crate::db::sea_models::user::UserRole;
+use crate::services::route_blocker_rules::BLOCK_STATUSES;
 use crate::utils::sort::SortParam;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use validator::Validate;
+use validator::{Validate, ValidationError};
 
-#[derive(Debug, Deserialize, Serialize, Validate)]
+const METHODS: [&str; 7] = ["GET", "HEAD", "POST", "PUT", "PATCH", "DELETE", "OPTIONS"];
+
+fn validate_methods(methods: &[String]) -> Result<(), ValidationError> {
+    if methods
+        .iter()
+        .all(|method| METHODS.contains(&method.to_ascii_uppercase().as_str()))
+    {
+        Ok(())
+    } else {
+        Err(ValidationError::new("unknown_method"))
+    }
+}
+
+fn validate_block_status(status: u16) -> Result<(), ValidationError> {
+    if BLOCK_STATUSES.contains(&status) {
+        Ok(())
+    } else {
+        Err(ValidationError::new("unsupported_status"))
+    }
+}
+
+fn validate_window(rule: &V1RouteRuleFields) -> Result<(), ValidationError> {
+    match (rule.active_from, rule.active_until) {
+        (Some(from), Some(until)) if from >= until => {
+            Err(ValidationError::new("active_until_before_active_from"))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Rule settings for a block. Omitted fields fall back to the defaults:
+/// exact match, every method, nobody exempt, always in effect, 403.
+#[derive(Debug, Default, Deserialize, Serialize, Validate, JsonSchema)]
+#[validate(schema(function = "validate_window"))]
+pub struct V1RouteRuleFields {
+    pub match_type: Option<RouteMatch>,
+
+    #[validate(custom(function = "validate_methods"))]
+    pub methods: Option<Vec<String>>,
+
+    pub exempt_role: Option<UserRole>,
+
+    pub active_from: Option<chrono::DateTime<chrono::FixedOffset>>,
+
+    pub active_until: Option<chrono::DateTime<chrono::FixedOffset>>,
+
+    #[validate(custom(function = "validate_block_status"))]
+    pub status_code: Option<u16>,
+
+    #[validate(length(max = 500, message = "Message must be less than 500 characters"))]
+    pub message: Option<String>,
+
+    #[validate(range(max = 604800, message = "Retry-After must be at most a week"))]
+    pub retry_after_secs: Option<u32>,
+}
+
+impl V1RouteRuleFields {
+    pub fn into_rule(self) -> RouteRule {
+        RouteRule {
+            match_type: self.match_type.unwrap_or(RouteMatch::Exact),
+            methods: self
+                .methods
+                .unwrap_or_default()
+                .into_iter()
+                .map(|method| method.to_ascii_uppercase())
+                .collect(),
+            exempt_role: self.exempt_role,
+            active_from: self.active_from,
+            active_until: self.active_until,
+            status_code: self.status_code.map(i32::from),
+            message: self.message,
+            retry_after_secs: self.retry_after_secs.map(|secs| secs as i32),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Validate, JsonSchema)]
 pub struct V1BlockRoutePayload {
     #[validate(length(
         min = 1,
@@ -14,9 +93,13 @@ pub struct V1BlockRoutePayload {
 
     #[validate(length(max = 500, message = "Reason must be less than 500 characters"))]
     pub reason: Option<String>,
+
+    #[serde(flatten)]
+    #[validate(nested)]
+    pub rule: V1RouteRuleFields,
 }
 
-#[derive(Debug, Deserialize, Serialize, Validate)]
+#[derive(Debug, Deserialize, Serialize, Validate, JsonSchema)]
 pub struct V1UnblockRoutePayload {
     #[validate(length(
         min = 1,
@@ -26,7 +109,7 @@ pub struct V1UnblockRoutePayload {
     pub pattern: String,
 }
 
-#[derive(Debug, Deserialize, Serialize, Validate)]
+#[derive(Debug, Deserialize, Serialize, Validate, JsonSchema)]
 pub struct V1UpdateRoutePayload {
     #[validate(length(
         min = 1,
@@ -39,9 +122,14 @@ pub struct V1UpdateRoutePayload {
 
     #[validate(length(max = 500, message = "Reason must be less than 500 characters"))]
     pub reason: Option<String>,
+
+    /// Only used when blocking.
+    #[serde(flatten)]
+    #[validate(nested)]
+    pub rule: V1RouteRuleFields,
 }
 
-#[derive(Debug, Deserialize, Serialize, Validate)]
+#[derive(Debug, Deserialize, Serialize, Validate, JsonSchema)]
 pub struct V1DeleteRoutePayload {
     #[validate(length(
         min = 1,
@@ -51,7 +139,7 @@ pub struct V1DeleteRoutePayload {
     pub pattern: String,
 }
 
-#[derive(Debug, Deserialize, Serialize, Validate, Clone)]
+#[derive(Debug, Deserialize, Serialize, Validate, Clone, JsonSchema)]
 pub struct V1RouteStatusQueryParams {
     pub page: Option<u64>,
     pub block_filter: Option<BlockFilter>,
@@ -80,7 +168,7 @@ impl V1RouteStatusQueryParams {
     }
 }
 
-#[derive(Debug, Deserialize, Serialize, Validate)]
+#[derive(Debug, Deserialize, Serialize, Validate, JsonSchema)]
 pub struct V1UpdateSyncIntervalPayload {
     #[validate(range(
         min = 60,
@@ -89,3 +177,19 @@ pub struct V1UpdateSyncIntervalPayload {
     ))]
     pub interval_secs: u64,
 }
+
+#[derive(Debug, Deserialize, Serialize, Validate, JsonSchema)]
+pub struct V1MaintenancePayload {
+    pub enabled: bool,
+
+    #[validate(length(max = 500, message = "Message must be less than 500 characters"))]
+    pub message: Option<String>,
+
+    #[validate(range(max = 86400, message = "Retry-After must be at most a day"))]
+    pub retry_after_secs: Option<u32>,
+
+    pub exempt_role: Option<UserRole>,
+
+    /// Path prefixes that stay reachable, replacing the current list.
+    pub allow: Option<Vec<String>>,
+}
@@ -0,0 +1,320 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use axum_macros::debug_handler;
+use serde_json::json;
+use tracing::{error, info, instrument};
+
+use crate::{
+    db::sea_models::{
+        webhook::{self, Entity as Webhook, NewWebhook},
+        webhook_delivery::{Entity as WebhookDelivery, NewDelivery},
+    },
+    error::{ErrorCode, ErrorResponse},
+    extractors::ValidatedJson,
+    services::{audit::AuditEvent, auth::AuthSession, webhooks},
+    AppState,
+};
+
+use super::validator::{
+    V1CreateWebhookPayload, V1UpdateWebhookPayload, V1WebhookDeliveryQueryParams,
+};
+
+fn webhook_not_found() -> ErrorResponse {
+    ErrorResponse::new(ErrorCode::RecordNotFound).with_message("Webhook not found")
+}
+
+fn delivery_not_found() -> ErrorResponse {
+    ErrorResponse::new(ErrorCode::RecordNotFound).with_message("Webhook delivery not found")
+}
+
+fn webhook_disabled(webhook: &webhook::Model) -> ErrorResponse {
+    ErrorResponse::new(ErrorCode::ResourceConflict)
+        .with_message("Webhook is disabled")
+        .with_context(json!({ "disabled_reason": webhook.disabled_reason }))
+}
+
+/// Why `url` or `events` can't be used: only http(s) endpoints, and only
+/// events that exist.
+fn invalid_target(url: Option<&str>, events: Option<&[String]>) -> Option<ErrorResponse> {
+    if let Some(url) = url {
+        if !(url.starts_with("https://") || url.starts_with("http://")) {
+            return Some(
+                ErrorResponse::new(ErrorCode::InvalidValue)
+                    .with_message("Webhook URL must use http or https")
+                    .with_context(json!({ "url": url })),
+            );
+        }
+    }
+    let unknown: Vec<&String> = events
+        .unwrap_or_default()
+        .iter()
+        .filter(|pattern| !webhook::is_valid_pattern(pattern))
+        .collect();
+    (!unknown.is_empty()).then(|| {
+        ErrorResponse::new(ErrorCode::InvalidValue)
+            .with_message("Unknown webhook events")
+            .with_context(json!({ "events": unknown }))
+    })
+}
+
+/// The webhook with its secret, for the responses that reveal it.
+fn with_secret(webhook: &webhook::Model) -> serde_json::Value {
+    let mut value = json!(webhook);
+    value["secret"] = json!(webhook.secret);
+    value
+}
+
+#[debug_handler]
+pub async fn list(State(state): State<AppState>) -> Result<impl IntoResponse, ErrorResponse> {
+    let webhooks = Webhook::list(&state.sea_db).await?;
+    Ok(Json(json!({ "data": webhooks })))
+}
+
+/// Events a webhook can subscribe to.
+#[debug_handler]
+pub async fn events() -> impl IntoResponse {
+    let events: Vec<_> = webhook::EVENTS
+        .iter()
+        .map(|(name, description)| json!({ "event": name, "description": description }))
+        .collect();
+    Json(json!({ "data": events }))
+}
+
+#[debug_handler]
+#[instrument(skip(state), fields(webhook_id))]
+pub async fn view(
+    State(state): State<AppState>,
+    Path(webhook_id): Path<i32>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    match Webhook::get_by_id(&state.sea_db, webhook_id).await? {
+        Some(webhook) => Ok((StatusCode::OK, Json(json!(webhook)))),
+        None => Err(webhook_not_found()),
+    }
+}
+
+/// Register an endpoint. The generated secret is only returned here and by
+/// `rotate_secret`.
+#[debug_handler]
+#[instrument(skip(state, auth, payload), fields(name = %payload.name))]
+pub async fn create(
+    State(state): State<AppState>,
+    auth: AuthSession,
+    payload: ValidatedJson<V1CreateWebhookPayload>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let payload = payload.0;
+    let url = payload.url.trim().to_string();
+    if let Some(err) = invalid_target(Some(&url), Some(&payload.events)) {
+        return Err(err);
+    }
+
+    let webhook = Webhook::create(
+        &state.sea_db,
+        NewWebhook {
+            name: payload.name.trim().to_string(),
+            url,
+            secret: webhooks::generate_secret(),
+            events: payload.events,
+            enabled: payload.enabled.unwrap_or(true),
+            created_by: auth.user.as_ref().map(|user| user.id),
+        },
+    )
+    .await?;
+    info!(webhook_id = webhook.id, url = %webhook.url, "Webhook created");
+
+    Ok((
+        StatusCode::CREATED,
+        AuditEvent::new("webhook.create")
+            .target("webhook", webhook.id)
+            .after(&webhook),
+        Json(with_secret(&webhook)),
+    ))
+}
+
+#[debug_handler]
+#[instrument(skip(state, payload), fields(webhook_id))]
+pub async fn update(
+    State(state): State<AppState>,
+    Path(webhook_id): Path<i32>,
+    payload: ValidatedJson<V1UpdateWebhookPayload>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let Some(before) = Webhook::get_by_id(&state.sea_db, webhook_id).await? else {
+        return Err(webhook_not_found());
+    };
+
+    let changes = payload.0.into_update();
+    if let Some(err) = invalid_target(changes.url.as_deref(), changes.events.as_deref()) {
+        return Err(err);
+    }
+
+    let webhook = Webhook::update(&state.sea_db, before.clone(), changes).await?;
+    info!(webhook_id, enabled = webhook.enabled, "Webhook updated");
+
+    Ok((
+        AuditEvent::new("webhook.update")
+            .target("webhook", webhook_id)
+            .before(&before)
+            .after(&webhook),
+        Json(json!(webhook)),
+    ))
+}
+
+/// Delete the webhook along with its delivery log.
+#[debug_handler]
+#[instrument(skip(state), fields(webhook_id))]
+pub async fn delete(
+    State(state): State<AppState>,
+    Path(webhook_id): Path<i32>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let Some(before) = Webhook::get_by_id(&state.sea_db, webhook_id).await? else {
+        return Err(webhook_not_found());
+    };
+
+    match Webhook::delete(&state.sea_db, webhook_id).await {
+        Ok(_) => {
+            info!(webhook_id, "Webhook deleted");
+            Ok((
+                AuditEvent::new("webhook.delete")
+                    .target("webhook", webhook_id)
+                    .before(&before),
+                Json(json!({ "message": "Webhook deleted" })),
+            ))
+        }
+        Err(err) => {
+            error!(webhook_id, error = ?err, "Failed to delete webhook");
+            Err(err)
+        }
+    }
+}
+
+/// Replace the signing secret; deliveries already queued are signed with the
+/// new one when they're sent.
+#[debug_handler]
+#[instrument(skip(state), fields(webhook_id))]
+pub async fn rotate_secret(
+    State(state): State<AppState>,
+    Path(webhook_id): Path<i32>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let Some(webhook) = Webhook::get_by_id(&state.sea_db, webhook_id).await? else {
+        return Err(webhook_not_found());
+    };
+
+    let webhook =
+        Webhook::rotate_secret(&state.sea_db, webhook, webhooks::generate_secret()).await?;
+    info!(webhook_id, "Webhook secret rotated");
+
+    Ok((
+        AuditEvent::new("webhook.rotate_secret").target("webhook", webhook_id),
+        Json(with_secret(&webhook)),
+    ))
+}
+
+/// Queue a `webhook.ping` delivery to this webhook whatever its events.
+#[debug_handler]
+#[instrument(skip(state), fields(webhook_id))]
+pub async fn test(
+    State(state): State<AppState>,
+    Path(webhook_id): Path<i32>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let Some(webhook) = Webhook::get_by_id(&state.sea_db, webhook_id).await? else {
+        return Err(webhook_not_found());
+    };
+    if !webhook.enabled {
+        return Err(webhook_disabled(&webhook));
+    }
+
+    let delivery = Webhook::send_to(
+        &state.sea_db,
+        &webhook,
+        webhook::events::PING,
+        webhooks::ping_data(&webhook),
+    )
+    .await?;
+    info!(webhook_id, delivery_id = delivery.id, "Webhook test queued");
+
+    Ok((StatusCode::ACCEPTED, Json(json!(delivery))))
+}
+
+#[debug_handler]
+#[instrument(skip(state, payload))]
+pub async fn deliveries(
+    State(state): State<AppState>,
+    payload: Option<ValidatedJson<V1WebhookDeliveryQueryParams>>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let query = payload.map(|p| p.0).unwrap_or_default().into_query();
+    let page = query.page.unwrap_or(1);
+
+    match WebhookDelivery::find_with_query(&state.sea_db, query).await {
+        Ok((deliveries, total)) => Ok((
+            StatusCode::OK,
+            Json(json!({
+                "data": deliveries,
+                "total": total,
+                "per_page": WebhookDelivery::PER_PAGE,
+                "page": page,
+            })),
+        )),
+        Err(err) => {
+            error!(error = ?err, "Failed to list webhook deliveries");
+            Err(err)
+        }
+    }
+}
+
+#[debug_handler]
+#[instrument(skip(state), fields(delivery_id))]
+pub async fn view_delivery(
+    State(state): State<AppState>,
+    Path(delivery_id): Path<i64>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    match WebhookDelivery::get_by_id(&state.sea_db, delivery_id).await? {
+        Some(delivery) => Ok((StatusCode::OK, Json(json!(delivery)))),
+        None => Err(delivery_not_found()),
+    }
+}
+
+/// Send a delivery's body again as a new delivery with the same event id.
+#[debug_handler]
+#[instrument(skip(state), fields(delivery_id))]
+pub async fn redeliver(
+    State(state): State<AppState>,
+    Path(delivery_id): Path<i64>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let Some(original) = WebhookDelivery::get_by_id(&state.sea_db, delivery_id).await? else {
+        return Err(delivery_not_found());
+    };
+    let Some(webhook) = Webhook::get_by_id(&state.sea_db, original.webhook_id).await? else {
+        return Err(webhook_not_found());
+    };
+    if !webhook.enabled {
+        return Err(webhook_disabled(&webhook));
+    }
+
+    let delivery = WebhookDelivery::queue(
+        &state.sea_db,
+        NewDelivery {
+            webhook_id: original.webhook_id,
+            event: original.event,
+            event_id: original.event_id,
+            payload: original.payload,
+            redelivery_of: Some(original.id),
+        },
+    )
+    .await?;
+    info!(
+        delivery_id,
+        redelivery_id = delivery.id,
+        "Webhook delivery queued again"
+    );
+
+    Ok((
+        StatusCode::ACCEPTED,
+        AuditEvent::new("webhook.redeliver")
+            .target("webhook", webhook.id)
+            .after(&json!({ "delivery_id": delivery.id, "redelivery_of": delivery_id })),
+        Json(json!(delivery)),
+    ))
+}
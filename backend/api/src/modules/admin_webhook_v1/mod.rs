@@ -0,0 +1,42 @@
+pub mod controller;
+pub mod validator;
+
+use axum::{
+    middleware,
+    routing::{get, post},
+    Router,
+};
+
+use crate::{
+    middlewares::{audit, auth_guard},
+    services::permission_service,
+    AppState,
+};
+
+pub fn routes() -> Router<AppState> {
+    Router::<AppState>::new()
+        .route("/list", get(controller::list))
+        .route("/events", get(controller::events))
+        .route("/create", post(controller::create))
+        .route("/view/{webhook_id}", get(controller::view))
+        .route("/update/{webhook_id}", post(controller::update))
+        .route("/delete/{webhook_id}", post(controller::delete))
+        .route(
+            "/rotate_secret/{webhook_id}",
+            post(controller::rotate_secret),
+        )
+        .route("/test/{webhook_id}", post(controller::test))
+        .route("/deliveries/list", post(controller::deliveries))
+        .route(
+            "/deliveries/view/{delivery_id}",
+            get(controller::view_delivery),
+        )
+        .route(
+            "/deliveries/redeliver/{delivery_id}",
+            post(controller::redeliver),
+        )
+        .route_layer(middleware::from_fn(auth_guard::verified_with_permission(
+            permission_service::WEBHOOK_MANAGE,
+        )))
+        .route_layer(middleware::from_fn(audit::audit))
+}
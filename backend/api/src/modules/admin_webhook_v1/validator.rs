@@ -0,0 +1,68 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::db::sea_models::{
+    webhook::WebhookUpdate,
+    webhook_delivery::{DeliveryStatus, WebhookDeliveryQuery},
+};
+
+#[derive(Debug, Deserialize, Serialize, Validate, JsonSchema)]
+pub struct V1CreateWebhookPayload {
+    #[validate(length(min = 1, max = 128))]
+    pub name: String,
+    #[validate(url, length(max = 2048))]
+    pub url: String,
+    /// Event names or `prefix.*` patterns; empty or missing means all events
+    #[serde(default)]
+    #[validate(length(max = 32))]
+    pub events: Vec<String>,
+    pub enabled: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Validate, JsonSchema)]
+pub struct V1UpdateWebhookPayload {
+    #[validate(length(min = 1, max = 128))]
+    pub name: Option<String>,
+    #[validate(url, length(max = 2048))]
+    pub url: Option<String>,
+    #[validate(length(max = 32))]
+    pub events: Option<Vec<String>>,
+    /// Turning a disabled webhook back on clears its failure count
+    pub enabled: Option<bool>,
+}
+
+impl V1UpdateWebhookPayload {
+    pub fn into_update(self) -> WebhookUpdate {
+        WebhookUpdate {
+            name: self.name.map(|name| name.trim().to_string()),
+            url: self.url.map(|url| url.trim().to_string()),
+            events: self.events,
+            enabled: self.enabled,
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, Validate, JsonSchema)]
+pub struct V1WebhookDeliveryQueryParams {
+    pub page: Option<u64>,
+    pub webhook_id: Option<i32>,
+    #[validate(length(max = 64))]
+    pub event: Option<String>,
+    pub status: Option<DeliveryStatus>,
+    pub created_at_gt: Option<chrono::DateTime<chrono::FixedOffset>>,
+    pub created_at_lt: Option<chrono::DateTime<chrono::FixedOffset>>,
+}
+
+impl V1WebhookDeliveryQueryParams {
+    pub fn into_query(self) -> WebhookDeliveryQuery {
+        WebhookDeliveryQuery {
+            page: self.page,
+            webhook_id: self.webhook_id,
+            event: self.event,
+            status: self.status,
+            created_at_gt: self.created_at_gt,
+            created_at_lt: self.created_at_lt,
+        }
+    }
+}
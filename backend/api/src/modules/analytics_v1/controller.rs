@@ -987,6 +987,9 @@ fn status_label_from_enum(status: &PostStatus) -> String {
         PostStatus::Draft => "Draft".to_string(),
         PostStatus::Published => "Published".to_string(),
         PostStatus::Archived => "Archived".to_string(),
+        PostStatus::InReview => "In review".to_string(),
+        PostStatus::ChangesRequested => "Changes requested".to_string(),
+        PostStatus::Approved => "Approved".to_string(),
     }
 }
 
@@ -995,6 +998,9 @@ fn status_label_from_str(status: &str) -> String {
         "draft" => "Draft".to_string(),
         "published" => "Published".to_string(),
         "archived" => "Archived".to_string(),
+        "in_review" => "In review".to_string(),
+        "changes_requested" => "Changes requested".to_string(),
+        "approved" => "Approved".to_string(),
         other => {
             let mut chars = other.chars();
             match chars.next() {
@@ -1022,6 +1028,9 @@ fn parse_status_filters(
                     "draft" => PostStatus::Draft,
                     "published" => PostStatus::Published,
                     "archived" => PostStatus::Archived,
+                    "in_review" => PostStatus::InReview,
+                    "changes_requested" => PostStatus::ChangesRequested,
+                    "approved" => PostStatus::Approved,
                     _ => {
                         return Err(ErrorResponse::new(ErrorCode::InvalidInput)
                             .with_message(format!("Invalid post status filter: {}", value)))
@@ -3,7 +3,7 @@ pub mod validator;
 
 use axum::{middleware, routing::post, Router};
 
-use crate::{middlewares::auth_guard, AppState};
+use crate::{middlewares::auth_guard, services::permission_service, AppState};
 
 /// Routes for the analytics v1 module.
 pub fn routes() -> Router<AppState> {
@@ -31,5 +31,7 @@ pub fn routes() -> Router<AppState> {
             post(controller::media_upload_trends),
         )
         .route("/dashboard/summary", post(controller::dashboard_summary))
-        .route_layer(middleware::from_fn(auth_guard::verified_with_role::<{ auth_guard::ROLE_ADMIN }>))
+        .route_layer(middleware::from_fn(auth_guard::verified_with_permission(
+            permission_service::ANALYTICS_VIEW,
+        )))
 }
@@ -1,6 +1,7 @@
 use std::{collections::BTreeMap, ops::Bound};
 
 use chrono::{DateTime, Datelike, Duration, FixedOffset, NaiveDate, TimeZone, Utc};
+use schemars::JsonSchema;
 use sea_orm::prelude::DateTimeWithTimeZone;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -10,7 +11,7 @@ pub const DEFAULT_PER_PAGE: u64 = 30;
 pub const MAX_PER_PAGE: u64 = 200;
 
 /// Shared request envelope for analytics endpoints.
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
 pub struct AnalyticsEnvelope {
     #[serde(
         default,
@@ -167,7 +168,7 @@ impl SortOrder {
     }
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum AnalyticsInterval {
     Hour,
@@ -210,7 +211,7 @@ impl Default for AnalyticsInterval {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+#[derive(Debug, Clone, Serialize, Deserialize, Validate, JsonSchema)]
 pub struct RegistrationTrendsFilters {
     #[serde(default)]
     pub group_by: AnalyticsInterval,
@@ -224,7 +225,7 @@ impl Default for RegistrationTrendsFilters {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct RegistrationTrendsRequest {
     #[serde(flatten)]
     pub envelope: AnalyticsEnvelope,
@@ -239,13 +240,13 @@ impl Validate for RegistrationTrendsRequest {
     }
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct RegistrationTrendPoint {
     pub bucket: String,
     pub new_users: i64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+#[derive(Debug, Clone, Serialize, Deserialize, Validate, JsonSchema)]
 pub struct VerificationRatesFilters {
     #[serde(default)]
     pub group_by: AnalyticsInterval,
@@ -259,7 +260,7 @@ impl Default for VerificationRatesFilters {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct VerificationRatesRequest {
     #[serde(flatten)]
     pub envelope: AnalyticsEnvelope,
@@ -274,7 +275,7 @@ impl Validate for VerificationRatesRequest {
     }
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct VerificationRatePoint {
     pub bucket: String,
     pub requested: i64,
@@ -282,7 +283,7 @@ pub struct VerificationRatePoint {
     pub success_rate: f64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+#[derive(Debug, Clone, Serialize, Deserialize, Validate, JsonSchema)]
 pub struct PublishingTrendsFilters {
     #[serde(default)]
     pub group_by: AnalyticsInterval,
@@ -299,7 +300,7 @@ impl Default for PublishingTrendsFilters {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct PublishingTrendsRequest {
     #[serde(flatten)]
     pub envelope: AnalyticsEnvelope,
@@ -314,13 +315,13 @@ impl Validate for PublishingTrendsRequest {
     }
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct PublishingTrendPoint {
     pub bucket: String,
     pub counts: BTreeMap<String, i64>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+#[derive(Debug, Clone, Serialize, Deserialize, Validate, JsonSchema)]
 pub struct PageViewsFilters {
     #[serde(default)]
     pub group_by: AnalyticsInterval,
@@ -345,7 +346,7 @@ impl Default for PageViewsFilters {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct PageViewsRequest {
     #[serde(flatten)]
     pub envelope: AnalyticsEnvelope,
@@ -360,7 +361,7 @@ impl Validate for PageViewsRequest {
     }
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct PageViewPoint {
     pub bucket: String,
     pub views: i64,
@@ -371,7 +372,7 @@ fn default_min_views() -> i64 {
     100
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum CommentRateSort {
     CommentRate,
@@ -384,7 +385,7 @@ impl Default for CommentRateSort {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+#[derive(Debug, Clone, Serialize, Deserialize, Validate, JsonSchema)]
 pub struct CommentRateFilters {
     #[serde(default = "default_min_views")]
     pub min_views: i64,
@@ -401,7 +402,7 @@ impl Default for CommentRateFilters {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct CommentRateRequest {
     #[serde(flatten)]
     pub envelope: AnalyticsEnvelope,
@@ -416,7 +417,7 @@ impl Validate for CommentRateRequest {
     }
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct CommentRatePoint {
     pub post_id: i32,
     pub title: String,
@@ -425,7 +426,7 @@ pub struct CommentRatePoint {
     pub comment_rate: f64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+#[derive(Debug, Clone, Serialize, Deserialize, Validate, JsonSchema)]
 pub struct NewsletterGrowthFilters {
     #[serde(default)]
     pub group_by: AnalyticsInterval,
@@ -439,7 +440,7 @@ impl Default for NewsletterGrowthFilters {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct NewsletterGrowthRequest {
     #[serde(flatten)]
     pub envelope: AnalyticsEnvelope,
@@ -454,7 +455,7 @@ impl Validate for NewsletterGrowthRequest {
     }
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct NewsletterGrowthPoint {
     pub bucket: String,
     pub new_subscribers: i64,
@@ -463,7 +464,7 @@ pub struct NewsletterGrowthPoint {
     pub net_growth: i64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+#[derive(Debug, Clone, Serialize, Deserialize, Validate, JsonSchema)]
 pub struct MediaUploadFilters {
     #[serde(default)]
     pub group_by: AnalyticsInterval,
@@ -477,7 +478,7 @@ impl Default for MediaUploadFilters {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct MediaUploadRequest {
     #[serde(flatten)]
     pub envelope: AnalyticsEnvelope,
@@ -492,7 +493,7 @@ impl Validate for MediaUploadRequest {
     }
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct MediaUploadPoint {
     pub bucket: String,
     pub upload_count: i64,
@@ -500,7 +501,7 @@ pub struct MediaUploadPoint {
     pub avg_size_mb: f64,
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema)]
 pub enum DashboardPeriod {
     #[serde(rename = "7d")]
     SevenDays,
@@ -534,7 +535,7 @@ impl Default for DashboardPeriod {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+#[derive(Debug, Clone, Serialize, Deserialize, Validate, JsonSchema)]
 pub struct DashboardSummaryFilters {
     #[serde(default)]
     pub period: DashboardPeriod,
@@ -548,7 +549,7 @@ impl Default for DashboardSummaryFilters {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct DashboardSummaryRequest {
     #[serde(flatten)]
     pub envelope: Option<AnalyticsEnvelope>,
@@ -565,32 +566,32 @@ impl Validate for DashboardSummaryRequest {
     }
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct DashboardSummaryUsers {
     pub total: i64,
     pub new_in_period: i64,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct DashboardSummaryPosts {
     pub published: i64,
     pub drafts: i64,
     pub views_in_period: i64,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct DashboardSummaryEngagement {
     pub comments_in_period: i64,
     pub newsletter_confirmed: i64,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct DashboardSummaryMedia {
     pub total_files: i64,
     pub uploads_in_period: i64,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct DashboardSummaryData {
     pub users: DashboardSummaryUsers,
     pub posts: DashboardSummaryPosts,
@@ -598,7 +599,7 @@ pub struct DashboardSummaryData {
     pub media: DashboardSummaryMedia,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct AnalyticsMeta {
     pub total: u64,
     pub page: u64,
@@ -642,7 +643,8 @@ impl AnalyticsMeta {
     }
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+#[schemars(rename = "AnalyticsEnvelopeResponse_{T}")]
 pub struct AnalyticsEnvelopeResponse<T> {
     pub data: T,
     pub meta: AnalyticsMeta,
@@ -73,7 +73,10 @@ pub async fn log_in(
                 .and_then(|v| v.to_str().ok())
                 .map(|s| s.to_string());
 
-            match auth.login_with_metadata(&user, device.clone(), ip.clone()).await {
+            match auth
+                .login_with_metadata(&user, device.clone(), ip.clone())
+                .await
+            {
                 Ok(_) => {
                     info!(
                         user_id = user.id,
@@ -216,11 +219,11 @@ pub async fn twofa_setup(
 
     Ok((
         StatusCode::OK,
-        Json(json!({
-            "secret": secret_b32,
-            "otpauth_url": otpauth_url,
-            "backup_codes": backup_codes,
-        })),
+        Json(user::TwoFASetup {
+            secret: secret_b32,
+            otpauth_url,
+            backup_codes,
+        }),
     ))
 }
 
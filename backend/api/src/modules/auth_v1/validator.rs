@@ -1,4 +1,5 @@
 use regex::Regex;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use validator::{Validate, ValidationError};
 
@@ -13,7 +14,7 @@ fn validate_email(email: &str) -> Result<(), ValidationError> {
     }
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize, Validate)]
+#[derive(Debug, Clone, Deserialize, Serialize, Validate, JsonSchema)]
 pub struct V1LoginPayload {
     #[validate(email)]
     pub email: String,
@@ -21,7 +22,7 @@ pub struct V1LoginPayload {
     pub password: String,
 }
 
-#[derive(Debug, Deserialize, Serialize, Validate)]
+#[derive(Debug, Deserialize, Serialize, Validate, JsonSchema)]
 pub struct V1RegisterPayload {
     #[validate(length(min = 1))]
     pub name: String,
@@ -42,7 +43,7 @@ impl V1RegisterPayload {
     }
 }
 
-#[derive(Debug, Deserialize, Serialize, Validate)]
+#[derive(Debug, Deserialize, Serialize, Validate, JsonSchema)]
 pub struct V1TwoFAVerifyPayload {
     #[validate(length(min = 6, max = 12))]
     pub code: String,
@@ -50,13 +51,13 @@ pub struct V1TwoFAVerifyPayload {
     pub backup_code: Option<String>,
 }
 
-#[derive(Debug, Deserialize, Serialize, Validate)]
+#[derive(Debug, Deserialize, Serialize, Validate, JsonSchema)]
 pub struct V1TwoFADisablePayload {
     #[validate(length(min = 6, max = 64))]
     pub code: Option<String>,
 }
 
-#[derive(Debug, Deserialize, Serialize, Validate)]
+#[derive(Debug, Deserialize, Serialize, Validate, JsonSchema)]
 pub struct V1TerminateSessionPath {
     pub id: i32,
 }
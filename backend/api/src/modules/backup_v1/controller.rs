@@ -0,0 +1,288 @@
+use axum::{
+    extract::{Path, State},
+    http::{header, StatusCode},
+    response::IntoResponse,
+    Json,
+};
+use axum_macros::debug_handler;
+use bytes::Bytes;
+use serde_json::json;
+use tracing::{error, info, instrument};
+
+use crate::{
+    db::sea_models::{
+        backup_job::{self, BackupJobKind, BackupJobStatus},
+        job_schedule,
+    },
+    error::{ErrorCode, ErrorResponse},
+    extractors::{ValidatedJson, ValidatedMultipart},
+    services::{auth::AuthSession, backup, jobs, media_storage, settings},
+    AppState,
+};
+
+use super::validator::{
+    V1BackupListQuery, V1ExportRequestPayload, V1RestoreFromJobPayload, V1SchedulePayload,
+};
+
+/// Start a full export in the background
+#[debug_handler]
+#[instrument(
+    skip(state, auth, payload),
+    fields(user_id, job_id, include_media, encrypted)
+)]
+pub async fn export(
+    State(state): State<AppState>,
+    auth: AuthSession,
+    payload: ValidatedJson<V1ExportRequestPayload>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let user = auth.user.unwrap();
+    let payload = payload.0;
+    tracing::Span::current().record("user_id", user.id);
+    tracing::Span::current().record("include_media", payload.include_media);
+    tracing::Span::current().record("encrypted", payload.passphrase.is_some());
+
+    let job = backup::start_export(
+        &state,
+        backup::ExportOptions {
+            include_media: payload.include_media,
+            passphrase: payload.passphrase,
+            requested_by: Some(user.id),
+        },
+    )
+    .await?;
+
+    tracing::Span::current().record("job_id", job.id);
+    info!(job_id = job.id, "Backup export queued");
+    Ok((StatusCode::ACCEPTED, Json(json!(job))))
+}
+
+/// Check the status (and report) of a backup job
+#[debug_handler]
+#[instrument(skip(state))]
+pub async fn status(
+    State(state): State<AppState>,
+    Path(job_id): Path<i32>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let job = find_job(&state, job_id).await?;
+    Ok((StatusCode::OK, Json(json!(job))))
+}
+
+/// List export and restore jobs, newest first
+#[debug_handler]
+#[instrument(skip(state, payload))]
+pub async fn list(
+    State(state): State<AppState>,
+    payload: ValidatedJson<V1BackupListQuery>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let query = payload.0.into_query();
+    let page = query.page.unwrap_or(1);
+
+    let (items, total) = backup_job::Entity::list(&state.sea_db, query).await?;
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "data": items,
+            "total": total,
+            "per_page": backup_job::Entity::PER_PAGE,
+            "page": page,
+        })),
+    ))
+}
+
+/// Download the archive of a completed export
+#[debug_handler]
+#[instrument(skip(state), fields(size_bytes))]
+pub async fn download(
+    State(state): State<AppState>,
+    Path(job_id): Path<i32>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let job = completed_export(&state, job_id).await?;
+    let key = job.object_key.as_deref().unwrap_or_default();
+
+    let bytes = media_storage::get_object(&state, key).await?;
+    tracing::Span::current().record("size_bytes", bytes.len());
+
+    let content_type = if job.encrypted {
+        "application/octet-stream"
+    } else {
+        "application/zip"
+    };
+    Ok((
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, content_type.to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!(
+                    "attachment; filename=\"{}\"",
+                    backup::export::file_name(&job)
+                ),
+            ),
+        ],
+        bytes,
+    ))
+}
+
+/// Configure periodic exports (the `backup.schedule` job schedule)
+#[debug_handler]
+#[instrument(skip(state, payload), fields(cron = %payload.cron))]
+pub async fn schedule(
+    State(state): State<AppState>,
+    payload: ValidatedJson<V1SchedulePayload>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let payload = payload.0;
+
+    if payload.encrypt && settings::BACKUP_PASSPHRASE.value(&state).await.is_none() {
+        return Err(
+            ErrorResponse::new(ErrorCode::ConfigurationError).with_message(format!(
+                "Encrypted schedules require the {} setting",
+                settings::BACKUP_PASSPHRASE.key
+            )),
+        );
+    }
+
+    let next_run_at = if payload.enabled {
+        jobs::next_run(&payload.cron, chrono::Utc::now())
+            .map(|next| Some(next.fixed_offset()))
+            .map_err(|err| {
+                ErrorResponse::new(ErrorCode::InvalidValue)
+                    .with_message("Invalid cron expression")
+                    .with_details(err)
+            })?
+    } else {
+        None
+    };
+
+    let schedule = job_schedule::Entity::upsert(
+        &state.sea_db,
+        jobs::schedule(
+            backup::SCHEDULE_NAME,
+            &payload.cron,
+            &backup::BackupSchedule {
+                include_media: payload.include_media,
+                encrypt: payload.encrypt,
+                retain: payload.retain.unwrap_or(7),
+            },
+        ),
+        payload.enabled,
+        next_run_at,
+    )
+    .await?;
+
+    Ok((StatusCode::OK, Json(json!(schedule))))
+}
+
+/// View the current backup schedule
+#[debug_handler]
+#[instrument(skip(state))]
+pub async fn schedule_view(
+    State(state): State<AppState>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let schedule = job_schedule::Entity::get_by_name(&state.sea_db, backup::SCHEDULE_NAME).await?;
+    Ok((StatusCode::OK, Json(json!(schedule))))
+}
+
+/// Restore from an uploaded archive (multipart `file`, optional `passphrase`)
+#[debug_handler]
+#[instrument(skip(state, auth, multipart), fields(user_id, job_id, file_size))]
+pub async fn restore_upload(
+    State(state): State<AppState>,
+    auth: AuthSession,
+    mut multipart: ValidatedMultipart,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let user = auth.user.unwrap();
+    tracing::Span::current().record("user_id", user.id);
+
+    let mut file: Option<Bytes> = None;
+    let mut passphrase: Option<String> = None;
+
+    while let Some(field) = multipart.next_field().await.map_err(|err| {
+        error!(error = %err, "Failed to read multipart field");
+        ErrorResponse::new(ErrorCode::ValidationError).with_details(err.to_string())
+    })? {
+        match field.name().unwrap_or_default() {
+            "file" => {
+                file = Some(field.bytes().await.map_err(|err| {
+                    ErrorResponse::new(ErrorCode::FileUploadError)
+                        .with_message("Failed to read uploaded file")
+                        .with_details(err.to_string())
+                })?);
+            }
+            "passphrase" => {
+                let value = field.text().await.map_err(|err| {
+                    ErrorResponse::new(ErrorCode::InvalidFormat)
+                        .with_message("Failed to read passphrase field")
+                        .with_details(err.to_string())
+                })?;
+                passphrase = Some(value).filter(|v| !v.is_empty());
+            }
+            _ => {}
+        }
+    }
+
+    let file = file.ok_or_else(|| {
+        ErrorResponse::new(ErrorCode::MissingRequiredField).with_message("Missing file field")
+    })?;
+    tracing::Span::current().record("file_size", file.len());
+
+    let job = backup::start_restore(
+        &state,
+        backup::RestoreSource::Upload(file),
+        passphrase,
+        Some(user.id),
+    )
+    .await?;
+
+    tracing::Span::current().record("job_id", job.id);
+    info!(job_id = job.id, "Backup restore queued");
+    Ok((StatusCode::ACCEPTED, Json(json!(job))))
+}
+
+/// Restore from the archive of a completed export
+#[debug_handler]
+#[instrument(skip(state, auth, payload), fields(user_id, job_id))]
+pub async fn restore_from_job(
+    State(state): State<AppState>,
+    auth: AuthSession,
+    Path(source_job_id): Path<i32>,
+    payload: ValidatedJson<V1RestoreFromJobPayload>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let user = auth.user.unwrap();
+    tracing::Span::current().record("user_id", user.id);
+
+    let source = completed_export(&state, source_job_id).await?;
+    let job = backup::start_restore(
+        &state,
+        backup::RestoreSource::Job(source),
+        payload.0.passphrase,
+        Some(user.id),
+    )
+    .await?;
+
+    tracing::Span::current().record("job_id", job.id);
+    info!(job_id = job.id, source_job_id, "Backup restore queued");
+    Ok((StatusCode::ACCEPTED, Json(json!(job))))
+}
+
+async fn find_job(state: &AppState, job_id: i32) -> Result<backup_job::Model, ErrorResponse> {
+    backup_job::Entity::find_by_id(&state.sea_db, job_id)
+        .await?
+        .ok_or_else(|| {
+            ErrorResponse::new(ErrorCode::RecordNotFound).with_message("Backup job not found")
+        })
+}
+
+async fn completed_export(
+    state: &AppState,
+    job_id: i32,
+) -> Result<backup_job::Model, ErrorResponse> {
+    let job = find_job(state, job_id).await?;
+    if job.kind != BackupJobKind::Export
+        || job.status != BackupJobStatus::Completed
+        || job.object_key.is_none()
+    {
+        return Err(ErrorResponse::new(ErrorCode::OperationNotAllowed)
+            .with_message("Backup job has no downloadable archive"));
+    }
+    Ok(job)
+}
@@ -0,0 +1,33 @@
+pub mod controller;
+pub mod validator;
+
+use axum::{
+    extract::DefaultBodyLimit,
+    middleware,
+    routing::{get, post},
+    Router,
+};
+
+use crate::{config, middlewares::auth_guard, services::permission_service, AppState};
+
+pub fn routes() -> Router<AppState> {
+    let restore_limited = Router::<AppState>::new()
+        .route("/restore", post(controller::restore_upload))
+        .layer(DefaultBodyLimit::max(config::body_limits::RESTORE));
+
+    Router::<AppState>::new()
+        .route("/export", post(controller::export))
+        .route("/status/{job_id}", post(controller::status))
+        .route("/list", post(controller::list))
+        .route(
+            "/download/{job_id}",
+            get(controller::download).post(controller::download),
+        )
+        .route("/schedule", post(controller::schedule))
+        .route("/schedule/view", post(controller::schedule_view))
+        .route("/restore/{job_id}", post(controller::restore_from_job))
+        .merge(restore_limited)
+        .route_layer(middleware::from_fn(auth_guard::verified_with_permission(
+            permission_service::BACKUP_MANAGE,
+        )))
+}
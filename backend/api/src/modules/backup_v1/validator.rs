@@ -0,0 +1,67 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use validator::{Validate, ValidationError};
+
+use crate::db::sea_models::backup_job::{BackupJobKind, BackupJobQuery, BackupJobStatus};
+use crate::services::jobs;
+
+fn validate_cron(cron: &str) -> Result<(), ValidationError> {
+    jobs::next_run(cron, chrono::Utc::now())
+        .map(|_| ())
+        .map_err(|_| ValidationError::new("cron"))
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, Validate, JsonSchema)]
+pub struct V1ExportRequestPayload {
+    /// Bundle original and variant files alongside the media metadata.
+    #[serde(default)]
+    pub include_media: bool,
+    /// Encrypt the archive; the same passphrase is required to restore it.
+    #[validate(length(min = 8, max = 1024))]
+    #[serde(default, skip_serializing)]
+    pub passphrase: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Validate, JsonSchema)]
+#[schemars(rename = "V1BackupSchedulePayload")]
+pub struct V1SchedulePayload {
+    #[validate(custom(function = "validate_cron"))]
+    pub cron: String,
+    #[serde(default)]
+    pub include_media: bool,
+    /// Encrypt scheduled archives with the `BACKUP_PASSPHRASE` setting.
+    #[serde(default)]
+    pub encrypt: bool,
+    #[validate(range(min = 1, max = 365))]
+    pub retain: Option<i32>,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, Validate, JsonSchema)]
+pub struct V1BackupListQuery {
+    pub page: Option<u64>,
+    pub kind: Option<BackupJobKind>,
+    pub status: Option<BackupJobStatus>,
+}
+
+impl V1BackupListQuery {
+    pub fn into_query(self) -> BackupJobQuery {
+        BackupJobQuery {
+            page: self.page,
+            kind: self.kind,
+            status: self.status,
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, Validate, JsonSchema)]
+pub struct V1RestoreFromJobPayload {
+    #[validate(length(min = 1, max = 1024))]
+    #[serde(default, skip_serializing)]
+    pub passphrase: Option<String>,
+}
@@ -9,14 +9,20 @@ use serde_json::json;
 use tracing::{error, info, instrument, warn};
 
 use crate::{
-    db::sea_models::category::Entity as Category,
+    db::sea_models::{
+        category::{build_tree, Entity as Category},
+        slug_redirect::redirect_hit,
+    },
     error::{ErrorCode, ErrorResponse},
     extractors::ValidatedJson,
     services::auth::AuthSession,
     AppState,
 };
 
-use super::validator::{V1CategoryQueryParams, V1CreateCategoryPayload, V1UpdateCategoryPayload};
+use super::validator::{
+    V1CategoryQueryParams, V1CreateCategoryPayload, V1DeleteCategoryPayload,
+    V1UpdateCategoryPayload,
+};
 
 /// Create a new category using SeaORM
 #[debug_handler]
@@ -71,13 +77,16 @@ pub async fn update(
 
 /// Delete a category using SeaORM
 #[debug_handler]
-#[instrument(skip(state, _auth), fields(category_id))]
+#[instrument(skip(state, _auth, payload), fields(category_id))]
 pub async fn delete(
     State(state): State<AppState>,
     _auth: AuthSession,
     Path(category_id): Path<i32>,
+    payload: Option<ValidatedJson<V1DeleteCategoryPayload>>,
 ) -> Result<impl IntoResponse, ErrorResponse> {
-    match Category::delete(&state.sea_db, category_id).await {
+    let children = payload.and_then(|p| p.0.children).unwrap_or_default();
+
+    match Category::delete(&state.sea_db, category_id, children).await {
         Ok(1) => {
             info!(category_id, "Category deleted");
             Ok((
@@ -119,7 +128,7 @@ pub async fn find_by_id_or_slug(
             id = Some(parsed_id);
         }
         Err(_) => {
-            slug = Some(slug_or_id);
+            slug = Some(slug_or_id.clone());
         }
     }
 
@@ -130,7 +139,11 @@ pub async fn find_by_id_or_slug(
                 category_id = category.id,
                 "Category retrieved by id or slug"
             );
-            Ok((StatusCode::OK, Json(json!(category))))
+            let mut body = json!(category);
+            if let Some(hit) = redirect_hit(&slug_or_id, &category.slug) {
+                body["redirect"] = json!(hit);
+            }
+            Ok((StatusCode::OK, Json(body)))
         }
         Ok(None) => {
             warn!("Category not found");
@@ -188,3 +201,64 @@ pub async fn find_with_query(
         }
     }
 }
+
+/// Resolve a `{category_id}` path segment (id or slug, following slug
+/// redirects) to a category id.
+async fn resolve_category_id(state: &AppState, slug_or_id: String) -> Result<i32, ErrorResponse> {
+    let (id, slug) = match slug_or_id.parse::<i32>() {
+        Ok(id) => (Some(id), None),
+        Err(_) => (None, Some(slug_or_id)),
+    };
+    match Category::find_by_id_or_slug(&state.sea_db, id, slug).await? {
+        Some(category) => Ok(category.id),
+        None => {
+            Err(ErrorResponse::new(ErrorCode::RecordNotFound).with_message("Category not found"))
+        }
+    }
+}
+
+/// The whole category tree, roots first
+#[debug_handler]
+#[instrument(skip(state))]
+pub async fn tree(State(state): State<AppState>) -> Result<impl IntoResponse, ErrorResponse> {
+    match Category::tree_rows(&state.sea_db, None).await {
+        Ok(rows) => Ok((StatusCode::OK, Json(json!(build_tree(rows))))),
+        Err(err) => {
+            error!("Failed to load category tree: {}", err);
+            Err(err)
+        }
+    }
+}
+
+/// A category with everything below it
+#[debug_handler]
+#[instrument(skip(state), fields(slug_or_id = %slug_or_id, category_id))]
+pub async fn subtree(
+    State(state): State<AppState>,
+    Path(slug_or_id): Path<String>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let category_id = resolve_category_id(&state, slug_or_id).await?;
+    tracing::Span::current().record("category_id", category_id);
+
+    let rows = Category::tree_rows(&state.sea_db, Some(category_id)).await?;
+    match build_tree(rows).into_iter().next() {
+        Some(root) => Ok((StatusCode::OK, Json(json!(root)))),
+        None => {
+            Err(ErrorResponse::new(ErrorCode::RecordNotFound).with_message("Category not found"))
+        }
+    }
+}
+
+/// Breadcrumb path from the root down to a category
+#[debug_handler]
+#[instrument(skip(state), fields(slug_or_id = %slug_or_id, category_id))]
+pub async fn path(
+    State(state): State<AppState>,
+    Path(slug_or_id): Path<String>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let category_id = resolve_category_id(&state, slug_or_id).await?;
+    tracing::Span::current().record("category_id", category_id);
+
+    let rows = Category::ancestors(&state.sea_db, category_id).await?;
+    Ok((StatusCode::OK, Json(json!(rows))))
+}
@@ -7,7 +7,7 @@ use axum::{
     Router,
 };
 
-use crate::{middlewares::auth_guard, AppState};
+use crate::{middlewares::auth_guard, services::permission_service, AppState};
 
 pub fn routes() -> Router<AppState> {
     let admin = Router::<AppState>::new()
@@ -15,11 +15,16 @@ pub fn routes() -> Router<AppState> {
         .route("/update/{category_id}", post(controller::update))
         .route("/delete/{category_id}", post(controller::delete))
         .route("/list/query", post(controller::find_with_query))
-        .route_layer(middleware::from_fn(auth_guard::verified_with_role::<{ auth_guard::ROLE_ADMIN }>));
+        .route_layer(middleware::from_fn(auth_guard::verified_with_permission(
+            permission_service::TAXONOMY_MANAGE,
+        )));
 
     let public = Router::<AppState>::new()
         .route("/list", get(controller::find_all))
-        .route("/view/{category_id}", get(controller::find_by_id_or_slug));
+        .route("/view/{category_id}", get(controller::find_by_id_or_slug))
+        .route("/tree", get(controller::tree))
+        .route("/tree/{category_id}", get(controller::subtree))
+        .route("/path/{category_id}", get(controller::path));
 
     admin.merge(public)
 }
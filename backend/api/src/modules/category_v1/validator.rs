@@ -1,12 +1,13 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use validator::Validate;
 
 use crate::{
-    db::sea_models::category::{CategoryQuery, NewCategory, UpdateCategory},
-    utils::SortParam,
+    db::sea_models::category::{CategoryQuery, ChildPolicy, NewCategory, UpdateCategory},
+    utils::{normalize_locale, validate_locale, SortParam},
 };
 
-#[derive(Debug, Deserialize, Serialize, Validate)]
+#[derive(Debug, Deserialize, Serialize, Validate, JsonSchema)]
 pub struct V1CreateCategoryPayload {
     #[validate(length(min = 1, max = 255))]
     pub name: String,
@@ -22,6 +23,9 @@ pub struct V1CreateCategoryPayload {
     #[validate(custom(function = "validate_hex_color"), skip)]
     pub text_color: Option<String>,
     pub is_active: Option<bool>,
+    /// Leave out to share the category across every locale.
+    #[validate(custom(function = "validate_locale"))]
+    pub locale: Option<String>,
 }
 
 impl V1CreateCategoryPayload {
@@ -36,11 +40,12 @@ impl V1CreateCategoryPayload {
             color: Some(self.color),
             text_color: self.text_color,
             is_active: self.is_active,
+            locale: self.locale.as_deref().and_then(normalize_locale),
         }
     }
 }
 
-#[derive(Debug, Deserialize, Serialize, Validate)]
+#[derive(Debug, Deserialize, Serialize, Validate, JsonSchema)]
 pub struct V1UpdateCategoryPayload {
     #[validate(length(min = 1, max = 255))]
     pub name: Option<String>,
@@ -56,6 +61,8 @@ pub struct V1UpdateCategoryPayload {
     #[validate(custom(function = "validate_hex_color"), skip)]
     pub text_color: Option<String>,
     pub is_active: Option<bool>,
+    #[validate(custom(function = "validate_locale"))]
+    pub locale: Option<Option<String>>,
 }
 
 impl V1UpdateCategoryPayload {
@@ -70,18 +77,23 @@ impl V1UpdateCategoryPayload {
             color: self.color,
             text_color: self.text_color,
             is_active: self.is_active,
+            locale: self
+                .locale
+                .map(|locale| locale.as_deref().and_then(normalize_locale)),
             updated_at: chrono::Utc::now().fixed_offset(),
         }
     }
 }
 
-#[derive(Debug, Deserialize, Serialize, Validate)]
+#[derive(Debug, Deserialize, Serialize, Validate, JsonSchema)]
 pub struct V1CategoryQueryParams {
     pub page: Option<u64>,
     pub search: Option<String>,
     pub sorts: Option<Vec<SortParam>>,
     pub parent_id: Option<i32>,
     pub is_active: Option<bool>,
+    #[validate(custom(function = "validate_locale"))]
+    pub locale: Option<String>,
     pub created_at_gt: Option<chrono::DateTime<chrono::FixedOffset>>,
     pub created_at_lt: Option<chrono::DateTime<chrono::FixedOffset>>,
     pub updated_at_gt: Option<chrono::DateTime<chrono::FixedOffset>>,
@@ -96,6 +108,7 @@ impl V1CategoryQueryParams {
             sorts: self.sorts,
             parent_id: self.parent_id,
             is_active: self.is_active,
+            locale: self.locale.as_deref().and_then(normalize_locale),
             created_at_gt: self.created_at_gt,
             created_at_lt: self.created_at_lt,
             updated_at_gt: self.updated_at_gt,
@@ -103,3 +116,10 @@ impl V1CategoryQueryParams {
         }
     }
 }
+
+#[derive(Debug, Default, Deserialize, Serialize, Validate, JsonSchema)]
+pub struct V1DeleteCategoryPayload {
+    /// `block` (default) refuses to delete a category with children;
+    /// `reparent` moves them up to its parent.
+    pub children: Option<ChildPolicy>,
+}
@@ -0,0 +1,660 @@
+//! Rust types for `crates/ruxlog-client`, generated from the OpenAPI
+//! document so the client can't drift from the API.
+//!
+//! [`CLIENT_SCHEMAS`] lists the schemas the client uses; everything they
+//! reference is generated with them. `scripts/generate_client_dtos.rs`
+//! writes [`client_dtos`] to `crates/ruxlog-client/src/dto/generated.rs`,
+//! and `tests/client_dtos.rs` fails when that file is out of date.
+//!
+//! Mapping:
+//! - objects become structs; optional properties become `Option`s that are
+//!   left out when unset, unless their default is the type's own default
+//! - string enums become Rust enums with an `as_str`; an enum declared
+//!   inline is named after its struct and property (`SortParam.order` is
+//!   `SortParamOrder`)
+//! - `{T}Page` schemas are aliases of the client's generic `Page<T>`
+
+use std::collections::BTreeMap;
+
+use serde_json::{Map, Value};
+
+const REF_PREFIX: &str = "#/components/schemas/";
+
+/// Schemas the client sends or receives.
+pub const CLIENT_SCHEMAS: &[&str] = &[
+    // auth_v1, email_verification_v1, forgot_password_v1
+    "Message",
+    "V1RegisterPayload",
+    "V1LoginPayload",
+    "V1TwoFAVerifyPayload",
+    "V1TwoFADisablePayload",
+    "UserSessionPage",
+    "V1VerifyPayload",
+    "V1GeneratePayload",
+    "V1ForgotPasswordVerifyPayload",
+    "V1ResetPayload",
+    "TwoFASetup",
+    // user_v1
+    "User",
+    "UserWithRelations",
+    "UserWithRelationsPage",
+    "V1UpdateProfilePayload",
+    "V1AdminCreateUserPayload",
+    "V1AdminUpdateUserPayload",
+    "V1AdminUserQueryParams",
+    // post_v1
+    "PostWithRelations",
+    "PostWithRelationsPage",
+    "V1CreatePostPayload",
+    "V1UpdatePostPayload",
+    "V1AutosavePayload",
+    "V1PostQueryParams",
+    "V1PostViewQuery",
+    "V1PostUnlockPayload",
+    "RenderFormat",
+    "LikeStatus",
+    "LikeStatusBatchRequest",
+    "LikeStatusBatchResponse",
+    "LikeActionResponse",
+    "V1SchedulePayload",
+    "ScheduledPost",
+    "PostSitemap",
+    "PostSeries",
+    "V1SeriesCreatePayload",
+    "V1SeriesUpdatePayload",
+    "V1SeriesListQuery",
+    "PostSeriesList",
+    // post_comment_v1
+    "PostComment",
+    "CommentWithUserPage",
+    "V1CreatePostCommentPayload",
+    "V1UpdatePostCommentPayload",
+    "V1FlagCommentPayload",
+    "V1AdminPostCommentListQuery",
+    "V1CommentLikeStatusPayload",
+    "CommentLikeStatus",
+    "CommentLikeResponse",
+    // category_v1
+    "Category",
+    "CategoryWithRelationsPage",
+    "CategoryNode",
+    "V1CreateCategoryPayload",
+    "V1UpdateCategoryPayload",
+    "V1DeleteCategoryPayload",
+    "V1CategoryQueryParams",
+    // tag_v1
+    "Tag",
+    "TagPage",
+    "V1CreateTagPayload",
+    "V1UpdateTagPayload",
+    "V1TagQueryParams",
+    // media_v1
+    "Media",
+    "MediaWithUsagePage",
+    "MediaUploadMetadata",
+    "V1MediaListQuery",
+    // newsletter_v1
+    "V1SubscribePayload",
+    "V1UnsubscribePayload",
+    // notification_v1
+    "NotificationPage",
+    "NotificationPreference",
+    "V1NotificationListQuery",
+    "V1MarkReadPayload",
+    "MarkReadResponse",
+    "UnreadCount",
+    "V1UpdatePreferencesPayload",
+];
+
+/// Source of the client's `dto/generated.rs` for `document`.
+pub fn client_dtos(document: &Value) -> String {
+    let schemas = document["components"]["schemas"]
+        .as_object()
+        .expect("components.schemas");
+    let mut generator = Generator {
+        schemas,
+        queue: CLIENT_SCHEMAS
+            .iter()
+            .rev()
+            .map(|name| name.to_string())
+            .collect(),
+        items: BTreeMap::new(),
+        uses_date_time: false,
+        uses_date: false,
+    };
+    while let Some(name) = generator.queue.pop() {
+        if !generator.items.contains_key(&name) {
+            let item = generator.item(&name);
+            generator.items.insert(name, item);
+        }
+    }
+
+    let mut chrono = Vec::new();
+    if generator.uses_date_time {
+        chrono.extend(["DateTime", "FixedOffset"]);
+    }
+    if generator.uses_date {
+        chrono.push("NaiveDate");
+    }
+
+    let mut out = String::from(
+        "//! Generated from the backend's OpenAPI document by\n\
+         //! `cargo run --bin generate_client_dtos`; don't edit by hand.\n\n",
+    );
+    match chrono.as_slice() {
+        [] => {}
+        [one] => out.push_str(&format!("use chrono::{};\n", one)),
+        many => out.push_str(&format!("use chrono::{{{}}};\n", many.join(", "))),
+    }
+    out.push_str("use serde::{Deserialize, Serialize};\n\nuse super::Page;\n");
+    for item in generator.items.values() {
+        out.push('\n');
+        out.push_str(item);
+    }
+    out
+}
+
+struct Generator<'a> {
+    schemas: &'a Map<String, Value>,
+    queue: Vec<String>,
+    /// Name => source, emitted in name order.
+    items: BTreeMap<String, String>,
+    uses_date_time: bool,
+    uses_date: bool,
+}
+
+/// A property's Rust type.
+struct FieldType {
+    rust: String,
+    nullable: bool,
+    /// Implements `Default`.
+    defaultable: bool,
+}
+
+impl Generator<'_> {
+    fn schema(&self, name: &str) -> &Value {
+        self.schemas
+            .get(name)
+            .unwrap_or_else(|| panic!("no schema named {}", name))
+    }
+
+    fn item(&mut self, name: &str) -> String {
+        let schema = self.schema(name).clone();
+        if let Some(inner) = page_of(&schema) {
+            self.queue.push(inner.to_string());
+            return format!("{}pub type {} = Page<{}>;\n", doc(&schema, ""), name, inner);
+        }
+        if let Some(variants) = enum_variants(&schema) {
+            return enum_item(name, &schema, &variants);
+        }
+        if let Some(properties) = schema.get("properties").and_then(Value::as_object) {
+            return self.struct_item(name, &schema, properties);
+        }
+        panic!("{}: only objects and string enums are supported", name);
+    }
+
+    fn struct_item(
+        &mut self,
+        name: &str,
+        schema: &Value,
+        properties: &Map<String, Value>,
+    ) -> String {
+        let required: Vec<&str> = schema
+            .get("required")
+            .and_then(Value::as_array)
+            .map(|required| required.iter().filter_map(Value::as_str).collect())
+            .unwrap_or_default();
+
+        let mut fields = String::new();
+        let mut defaultable = true;
+        for (property, property_schema) in properties {
+            let field = self.field_type(name, property, property_schema);
+            let is_required = required.contains(&property.as_str());
+            let default = property_schema.get("default");
+
+            fields.push_str(&doc(property_schema, "    "));
+            let rust = if is_required {
+                if field.nullable {
+                    format!("Option<{}>", field.rust)
+                } else {
+                    defaultable &= field.defaultable;
+                    field.rust
+                }
+            } else if !field.nullable && field.defaultable && default.is_some_and(is_type_default) {
+                fields.push_str("    #[serde(default)]\n");
+                field.rust
+            } else {
+                fields
+                    .push_str("    #[serde(default, skip_serializing_if = \"Option::is_none\")]\n");
+                format!("Option<{}>", field.rust)
+            };
+            fields.push_str(&format!("    pub {}: {},\n", field_ident(property), rust));
+        }
+
+        let derive = if defaultable {
+            "Debug, Clone, Default, PartialEq, Serialize, Deserialize"
+        } else {
+            "Debug, Clone, PartialEq, Serialize, Deserialize"
+        };
+        format!(
+            "{}#[derive({})]\npub struct {} {{\n{}}}\n",
+            doc(schema, ""),
+            derive,
+            name,
+            fields
+        )
+    }
+
+    fn field_type(&mut self, owner: &str, property: &str, schema: &Value) -> FieldType {
+        let Some(object) = schema.as_object() else {
+            return plain("serde_json::Value");
+        };
+        let nullable = object.get("nullable") == Some(&Value::Bool(true));
+        let mut field = self.base_type(owner, property, object);
+        field.nullable |= nullable;
+        field
+    }
+
+    fn base_type(&mut self, owner: &str, property: &str, object: &Map<String, Value>) -> FieldType {
+        if let Some(reference) = object.get("$ref").and_then(Value::as_str) {
+            let name = reference
+                .strip_prefix(REF_PREFIX)
+                .unwrap_or_else(|| panic!("{}.{}: unexpected $ref {}", owner, property, reference));
+            self.queue.push(name.to_string());
+            return FieldType {
+                rust: name.to_string(),
+                nullable: false,
+                defaultable: false,
+            };
+        }
+
+        if let Some(branches) = object
+            .get("anyOf")
+            .or_else(|| object.get("oneOf"))
+            .and_then(Value::as_array)
+        {
+            let (nulls, rest): (Vec<&Value>, Vec<&Value>) =
+                branches.iter().partition(|branch| is_null(branch));
+            let [only] = rest.as_slice() else {
+                panic!(
+                    "{}.{}: unions other than `T | null` are unsupported",
+                    owner, property
+                );
+            };
+            let mut field = self.field_type(owner, property, only);
+            field.nullable |= !nulls.is_empty();
+            return field;
+        }
+
+        if let Some(all_of) = object.get("allOf").and_then(Value::as_array) {
+            let [only] = all_of.as_slice() else {
+                panic!(
+                    "{}.{}: allOf with several schemas is unsupported",
+                    owner, property
+                );
+            };
+            return self.field_type(owner, property, only);
+        }
+
+        if object.contains_key("enum") {
+            let name = format!("{}{}", owner, pascal_case(property));
+            let schema = Value::Object(object.clone());
+            let variants = enum_variants(&schema).unwrap_or_else(|| {
+                panic!("{}.{}: only string enums are supported", owner, property)
+            });
+            let item = enum_item(&name, &Value::Null, &variants);
+            self.items.insert(name.clone(), item);
+            return FieldType {
+                rust: name,
+                nullable: false,
+                defaultable: false,
+            };
+        }
+
+        match object.get("type").and_then(Value::as_str) {
+            Some("string") => match object.get("format").and_then(Value::as_str) {
+                Some("date-time") => {
+                    self.uses_date_time = true;
+                    FieldType {
+                        rust: "DateTime<FixedOffset>".to_string(),
+                        nullable: false,
+                        defaultable: false,
+                    }
+                }
+                Some("date") => {
+                    self.uses_date = true;
+                    FieldType {
+                        rust: "NaiveDate".to_string(),
+                        nullable: false,
+                        defaultable: false,
+                    }
+                }
+                _ => plain("String"),
+            },
+            Some("integer") => plain(match object.get("format").and_then(Value::as_str) {
+                Some("int8") => "i8",
+                Some("int16") => "i16",
+                Some("int32") => "i32",
+                Some("uint8") => "u8",
+                Some("uint16") => "u16",
+                Some("uint32") => "u32",
+                Some("uint64" | "uint") => "u64",
+                _ => "i64",
+            }),
+            Some("number") => plain(match object.get("format").and_then(Value::as_str) {
+                Some("float") => "f32",
+                _ => "f64",
+            }),
+            Some("boolean") => plain("bool"),
+            Some("array") => {
+                let items = object.get("items").cloned().unwrap_or(Value::Bool(true));
+                let item = self.field_type(owner, property, &items);
+                let item = if item.nullable {
+                    format!("Option<{}>", item.rust)
+                } else {
+                    item.rust
+                };
+                plain(&format!("Vec<{}>", item))
+            }
+            Some("object") if object.contains_key("properties") => {
+                panic!(
+                    "{}.{}: inline objects are unsupported; name the type",
+                    owner, property
+                )
+            }
+            Some("object") | None => plain("serde_json::Value"),
+            Some(other) => panic!("{}.{}: unsupported type {}", owner, property, other),
+        }
+    }
+}
+
+fn plain(rust: &str) -> FieldType {
+    FieldType {
+        rust: rust.to_string(),
+        nullable: false,
+        defaultable: true,
+    }
+}
+
+fn is_null(schema: &Value) -> bool {
+    schema.get("enum") == Some(&Value::Array(vec![Value::Null]))
+        || schema.get("type") == Some(&Value::String("null".to_string()))
+}
+
+/// Whether a schema default is what `Default::default()` gives anyway.
+fn is_type_default(value: &Value) -> bool {
+    match value {
+        Value::Bool(value) => !value,
+        Value::String(value) => value.is_empty(),
+        Value::Number(value) => value.as_f64() == Some(0.0),
+        Value::Array(values) => values.is_empty(),
+        _ => false,
+    }
+}
+
+/// `T` when `schema` is the `{data, total, per_page, page}` of a `Page<T>`.
+fn page_of(schema: &Value) -> Option<&str> {
+    let properties = schema.get("properties")?.as_object()?;
+    let mut keys: Vec<&str> = properties.keys().map(String::as_str).collect();
+    keys.sort_unstable();
+    if keys != ["data", "page", "per_page", "total"] {
+        return None;
+    }
+    properties["data"]["items"]["$ref"]
+        .as_str()?
+        .strip_prefix(REF_PREFIX)
+}
+
+/// Values of a string enum, with the description of each value documented
+/// on its own (`oneOf` of single-value enums).
+fn enum_variants(schema: &Value) -> Option<Vec<(String, Option<String>)>> {
+    let strings = |values: &Value| -> Option<Vec<String>> {
+        values
+            .as_array()?
+            .iter()
+            .map(|value| value.as_str().map(str::to_string))
+            .collect()
+    };
+    if let Some(values) = schema.get("enum") {
+        return Some(
+            strings(values)?
+                .into_iter()
+                .map(|value| (value, None))
+                .collect(),
+        );
+    }
+    let mut variants = Vec::new();
+    for branch in schema.get("oneOf")?.as_array()? {
+        let values = strings(branch.get("enum")?)?;
+        let description = branch
+            .get("description")
+            .and_then(Value::as_str)
+            .filter(|_| values.len() == 1)
+            .map(str::to_string);
+        variants.extend(values.into_iter().map(|value| (value, description.clone())));
+    }
+    Some(variants)
+}
+
+fn enum_item(name: &str, schema: &Value, variants: &[(String, Option<String>)]) -> String {
+    let names: Vec<String> = variants
+        .iter()
+        .map(|(value, _)| pascal_case(value))
+        .collect();
+    let rename_all = [
+        ("lowercase", to_lowercase as fn(&str) -> String),
+        ("snake_case", to_snake_case),
+        ("kebab-case", to_kebab_case),
+        ("SCREAMING_SNAKE_CASE", to_screaming_snake_case),
+    ]
+    .into_iter()
+    .find(|(_, convert)| {
+        names
+            .iter()
+            .zip(variants)
+            .all(|(name, (value, _))| convert(name) == *value)
+    })
+    .map(|(rule, _)| rule);
+    let verbatim = names
+        .iter()
+        .zip(variants)
+        .all(|(name, (value, _))| name == value);
+
+    let mut out = doc(schema, "");
+    out.push_str("#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]\n");
+    if let (Some(rule), false) = (rename_all, verbatim) {
+        out.push_str(&format!("#[serde(rename_all = \"{}\")]\n", rule));
+    }
+    out.push_str(&format!("pub enum {} {{\n", name));
+    for (variant, (value, description)) in names.iter().zip(variants) {
+        if let Some(description) = description {
+            out.push_str(&doc_lines(description, "    "));
+        }
+        if !verbatim && rename_all.is_none() {
+            out.push_str(&format!("    #[serde(rename = \"{}\")]\n", value));
+        }
+        out.push_str(&format!("    {},\n", variant));
+    }
+    out.push_str("}\n\n");
+
+    out.push_str(&format!(
+        "impl {} {{\n    pub fn as_str(&self) -> &'static str {{\n        match self {{\n",
+        name
+    ));
+    for (variant, (value, _)) in names.iter().zip(variants) {
+        out.push_str(&format!(
+            "            {}::{} => \"{}\",\n",
+            name, variant, value
+        ));
+    }
+    out.push_str("        }\n    }\n}\n");
+    out
+}
+
+fn doc(schema: &Value, indent: &str) -> String {
+    schema
+        .get("description")
+        .and_then(Value::as_str)
+        .map(|description| doc_lines(description, indent))
+        .unwrap_or_default()
+}
+
+fn doc_lines(description: &str, indent: &str) -> String {
+    description
+        .lines()
+        .map(|line| {
+            if line.trim().is_empty() {
+                format!("{}///\n", indent)
+            } else {
+                format!("{}/// {}\n", indent, line.trim_end())
+            }
+        })
+        .collect()
+}
+
+fn field_ident(property: &str) -> String {
+    const KEYWORDS: &[&str] = &[
+        "as", "async", "await", "box", "break", "const", "continue", "crate", "dyn", "else",
+        "enum", "extern", "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod",
+        "move", "mut", "pub", "ref", "return", "static", "struct", "trait", "true", "type",
+        "unsafe", "use", "where", "while", "yield",
+    ];
+    assert!(
+        property
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_'),
+        "property {} is not snake_case",
+        property
+    );
+    if KEYWORDS.contains(&property) {
+        format!("r#{}", property)
+    } else {
+        property.to_string()
+    }
+}
+
+/// `comment_reply` => `CommentReply`; already Pascal-cased values are kept.
+fn pascal_case(value: &str) -> String {
+    value
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            let first = chars.next().unwrap().to_ascii_uppercase();
+            std::iter::once(first).chain(chars).collect::<String>()
+        })
+        .collect()
+}
+
+fn to_lowercase(name: &str) -> String {
+    name.to_ascii_lowercase()
+}
+
+fn to_snake_case(name: &str) -> String {
+    let mut out = String::new();
+    for (index, c) in name.chars().enumerate() {
+        if c.is_ascii_uppercase() && index > 0 {
+            out.push('_');
+        }
+        out.push(c.to_ascii_lowercase());
+    }
+    out
+}
+
+fn to_kebab_case(name: &str) -> String {
+    to_snake_case(name).replace('_', "-")
+}
+
+fn to_screaming_snake_case(name: &str) -> String {
+    to_snake_case(name).to_ascii_uppercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn generate(schemas: Value, roots: &[&str]) -> String {
+        let schemas = schemas.as_object().unwrap();
+        let mut generator = Generator {
+            schemas,
+            queue: roots.iter().map(|name| name.to_string()).collect(),
+            items: BTreeMap::new(),
+            uses_date_time: false,
+            uses_date: false,
+        };
+        while let Some(name) = generator.queue.pop() {
+            if !generator.items.contains_key(&name) {
+                let item = generator.item(&name);
+                generator.items.insert(name, item);
+            }
+        }
+        generator.items.into_values().collect::<Vec<_>>().join("\n")
+    }
+
+    #[test]
+    fn objects_become_structs() {
+        let source = generate(
+            json!({
+                "Tag": {
+                    "description": "A tag.",
+                    "properties": {
+                        "id": { "type": "integer", "format": "int32" },
+                        "type": { "type": "string" },
+                        "note": { "type": "string", "nullable": true },
+                        "tags": { "type": "array", "items": { "type": "string" }, "default": [] },
+                        "at": { "type": "string", "format": "date-time" },
+                        "order": { "type": "string", "enum": ["asc", "desc"] },
+                    },
+                    "required": ["id", "type", "at"],
+                    "type": "object",
+                },
+            }),
+            &["Tag"],
+        );
+        assert!(source.contains("/// A tag.\n#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]\npub struct Tag {"));
+        assert!(source.contains("    pub id: i32,\n"));
+        assert!(source.contains("    pub r#type: String,\n"));
+        assert!(source.contains("    #[serde(default, skip_serializing_if = \"Option::is_none\")]\n    pub note: Option<String>,\n"));
+        assert!(source.contains("    #[serde(default)]\n    pub tags: Vec<String>,\n"));
+        assert!(source.contains("    pub at: DateTime<FixedOffset>,\n"));
+        assert!(source.contains("    pub order: Option<TagOrder>,\n"));
+        assert!(source.contains(
+            "#[serde(rename_all = \"lowercase\")]\npub enum TagOrder {\n    Asc,\n    Desc,\n}"
+        ));
+    }
+
+    #[test]
+    fn documented_enums_and_pages() {
+        let source = generate(
+            json!({
+                "Kind": {
+                    "oneOf": [
+                        { "enum": ["comment_reply"], "type": "string", "description": "A reply." },
+                        { "enum": ["post_published"], "type": "string" },
+                    ],
+                },
+                "Item": {
+                    "properties": { "kind": { "$ref": "#/components/schemas/Kind" } },
+                    "type": "object",
+                },
+                "ItemPage": {
+                    "properties": {
+                        "data": { "items": { "$ref": "#/components/schemas/Item" }, "type": "array" },
+                        "page": { "type": "integer" },
+                        "per_page": { "type": "integer" },
+                        "total": { "type": "integer" },
+                    },
+                    "type": "object",
+                },
+            }),
+            &["ItemPage"],
+        );
+        assert!(source.contains("pub type ItemPage = Page<Item>;"));
+        assert!(source.contains("#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]\npub struct Item {"));
+        assert!(source.contains(
+            "#[serde(rename_all = \"snake_case\")]\npub enum Kind {\n    /// A reply.\n    CommentReply,\n    PostPublished,\n}"
+        ));
+        assert!(source.contains("            Kind::CommentReply => \"comment_reply\",\n"));
+    }
+}
@@ -0,0 +1,70 @@
+use std::sync::OnceLock;
+
+use axum::{
+    extract::State,
+    http::header,
+    response::{Html, IntoResponse},
+    Json,
+};
+use axum_macros::debug_handler;
+use serde_json::Value;
+
+use super::spec;
+use crate::{
+    error::{ErrorCode, ErrorResponse},
+    services::settings,
+    AppState,
+};
+
+const SWAGGER_UI_VERSION: &str = "5.17.14";
+const CACHE_CONTROL: &str = "public, max-age=300";
+
+/// Built once per process; the routes can't change while it runs.
+static DOCUMENT: OnceLock<Value> = OnceLock::new();
+
+async fn ensure_enabled(state: &AppState) -> Result<(), ErrorResponse> {
+    if settings::API_DOCS_ENABLED.bool(state).await {
+        Ok(())
+    } else {
+        Err(ErrorResponse::new(ErrorCode::RecordNotFound).with_message("API docs are disabled"))
+    }
+}
+
+#[debug_handler]
+pub async fn openapi(State(state): State<AppState>) -> Result<impl IntoResponse, ErrorResponse> {
+    ensure_enabled(&state).await?;
+    let document = DOCUMENT.get_or_init(spec::document);
+    Ok((
+        [(header::CACHE_CONTROL, CACHE_CONTROL)],
+        Json(document.clone()),
+    ))
+}
+
+#[debug_handler]
+pub async fn swagger_ui(State(state): State<AppState>) -> Result<impl IntoResponse, ErrorResponse> {
+    ensure_enabled(&state).await?;
+    Ok(Html(format!(
+        r##"<!doctype html>
+<html lang="en">
+<head>
+  <meta charset="utf-8">
+  <meta name="viewport" content="width=device-width, initial-scale=1">
+  <title>Ruxlog API</title>
+  <link rel="stylesheet" href="https://unpkg.com/swagger-ui-dist@{version}/swagger-ui.css">
+</head>
+<body>
+  <div id="swagger-ui"></div>
+  <script src="https://unpkg.com/swagger-ui-dist@{version}/swagger-ui-bundle.js" crossorigin></script>
+  <script>
+    window.ui = SwaggerUIBundle({{
+      url: "/docs/openapi.json",
+      dom_id: "#swagger-ui",
+      withCredentials: true,
+    }});
+  </script>
+</body>
+</html>
+"##,
+        version = SWAGGER_UI_VERSION,
+    )))
+}
@@ -0,0 +1,13 @@
+pub mod client_codegen;
+pub mod controller;
+pub mod spec;
+
+use axum::{routing::get, Router};
+
+use crate::AppState;
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/", get(controller::swagger_ui))
+        .route("/openapi.json", get(controller::openapi))
+}
@@ -0,0 +1,1401 @@
+//! OpenAPI 3 document for the HTTP API.
+//!
+//! [`operations`] lists every route mounted by `router.rs` with the payload it
+//! validates and the type it answers with. Schemas come from the `JsonSchema`
+//! derives on those types, so request shapes follow the `V1*Payload`
+//! validators. `tests/openapi_routes.rs` fails when a mounted route has no
+//! entry here, or an entry no longer matches a route.
+
+use schemars::{generate::SchemaSettings, JsonSchema, Schema, SchemaGenerator};
+use serde_json::{json, Map, Value};
+
+use crate::{
+    db::sea_models::user::UserRole,
+    db::sea_models::{
+        audit_log, backup_job, category, comment_flag, comment_like, import_job, job, job_schedule,
+        media, newsletter_subscriber, notification, notification_preference, post, post_comment,
+        post_like, post_revision, post_series, post_series_post, scheduled_post, slug_redirect,
+        tag, user, user_session, webhook, webhook_delivery,
+    },
+    error::{ErrorCode, ErrorResponse},
+    modules::{
+        admin_acl_v1, admin_audit_v1, admin_job_v1, admin_permission_v1, admin_redirect_v1,
+        admin_route_v1, admin_webhook_v1, analytics_v1, auth_v1, backup_v1, category_v1,
+        email_verification_v1, feed_v1, forgot_password_v1, google_auth_v1, import_v1, media_v1,
+        newsletter_v1, notification_v1, post_comment_v1, post_v1, seed_v1, tag_v1, user_v1,
+    },
+    services::{permission_service as permission, post_lock, route_blocker_rules},
+};
+
+type SchemaFn = fn(&mut SchemaGenerator) -> Schema;
+
+fn schema<T: JsonSchema>(generator: &mut SchemaGenerator) -> Schema {
+    generator.subschema_for::<T>()
+}
+
+/// What a caller needs before the route answers, mirroring the guard layered
+/// on it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Access {
+    Public,
+    /// Only callers without a session (`auth_guard::unauthenticated`).
+    Guest,
+    Authenticated,
+    /// Signed in with an email that still needs verifying.
+    Unverified,
+    Verified,
+    Role(UserRole),
+    Permission(&'static str),
+}
+
+impl Access {
+    fn describe(self) -> Value {
+        match self {
+            Access::Public => json!({ "level": "public" }),
+            Access::Guest => json!({ "level": "guest" }),
+            Access::Authenticated => json!({ "level": "authenticated" }),
+            Access::Unverified => json!({ "level": "unverified" }),
+            Access::Verified => json!({ "level": "verified" }),
+            Access::Role(role) => json!({ "level": "role", "role": role.to_string() }),
+            Access::Permission(permission) => {
+                json!({ "level": "permission", "permission": permission })
+            }
+        }
+    }
+
+    fn needs_session(self) -> bool {
+        !matches!(self, Access::Public | Access::Guest)
+    }
+}
+
+/// `{data, total, per_page, page}` answered by the paginated list endpoints.
+#[derive(JsonSchema)]
+#[schemars(rename = "{T}Page")]
+pub struct Paginated<T> {
+    pub data: Vec<T>,
+    pub total: u64,
+    pub per_page: u64,
+    pub page: u64,
+}
+
+/// `{data}` answered by the unpaginated list endpoints.
+#[derive(JsonSchema)]
+#[schemars(rename = "{T}List")]
+pub struct DataList<T> {
+    pub data: Vec<T>,
+}
+
+/// `{message}` acknowledging an action.
+#[derive(JsonSchema)]
+pub struct Message {
+    pub message: String,
+}
+
+/// One method on one path.
+pub struct Operation {
+    pub method: &'static str,
+    pub path: &'static str,
+    pub summary: &'static str,
+    pub tag: &'static str,
+    access: Option<Access>,
+    body: Option<SchemaFn>,
+    body_required: bool,
+    /// Name of the file field when the body is `multipart/form-data`.
+    upload: Option<&'static str>,
+    query: Option<SchemaFn>,
+    status: u16,
+    returns: Option<SchemaFn>,
+    json: bool,
+    content: Vec<&'static str>,
+    string_path: bool,
+}
+
+impl Operation {
+    fn new(method: &'static str, path: &'static str, summary: &'static str) -> Self {
+        Self {
+            method,
+            path,
+            summary,
+            tag: "",
+            access: None,
+            body: None,
+            body_required: true,
+            upload: None,
+            query: None,
+            status: 200,
+            returns: None,
+            json: true,
+            content: Vec::new(),
+            string_path: false,
+        }
+    }
+
+    pub fn get(path: &'static str, summary: &'static str) -> Self {
+        Self::new("GET", path, summary)
+    }
+
+    pub fn post(path: &'static str, summary: &'static str) -> Self {
+        Self::new("POST", path, summary)
+    }
+
+    pub fn delete(path: &'static str, summary: &'static str) -> Self {
+        Self::new("DELETE", path, summary)
+    }
+
+    pub fn access(&self) -> Access {
+        self.access.unwrap_or(Access::Public)
+    }
+
+    /// Overrides the access of the section the operation is listed in.
+    fn guard(mut self, access: Access) -> Self {
+        self.access = Some(access);
+        self
+    }
+
+    fn body<T: JsonSchema>(mut self) -> Self {
+        self.body = Some(schema::<T>);
+        self
+    }
+
+    fn optional_body<T: JsonSchema>(mut self) -> Self {
+        self.body_required = false;
+        self.body::<T>()
+    }
+
+    /// `multipart/form-data` with a `file` field next to the fields of `T`.
+    fn multipart<T: JsonSchema>(mut self, file: &'static str) -> Self {
+        self.upload = Some(file);
+        self.body::<T>()
+    }
+
+    fn upload(mut self, file: &'static str) -> Self {
+        self.upload = Some(file);
+        self
+    }
+
+    fn query<T: JsonSchema>(mut self) -> Self {
+        self.query = Some(schema::<T>);
+        self
+    }
+
+    fn returns<T: JsonSchema>(mut self) -> Self {
+        self.returns = Some(schema::<T>);
+        self
+    }
+
+    fn status(mut self, status: u16) -> Self {
+        self.status = status;
+        self
+    }
+
+    /// Answers with `mime` instead of JSON.
+    fn content(mut self, mime: &'static str) -> Self {
+        self.json = false;
+        self.content.push(mime);
+        self
+    }
+
+    /// Answers with `mime` as well as JSON, depending on the request.
+    fn or_content(mut self, mime: &'static str) -> Self {
+        self.content.push(mime);
+        self
+    }
+
+    fn redirect(mut self) -> Self {
+        self.status = 307;
+        self.json = false;
+        self
+    }
+
+    /// Path parameters are ids or slugs rather than integer ids.
+    fn string_path(mut self) -> Self {
+        self.string_path = true;
+        self
+    }
+}
+
+fn section(tag: &'static str, access: Access, operations: Vec<Operation>) -> Vec<Operation> {
+    operations
+        .into_iter()
+        .map(|mut operation| {
+            operation.tag = tag;
+            operation.access.get_or_insert(access);
+            operation
+        })
+        .collect()
+}
+
+/// Every route the API mounts, grouped by module.
+pub fn operations() -> Vec<Operation> {
+    use Access::*;
+    use Operation as Op;
+    let author = Role(UserRole::Author);
+
+    let mut operations = section(
+        "meta",
+        Public,
+        vec![
+            Op::get("/healthz", "Liveness probe").status(204),
+            Op::get("/docs/openapi.json", "This OpenAPI document"),
+            Op::get("/docs", "Swagger UI").content("text/html"),
+        ],
+    );
+
+    operations.extend(section(
+        "auth",
+        Authenticated,
+        vec![
+            Op::post("/auth/v1/register", "Register an account")
+                .guard(Guest)
+                .body::<auth_v1::validator::V1RegisterPayload>()
+                .returns::<user::Model>()
+                .status(201),
+            Op::post("/auth/v1/log_in", "Log in")
+                .guard(Guest)
+                .body::<auth_v1::validator::V1LoginPayload>()
+                .returns::<user::Model>(),
+            Op::post("/auth/v1/log_out", "Log out").returns::<Message>(),
+            Op::post("/auth/v1/2fa/setup", "Start two-factor setup").returns::<user::TwoFASetup>(),
+            Op::post("/auth/v1/2fa/verify", "Confirm two-factor setup")
+                .body::<auth_v1::validator::V1TwoFAVerifyPayload>()
+                .returns::<user::Model>(),
+            Op::post("/auth/v1/2fa/disable", "Disable two-factor")
+                .body::<auth_v1::validator::V1TwoFADisablePayload>()
+                .returns::<user::Model>(),
+            Op::post("/auth/v1/sessions/list", "List your sessions")
+                .returns::<Paginated<user_session::Model>>(),
+            Op::post("/auth/v1/sessions/terminate/{id}", "Terminate a session")
+                .returns::<Message>(),
+        ],
+    ));
+
+    operations.extend(section(
+        "auth",
+        Public,
+        vec![
+            Op::get("/auth/google/v1/login", "Start Google sign-in").redirect(),
+            Op::get("/auth/google/v1/callback", "Google sign-in callback")
+                .query::<google_auth_v1::validator::GoogleCallbackQuery>()
+                .redirect(),
+            Op::post(
+                "/auth/google/v1/exchange",
+                "Exchange a Google code for a session",
+            )
+            .body::<google_auth_v1::validator::GoogleExchangeRequest>()
+            .returns::<user::Model>(),
+            Op::get("/auth/google/v1/user", "Google profile of the session")
+                .returns::<google_auth_v1::validator::GoogleUserInfo>(),
+        ],
+    ));
+
+    operations.extend(section(
+        "user",
+        Verified,
+        vec![
+            Op::post("/user/v1/update", "Update your profile")
+                .body::<user_v1::validator::V1UpdateProfilePayload>()
+                .returns::<user::Model>(),
+            Op::post("/user/v1/follow/{user_id}", "Follow an author"),
+            Op::post("/user/v1/unfollow/{user_id}", "Unfollow an author"),
+            Op::get("/user/v1/following", "Ids of the authors you follow"),
+            Op::get("/user/v1/get", "Your profile")
+                .guard(Authenticated)
+                .returns::<user::Model>(),
+        ],
+    ));
+
+    operations.extend(section(
+        "user",
+        Permission(permission::USER_MANAGE),
+        vec![
+            Op::post("/user/v1/admin/list", "List users")
+                .body::<user_v1::validator::V1AdminUserQueryParams>()
+                .returns::<Paginated<user::UserWithRelations>>(),
+            Op::post("/user/v1/admin/view/{user_id}", "View a user")
+                .returns::<user::UserWithRelations>(),
+            Op::post("/user/v1/admin/create", "Create a user")
+                .body::<user_v1::validator::V1AdminCreateUserPayload>()
+                .returns::<user::Model>()
+                .status(201),
+            Op::post("/user/v1/admin/update/{user_id}", "Update a user")
+                .body::<user_v1::validator::V1AdminUpdateUserPayload>()
+                .returns::<user::Model>(),
+            Op::post("/user/v1/admin/delete/{user_id}", "Delete a user").returns::<Message>(),
+        ],
+    ));
+
+    operations.extend(section(
+        "auth",
+        Unverified,
+        vec![
+            Op::post("/email_verification/v1/verify", "Verify your email")
+                .body::<email_verification_v1::validator::V1VerifyPayload>()
+                .returns::<Message>(),
+            Op::post(
+                "/email_verification/v1/resend",
+                "Resend the verification code",
+            )
+            .returns::<Message>(),
+        ],
+    ));
+
+    operations.extend(section(
+        "auth",
+        Guest,
+        vec![
+            Op::post("/forgot_password/v1/request", "Email a password reset code")
+                .body::<forgot_password_v1::validator::V1GeneratePayload>()
+                .returns::<Message>(),
+            Op::post("/forgot_password/v1/verify", "Check a password reset code")
+                .body::<forgot_password_v1::validator::V1VerifyPayload>()
+                .returns::<Message>(),
+            Op::post("/forgot_password/v1/reset", "Reset your password")
+                .body::<forgot_password_v1::validator::V1ResetPayload>()
+                .returns::<Message>(),
+        ],
+    ));
+
+    operations.extend(section(
+        "post",
+        author,
+        vec![
+            Op::post("/post/v1/create", "Create a post")
+                .body::<post_v1::validator::V1CreatePostPayload>()
+                .returns::<post::PostWithRelations>()
+                .status(201),
+            Op::post("/post/v1/update/{post_id}", "Update a post")
+                .body::<post_v1::validator::V1UpdatePostPayload>()
+                .returns::<post::PostWithRelations>(),
+            Op::post("/post/v1/autosave", "Autosave a draft")
+                .body::<post_v1::validator::V1AutosavePayload>(),
+            Op::post("/post/v1/import/markdown", "Import Markdown files")
+                .multipart::<post_v1::validator::V1MarkdownImportOptions>("files"),
+            Op::post("/post/v1/query", "Query posts")
+                .body::<post_v1::validator::V1PostQueryParams>()
+                .returns::<Paginated<post::PostWithRelations>>(),
+            Op::post("/post/v1/delete/{post_id}", "Delete a post").returns::<Message>(),
+            Op::post("/post/v1/revisions/{post_id}/list", "List revisions")
+                .returns::<Paginated<post_revision::Model>>(),
+            Op::post(
+                "/post/v1/revisions/{post_id}/restore/{revision_id}",
+                "Restore a revision",
+            )
+            .body::<post_v1::validator::V1RevisionRestorePayload>()
+            .returns::<post_revision::Model>(),
+            Op::post(
+                "/post/v1/revisions/{post_id}/diff/{from_id}/{to_id}",
+                "Diff two revisions",
+            ),
+            Op::post("/post/v1/schedule", "Schedule a post")
+                .body::<post_v1::validator::V1SchedulePayload>()
+                .returns::<scheduled_post::Model>(),
+            Op::post(
+                "/post/v1/review/{post_id}/transition",
+                "Move a post through review",
+            )
+            .body::<post_v1::validator::V1PostTransitionPayload>()
+            .returns::<post::PostWithRelations>(),
+            Op::post("/post/v1/review/{post_id}/history", "Review history"),
+            Op::post("/post/v1/review/{post_id}/notes", "List review notes")
+                .body::<post_v1::validator::V1ReviewNotesQuery>(),
+            Op::post(
+                "/post/v1/review/{post_id}/notes/create",
+                "Add a review note",
+            )
+            .body::<post_v1::validator::V1ReviewNoteCreatePayload>()
+            .status(201),
+            Op::post(
+                "/post/v1/review/{post_id}/notes/{note_id}/update",
+                "Update a review note",
+            )
+            .body::<post_v1::validator::V1ReviewNoteUpdatePayload>(),
+            Op::post(
+                "/post/v1/review/{post_id}/notes/{note_id}/delete",
+                "Delete a review note",
+            )
+            .returns::<Message>(),
+            Op::post("/post/v1/lock/{post_id}", "Acquire or renew the edit lock")
+                .body::<post_v1::validator::V1PostLockPayload>()
+                .returns::<post_lock::LockStatus>(),
+            Op::post("/post/v1/lock/{post_id}/status", "Edit lock status")
+                .returns::<post_lock::LockStatus>(),
+            Op::post("/post/v1/lock/{post_id}/release", "Release the edit lock"),
+            Op::get("/post/v1/export/{file}", "Export a post as Markdown")
+                .string_path()
+                .content("text/markdown"),
+            Op::post("/post/v1/export/{file}", "Export a post as Markdown")
+                .string_path()
+                .content("text/markdown"),
+            Op::post("/post/v1/series/create", "Create a series")
+                .body::<post_v1::validator::V1SeriesCreatePayload>()
+                .returns::<post_series::Model>()
+                .status(201),
+            Op::post("/post/v1/series/update/{series_id}", "Update a series")
+                .body::<post_v1::validator::V1SeriesUpdatePayload>()
+                .returns::<post_series::Model>(),
+            Op::post("/post/v1/series/delete/{series_id}", "Delete a series").returns::<Message>(),
+            Op::post("/post/v1/series/list", "List series")
+                .body::<post_v1::validator::V1SeriesListQuery>()
+                .returns::<post_series::PostSeriesList>(),
+            Op::post(
+                "/post/v1/series/add/{post_id}/{series_id}",
+                "Add a post to a series",
+            )
+            .returns::<post_series_post::Model>()
+            .status(201),
+            Op::post(
+                "/post/v1/series/remove/{post_id}/{series_id}",
+                "Remove a post from a series",
+            )
+            .returns::<Message>(),
+            Op::post("/post/v1/series/reorder/{series_id}", "Reorder a series")
+                .body::<post_v1::validator::V1SeriesReorderPayload>(),
+            Op::post("/post/v1/contributors/{post_id}", "List contributors"),
+            Op::post(
+                "/post/v1/contributors/{post_id}/set",
+                "Replace contributors",
+            )
+            .body::<post_v1::validator::V1SetContributorsPayload>(),
+            Op::post("/post/v1/translations/{post_id}", "List translations"),
+            Op::post(
+                "/post/v1/translations/{post_id}/link/{other_id}",
+                "Link two translations",
+            ),
+            Op::post(
+                "/post/v1/translations/{post_id}/unlink",
+                "Unlink a translation",
+            ),
+        ],
+    ));
+
+    operations.extend(section(
+        "post",
+        Public,
+        vec![
+            Op::post("/post/v1/like/{post_id}", "Like a post")
+                .guard(Authenticated)
+                .returns::<post_like::LikeActionResponse>(),
+            Op::post("/post/v1/unlike/{post_id}", "Unlike a post")
+                .guard(Authenticated)
+                .returns::<post_like::LikeActionResponse>(),
+            Op::post("/post/v1/like/status/{post_id}", "Like status of a post")
+                .guard(Authenticated)
+                .returns::<post_like::LikeStatus>(),
+            Op::post("/post/v1/like/status/batch", "Like status of several posts")
+                .guard(Authenticated)
+                .body::<post_like::LikeStatusBatchRequest>()
+                .returns::<post_like::LikeStatusBatchResponse>(),
+            Op::post("/post/v1/view/{id_or_slug}", "View a post by id or slug")
+                .string_path()
+                .query::<post_v1::validator::V1PostViewQuery>()
+                .optional_body::<post_v1::validator::V1PostUnlockPayload>()
+                .returns::<post::PostWithRelations>()
+                .or_content("text/html")
+                .or_content("text/markdown")
+                .or_content("text/plain"),
+            Op::post("/post/v1/list/published", "List published posts")
+                .body::<post_v1::validator::V1PostQueryParams>()
+                .returns::<Paginated<post::PostWithRelations>>(),
+            Op::post("/post/v1/series/view/{slug}", "View a series").string_path(),
+            Op::post("/post/v1/author/{author_id}", "Author profile"),
+            Op::post("/post/v1/author/{author_id}/posts", "Posts by an author")
+                .body::<post_v1::validator::V1AuthorPostsQuery>()
+                .returns::<Paginated<post::PostWithRelations>>(),
+            Op::post("/post/v1/sitemap", "Published post slugs")
+                .returns::<Vec<post::PostSitemap>>(),
+            Op::post("/post/v1/track_view/{post_id}", "Record a view").returns::<Message>(),
+        ],
+    ));
+
+    operations.extend(section(
+        "comment",
+        Verified,
+        vec![
+            Op::post("/post/comment/v1/create", "Comment on a post")
+                .body::<post_comment_v1::validator::V1CreatePostCommentPayload>()
+                .returns::<post_comment::Model>()
+                .status(201),
+            Op::post("/post/comment/v1/update/{comment_id}", "Edit your comment")
+                .body::<post_comment_v1::validator::V1UpdatePostCommentPayload>()
+                .returns::<post_comment::Model>(),
+            Op::post(
+                "/post/comment/v1/delete/{comment_id}",
+                "Delete your comment",
+            )
+            .returns::<Message>(),
+            Op::post("/post/comment/v1/flag/{comment_id}", "Flag a comment")
+                .body::<post_comment_v1::validator::V1FlagCommentPayload>(),
+            Op::post("/post/comment/v1/like/{comment_id}", "Like a comment")
+                .returns::<comment_like::CommentLikeResponse>(),
+            Op::post("/post/comment/v1/unlike/{comment_id}", "Unlike a comment")
+                .returns::<comment_like::CommentLikeResponse>(),
+            Op::post("/post/comment/v1/like/status", "Which comments you liked")
+                .body::<post_comment_v1::validator::V1CommentLikeStatusPayload>()
+                .returns::<comment_like::CommentLikeStatus>(),
+            Op::post("/post/comment/v1/{post_id}", "Comments on a post")
+                .guard(Public)
+                .returns::<Vec<post_comment::CommentWithUser>>(),
+        ],
+    ));
+
+    operations.extend(section(
+        "comment",
+        Permission(permission::COMMENT_MODERATE),
+        vec![
+            Op::post("/post/comment/v1/admin/list", "Query comments")
+                .body::<post_comment_v1::validator::V1AdminPostCommentListQuery>()
+                .returns::<Paginated<post_comment::CommentWithUser>>(),
+            Op::post("/post/comment/v1/admin/hide/{comment_id}", "Hide a comment"),
+            Op::post(
+                "/post/comment/v1/admin/unhide/{comment_id}",
+                "Unhide a comment",
+            ),
+            Op::post(
+                "/post/comment/v1/admin/delete/{comment_id}",
+                "Delete a comment",
+            )
+            .returns::<Message>(),
+            Op::post(
+                "/post/comment/v1/admin/flags/clear/{comment_id}",
+                "Clear a comment's flags",
+            ),
+            Op::post("/post/comment/v1/admin/flags/list", "List flags")
+                .body::<post_comment_v1::validator::V1AdminCommentFlagListQuery>()
+                .returns::<Paginated<comment_flag::FlagWithUser>>(),
+            Op::post(
+                "/post/comment/v1/admin/flags/summary/{comment_id}",
+                "Flag counts for a comment",
+            )
+            .returns::<comment_flag::FlagsSummary>(),
+            Op::post(
+                "/post/comment/v1/admin/flags/details/{comment_id}",
+                "Flags on a comment",
+            )
+            .returns::<Vec<comment_flag::FlagWithUser>>(),
+        ],
+    ));
+
+    operations.extend(section(
+        "taxonomy",
+        Permission(permission::TAXONOMY_MANAGE),
+        vec![
+            Op::post("/category/v1/create", "Create a category")
+                .body::<category_v1::validator::V1CreateCategoryPayload>()
+                .returns::<category::CategoryWithRelations>()
+                .status(201),
+            Op::post("/category/v1/update/{category_id}", "Update a category")
+                .body::<category_v1::validator::V1UpdateCategoryPayload>()
+                .returns::<category::CategoryWithRelations>(),
+            Op::post("/category/v1/delete/{category_id}", "Delete a category")
+                .body::<category_v1::validator::V1DeleteCategoryPayload>()
+                .returns::<Message>(),
+            Op::post("/category/v1/list/query", "Query categories")
+                .guard(Public)
+                .body::<category_v1::validator::V1CategoryQueryParams>()
+                .returns::<Paginated<category::CategoryWithRelations>>(),
+            Op::get("/category/v1/list", "All categories")
+                .guard(Public)
+                .returns::<Vec<category::Model>>(),
+            Op::get(
+                "/category/v1/view/{category_id}",
+                "View a category by id or slug",
+            )
+            .guard(Public)
+            .string_path()
+            .returns::<category::CategoryWithRelations>(),
+            Op::get("/category/v1/tree", "Category tree")
+                .guard(Public)
+                .returns::<Vec<category::CategoryNode>>(),
+            Op::get(
+                "/category/v1/tree/{category_id}",
+                "Subtree under a category",
+            )
+            .guard(Public)
+            .string_path()
+            .returns::<category::CategoryNode>(),
+            Op::get(
+                "/category/v1/path/{category_id}",
+                "Breadcrumb to a category",
+            )
+            .guard(Public)
+            .string_path()
+            .returns::<Vec<category::CategoryTreeRow>>(),
+            Op::post("/tag/v1/create", "Create a tag")
+                .body::<tag_v1::validator::V1CreateTagPayload>()
+                .returns::<tag::Model>()
+                .status(201),
+            Op::post("/tag/v1/update/{tag_id}", "Update a tag")
+                .body::<tag_v1::validator::V1UpdateTagPayload>()
+                .returns::<tag::Model>(),
+            Op::post("/tag/v1/delete/{tag_id}", "Delete a tag").returns::<Message>(),
+            Op::post("/tag/v1/view/{tag_id}", "View a tag")
+                .guard(Public)
+                .returns::<tag::Model>(),
+            Op::post("/tag/v1/list/query", "Query tags")
+                .guard(Public)
+                .body::<tag_v1::validator::V1TagQueryParams>()
+                .returns::<Paginated<tag::Model>>(),
+            Op::get("/tag/v1/list", "All tags")
+                .guard(Public)
+                .returns::<Vec<tag::Model>>(),
+        ],
+    ));
+
+    operations.extend(section(
+        "media",
+        author,
+        vec![
+            Op::post("/media/v1/create", "Upload media")
+                .multipart::<media_v1::validator::MediaUploadMetadata>("file")
+                .returns::<media::Model>()
+                .status(201),
+            Op::post("/media/v1/view/{media_id}", "View media").returns::<media::MediaWithUsage>(),
+            Op::post("/media/v1/list/query", "Query media")
+                .body::<media_v1::validator::V1MediaListQuery>()
+                .returns::<Paginated<media::MediaWithUsage>>(),
+            Op::post("/media/v1/usage/details", "Where media is used")
+                .body::<media_v1::validator::V1MediaUsageQuery>(),
+            Op::post("/media/v1/delete/{media_id}", "Delete media").returns::<Message>(),
+        ],
+    ));
+
+    operations.extend(section(
+        "feed",
+        Public,
+        vec![
+            Op::get("/feed/v1/rss", "RSS 2.0 feed")
+                .query::<feed_v1::validator::V1FeedQuery>()
+                .content("application/rss+xml"),
+            Op::get("/feed/v1/atom", "Atom feed")
+                .query::<feed_v1::validator::V1FeedQuery>()
+                .content("application/atom+xml"),
+            Op::get("/feed/v1/json", "JSON Feed")
+                .query::<feed_v1::validator::V1FeedQuery>()
+                .content("application/feed+json"),
+            Op::get("/seo/v1/sitemap.xml", "Sitemap index").content("application/xml"),
+            Op::get("/seo/v1/sitemaps/{name}", "Child sitemap")
+                .string_path()
+                .content("application/xml"),
+            Op::get("/seo/v1/robots.txt", "robots.txt").content("text/plain"),
+        ],
+    ));
+
+    operations.extend(section(
+        "newsletter",
+        Public,
+        vec![
+            Op::post("/newsletter/v1/subscribe", "Subscribe")
+                .body::<newsletter_v1::validator::V1SubscribePayload>()
+                .status(201),
+            Op::post("/newsletter/v1/unsubscribe", "Unsubscribe")
+                .body::<newsletter_v1::validator::V1UnsubscribePayload>()
+                .returns::<Message>(),
+            Op::post("/newsletter/v1/confirm", "Confirm a subscription")
+                .body::<newsletter_v1::validator::V1UnsubscribePayload>()
+                .returns::<Message>(),
+            Op::post("/newsletter/v1/send", "Send a newsletter")
+                .guard(Permission(permission::NEWSLETTER_MANAGE))
+                .body::<newsletter_v1::validator::V1SendNewsletterPayload>()
+                .status(202),
+            Op::post("/newsletter/v1/subscribers/list", "List subscribers")
+                .guard(Permission(permission::NEWSLETTER_MANAGE))
+                .body::<newsletter_v1::validator::V1ListSubscribersQuery>()
+                .returns::<Paginated<newsletter_subscriber::SubscriberListItem>>(),
+        ],
+    ));
+
+    operations.extend(section(
+        "notification",
+        Verified,
+        vec![
+            Op::post("/notification/v1/list", "List your notifications")
+                .body::<notification_v1::validator::V1NotificationListQuery>()
+                .returns::<Paginated<notification::Model>>(),
+            Op::post("/notification/v1/mark_read", "Mark notifications read")
+                .body::<notification_v1::validator::V1MarkReadPayload>()
+                .returns::<notification::MarkReadResponse>(),
+            Op::get("/notification/v1/unread_count", "Unread notification count")
+                .returns::<notification::UnreadCount>(),
+            Op::get(
+                "/notification/v1/preferences",
+                "Your notification preferences",
+            )
+            .returns::<notification_preference::Model>(),
+            Op::post(
+                "/notification/v1/preferences/update",
+                "Update notification preferences",
+            )
+            .body::<notification_v1::validator::V1UpdatePreferencesPayload>()
+            .returns::<notification_preference::Model>(),
+        ],
+    ));
+
+    {
+        use analytics_v1::validator::*;
+        operations.extend(section(
+            "analytics",
+            Permission(permission::ANALYTICS_VIEW),
+            vec![
+                Op::post(
+                    "/analytics/v1/user/registration-trends",
+                    "Registrations over time",
+                )
+                .body::<RegistrationTrendsRequest>()
+                .returns::<AnalyticsEnvelopeResponse<Vec<RegistrationTrendPoint>>>(),
+                Op::post(
+                    "/analytics/v1/user/verification-rates",
+                    "Email verification rates",
+                )
+                .body::<VerificationRatesRequest>()
+                .returns::<AnalyticsEnvelopeResponse<Vec<VerificationRatePoint>>>(),
+                Op::post(
+                    "/analytics/v1/content/publishing-trends",
+                    "Posts published over time",
+                )
+                .body::<PublishingTrendsRequest>()
+                .returns::<AnalyticsEnvelopeResponse<Vec<PublishingTrendPoint>>>(),
+                Op::post(
+                    "/analytics/v1/engagement/page-views",
+                    "Page views over time",
+                )
+                .body::<PageViewsRequest>()
+                .returns::<AnalyticsEnvelopeResponse<Vec<PageViewPoint>>>(),
+                Op::post("/analytics/v1/engagement/comment-rate", "Comments per post")
+                    .body::<CommentRateRequest>()
+                    .returns::<AnalyticsEnvelopeResponse<Vec<CommentRatePoint>>>(),
+                Op::post(
+                    "/analytics/v1/engagement/newsletter-growth",
+                    "Newsletter growth",
+                )
+                .body::<NewsletterGrowthRequest>()
+                .returns::<AnalyticsEnvelopeResponse<Vec<NewsletterGrowthPoint>>>(),
+                Op::post(
+                    "/analytics/v1/media/upload-trends",
+                    "Media uploads over time",
+                )
+                .body::<MediaUploadRequest>()
+                .returns::<AnalyticsEnvelopeResponse<Vec<MediaUploadPoint>>>(),
+                Op::post("/analytics/v1/dashboard/summary", "Dashboard summary")
+                    .body::<DashboardSummaryRequest>()
+                    .returns::<AnalyticsEnvelopeResponse<DashboardSummaryData>>(),
+            ],
+        ));
+    }
+
+    operations.extend(section(
+        "admin",
+        Permission(permission::ROUTE_MANAGE),
+        vec![
+            Op::post("/admin/route/v1/block", "Block a route")
+                .body::<admin_route_v1::validator::V1BlockRoutePayload>()
+                .status(201),
+            Op::post("/admin/route/v1/unblock", "Unblock a route")
+                .body::<admin_route_v1::validator::V1UnblockRoutePayload>(),
+            Op::post("/admin/route/v1/update", "Update a route rule")
+                .body::<admin_route_v1::validator::V1UpdateRoutePayload>(),
+            Op::post("/admin/route/v1/delete", "Delete a route rule")
+                .body::<admin_route_v1::validator::V1DeleteRoutePayload>(),
+            Op::post("/admin/route/v1/list", "List route rules")
+                .body::<admin_route_v1::validator::V1RouteStatusQueryParams>(),
+            Op::get("/admin/route/v1/sync", "Sync route rules to Redis"),
+            Op::get("/admin/route/v1/maintenance", "Maintenance mode")
+                .returns::<route_blocker_rules::Maintenance>(),
+            Op::post("/admin/route/v1/maintenance", "Update maintenance mode")
+                .body::<admin_route_v1::validator::V1MaintenancePayload>()
+                .returns::<route_blocker_rules::Maintenance>(),
+            Op::get("/admin/route/v1/sync_interval", "Route sync interval"),
+            Op::post(
+                "/admin/route/v1/sync_interval",
+                "Update the route sync interval",
+            )
+            .body::<admin_route_v1::validator::V1UpdateSyncIntervalPayload>(),
+            Op::post("/admin/route/v1/sync_interval/pause", "Pause route sync"),
+            Op::post("/admin/route/v1/sync_interval/resume", "Resume route sync"),
+            Op::post(
+                "/admin/route/v1/sync_interval/restart",
+                "Restart route sync",
+            )
+            .status(202),
+        ],
+    ));
+
+    operations.extend(section(
+        "admin",
+        Permission(permission::ACL_MANAGE),
+        vec![
+            Op::get("/admin/acl/v1/list", "List app constants")
+                .query::<admin_acl_v1::validator::ConstantsListQuery>(),
+            Op::get("/admin/acl/v1/get/{key}", "View an app constant").string_path(),
+            Op::post("/admin/acl/v1/create", "Create an app constant")
+                .body::<admin_acl_v1::validator::UpsertConstantRequest>()
+                .status(201),
+            Op::post("/admin/acl/v1/update/{key}", "Update an app constant")
+                .string_path()
+                .body::<admin_acl_v1::validator::UpsertConstantRequest>(),
+            Op::delete("/admin/acl/v1/delete/{key}", "Delete an app constant").string_path(),
+            Op::post("/admin/acl/v1/sync", "Sync app constants to Redis"),
+            Op::post(
+                "/admin/acl/v1/import_env",
+                "Import app constants from the environment",
+            ),
+            Op::get("/admin/acl/v1/registry", "Known settings"),
+            Op::get("/admin/acl/v1/history", "App constant change history")
+                .query::<admin_acl_v1::validator::ConstantHistoryQuery>(),
+        ],
+    ));
+
+    operations.extend(section(
+        "admin",
+        Permission(permission::AUDIT_VIEW),
+        vec![
+            Op::post("/admin/audit/v1/list", "Query the audit log")
+                .body::<admin_audit_v1::validator::V1AuditLogQueryParams>()
+                .returns::<Paginated<audit_log::AuditLogEntry>>(),
+            Op::get("/admin/audit/v1/view/{log_id}", "View an audit entry")
+                .returns::<audit_log::AuditLogEntry>(),
+            Op::post("/admin/audit/v1/export", "Export the audit log")
+                .optional_body::<admin_audit_v1::validator::V1AuditExportPayload>()
+                .or_content("text/csv"),
+            Op::post("/admin/audit/v1/purge", "Purge expired audit entries"),
+        ],
+    ));
+
+    operations.extend(section(
+        "admin",
+        Role(UserRole::Moderator),
+        vec![Op::get("/admin/events/v1/stream", "Live admin events").content("text/event-stream")],
+    ));
+
+    operations.extend(section(
+        "admin",
+        Permission(permission::JOB_MANAGE),
+        vec![
+            Op::post("/admin/job/v1/list", "Query background jobs")
+                .body::<admin_job_v1::validator::V1JobQueryParams>()
+                .returns::<Paginated<job::Model>>(),
+            Op::get("/admin/job/v1/view/{job_id}", "View a job").returns::<job::Model>(),
+            Op::post("/admin/job/v1/retry/{job_id}", "Retry a job").returns::<job::Model>(),
+            Op::post("/admin/job/v1/cancel/{job_id}", "Cancel a job").returns::<job::Model>(),
+            Op::get("/admin/job/v1/stats", "Job counts per queue and status"),
+            Op::get("/admin/job/v1/schedules", "Recurring job schedules")
+                .returns::<DataList<job_schedule::Model>>(),
+            Op::post(
+                "/admin/job/v1/schedules/update/{schedule_id}",
+                "Update a job schedule",
+            )
+            .body::<admin_job_v1::validator::V1UpdateSchedulePayload>()
+            .returns::<job_schedule::Model>(),
+            Op::post(
+                "/admin/job/v1/schedules/run/{schedule_id}",
+                "Run a schedule now",
+            )
+            .returns::<job::Model>()
+            .status(202),
+        ],
+    ));
+
+    operations.extend(section(
+        "admin",
+        Permission(permission::PERMISSION_MANAGE),
+        vec![
+            Op::get("/admin/permission/v1/catalog", "Permission catalog"),
+            Op::get("/admin/permission/v1/matrix", "Permissions per role"),
+            Op::post(
+                "/admin/permission/v1/role/{role}",
+                "Set a role's permissions",
+            )
+            .string_path()
+            .body::<admin_permission_v1::validator::V1RolePermissionsPayload>(),
+            Op::post(
+                "/admin/permission/v1/reset",
+                "Reset role permissions to defaults",
+            ),
+            Op::get(
+                "/admin/permission/v1/user/{user_id}",
+                "A user's permission overrides",
+            ),
+            Op::post(
+                "/admin/permission/v1/user/{user_id}",
+                "Set a user's permission overrides",
+            )
+            .body::<admin_permission_v1::validator::V1UserPermissionPayload>(),
+            Op::post("/admin/permission/v1/sync", "Sync permissions to Redis"),
+        ],
+    ));
+
+    operations.extend(section(
+        "admin",
+        Permission(permission::REDIRECT_MANAGE),
+        vec![
+            Op::post("/admin/redirect/v1/create", "Create a redirect")
+                .body::<admin_redirect_v1::validator::V1CreateRedirectPayload>()
+                .returns::<slug_redirect::Model>()
+                .status(201),
+            Op::post(
+                "/admin/redirect/v1/update/{redirect_id}",
+                "Update a redirect",
+            )
+            .body::<admin_redirect_v1::validator::V1UpdateRedirectPayload>()
+            .returns::<slug_redirect::Model>(),
+            Op::post(
+                "/admin/redirect/v1/delete/{redirect_id}",
+                "Delete a redirect",
+            )
+            .returns::<Message>(),
+            Op::post("/admin/redirect/v1/list", "Query redirects")
+                .body::<admin_redirect_v1::validator::V1RedirectQueryParams>()
+                .returns::<Paginated<slug_redirect::Model>>(),
+        ],
+    ));
+
+    operations.extend(section(
+        "admin",
+        Permission(permission::WEBHOOK_MANAGE),
+        vec![
+            Op::get("/admin/webhook/v1/list", "List webhooks")
+                .returns::<DataList<webhook::Model>>(),
+            Op::get(
+                "/admin/webhook/v1/events",
+                "Events a webhook can subscribe to",
+            ),
+            Op::post("/admin/webhook/v1/create", "Create a webhook")
+                .body::<admin_webhook_v1::validator::V1CreateWebhookPayload>()
+                .status(201),
+            Op::get("/admin/webhook/v1/view/{webhook_id}", "View a webhook")
+                .returns::<webhook::Model>(),
+            Op::post("/admin/webhook/v1/update/{webhook_id}", "Update a webhook")
+                .body::<admin_webhook_v1::validator::V1UpdateWebhookPayload>()
+                .returns::<webhook::Model>(),
+            Op::post("/admin/webhook/v1/delete/{webhook_id}", "Delete a webhook")
+                .returns::<Message>(),
+            Op::post(
+                "/admin/webhook/v1/rotate_secret/{webhook_id}",
+                "Rotate a webhook's signing secret",
+            ),
+            Op::post(
+                "/admin/webhook/v1/test/{webhook_id}",
+                "Send a test delivery",
+            )
+            .returns::<webhook_delivery::Model>()
+            .status(202),
+            Op::post("/admin/webhook/v1/deliveries/list", "Query deliveries")
+                .body::<admin_webhook_v1::validator::V1WebhookDeliveryQueryParams>()
+                .returns::<Paginated<webhook_delivery::Model>>(),
+            Op::get(
+                "/admin/webhook/v1/deliveries/view/{delivery_id}",
+                "View a delivery",
+            )
+            .returns::<webhook_delivery::Model>(),
+            Op::post(
+                "/admin/webhook/v1/deliveries/redeliver/{delivery_id}",
+                "Redeliver a delivery",
+            )
+            .returns::<webhook_delivery::Model>()
+            .status(202),
+        ],
+    ));
+
+    operations.extend(section(
+        "seed",
+        Public,
+        vec![
+            Op::post("/admin/seed/v1/seed_tags", "Seed tags"),
+            Op::post("/admin/seed/v1/seed_categories", "Seed categories"),
+            Op::post("/admin/seed/v1/seed_posts", "Seed posts"),
+            Op::post("/admin/seed/v1/seed_post_comments", "Seed comments"),
+            Op::post("/admin/seed/v1/seed_user_sessions", "Seed sessions"),
+            Op::post(
+                "/admin/seed/v1/seed_email_verifications",
+                "Seed email verifications",
+            ),
+            Op::post(
+                "/admin/seed/v1/seed_forgot_passwords",
+                "Seed password resets",
+            ),
+            Op::post("/admin/seed/v1/seed_post_revisions", "Seed revisions"),
+            Op::post("/admin/seed/v1/seed_post_series", "Seed series"),
+            Op::post("/admin/seed/v1/seed_post_views", "Seed post views"),
+            Op::post(
+                "/admin/seed/v1/seed_scheduled_posts",
+                "Seed scheduled posts",
+            ),
+            Op::post("/admin/seed/v1/seed_media", "Seed media"),
+            Op::post("/admin/seed/v1/seed_media_variants", "Seed media variants"),
+            Op::post("/admin/seed/v1/seed_media_usage", "Seed media usage"),
+            Op::post("/admin/seed/v1/seed_comment_flags", "Seed comment flags"),
+            Op::post(
+                "/admin/seed/v1/seed_newsletter_subscribers",
+                "Seed newsletter subscribers",
+            ),
+            Op::post("/admin/seed/v1/seed_route_status", "Seed route rules"),
+            Op::post("/admin/seed/v1/seed", "Seed from a preset")
+                .body::<seed_v1::validator::V1SeedPayload>(),
+            Op::post("/admin/seed/v1/presets", "List seed presets"),
+        ],
+    ));
+
+    operations.extend(section(
+        "backup",
+        Permission(permission::BACKUP_MANAGE),
+        vec![
+            Op::post("/backup/v1/restore", "Restore an uploaded backup")
+                .upload("file")
+                .returns::<backup_job::Model>()
+                .status(202),
+            Op::post("/backup/v1/export", "Start a backup export")
+                .body::<backup_v1::validator::V1ExportRequestPayload>()
+                .returns::<backup_job::Model>()
+                .status(202),
+            Op::post("/backup/v1/status/{job_id}", "Backup job status")
+                .returns::<backup_job::Model>(),
+            Op::post("/backup/v1/list", "Query backup jobs")
+                .body::<backup_v1::validator::V1BackupListQuery>()
+                .returns::<Paginated<backup_job::Model>>(),
+            Op::get("/backup/v1/download/{job_id}", "Download a backup archive")
+                .content("application/zip")
+                .content("application/octet-stream"),
+            Op::post("/backup/v1/download/{job_id}", "Download a backup archive")
+                .content("application/zip")
+                .content("application/octet-stream"),
+            Op::post("/backup/v1/schedule", "Update the backup schedule")
+                .body::<backup_v1::validator::V1SchedulePayload>()
+                .returns::<job_schedule::Model>(),
+            Op::post("/backup/v1/schedule/view", "The backup schedule")
+                .returns::<job_schedule::Model>(),
+            Op::post("/backup/v1/restore/{job_id}", "Restore a stored backup")
+                .body::<backup_v1::validator::V1RestoreFromJobPayload>()
+                .returns::<backup_job::Model>()
+                .status(202),
+        ],
+    ));
+
+    operations.extend(section(
+        "import",
+        Permission(permission::IMPORT_RUN),
+        vec![
+            Op::post("/import/v1/wordpress", "Import a WordPress export")
+                .multipart::<import_v1::validator::V1BlogImportOptions>("file")
+                .returns::<import_job::Model>()
+                .status(202),
+            Op::post("/import/v1/ghost", "Import a Ghost export")
+                .multipart::<import_v1::validator::V1BlogImportOptions>("file")
+                .returns::<import_job::Model>()
+                .status(202),
+            Op::post("/import/v1/status/{job_id}", "Import job status")
+                .returns::<import_job::Model>(),
+            Op::post("/import/v1/list", "Query import jobs")
+                .body::<import_v1::validator::V1ImportListQuery>()
+                .returns::<Paginated<import_job::Model>>(),
+        ],
+    ));
+
+    operations
+}
+
+/// Path parameters named `id` or `*_id` are integers unless the operation says
+/// otherwise.
+fn path_parameters(operation: &Operation) -> Vec<Value> {
+    operation
+        .path
+        .split('/')
+        .filter_map(|segment| segment.strip_prefix('{')?.strip_suffix('}'))
+        .map(|name| {
+            let integer = !operation.string_path && (name == "id" || name.ends_with("_id"));
+            json!({
+                "name": name,
+                "in": "path",
+                "required": true,
+                "schema": { "type": if integer { "integer" } else { "string" } },
+            })
+        })
+        .collect()
+}
+
+/// Query structs are spread into one parameter per field.
+fn query_parameters(query: &Schema, schemas: &Map<String, Value>) -> Vec<Value> {
+    let Some(definition) = query
+        .get("$ref")
+        .and_then(Value::as_str)
+        .and_then(|reference| reference.rsplit('/').next())
+        .and_then(|name| schemas.get(name))
+    else {
+        return Vec::new();
+    };
+    let required: Vec<&str> = definition["required"]
+        .as_array()
+        .map(|names| names.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default();
+
+    definition["properties"]
+        .as_object()
+        .map(|properties| {
+            properties
+                .iter()
+                .map(|(name, schema)| {
+                    let mut parameter = json!({
+                        "name": name,
+                        "in": "query",
+                        "required": required.contains(&name.as_str()),
+                        "schema": schema,
+                    });
+                    if let Some(description) = schema.get("description") {
+                        parameter["description"] = description.clone();
+                    }
+                    parameter
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+struct Schemas {
+    body: Option<Schema>,
+    query: Option<Schema>,
+    returns: Option<Schema>,
+}
+
+fn render(operation: &Operation, schemas: Schemas, definitions: &Map<String, Value>) -> Value {
+    let mut parameters = path_parameters(operation);
+    if let Some(query) = &schemas.query {
+        parameters.extend(query_parameters(query, definitions));
+    }
+
+    let mut content = Map::new();
+    if operation.json {
+        let schema = schemas
+            .returns
+            .map(Value::from)
+            .unwrap_or_else(|| json!({ "type": "object" }));
+        content.insert("application/json".into(), json!({ "schema": schema }));
+    }
+    for mime in &operation.content {
+        content.insert(
+            (*mime).into(),
+            json!({ "schema": { "type": "string", "format": "binary" } }),
+        );
+    }
+    let mut success = json!({ "description": "Success" });
+    if operation.status == 307 {
+        success = json!({
+            "description": "Redirect",
+            "headers": { "Location": { "schema": { "type": "string" } } },
+        });
+    } else if !content.is_empty() && operation.status != 204 {
+        success["content"] = Value::Object(content);
+    }
+
+    let mut item = json!({
+        "tags": [operation.tag],
+        "summary": operation.summary,
+        "operationId": operation_id(operation),
+        "responses": {
+            operation.status.to_string(): success,
+            "default": {
+                "description": "Error",
+                "content": {
+                    "application/json": {
+                        "schema": { "$ref": "#/components/schemas/ErrorResponse" },
+                    },
+                },
+            },
+        },
+        "x-access": operation.access().describe(),
+    });
+    if !parameters.is_empty() {
+        item["parameters"] = Value::Array(parameters);
+    }
+
+    let body = schemas.body.map(Value::from);
+    if let Some(file) = operation.upload {
+        let upload = json!({
+            "type": "object",
+            "required": [file],
+            "properties": { file: { "type": "string", "format": "binary" } },
+        });
+        let schema = match body {
+            Some(options) => json!({ "allOf": [options, upload] }),
+            None => upload,
+        };
+        item["requestBody"] = json!({
+            "required": true,
+            "content": { "multipart/form-data": { "schema": schema } },
+        });
+    } else if let Some(schema) = body {
+        item["requestBody"] = json!({
+            "required": operation.body_required,
+            "content": { "application/json": { "schema": schema } },
+        });
+    }
+
+    if operation.access().needs_session() {
+        item["security"] = json!([{ "session": [], "csrf": [] }]);
+    }
+    item
+}
+
+/// `POST /post/v1/like/{post_id}` -> `post_post_v1_like_post_id`.
+fn operation_id(operation: &Operation) -> String {
+    let path: String = operation
+        .path
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    let path: Vec<&str> = path.split('_').filter(|part| !part.is_empty()).collect();
+    format!("{}_{}", operation.method.to_lowercase(), path.join("_"))
+}
+
+/// Lists each error code with its default message and HTTP status, so clients
+/// can map `type` without reading the server source.
+fn describe_error_codes(definitions: &mut Map<String, Value>) {
+    let Some(schema) = definitions.get_mut("ErrorCode") else {
+        return;
+    };
+    let mut codes: Vec<Value> = Vec::new();
+    collect_enum_values(schema, &mut codes);
+
+    let mut descriptions = Map::new();
+    for value in &codes {
+        if let (Some(name), Ok(code)) = (
+            value.as_str(),
+            serde_json::from_value::<ErrorCode>(value.clone()),
+        ) {
+            descriptions.insert(
+                name.to_string(),
+                json!(format!(
+                    "{} ({})",
+                    code.default_message(),
+                    code.status_code().as_u16()
+                )),
+            );
+        }
+    }
+
+    let description = schema.get("description").cloned();
+    *schema = json!({
+        "type": "string",
+        "enum": codes,
+        "x-enum-descriptions": descriptions,
+    });
+    if let Some(description) = description {
+        schema["description"] = description;
+    }
+}
+
+fn collect_enum_values(schema: &Value, values: &mut Vec<Value>) {
+    if let Some(found) = schema.get("enum").and_then(Value::as_array) {
+        values.extend(found.iter().cloned());
+    }
+    if let Some(found) = schema.get("const") {
+        values.push(found.clone());
+    }
+    if let Some(variants) = schema.get("oneOf").and_then(Value::as_array) {
+        for variant in variants {
+            collect_enum_values(variant, values);
+        }
+    }
+}
+
+/// The OpenAPI document served at `/docs/openapi.json`.
+pub fn document() -> Value {
+    let mut generator = SchemaSettings::openapi3()
+        .for_deserialize()
+        .into_generator();
+    generator.subschema_for::<ErrorResponse>();
+
+    let operations = operations();
+    let mut rendered = Vec::with_capacity(operations.len());
+    for operation in &operations {
+        let mut schemas = Schemas {
+            body: operation.body.map(|f| f(&mut generator)),
+            query: operation.query.map(|f| f(&mut generator)),
+            returns: operation.returns.map(|f| f(&mut generator)),
+        };
+        // Inline schemas (`Vec<T>`, primitives) miss the transforms the
+        // generator applies to definitions.
+        for transform in generator.transforms_mut() {
+            for schema in [&mut schemas.body, &mut schemas.query, &mut schemas.returns]
+                .into_iter()
+                .flatten()
+            {
+                transform.transform(schema);
+            }
+        }
+        rendered.push(schemas);
+    }
+
+    let mut definitions = generator.take_definitions(true);
+    describe_error_codes(&mut definitions);
+
+    let mut paths = Map::new();
+    let mut tags: Vec<&str> = Vec::new();
+    for (operation, schemas) in operations.iter().zip(rendered) {
+        if !tags.contains(&operation.tag) {
+            tags.push(operation.tag);
+        }
+        let item = render(operation, schemas, &definitions);
+        paths
+            .entry(operation.path)
+            .or_insert_with(|| json!({}))
+            .as_object_mut()
+            .expect("path items are objects")
+            .insert(operation.method.to_lowercase(), item);
+    }
+
+    json!({
+        "openapi": "3.0.3",
+        "info": {
+            "title": "Ruxlog API",
+            "version": env!("CARGO_PKG_VERSION"),
+            "description": "Errors answer with an `ErrorResponse` whose `type` is an `ErrorCode`. \
+                Routes marked with a session need the `id` cookie set by `/auth/v1/log_in`; \
+                every route needs the `csrf-token` header.",
+        },
+        "tags": tags.iter().map(|tag| json!({ "name": tag })).collect::<Vec<_>>(),
+        "paths": paths,
+        "components": {
+            "schemas": definitions,
+            "securitySchemes": {
+                "session": { "type": "apiKey", "in": "cookie", "name": "id" },
+                "csrf": { "type": "apiKey", "in": "header", "name": "csrf-token" },
+            },
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn document_references_resolve() {
+        let document = document();
+        let schemas = document["components"]["schemas"].as_object().unwrap();
+        let text = document.to_string();
+        for reference in text.split("\"#/components/schemas/").skip(1) {
+            let name = reference.split('"').next().unwrap();
+            assert!(schemas.contains_key(name), "missing schema {}", name);
+        }
+        assert!(schemas["ErrorCode"]["x-enum-descriptions"]["AUTH_001"].is_string());
+    }
+
+    #[test]
+    fn operations_are_unique() {
+        let mut seen = std::collections::HashSet::new();
+        for operation in operations() {
+            assert!(
+                seen.insert((operation.method, operation.path)),
+                "{} {} listed twice",
+                operation.method,
+                operation.path
+            );
+        }
+    }
+
+    #[test]
+    fn path_parameters_default_to_integer_ids() {
+        let params = path_parameters(&Operation::post("/post/v1/like/{post_id}", ""));
+        assert_eq!(params[0]["schema"]["type"], "integer");
+        let params = path_parameters(&Operation::get("/seo/v1/sitemaps/{name}", ""));
+        assert_eq!(params[0]["schema"]["type"], "string");
+    }
+}
@@ -0,0 +1,691 @@
+use std::collections::HashMap;
+
+use axum::{
+    extract::State,
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+};
+use axum_macros::debug_handler;
+use chrono::{DateTime, FixedOffset, Utc};
+use sea_orm::{
+    sea_query::{Expr, Query},
+    ColumnTrait, Condition, EntityTrait, QueryFilter, QueryOrder, QuerySelect,
+};
+use serde_json::{json, Map, Value};
+use sha2::{Digest, Sha256};
+use tracing::instrument;
+
+use crate::{
+    db::sea_models::{
+        category,
+        post::{self, Column as PostColumn, Entity as PostEntity, PostStatus, PostVisibility},
+        post_author::{self, PostAuthorRole},
+        post_series, post_series_post,
+        slug_redirect::{self, RedirectEntity},
+        tag, user,
+    },
+    error::{ErrorCode, ErrorResponse},
+    extractors::ValidatedQuery,
+    services::{
+        content_render, seo, settings,
+        websub::{self, FeedFormat},
+    },
+    AppState,
+};
+
+use super::validator::V1FeedQuery;
+
+const CACHE_CONTROL: &str = "public, max-age=300, s-maxage=300";
+
+/// Posts matched by a feed and how to describe it.
+struct FeedScope {
+    condition: Condition,
+    /// Category/tag/author/series name appended to the site name.
+    label: Option<String>,
+}
+
+struct FeedItem {
+    url: String,
+    title: String,
+    summary: String,
+    /// Rendered body; `None` when the feed was requested with `full=false`.
+    html: Option<String>,
+    published: DateTime<FixedOffset>,
+    modified: DateTime<FixedOffset>,
+    /// Credited authors, in order.
+    authors: Vec<String>,
+    tags: Vec<String>,
+}
+
+struct Feed {
+    title: String,
+    description: String,
+    home_url: String,
+    self_url: String,
+    hubs: Vec<String>,
+    /// Locale the feed is narrowed to, if any.
+    language: Option<String>,
+    updated: DateTime<FixedOffset>,
+    items: Vec<FeedItem>,
+}
+
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('\"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+// Short plain-text summary from the rendered Editor.js document
+fn content_to_summary(value: &serde_json::Value, max_len: usize) -> String {
+    content_render::summary(value, max_len)
+}
+
+// Wrap rendered HTML in CDATA, splitting any embedded terminator
+fn cdata(html: &str) -> String {
+    format!("<![CDATA[{}]]>", html.replace("]]>", "]]]]><![CDATA[>"))
+}
+
+fn not_found(what: &str) -> ErrorResponse {
+    ErrorResponse::new(ErrorCode::RecordNotFound).with_message(format!("{} not found", what))
+}
+
+/// Target of a slug redirect, so feeds of renamed categories, tags and
+/// series keep working under their old URL.
+async fn redirected_id(
+    db: &sea_orm::DatabaseConnection,
+    entity: RedirectEntity,
+    slug: &str,
+) -> Result<Option<i32>, ErrorResponse> {
+    Ok(slug_redirect::Entity::resolve(db, entity, None, slug)
+        .await?
+        .map(|redirect| redirect.target_id))
+}
+
+async fn resolve_scope(state: &AppState, query: &V1FeedQuery) -> Result<FeedScope, ErrorResponse> {
+    let db = &state.sea_db;
+    let mut condition = Condition::all()
+        .add(PostColumn::Status.eq(PostStatus::Published))
+        .add(PostColumn::Visibility.eq(PostVisibility::Public));
+    let mut labels = Vec::new();
+
+    if let Some(slug) = &query.category {
+        let category = match category::Entity::find_by_slug(db, slug.clone()).await? {
+            Some(found) => Some(found),
+            None => match redirected_id(db, RedirectEntity::Category, slug).await? {
+                Some(id) => category::Entity::find_by_id(id).one(db).await?,
+                None => None,
+            },
+        }
+        .ok_or_else(|| not_found("Category"))?;
+        condition = condition.add(PostColumn::CategoryId.eq(category.id));
+        labels.push(category.name);
+    }
+    if let Some(slug) = &query.tag {
+        let tag = match tag::Entity::find_by_slug(db, slug.clone()).await? {
+            Some(found) => Some(found),
+            None => match redirected_id(db, RedirectEntity::Tag, slug).await? {
+                Some(id) => tag::Entity::find_by_id(id).one(db).await?,
+                None => None,
+            },
+        }
+        .ok_or_else(|| not_found("Tag"))?;
+        condition = condition.add(Expr::cust_with_values("? = ANY(posts.tag_ids)", [tag.id]));
+        labels.push(format!("#{}", tag.name));
+    }
+    if let Some(author_id) = query.author {
+        let author = user::Entity::find_by_id(author_id)
+            .one(db)
+            .await?
+            .ok_or_else(|| not_found("Author"))?;
+        condition = condition.add(Expr::cust_with_values(
+            "posts.id IN (SELECT post_id FROM post_authors WHERE user_id = ? AND role = 'author')",
+            [author.id],
+        ));
+        labels.push(author.name);
+    }
+    if let Some(slug) = &query.series {
+        let series = match post_series::Entity::find_by_slug(db, slug.clone()).await? {
+            Some(found) => Some(found),
+            None => match redirected_id(db, RedirectEntity::Series, slug).await? {
+                Some(id) => post_series::Entity::find_by_id(id).one(db).await?,
+                None => None,
+            },
+        }
+        .ok_or_else(|| not_found("Series"))?;
+        condition = condition.add(
+            PostColumn::Id.in_subquery(
+                Query::select()
+                    .column(post_series_post::Column::PostId)
+                    .from(post_series_post::Entity)
+                    .and_where(post_series_post::Column::SeriesId.eq(series.id))
+                    .to_owned(),
+            ),
+        );
+        labels.push(series.name);
+    }
+    if let Some(locale) = query.locale() {
+        condition = condition.add(PostEntity::locale_filter(&locale, query.fallback()));
+    }
+
+    Ok(FeedScope {
+        condition,
+        label: (!labels.is_empty()).then(|| labels.join(" · ")),
+    })
+}
+
+/// Newest `updated_at` and number of posts in scope. Together they change
+/// whenever a post in the feed is edited, published or unpublished.
+async fn feed_version(
+    state: &AppState,
+    scope: &FeedScope,
+) -> Result<(Option<DateTime<FixedOffset>>, i64), ErrorResponse> {
+    let version = PostEntity::find()
+        .select_only()
+        .column_as(
+            Expr::col((PostEntity, PostColumn::UpdatedAt)).max(),
+            "newest",
+        )
+        .column_as(Expr::col((PostEntity, PostColumn::Id)).count(), "total")
+        .filter(scope.condition.clone())
+        .into_tuple::<(Option<DateTime<FixedOffset>>, i64)>()
+        .one(&state.sea_db)
+        .await?;
+
+    Ok(version.unwrap_or((None, 0)))
+}
+
+fn compute_etag(
+    format: FeedFormat,
+    query: &V1FeedQuery,
+    newest: Option<DateTime<FixedOffset>>,
+    total: i64,
+) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(format!(
+        "{}|{}|{}|{}|{}|{}|{}",
+        format.path(),
+        websub::site_url(),
+        query.scope_query(),
+        query.limit(),
+        query.full(),
+        newest.map(|d| d.timestamp_micros()).unwrap_or_default(),
+        total
+    ));
+    format!("W/\"{}\"", hex::encode(&hasher.finalize()[..16]))
+}
+
+fn http_date(date: DateTime<FixedOffset>) -> String {
+    date.with_timezone(&Utc)
+        .format("%a, %d %b %Y %H:%M:%S GMT")
+        .to_string()
+}
+
+fn strip_weak(tag: &str) -> &str {
+    tag.trim().trim_start_matches("W/")
+}
+
+/// `If-None-Match` takes precedence; `If-Modified-Since` is only consulted
+/// when it is absent (RFC 9110 §13.2.2).
+fn is_not_modified(
+    headers: &HeaderMap,
+    etag: &str,
+    last_modified: Option<DateTime<FixedOffset>>,
+) -> bool {
+    if let Some(value) = headers.get(header::IF_NONE_MATCH) {
+        let Ok(value) = value.to_str() else {
+            return false;
+        };
+        return value
+            .split(',')
+            .any(|candidate| candidate.trim() == "*" || strip_weak(candidate) == strip_weak(etag));
+    }
+
+    let (Some(since), Some(modified)) = (headers.get(header::IF_MODIFIED_SINCE), last_modified)
+    else {
+        return false;
+    };
+    since
+        .to_str()
+        .ok()
+        .and_then(|value| DateTime::parse_from_rfc2822(value).ok())
+        .is_some_and(|since| modified.timestamp() <= since.timestamp())
+}
+
+fn build_response(
+    status: StatusCode,
+    content_type: &'static str,
+    etag: &str,
+    last_modified: Option<DateTime<FixedOffset>>,
+    body: String,
+) -> Response {
+    let mut builder = axum::http::Response::builder()
+        .status(status)
+        .header(
+            header::CACHE_CONTROL,
+            HeaderValue::from_static(CACHE_CONTROL),
+        )
+        .header(header::ETAG, etag);
+    if status != StatusCode::NOT_MODIFIED {
+        builder = builder.header(header::CONTENT_TYPE, content_type);
+    }
+    if let Some(modified) = last_modified {
+        builder = builder.header(header::LAST_MODIFIED, http_date(modified));
+    }
+
+    builder
+        .body(axum::body::Body::from(body))
+        .unwrap_or_else(|_| ErrorResponse::new(ErrorCode::InternalServerError).into_response())
+}
+
+async fn load_feed(
+    state: &AppState,
+    format: FeedFormat,
+    query: &V1FeedQuery,
+    scope: FeedScope,
+    updated: Option<DateTime<FixedOffset>>,
+) -> Result<Feed, ErrorResponse> {
+    let db = &state.sea_db;
+    let site_url = websub::site_url();
+    let site_name = settings::SITE_NAME.string(state).await;
+
+    let posts = PostEntity::find()
+        .filter(scope.condition)
+        .order_by_desc(PostColumn::PublishedAt)
+        .order_by_desc(PostColumn::UpdatedAt)
+        .limit(query.limit())
+        .all(db)
+        .await?;
+
+    let post_ids: Vec<i32> = posts.iter().map(|p| p.id).collect();
+    let mut authors: HashMap<i32, Vec<String>> = post_author::Entity::list_for_posts(db, &post_ids)
+        .await?
+        .into_iter()
+        .map(|(post_id, contributors)| {
+            let names = contributors
+                .into_iter()
+                .filter(|c| c.role == PostAuthorRole::Author)
+                .map(|c| c.name)
+                .collect();
+            (post_id, names)
+        })
+        .collect();
+
+    let mut tag_ids: Vec<i32> = posts.iter().flat_map(|p| p.tag_ids.clone()).collect();
+    tag_ids.sort_unstable();
+    tag_ids.dedup();
+    let tags: HashMap<i32, String> = if tag_ids.is_empty() {
+        HashMap::new()
+    } else {
+        tag::Entity::find()
+            .filter(tag::Column::Id.is_in(tag_ids))
+            .all(db)
+            .await?
+            .into_iter()
+            .map(|t| (t.id, t.name))
+            .collect()
+    };
+
+    let items = posts
+        .iter()
+        .map(|p: &post::Model| FeedItem {
+            url: seo::post_url(&p.locale, &p.slug),
+            title: p.title.clone(),
+            summary: match &p.excerpt {
+                Some(excerpt) => excerpt.clone(),
+                None => content_to_summary(&p.content, 500),
+            },
+            html: query.full().then(|| content_render::to_html(&p.content)),
+            published: p.published_at.unwrap_or(p.updated_at),
+            modified: p.updated_at,
+            authors: authors.remove(&p.id).unwrap_or_default(),
+            tags: p
+                .tag_ids
+                .iter()
+                .filter_map(|id| tags.get(id).cloned())
+                .collect(),
+        })
+        .collect();
+
+    let (title, description) = match scope.label {
+        Some(label) => (
+            format!("{} · {}", site_name, label),
+            format!("Latest posts in {} from {}", label, site_name),
+        ),
+        None => (
+            site_name.clone(),
+            format!("Latest posts from {}", site_name),
+        ),
+    };
+
+    Ok(Feed {
+        title,
+        description,
+        home_url: format!("{}/", site_url),
+        self_url: websub::feed_url(format, &query.scope_query()),
+        hubs: settings::WEBSUB_HUBS.list(state).await,
+        language: query.locale(),
+        updated: updated.unwrap_or_else(|| Utc::now().fixed_offset()),
+        items,
+    })
+}
+
+async fn serve(
+    state: &AppState,
+    headers: &HeaderMap,
+    query: &V1FeedQuery,
+    format: FeedFormat,
+) -> Result<Response, ErrorResponse> {
+    let content_type = match format {
+        FeedFormat::Rss => "application/rss+xml; charset=utf-8",
+        FeedFormat::Atom => "application/atom+xml; charset=utf-8",
+        FeedFormat::Json => "application/feed+json; charset=utf-8",
+    };
+
+    let scope = resolve_scope(state, query).await?;
+    let (newest, total) = feed_version(state, &scope).await?;
+    let etag = compute_etag(format, query, newest, total);
+
+    if is_not_modified(headers, &etag, newest) {
+        return Ok(build_response(
+            StatusCode::NOT_MODIFIED,
+            content_type,
+            &etag,
+            newest,
+            String::new(),
+        ));
+    }
+
+    let feed = load_feed(state, format, query, scope, newest).await?;
+    let body = match format {
+        FeedFormat::Rss => render_rss(&feed),
+        FeedFormat::Atom => render_atom(&feed),
+        FeedFormat::Json => render_json(&feed),
+    };
+
+    Ok(build_response(
+        StatusCode::OK,
+        content_type,
+        &etag,
+        newest,
+        body,
+    ))
+}
+
+fn render_rss(feed: &Feed) -> String {
+    let mut xml = String::new();
+    xml.push_str(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+    xml.push_str(
+        "<rss version=\"2.0\" xmlns:content=\"http://purl.org/rss/1.0/modules/content/\" xmlns:atom=\"http://www.w3.org/2005/Atom\" xmlns:dc=\"http://purl.org/dc/elements/1.1/\"><channel>",
+    );
+    xml.push_str(&format!("<title>{}</title>", xml_escape(&feed.title)));
+    xml.push_str(&format!("<link>{}</link>", xml_escape(&feed.home_url)));
+    xml.push_str(&format!(
+        "<description>{}</description>",
+        xml_escape(&feed.description)
+    ));
+    xml.push_str(&format!(
+        r#"<atom:link rel="self" type="application/rss+xml" href="{}" />"#,
+        xml_escape(&feed.self_url)
+    ));
+    for hub in &feed.hubs {
+        xml.push_str(&format!(
+            r#"<atom:link rel="hub" href="{}" />"#,
+            xml_escape(hub)
+        ));
+    }
+    xml.push_str(&format!(
+        "<lastBuildDate>{}</lastBuildDate>",
+        feed.updated.to_rfc2822()
+    ));
+    if let Some(language) = &feed.language {
+        xml.push_str(&format!("<language>{}</language>", xml_escape(language)));
+    }
+    xml.push_str("<generator>ruxlog</generator>");
+
+    for item in &feed.items {
+        xml.push_str("<item>");
+        xml.push_str(&format!("<title>{}</title>", xml_escape(&item.title)));
+        xml.push_str(&format!("<link>{}</link>", xml_escape(&item.url)));
+        xml.push_str(&format!(
+            "<guid isPermaLink=\"true\">{}</guid>",
+            xml_escape(&item.url)
+        ));
+        xml.push_str(&format!(
+            "<pubDate>{}</pubDate>",
+            item.published.to_rfc2822()
+        ));
+        for author in &item.authors {
+            xml.push_str(&format!("<dc:creator>{}</dc:creator>", xml_escape(author)));
+        }
+        for tag in &item.tags {
+            xml.push_str(&format!("<category>{}</category>", xml_escape(tag)));
+        }
+        xml.push_str(&format!(
+            "<description>{}</description>",
+            xml_escape(&item.summary)
+        ));
+        if let Some(html) = &item.html {
+            xml.push_str(&format!(
+                "<content:encoded>{}</content:encoded>",
+                cdata(html)
+            ));
+        }
+        xml.push_str("</item>");
+    }
+
+    xml.push_str("</channel></rss>");
+    xml
+}
+
+fn render_atom(feed: &Feed) -> String {
+    let feed_id = format!("tag:{},feed:{}", feed.home_url, feed.self_url);
+
+    let mut xml = String::new();
+    xml.push_str(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+    match &feed.language {
+        Some(language) => xml.push_str(&format!(
+            r#"<feed xmlns="http://www.w3.org/2005/Atom" xml:lang="{}">"#,
+            xml_escape(language)
+        )),
+        None => xml.push_str(r#"<feed xmlns="http://www.w3.org/2005/Atom">"#),
+    }
+    xml.push_str(&format!("<title>{}</title>", xml_escape(&feed.title)));
+    xml.push_str(&format!(
+        "<subtitle>{}</subtitle>",
+        xml_escape(&feed.description)
+    ));
+    xml.push_str(&format!("<id>{}</id>", xml_escape(&feed_id)));
+    xml.push_str(&format!("<updated>{}</updated>", feed.updated.to_rfc3339()));
+    xml.push_str(&format!(
+        r#"<link rel="self" href="{}" />"#,
+        xml_escape(&feed.self_url)
+    ));
+    xml.push_str(&format!(
+        r#"<link href="{}" />"#,
+        xml_escape(&feed.home_url)
+    ));
+    for hub in &feed.hubs {
+        xml.push_str(&format!(r#"<link rel="hub" href="{}" />"#, xml_escape(hub)));
+    }
+
+    for item in &feed.items {
+        xml.push_str("<entry>");
+        xml.push_str(&format!("<title>{}</title>", xml_escape(&item.title)));
+        xml.push_str(&format!("<id>{}</id>", xml_escape(&item.url)));
+        xml.push_str(&format!(
+            r#"<link rel="alternate" href="{}" />"#,
+            xml_escape(&item.url)
+        ));
+        xml.push_str(&format!(
+            "<published>{}</published>",
+            item.published.to_rfc3339()
+        ));
+        xml.push_str(&format!(
+            "<updated>{}</updated>",
+            item.modified.to_rfc3339()
+        ));
+        if item.authors.is_empty() {
+            xml.push_str(&format!(
+                "<author><name>{}</name></author>",
+                xml_escape(&feed.title)
+            ));
+        }
+        for author in &item.authors {
+            xml.push_str(&format!(
+                "<author><name>{}</name></author>",
+                xml_escape(author)
+            ));
+        }
+        for tag in &item.tags {
+            xml.push_str(&format!(r#"<category term="{}" />"#, xml_escape(tag)));
+        }
+        xml.push_str(&format!("<summary>{}</summary>", xml_escape(&item.summary)));
+        if let Some(html) = &item.html {
+            xml.push_str(&format!(
+                r#"<content type="html">{}</content>"#,
+                xml_escape(html)
+            ));
+        }
+        xml.push_str("</entry>");
+    }
+
+    xml.push_str("</feed>");
+    xml
+}
+
+/// JSON Feed 1.1 (https://www.jsonfeed.org/version/1.1/).
+fn render_json(feed: &Feed) -> String {
+    let items: Vec<Value> = feed
+        .items
+        .iter()
+        .map(|item| {
+            let mut entry = Map::new();
+            entry.insert("id".into(), json!(item.url));
+            entry.insert("url".into(), json!(item.url));
+            entry.insert("title".into(), json!(item.title));
+            match &item.html {
+                Some(html) => {
+                    entry.insert("content_html".into(), json!(html));
+                    entry.insert("summary".into(), json!(item.summary));
+                }
+                None => {
+                    entry.insert("content_text".into(), json!(item.summary));
+                }
+            }
+            entry.insert("date_published".into(), json!(item.published.to_rfc3339()));
+            entry.insert("date_modified".into(), json!(item.modified.to_rfc3339()));
+            if !item.authors.is_empty() {
+                let authors: Vec<Value> = item
+                    .authors
+                    .iter()
+                    .map(|name| json!({ "name": name }))
+                    .collect();
+                entry.insert("authors".into(), json!(authors));
+            }
+            if !item.tags.is_empty() {
+                entry.insert("tags".into(), json!(item.tags));
+            }
+            Value::Object(entry)
+        })
+        .collect();
+
+    let mut body = json!({
+        "version": "https://jsonfeed.org/version/1.1",
+        "title": feed.title,
+        "description": feed.description,
+        "home_page_url": feed.home_url,
+        "feed_url": feed.self_url,
+        "items": items,
+    });
+    if let Some(language) = &feed.language {
+        body["language"] = json!(language);
+    }
+    if !feed.hubs.is_empty() {
+        body["hubs"] = feed
+            .hubs
+            .iter()
+            .map(|hub| json!({ "type": "WebSub", "url": hub }))
+            .collect();
+    }
+
+    body.to_string()
+}
+
+#[debug_handler]
+#[instrument(skip(state, headers))]
+pub async fn rss(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    ValidatedQuery(query): ValidatedQuery<V1FeedQuery>,
+) -> Result<Response, ErrorResponse> {
+    serve(&state, &headers, &query, FeedFormat::Rss).await
+}
+
+#[debug_handler]
+#[instrument(skip(state, headers))]
+pub async fn atom(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    ValidatedQuery(query): ValidatedQuery<V1FeedQuery>,
+) -> Result<Response, ErrorResponse> {
+    serve(&state, &headers, &query, FeedFormat::Atom).await
+}
+
+#[debug_handler]
+#[instrument(skip(state, headers))]
+pub async fn json_feed(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    ValidatedQuery(query): ValidatedQuery<V1FeedQuery>,
+) -> Result<Response, ErrorResponse> {
+    serve(&state, &headers, &query, FeedFormat::Json).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(name: header::HeaderName, value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(name, HeaderValue::from_str(value).unwrap());
+        headers
+    }
+
+    #[test]
+    fn if_none_match_uses_weak_comparison() {
+        let etag = "W/\"abc\"";
+        assert!(is_not_modified(
+            &headers(header::IF_NONE_MATCH, "\"xyz\", \"abc\""),
+            etag,
+            None
+        ));
+        assert!(is_not_modified(
+            &headers(header::IF_NONE_MATCH, "*"),
+            etag,
+            None
+        ));
+        assert!(!is_not_modified(
+            &headers(header::IF_NONE_MATCH, "W/\"xyz\""),
+            etag,
+            None
+        ));
+    }
+
+    #[test]
+    fn if_modified_since_compares_whole_seconds() {
+        let modified = DateTime::parse_from_rfc3339("2024-05-01T10:00:00.750Z").unwrap();
+        let since = http_date(modified);
+        assert!(is_not_modified(
+            &headers(header::IF_MODIFIED_SINCE, &since),
+            "W/\"abc\"",
+            Some(modified)
+        ));
+        assert!(!is_not_modified(
+            &headers(header::IF_MODIFIED_SINCE, "Wed, 01 May 2024 09:59:59 GMT"),
+            "W/\"abc\"",
+            Some(modified)
+        ));
+    }
+}
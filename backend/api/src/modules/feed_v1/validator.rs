@@ -0,0 +1,68 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::{
+    services::websub,
+    utils::{normalize_locale, validate_locale},
+};
+
+#[derive(Debug, Deserialize, Serialize, Validate, Clone, Default, JsonSchema)]
+pub struct V1FeedQuery {
+    #[validate(range(min = 1, max = 100))]
+    pub limit: Option<u64>,
+    /// Include the rendered post body in every item (default `true`);
+    /// `false` keeps items to the excerpt/summary.
+    pub full: Option<bool>,
+    /// Category slug.
+    #[validate(length(min = 1, max = 255))]
+    pub category: Option<String>,
+    /// Tag slug.
+    #[validate(length(min = 1, max = 255))]
+    pub tag: Option<String>,
+    /// Author user ID.
+    pub author: Option<i32>,
+    /// Series slug.
+    #[validate(length(min = 1, max = 255))]
+    pub series: Option<String>,
+    /// Only posts in this locale.
+    #[validate(custom(function = "validate_locale"))]
+    pub locale: Option<String>,
+    /// With `locale`, also include default-locale posts that have no
+    /// translation in it.
+    pub fallback: Option<bool>,
+}
+
+impl V1FeedQuery {
+    pub fn limit(&self) -> u64 {
+        self.limit.unwrap_or(20).min(100)
+    }
+
+    pub fn full(&self) -> bool {
+        self.full.unwrap_or(true)
+    }
+
+    pub fn locale(&self) -> Option<String> {
+        self.locale.as_deref().and_then(normalize_locale)
+    }
+
+    pub fn fallback(&self) -> bool {
+        self.fallback.unwrap_or(false)
+    }
+
+    /// Canonical query string identifying the scope (empty for the site feed).
+    /// Used for self links and must match the topics pinged to WebSub hubs.
+    pub fn scope_query(&self) -> String {
+        let mut query = websub::scope_query(
+            self.category.as_deref(),
+            self.tag.as_deref(),
+            self.author,
+            self.series.as_deref(),
+            self.locale().as_deref(),
+        );
+        if self.locale.is_some() && self.fallback() {
+            query.push_str("&fallback=true");
+        }
+        query
+    }
+}
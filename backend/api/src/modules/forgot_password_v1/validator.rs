@@ -1,13 +1,15 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use validator::Validate;
 
-#[derive(Debug, Deserialize, Serialize, Validate)]
+#[derive(Debug, Deserialize, Serialize, Validate, JsonSchema)]
 pub struct V1GeneratePayload {
     #[validate(email)]
     pub email: String,
 }
 
-#[derive(Debug, Deserialize, Serialize, Validate)]
+#[derive(Debug, Deserialize, Serialize, Validate, JsonSchema)]
+#[schemars(rename = "V1ForgotPasswordVerifyPayload")]
 pub struct V1VerifyPayload {
     #[validate(length(min = 6, max = 6))]
     pub code: String,
@@ -15,7 +17,7 @@ pub struct V1VerifyPayload {
     pub email: String,
 }
 
-#[derive(Debug, Deserialize, Serialize, Validate)]
+#[derive(Debug, Deserialize, Serialize, Validate, JsonSchema)]
 pub struct V1ResetPayload {
     #[validate(length(min = 6, max = 6))]
     pub code: String,
@@ -14,7 +14,7 @@ use crate::{
     db::sea_models::{user, user_session},
     error::{ErrorCode, ErrorResponse},
     extractors::ValidatedQuery,
-    services::auth::AuthSession,
+    services::{auth::AuthSession, settings},
     AppState,
 };
 
@@ -112,9 +112,10 @@ pub async fn google_callback(
     info!(user_id = user.id, "Google login successful");
     tracing::Span::current().record("result", "success");
 
-    let frontend_url =
-        std::env::var("FRONTEND_URL").unwrap_or_else(|_| "http://localhost:3000".to_string());
-    let redirect_url = format!("{}/auth/google/success", frontend_url);
+    let redirect_url = format!(
+        "{}/auth/google/success",
+        settings::FRONTEND_URL.env_string()
+    );
 
     Ok(Redirect::temporary(&redirect_url))
 }
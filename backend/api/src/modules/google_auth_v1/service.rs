@@ -1,22 +1,27 @@
 use oauth2::{basic::BasicClient, AuthUrl, ClientId, ClientSecret, RedirectUrl, TokenUrl};
 
-use crate::error::{ErrorCode, ErrorResponse};
+use crate::{
+    error::{ErrorCode, ErrorResponse},
+    services::settings::{self, Setting},
+};
+
+fn not_configured(setting: &Setting) -> ErrorResponse {
+    ErrorResponse::new(ErrorCode::InternalServerError)
+        .with_message(format!("{} not configured", setting.key))
+}
 
 pub fn get_google_oauth_client() -> Result<BasicClient, ErrorResponse> {
-    let client_id = std::env::var("GOOGLE_CLIENT_ID").map_err(|_| {
-        ErrorResponse::new(ErrorCode::InternalServerError)
-            .with_message("GOOGLE_CLIENT_ID not configured")
-    })?;
+    let client_id = settings::GOOGLE_CLIENT_ID
+        .env_value()
+        .ok_or_else(|| not_configured(&settings::GOOGLE_CLIENT_ID))?;
 
-    let client_secret = std::env::var("GOOGLE_CLIENT_SECRET").map_err(|_| {
-        ErrorResponse::new(ErrorCode::InternalServerError)
-            .with_message("GOOGLE_CLIENT_SECRET not configured")
-    })?;
+    let client_secret = settings::GOOGLE_CLIENT_SECRET
+        .env_value()
+        .ok_or_else(|| not_configured(&settings::GOOGLE_CLIENT_SECRET))?;
 
-    let redirect_url = std::env::var("GOOGLE_REDIRECT_URI").map_err(|_| {
-        ErrorResponse::new(ErrorCode::InternalServerError)
-            .with_message("GOOGLE_REDIRECT_URI not configured")
-    })?;
+    let redirect_url = settings::GOOGLE_REDIRECT_URI
+        .env_value()
+        .ok_or_else(|| not_configured(&settings::GOOGLE_REDIRECT_URI))?;
 
     let auth_url = AuthUrl::new("https://accounts.google.com/o/oauth2/v2/auth".to_string())
         .map_err(|e| {
@@ -1,7 +1,8 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use validator::Validate;
 
-#[derive(Debug, Deserialize, Serialize, Validate)]
+#[derive(Debug, Deserialize, Serialize, Validate, JsonSchema)]
 pub struct GoogleCallbackQuery {
     #[validate(length(min = 1))]
     pub code: String,
@@ -9,7 +10,7 @@ pub struct GoogleCallbackQuery {
     pub state: String,
 }
 
-#[derive(Debug, Deserialize, Serialize, Validate)]
+#[derive(Debug, Deserialize, Serialize, Validate, JsonSchema)]
 pub struct GoogleExchangeRequest {
     #[validate(length(min = 1))]
     pub code: String,
@@ -17,7 +18,7 @@ pub struct GoogleExchangeRequest {
     pub state: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct GoogleUserInfo {
     pub id: String,
     pub email: String,
@@ -0,0 +1,128 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use axum_macros::debug_handler;
+use bytes::Bytes;
+use serde_json::json;
+use tracing::{error, info, instrument};
+
+use crate::{
+    db::sea_models::import_job::{self, ImportSource},
+    error::{ErrorCode, ErrorResponse},
+    extractors::{ValidatedJson, ValidatedMultipart},
+    services::{auth::AuthSession, blog_import},
+    AppState,
+};
+
+use super::validator::{V1BlogImportOptions, V1ImportListQuery};
+
+/// Import a WordPress WXR export (multipart `file` plus options)
+#[debug_handler]
+#[instrument(skip(state, auth, multipart), fields(user_id, job_id, file_size))]
+pub async fn import_wordpress(
+    State(state): State<AppState>,
+    auth: AuthSession,
+    multipart: ValidatedMultipart,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    start_import(state, auth, multipart, ImportSource::Wordpress).await
+}
+
+/// Import a Ghost JSON export (multipart `file` plus options)
+#[debug_handler]
+#[instrument(skip(state, auth, multipart), fields(user_id, job_id, file_size))]
+pub async fn import_ghost(
+    State(state): State<AppState>,
+    auth: AuthSession,
+    multipart: ValidatedMultipart,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    start_import(state, auth, multipart, ImportSource::Ghost).await
+}
+
+/// Check the status (and report) of an import job
+#[debug_handler]
+#[instrument(skip(state))]
+pub async fn status(
+    State(state): State<AppState>,
+    Path(job_id): Path<i32>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let job = import_job::Entity::find_by_id(&state.sea_db, job_id)
+        .await?
+        .ok_or_else(|| {
+            ErrorResponse::new(ErrorCode::RecordNotFound).with_message("Import job not found")
+        })?;
+    Ok((StatusCode::OK, Json(json!(job))))
+}
+
+/// List import jobs, newest first
+#[debug_handler]
+#[instrument(skip(state, payload))]
+pub async fn list(
+    State(state): State<AppState>,
+    payload: ValidatedJson<V1ImportListQuery>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let query = payload.0.into_query();
+    let page = query.page.unwrap_or(1);
+
+    let (items, total) = import_job::Entity::list(&state.sea_db, query).await?;
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "data": items,
+            "total": total,
+            "per_page": import_job::Entity::PER_PAGE,
+            "page": page,
+        })),
+    ))
+}
+
+async fn start_import(
+    state: AppState,
+    auth: AuthSession,
+    mut multipart: ValidatedMultipart,
+    source: ImportSource,
+) -> Result<(StatusCode, Json<serde_json::Value>), ErrorResponse> {
+    let user = auth.user.unwrap();
+    tracing::Span::current().record("user_id", user.id);
+
+    let mut options = V1BlogImportOptions::default();
+    let mut file: Option<(Option<String>, Bytes)> = None;
+
+    while let Some(field) = multipart.next_field().await.map_err(|err| {
+        error!(error = %err, "Failed to read multipart field");
+        ErrorResponse::new(ErrorCode::ValidationError).with_details(err.to_string())
+    })? {
+        let field_name = field.name().unwrap_or_default().to_string();
+        if field_name == "file" {
+            let name = field.file_name().map(str::to_string);
+            let bytes = field.bytes().await.map_err(|err| {
+                ErrorResponse::new(ErrorCode::FileUploadError)
+                    .with_message("Failed to read uploaded file")
+                    .with_details(err.to_string())
+            })?;
+            file = Some((name, bytes));
+        } else {
+            let value = field.text().await.map_err(|err| {
+                ErrorResponse::new(ErrorCode::InvalidFormat)
+                    .with_message("Failed to read accompanying form field")
+                    .with_details(err.to_string())
+            })?;
+            options
+                .apply_field(&field_name, &value)
+                .map_err(|msg| ErrorResponse::new(ErrorCode::InvalidValue).with_message(&msg))?;
+        }
+    }
+
+    let (file_name, bytes) = file.ok_or_else(|| {
+        ErrorResponse::new(ErrorCode::MissingRequiredField).with_message("Missing file field")
+    })?;
+    tracing::Span::current().record("file_size", bytes.len());
+
+    let job = blog_import::start(&state, source, file_name, bytes, options, user.id).await?;
+
+    tracing::Span::current().record("job_id", job.id);
+    info!(job_id = job.id, source = %source, dry_run = job.dry_run, "Blog import queued");
+    Ok((StatusCode::ACCEPTED, Json(json!(job))))
+}
@@ -0,0 +1,21 @@
+pub mod controller;
+pub mod validator;
+
+use axum::{extract::DefaultBodyLimit, middleware, routing::post, Router};
+
+use crate::{config, middlewares::auth_guard, services::permission_service, AppState};
+
+pub fn routes() -> Router<AppState> {
+    let upload_limited = Router::<AppState>::new()
+        .route("/wordpress", post(controller::import_wordpress))
+        .route("/ghost", post(controller::import_ghost))
+        .layer(DefaultBodyLimit::max(config::body_limits::BLOG_IMPORT));
+
+    Router::<AppState>::new()
+        .route("/status/{job_id}", post(controller::status))
+        .route("/list", post(controller::list))
+        .merge(upload_limited)
+        .route_layer(middleware::from_fn(auth_guard::verified_with_permission(
+            permission_service::IMPORT_RUN,
+        )))
+}
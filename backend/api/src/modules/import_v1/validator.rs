@@ -0,0 +1,93 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::db::sea_models::import_job::{ImportJobQuery, ImportJobStatus, ImportSource};
+
+/// Form fields accompanying a WordPress or Ghost export upload.
+#[derive(Debug, Deserialize, Serialize, Validate, Clone, JsonSchema)]
+pub struct V1BlogImportOptions {
+    /// Only report what would be imported.
+    pub dry_run: bool,
+    /// Download referenced images into the media library.
+    pub import_media: bool,
+    /// Import approved and held comments (WordPress only).
+    pub import_comments: bool,
+    /// Category for posts that have none; defaults to "Uncategorized".
+    pub category_id: Option<i32>,
+    /// Public URL of the old site, used to resolve relative and
+    /// `__GHOST_URL__` image links.
+    pub site_url: Option<String>,
+}
+
+impl Default for V1BlogImportOptions {
+    fn default() -> Self {
+        Self {
+            dry_run: false,
+            import_media: true,
+            import_comments: true,
+            category_id: None,
+            site_url: None,
+        }
+    }
+}
+
+fn parse_bool(name: &str, value: &str) -> Result<bool, String> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "1" | "true" | "yes" | "on" => Ok(true),
+        "0" | "false" | "no" | "off" => Ok(false),
+        other => Err(format!("Invalid {}: {}", name, other)),
+    }
+}
+
+impl V1BlogImportOptions {
+    pub fn apply_field(&mut self, name: &str, value: &str) -> Result<(), String> {
+        match name {
+            "dry_run" => self.dry_run = parse_bool(name, value)?,
+            "import_media" => self.import_media = parse_bool(name, value)?,
+            "import_comments" => self.import_comments = parse_bool(name, value)?,
+            "category_id" => {
+                if value.trim().is_empty() {
+                    self.category_id = None;
+                } else {
+                    self.category_id = Some(
+                        value
+                            .trim()
+                            .parse::<i32>()
+                            .map_err(|_| format!("Invalid category_id: {}", value.trim()))?,
+                    );
+                }
+            }
+            "site_url" => {
+                let url = value.trim();
+                if url.is_empty() {
+                    self.site_url = None;
+                } else if url.starts_with("http://") || url.starts_with("https://") {
+                    self.site_url = Some(url.trim_end_matches('/').to_string());
+                } else {
+                    return Err(format!("Invalid site_url: {}", url));
+                }
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, Validate, JsonSchema)]
+pub struct V1ImportListQuery {
+    pub page: Option<u64>,
+    pub source: Option<ImportSource>,
+    pub status: Option<ImportJobStatus>,
+}
+
+impl V1ImportListQuery {
+    pub fn into_query(self) -> ImportJobQuery {
+        ImportJobQuery {
+            page: self.page,
+            source: self.source,
+            status: self.status,
+        }
+    }
+}
@@ -1,6 +1,5 @@
 use std::collections::{BTreeSet, HashMap};
 
-use aws_sdk_s3::primitives::ByteStream;
 use axum::{
     extract::{Path, State},
     http::StatusCode,
@@ -9,26 +8,26 @@ use axum::{
 };
 use axum_macros::debug_handler;
 use bytes::Bytes;
-use chrono::{Datelike, Utc};
 use sea_orm::{prelude::DateTimeWithTimeZone, ColumnTrait, EntityTrait, QueryFilter, QueryOrder};
 use serde::Serialize;
 use serde_json::json;
-use sha2::{Digest, Sha256};
-use uuid::Uuid;
 
 use crate::{
     config,
     db::sea_models::{
         category::{self, Model as CategoryModel},
-        media::{self, Entity as Media, NewMedia},
+        media::{self, Entity as Media},
         media_usage,
-        media_variant::{Entity as MediaVariant, NewMediaVariant},
         post::{self, Model as PostModel},
         user::{self, Model as UserModel},
     },
     error::{ErrorCode, ErrorResponse},
     extractors::{ValidatedJson, ValidatedMultipart},
-    services::{auth::AuthSession, image_optimizer},
+    services::{
+        auth::AuthSession,
+        media_storage,
+        permission_service::{self, PermissionService},
+    },
     AppState,
 };
 use tracing::{debug, error, info, instrument, warn};
@@ -139,18 +138,7 @@ struct MediaUsageGroup {
 }
 
 #[debug_handler]
-#[instrument(
-    skip(state, auth, multipart),
-    fields(
-        user_id,
-        file_size,
-        content_hash,
-        is_duplicate,
-        is_optimized,
-        variant_count,
-        result
-    )
-)]
+#[instrument(skip(state, auth, multipart), fields(user_id, file_size, result))]
 pub async fn create(
     State(state): State<AppState>,
     auth: AuthSession,
@@ -224,227 +212,24 @@ pub async fn create(
 
     tracing::Span::current().record("file_size", file_bytes.len() as i64);
 
-    let mut hasher = Sha256::new();
-    hasher.update(&file_bytes);
-    let content_hash = format!("{:x}", hasher.finalize());
-
-    debug!(content_hash = %content_hash, "File hash calculated");
-    tracing::Span::current().record("content_hash", &content_hash);
+    let stored = media_storage::store(
+        &state,
+        media_storage::UploadedFile {
+            bytes: file_bytes,
+            original_name,
+            mime_type,
+        },
+        metadata,
+        Some(uploader.id),
+    )
+    .await?;
 
-    if let Some(existing) = Media::find_by_hash(&state.sea_db, &content_hash).await? {
-        info!(
-            media_id = existing.id,
-            content_hash = %content_hash,
-            "Duplicate file detected, returning existing media"
-        );
-        tracing::Span::current().record("is_duplicate", true);
+    if stored.duplicate {
         tracing::Span::current().record("result", "duplicate");
-        return Ok((StatusCode::OK, Json(json!(existing))));
-    }
-
-    tracing::Span::current().record("is_duplicate", false);
-
-    // Derive useful metadata if it was not supplied
-    if metadata.width.is_none() || metadata.height.is_none() {
-        if let Ok(dimensions) = imagesize::blob_size(&file_bytes) {
-            debug!(
-                width = dimensions.width,
-                height = dimensions.height,
-                "Image dimensions detected"
-            );
-            metadata.width = metadata
-                .width
-                .or_else(|| i32::try_from(dimensions.width).ok());
-            metadata.height = metadata
-                .height
-                .or_else(|| i32::try_from(dimensions.height).ok());
-        }
-    }
-
-    let mut extension = infer_extension(original_name.as_deref(), mime_type.as_deref());
-    let mut content_type = mime_type
-        .clone()
-        .unwrap_or_else(|| "application/octet-stream".to_string());
-    let mut final_bytes = file_bytes.clone();
-    let mut variants_to_upload = Vec::new();
-    let mut is_optimized = false;
-    let mut optimized_at = None;
-    struct PreparedVariant {
-        object_key: String,
-        mime_type: String,
-        width: Option<i32>,
-        height: Option<i32>,
-        size: i64,
-        extension: Option<String>,
-        quality: Option<i32>,
-        variant_type: String,
-    }
-    let mut prepared_variants: Vec<PreparedVariant> = Vec::new();
-
-    if content_type.starts_with("image/") {
-        let optimization_request = image_optimizer::OptimizationRequest {
-            bytes: &file_bytes,
-            metadata: &metadata,
-            reference: metadata.reference_type,
-            original_mime: mime_type.as_deref(),
-            original_extension: extension.as_deref(),
-        };
-
-        let optimization_outcome =
-            match image_optimizer::optimize(&state.optimizer, optimization_request) {
-                Ok(outcome) => outcome,
-                Err(err) => {
-                    warn!("image optimizer error: {}", err);
-                    image_optimizer::OptimizationOutcome::Skipped(
-                        image_optimizer::SkipReason::DecodeFailed,
-                    )
-                }
-            };
-
-        if let image_optimizer::OptimizationOutcome::Optimized(result) = optimization_outcome {
-            final_bytes = result.original.bytes.clone();
-            content_type = result.original.mime_type.clone();
-            extension = Some(result.original.extension.clone());
-
-            if let Ok(width) = i32::try_from(result.original.width) {
-                metadata.width = Some(width);
-            }
-            if let Ok(height) = i32::try_from(result.original.height) {
-                metadata.height = Some(height);
-            }
-
-            variants_to_upload = result.variants;
-            is_optimized = true;
-            optimized_at = Some(Utc::now().fixed_offset());
-        }
-    }
-
-    let size_bytes = i64::try_from(final_bytes.len()).map_err(|_| {
-        ErrorResponse::new(ErrorCode::InvalidValue)
-            .with_message("File size exceeds supported range")
-    })?;
-
-    let object_key = build_object_key(extension.as_deref());
-    let base_object_key = object_key
-        .rsplit_once('.')
-        .map(|(prefix, _)| prefix.to_string())
-        .unwrap_or_else(|| object_key.clone());
-
-    let byte_stream = ByteStream::from(final_bytes.clone().to_vec());
-
-    state
-        .s3_client
-        .put_object()
-        .bucket(&state.object_storage.bucket)
-        .key(&object_key)
-        .body(byte_stream)
-        .content_type(&content_type)
-        .send()
-        .await
-        .map_err(|err| {
-            ErrorResponse::new(ErrorCode::StorageError)
-                .with_message("Failed to persist media to storage")
-                .with_details(err.to_string())
-        })?;
-
-    for variant in variants_to_upload {
-        let suffix = match variant.label {
-            image_optimizer::VariantLabel::Width(width) => format!("@{}w", width),
-            image_optimizer::VariantLabel::Lqip => "@lqip".to_string(),
-            image_optimizer::VariantLabel::Original => continue,
-        };
-
-        let variant_key = format!(
-            "{}{}{}",
-            base_object_key,
-            suffix,
-            if variant.extension.is_empty() {
-                String::new()
-            } else {
-                format!(".{}", variant.extension)
-            }
-        );
-
-        let size_bytes = i64::try_from(variant.bytes.len()).map_err(|_| {
-            ErrorResponse::new(ErrorCode::InvalidValue)
-                .with_message("Variant size exceeds supported range")
-        })?;
-
-        prepared_variants.push(PreparedVariant {
-            object_key: variant_key.clone(),
-            mime_type: variant.mime_type.clone(),
-            width: i32::try_from(variant.width).ok(),
-            height: i32::try_from(variant.height).ok(),
-            size: size_bytes,
-            extension: if variant.extension.is_empty() {
-                None
-            } else {
-                Some(variant.extension.clone())
-            },
-            quality: variant.quality.map(|q| i32::from(q)),
-            variant_type: label_to_variant_type(&variant.label),
-        });
-
-        if let Err(err) = state
-            .s3_client
-            .put_object()
-            .bucket(&state.object_storage.bucket)
-            .key(&variant_key)
-            .body(ByteStream::from(variant.bytes.to_vec()))
-            .content_type(&variant.mime_type)
-            .send()
-            .await
-        {
-            warn!(
-                "failed to upload optimized variant {}: {}",
-                variant_key, err
-            );
-        }
-    }
-
-    let public_url = format!(
-        "{}/{}",
-        state.object_storage.public_url.trim_end_matches('/'),
-        &object_key
-    );
-
-    let new_media = NewMedia {
-        object_key,
-        file_url: public_url,
-        mime_type: content_type,
-        width: metadata.width,
-        height: metadata.height,
-        size: size_bytes,
-        extension,
-        uploader_id: Some(uploader.id),
-        reference_type: metadata.reference_type,
-        content_hash: Some(content_hash),
-        is_optimized,
-        optimized_at,
-    };
-
-    let stored = Media::create(&state.sea_db, new_media).await?;
-
-    if !prepared_variants.is_empty() {
-        let records = prepared_variants
-            .into_iter()
-            .map(|variant| NewMediaVariant {
-                media_id: stored.id,
-                object_key: variant.object_key,
-                mime_type: variant.mime_type,
-                width: variant.width,
-                height: variant.height,
-                size: variant.size,
-                extension: variant.extension,
-                quality: variant.quality,
-                variant_type: variant.variant_type,
-            })
-            .collect();
-
-        MediaVariant::create_many(&state.sea_db, records).await?;
+        return Ok((StatusCode::OK, Json(json!(stored.media))));
     }
 
-    Ok((StatusCode::CREATED, Json(json!(stored))))
+    Ok((StatusCode::CREATED, Json(json!(stored.media))))
 }
 
 #[debug_handler]
@@ -645,7 +430,10 @@ pub async fn delete(
         })?;
 
     if let Some(owner_id) = media.uploader_id {
-        if owner_id != uploader.id {
+        if owner_id != uploader.id
+            && !PermissionService::allows(&state, &uploader, permission_service::MEDIA_DELETE_ANY)
+                .await
+        {
             return Err(ErrorResponse::new(ErrorCode::OperationNotAllowed)
                 .with_message("You can only delete media you uploaded"));
         }
@@ -674,38 +462,3 @@ pub async fn delete(
         })),
     ))
 }
-
-fn infer_extension(filename: Option<&str>, mime_type: Option<&str>) -> Option<String> {
-    if let Some(name) = filename {
-        if let Some((_, ext)) = name.rsplit_once('.') {
-            let ext = ext.trim().trim_matches('.');
-            if !ext.is_empty() {
-                return Some(ext.to_ascii_lowercase());
-            }
-        }
-    }
-
-    mime_type
-        .and_then(|mt| mt.rsplit_once('/'))
-        .map(|(_, ext)| ext.trim().to_ascii_lowercase())
-        .filter(|ext| !ext.is_empty())
-}
-
-fn build_object_key(extension: Option<&str>) -> String {
-    let now = Utc::now();
-    let prefix = format!("media/{}/{:02}", now.year(), now.month());
-    let base = format!("{}/{}", prefix, Uuid::new_v4());
-
-    match extension {
-        Some(ext) => format!("{}.{}", base, ext),
-        None => base,
-    }
-}
-
-fn label_to_variant_type(label: &image_optimizer::VariantLabel) -> String {
-    match label {
-        image_optimizer::VariantLabel::Width(width) => format!("{}w", width),
-        image_optimizer::VariantLabel::Lqip => "lqip".to_string(),
-        image_optimizer::VariantLabel::Original => "original".to_string(),
-    }
-}
@@ -1,3 +1,4 @@
+use schemars::JsonSchema;
 use sea_orm::prelude::DateTimeWithTimeZone;
 use serde::{Deserialize, Serialize};
 use validator::Validate;
@@ -5,7 +6,7 @@ use validator::Validate;
 use crate::db::sea_models::media::{MediaQuery, MediaReference};
 use crate::utils::SortParam;
 
-#[derive(Debug, Default, Deserialize, Serialize, Validate)]
+#[derive(Debug, Default, Deserialize, Serialize, Validate, JsonSchema)]
 pub struct MediaUploadMetadata {
     pub reference_type: Option<MediaReference>,
     pub width: Option<i32>,
@@ -53,7 +54,7 @@ impl MediaUploadMetadata {
     }
 }
 
-#[derive(Debug, Deserialize, Serialize, Validate)]
+#[derive(Debug, Deserialize, Serialize, Validate, JsonSchema)]
 pub struct V1MediaListQuery {
     pub page: Option<u64>,
     pub search: Option<String>,
@@ -87,7 +88,7 @@ impl V1MediaListQuery {
     }
 }
 
-#[derive(Debug, Deserialize, Serialize, Validate)]
+#[derive(Debug, Deserialize, Serialize, Validate, JsonSchema)]
 pub struct V1MediaUsageQuery {
     #[validate(length(min = 1, message = "media_ids must contain at least one id"))]
     pub media_ids: Vec<i32>,
@@ -1,20 +1,31 @@
 pub mod analytics_v1;
 pub mod auth_v1;
+pub mod backup_v1;
 pub mod category_v1;
 pub mod csrf_v1;
+pub mod docs_v1;
 
 pub mod email_verification_v1;
 pub mod feed_v1;
 pub mod forgot_password_v1;
 pub mod google_auth_v1;
+pub mod import_v1;
 pub mod media_v1;
 pub mod newsletter_v1;
+pub mod notification_v1;
 
 pub mod admin_acl_v1;
+pub mod admin_audit_v1;
+pub mod admin_events_v1;
+pub mod admin_job_v1;
+pub mod admin_permission_v1;
+pub mod admin_redirect_v1;
 pub mod admin_route_v1;
+pub mod admin_webhook_v1;
 pub mod post_comment_v1;
 pub mod post_v1;
 pub mod seed_v1;
+pub mod seo_v1;
 pub mod super_admin_v1;
 pub mod tag_v1;
 pub mod user_v1;
@@ -1,20 +1,21 @@
 use axum::{extract::State, http::StatusCode, response::IntoResponse, Json};
 use axum_macros::debug_handler;
 use lettre::{message::header::ContentType, AsyncTransport, Message};
-use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
 use serde_json::json;
 use tracing::{error, info, instrument, warn};
 use uuid::Uuid;
 
 use crate::{
     db::sea_models::newsletter_subscriber::{
-        Column as SubscriberColumn, Entity as SubscriberEntity, NewSubscriber, SubscriberStatus,
+        Entity as SubscriberEntity, NewSubscriber, SubscriberStatus,
     },
     error::{ErrorCode, ErrorResponse},
     extractors::ValidatedJson,
     services::{
         abuse_limiter::{limiter, AbuseLimiterConfig},
         auth::AuthSession,
+        jobs::{self, handlers::NewsletterSend},
+        settings,
     },
     AppState,
 };
@@ -95,11 +96,9 @@ pub async fn subscribe(
     match SubscriberEntity::create(&state.sea_db, new_sub).await {
         Ok(_model) => {
             info!(email = %email, "Newsletter subscription created");
-            let site_url =
-                std::env::var("SITE_URL").unwrap_or_else(|_| "http://localhost:8888".to_string());
             let confirm_url = format!(
                 "{}/newsletter/confirm?email={}&token={}",
-                site_url.trim_end_matches('/'),
+                settings::SITE_URL.env_string(),
                 urlencoding::encode(&email),
                 urlencoding::encode(&token)
             );
@@ -160,66 +159,31 @@ pub async fn unsubscribe(
 }
 
 #[debug_handler]
-#[instrument(skip(state, _auth, payload), fields(subject = %payload.subject))]
+#[instrument(skip(state, auth, payload), fields(subject = %payload.subject))]
 pub async fn send(
     State(state): State<AppState>,
-    _auth: AuthSession,
+    auth: AuthSession,
     payload: ValidatedJson<V1SendNewsletterPayload>,
 ) -> Result<impl IntoResponse, ErrorResponse> {
-    // Offload to background task
-    let subject = payload.subject.clone();
-    let text = payload.text.clone();
-    let html = payload.html.clone();
-    let state_cloned = state.clone();
-
-    tokio::spawn(
-        tracing::info_span!("newsletter_send_background", subject = %subject).in_scope(
-            || async move {
-                // Load confirmed subscribers and send best-effort
-                let Ok(subscribers) = SubscriberEntity::find()
-                    .filter(SubscriberColumn::Status.eq(SubscriberStatus::Confirmed))
-                    .all(&state_cloned.sea_db)
-                    .await
-                else {
-                    error!("Failed to load confirmed subscribers for newsletter send");
-                    tracing::Span::current().record("result", "db_error");
-                    return;
-                };
-
-                let total = subscribers.len();
-                info!(count = total, "Sending newsletter to subscribers");
-
-                let mut sent = 0u64;
-                let mut failed = 0u64;
-
-                for sub in subscribers {
-                    match send_mail(
-                        &state_cloned.mailer,
-                        &sub.email,
-                        &subject,
-                        html.as_deref(),
-                        Some(&text),
-                    )
-                    .await
-                    {
-                        Ok(_) => sent += 1,
-                        Err(_) => failed += 1,
-                    }
-                }
-
-                info!(sent, failed, total, "Newsletter send task completed");
-                tracing::Span::current().record("sent", sent);
-                tracing::Span::current().record("failed", failed);
-                tracing::Span::current().record("result", "completed");
-            },
-        ),
-    );
+    let payload = payload.0;
+    let mut new_job = jobs::job(&NewsletterSend {
+        subject: payload.subject,
+        text: payload.text,
+        html: payload.html,
+    });
+    if let Some(user) = &auth.user {
+        new_job = new_job.created_by(user.id);
+    }
+    let job = jobs::enqueue(&state, new_job).await?;
 
-    info!("Newsletter send task spawned");
+    info!(job_id = ?job.as_ref().map(|job| job.id), "Newsletter send queued");
 
     Ok((
         StatusCode::ACCEPTED,
-        Json(json!({ "message": "Newsletter send queued" })),
+        Json(json!({
+            "message": "Newsletter send queued",
+            "job_id": job.map(|job| job.id),
+        })),
     ))
 }
 
@@ -1,6 +1,6 @@
 use axum::{middleware, routing::post, Router};
 
-use crate::{middlewares::auth_guard, AppState};
+use crate::{middlewares::auth_guard, services::permission_service, AppState};
 
 pub mod controller;
 pub mod validator;
@@ -14,7 +14,9 @@ pub fn routes() -> Router<AppState> {
     let admin = Router::<AppState>::new()
         .route("/send", post(controller::send))
         .route("/subscribers/list", post(controller::list_subscribers))
-        .route_layer(middleware::from_fn(auth_guard::verified_with_role::<{ auth_guard::ROLE_ADMIN }>));
+        .route_layer(middleware::from_fn(auth_guard::verified_with_permission(
+            permission_service::NEWSLETTER_MANAGE,
+        )));
 
     public.merge(admin)
 }
@@ -1,3 +1,4 @@
+use schemars::JsonSchema;
 use sea_orm::prelude::DateTimeWithTimeZone;
 use serde::{Deserialize, Serialize};
 use validator::Validate;
@@ -5,14 +6,14 @@ use validator::Validate;
 use crate::{db::sea_models::newsletter_subscriber::SubscriberQuery, utils::SortParam};
 
 /// Subscribe to newsletter (double opt-in)
-#[derive(Debug, Deserialize, Serialize, Validate)]
+#[derive(Debug, Deserialize, Serialize, Validate, JsonSchema)]
 pub struct V1SubscribePayload {
     #[validate(email)]
     pub email: String,
 }
 
 /// Unsubscribe from newsletter
-#[derive(Debug, Deserialize, Serialize, Validate)]
+#[derive(Debug, Deserialize, Serialize, Validate, JsonSchema)]
 pub struct V1UnsubscribePayload {
     #[validate(email)]
     pub email: String,
@@ -23,7 +24,7 @@ pub struct V1UnsubscribePayload {
 pub type V1ConfirmPayload = V1UnsubscribePayload;
 
 /// Send a newsletter (admin)
-#[derive(Debug, Deserialize, Serialize, Validate)]
+#[derive(Debug, Deserialize, Serialize, Validate, JsonSchema)]
 pub struct V1SendNewsletterPayload {
     #[validate(length(min = 1, max = 200))]
     pub subject: String,
@@ -33,7 +34,7 @@ pub struct V1SendNewsletterPayload {
 }
 
 /// List subscribers (admin) with optional pagination and search
-#[derive(Debug, Deserialize, Serialize, Validate, Clone)]
+#[derive(Debug, Deserialize, Serialize, Validate, Clone, JsonSchema)]
 pub struct V1ListSubscribersQuery {
     pub page: Option<u64>,
     #[validate(length(min = 1, max = 100))]
@@ -0,0 +1,118 @@
+use axum::{extract::State, http::StatusCode, response::IntoResponse, Json};
+use axum_macros::debug_handler;
+use serde_json::json;
+use tracing::{error, info, instrument};
+
+use crate::{
+    db::sea_models::{
+        notification::{Entity as Notification, MarkReadResponse, NotificationType, UnreadCount},
+        notification_preference::Entity as NotificationPreference,
+    },
+    error::{ErrorCode, ErrorResponse},
+    extractors::ValidatedJson,
+    services::auth::AuthSession,
+    AppState,
+};
+
+use super::validator::{V1MarkReadPayload, V1NotificationListQuery, V1UpdatePreferencesPayload};
+
+#[debug_handler]
+#[instrument(skip(state, auth, payload), fields(user_id = auth.user.as_ref().map(|u| u.id)))]
+pub async fn list(
+    State(state): State<AppState>,
+    auth: AuthSession,
+    payload: Option<ValidatedJson<V1NotificationListQuery>>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let user = auth.user.unwrap();
+    let query = payload.map(|p| p.0).unwrap_or_default().into_query();
+    let page = query.page.filter(|page| *page > 0).unwrap_or(1);
+
+    match Notification::list(&state.sea_db, user.id, query).await {
+        Ok((notifications, total)) => Ok((
+            StatusCode::OK,
+            Json(json!({
+                "data": notifications,
+                "total": total,
+                "per_page": Notification::PER_PAGE,
+                "page": page,
+            })),
+        )),
+        Err(err) => {
+            error!(user_id = user.id, error = ?err, "Failed to list notifications");
+            Err(err)
+        }
+    }
+}
+
+#[debug_handler]
+#[instrument(skip(state, auth, payload), fields(user_id = auth.user.as_ref().map(|u| u.id)))]
+pub async fn mark_read(
+    State(state): State<AppState>,
+    auth: AuthSession,
+    payload: Option<ValidatedJson<V1MarkReadPayload>>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let user = auth.user.unwrap();
+    let ids = payload.and_then(|p| p.0.ids);
+
+    let updated = Notification::mark_read(&state.sea_db, user.id, ids).await?;
+    info!(user_id = user.id, updated, "Notifications marked read");
+    Ok((StatusCode::OK, Json(MarkReadResponse { updated })))
+}
+
+#[debug_handler]
+pub async fn unread_count(
+    State(state): State<AppState>,
+    auth: AuthSession,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let user = auth.user.unwrap();
+    let unread = Notification::unread_count(&state.sea_db, user.id).await?;
+    Ok(Json(UnreadCount { unread }))
+}
+
+#[debug_handler]
+pub async fn preferences(
+    State(state): State<AppState>,
+    auth: AuthSession,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let user = auth.user.unwrap();
+    let preferences = NotificationPreference::for_user(&state.sea_db, user.id).await?;
+    Ok(Json(json!(preferences)))
+}
+
+#[debug_handler]
+#[instrument(skip(state, auth, payload), fields(user_id = auth.user.as_ref().map(|u| u.id)))]
+pub async fn update_preferences(
+    State(state): State<AppState>,
+    auth: AuthSession,
+    payload: ValidatedJson<V1UpdatePreferencesPayload>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let user = auth.user.unwrap();
+    let changes = payload.0.into_update();
+
+    let unknown: Vec<&String> = changes
+        .muted_types
+        .as_deref()
+        .unwrap_or_default()
+        .iter()
+        .filter(|kind| NotificationType::parse(kind).is_none())
+        .collect();
+    if !unknown.is_empty() {
+        return Err(ErrorResponse::new(ErrorCode::InvalidValue)
+            .with_message("Unknown notification types")
+            .with_context(json!({
+                "muted_types": unknown,
+                "allowed": NotificationType::ALL.iter().map(|kind| kind.as_str()).collect::<Vec<_>>(),
+            })));
+    }
+
+    match NotificationPreference::save(&state.sea_db, user.id, changes).await {
+        Ok(preferences) => {
+            info!(user_id = user.id, "Notification preferences updated");
+            Ok((StatusCode::OK, Json(json!(preferences))))
+        }
+        Err(err) => {
+            error!(user_id = user.id, error = ?err, "Failed to update notification preferences");
+            Err(err)
+        }
+    }
+}
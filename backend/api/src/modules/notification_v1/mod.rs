@@ -0,0 +1,21 @@
+pub mod controller;
+pub mod validator;
+
+use axum::{
+    middleware,
+    routing::{get, post},
+    Router,
+};
+
+use crate::{middlewares::auth_guard, AppState};
+
+/// The signed-in user's notifications and notification preferences
+pub fn routes() -> Router<AppState> {
+    Router::<AppState>::new()
+        .route("/list", post(controller::list))
+        .route("/mark_read", post(controller::mark_read))
+        .route("/unread_count", get(controller::unread_count))
+        .route("/preferences", get(controller::preferences))
+        .route("/preferences/update", post(controller::update_preferences))
+        .route_layer(middleware::from_fn(auth_guard::verified))
+}
@@ -0,0 +1,58 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::db::sea_models::{
+    notification::{NotificationQuery, NotificationType},
+    notification_preference::{DigestFrequency, PreferenceUpdate},
+};
+
+#[derive(Debug, Default, Deserialize, Serialize, Validate, JsonSchema)]
+pub struct V1NotificationListQuery {
+    pub page: Option<u64>,
+    #[serde(default)]
+    pub unread_only: bool,
+    #[serde(rename = "type")]
+    pub kind: Option<NotificationType>,
+}
+
+impl V1NotificationListQuery {
+    pub fn into_query(self) -> NotificationQuery {
+        NotificationQuery {
+            page: self.page,
+            unread_only: self.unread_only,
+            kind: self.kind,
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, Validate, JsonSchema)]
+pub struct V1MarkReadPayload {
+    /// Notifications to mark read; missing means all of them
+    #[validate(length(min = 1, max = 100))]
+    pub ids: Option<Vec<i64>>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Validate, JsonSchema)]
+pub struct V1UpdatePreferencesPayload {
+    pub in_app: Option<bool>,
+    pub email_digest: Option<DigestFrequency>,
+    /// Notification types to stop receiving, e.g. `comment_like`
+    #[validate(length(max = 16))]
+    pub muted_types: Option<Vec<String>>,
+}
+
+impl V1UpdatePreferencesPayload {
+    pub fn into_update(self) -> PreferenceUpdate {
+        PreferenceUpdate {
+            in_app: self.in_app,
+            email_digest: self.email_digest,
+            muted_types: self.muted_types.map(|types| {
+                let mut types: Vec<String> = types.iter().map(|t| t.trim().to_string()).collect();
+                types.sort();
+                types.dedup();
+                types
+            }),
+        }
+    }
+}
@@ -9,16 +9,21 @@ use serde_json::json;
 use tracing::{error, info, instrument, warn};
 
 use crate::{
-    db::sea_models::{comment_flag, post_comment},
+    db::sea_models::{comment_flag, comment_like, post_comment},
     error::{ErrorCode, ErrorResponse},
     extractors::ValidatedJson,
-    services::auth::AuthSession,
+    services::{
+        admin_events::{self, AdminEvent},
+        audit::AuditEvent,
+        auth::AuthSession,
+        settings,
+    },
     AppState,
 };
 
 use super::validator::{
-    V1AdminCommentFlagListQuery, V1AdminPostCommentListQuery, V1CreatePostCommentPayload,
-    V1FlagCommentPayload, V1UpdatePostCommentPayload,
+    V1AdminCommentFlagListQuery, V1AdminPostCommentListQuery, V1CommentLikeStatusPayload,
+    V1CreatePostCommentPayload, V1FlagCommentPayload, V1UpdatePostCommentPayload,
 };
 
 #[debug_handler]
@@ -41,6 +46,15 @@ pub async fn create(
                 comment_id = comment.id,
                 "Comment created"
             );
+            admin_events::spawn_publish(
+                &state,
+                AdminEvent::CommentCreated {
+                    comment_id: comment.id,
+                    post_id: comment.post_id,
+                    user_id: user.id,
+                    excerpt: comment.content.chars().take(200).collect(),
+                },
+            );
             Ok((StatusCode::CREATED, Json(json!(comment))))
         }
         Err(err) => {
@@ -186,12 +200,17 @@ pub async fn admin_hide(
     Path(comment_id): Path<i32>,
 ) -> Result<impl IntoResponse, ErrorResponse> {
     let _user = auth.user.unwrap();
+    let before = post_comment::Entity::get_by_id(&state.sea_db, comment_id).await?;
 
     match post_comment::Entity::admin_hide(&state.sea_db, comment_id).await {
-        Ok(Some(_)) => {
+        Ok(Some(comment)) => {
             info!(comment_id, "Admin hid comment");
             Ok((
                 StatusCode::OK,
+                AuditEvent::new("comment.hide")
+                    .target("comment", comment_id)
+                    .before(&before)
+                    .after(&comment),
                 Json(json!({ "message": "Hidden successfully" })),
             ))
         }
@@ -214,12 +233,17 @@ pub async fn admin_unhide(
     Path(comment_id): Path<i32>,
 ) -> Result<impl IntoResponse, ErrorResponse> {
     let _user = auth.user.unwrap();
+    let before = post_comment::Entity::get_by_id(&state.sea_db, comment_id).await?;
 
     match post_comment::Entity::admin_unhide(&state.sea_db, comment_id).await {
-        Ok(Some(_)) => {
+        Ok(Some(comment)) => {
             info!(comment_id, "Admin unhid comment");
             Ok((
                 StatusCode::OK,
+                AuditEvent::new("comment.unhide")
+                    .target("comment", comment_id)
+                    .before(&before)
+                    .after(&comment),
                 Json(json!({ "message": "Unhidden successfully" })),
             ))
         }
@@ -242,12 +266,16 @@ pub async fn admin_delete(
     Path(comment_id): Path<i32>,
 ) -> Result<impl IntoResponse, ErrorResponse> {
     let _user = auth.user.unwrap();
+    let before = post_comment::Entity::get_by_id(&state.sea_db, comment_id).await?;
 
     match post_comment::Entity::admin_delete(&state.sea_db, comment_id).await {
         Ok(affected) if affected > 0 => {
             info!(comment_id, "Admin deleted comment");
             Ok((
                 StatusCode::OK,
+                AuditEvent::new("comment.delete")
+                    .target("comment", comment_id)
+                    .before(&before),
                 Json(json!({ "message": "Deleted successfully" })),
             ))
         }
@@ -270,12 +298,17 @@ pub async fn admin_flags_clear(
     Path(comment_id): Path<i32>,
 ) -> Result<impl IntoResponse, ErrorResponse> {
     let _user = auth.user.unwrap();
+    let before = post_comment::Entity::get_by_id(&state.sea_db, comment_id).await?;
 
     match post_comment::Entity::admin_flags_clear(&state.sea_db, comment_id).await {
-        Ok(Some(_)) | Ok(None) => {
+        Ok(after) => {
             info!(comment_id, "Admin cleared comment flags");
             Ok((
                 StatusCode::OK,
+                AuditEvent::new("comment.flags_clear")
+                    .target("comment", comment_id)
+                    .before(&before)
+                    .after(&after),
                 Json(json!({ "message": "Flags cleared successfully" })),
             ))
         }
@@ -314,6 +347,25 @@ pub async fn flag(
                 flags_count = count,
                 "Comment flagged"
             );
+            // Alert once, when the count first reaches the threshold.
+            let threshold = settings::COMMENT_FLAG_ALERT_THRESHOLD.int(&state).await;
+            if threshold > 0 && count == threshold {
+                let post_id = post_comment::Entity::get_by_id(&state.sea_db, comment_id)
+                    .await
+                    .ok()
+                    .flatten()
+                    .map(|comment| comment.post_id);
+                if let Some(post_id) = post_id {
+                    admin_events::spawn_publish(
+                        &state,
+                        AdminEvent::CommentFlagged {
+                            comment_id,
+                            post_id,
+                            flags_count: count,
+                        },
+                    );
+                }
+            }
             Ok(Json(
                 json!({ "message": "Flag recorded", "flags_count": count }),
             ))
@@ -328,6 +380,80 @@ pub async fn flag(
     }
 }
 
+#[debug_handler]
+#[instrument(skip(state, auth), fields(user_id = auth.user.as_ref().map(|u| u.id), comment_id))]
+pub async fn like(
+    State(state): State<AppState>,
+    auth: AuthSession,
+    Path(comment_id): Path<i32>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let user = auth.user.unwrap();
+
+    match comment_like::Entity::like_comment(&state.sea_db, comment_id, user.id).await {
+        Ok((liked, likes_count)) => Ok(Json(json!(comment_like::CommentLikeResponse {
+            comment_id,
+            is_liked: true,
+            likes_count,
+            message: if liked {
+                "Comment liked successfully".to_string()
+            } else {
+                "Comment was already liked".to_string()
+            },
+        }))),
+        Err(err) => {
+            error!(
+                user_id = user.id,
+                comment_id, "Failed to like comment: {}", err
+            );
+            Err(err)
+        }
+    }
+}
+
+#[debug_handler]
+#[instrument(skip(state, auth), fields(user_id = auth.user.as_ref().map(|u| u.id), comment_id))]
+pub async fn unlike(
+    State(state): State<AppState>,
+    auth: AuthSession,
+    Path(comment_id): Path<i32>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let user = auth.user.unwrap();
+
+    match comment_like::Entity::unlike_comment(&state.sea_db, comment_id, user.id).await {
+        Ok((unliked, likes_count)) => Ok(Json(json!(comment_like::CommentLikeResponse {
+            comment_id,
+            is_liked: false,
+            likes_count,
+            message: if unliked {
+                "Comment unliked successfully".to_string()
+            } else {
+                "Comment was not liked".to_string()
+            },
+        }))),
+        Err(err) => {
+            error!(
+                user_id = user.id,
+                comment_id, "Failed to unlike comment: {}", err
+            );
+            Err(err)
+        }
+    }
+}
+
+/// Which of the given comments the user has liked
+#[debug_handler]
+#[instrument(skip(state, auth, payload), fields(user_id = auth.user.as_ref().map(|u| u.id)))]
+pub async fn like_status(
+    State(state): State<AppState>,
+    auth: AuthSession,
+    payload: ValidatedJson<V1CommentLikeStatusPayload>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let user = auth.user.unwrap();
+    let liked =
+        comment_like::Entity::liked_ids(&state.sea_db, user.id, payload.0.comment_ids).await?;
+    Ok(Json(comment_like::CommentLikeStatus { liked }))
+}
+
 #[debug_handler]
 #[instrument(skip(state, _auth, payload))]
 pub async fn admin_flags_list(
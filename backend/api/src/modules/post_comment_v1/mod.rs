@@ -1,6 +1,10 @@
 use axum::{middleware, routing::post, Router};
 
-use crate::{middlewares::auth_guard, AppState};
+use crate::{
+    middlewares::{audit, auth_guard},
+    services::permission_service,
+    AppState,
+};
 
 pub mod controller;
 pub mod validator;
@@ -13,6 +17,9 @@ pub fn routes() -> Router<AppState> {
         .route("/update/{comment_id}", post(controller::update))
         .route("/delete/{comment_id}", post(controller::delete))
         .route("/flag/{comment_id}", post(controller::flag))
+        .route("/like/{comment_id}", post(controller::like))
+        .route("/unlike/{comment_id}", post(controller::unlike))
+        .route("/like/status", post(controller::like_status))
         .route_layer(middleware::from_fn(auth_guard::verified))
         // Public route for listing comments by post
         .route("/{post_id}", post(controller::find_all_by_post));
@@ -36,9 +43,10 @@ pub fn routes() -> Router<AppState> {
             "/flags/details/{comment_id}",
             post(controller::admin_flags_details),
         )
-        .route_layer(middleware::from_fn(
-            auth_guard::verified_with_role::<{ auth_guard::ROLE_ADMIN }>,
-        ));
+        .route_layer(middleware::from_fn(auth_guard::verified_with_permission(
+            permission_service::COMMENT_MODERATE,
+        )))
+        .route_layer(middleware::from_fn(audit::audit));
 
     base.nest("/admin", admin)
 }
@@ -1,3 +1,4 @@
+use schemars::JsonSchema;
 use sea_orm::prelude::DateTimeWithTimeZone;
 use serde::{Deserialize, Serialize};
 use validator::Validate;
@@ -7,9 +8,11 @@ use crate::db::sea_models::post_comment::{
 };
 use crate::utils::SortParam;
 
-#[derive(Debug, Deserialize, Serialize, Validate)]
+#[derive(Debug, Deserialize, Serialize, Validate, JsonSchema)]
 pub struct V1CreatePostCommentPayload {
     pub post_id: i32,
+    /// Comment being replied to; must be on the same post.
+    pub parent_id: Option<i32>,
     #[validate(length(min = 1, max = 1000))]
     pub content: String,
 }
@@ -19,13 +22,14 @@ impl V1CreatePostCommentPayload {
         NewComment {
             post_id: self.post_id,
             user_id,
+            parent_id: self.parent_id,
             content: self.content,
             likes_count: Some(0),
         }
     }
 }
 
-#[derive(Debug, Deserialize, Serialize, Validate)]
+#[derive(Debug, Deserialize, Serialize, Validate, JsonSchema)]
 pub struct V1UpdatePostCommentPayload {
     #[validate(length(min = 1, max = 1000))]
     pub content: Option<String>,
@@ -40,7 +44,7 @@ impl V1UpdatePostCommentPayload {
     }
 }
 
-#[derive(Debug, Deserialize, Serialize, Validate, Clone)]
+#[derive(Debug, Deserialize, Serialize, Validate, Clone, JsonSchema)]
 pub struct V1AdminPostCommentListQuery {
     pub page: Option<u64>,
     pub user_id: Option<i32>,
@@ -79,18 +83,18 @@ impl V1AdminPostCommentListQuery {
     }
 }
 
-#[derive(Debug, Deserialize, Serialize, Validate)]
+#[derive(Debug, Deserialize, Serialize, Validate, JsonSchema)]
 pub struct V1AdminModerationPayload {
     pub reason: Option<String>,
 }
 
-#[derive(Debug, Deserialize, Serialize, Validate)]
+#[derive(Debug, Deserialize, Serialize, Validate, JsonSchema)]
 pub struct V1FlagCommentPayload {
     #[validate(length(min = 1, max = 500))]
     pub reason: Option<String>,
 }
 
-#[derive(Debug, Deserialize, Serialize, Validate, Clone)]
+#[derive(Debug, Deserialize, Serialize, Validate, Clone, JsonSchema)]
 pub struct V1AdminCommentFlagListQuery {
     pub page: Option<u64>,
     pub comment_id: Option<i32>,
@@ -99,3 +103,9 @@ pub struct V1AdminCommentFlagListQuery {
     pub sort_by: Option<Vec<String>>,
     pub sort_order: Option<String>,
 }
+
+#[derive(Debug, Deserialize, Serialize, Validate, JsonSchema)]
+pub struct V1CommentLikeStatusPayload {
+    #[validate(length(min = 1, max = 100))]
+    pub comment_ids: Vec<i32>,
+}
@@ -1,32 +1,145 @@
 use axum::{
     extract::{Path, State},
-    http::StatusCode,
+    http::{header, StatusCode},
     response::IntoResponse,
     Json,
 };
 
-use crate::db::sea_models::post::UpdatePost;
-use crate::db::sea_models::{post_revision, post_series, post_series_post, scheduled_post};
+use crate::db::sea_models::post::{PostStatus, UpdateOutcome, UpdatePost};
+use crate::db::sea_models::post_author::PostAccess;
+use crate::db::sea_models::post_revision::RevisionField;
+use crate::db::sea_models::{
+    category, media, post_author, post_review_note, post_revision, post_series, post_series_post,
+    post_status_transition, post_translation_group, scheduled_post, slug_redirect, user,
+};
+use axum_client_ip::ClientIp;
 use axum_macros::debug_handler;
 use sea_orm::EntityTrait;
 use serde_json::json;
 use tracing::{error, info, instrument, warn};
 
-use crate::db::sea_models::user::UserRole;
 use crate::{
     db::sea_models::post,
     error::{ErrorCode, ErrorResponse},
-    extractors::ValidatedJson,
+    extractors::{ValidatedJson, ValidatedMultipart, ValidatedQuery},
     modules::post_v1::validator::V1UpdatePostPayload,
-    services::auth::AuthSession,
+    services::{
+        abuse_limiter::{self, AbuseLimiterConfig},
+        auth::AuthSession,
+        content_render,
+        permission_service::{self, PermissionService},
+        post_diff, post_lock, post_markdown, post_review, seo, websub,
+    },
+    utils::normalize_locale,
     AppState,
 };
 
 use super::validator::{
-    V1AutosavePayload, V1CreatePostPayload, V1PostQueryParams, V1SchedulePayload,
-    V1SeriesCreatePayload, V1SeriesListQuery, V1SeriesUpdatePayload,
+    V1AuthorPostsQuery, V1AutosavePayload, V1CreatePostPayload, V1MarkdownImportOptions,
+    V1PostLockPayload, V1PostQueryParams, V1PostTransitionPayload, V1PostUnlockPayload,
+    V1PostViewQuery, V1ReviewNoteCreatePayload, V1ReviewNoteUpdatePayload, V1ReviewNotesQuery,
+    V1RevisionRestorePayload, V1SchedulePayload, V1SeriesCreatePayload, V1SeriesListQuery,
+    V1SeriesReorderPayload, V1SeriesUpdatePayload, V1SetContributorsPayload,
+};
+
+/// Snapshot a post after a create/update. A failed snapshot is logged but
+/// doesn't fail the edit itself.
+async fn record_revision(state: &AppState, post_id: i32, user_id: i32) {
+    if let Err(err) =
+        post_revision::Entity::snapshot_post(&state.sea_db, post_id, Some(user_id), None).await
+    {
+        warn!(error = ?err, post_id, "Failed to record post revision");
+    }
+}
+
+/// Protected posts in a listing, cut to their teasers for viewers who can't
+/// read them. Listings never take a password.
+fn for_viewer(
+    posts: Vec<post::PostWithRelations>,
+    viewer: Option<&user::Model>,
+) -> Vec<post::PostWithRelations> {
+    posts
+        .into_iter()
+        .map(|post| post.for_viewer(viewer, false))
+        .collect()
+}
+
+/// Wrong passwords for a protected post, per post and address: 5 in 5
+/// minutes blocks for 15 minutes, 20 in an hour for a day.
+const UNLOCK_LIMITER_CONFIG: AbuseLimiterConfig = AbuseLimiterConfig {
+    temp_block_attempts: 5,
+    temp_block_range: 300,
+    temp_block_duration: 900,
+    block_retry_limit: 20,
+    block_range: 3600,
+    block_duration: 86400,
 };
 
+/// Check a password given for a protected post. Only failures count
+/// towards the limiter, and a blocked address is refused before the
+/// password is checked, so guessing can't go on behind the block.
+async fn unlock(
+    state: &AppState,
+    client_ip: std::net::IpAddr,
+    post: &post::PostWithRelations,
+    password: String,
+) -> Result<bool, ErrorResponse> {
+    let key_prefix = format!("post_unlock:{}:{}", post.id, client_ip);
+    if let Some(retry_after) = abuse_limiter::blocked_for(&state.redis_pool, &key_prefix).await? {
+        warn!(post_id = post.id, "Post unlock refused while blocked");
+        return Err(abuse_limiter::too_many_attempts(retry_after));
+    }
+    if post.password_matches(password).await {
+        return Ok(true);
+    }
+    abuse_limiter::limiter(&state.redis_pool, &key_prefix, UNLOCK_LIMITER_CONFIG).await?;
+    Ok(false)
+}
+
+/// Users may only work on posts they are credited on, as far as their
+/// contributor role allows, unless they hold `post.view_any` (for `View`) or
+/// `post.edit_any`. Returns the post as stored.
+async fn ensure_post_access(
+    state: &AppState,
+    user: &user::Model,
+    post_id: i32,
+    access: PostAccess,
+) -> Result<post::Model, ErrorResponse> {
+    let Some(post) = post::Entity::find_by_id(post_id).one(&state.sea_db).await? else {
+        return Err(
+            ErrorResponse::new(ErrorCode::RecordNotFound).with_message("Post does not exist")
+        );
+    };
+    if PermissionService::allows(state, user, permission_service::POST_EDIT_ANY).await
+        || (access == PostAccess::View
+            && PermissionService::allows(state, user, permission_service::POST_VIEW_ANY).await)
+    {
+        return Ok(post);
+    }
+
+    match post_author::Entity::role_of(&state.sea_db, post_id, user.id).await? {
+        Some(role) if role.allows(access) => Ok(post),
+        Some(role) => {
+            warn!(post_id, user_id = user.id, %role, ?access, "Post access denied");
+            Err(
+                ErrorResponse::new(ErrorCode::OperationNotAllowed).with_message(format!(
+                    "Your role on this post ({}) does not allow this",
+                    role
+                )),
+            )
+        }
+        None => {
+            warn!(
+                post_id,
+                user_id = user.id,
+                "Post access denied: not a contributor"
+            );
+            Err(ErrorResponse::new(ErrorCode::OperationNotAllowed)
+                .with_message("You are not credited on this post"))
+        }
+    }
+}
+
 #[debug_handler]
 #[instrument(skip(state, auth, payload), fields(user_id, post_id, slug, result))]
 pub async fn create(
@@ -39,6 +152,16 @@ pub async fn create(
 
     info!(user_id = user.id, "Creating post");
 
+    if payload.is_published && !post_review::may_publish_directly(&state, &user).await {
+        return Err(post_review::needs_approval_error());
+    }
+    if let Some(message) = post::visibility_problem(
+        payload.visibility,
+        payload.min_role,
+        payload.password.is_some(),
+    ) {
+        return Err(ErrorResponse::new(ErrorCode::InvalidInput).with_message(message));
+    }
     let new_post = payload.0.into_new_post(user.id);
 
     match post::Entity::create(&state.sea_db, new_post).await {
@@ -47,6 +170,10 @@ pub async fn create(
             tracing::Span::current().record("post_id", post.id);
             tracing::Span::current().record("slug", &post.slug);
             tracing::Span::current().record("result", "success");
+            record_revision(&state, post.id, user.id).await;
+            if post.status == post::PostStatus::Published {
+                websub::notify_post_published(&state, post.id);
+            }
             Ok((StatusCode::CREATED, Json(json!(post))))
         }
         Err(err) => {
@@ -58,10 +185,14 @@ pub async fn create(
 }
 
 #[debug_handler]
-#[instrument(skip(state), fields(identifier = %slug_or_id, post_id, result))]
+#[instrument(skip(state, auth, view, unlock_payload), fields(identifier = %slug_or_id, client_ip = %client_ip, post_id, result))]
 pub async fn find_by_id_or_slug(
     State(state): State<AppState>,
+    auth: AuthSession,
+    ClientIp(client_ip): ClientIp,
     Path(slug_or_id): Path<String>,
+    ValidatedQuery(view): ValidatedQuery<V1PostViewQuery>,
+    unlock_payload: Option<ValidatedJson<V1PostUnlockPayload>>,
 ) -> Result<impl IntoResponse, ErrorResponse> {
     info!(identifier = %slug_or_id, "Finding post by ID or slug");
 
@@ -70,10 +201,17 @@ pub async fn find_by_id_or_slug(
             info!(post_id = id, "Searching by ID");
             post::Entity::find_by_id_or_slug(&state.sea_db, Some(id), None).await
         }
-        Err(_) => {
-            info!(slug = %slug_or_id, "Searching by slug");
-            post::Entity::find_by_id_or_slug(&state.sea_db, None, Some(slug_or_id)).await
-        }
+        Err(_) => match view.locale.as_deref().and_then(normalize_locale) {
+            Some(locale) => {
+                info!(slug = %slug_or_id, %locale, "Searching by slug in locale");
+                post::Entity::find_localized(&state.sea_db, slug_or_id.clone(), &locale).await
+            }
+            None => {
+                info!(slug = %slug_or_id, "Searching by slug");
+                post::Entity::find_by_id_or_slug(&state.sea_db, None, Some(slug_or_id.clone()))
+                    .await
+            }
+        },
     };
 
     match query {
@@ -81,7 +219,50 @@ pub async fn find_by_id_or_slug(
             info!(post_id = post.id, "Post found");
             tracing::Span::current().record("post_id", post.id);
             tracing::Span::current().record("result", "found");
-            Ok((StatusCode::OK, Json(json!(post))))
+
+            let password_ok = match unlock_payload.and_then(|u| u.0.password) {
+                Some(password) if post.asks_password(auth.user.as_ref()) => {
+                    unlock(&state, client_ip, &post, password).await?
+                }
+                _ => false,
+            };
+            let post = post.for_viewer(auth.user.as_ref(), password_ok);
+            if let Some(reason) = post.locked {
+                info!(
+                    post_id = post.id,
+                    ?reason,
+                    "Serving teaser of protected post"
+                );
+            }
+
+            // `?format=` answers with the content itself rather than the JSON post
+            if let Some(format) = view.format {
+                return Ok((
+                    StatusCode::OK,
+                    [(header::CONTENT_TYPE, format.content_type())],
+                    content_render::render(&post.content, format),
+                )
+                    .into_response());
+            }
+
+            let mut body = json!(post);
+            body["series"] =
+                json!(post_series::Entity::navigation_for_post(&state.sea_db, post.id).await?);
+            body["translations"] = json!(
+                post_translation_group::Entity::translations_of(
+                    &state.sea_db,
+                    post.id,
+                    post.translation_group_id,
+                    true,
+                )
+                .await?
+            );
+            body["seo"] = json!(seo::post_metadata(&state, &post).await?);
+            if let Some(hit) = slug_redirect::redirect_hit(&slug_or_id, &post.slug) {
+                info!(from = %hit.from, to = %hit.to, "Post found through slug redirect");
+                body["redirect"] = json!(hit);
+            }
+            Ok((StatusCode::OK, Json(body)).into_response())
         }
         Ok(None) => {
             warn!("Post not found");
@@ -97,23 +278,73 @@ pub async fn find_by_id_or_slug(
 }
 
 #[debug_handler]
-#[instrument(skip(state, payload), fields(post_id = %post_id, result))]
+#[instrument(skip(state, auth, payload), fields(post_id = %post_id, result))]
 pub async fn update(
     State(state): State<AppState>,
+    auth: AuthSession,
     Path(post_id): Path<i32>,
     payload: ValidatedJson<V1UpdatePostPayload>,
 ) -> Result<impl IntoResponse, ErrorResponse> {
+    let user = auth.user.unwrap();
     info!(post_id, "Updating post");
+    let current = ensure_post_access(&state, &user, post_id, PostAccess::Edit).await?;
 
-    let update_post = payload.0.into_update_post();
+    // A hash only counts while the post is still password-protected; one
+    // left over from an earlier protection doesn't carry into a new one
+    let has_password = payload.password.is_some()
+        || (current.visibility == post::PostVisibility::Password
+            && current.password_hash.is_some());
+    if let Some(message) = post::visibility_problem(
+        payload.visibility.unwrap_or(current.visibility),
+        payload.min_role.unwrap_or(current.min_role),
+        has_password,
+    ) {
+        return Err(ErrorResponse::new(ErrorCode::InvalidInput).with_message(message));
+    }
 
-    match post::Entity::update(&state.sea_db, post_id, update_post).await {
-        Ok(Some(post)) => {
+    let expected_updated_at = payload.0.updated_at;
+    let mut update_post = payload.0.into_update_post();
+    update_post.status =
+        post_review::status_after_edit(&state, &user, &current, update_post.status).await?;
+
+    let result = post::Entity::update_if_unmodified(
+        &state.sea_db,
+        post_id,
+        expected_updated_at,
+        update_post,
+    )
+    .await;
+
+    match result {
+        Ok(UpdateOutcome::Updated(post)) => {
             info!(post_id, slug = %post.slug, "Post updated successfully");
             tracing::Span::current().record("result", "success");
+            record_revision(&state, post.id, user.id).await;
+            post_review::record_transition(
+                &state,
+                post.id,
+                &post.title,
+                current.status,
+                post.status,
+                &user,
+                None,
+            )
+            .await;
+            if post.status == post::PostStatus::Published {
+                websub::notify_post_published(&state, post.id);
+            }
             Ok((StatusCode::OK, Json(json!(post))))
         }
-        Ok(None) => {
+        Ok(UpdateOutcome::Conflict(current)) => {
+            warn!(post_id, "Post changed since the editor loaded it");
+            tracing::Span::current().record("result", "conflict");
+            let revision = post_revision::Entity::latest_for_post(&state.sea_db, current.id)
+                .await
+                .ok()
+                .flatten();
+            Err(conflict_error(*current, revision))
+        }
+        Ok(UpdateOutcome::NotFound) => {
             warn!(post_id, "Post not found for update");
             tracing::Span::current().record("result", "not_found");
             Err(ErrorResponse::new(ErrorCode::RecordNotFound).with_message("Post does not exist"))
@@ -126,11 +357,27 @@ pub async fn update(
     }
 }
 
+/// 409 for a stale save, carrying the stored post and its latest revision so
+/// the editor can merge instead of overwriting.
+fn conflict_error(current: post::Model, revision: Option<post_revision::Model>) -> ErrorResponse {
+    ErrorResponse::new(ErrorCode::ResourceConflict)
+        .with_message("The post was changed by someone else since you loaded it")
+        .with_context(json!({
+            "updated_at": current.updated_at,
+            "post": current,
+            "revision": revision,
+        }))
+}
+
 #[debug_handler]
 pub async fn delete(
     State(state): State<AppState>,
+    auth: AuthSession,
     Path(post_id): Path<i32>,
 ) -> Result<impl IntoResponse, ErrorResponse> {
+    let user = auth.user.unwrap();
+    ensure_post_access(&state, &user, post_id, PostAccess::Manage).await?;
+
     match post::Entity::delete(&state.sea_db, post_id).await {
         Ok(1) => Ok((
             StatusCode::OK,
@@ -148,6 +395,7 @@ pub async fn delete(
 #[debug_handler]
 pub async fn find_published_posts(
     State(state): State<AppState>,
+    auth: AuthSession,
     payload: ValidatedJson<V1PostQueryParams>,
 ) -> Result<impl IntoResponse, ErrorResponse> {
     let page = payload.page.clone().unwrap_or(1);
@@ -155,7 +403,7 @@ pub async fn find_published_posts(
         Ok((posts, total)) => Ok((
             StatusCode::OK,
             Json(json!({
-                "data": posts,
+                "data": for_viewer(posts, auth.user.as_ref()),
                 "total": total,
                 "per_page": post::Entity::PER_PAGE as u64,
                 "page": page,
@@ -200,28 +448,16 @@ pub async fn query(
     payload: ValidatedJson<V1PostQueryParams>,
 ) -> Result<impl IntoResponse, ErrorResponse> {
     let user = auth.user.unwrap();
-    let mut query_params = payload.0.clone();
+    let query_params = payload.0.clone();
+    let page = query_params.page.unwrap_or(1);
+    let mut post_query = query_params.into_post_query();
 
-    // Role-based access control
-    match user.role {
-        UserRole::Author => {
-            query_params.author_id = Some(user.id);
-        }
-        UserRole::Admin | UserRole::SuperAdmin => {}
-        UserRole::Moderator => {
-            // Moderators can view all posts for moderation purposes
-            // No modification needed
-        }
-        UserRole::User => {
-            return Err(
-                ErrorResponse::new(ErrorCode::OperationNotAllowed).with_message("Access denied")
-            );
-        }
+    // Without `post.view_any`, every post they are credited on, in any role
+    if !PermissionService::allows(&state, &user, permission_service::POST_VIEW_ANY).await {
+        post_query.contributor_id = Some(user.id);
     }
 
-    let page = query_params.page.clone().unwrap_or(1);
-
-    match post::Entity::search(&state.sea_db, query_params.into_post_query()).await {
+    match post::Entity::search(&state.sea_db, post_query).await {
         Ok((posts, total)) => Ok((
             StatusCode::OK,
             Json(json!({
@@ -241,38 +477,76 @@ pub async fn autosave(
     auth: AuthSession,
     payload: ValidatedJson<V1AutosavePayload>,
 ) -> Result<impl IntoResponse, ErrorResponse> {
-    let _user = auth.user.unwrap();
+    let user = auth.user.unwrap();
     let p = payload.0;
+    let current = ensure_post_access(&state, &user, p.post_id, PostAccess::Edit).await?;
 
-    match post_revision::Entity::create(
-        &state.sea_db,
-        p.post_id,
-        serde_json::to_string(&p.content).unwrap_or_else(|_| "{}".to_string()),
-        None,
-    )
-    .await
-    {
-        Ok(revision) => {
-            let update = UpdatePost {
-                title: None,
-                slug: None,
-                content: Some(serde_json::to_value(&p.content).unwrap_or(serde_json::json!({}))),
-                excerpt: None,
-                featured_image_id: None,
-                status: None,
-                published_at: None,
-                updated_at: p.updated_at,
-                category_id: None,
-                view_count: None,
-                likes_count: None,
-                tag_ids: None,
-            };
+    let update = UpdatePost {
+        title: None,
+        slug: None,
+        content: Some(serde_json::to_value(&p.content).unwrap_or(serde_json::json!({}))),
+        excerpt: None,
+        featured_image_id: None,
+        status: post_review::status_after_edit(&state, &user, &current, None).await?,
+        published_at: None,
+        updated_at: chrono::Utc::now().fixed_offset(),
+        category_id: None,
+        view_count: None,
+        likes_count: None,
+        tag_ids: None,
+        visibility: None,
+        min_role: None,
+        password: None,
+        locale: None,
+        meta_title: None,
+        meta_description: None,
+        canonical_url: None,
+        noindex: None,
+        og_image_id: None,
+        og_image_variant: None,
+    };
 
-            match post::Entity::update(&state.sea_db, p.post_id, update).await {
-                Ok(_) => Ok((StatusCode::OK, Json(json!(revision)))),
+    match post::Entity::update_if_unmodified(&state.sea_db, p.post_id, p.updated_at, update).await {
+        Ok(UpdateOutcome::Updated(post)) => {
+            post_review::record_transition(
+                &state,
+                post.id,
+                &post.title,
+                current.status,
+                post.status,
+                &user,
+                None,
+            )
+            .await;
+            match post_revision::Entity::snapshot_post(
+                &state.sea_db,
+                p.post_id,
+                Some(user.id),
+                None,
+            )
+            .await
+            {
+                Ok(Some(revision)) => {
+                    // The editor sends this back as `updated_at` on its next save
+                    let mut body = json!(revision);
+                    body["post_updated_at"] = json!(post.updated_at);
+                    Ok((StatusCode::OK, Json(body)))
+                }
+                Ok(None) => Err(ErrorResponse::new(ErrorCode::RecordNotFound)
+                    .with_message("Post does not exist")),
                 Err(err) => Err(err.into()),
             }
         }
+        Ok(UpdateOutcome::Conflict(current)) => {
+            let revision = post_revision::Entity::latest_for_post(&state.sea_db, current.id)
+                .await
+                .ok()
+                .flatten();
+            Err(conflict_error(*current, revision))
+        }
+        Ok(UpdateOutcome::NotFound) => {
+            Err(ErrorResponse::new(ErrorCode::RecordNotFound).with_message("Post does not exist"))
+        }
         Err(err) => Err(err.into()),
     }
 }
@@ -283,7 +557,8 @@ pub async fn revisions_list(
     auth: AuthSession,
     Path(post_id): Path<i32>,
 ) -> Result<impl IntoResponse, ErrorResponse> {
-    let _user = auth.user.unwrap();
+    let user = auth.user.unwrap();
+    ensure_post_access(&state, &user, post_id, PostAccess::View).await?;
     let page: u64 = 1;
 
     match post_revision::Entity::list_by_post(&state.sea_db, post_id, Some(page), None).await {
@@ -295,66 +570,115 @@ pub async fn revisions_list(
     }
 }
 
+async fn find_revision(
+    state: &AppState,
+    post_id: i32,
+    revision_id: i32,
+) -> Result<post_revision::Model, ErrorResponse> {
+    post_revision::Entity::find_for_post(&state.sea_db, post_id, revision_id)
+        .await?
+        .ok_or_else(|| {
+            ErrorResponse::new(ErrorCode::RecordNotFound)
+                .with_message(format!("Revision {} not found for this post", revision_id))
+        })
+}
+
 #[debug_handler]
-pub async fn revisions_restore(
+pub async fn revisions_diff(
     State(state): State<AppState>,
     auth: AuthSession,
-    Path((post_id, revision_id)): Path<(i32, i32)>,
+    Path((post_id, from_id, to_id)): Path<(i32, i32, i32)>,
 ) -> Result<impl IntoResponse, ErrorResponse> {
-    let _user = auth.user.unwrap();
+    let user = auth.user.unwrap();
+    ensure_post_access(&state, &user, post_id, PostAccess::View).await?;
+    let from = find_revision(&state, post_id, from_id).await?;
+    let to = find_revision(&state, post_id, to_id).await?;
 
-    let rev_opt = match post_revision::Entity::find_by_id(revision_id)
-        .one(&state.sea_db)
-        .await
-    {
-        Ok(r) => r,
-        Err(err) => return Err(err.into()),
+    let fields = match (from.snapshot_fields(), to.snapshot_fields()) {
+        (Some(a), Some(b)) => Some(post_diff::diff_fields(&a, &b)),
+        _ => None,
     };
+    let revision_info = |r: &post_revision::Model| json!({ "id": r.id, "created_at": r.created_at, "created_by": r.created_by });
 
-    let rev = if let Some(r) = rev_opt {
-        r
-    } else {
-        return Err(
-            ErrorResponse::new(ErrorCode::RecordNotFound).with_message("Revision not found")
-        );
-    };
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "post_id": post_id,
+            "from": revision_info(&from),
+            "to": revision_info(&to),
+            "blocks": post_diff::diff_blocks(&from.content_json(), &to.content_json()),
+            "fields_available": fields.is_some(),
+            "fields": fields.unwrap_or_default(),
+        })),
+    ))
+}
 
-    if rev.post_id != post_id {
-        return Err(ErrorResponse::new(ErrorCode::OperationNotAllowed)
-            .with_message("Revision does not belong to the specified post"));
-    }
+#[debug_handler]
+pub async fn revisions_restore(
+    State(state): State<AppState>,
+    auth: AuthSession,
+    Path((post_id, revision_id)): Path<(i32, i32)>,
+    payload: Option<ValidatedJson<V1RevisionRestorePayload>>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let user = auth.user.unwrap();
+    let current = ensure_post_access(&state, &user, post_id, PostAccess::Edit).await?;
+    let fields = payload.map(|p| p.0).unwrap_or_default().fields();
 
-    let now = chrono::Utc::now().fixed_offset();
-    let update = UpdatePost {
-        title: None,
-        slug: None,
-        content: Some(serde_json::from_str(&rev.content).unwrap_or(serde_json::json!({}))),
-        excerpt: None,
-        featured_image_id: None,
-        status: None,
-        published_at: None,
-        updated_at: now,
-        category_id: None,
-        view_count: None,
-        likes_count: None,
-        tag_ids: None,
-    };
+    let rev = find_revision(&state, post_id, revision_id).await?;
 
-    match post::Entity::update(&state.sea_db, post_id, update).await {
-        Ok(_) => {
-            let meta = serde_json::json!({ "restored_from_revision_id": revision_id });
-            match post_revision::Entity::create(
-                &state.sea_db,
-                post_id,
-                rev.content.clone(),
-                Some(meta),
+    let snapshot = rev.snapshot_fields();
+    if snapshot.is_none() && fields.iter().any(|f| *f != RevisionField::Content) {
+        return Err(ErrorResponse::new(ErrorCode::InvalidInput).with_message(
+            "This revision predates field snapshots; only `content` can be restored",
+        ));
+    }
+    if let Some(snapshot) = &snapshot {
+        if fields.contains(&RevisionField::Category)
+            && !matches!(
+                category::Entity::find_by_id(snapshot.category_id)
+                    .one(&state.sea_db)
+                    .await,
+                Ok(Some(_))
             )
-            .await
-            {
-                Ok(new_rev) => Ok((StatusCode::OK, Json(json!(new_rev)))),
-                Err(err) => Err(err.into()),
+        {
+            return Err(ErrorResponse::new(ErrorCode::InvalidInput)
+                .with_message("The revision's category no longer exists"));
+        }
+        if let (true, Some(media_id)) = (
+            fields.contains(&RevisionField::FeaturedImage),
+            snapshot.featured_image_id,
+        ) {
+            if !matches!(
+                media::Entity::find_by_id(&state.sea_db, media_id).await,
+                Ok(Some(_))
+            ) {
+                return Err(ErrorResponse::new(ErrorCode::InvalidInput)
+                    .with_message("The revision's featured image no longer exists"));
             }
         }
+    }
+
+    // Restoring is an edit, so it goes back through review like one
+    let status = post_review::status_after_edit(&state, &user, &current, None).await?;
+
+    match post_revision::Entity::restore(&state.sea_db, &rev, &fields, status, Some(user.id)).await
+    {
+        Ok(Some((post, new_rev))) => {
+            post_review::record_transition(
+                &state,
+                post.id,
+                &post.title,
+                current.status,
+                post.status,
+                &user,
+                None,
+            )
+            .await;
+            Ok((StatusCode::OK, Json(json!(new_rev))))
+        }
+        Ok(None) => {
+            Err(ErrorResponse::new(ErrorCode::RecordNotFound).with_message("Post does not exist"))
+        }
         Err(err) => Err(err.into()),
     }
 }
@@ -365,8 +689,14 @@ pub async fn schedule(
     auth: AuthSession,
     payload: ValidatedJson<V1SchedulePayload>,
 ) -> Result<impl IntoResponse, ErrorResponse> {
-    let _user = auth.user.unwrap();
+    let user = auth.user.unwrap();
     let p = payload.0;
+    let current = ensure_post_access(&state, &user, p.post_id, PostAccess::Edit).await?;
+    if current.status != PostStatus::Approved
+        && !post_review::may_publish_directly(&state, &user).await
+    {
+        return Err(post_review::needs_approval_error());
+    }
 
     match scheduled_post::Entity::upsert(&state.sea_db, p.post_id, p.publish_at).await {
         Ok(model) => Ok((StatusCode::OK, Json(json!(model)))),
@@ -454,20 +784,20 @@ pub async fn series_list(
                 let count = post_series_post::Entity::count_by_series(&state.sea_db, s.id)
                     .await
                     .unwrap_or(0);
-                data.push(serde_json::json!({
-                    "id": s.id,
-                    "name": s.name,
-                    "slug": s.slug,
-                    "description": s.description,
-                    "created_at": s.created_at,
-                    "updated_at": s.updated_at,
-                    "posts_count": count as i64,
-                }));
+                data.push(post_series::PostSeriesWithCount {
+                    id: s.id,
+                    name: s.name,
+                    slug: s.slug,
+                    description: s.description,
+                    created_at: s.created_at,
+                    updated_at: s.updated_at,
+                    posts_count: count as i64,
+                });
             }
 
             Ok((
                 StatusCode::OK,
-                Json(json!({ "data": data, "total": total, "page": page })),
+                Json(post_series::PostSeriesList { data, total, page }),
             ))
         }
         Err(err) => Err(err.into()),
@@ -480,7 +810,8 @@ pub async fn series_add(
     auth: AuthSession,
     Path((post_id, series_id)): Path<(i32, i32)>,
 ) -> Result<impl IntoResponse, ErrorResponse> {
-    let _user = auth.user.unwrap();
+    let user = auth.user.unwrap();
+    ensure_post_access(&state, &user, post_id, PostAccess::Edit).await?;
 
     let payload = post_series_post::NewPostSeriesPost {
         series_id,
@@ -500,7 +831,8 @@ pub async fn series_remove(
     auth: AuthSession,
     Path((post_id, series_id)): Path<(i32, i32)>,
 ) -> Result<impl IntoResponse, ErrorResponse> {
-    let _user = auth.user.unwrap();
+    let user = auth.user.unwrap();
+    ensure_post_access(&state, &user, post_id, PostAccess::Edit).await?;
 
     let payload = post_series_post::RemovePostSeriesPost { series_id, post_id };
 
@@ -516,6 +848,538 @@ pub async fn series_remove(
     }
 }
 
+/// Replace the order of a series with the full list of its posts.
+#[debug_handler]
+pub async fn series_reorder(
+    State(state): State<AppState>,
+    auth: AuthSession,
+    Path(series_id): Path<i32>,
+    payload: ValidatedJson<V1SeriesReorderPayload>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let _user = auth.user.unwrap();
+
+    if post_series::Entity::find_by_id(series_id)
+        .one(&state.sea_db)
+        .await?
+        .is_none()
+    {
+        return Err(ErrorResponse::new(ErrorCode::RecordNotFound).with_message("Series not found"));
+    }
+
+    let items =
+        post_series_post::Entity::reorder_all(&state.sea_db, series_id, &payload.post_ids).await?;
+    Ok((
+        StatusCode::OK,
+        Json(json!({ "series_id": series_id, "data": items })),
+    ))
+}
+
+/// Public series page: the series and its published posts in order. Old
+/// slugs resolve through slug redirects.
+#[debug_handler]
+#[instrument(skip(state), fields(slug = %slug))]
+pub async fn series_view(
+    State(state): State<AppState>,
+    Path(slug): Path<String>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let series = match post_series::Entity::find_by_slug(&state.sea_db, slug.clone()).await? {
+        Some(series) => Some(series),
+        None => match slug_redirect::Entity::resolve(
+            &state.sea_db,
+            slug_redirect::RedirectEntity::Series,
+            None,
+            &slug,
+        )
+        .await?
+        {
+            Some(redirect) => {
+                post_series::Entity::find_by_id(redirect.target_id)
+                    .one(&state.sea_db)
+                    .await?
+            }
+            None => None,
+        },
+    };
+    let Some(series) = series else {
+        return Err(ErrorResponse::new(ErrorCode::RecordNotFound).with_message("Series not found"));
+    };
+
+    let posts = post_series::Entity::published_posts(&state.sea_db, series.id).await?;
+    let mut body = json!({
+        "id": series.id,
+        "name": series.name,
+        "slug": series.slug,
+        "description": series.description,
+        "created_at": series.created_at,
+        "updated_at": series.updated_at,
+        "posts": posts,
+    });
+    if let Some(hit) = slug_redirect::redirect_hit(&slug, &series.slug) {
+        body["redirect"] = json!(hit);
+    }
+    Ok((StatusCode::OK, Json(body)))
+}
+
+// ============================================================================
+// Contributor and author profile endpoints
+// ============================================================================
+
+#[debug_handler]
+pub async fn contributors_list(
+    State(state): State<AppState>,
+    auth: AuthSession,
+    Path(post_id): Path<i32>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let user = auth.user.unwrap();
+    ensure_post_access(&state, &user, post_id, PostAccess::View).await?;
+
+    let contributors = post_author::Entity::list_for_post(&state.sea_db, post_id).await?;
+    Ok((
+        StatusCode::OK,
+        Json(json!({ "post_id": post_id, "data": contributors })),
+    ))
+}
+
+/// Replace everyone credited on a post. Needs the `author` role on the post
+/// (or moderator and above).
+#[debug_handler]
+#[instrument(skip(state, auth, payload), fields(user_id, post_id = %post_id))]
+pub async fn contributors_set(
+    State(state): State<AppState>,
+    auth: AuthSession,
+    Path(post_id): Path<i32>,
+    payload: ValidatedJson<V1SetContributorsPayload>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let user = auth.user.unwrap();
+    tracing::Span::current().record("user_id", user.id);
+    ensure_post_access(&state, &user, post_id, PostAccess::Manage).await?;
+
+    let contributors =
+        post_author::Entity::replace(&state.sea_db, post_id, payload.0.contributors).await?;
+    info!(
+        post_id,
+        count = contributors.len(),
+        "Post contributors updated"
+    );
+    Ok((
+        StatusCode::OK,
+        Json(json!({ "post_id": post_id, "data": contributors })),
+    ))
+}
+
+/// Load a user who can be shown as an author; anyone else is a 404.
+async fn find_author(state: &AppState, author_id: i32) -> Result<user::Model, ErrorResponse> {
+    match user::Entity::find_by_id(author_id)
+        .one(&state.sea_db)
+        .await?
+    {
+        Some(author) if author.is_author() => Ok(author),
+        _ => Err(ErrorResponse::new(ErrorCode::RecordNotFound).with_message("Author not found")),
+    }
+}
+
+/// Public author profile.
+#[debug_handler]
+pub async fn author_view(
+    State(state): State<AppState>,
+    Path(author_id): Path<i32>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let author = find_author(&state, author_id).await?;
+
+    let avatar = match author.avatar_id {
+        Some(media_id) => media::Entity::find_by_id(&state.sea_db, media_id).await?,
+        None => None,
+    };
+    let published_posts = post_author::Entity::published_count(&state.sea_db, author.id).await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "id": author.id,
+            "name": author.name,
+            "avatar": avatar,
+            "published_posts": published_posts,
+            "created_at": author.created_at,
+        })),
+    ))
+}
+
+/// Published posts crediting the user as an author, newest first.
+#[debug_handler]
+pub async fn author_posts(
+    State(state): State<AppState>,
+    auth: AuthSession,
+    Path(author_id): Path<i32>,
+    payload: Option<ValidatedJson<V1AuthorPostsQuery>>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let author = find_author(&state, author_id).await?;
+    let page = payload.and_then(|p| p.0.page).unwrap_or(1);
+
+    let query = post::PostQuery {
+        page_no: Some(page),
+        title: None,
+        status: None,
+        author_id: Some(author.id),
+        contributor_id: None,
+        sorts: None,
+        category_id: None,
+        include_subcategories: None,
+        search: None,
+        tag_ids: None,
+        visibility: None,
+        locale: None,
+        locale_fallback: None,
+        created_at_gt: None,
+        created_at_lt: None,
+        updated_at_gt: None,
+        updated_at_lt: None,
+        published_at_gt: None,
+        published_at_lt: None,
+    };
+    let (posts, total) = post::Entity::find_published_paginated(&state.sea_db, query).await?;
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "author": { "id": author.id, "name": author.name },
+            "data": for_viewer(posts, auth.user.as_ref()),
+            "total": total,
+            "per_page": post::Entity::PER_PAGE,
+            "page": page,
+        })),
+    ))
+}
+
+// ============================================================================
+// Translation endpoints
+// ============================================================================
+
+/// Every post in the post's translation group, drafts included.
+#[debug_handler]
+pub async fn translations_list(
+    State(state): State<AppState>,
+    auth: AuthSession,
+    Path(post_id): Path<i32>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let user = auth.user.unwrap();
+    let post = ensure_post_access(&state, &user, post_id, PostAccess::View).await?;
+
+    let translations = match post.translation_group_id {
+        Some(group_id) => post_translation_group::Entity::members(&state.sea_db, group_id).await?,
+        None => Vec::new(),
+    };
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "post_id": post.id,
+            "locale": post.locale,
+            "translation_group_id": post.translation_group_id,
+            "data": translations,
+        })),
+    ))
+}
+
+/// Mark two posts as translations of each other.
+#[debug_handler]
+#[instrument(skip(state, auth), fields(user_id))]
+pub async fn translations_link(
+    State(state): State<AppState>,
+    auth: AuthSession,
+    Path((post_id, other_id)): Path<(i32, i32)>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let user = auth.user.unwrap();
+    tracing::Span::current().record("user_id", user.id);
+    if post_id == other_id {
+        return Err(ErrorResponse::new(ErrorCode::InvalidInput)
+            .with_message("A post can't be its own translation"));
+    }
+    ensure_post_access(&state, &user, post_id, PostAccess::Edit).await?;
+    ensure_post_access(&state, &user, other_id, PostAccess::Edit).await?;
+
+    let translations =
+        post_translation_group::Entity::link(&state.sea_db, post_id, other_id).await?;
+    Ok((
+        StatusCode::OK,
+        Json(json!({ "post_id": post_id, "data": translations })),
+    ))
+}
+
+#[debug_handler]
+pub async fn translations_unlink(
+    State(state): State<AppState>,
+    auth: AuthSession,
+    Path(post_id): Path<i32>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let user = auth.user.unwrap();
+    ensure_post_access(&state, &user, post_id, PostAccess::Edit).await?;
+
+    post_translation_group::Entity::unlink(&state.sea_db, post_id).await?;
+    Ok((
+        StatusCode::OK,
+        Json(json!({ "message": "Post removed from its translation group" })),
+    ))
+}
+
+// ============================================================================
+// Editorial review endpoints
+// ============================================================================
+
+/// Move a post through the review workflow (submit, approve, request
+/// changes, publish...). The change is recorded and announced by email.
+#[debug_handler]
+#[instrument(skip(state, auth, payload), fields(user_id, post_id = %post_id))]
+pub async fn review_transition(
+    State(state): State<AppState>,
+    auth: AuthSession,
+    Path(post_id): Path<i32>,
+    payload: ValidatedJson<V1PostTransitionPayload>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let user = auth.user.unwrap();
+    tracing::Span::current().record("user_id", user.id);
+    let current = ensure_post_access(&state, &user, post_id, PostAccess::Edit).await?;
+    let p = payload.0;
+
+    if p.status == current.status {
+        return Err(ErrorResponse::new(ErrorCode::InvalidInput)
+            .with_message(format!("The post is already {}", current.status)));
+    }
+    post_review::ensure_transition(&state, &user, current.status, p.status).await?;
+
+    let now = chrono::Utc::now().fixed_offset();
+    let update = UpdatePost {
+        title: None,
+        slug: None,
+        content: None,
+        excerpt: None,
+        featured_image_id: None,
+        status: Some(p.status),
+        published_at: (p.status == PostStatus::Published && current.published_at.is_none())
+            .then_some(now),
+        updated_at: now,
+        category_id: None,
+        view_count: None,
+        likes_count: None,
+        tag_ids: None,
+        visibility: None,
+        min_role: None,
+        password: None,
+        locale: None,
+        meta_title: None,
+        meta_description: None,
+        canonical_url: None,
+        noindex: None,
+        og_image_id: None,
+        og_image_variant: None,
+    };
+    let Some(post) = post::Entity::update(&state.sea_db, post_id, update).await? else {
+        return Err(
+            ErrorResponse::new(ErrorCode::RecordNotFound).with_message("Post does not exist")
+        );
+    };
+
+    info!(post_id, from = %current.status, to = %post.status, "Post status changed");
+    post_review::record_transition(
+        &state,
+        post.id,
+        &post.title,
+        current.status,
+        post.status,
+        &user,
+        p.note,
+    )
+    .await;
+    if post.status == PostStatus::Published {
+        websub::notify_post_published(&state, post.id);
+    }
+    Ok((StatusCode::OK, Json(json!(post))))
+}
+
+#[debug_handler]
+pub async fn review_history(
+    State(state): State<AppState>,
+    auth: AuthSession,
+    Path(post_id): Path<i32>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let user = auth.user.unwrap();
+    let current = ensure_post_access(&state, &user, post_id, PostAccess::View).await?;
+
+    let history = post_status_transition::Entity::list_for_post(&state.sea_db, post_id).await?;
+    Ok((
+        StatusCode::OK,
+        Json(json!({ "post_id": post_id, "status": current.status, "data": history })),
+    ))
+}
+
+#[debug_handler]
+pub async fn review_notes_list(
+    State(state): State<AppState>,
+    auth: AuthSession,
+    Path(post_id): Path<i32>,
+    payload: Option<ValidatedJson<V1ReviewNotesQuery>>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let user = auth.user.unwrap();
+    let current = ensure_post_access(&state, &user, post_id, PostAccess::View).await?;
+    let include_resolved = payload.and_then(|p| p.0.include_resolved).unwrap_or(false);
+
+    let blocks = post_review_note::block_ids(&current.content);
+    let notes =
+        post_review_note::Entity::list_for_post(&state.sea_db, post_id, include_resolved, &blocks)
+            .await?;
+    Ok((
+        StatusCode::OK,
+        Json(json!({ "post_id": post_id, "data": notes })),
+    ))
+}
+
+/// Add a reviewer note, optionally pinned to an Editor.js block.
+#[debug_handler]
+pub async fn review_note_create(
+    State(state): State<AppState>,
+    auth: AuthSession,
+    Path(post_id): Path<i32>,
+    payload: ValidatedJson<V1ReviewNoteCreatePayload>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let user = auth.user.unwrap();
+    let current = ensure_post_access(&state, &user, post_id, PostAccess::View).await?;
+    let p = payload.0;
+
+    if let Some(block_id) = &p.block_id {
+        if !post_review_note::block_ids(&current.content).contains(block_id) {
+            return Err(ErrorResponse::new(ErrorCode::InvalidInput)
+                .with_message("The post has no block with this id")
+                .with_context(json!({ "block_id": block_id })));
+        }
+    }
+
+    let note = post_review_note::Entity::create(
+        &state.sea_db,
+        post_review_note::NewReviewNote {
+            post_id,
+            block_id: p.block_id,
+            body: p.body,
+            author_id: user.id,
+        },
+    )
+    .await?;
+    Ok((StatusCode::CREATED, Json(json!(note))))
+}
+
+async fn find_review_note(
+    state: &AppState,
+    post_id: i32,
+    note_id: i32,
+) -> Result<post_review_note::Model, ErrorResponse> {
+    post_review_note::Entity::find_for_post(&state.sea_db, post_id, note_id)
+        .await?
+        .ok_or_else(|| {
+            ErrorResponse::new(ErrorCode::RecordNotFound).with_message("Review note not found")
+        })
+}
+
+/// Edit a note (its author or a moderator) or resolve/reopen it (also anyone
+/// who can edit the post).
+#[debug_handler]
+pub async fn review_note_update(
+    State(state): State<AppState>,
+    auth: AuthSession,
+    Path((post_id, note_id)): Path<(i32, i32)>,
+    payload: ValidatedJson<V1ReviewNoteUpdatePayload>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let user = auth.user.unwrap();
+    ensure_post_access(&state, &user, post_id, PostAccess::View).await?;
+    let note = find_review_note(&state, post_id, note_id).await?;
+    let p = payload.0;
+
+    let owns = note.author_id == Some(user.id)
+        || PermissionService::allows(&state, &user, permission_service::POST_REVIEW).await;
+    if p.body.is_some() && !owns {
+        return Err(ErrorResponse::new(ErrorCode::OperationNotAllowed)
+            .with_message("Only the note's author or a moderator can edit it"));
+    }
+    if p.resolved.is_some() && !owns {
+        ensure_post_access(&state, &user, post_id, PostAccess::Edit).await?;
+    }
+
+    let note = post_review_note::Entity::update(
+        &state.sea_db,
+        note,
+        post_review_note::UpdateReviewNote {
+            body: p.body,
+            resolved: p.resolved,
+        },
+        user.id,
+    )
+    .await?;
+    Ok((StatusCode::OK, Json(json!(note))))
+}
+
+#[debug_handler]
+pub async fn review_note_delete(
+    State(state): State<AppState>,
+    auth: AuthSession,
+    Path((post_id, note_id)): Path<(i32, i32)>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let user = auth.user.unwrap();
+    ensure_post_access(&state, &user, post_id, PostAccess::View).await?;
+    let note = find_review_note(&state, post_id, note_id).await?;
+
+    if note.author_id != Some(user.id)
+        && !PermissionService::allows(&state, &user, permission_service::POST_REVIEW).await
+    {
+        return Err(ErrorResponse::new(ErrorCode::OperationNotAllowed)
+            .with_message("Only the note's author or a moderator can delete it"));
+    }
+
+    post_review_note::Entity::delete(&state.sea_db, note.id).await?;
+    Ok((
+        StatusCode::OK,
+        Json(json!({ "message": "Review note deleted" })),
+    ))
+}
+
+// ============================================================================
+// Edit lock endpoints
+// ============================================================================
+
+/// Take or renew the soft edit lock (heartbeat). When another editor holds
+/// it, `acquired` is false and `lock` names them.
+#[debug_handler]
+pub async fn lock_acquire(
+    State(state): State<AppState>,
+    auth: AuthSession,
+    Path(post_id): Path<i32>,
+    payload: Option<ValidatedJson<V1PostLockPayload>>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let user = auth.user.unwrap();
+    let force = payload.and_then(|p| p.0.force).unwrap_or(false);
+    ensure_post_access(&state, &user, post_id, PostAccess::Edit).await?;
+
+    let status = post_lock::acquire(&state.redis_pool, post_id, user.id, &user.name, force).await?;
+    Ok((StatusCode::OK, Json(json!(status))))
+}
+
+#[debug_handler]
+pub async fn lock_status(
+    State(state): State<AppState>,
+    auth: AuthSession,
+    Path(post_id): Path<i32>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let user = auth.user.unwrap();
+    ensure_post_access(&state, &user, post_id, PostAccess::View).await?;
+    let status = post_lock::status(&state.redis_pool, post_id, user.id).await?;
+    Ok((StatusCode::OK, Json(json!(status))))
+}
+
+#[debug_handler]
+pub async fn lock_release(
+    State(state): State<AppState>,
+    auth: AuthSession,
+    Path(post_id): Path<i32>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let user = auth.user.unwrap();
+    let released = post_lock::release(&state.redis_pool, post_id, user.id).await?;
+    Ok((StatusCode::OK, Json(json!({ "released": released }))))
+}
+
 // ============================================================================
 // Like/Unlike endpoints
 // ============================================================================
@@ -661,3 +1525,194 @@ pub async fn like_status_batch(
         }
     }
 }
+
+/// Import posts from uploaded Markdown files or a zip archive of them
+#[debug_handler]
+#[instrument(skip(state, auth, multipart), fields(user_id, file_count, result))]
+pub async fn import_markdown(
+    State(state): State<AppState>,
+    auth: AuthSession,
+    mut multipart: ValidatedMultipart,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let user = auth.user.unwrap();
+    tracing::Span::current().record("user_id", user.id);
+
+    let mut options = V1MarkdownImportOptions::default();
+    let mut files = Vec::new();
+
+    while let Some(field) = multipart.next_field().await.map_err(|err| {
+        error!(error = %err, "Failed to read multipart field");
+        ErrorResponse::new(ErrorCode::ValidationError).with_details(err.to_string())
+    })? {
+        let field_name = field.name().unwrap_or_default().to_string();
+        match field.file_name().map(|name| name.to_string()) {
+            Some(name) => {
+                let bytes = field.bytes().await.map_err(|err| {
+                    ErrorResponse::new(ErrorCode::FileUploadError)
+                        .with_message("Failed to read uploaded file")
+                        .with_details(err.to_string())
+                })?;
+                files.push(post_markdown::import::ImportFile { name, bytes });
+            }
+            None => {
+                let value = field.text().await.map_err(|err| {
+                    ErrorResponse::new(ErrorCode::InvalidFormat)
+                        .with_message("Failed to read accompanying form field")
+                        .with_details(err.to_string())
+                })?;
+                options.apply_field(&field_name, &value).map_err(|msg| {
+                    ErrorResponse::new(ErrorCode::InvalidValue).with_message(&msg)
+                })?;
+            }
+        }
+    }
+
+    if files.is_empty() {
+        return Err(
+            ErrorResponse::new(ErrorCode::MissingRequiredField).with_message("Missing file field")
+        );
+    }
+    tracing::Span::current().record("file_count", files.len());
+
+    let report = post_markdown::import::import(&state, &user, files, options).await?;
+    tracing::Span::current().record("result", "success");
+    Ok((StatusCode::OK, Json(json!(report))))
+}
+
+/// Download a post as Markdown with YAML front matter (`/export/{id}.md`)
+#[debug_handler]
+#[instrument(skip(state, auth), fields(post_id, result))]
+pub async fn export_markdown(
+    State(state): State<AppState>,
+    auth: AuthSession,
+    Path(file): Path<String>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let user = auth.user.unwrap();
+    let post_id = file
+        .strip_suffix(".md")
+        .unwrap_or(&file)
+        .parse::<i32>()
+        .map_err(|_| ErrorResponse::new(ErrorCode::InvalidValue).with_message("Invalid post id"))?;
+    tracing::Span::current().record("post_id", post_id);
+    ensure_post_access(&state, &user, post_id, PostAccess::View).await?;
+
+    let post = post::Entity::find_by_id_or_slug(&state.sea_db, Some(post_id), None)
+        .await?
+        .ok_or_else(|| {
+            ErrorResponse::new(ErrorCode::RecordNotFound).with_message("Post not found")
+        })?;
+
+    let body = post_markdown::export(&post);
+    tracing::Span::current().record("result", "success");
+    Ok((
+        StatusCode::OK,
+        [
+            (
+                header::CONTENT_TYPE,
+                "text/markdown; charset=utf-8".to_string(),
+            ),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}.md\"", post.slug),
+            ),
+        ],
+        body,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{DateTime, Duration};
+
+    fn stored_post(updated_at: &str) -> post::Model {
+        let updated_at = DateTime::parse_from_rfc3339(updated_at).unwrap();
+        post::Model {
+            id: 4,
+            title: "Hello".to_string(),
+            slug: "hello".to_string(),
+            content: json!({ "blocks": [] }),
+            excerpt: None,
+            featured_image_id: None,
+            status: PostStatus::Draft,
+            published_at: None,
+            author_id: 1,
+            category_id: 1,
+            view_count: 0,
+            likes_count: 0,
+            tag_ids: vec![],
+            visibility: post::PostVisibility::Public,
+            min_role: None,
+            password_hash: None,
+            locale: "en".to_string(),
+            translation_group_id: None,
+            meta_title: None,
+            meta_description: None,
+            canonical_url: None,
+            noindex: false,
+            og_image_id: None,
+            og_image_variant: None,
+            created_at: updated_at,
+            updated_at,
+        }
+    }
+
+    #[test]
+    fn leaving_password_visibility_drops_the_hash() {
+        let update = |mut body: serde_json::Value| {
+            body["updated_at"] = json!("2026-03-01T10:00:00+00:00");
+            serde_json::from_value::<V1UpdatePostPayload>(body)
+                .unwrap()
+                .into_update_post()
+                .password
+        };
+        assert_eq!(update(json!({ "visibility": "public" })), Some(None));
+        assert_eq!(
+            update(json!({ "visibility": "members", "password": "secret" })),
+            Some(None)
+        );
+        assert_eq!(
+            update(json!({ "visibility": "password", "password": "secret" })),
+            Some(Some("secret".to_string()))
+        );
+        assert_eq!(update(json!({ "visibility": "password" })), None);
+        assert_eq!(update(json!({ "title": "Renamed" })), None);
+    }
+
+    #[test]
+    fn unmodified_posts_compare_at_microseconds() {
+        let current = stored_post("2026-03-01T10:00:00.123456789+00:00");
+        let loaded = DateTime::parse_from_rfc3339("2026-03-01T10:00:00.123456+00:00").unwrap();
+        assert!(UpdateOutcome::check_unmodified(current, loaded).is_ok());
+    }
+
+    #[test]
+    fn stale_updates_conflict_with_the_current_revision() {
+        let current = stored_post("2026-03-01T10:00:00.123456+00:00");
+        let loaded = current.updated_at - Duration::seconds(30);
+
+        let Err(UpdateOutcome::Conflict(stored)) =
+            UpdateOutcome::check_unmodified(current.clone(), loaded)
+        else {
+            panic!("stale update was not rejected");
+        };
+        assert_eq!(*stored, current);
+
+        let revision = post_revision::Model {
+            id: 9,
+            post_id: current.id,
+            content: "{}".to_string(),
+            metadata: None,
+            fields: None,
+            created_by: Some(2),
+            created_at: current.updated_at,
+        };
+        let error = conflict_error(*stored, Some(revision));
+        assert_eq!(error.status, 409);
+        assert_eq!(error.code, ErrorCode::ResourceConflict);
+        let context = error.context.unwrap();
+        assert_eq!(context["revision"]["id"], 9);
+        assert_eq!(context["post"]["id"], current.id);
+        assert_eq!(context["updated_at"], json!(current.updated_at));
+    }
+}
@@ -1,7 +1,12 @@
 pub mod controller;
 pub mod validator;
 
-use axum::{extract::DefaultBodyLimit, middleware, routing::post, Router};
+use axum::{
+    extract::DefaultBodyLimit,
+    middleware,
+    routing::{get, post},
+    Router,
+};
 
 use crate::{config, middlewares::auth_guard, AppState};
 
@@ -12,6 +17,10 @@ pub fn routes() -> Router<AppState> {
         .route("/autosave", post(controller::autosave))
         .layer(DefaultBodyLimit::max(config::body_limits::POST));
 
+    let import_limited = Router::<AppState>::new()
+        .route("/import/markdown", post(controller::import_markdown))
+        .layer(DefaultBodyLimit::max(config::body_limits::IMPORT));
+
     let protected = Router::<AppState>::new()
         .route("/query", post(controller::query))
         .route("/delete/{post_id}", post(controller::delete))
@@ -23,7 +32,42 @@ pub fn routes() -> Router<AppState> {
             "/revisions/{post_id}/restore/{revision_id}",
             post(controller::revisions_restore),
         )
+        .route(
+            "/revisions/{post_id}/diff/{from_id}/{to_id}",
+            post(controller::revisions_diff),
+        )
         .route("/schedule", post(controller::schedule))
+        .route(
+            "/review/{post_id}/transition",
+            post(controller::review_transition),
+        )
+        .route(
+            "/review/{post_id}/history",
+            post(controller::review_history),
+        )
+        .route(
+            "/review/{post_id}/notes",
+            post(controller::review_notes_list),
+        )
+        .route(
+            "/review/{post_id}/notes/create",
+            post(controller::review_note_create),
+        )
+        .route(
+            "/review/{post_id}/notes/{note_id}/update",
+            post(controller::review_note_update),
+        )
+        .route(
+            "/review/{post_id}/notes/{note_id}/delete",
+            post(controller::review_note_delete),
+        )
+        .route("/lock/{post_id}", post(controller::lock_acquire))
+        .route("/lock/{post_id}/status", post(controller::lock_status))
+        .route("/lock/{post_id}/release", post(controller::lock_release))
+        .route(
+            "/export/{file}",
+            get(controller::export_markdown).post(controller::export_markdown),
+        )
         .route("/series/create", post(controller::series_create))
         .route(
             "/series/update/{series_id}",
@@ -42,8 +86,35 @@ pub fn routes() -> Router<AppState> {
             "/series/remove/{post_id}/{series_id}",
             post(controller::series_remove),
         )
+        .route(
+            "/series/reorder/{series_id}",
+            post(controller::series_reorder),
+        )
+        .route(
+            "/contributors/{post_id}",
+            post(controller::contributors_list),
+        )
+        .route(
+            "/contributors/{post_id}/set",
+            post(controller::contributors_set),
+        )
+        .route(
+            "/translations/{post_id}",
+            post(controller::translations_list),
+        )
+        .route(
+            "/translations/{post_id}/link/{other_id}",
+            post(controller::translations_link),
+        )
+        .route(
+            "/translations/{post_id}/unlink",
+            post(controller::translations_unlink),
+        )
         .merge(post_limited)
-        .route_layer(middleware::from_fn(auth_guard::verified_with_role::<{ auth_guard::ROLE_AUTHOR }>));
+        .merge(import_limited)
+        .route_layer(middleware::from_fn(
+            auth_guard::verified_with_role::<{ auth_guard::ROLE_AUTHOR }>,
+        ));
 
     // Routes requiring authentication (any logged-in user)
     let authenticated = Router::<AppState>::new()
@@ -56,6 +127,9 @@ pub fn routes() -> Router<AppState> {
     let public = Router::<AppState>::new()
         .route("/view/{id_or_slug}", post(controller::find_by_id_or_slug))
         .route("/list/published", post(controller::find_published_posts))
+        .route("/series/view/{slug}", post(controller::series_view))
+        .route("/author/{author_id}", post(controller::author_view))
+        .route("/author/{author_id}/posts", post(controller::author_posts))
         .route("/sitemap", post(controller::sitemap))
         .route("/track_view/{post_id}", post(controller::track_view));
 
@@ -1,20 +1,26 @@
+use schemars::JsonSchema;
 use sea_orm::prelude::{DateTimeWithTimeZone, Json};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use validator::{Validate, ValidationError, ValidationErrors};
 
-use crate::db::sea_models::post::{NewPost, PostQuery, PostStatus, UpdatePost};
-use crate::utils::SortParam;
+use crate::db::sea_models::post::{NewPost, PostQuery, PostStatus, PostVisibility, UpdatePost};
+use crate::db::sea_models::post_author::NewContributor;
+use crate::db::sea_models::post_revision::RevisionField;
+use crate::db::sea_models::user::UserRole;
+use crate::services::content_render::RenderFormat;
+use crate::services::post_markdown::import::ConflictStrategy;
+use crate::utils::{normalize_locale, validate_locale, SortParam};
 
 // Validated Editor.js document types
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
 pub struct EditorJsDocument {
     pub time: Option<i64>,
     pub blocks: Vec<EditorJsBlock>,
     pub version: Option<String>,
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
 pub struct EditorJsBlock {
     #[serde(rename = "type")]
     pub kind: String,
@@ -258,7 +264,7 @@ impl EditorJsDocument {
     }
 }
 
-#[derive(Debug, Deserialize, Serialize, Validate)]
+#[derive(Debug, Deserialize, Serialize, Validate, JsonSchema)]
 pub struct V1CreatePostPayload {
     #[validate(length(min = 3, max = 255))]
     pub title: String,
@@ -273,8 +279,36 @@ pub struct V1CreatePostPayload {
     pub excerpt: Option<String>,
     pub featured_image_id: Option<i32>,
     pub category_id: i32,
-    #[serde(default = "Vec::new")]
+    #[serde(default)]
     pub tag_ids: Vec<i32>,
+    #[serde(default)]
+    pub visibility: PostVisibility,
+    /// Required with `visibility: role`.
+    pub min_role: Option<UserRole>,
+    /// Required with `visibility: password`.
+    #[validate(length(min = 4, max = 128))]
+    pub password: Option<String>,
+    /// BCP 47 tag such as `en` or `pt-BR`; the default locale when omitted.
+    #[validate(custom(function = "validate_locale"))]
+    pub locale: Option<String>,
+    /// Title for search results and link previews; `title` when omitted.
+    #[validate(length(min = 1, max = 255))]
+    pub meta_title: Option<String>,
+    /// Description for search results and link previews; the excerpt when
+    /// omitted.
+    #[validate(length(min = 1, max = 500))]
+    pub meta_description: Option<String>,
+    /// Canonical URL when the post was first published elsewhere.
+    #[validate(url)]
+    pub canonical_url: Option<String>,
+    #[serde(default)]
+    pub noindex: bool,
+    /// Image for Open Graph/Twitter cards; the featured image when omitted.
+    pub og_image_id: Option<i32>,
+    /// Variant of the image to share (`1200w`, ...); picked by size when
+    /// omitted.
+    #[validate(length(min = 1, max = 32))]
+    pub og_image_variant: Option<String>,
 }
 
 impl V1CreatePostPayload {
@@ -296,11 +330,21 @@ impl V1CreatePostPayload {
             view_count: 0,
             likes_count: 0,
             tag_ids: self.tag_ids,
+            visibility: self.visibility,
+            min_role: self.min_role,
+            password: self.password,
+            locale: self.locale.as_deref().and_then(normalize_locale),
+            meta_title: self.meta_title,
+            meta_description: self.meta_description,
+            canonical_url: self.canonical_url,
+            noindex: self.noindex,
+            og_image_id: self.og_image_id,
+            og_image_variant: self.og_image_variant,
         }
     }
 }
 
-#[derive(Debug, Deserialize, Serialize, Validate)]
+#[derive(Debug, Deserialize, Serialize, Validate, JsonSchema)]
 pub struct V1UpdatePostPayload {
     #[validate(length(min = 3, max = 255))]
     pub title: Option<String>,
@@ -315,6 +359,27 @@ pub struct V1UpdatePostPayload {
     pub featured_image_id: Option<Option<i32>>,
     pub category_id: Option<i32>,
     pub tag_ids: Option<Vec<i32>>,
+    pub visibility: Option<PostVisibility>,
+    pub min_role: Option<Option<UserRole>>,
+    /// Sets a new password for `visibility: password`; required when the
+    /// post becomes password-protected. Any other visibility drops it.
+    #[validate(length(min = 4, max = 128))]
+    pub password: Option<String>,
+    #[validate(custom(function = "validate_locale"))]
+    pub locale: Option<String>,
+    #[validate(length(min = 1, max = 255))]
+    pub meta_title: Option<Option<String>>,
+    #[validate(length(min = 1, max = 500))]
+    pub meta_description: Option<Option<String>>,
+    #[validate(url)]
+    pub canonical_url: Option<Option<String>>,
+    pub noindex: Option<bool>,
+    pub og_image_id: Option<Option<i32>>,
+    #[validate(length(min = 1, max = 32))]
+    pub og_image_variant: Option<Option<String>>,
+    /// The post's `updated_at` as loaded by the editor; the update is
+    /// rejected with a conflict if the post has changed since.
+    pub updated_at: DateTimeWithTimeZone,
 }
 
 impl V1UpdatePostPayload {
@@ -333,20 +398,42 @@ impl V1UpdatePostPayload {
             view_count: None,
             likes_count: None,
             tag_ids: self.tag_ids,
+            visibility: self.visibility,
+            min_role: self.min_role,
+            // Leaving `password` visibility drops the old hash
+            password: match self.visibility {
+                Some(visibility) if visibility != PostVisibility::Password => Some(None),
+                _ => self.password.map(Some),
+            },
+            locale: self.locale.as_deref().and_then(normalize_locale),
+            meta_title: self.meta_title,
+            meta_description: self.meta_description,
+            canonical_url: self.canonical_url,
+            noindex: self.noindex,
+            og_image_id: self.og_image_id,
+            og_image_variant: self.og_image_variant,
         }
     }
 }
 
-#[derive(Debug, Deserialize, Serialize, Validate, Clone)]
+#[derive(Debug, Deserialize, Serialize, Validate, Clone, JsonSchema)]
 pub struct V1PostQueryParams {
     pub page: Option<u64>,
     pub author_id: Option<i32>,
     pub category_id: Option<i32>,
+    /// With `category_id`, also match posts in its subcategories.
+    pub include_subcategories: Option<bool>,
     pub status: Option<PostStatus>,
     pub search: Option<String>,
     pub sorts: Option<Vec<SortParam>>,
     pub tag_ids: Option<Vec<i32>>,
     pub title: Option<String>,
+    pub visibility: Option<PostVisibility>,
+    #[validate(custom(function = "validate_locale"))]
+    pub locale: Option<String>,
+    /// With `locale`, fill in default-locale posts that have no translation
+    /// in it (default true).
+    pub locale_fallback: Option<bool>,
     // Date range filters
     pub created_at_gt: Option<DateTimeWithTimeZone>,
     pub created_at_lt: Option<DateTimeWithTimeZone>,
@@ -361,12 +448,17 @@ impl V1PostQueryParams {
         PostQuery {
             page_no: self.page,
             author_id: self.author_id,
+            contributor_id: None,
             category_id: self.category_id,
+            include_subcategories: self.include_subcategories,
             status: self.status,
             search: self.search,
             sorts: self.sorts,
             tag_ids: self.tag_ids,
             title: self.title,
+            visibility: self.visibility,
+            locale: self.locale.as_deref().and_then(normalize_locale),
+            locale_fallback: self.locale_fallback,
             created_at_gt: self.created_at_gt,
             created_at_lt: self.created_at_lt,
             updated_at_gt: self.updated_at_gt,
@@ -377,21 +469,104 @@ impl V1PostQueryParams {
     }
 }
 
-#[derive(Debug, Deserialize, Serialize, Validate)]
+#[derive(Debug, Deserialize, Serialize, Validate, Clone, Default, JsonSchema)]
+pub struct V1PostViewQuery {
+    /// Answer with the content rendered as `html`, `md` or `text`, served
+    /// with that format's `Content-Type`, instead of the JSON post.
+    pub format: Option<RenderFormat>,
+    /// Pick the post with this slug in the given locale.
+    #[validate(custom(function = "validate_locale"))]
+    pub locale: Option<String>,
+}
+
+/// Optional body of the post view: the password of a protected post.
+#[derive(Debug, Deserialize, Serialize, Validate, Clone, Default, JsonSchema)]
+pub struct V1PostUnlockPayload {
+    #[validate(length(max = 128))]
+    pub password: Option<String>,
+}
+
+/// Form fields accompanying a Markdown import upload.
+#[derive(Debug, Deserialize, Serialize, Validate, Clone, Default, JsonSchema)]
+pub struct V1MarkdownImportOptions {
+    /// Category used when a file's front matter names none.
+    pub category_id: Option<i32>,
+    /// What to do when a post with the same slug already exists.
+    #[serde(default)]
+    pub on_conflict: ConflictStrategy,
+}
+
+impl V1MarkdownImportOptions {
+    pub fn apply_field(&mut self, name: &str, value: &str) -> Result<(), String> {
+        match name {
+            "category_id" => {
+                if value.trim().is_empty() {
+                    self.category_id = None;
+                } else {
+                    self.category_id = Some(
+                        value
+                            .trim()
+                            .parse::<i32>()
+                            .map_err(|_| format!("Invalid category_id: {}", value.trim()))?,
+                    );
+                }
+            }
+            "on_conflict" => {
+                self.on_conflict = match value.trim().to_ascii_lowercase().as_str() {
+                    "" | "skip" => ConflictStrategy::Skip,
+                    "update" => ConflictStrategy::Update,
+                    other => return Err(format!("Invalid on_conflict: {}", other)),
+                };
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Validate, JsonSchema)]
 pub struct V1AutosavePayload {
     pub post_id: i32,
     #[validate(nested)]
     pub content: EditorJsDocument,
+    /// The post's `updated_at` as last seen by the editor (the previous
+    /// autosave's `post_updated_at`); a mismatch is a conflict.
     pub updated_at: DateTimeWithTimeZone,
 }
 
-#[derive(Debug, Deserialize, Serialize, Validate)]
+#[derive(Debug, Deserialize, Serialize, Validate, Default, JsonSchema)]
+pub struct V1PostLockPayload {
+    /// Take the lock over from another editor.
+    pub force: Option<bool>,
+}
+
+/// Fields to copy back from a revision; all fields when omitted.
+#[derive(Debug, Deserialize, Serialize, Validate, Clone, Default, JsonSchema)]
+pub struct V1RevisionRestorePayload {
+    #[validate(length(min = 1))]
+    pub fields: Option<Vec<RevisionField>>,
+}
+
+impl V1RevisionRestorePayload {
+    pub fn fields(&self) -> Vec<RevisionField> {
+        let mut fields = self
+            .fields
+            .clone()
+            .unwrap_or_else(|| RevisionField::ALL.to_vec());
+        fields.sort_by_key(|f| RevisionField::ALL.iter().position(|a| a == f));
+        fields.dedup();
+        fields
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Validate, JsonSchema)]
 pub struct V1SchedulePayload {
     pub post_id: i32,
     pub publish_at: DateTimeWithTimeZone,
 }
 
-#[derive(Debug, Deserialize, Serialize, Validate)]
+#[derive(Debug, Deserialize, Serialize, Validate, JsonSchema)]
 pub struct V1SeriesCreatePayload {
     #[validate(length(min = 3, max = 255))]
     pub name: String,
@@ -401,7 +576,7 @@ pub struct V1SeriesCreatePayload {
     pub description: Option<String>,
 }
 
-#[derive(Debug, Deserialize, Serialize, Validate)]
+#[derive(Debug, Deserialize, Serialize, Validate, JsonSchema)]
 pub struct V1SeriesUpdatePayload {
     #[validate(length(min = 3, max = 255))]
     pub name: Option<String>,
@@ -411,8 +586,56 @@ pub struct V1SeriesUpdatePayload {
     pub description: Option<String>,
 }
 
-#[derive(Debug, Deserialize, Serialize, Validate, Clone)]
+#[derive(Debug, Deserialize, Serialize, Validate, Clone, JsonSchema)]
 pub struct V1SeriesListQuery {
     pub page: Option<u64>,
     pub search: Option<String>,
 }
+
+#[derive(Debug, Deserialize, Serialize, Validate, JsonSchema)]
+pub struct V1SeriesReorderPayload {
+    /// Every post of the series, first to last.
+    #[validate(length(max = 1000))]
+    pub post_ids: Vec<i32>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Validate, JsonSchema)]
+pub struct V1SetContributorsPayload {
+    /// Everyone credited on the post, in display order.
+    #[validate(length(min = 1, max = 20))]
+    pub contributors: Vec<NewContributor>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Validate, Clone, Default, JsonSchema)]
+pub struct V1AuthorPostsQuery {
+    pub page: Option<u64>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Validate, JsonSchema)]
+pub struct V1PostTransitionPayload {
+    pub status: PostStatus,
+    /// Shown in the history and in the notification email.
+    #[validate(length(max = 2000))]
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Validate, Clone, Default, JsonSchema)]
+pub struct V1ReviewNotesQuery {
+    pub include_resolved: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Validate, JsonSchema)]
+pub struct V1ReviewNoteCreatePayload {
+    /// Editor.js block the note refers to; omit for the whole post.
+    #[validate(length(min = 1, max = 64))]
+    pub block_id: Option<String>,
+    #[validate(length(min = 1, max = 5000))]
+    pub body: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, Validate, JsonSchema)]
+pub struct V1ReviewNoteUpdatePayload {
+    #[validate(length(min = 1, max = 5000))]
+    pub body: Option<String>,
+    pub resolved: Option<bool>,
+}
@@ -16,7 +16,7 @@ use crate::{
         media_variant, newsletter_subscriber, post, post_comment, post_revision, post_series,
         post_view, route_status, scheduled_post, tag, user::UserRole, user_session,
     },
-    services::auth::AuthSession,
+    services::{admin_events::SeedReporter, auth::AuthSession},
     AppState,
 };
 
@@ -42,6 +42,7 @@ pub async fn seed_tags(State(state): State<AppState>, _auth: AuthSession) -> imp
             color: Some("#3b82f6".to_string()),
             text_color: None,
             is_active: Some(true),
+            locale: None,
         };
 
         match tag::Entity::create(&state.sea_db, new_tag).await {
@@ -86,6 +87,7 @@ pub async fn seed_categories(
             color: Some("#3b82f6".to_string()),
             text_color: None,
             is_active: Some(true),
+            locale: None,
         };
 
         match category::Entity::create(&state.sea_db, new_cat).await {
@@ -232,6 +234,16 @@ pub async fn seed_posts(State(state): State<AppState>, _auth: AuthSession) -> im
                 view_count: 0,
                 likes_count: 0,
                 tag_ids,
+                visibility: post::PostVisibility::Public,
+                min_role: None,
+                password: None,
+                locale: None,
+                meta_title: None,
+                meta_description: None,
+                canonical_url: None,
+                noindex: false,
+                og_image_id: None,
+                og_image_variant: None,
             };
 
             if let Err(err) = post::Entity::create(&state.sea_db, new_post).await {
@@ -334,7 +346,7 @@ pub async fn seed_post_comments(
             let new_comment = post_comment::NewComment {
                 post_id,
                 user_id: user.id,
-                // parent_id: None,
+                parent_id: None,
                 content,
                 likes_count: Some(0),
             };
@@ -566,6 +578,8 @@ pub async fn seed_post_revisions(
                 metadata: Some(serde_json::json!({
                     "title": format!("{} (Revision {})", post.title, i + 1)
                 })),
+                fields: None,
+                created_by: None,
                 created_at: chrono::Utc::now().fixed_offset()
                     - chrono::Duration::hours(i as i64 * 24),
             };
@@ -575,6 +589,8 @@ pub async fn seed_post_revisions(
                 post_id: Set(revision.post_id),
                 content: Set(revision.content),
                 metadata: Set(revision.metadata),
+                fields: Set(revision.fields),
+                created_by: Set(revision.created_by),
                 created_at: Set(revision.created_at),
             };
 
@@ -1237,6 +1253,14 @@ pub async fn seed_route_status(
             route_pattern: route.to_string(),
             is_blocked: false,
             reason: None,
+            match_type: "exact".to_string(),
+            methods: Vec::new(),
+            exempt_role: None,
+            active_from: None,
+            active_until: None,
+            status_code: None,
+            message: None,
+            retry_after_secs: None,
             created_at: chrono::Utc::now().fixed_offset(),
             updated_at: chrono::Utc::now().fixed_offset(),
         };
@@ -1246,6 +1270,14 @@ pub async fn seed_route_status(
             route_pattern: Set(route_status_entry.route_pattern),
             is_blocked: Set(route_status_entry.is_blocked),
             reason: Set(route_status_entry.reason),
+            match_type: Set(route_status_entry.match_type),
+            methods: Set(route_status_entry.methods),
+            exempt_role: Set(route_status_entry.exempt_role),
+            active_from: Set(route_status_entry.active_from),
+            active_until: Set(route_status_entry.active_until),
+            status_code: Set(route_status_entry.status_code),
+            message: Set(route_status_entry.message),
+            retry_after_secs: Set(route_status_entry.retry_after_secs),
             created_at: Set(route_status_entry.created_at),
             updated_at: Set(route_status_entry.updated_at),
         };
@@ -1289,7 +1321,19 @@ pub async fn seed(
         None
     };
 
-    match crate::services::seed::seed_all_with_progress(&state.sea_db, None, seed_mode).await {
+    let reporter = SeedReporter::start(&state);
+    let seeded = crate::services::seed::seed_all_with_progress(
+        &state.sea_db,
+        Some(reporter.callback()),
+        seed_mode,
+    )
+    .await;
+    match &seeded {
+        Ok(outcome) => reporter.finish("Seed finished".to_string(), outcome.seed_run_id),
+        Err(err) => reporter.finish(format!("Seed failed: {}", err), None),
+    }
+
+    match seeded {
         Ok(outcome) => {
             let counts = outcome.counts();
             (
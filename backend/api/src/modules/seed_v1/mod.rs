@@ -1,8 +1,8 @@
 pub mod controller;
 pub mod validator;
 
-use crate::AppState;
-use axum::{routing::post, Router};
+use crate::{middlewares::audit, AppState};
+use axum::{middleware, routing::post, Router};
 
 pub fn routes() -> Router<AppState> {
     Router::new()
@@ -43,4 +43,5 @@ pub fn routes() -> Router<AppState> {
         .route("/seed_route_status", post(controller::seed_route_status))
         .route("/seed", post(controller::seed))
         .route("/presets", post(controller::list_presets))
+        .route_layer(middleware::from_fn(audit::audit))
 }
@@ -1,9 +1,10 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use validator::Validate;
 
 use crate::services::seed_config::{preset_to_seed, SeedMode};
 
-#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+#[derive(Debug, Clone, Serialize, Deserialize, Validate, JsonSchema)]
 pub struct V1SeedPayload {
     /// Seed mode: "random" or "static" or "preset"
     pub seed_mode: Option<String>,
@@ -0,0 +1,264 @@
+use axum::{
+    extract::{Path, State},
+    http::{header, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+};
+use axum_macros::debug_handler;
+use sea_orm::{
+    prelude::DateTimeWithTimeZone, ColumnTrait, DatabaseConnection, EntityTrait, PaginatorTrait,
+    QueryFilter, QueryOrder, QuerySelect, Select,
+};
+use tracing::instrument;
+
+use crate::{
+    db::sea_models::{category, post, post_series, tag},
+    error::{ErrorCode, ErrorResponse},
+    services::{
+        seo::{self, SitemapUrl, SITEMAP_CHUNK},
+        websub,
+    },
+    utils::default_locale,
+    AppState,
+};
+
+const CACHE_CONTROL: &str = "public, max-age=3600, s-maxage=3600";
+
+/// Child sitemaps, in the order the index lists them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum SitemapKind {
+    Posts,
+    Categories,
+    Tags,
+    Series,
+}
+
+impl SitemapKind {
+    const ALL: [SitemapKind; 4] = [Self::Posts, Self::Categories, Self::Tags, Self::Series];
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Posts => "posts",
+            Self::Categories => "categories",
+            Self::Tags => "tags",
+            Self::Series => "series",
+        }
+    }
+}
+
+/// `posts-2.xml` -> (`Posts`, 2). Pages start at 1.
+fn parse_sitemap_name(name: &str) -> Option<(SitemapKind, u64)> {
+    let (kind, page) = name.strip_suffix(".xml")?.rsplit_once('-')?;
+    let kind = SitemapKind::ALL.into_iter().find(|k| k.name() == kind)?;
+    let page = page.parse::<u64>().ok().filter(|page| *page >= 1)?;
+    Some((kind, page))
+}
+
+fn pages(count: u64) -> u64 {
+    count.div_ceil(SITEMAP_CHUNK).max(1)
+}
+
+fn not_found() -> ErrorResponse {
+    ErrorResponse::new(ErrorCode::RecordNotFound).with_message("Sitemap not found")
+}
+
+fn respond(content_type: &'static str, body: String) -> Response {
+    (
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, HeaderValue::from_static(content_type)),
+            (
+                header::CACHE_CONTROL,
+                HeaderValue::from_static(CACHE_CONTROL),
+            ),
+        ],
+        body,
+    )
+        .into_response()
+}
+
+fn active_categories() -> Select<category::Entity> {
+    category::Entity::find().filter(category::Column::IsActive.eq(true))
+}
+
+fn active_tags() -> Select<tag::Entity> {
+    tag::Entity::find().filter(tag::Column::IsActive.eq(true))
+}
+
+async fn count(db: &DatabaseConnection, kind: SitemapKind) -> Result<u64, ErrorResponse> {
+    Ok(match kind {
+        SitemapKind::Posts => post::Entity::sitemap_count(db).await?,
+        SitemapKind::Categories => active_categories().count(db).await?,
+        SitemapKind::Tags => active_tags().count(db).await?,
+        SitemapKind::Series => post_series::Entity::find().count(db).await?,
+    })
+}
+
+/// Slugs and modification dates of one page of `query`, in slug order.
+async fn slug_page<E: EntityTrait>(
+    db: &DatabaseConnection,
+    query: Select<E>,
+    slug: E::Column,
+    updated_at: E::Column,
+    page: u64,
+) -> Result<Vec<(String, DateTimeWithTimeZone)>, ErrorResponse> {
+    Ok(query
+        .select_only()
+        .column(slug)
+        .column(updated_at)
+        .order_by_asc(slug)
+        .offset((page - 1) * SITEMAP_CHUNK)
+        .limit(SITEMAP_CHUNK)
+        .into_tuple()
+        .all(db)
+        .await?)
+}
+
+/// Posts whose canonical URL points to another site are left out.
+async fn post_urls(db: &DatabaseConnection, page: u64) -> Result<Vec<SitemapUrl>, ErrorResponse> {
+    let site_url = websub::site_url();
+    let posts = post::Entity::sitemap_chunk(db, (page - 1) * SITEMAP_CHUNK, SITEMAP_CHUNK).await?;
+    Ok(posts
+        .into_iter()
+        .filter_map(|p| {
+            let loc = match p.canonical_url {
+                Some(url) if url.starts_with(&site_url) => url,
+                Some(_) => return None,
+                None => seo::post_url(&p.locale, &p.slug),
+            };
+            Some(SitemapUrl {
+                loc,
+                lastmod: Some(p.updated_at),
+                alternates: p
+                    .alternates
+                    .into_iter()
+                    .map(|alt| {
+                        let href = if alt.hreflang == "x-default" {
+                            seo::post_url(default_locale(), &alt.slug)
+                        } else {
+                            seo::post_url(&alt.hreflang, &alt.slug)
+                        };
+                        (alt.hreflang, href)
+                    })
+                    .collect(),
+            })
+        })
+        .collect())
+}
+
+fn slug_urls(
+    rows: Vec<(String, DateTimeWithTimeZone)>,
+    url: fn(&str) -> String,
+) -> Vec<SitemapUrl> {
+    rows.into_iter()
+        .map(|(slug, updated_at)| SitemapUrl {
+            loc: url(&slug),
+            lastmod: Some(updated_at),
+            alternates: Vec::new(),
+        })
+        .collect()
+}
+
+/// Sitemap index listing every child sitemap.
+#[debug_handler]
+#[instrument(skip(state))]
+pub async fn sitemap_index(State(state): State<AppState>) -> Result<Response, ErrorResponse> {
+    let mut sitemaps = Vec::new();
+    for kind in SitemapKind::ALL {
+        let count = count(&state.sea_db, kind).await?;
+        if count == 0 {
+            continue;
+        }
+        sitemaps.extend((1..=pages(count)).map(|page| seo::sitemap_url(kind.name(), page)));
+    }
+    Ok(respond(
+        "application/xml; charset=utf-8",
+        seo::render_index(&sitemaps),
+    ))
+}
+
+/// One child sitemap, e.g. `posts-1.xml`.
+#[debug_handler]
+#[instrument(skip(state))]
+pub async fn sitemap(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+) -> Result<Response, ErrorResponse> {
+    let (kind, page) = parse_sitemap_name(&name).ok_or_else(not_found)?;
+    let db = &state.sea_db;
+    if page > pages(count(db, kind).await?) {
+        return Err(not_found());
+    }
+
+    let urls = match kind {
+        SitemapKind::Posts => post_urls(db, page).await?,
+        SitemapKind::Categories => slug_urls(
+            slug_page(
+                db,
+                active_categories(),
+                category::Column::Slug,
+                category::Column::UpdatedAt,
+                page,
+            )
+            .await?,
+            seo::category_url,
+        ),
+        SitemapKind::Tags => slug_urls(
+            slug_page(
+                db,
+                active_tags(),
+                tag::Column::Slug,
+                tag::Column::UpdatedAt,
+                page,
+            )
+            .await?,
+            seo::tag_url,
+        ),
+        SitemapKind::Series => slug_urls(
+            slug_page(
+                db,
+                post_series::Entity::find(),
+                post_series::Column::Slug,
+                post_series::Column::UpdatedAt,
+                page,
+            )
+            .await?,
+            seo::series_url,
+        ),
+    };
+    Ok(respond(
+        "application/xml; charset=utf-8",
+        seo::render_urlset(&urls),
+    ))
+}
+
+#[debug_handler]
+#[instrument(skip(state))]
+pub async fn robots(State(state): State<AppState>) -> Result<Response, ErrorResponse> {
+    let rules = seo::robots_rules(&state).await;
+    Ok(respond(
+        "text/plain; charset=utf-8",
+        seo::render_robots(&rules, &seo::sitemap_index_url()),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sitemap_names_parse_kind_and_page() {
+        assert_eq!(
+            parse_sitemap_name("posts-2.xml"),
+            Some((SitemapKind::Posts, 2))
+        );
+        assert_eq!(
+            parse_sitemap_name("series-1.xml"),
+            Some((SitemapKind::Series, 1))
+        );
+        assert_eq!(parse_sitemap_name("posts-0.xml"), None);
+        assert_eq!(parse_sitemap_name("users-1.xml"), None);
+        assert_eq!(parse_sitemap_name("posts-1"), None);
+        assert_eq!(pages(0), 1);
+        assert_eq!(pages(SITEMAP_CHUNK + 1), 2);
+    }
+}
@@ -0,0 +1,12 @@
+pub mod controller;
+
+use axum::{routing::get, Router};
+
+use crate::AppState;
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/sitemap.xml", get(controller::sitemap_index))
+        .route("/sitemaps/{name}", get(controller::sitemap))
+        .route("/robots.txt", get(controller::robots))
+}
@@ -7,7 +7,7 @@ use axum::{
     Router,
 };
 
-use crate::{middlewares::auth_guard, AppState};
+use crate::{middlewares::auth_guard, services::permission_service, AppState};
 
 pub fn routes() -> Router<AppState> {
     let admin = Router::<AppState>::new()
@@ -16,7 +16,9 @@ pub fn routes() -> Router<AppState> {
         .route("/delete/{tag_id}", post(controller::delete))
         .route("/view/{tag_id}", post(controller::find_by_id))
         .route("/list/query", post(controller::find_with_query))
-        .route_layer(middleware::from_fn(auth_guard::verified_with_role::<{ auth_guard::ROLE_ADMIN }>));
+        .route_layer(middleware::from_fn(auth_guard::verified_with_permission(
+            permission_service::TAXONOMY_MANAGE,
+        )));
 
     let public = Router::<AppState>::new().route("/list", get(controller::find_all));
 
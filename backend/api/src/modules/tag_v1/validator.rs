@@ -1,11 +1,12 @@
+use schemars::JsonSchema;
 use sea_orm::prelude::DateTimeWithTimeZone;
 use serde::{Deserialize, Serialize};
 use validator::Validate;
 
 use crate::db::sea_models::tag::{NewTag, TagQuery, UpdateTag};
-use crate::utils::SortParam;
+use crate::utils::{normalize_locale, validate_locale, SortParam};
 
-#[derive(Debug, Deserialize, Serialize, Validate)]
+#[derive(Debug, Deserialize, Serialize, Validate, JsonSchema)]
 pub struct V1CreateTagPayload {
     #[validate(length(min = 1, max = 255))]
     pub name: String,
@@ -18,6 +19,9 @@ pub struct V1CreateTagPayload {
     #[validate(custom(function = "validate_hex_color"), skip)]
     pub text_color: Option<String>,
     pub is_active: Option<bool>,
+    /// Leave out to share the tag across every locale.
+    #[validate(custom(function = "validate_locale"))]
+    pub locale: Option<String>,
 }
 
 impl V1CreateTagPayload {
@@ -29,11 +33,12 @@ impl V1CreateTagPayload {
             color: self.color,
             text_color: self.text_color,
             is_active: self.is_active,
+            locale: self.locale.as_deref().and_then(normalize_locale),
         }
     }
 }
 
-#[derive(Debug, Deserialize, Serialize, Validate)]
+#[derive(Debug, Deserialize, Serialize, Validate, JsonSchema)]
 pub struct V1UpdateTagPayload {
     #[validate(length(min = 1, max = 255))]
     pub name: Option<String>,
@@ -46,6 +51,8 @@ pub struct V1UpdateTagPayload {
     #[validate(custom(function = "validate_hex_color"), skip)]
     pub text_color: Option<String>,
     pub is_active: Option<bool>,
+    #[validate(custom(function = "validate_locale"))]
+    pub locale: Option<Option<String>>,
 }
 
 impl V1UpdateTagPayload {
@@ -57,17 +64,22 @@ impl V1UpdateTagPayload {
             color: self.color,
             text_color: self.text_color,
             is_active: self.is_active,
+            locale: self
+                .locale
+                .map(|locale| locale.as_deref().and_then(normalize_locale)),
             updated_at: chrono::Utc::now().fixed_offset(),
         }
     }
 }
 
-#[derive(Debug, Deserialize, Serialize, Validate)]
+#[derive(Debug, Deserialize, Serialize, Validate, JsonSchema)]
 pub struct V1TagQueryParams {
     pub page: Option<u64>,
     pub search: Option<String>,
     pub sorts: Option<Vec<SortParam>>, // [{ field, order }]
     pub is_active: Option<bool>,
+    #[validate(custom(function = "validate_locale"))]
+    pub locale: Option<String>,
     // Optional created_at/updated_at range filters (ISO8601)
     pub created_at_gt: Option<DateTimeWithTimeZone>,
     pub created_at_lt: Option<DateTimeWithTimeZone>,
@@ -82,6 +94,7 @@ impl V1TagQueryParams {
             search: self.search,
             sorts: self.sorts,
             is_active: self.is_active,
+            locale: self.locale.as_deref().and_then(normalize_locale),
             created_at_gt: self.created_at_gt,
             created_at_lt: self.created_at_lt,
             updated_at_gt: self.updated_at_gt,
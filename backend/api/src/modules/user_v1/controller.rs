@@ -10,10 +10,13 @@ use tracing::{error, info, instrument, warn};
 
 use super::validator::*;
 use crate::{
-    db::sea_models::user::Entity as User,
+    db::sea_models::{
+        user::{Entity as User, UserRole},
+        user_follow::Entity as UserFollow,
+    },
     error::{ErrorCode, ErrorResponse},
     extractors::ValidatedJson,
-    services::auth::AuthSession,
+    services::{audit::AuditEvent, auth::AuthSession},
     AppState,
 };
 
@@ -63,6 +66,71 @@ pub async fn update_profile(
     }
 }
 
+/// Follow an author to be notified when they publish
+#[debug_handler]
+#[instrument(skip(auth, state), fields(user_id = auth.user.as_ref().map(|u| u.id)))]
+pub async fn follow(
+    auth: AuthSession,
+    state: State<AppState>,
+    Path(followed_id): Path<i32>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let user = auth.user.unwrap();
+    if followed_id == user.id {
+        return Err(
+            ErrorResponse::new(ErrorCode::InvalidInput).with_message("You cannot follow yourself")
+        );
+    }
+    let Some(target) = User::get_by_id(&state.sea_db, followed_id).await? else {
+        return Err(ErrorResponse::new(ErrorCode::RecordNotFound)
+            .with_message("No user with this ID exists"));
+    };
+    if target.role.to_i32() < UserRole::Author.to_i32() {
+        return Err(ErrorResponse::new(ErrorCode::InvalidInput)
+            .with_message("Only authors can be followed"));
+    }
+
+    let followed = UserFollow::follow(&state.sea_db, user.id, followed_id).await?;
+    let followers = UserFollow::followers_count(&state.sea_db, followed_id).await?;
+    Ok(Json(json!({
+        "user_id": followed_id,
+        "is_following": true,
+        "followers_count": followers,
+        "message": if followed { "Now following" } else { "Already following" },
+    })))
+}
+
+#[debug_handler]
+#[instrument(skip(auth, state), fields(user_id = auth.user.as_ref().map(|u| u.id)))]
+pub async fn unfollow(
+    auth: AuthSession,
+    state: State<AppState>,
+    Path(followed_id): Path<i32>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let user = auth.user.unwrap();
+    let unfollowed = UserFollow::unfollow(&state.sea_db, user.id, followed_id).await?;
+    if unfollowed {
+        info!(user_id = user.id, followed_id, "User unfollowed");
+    }
+    let followers = UserFollow::followers_count(&state.sea_db, followed_id).await?;
+    Ok(Json(json!({
+        "user_id": followed_id,
+        "is_following": false,
+        "followers_count": followers,
+        "message": if unfollowed { "Unfollowed" } else { "Was not following" },
+    })))
+}
+
+/// Ids of the authors the current user follows
+#[debug_handler]
+pub async fn following(
+    auth: AuthSession,
+    state: State<AppState>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let user = auth.user.unwrap();
+    let following = UserFollow::following(&state.sea_db, user.id).await?;
+    Ok(Json(json!({ "data": following })))
+}
+
 #[debug_handler]
 #[instrument(skip(state, payload))]
 pub async fn admin_create(
@@ -72,7 +140,13 @@ pub async fn admin_create(
     let payload = payload.0.into_new_user();
     let user = User::admin_create(&state.sea_db, payload).await?;
     info!(user_id = user.id, "Admin created user");
-    Ok((StatusCode::CREATED, Json(json!(user))))
+    Ok((
+        StatusCode::CREATED,
+        AuditEvent::new("user.create")
+            .target("user", user.id)
+            .after(&user),
+        Json(json!(user)),
+    ))
 }
 
 #[debug_handler]
@@ -81,11 +155,16 @@ pub async fn admin_delete(
     state: State<AppState>,
     Path(user_id): Path<i32>,
 ) -> Result<impl IntoResponse, ErrorResponse> {
+    let before = User::get_by_id(&state.sea_db, user_id).await?;
+    let audit = AuditEvent::new("user.delete")
+        .target("user", user_id)
+        .before(&before);
     match User::admin_delete(&state.sea_db, user_id).await {
         Ok(1) => {
             info!(user_id, "Admin deleted user");
             Ok((
                 StatusCode::OK,
+                audit,
                 Json(json!({ "message": "User deleted successfully" })),
             ))
         }
@@ -97,6 +176,7 @@ pub async fn admin_delete(
             info!(user_id, "Admin deleted user");
             Ok((
                 StatusCode::OK,
+                audit,
                 Json(json!({ "message": "User deleted successfully" })),
             ))
         }
@@ -115,10 +195,18 @@ pub async fn admin_update(
     payload: ValidatedJson<V1AdminUpdateUserPayload>,
 ) -> Result<impl IntoResponse, ErrorResponse> {
     let payload = payload.0.into_update_user();
+    let before = User::get_by_id(&state.sea_db, user_id).await?;
     match User::admin_update(&state.sea_db, user_id, payload).await {
         Ok(Some(user)) => {
             info!(user_id, "Admin updated user");
-            Ok((StatusCode::OK, Json(json!(user))))
+            Ok((
+                StatusCode::OK,
+                AuditEvent::new("user.update")
+                    .target("user", user_id)
+                    .before(&before)
+                    .after(&user),
+                Json(json!(user)),
+            ))
         }
         Ok(None) => {
             warn!(user_id, "Admin tried to update non-existent user");
@@ -143,6 +231,7 @@ pub async fn admin_change_password(
     info!(user_id, "Admin changed user password");
     Ok((
         StatusCode::OK,
+        AuditEvent::new("user.change_password").target("user", user_id),
         Json(json!({ "message": "Password changed successfully" })),
     ))
 }
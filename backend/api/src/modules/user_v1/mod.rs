@@ -7,12 +7,19 @@ use axum::{
     Router,
 };
 
-use crate::{middlewares::auth_guard, AppState};
+use crate::{
+    middlewares::{audit, auth_guard},
+    services::permission_service,
+    AppState,
+};
 
 pub fn routes() -> Router<AppState> {
     // Only verified users can update
     let base = Router::<AppState>::new()
         .route("/update", post(controller::update_profile))
+        .route("/follow/{user_id}", post(controller::follow))
+        .route("/unfollow/{user_id}", post(controller::unfollow))
+        .route("/following", get(controller::following))
         .route_layer(middleware::from_fn(auth_guard::verified))
         // Any authenticated user can get their profile
         .merge(
@@ -27,7 +34,10 @@ pub fn routes() -> Router<AppState> {
         .route("/create", post(controller::admin_create))
         .route("/update/{user_id}", post(controller::admin_update))
         .route("/delete/{user_id}", post(controller::admin_delete))
-        .route_layer(middleware::from_fn(auth_guard::verified_with_role::<{ auth_guard::ROLE_ADMIN }>));
+        .route_layer(middleware::from_fn(auth_guard::verified_with_permission(
+            permission_service::USER_MANAGE,
+        )))
+        .route_layer(middleware::from_fn(audit::audit));
 
     base.nest("/admin", admin)
 }
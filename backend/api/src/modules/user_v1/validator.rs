@@ -1,3 +1,4 @@
+use schemars::JsonSchema;
 use sea_orm::prelude::DateTimeWithTimeZone;
 use serde::{Deserialize, Serialize};
 use validator::{Validate, ValidationError};
@@ -7,7 +8,7 @@ use crate::db::sea_models::user::{
 };
 use crate::utils::SortParam;
 
-#[derive(Debug, Deserialize, Serialize, Validate)]
+#[derive(Debug, Deserialize, Serialize, Validate, JsonSchema)]
 pub struct V1UpdateProfilePayload {
     #[validate(length(min = 1))]
     pub name: Option<String>,
@@ -27,7 +28,7 @@ impl V1UpdateProfilePayload {
     }
 }
 
-#[derive(Debug, Deserialize, Serialize, Validate)]
+#[derive(Debug, Deserialize, Serialize, Validate, JsonSchema)]
 pub struct V1AdminCreateUserPayload {
     #[validate(length(min = 1))]
     pub name: String,
@@ -67,7 +68,7 @@ impl V1AdminCreateUserPayload {
     }
 }
 
-#[derive(Debug, Deserialize, Validate)]
+#[derive(Debug, Deserialize, Validate, JsonSchema)]
 pub struct V1AdminUpdateUserPayload {
     #[validate(length(min = 1))]
     pub name: Option<String>,
@@ -95,13 +96,13 @@ impl V1AdminUpdateUserPayload {
     }
 }
 
-#[derive(Debug, Deserialize, Validate)]
+#[derive(Debug, Deserialize, Validate, JsonSchema)]
 pub struct AdminChangePassword {
     #[validate(length(min = 1))]
     pub password: String,
 }
 
-#[derive(Debug, Deserialize, Serialize, Validate, Clone)]
+#[derive(Debug, Deserialize, Serialize, Validate, Clone, JsonSchema)]
 pub struct V1AdminUserQueryParams {
     pub page: Option<u64>,
     pub email: Option<String>,
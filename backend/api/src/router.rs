@@ -8,8 +8,10 @@ use tracing::Level;
 use crate::middlewares::{http_metrics, request_id_middleware};
 use crate::modules::post_comment_v1;
 use crate::modules::{
-    admin_acl_v1, admin_route_v1, analytics_v1, category_v1, feed_v1, media_v1, newsletter_v1,
-    post_v1, seed_v1, tag_v1,
+    admin_acl_v1, admin_audit_v1, admin_events_v1, admin_job_v1, admin_permission_v1,
+    admin_redirect_v1, admin_route_v1, admin_webhook_v1, analytics_v1, backup_v1, category_v1,
+    docs_v1, feed_v1, import_v1, media_v1, newsletter_v1, notification_v1, post_v1, seed_v1,
+    seo_v1, tag_v1,
 };
 
 use super::{
@@ -31,11 +33,22 @@ pub fn router() -> Router<AppState> {
         .nest("/tag/v1", tag_v1::routes())
         .nest("/media/v1", media_v1::routes())
         .nest("/feed/v1", feed_v1::routes())
+        .nest("/seo/v1", seo_v1::routes())
         .nest("/newsletter/v1", newsletter_v1::routes())
+        .nest("/notification/v1", notification_v1::routes())
         .nest("/analytics/v1", analytics_v1::routes())
         .nest("/admin/route/v1", admin_route_v1::routes())
         .nest("/admin/acl/v1", admin_acl_v1::routes())
+        .nest("/admin/audit/v1", admin_audit_v1::routes())
+        .nest("/admin/events/v1", admin_events_v1::routes())
+        .nest("/admin/job/v1", admin_job_v1::routes())
+        .nest("/admin/permission/v1", admin_permission_v1::routes())
+        .nest("/admin/redirect/v1", admin_redirect_v1::routes())
+        .nest("/admin/webhook/v1", admin_webhook_v1::routes())
         .nest("/admin/seed/v1", seed_v1::routes())
+        .nest("/backup/v1", backup_v1::routes())
+        .nest("/import/v1", import_v1::routes())
+        .nest("/docs", docs_v1::routes())
         .layer(middleware::from_fn(request_id_middleware))
         .layer(middleware::from_fn(http_metrics::track_metrics))
         .layer(
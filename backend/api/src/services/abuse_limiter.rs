@@ -1,4 +1,4 @@
-use tower_sessions_redis_store::fred::interfaces::LuaInterface;
+use tower_sessions_redis_store::fred::interfaces::{KeysInterface, LuaInterface};
 use tower_sessions_redis_store::fred::prelude::Pool as RedisPool;
 use tower_sessions_redis_store::fred::types::{FromValue, Value};
 use tracing::{debug, error, info, instrument, warn};
@@ -234,8 +234,6 @@ pub async fn limiter(
     key_prefix: &str,
     config: AbuseLimiterConfig,
 ) -> Result<(), ErrorResponse> {
-    use serde_json::json;
-
     match check(redis_pool, key_prefix, config).await? {
         LimiterDecision::Allowed { .. } => {
             info!("Access allowed");
@@ -248,13 +246,42 @@ pub async fn limiter(
                 retry_after = retry_after_secs,
                 "Access denied - rate limited"
             );
-            Err(ErrorResponse::new(ErrorCode::TooManyAttempts)
-                .with_message(format!(
-                    "Too many attempts. Try again in {} seconds.",
-                    retry_after_secs
-                ))
-                .with_retry_after(retry_after_secs)
-                .with_context(json!({ "retryAfter": retry_after_secs })))
+            Err(too_many_attempts(retry_after_secs))
+        }
+    }
+}
+
+/// Seconds left on a block for `key_prefix`, if it is blocked. Unlike
+/// [`check`] this doesn't count as an attempt, so callers that only record
+/// failures can refuse a blocked client before doing any work.
+pub async fn blocked_for(
+    redis_pool: &RedisPool,
+    key_prefix: &str,
+) -> Result<Option<u64>, ErrorResponse> {
+    let block_key = format!("abuse_limiter:block:{}", key_prefix);
+    match redis_pool.ttl::<i64, _>(block_key).await {
+        Ok(ttl) if ttl > 0 => Ok(Some(ttl as u64)),
+        Ok(_) => Ok(None),
+        Err(err) => {
+            error!(
+                error = %err,
+                key_prefix = %key_prefix,
+                "Redis error during limiter block lookup"
+            );
+            Err(ErrorResponse::new(ErrorCode::ServiceUnavailable)
+                .with_message("Limiter unavailable (Redis error)")
+                .with_details(err.to_string()))
         }
     }
 }
+
+/// The error for a client blocked for another `retry_after_secs`.
+pub fn too_many_attempts(retry_after_secs: u64) -> ErrorResponse {
+    ErrorResponse::new(ErrorCode::TooManyAttempts)
+        .with_message(format!(
+            "Too many attempts. Try again in {} seconds.",
+            retry_after_secs
+        ))
+        .with_retry_after(retry_after_secs)
+        .with_context(serde_json::json!({ "retryAfter": retry_after_secs }))
+}
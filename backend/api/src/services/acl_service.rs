@@ -1,13 +1,19 @@
 use crate::db::sea_models::app_constant::Entity as AppConstant;
 use crate::db::sea_models::app_constant::Model as AppConstantModel;
+use crate::db::sea_models::app_constant::{ConstantWrite, REDACTED};
+use crate::db::sea_models::app_constant_change::{
+    ConstantAction, ConstantChangeEntry, Entity as AppConstantChange, NewConstantChange,
+};
 use crate::error::{ErrorCode, ErrorResponse};
+use crate::services::route_blocker_service::RouteBlockerService;
+use crate::services::settings::{self, crypto, Setting};
 use crate::state::AppState;
 use axum::extract::State;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::error::Error;
 use tower_sessions_redis_store::fred::prelude::*;
-use tracing::info;
+use tracing::{info, warn};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UpsertConstantPayload {
@@ -29,50 +35,174 @@ pub struct ConstantsListParams {
     pub value_type: Option<String>,
 }
 
+/// A registered setting with where its current value comes from.
+#[derive(Debug, Clone, Serialize)]
+pub struct SettingStatus {
+    #[serde(flatten)]
+    pub setting: Setting,
+    /// `db`, `env`, `default` or `unset`.
+    pub source: &'static str,
+    /// Current value, masked for sensitive settings.
+    pub value: Option<String>,
+}
+
 pub struct AclService;
 
+fn internal(err: impl ToString) -> ErrorResponse {
+    ErrorResponse::new(ErrorCode::InternalServerError).with_message(err.to_string())
+}
+
+fn missing_master_key() -> ErrorResponse {
+    ErrorResponse::new(ErrorCode::ConfigurationError).with_message(format!(
+        "{} must be set to store sensitive constants",
+        crypto::MASTER_KEY_ENV
+    ))
+}
+
 impl AclService {
     pub const VALUE_HASH: &'static str = "app_constants";
     pub const META_HASH: &'static str = "app_constants_meta";
 
+    /// Copy registered runtime settings from the environment into
+    /// `app_constants` (existing rows win), then seal any sensitive values
+    /// still stored in plain text, rewrap values sealed with the previous
+    /// master key and drop env copies of unregistered keys.
     pub async fn bootstrap_from_env(
         State(state): State<AppState>,
     ) -> Result<serde_json::Value, ErrorResponse> {
-        let env_vars: Vec<(String, String)> = std::env::vars().collect();
-        for (key, value) in env_vars {
-            let normalized_key = key.trim().to_string();
-            if normalized_key.is_empty() {
+        let master = crypto::master_key();
+        let mut imported = 0;
+
+        for setting in settings::REGISTRY.iter().filter(|s| s.runtime) {
+            let Ok(raw) = std::env::var(setting.key) else {
+                continue;
+            };
+            if raw.is_empty() {
                 continue;
             }
-            let is_sensitive =
-                Self::guess_sensitive(&normalized_key) || Self::guess_sensitive(&value);
+            let value = match setting.normalize(&raw) {
+                Ok(value) => value,
+                Err(err) => {
+                    warn!("{}; not importing it", err);
+                    continue;
+                }
+            };
+            let (value, is_encrypted) = if setting.sensitive {
+                let Some(master) = master.as_ref() else {
+                    warn!(
+                        key = setting.key,
+                        "{} is not set; not importing sensitive setting",
+                        crypto::MASTER_KEY_ENV
+                    );
+                    continue;
+                };
+                (crypto::seal(master, &value).map_err(internal)?, true)
+            } else {
+                (value, false)
+            };
 
-            let _ = AppConstant::ensure_exists(
+            let (model, created) = AppConstant::ensure_exists(
                 &state.sea_db,
-                &normalized_key,
-                &value,
-                None,
-                is_sensitive,
-                "env",
+                ConstantWrite {
+                    key: setting.key.to_string(),
+                    value,
+                    value_type: Some(setting.kind.as_str().to_string()),
+                    description: Some(setting.description.to_string()),
+                    is_sensitive: setting.sensitive,
+                    is_encrypted,
+                    source: "env".to_string(),
+                    updated_by: None,
+                },
             )
             .await
-            .map_err(|e| {
-                ErrorResponse::new(ErrorCode::InternalServerError).with_message(e.to_string())
-            })?;
+            .map_err(internal)?;
+
+            if created {
+                imported += 1;
+                AppConstantChange::record(
+                    &state.sea_db,
+                    NewConstantChange {
+                        key: model.key,
+                        action: ConstantAction::Import,
+                        old_value: None,
+                        new_value: Some(model.value),
+                        is_sensitive: model.is_sensitive,
+                        changed_by: None,
+                    },
+                )
+                .await?;
+            }
+        }
+
+        let runtime_keys: Vec<&str> = settings::REGISTRY
+            .iter()
+            .filter(|s| s.runtime)
+            .map(|s| s.key)
+            .collect();
+        let dropped = AppConstant::delete_env_copies(&state.sea_db, &runtime_keys)
+            .await
+            .map_err(internal)?;
+        if dropped > 0 {
+            info!(dropped, "Dropped env copies of unregistered constants");
         }
 
+        Self::secure_stored(&state, master.as_ref()).await?;
         Self::sync_all_to_redis(State(state)).await?;
 
-        Ok(json!({"message": "Env constants bootstrapped"}))
+        Ok(json!({
+            "message": "Env constants bootstrapped",
+            "imported": imported,
+            "dropped": dropped,
+        }))
     }
 
-    fn guess_sensitive(s: &str) -> bool {
-        let lower = s.to_ascii_lowercase();
-        lower.contains("secret")
-            || lower.contains("password")
-            || lower.contains("token")
-            || lower.contains("key")
-            || lower.contains("access")
+    /// Seal sensitive values stored in plain text and move values sealed
+    /// with the previous master key onto the current one.
+    async fn secure_stored(
+        state: &AppState,
+        master: Option<&crypto::MasterKey>,
+    ) -> Result<(), ErrorResponse> {
+        let previous = crypto::previous_master_key();
+
+        for model in AppConstant::all(&state.sea_db).await.map_err(internal)? {
+            if model.is_sensitive && !model.is_encrypted {
+                let Some(master) = master else {
+                    warn!(
+                        key = model.key,
+                        "Sensitive constant is stored in plain text; set {} to encrypt it",
+                        crypto::MASTER_KEY_ENV
+                    );
+                    continue;
+                };
+                let sealed = crypto::seal(master, &model.value).map_err(internal)?;
+                let key = model.key.clone();
+                AppConstant::replace_stored_value(&state.sea_db, model, sealed, true)
+                    .await
+                    .map_err(internal)?;
+                info!(key, "Encrypted sensitive constant");
+            } else if model.is_encrypted {
+                let (Some(master), Some(previous)) = (master, previous.as_ref()) else {
+                    continue;
+                };
+                if crypto::open(master, &model.value).is_ok() {
+                    continue;
+                }
+                match crypto::rewrap(previous, master, &model.value) {
+                    Ok(rewrapped) => {
+                        let key = model.key.clone();
+                        AppConstant::replace_stored_value(&state.sea_db, model, rewrapped, true)
+                            .await
+                            .map_err(internal)?;
+                        info!(key, "Rewrapped constant with the current master key");
+                    }
+                    Err(err) => {
+                        warn!(key = model.key, error = %err, "Constant opens with neither master key")
+                    }
+                }
+            }
+        }
+
+        Ok(())
     }
 
     pub async fn get_constant(
@@ -97,6 +227,10 @@ impl AclService {
                         .get("is_sensitive")
                         .and_then(|v| v.as_bool())
                         .unwrap_or(false);
+                    let is_encrypted = meta
+                        .get("is_encrypted")
+                        .and_then(|v| v.as_bool())
+                        .unwrap_or(false);
                     let model = AppConstantModel {
                         id: 0,
                         key: key.to_string(),
@@ -107,41 +241,51 @@ impl AclService {
                             .map(|s| s.to_string()),
                         description: None,
                         is_sensitive,
+                        is_encrypted,
                         source: "cache".to_string(),
                         updated_by: None,
                         created_at: chrono::Utc::now().fixed_offset(),
                         updated_at: chrono::Utc::now().fixed_offset(),
                     };
-                    return Ok(model);
+                    return Self::decrypted(model).await;
                 }
             }
         }
 
         let from_db = AppConstant::find_by_key(&state.sea_db, key)
             .await
-            .map_err(|e| {
-                ErrorResponse::new(ErrorCode::InternalServerError).with_message(e.to_string())
-            })?
+            .map_err(internal)?
             .ok_or_else(|| {
                 ErrorResponse::new(ErrorCode::RecordNotFound).with_message("Key not found")
             })?;
 
         Self::write_single_to_redis(&state, &from_db)
             .await
-            .map_err(|e| {
-                ErrorResponse::new(ErrorCode::InternalServerError).with_message(e.to_string())
-            })?;
+            .map_err(internal)?;
 
-        Ok(from_db)
+        Self::decrypted(from_db).await
+    }
+
+    async fn decrypted(mut model: AppConstantModel) -> Result<AppConstantModel, ErrorResponse> {
+        if model.is_encrypted {
+            let master = crypto::master_key().ok_or_else(missing_master_key)?;
+            model.value = crypto::open(&master, &model.value).map_err(|err| {
+                ErrorResponse::new(ErrorCode::InternalServerError)
+                    .with_message(format!("Could not decrypt {}: {}", model.key, err))
+            })?;
+            model.is_encrypted = false;
+        }
+        Ok(model)
     }
 
+    /// Constants as stored, with sensitive values masked.
     pub async fn list_constants(
         State(state): State<AppState>,
         params: ConstantsListParams,
     ) -> Result<(Vec<AppConstantModel>, u64), ErrorResponse> {
         let page = params.page.unwrap_or(1);
         let per_page = params.per_page.unwrap_or(20);
-        AppConstant::list(
+        let (items, total) = AppConstant::list(
             &state.sea_db,
             page,
             per_page,
@@ -150,9 +294,49 @@ impl AclService {
             params.value_type,
         )
         .await
-        .map_err(|e| ErrorResponse::new(ErrorCode::InternalServerError).with_message(e.to_string()))
+        .map_err(internal)?;
+        Ok((
+            items.into_iter().map(AppConstantModel::redacted).collect(),
+            total,
+        ))
+    }
+
+    /// Every registered setting with its current source and masked value.
+    pub async fn registry(
+        State(state): State<AppState>,
+    ) -> Result<Vec<SettingStatus>, ErrorResponse> {
+        let stored = AppConstant::all(&state.sea_db).await.map_err(internal)?;
+
+        Ok(settings::REGISTRY
+            .iter()
+            .map(|setting| {
+                let row = stored
+                    .iter()
+                    .find(|row| setting.runtime && row.key == setting.key);
+                let from_env = std::env::var(setting.key).is_ok_and(|v| !v.is_empty());
+                let (source, value) = match row {
+                    Some(row) => ("db", Some(row.value.clone())),
+                    None if from_env => ("env", setting.env_value()),
+                    None if setting.default.is_some() => ("default", setting.env_value()),
+                    None => ("unset", None),
+                };
+                let value = if setting.sensitive {
+                    value.map(|_| REDACTED.to_string())
+                } else {
+                    value
+                };
+                SettingStatus {
+                    setting: *setting,
+                    source,
+                    value,
+                }
+            })
+            .collect())
     }
 
+    /// Create or update a constant. Registered settings are validated against
+    /// the registry and must be runtime settings; sensitive values are sealed
+    /// before they're stored. The change is recorded against `updated_by`.
     pub async fn upsert_constant(
         State(state): State<AppState>,
         payload: UpsertConstantPayload,
@@ -165,32 +349,82 @@ impl AclService {
             );
         }
 
-        let value = payload.value;
-        let value_type = payload.value_type.clone();
-        let description = payload.description.clone();
-        let is_sensitive = payload.is_sensitive;
-        let source = payload.source.unwrap_or_else(|| "manual".to_string());
+        let (value, value_type, is_sensitive) = match settings::find(&key) {
+            Some(setting) if !setting.runtime => {
+                return Err(ErrorResponse::new(ErrorCode::InvalidInput)
+                    .with_message(format!(
+                        "{} is read from the environment at startup and can't be changed here",
+                        key
+                    ))
+                    .with_context(json!({ "key": key })));
+            }
+            Some(setting) => {
+                let value = setting.normalize(&payload.value).map_err(|err| {
+                    ErrorResponse::new(ErrorCode::InvalidInput)
+                        .with_message(err)
+                        .with_context(json!({ "key": key, "type": setting.kind }))
+                })?;
+                (
+                    value,
+                    Some(setting.kind.as_str().to_string()),
+                    setting.sensitive,
+                )
+            }
+            None => (payload.value, payload.value_type, payload.is_sensitive),
+        };
+
+        let existing = AppConstant::find_by_key(&state.sea_db, &key)
+            .await
+            .map_err(internal)?;
+        let description = payload.description.or_else(|| {
+            settings::find(&key)
+                .map(|s| s.description.to_string())
+                .or_else(|| existing.as_ref().and_then(|e| e.description.clone()))
+        });
+        let (stored_value, is_encrypted) = if is_sensitive {
+            let master = crypto::master_key().ok_or_else(missing_master_key)?;
+            (crypto::seal(&master, &value).map_err(internal)?, true)
+        } else {
+            (value.clone(), false)
+        };
 
         let model = AppConstant::upsert_value(
             &state.sea_db,
-            &key,
-            &value,
-            value_type.clone(),
-            description,
-            is_sensitive,
-            &source,
-            updated_by,
+            ConstantWrite {
+                key: key.clone(),
+                value: stored_value,
+                value_type,
+                description,
+                is_sensitive,
+                is_encrypted,
+                source: payload.source.unwrap_or_else(|| "manual".to_string()),
+                updated_by,
+            },
         )
         .await
-        .map_err(|e| {
-            ErrorResponse::new(ErrorCode::InternalServerError).with_message(e.to_string())
-        })?;
+        .map_err(internal)?;
+
+        AppConstantChange::record(
+            &state.sea_db,
+            NewConstantChange {
+                key: key.clone(),
+                action: if existing.is_some() {
+                    ConstantAction::Update
+                } else {
+                    ConstantAction::Create
+                },
+                old_value: existing.as_ref().map(|e| e.value.clone()),
+                new_value: Some(value),
+                is_sensitive: is_sensitive || existing.as_ref().is_some_and(|e| e.is_sensitive),
+                changed_by: updated_by,
+            },
+        )
+        .await?;
 
         Self::write_single_to_redis(&state, &model)
             .await
-            .map_err(|e| {
-                ErrorResponse::new(ErrorCode::InternalServerError).with_message(e.to_string())
-            })?;
+            .map_err(internal)?;
+        Self::after_change(&state, &key).await;
 
         info!(key, "ACL constant upserted and cached");
         Ok(model)
@@ -199,12 +433,30 @@ impl AclService {
     pub async fn delete_constant(
         State(state): State<AppState>,
         key: String,
+        deleted_by: Option<i32>,
     ) -> Result<(), ErrorResponse> {
+        let existing = AppConstant::find_by_key(&state.sea_db, &key)
+            .await
+            .map_err(internal)?;
+
         AppConstant::delete_by_key(&state.sea_db, &key)
             .await
-            .map_err(|e| {
-                ErrorResponse::new(ErrorCode::InternalServerError).with_message(e.to_string())
-            })?;
+            .map_err(internal)?;
+
+        if let Some(existing) = existing {
+            AppConstantChange::record(
+                &state.sea_db,
+                NewConstantChange {
+                    key: key.clone(),
+                    action: ConstantAction::Delete,
+                    old_value: Some(existing.value),
+                    new_value: None,
+                    is_sensitive: existing.is_sensitive,
+                    changed_by: deleted_by,
+                },
+            )
+            .await?;
+        }
 
         state
             .redis_pool
@@ -216,10 +468,29 @@ impl AclService {
             .hdel::<(), _, _>(Self::META_HASH, &key)
             .await
             .ok();
+        Self::after_change(&state, &key).await;
 
         Ok(())
     }
 
+    /// Settings cached outside the constants hash are republished here.
+    async fn after_change(state: &AppState, key: &str) {
+        if key.starts_with("MAINTENANCE_") {
+            if let Err(err) = RouteBlockerService::publish(state).await {
+                warn!(key, error = %err, "Failed to republish route blocker rules");
+            }
+        }
+    }
+
+    pub async fn history(
+        State(state): State<AppState>,
+        key: Option<String>,
+        page: u64,
+        per_page: u64,
+    ) -> Result<(Vec<ConstantChangeEntry>, u64), ErrorResponse> {
+        AppConstantChange::list(&state.sea_db, key, page, per_page).await
+    }
+
     pub async fn sync_all_to_redis(
         State(state): State<AppState>,
     ) -> Result<serde_json::Value, ErrorResponse> {
@@ -230,9 +501,7 @@ impl AclService {
             Self::META_HASH,
         )
         .await
-        .map_err(|e| {
-            ErrorResponse::new(ErrorCode::InternalServerError).with_message(e.to_string())
-        })?;
+        .map_err(internal)?;
 
         Ok(json!({"message": "ACL cache synced to redis"}))
     }
@@ -249,6 +518,7 @@ impl AclService {
         let meta = serde_json::json!({
             "value_type": model.value_type,
             "is_sensitive": model.is_sensitive,
+            "is_encrypted": model.is_encrypted,
             "updated_at": model.updated_at,
         })
         .to_string();
@@ -0,0 +1,399 @@
+//! Live notifications for the admin app.
+//!
+//! [`publish`] appends an [`AdminEvent`] to a capped Redis stream, which
+//! gives it an id, and then announces it on a pub/sub channel. Every
+//! instance keeps one subscriber on that channel (see [`start`]) and fans
+//! the events out to its open `admin_events_v1` streams, so an event raised
+//! on one replica reaches admins connected to any of them.
+//!
+//! Each event needs a permission; a connection only gets the events its
+//! user holds the permission for. A client that reconnects with
+//! `Last-Event-ID` is first sent what it missed from the stream log.
+
+use std::{
+    collections::BTreeSet,
+    convert::Infallible,
+    sync::{Arc, OnceLock},
+    time::Duration,
+};
+
+use axum::response::sse::Event;
+use serde::{Deserialize, Serialize};
+use tokio::sync::{broadcast, mpsc};
+use tokio_stream::wrappers::ReceiverStream;
+use tower_sessions_redis_store::fred::{
+    clients::SubscriberClient,
+    prelude::{EventInterface, PubsubInterface, StreamsInterface},
+};
+use tracing::{error, warn};
+
+use crate::{
+    db::sea_models::user,
+    services::{
+        permission_service, permission_service::PermissionService, redis, seed::ProgressCallback,
+    },
+    AppState,
+};
+
+/// Pub/sub channel carrying new events between instances.
+pub const CHANNEL: &str = "admin_events";
+/// Capped stream of recent events, replayed on `Last-Event-ID`.
+pub const LOG_KEY: &str = "admin_events:log";
+const LOG_MAX_LEN: i64 = 1000;
+
+/// Events buffered per instance for connections that fall behind.
+const HUB_CAPACITY: usize = 256;
+/// How often a connection reloads its user's permissions.
+const PERMISSION_REFRESH: Duration = Duration::from_secs(60);
+
+static HUB: OnceLock<broadcast::Sender<Arc<Envelope>>> = OnceLock::new();
+
+fn hub() -> &'static broadcast::Sender<Arc<Envelope>> {
+    HUB.get_or_init(|| broadcast::channel(HUB_CAPACITY).0)
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "data", rename_all = "snake_case")]
+pub enum AdminEvent {
+    CommentCreated {
+        comment_id: i32,
+        post_id: i32,
+        user_id: i32,
+        excerpt: String,
+    },
+    /// The comment's flag count reached `COMMENT_FLAG_ALERT_THRESHOLD`.
+    CommentFlagged {
+        comment_id: i32,
+        post_id: i32,
+        flags_count: i64,
+    },
+    /// Published by the scheduled post job.
+    PostPublished {
+        post_id: i32,
+        title: String,
+        slug: String,
+    },
+    SeedProgress {
+        /// Groups the messages of one seed request.
+        run: String,
+        message: String,
+        finished: bool,
+        seed_run_id: Option<i32>,
+    },
+    /// A job failed on its last attempt and is now dead.
+    JobFailed {
+        job_id: i64,
+        kind: String,
+        queue: String,
+        attempts: i32,
+        error: String,
+    },
+}
+
+impl AdminEvent {
+    /// SSE event name.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::CommentCreated { .. } => "comment.created",
+            Self::CommentFlagged { .. } => "comment.flagged",
+            Self::PostPublished { .. } => "post.published",
+            Self::SeedProgress { .. } => "seed.progress",
+            Self::JobFailed { .. } => "job.failed",
+        }
+    }
+
+    /// Permission a user needs to receive the event.
+    pub fn permission(&self) -> &'static str {
+        match self {
+            Self::CommentCreated { .. } | Self::CommentFlagged { .. } => {
+                permission_service::COMMENT_MODERATE
+            }
+            Self::PostPublished { .. } => permission_service::POST_VIEW_ANY,
+            // Seeding rewrites data wholesale, like a restore.
+            Self::SeedProgress { .. } => permission_service::BACKUP_MANAGE,
+            Self::JobFailed { .. } => permission_service::JOB_MANAGE,
+        }
+    }
+}
+
+/// An event with its stream id and the time it was raised.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Envelope {
+    pub id: String,
+    pub at: chrono::DateTime<chrono::Utc>,
+    #[serde(flatten)]
+    pub event: AdminEvent,
+}
+
+impl Envelope {
+    fn to_sse(&self) -> Event {
+        Event::default()
+            .id(&self.id)
+            .event(self.event.name())
+            .json_data(self)
+            .unwrap_or_else(|_| Event::default().id(&self.id).event(self.event.name()))
+    }
+}
+
+/// Record `event` and announce it to every instance. Failures are logged;
+/// notifications never fail the work that raised them.
+pub async fn publish(state: &AppState, event: AdminEvent) {
+    let at = chrono::Utc::now();
+    let name = event.name();
+    let body = match serde_json::to_string(&(at, &event)) {
+        Ok(body) => body,
+        Err(err) => {
+            error!(event = name, error = ?err, "Failed to encode admin event");
+            return;
+        }
+    };
+
+    let id: String = match state
+        .redis_pool
+        .xadd(
+            LOG_KEY,
+            false,
+            ("MAXLEN", "~", LOG_MAX_LEN),
+            "*",
+            vec![("event", body)],
+        )
+        .await
+    {
+        Ok(id) => id,
+        Err(err) => {
+            error!(event = name, error = ?err, "Failed to record admin event");
+            return;
+        }
+    };
+
+    let envelope = Envelope { id, at, event };
+    let message = match serde_json::to_string(&envelope) {
+        Ok(message) => message,
+        Err(_) => return,
+    };
+    if let Err(err) = state
+        .redis_pool
+        .next()
+        .publish::<(), _, _>(CHANNEL, message)
+        .await
+    {
+        error!(event = name, error = ?err, "Failed to announce admin event");
+    }
+}
+
+/// [`publish`] from code that can't wait for it.
+pub fn spawn_publish(state: &AppState, event: AdminEvent) {
+    let state = state.clone();
+    tokio::spawn(async move { publish(&state, event).await });
+}
+
+/// Relays one seed run's progress as [`AdminEvent::SeedProgress`], in order.
+pub struct SeedReporter {
+    run: String,
+    tx: mpsc::UnboundedSender<AdminEvent>,
+}
+
+impl SeedReporter {
+    pub fn start(state: &AppState) -> Self {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let state = state.clone();
+        tokio::spawn(async move {
+            while let Some(event) = rx.recv().await {
+                publish(&state, event).await;
+            }
+        });
+        Self {
+            run: uuid::Uuid::new_v4().to_string(),
+            tx,
+        }
+    }
+
+    /// Progress callback for the seeder.
+    pub fn callback(&self) -> ProgressCallback {
+        let run = self.run.clone();
+        let tx = self.tx.clone();
+        Box::new(move |message| {
+            let _ = tx.send(AdminEvent::SeedProgress {
+                run: run.clone(),
+                message,
+                finished: false,
+                seed_run_id: None,
+            });
+        })
+    }
+
+    /// Send the last event of the run.
+    pub fn finish(self, message: String, seed_run_id: Option<i32>) {
+        let _ = self.tx.send(AdminEvent::SeedProgress {
+            run: self.run,
+            message,
+            finished: true,
+            seed_run_id,
+        });
+    }
+}
+
+/// Subscribe this instance to the event channel. Keep the client to close
+/// it on shutdown.
+pub async fn start() -> Result<SubscriberClient, tower_sessions_redis_store::fred::error::Error> {
+    let subscriber = redis::init_subscriber().await?;
+    let mut messages = subscriber.message_rx();
+    subscriber.subscribe(CHANNEL).await?;
+
+    tokio::spawn(async move {
+        loop {
+            match messages.recv().await {
+                Ok(message) => {
+                    let Some(text) = message.value.as_str() else {
+                        continue;
+                    };
+                    match serde_json::from_str::<Envelope>(&text) {
+                        // No open streams is fine.
+                        Ok(envelope) => {
+                            let _ = hub().send(Arc::new(envelope));
+                        }
+                        Err(err) => warn!(error = ?err, "Ignoring malformed admin event"),
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    warn!(skipped, "Admin event subscriber fell behind");
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    });
+    Ok(subscriber)
+}
+
+/// Events for `user`: what they missed since `last_event_id`, then live ones.
+pub fn stream(
+    state: AppState,
+    user: user::Model,
+    last_event_id: Option<String>,
+) -> ReceiverStream<Result<Event, Infallible>> {
+    let (tx, rx) = mpsc::channel(32);
+    // Subscribe before replaying so nothing falls between the two.
+    let mut live = hub().subscribe();
+
+    tokio::spawn(async move {
+        let mut permissions = permissions_of(&state, &user).await;
+        let mut last_sent = last_event_id.as_deref().and_then(parse_id);
+
+        if let Some(after) = last_sent {
+            for envelope in missed_since(&state, after).await {
+                if !permissions.contains(envelope.event.permission()) {
+                    continue;
+                }
+                last_sent = parse_id(&envelope.id);
+                if tx.send(Ok(envelope.to_sse())).await.is_err() {
+                    return;
+                }
+            }
+        }
+
+        let mut refresh = tokio::time::interval(PERMISSION_REFRESH);
+        refresh.tick().await;
+        loop {
+            tokio::select! {
+                received = live.recv() => match received {
+                    Ok(envelope) => {
+                        let id = parse_id(&envelope.id);
+                        if id.is_some_and(|id| last_sent.is_some_and(|last| id <= last)) {
+                            continue;
+                        }
+                        if !permissions.contains(envelope.event.permission()) {
+                            continue;
+                        }
+                        last_sent = id.or(last_sent);
+                        if tx.send(Ok(envelope.to_sse())).await.is_err() {
+                            return;
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        warn!(user_id = user.id, skipped, "Admin event stream fell behind");
+                    }
+                    Err(broadcast::error::RecvError::Closed) => return,
+                },
+                _ = refresh.tick() => permissions = permissions_of(&state, &user).await,
+                _ = tx.closed() => return,
+            }
+        }
+    });
+
+    ReceiverStream::new(rx)
+}
+
+async fn permissions_of(state: &AppState, user: &user::Model) -> BTreeSet<String> {
+    PermissionService::effective_for(&state.sea_db, Some(&state.redis_pool), user)
+        .await
+        .unwrap_or_else(|err| {
+            warn!(user_id = user.id, error = ?err, "Permission lookup failed");
+            BTreeSet::new()
+        })
+}
+
+/// Logged events after `after`, oldest first.
+async fn missed_since(state: &AppState, after: (u64, u64)) -> Vec<Envelope> {
+    let start = format!("({}-{}", after.0, after.1);
+    let entries: Vec<(String, std::collections::HashMap<String, String>)> = match state
+        .redis_pool
+        .xrange_values(LOG_KEY, start, "+", Some(LOG_MAX_LEN as u64))
+        .await
+    {
+        Ok(entries) => entries,
+        Err(err) => {
+            warn!(error = ?err, "Failed to read the admin event log");
+            return Vec::new();
+        }
+    };
+
+    entries
+        .into_iter()
+        .filter_map(|(id, fields)| {
+            let (at, event) = serde_json::from_str(fields.get("event")?).ok()?;
+            Some(Envelope { id, at, event })
+        })
+        .collect()
+}
+
+/// A Redis stream id, `<ms>-<seq>`.
+fn parse_id(id: &str) -> Option<(u64, u64)> {
+    let (ms, seq) = id.trim().split_once('-')?;
+    Some((ms.parse().ok()?, seq.parse().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stream_ids_order_numerically() {
+        assert_eq!(parse_id("1700000000000-3"), Some((1_700_000_000_000, 3)));
+        assert!(parse_id("1700000000000-10") > parse_id("1700000000000-9"));
+        assert!(parse_id("1700000000001-0") > parse_id("1700000000000-99"));
+        assert_eq!(parse_id("nope"), None);
+        assert_eq!(parse_id("12-x"), None);
+    }
+
+    #[test]
+    fn envelopes_round_trip_with_the_event_type() {
+        let envelope = Envelope {
+            id: "1-0".to_string(),
+            at: chrono::Utc::now(),
+            event: AdminEvent::JobFailed {
+                job_id: 7,
+                kind: "newsletter.send".to_string(),
+                queue: "mail".to_string(),
+                attempts: 3,
+                error: "SMTP down".to_string(),
+            },
+        };
+        let json = serde_json::to_value(&envelope).unwrap();
+        assert_eq!(json["type"], "job_failed");
+        assert_eq!(json["data"]["job_id"], 7);
+
+        let back: Envelope = serde_json::from_value(json).unwrap();
+        assert_eq!(back.event, envelope.event);
+        assert_eq!(back.event.permission(), permission_service::JOB_MANAGE);
+    }
+}
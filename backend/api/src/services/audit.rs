@@ -0,0 +1,276 @@
+//! Audit trail of privileged actions.
+//!
+//! The `audit` middleware writes one `audit_logs` row per successful write
+//! request on the admin routers. Handlers describe what they did by returning
+//! an [`AuditEvent`] as a response part:
+//!
+//! ```ignore
+//! Ok((
+//!     AuditEvent::new("user.update").target("user", user_id).before(&old).after(&user),
+//!     Json(json!(user)),
+//! ))
+//! ```
+//!
+//! Requests without an event are still recorded under `METHOD /matched/path`.
+//! Snapshots are stored with secrets redacted, next to a diff of the fields
+//! that changed.
+
+use std::convert::Infallible;
+
+use axum::response::{IntoResponseParts, ResponseParts};
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::{
+    db::sea_models::audit_log::{self, AuditLogEntry},
+    error::DbResult,
+    services::settings,
+    AppState,
+};
+
+/// Field names whose values never reach the audit log.
+const REDACTED_KEYS: [&str; 6] = [
+    "password",
+    "secret",
+    "token",
+    "passphrase",
+    "two_fa",
+    "backup_codes",
+];
+const REDACTED: &str = "[redacted]";
+
+/// What a handler did, picked up by the `audit` middleware from the
+/// response extensions.
+#[derive(Clone, Debug, Default)]
+pub struct AuditEvent {
+    pub action: String,
+    pub entity_type: Option<String>,
+    pub entity_id: Option<String>,
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+}
+
+impl AuditEvent {
+    pub fn new(action: impl Into<String>) -> Self {
+        Self {
+            action: action.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn target(mut self, entity_type: &str, entity_id: impl ToString) -> Self {
+        self.entity_type = Some(entity_type.to_string());
+        self.entity_id = Some(entity_id.to_string());
+        self
+    }
+
+    pub fn before<T: Serialize>(mut self, value: &T) -> Self {
+        self.before = serde_json::to_value(value).ok();
+        self
+    }
+
+    pub fn after<T: Serialize>(mut self, value: &T) -> Self {
+        self.after = serde_json::to_value(value).ok();
+        self
+    }
+}
+
+impl IntoResponseParts for AuditEvent {
+    type Error = Infallible;
+
+    fn into_response_parts(self, mut res: ResponseParts) -> Result<ResponseParts, Self::Error> {
+        res.extensions_mut().insert(self);
+        Ok(res)
+    }
+}
+
+fn is_redacted(key: &str) -> bool {
+    let key = key.to_ascii_lowercase();
+    REDACTED_KEYS.iter().any(|needle| key.contains(needle))
+}
+
+/// Copy of `value` with secret-looking fields replaced.
+pub fn redact(value: Value) -> Value {
+    match value {
+        Value::Object(map) => Value::Object(
+            map.into_iter()
+                .map(|(key, value)| {
+                    let value = if is_redacted(&key) && !value.is_null() {
+                        Value::String(REDACTED.to_string())
+                    } else {
+                        redact(value)
+                    };
+                    (key, value)
+                })
+                .collect(),
+        ),
+        Value::Array(items) => Value::Array(items.into_iter().map(redact).collect()),
+        other => other,
+    }
+}
+
+fn flatten(prefix: &str, value: &Value, out: &mut Map<String, Value>) {
+    match value {
+        Value::Object(map) if !map.is_empty() => {
+            for (key, value) in map {
+                let path = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", prefix, key)
+                };
+                flatten(&path, value, out);
+            }
+        }
+        other => {
+            out.insert(prefix.to_string(), other.clone());
+        }
+    }
+}
+
+/// Fields that differ between two snapshots, keyed by dotted path:
+/// `{ "role": { "before": "user", "after": "admin" } }`. Arrays compare as a
+/// whole. `None` when nothing changed.
+pub fn diff(before: Option<&Value>, after: Option<&Value>) -> Option<Value> {
+    let mut old = Map::new();
+    let mut new = Map::new();
+    if let Some(before) = before {
+        flatten("", before, &mut old);
+    }
+    if let Some(after) = after {
+        flatten("", after, &mut new);
+    }
+
+    let mut keys: Vec<&String> = old.keys().chain(new.keys()).collect();
+    keys.sort();
+    keys.dedup();
+
+    let changes: Map<String, Value> = keys
+        .into_iter()
+        .filter_map(|key| {
+            let was = old.get(key).unwrap_or(&Value::Null);
+            let now = new.get(key).unwrap_or(&Value::Null);
+            (was != now).then(|| {
+                (
+                    key.clone(),
+                    serde_json::json!({ "before": was, "after": now }),
+                )
+            })
+        })
+        .collect();
+
+    (!changes.is_empty()).then_some(Value::Object(changes))
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Entries as CSV, one row each; snapshots and diffs as JSON text.
+pub fn to_csv(entries: &[AuditLogEntry]) -> String {
+    let json = |value: &Option<Value>| value.as_ref().map(Value::to_string).unwrap_or_default();
+    let mut out = String::from(
+        "id,created_at,actor_id,actor_name,actor_role,action,entity_type,entity_id,method,path,status,ip,request_id,diff,before,after\n",
+    );
+    for entry in entries {
+        let log = &entry.log;
+        let row = [
+            log.id.to_string(),
+            log.created_at.to_rfc3339(),
+            log.actor_id.map(|id| id.to_string()).unwrap_or_default(),
+            entry.actor_name.clone().unwrap_or_default(),
+            log.actor_role
+                .map(|role| role.to_string())
+                .unwrap_or_default(),
+            log.action.clone(),
+            log.entity_type.clone().unwrap_or_default(),
+            log.entity_id.clone().unwrap_or_default(),
+            log.method.clone(),
+            log.path.clone(),
+            log.status.to_string(),
+            log.ip.clone().unwrap_or_default(),
+            log.request_id.clone().unwrap_or_default(),
+            json(&log.diff),
+            json(&log.before),
+            json(&log.after),
+        ];
+        let row: Vec<String> = row.iter().map(|field| csv_field(field)).collect();
+        out.push_str(&row.join(","));
+        out.push('\n');
+    }
+    out
+}
+
+pub struct AuditService;
+
+impl AuditService {
+    /// Delete entries older than `AUDIT_RETENTION_DAYS`; 0 keeps everything.
+    /// Runs as the `audit.purge` job schedule.
+    pub async fn purge_expired(state: &AppState) -> DbResult<u64> {
+        let days = settings::AUDIT_RETENTION_DAYS.int(state).await;
+        if days <= 0 {
+            return Ok(0);
+        }
+        let cutoff = chrono::Utc::now() - chrono::Duration::days(days);
+        audit_log::Entity::purge_before(&state.sea_db, cutoff.fixed_offset()).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn diff_lists_changed_fields_only() {
+        let before = json!({ "name": "Ann", "role": "user", "meta": { "a": 1, "b": 2 } });
+        let after = json!({ "name": "Ann", "role": "admin", "meta": { "a": 1, "b": 3 } });
+
+        let changes = diff(Some(&before), Some(&after)).unwrap();
+        assert_eq!(
+            changes,
+            json!({
+                "meta.b": { "before": 2, "after": 3 },
+                "role": { "before": "user", "after": "admin" },
+            })
+        );
+        assert!(diff(Some(&before), Some(&before)).is_none());
+    }
+
+    #[test]
+    fn diff_of_create_and_delete() {
+        let row = json!({ "id": 4, "hidden": false });
+        assert_eq!(
+            diff(None, Some(&row)).unwrap()["id"],
+            json!({ "before": null, "after": 4 })
+        );
+        assert_eq!(
+            diff(Some(&row), None).unwrap()["hidden"],
+            json!({ "before": false, "after": null })
+        );
+    }
+
+    #[test]
+    fn secrets_are_redacted() {
+        let value = redact(json!({
+            "email": "a@b.c",
+            "password": "hunter2",
+            "two_fa_secret": null,
+            "nested": [{ "api_token": "x" }],
+        }));
+        assert_eq!(value["email"], "a@b.c");
+        assert_eq!(value["password"], REDACTED);
+        assert!(value["two_fa_secret"].is_null());
+        assert_eq!(value["nested"][0]["api_token"], REDACTED);
+    }
+
+    #[test]
+    fn csv_fields_are_quoted_when_needed() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+}
@@ -4,9 +4,13 @@ use rux_auth::{AuthBackend as RuxAuthBackend, AuthError, AuthErrorCode, AuthUser
 use sea_orm::DatabaseConnection;
 use std::time::Instant;
 use tokio::task;
+use tower_sessions_redis_store::fred::prelude::Pool as RedisPool;
 use tracing::{error, info, instrument, warn};
 
-use crate::{db::sea_models::user, db::sea_models::user_ban, utils::telemetry};
+use crate::{
+    db::sea_models::user, db::sea_models::user_ban,
+    services::permission_service::PermissionService, utils::telemetry,
+};
 
 /// Re-export the AuthSession from rux-auth
 pub type AuthSession = rux_auth::AuthSession<AuthBackend>;
@@ -15,11 +19,21 @@ pub type AuthSession = rux_auth::AuthSession<AuthBackend>;
 #[derive(Clone)]
 pub struct AuthBackend {
     pub pool: DatabaseConnection,
+    /// Permission cache; permissions are read from the database without it
+    pub redis: Option<RedisPool>,
 }
 
 impl AuthBackend {
     pub fn new(pool: &DatabaseConnection) -> Self {
-        Self { pool: pool.clone() }
+        Self {
+            pool: pool.clone(),
+            redis: None,
+        }
+    }
+
+    pub fn with_redis(mut self, redis: &RedisPool) -> Self {
+        self.redis = Some(redis.clone());
+        self
     }
 
     /// Verify password against hash
@@ -79,19 +93,18 @@ impl AuthBackend {
 
         // Verify password in blocking task
         let verify_start = Instant::now();
-        let password_valid = match task::spawn_blocking(move || Self::check_password(password, &pwd_hash))
-            .await
-        {
-            Ok(result) => result?,
-            Err(join_err) => {
-                error!(error = %join_err, "Password verification task failed");
-                metrics
-                    .login_failure
-                    .add(1, &[opentelemetry::KeyValue::new("reason", "task_error")]);
-                return Err(AuthError::new(AuthErrorCode::InternalError)
-                    .with_message("Password verification failed"));
-            }
-        };
+        let password_valid =
+            match task::spawn_blocking(move || Self::check_password(password, &pwd_hash)).await {
+                Ok(result) => result?,
+                Err(join_err) => {
+                    error!(error = %join_err, "Password verification task failed");
+                    metrics
+                        .login_failure
+                        .add(1, &[opentelemetry::KeyValue::new("reason", "task_error")]);
+                    return Err(AuthError::new(AuthErrorCode::InternalError)
+                        .with_message("Password verification failed"));
+                }
+            };
 
         let verify_duration = verify_start.elapsed().as_millis() as f64;
         metrics
@@ -117,7 +130,10 @@ impl AuthBackend {
 
     /// Authenticate with OAuth (Google ID)
     #[instrument(skip(self), fields(result))]
-    pub async fn authenticate_oauth(&self, google_id: String) -> Result<Option<user::Model>, AuthError> {
+    pub async fn authenticate_oauth(
+        &self,
+        google_id: String,
+    ) -> Result<Option<user::Model>, AuthError> {
         let metrics = telemetry::auth_metrics();
         info!("OAuth authentication attempt");
 
@@ -140,7 +156,10 @@ impl AuthBackend {
                 warn!("OAuth user not found");
                 metrics.login_failure.add(
                     1,
-                    &[opentelemetry::KeyValue::new("reason", "oauth_user_not_found")],
+                    &[opentelemetry::KeyValue::new(
+                        "reason",
+                        "oauth_user_not_found",
+                    )],
                 );
                 Ok(None)
             }
@@ -196,8 +215,7 @@ impl RuxAuthBackend for AuthBackend {
             .await
             .map_err(|err| {
                 error!(error = ?err, "Error retrieving user");
-                AuthError::new(AuthErrorCode::BackendError)
-                    .with_message("Failed to retrieve user")
+                AuthError::new(AuthErrorCode::BackendError).with_message("Failed to retrieve user")
             })
     }
 
@@ -237,14 +255,23 @@ impl RuxAuthBackend for AuthBackend {
 
         let password = password.to_string();
         match task::spawn_blocking(move || Self::check_password(password, &pwd_hash)).await {
-            Ok(result) => result.map_err(|_| {
-                AuthError::new(AuthErrorCode::InvalidCredentials)
-            }),
+            Ok(result) => result.map_err(|_| AuthError::new(AuthErrorCode::InvalidCredentials)),
             Err(_) => Err(AuthError::new(AuthErrorCode::InternalError)
                 .with_message("Password verification task failed")),
         }
     }
 
+    #[instrument(skip(self, user), fields(user_id = user.id))]
+    async fn has_permission(&self, user: &Self::User, permission: &str) -> Result<bool, AuthError> {
+        PermissionService::has_permission(&self.pool, self.redis.as_ref(), user, permission)
+            .await
+            .map_err(|err| {
+                error!(error = ?err, permission, "Error checking permission");
+                AuthError::new(AuthErrorCode::BackendError)
+                    .with_message("Failed to check permissions")
+            })
+    }
+
     async fn on_login(&self, user: &Self::User) -> Result<(), AuthError> {
         info!(user_id = user.id, "User logged in via rux-auth");
         Ok(())
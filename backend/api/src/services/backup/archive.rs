@@ -0,0 +1,430 @@
+//! The backup archive format.
+//!
+//! A backup is a zip file with:
+//! - `manifest.json`: format name/version, creation time and row counts
+//! - `data/<entity>.json`: one JSON array per table
+//! - `media/<object_key>`: original and variant bytes (only when media is bundled)
+//!
+//! The archive may additionally be sealed with a passphrase (see `crypto`).
+
+use std::{
+    collections::BTreeMap,
+    io::{Cursor, Read, Write},
+};
+
+use bytes::Bytes;
+use sea_orm::prelude::DateTimeWithTimeZone;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use zip::{write::SimpleFileOptions, CompressionMethod, ZipArchive, ZipWriter};
+
+use crate::db::sea_models::{
+    category, comment_flag, media, media_variant,
+    newsletter_subscriber::{self, SubscriberStatus},
+    post, post_author, post_comment, post_revision, post_series, post_series_post, tag,
+    user::{self, UserRole},
+};
+use crate::error::{ErrorCode, ErrorResponse};
+
+pub const FORMAT: &str = "ruxlog-backup";
+pub const FORMAT_VERSION: i32 = 1;
+
+const MANIFEST_PATH: &str = "manifest.json";
+const MEDIA_PREFIX: &str = "media/";
+/// Upper bound on the uncompressed size of an archive being restored.
+pub const MAX_EXPANDED: u64 = 1024 * 1024 * 1024;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Manifest {
+    pub format: String,
+    pub format_version: i32,
+    pub created_at: DateTimeWithTimeZone,
+    pub app_version: String,
+    pub include_media: bool,
+    pub counts: BTreeMap<String, usize>,
+}
+
+/// A user without credentials: password hash, 2FA secret/backup codes and
+/// OAuth identifiers are never written to a backup.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupUser {
+    pub id: i32,
+    pub name: String,
+    pub email: String,
+    pub avatar_id: Option<i32>,
+    pub is_verified: bool,
+    pub role: UserRole,
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+impl From<user::Model> for BackupUser {
+    fn from(model: user::Model) -> Self {
+        Self {
+            id: model.id,
+            name: model.name,
+            email: model.email,
+            avatar_id: model.avatar_id,
+            is_verified: model.is_verified,
+            role: model.role,
+            created_at: model.created_at,
+            updated_at: model.updated_at,
+        }
+    }
+}
+
+/// A newsletter subscriber without its confirmation/unsubscribe token;
+/// restores issue fresh tokens.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupSubscriber {
+    pub id: i32,
+    pub email: String,
+    pub status: SubscriberStatus,
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+impl From<newsletter_subscriber::Model> for BackupSubscriber {
+    fn from(model: newsletter_subscriber::Model) -> Self {
+        Self {
+            id: model.id,
+            email: model.email,
+            status: model.status,
+            created_at: model.created_at,
+            updated_at: model.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct BackupData {
+    pub users: Vec<BackupUser>,
+    pub media: Vec<media::Model>,
+    pub media_variants: Vec<media_variant::Model>,
+    pub categories: Vec<category::Model>,
+    pub tags: Vec<tag::Model>,
+    pub posts: Vec<post::Model>,
+    pub post_authors: Vec<post_author::Model>,
+    pub post_revisions: Vec<post_revision::Model>,
+    pub post_series: Vec<post_series::Model>,
+    pub post_series_posts: Vec<post_series_post::Model>,
+    pub comments: Vec<post_comment::Model>,
+    pub comment_flags: Vec<comment_flag::Model>,
+    pub subscribers: Vec<BackupSubscriber>,
+}
+
+impl BackupData {
+    pub fn counts(&self) -> BTreeMap<String, usize> {
+        BTreeMap::from([
+            ("users".to_string(), self.users.len()),
+            ("media".to_string(), self.media.len()),
+            ("media_variants".to_string(), self.media_variants.len()),
+            ("categories".to_string(), self.categories.len()),
+            ("tags".to_string(), self.tags.len()),
+            ("posts".to_string(), self.posts.len()),
+            ("post_authors".to_string(), self.post_authors.len()),
+            ("post_revisions".to_string(), self.post_revisions.len()),
+            ("post_series".to_string(), self.post_series.len()),
+            (
+                "post_series_posts".to_string(),
+                self.post_series_posts.len(),
+            ),
+            ("comments".to_string(), self.comments.len()),
+            ("comment_flags".to_string(), self.comment_flags.len()),
+            ("subscribers".to_string(), self.subscribers.len()),
+        ])
+    }
+}
+
+/// A fully read archive: manifest, table data and bundled media bytes keyed
+/// by object key.
+#[derive(Debug, Clone)]
+pub struct Archive {
+    pub manifest: Manifest,
+    pub data: BackupData,
+    pub media_files: BTreeMap<String, Bytes>,
+}
+
+#[derive(Debug)]
+pub enum ArchiveError {
+    Invalid(String),
+    TooLarge,
+    Unsupported(String),
+    PassphraseRequired,
+    Encrypt(String),
+    Decrypt(String),
+}
+
+impl From<ArchiveError> for ErrorResponse {
+    fn from(err: ArchiveError) -> Self {
+        match err {
+            ArchiveError::Invalid(details) => ErrorResponse::new(ErrorCode::InvalidFormat)
+                .with_message("Could not read backup archive")
+                .with_details(details),
+            ArchiveError::TooLarge => ErrorResponse::new(ErrorCode::FileTooLarge).with_message(
+                format!("Backup expands beyond {}MiB", MAX_EXPANDED / 1024 / 1024),
+            ),
+            ArchiveError::Unsupported(details) => ErrorResponse::new(ErrorCode::InvalidValue)
+                .with_message("Unsupported backup archive")
+                .with_details(details),
+            ArchiveError::PassphraseRequired => ErrorResponse::new(ErrorCode::MissingRequiredField)
+                .with_message("This backup is encrypted; a passphrase is required"),
+            ArchiveError::Encrypt(details) => ErrorResponse::new(ErrorCode::InternalServerError)
+                .with_message("Failed to encrypt backup")
+                .with_details(details),
+            ArchiveError::Decrypt(details) => ErrorResponse::new(ErrorCode::InvalidCredentials)
+                .with_message("Could not decrypt backup")
+                .with_details(details),
+        }
+    }
+}
+
+pub fn write(archive: &Archive) -> Result<Vec<u8>, ArchiveError> {
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    let deflated = SimpleFileOptions::default()
+        .compression_method(CompressionMethod::Deflated)
+        .large_file(true);
+    // Media is usually already compressed; storing it keeps exports fast.
+    let stored = SimpleFileOptions::default()
+        .compression_method(CompressionMethod::Stored)
+        .large_file(true);
+
+    write_json(&mut zip, MANIFEST_PATH, &archive.manifest, deflated)?;
+
+    let data = &archive.data;
+    write_json(&mut zip, "data/users.json", &data.users, deflated)?;
+    write_json(&mut zip, "data/media.json", &data.media, deflated)?;
+    write_json(
+        &mut zip,
+        "data/media_variants.json",
+        &data.media_variants,
+        deflated,
+    )?;
+    write_json(&mut zip, "data/categories.json", &data.categories, deflated)?;
+    write_json(&mut zip, "data/tags.json", &data.tags, deflated)?;
+    write_json(&mut zip, "data/posts.json", &data.posts, deflated)?;
+    write_json(
+        &mut zip,
+        "data/post_authors.json",
+        &data.post_authors,
+        deflated,
+    )?;
+    write_json(
+        &mut zip,
+        "data/post_revisions.json",
+        &data.post_revisions,
+        deflated,
+    )?;
+    write_json(
+        &mut zip,
+        "data/post_series.json",
+        &data.post_series,
+        deflated,
+    )?;
+    write_json(
+        &mut zip,
+        "data/post_series_posts.json",
+        &data.post_series_posts,
+        deflated,
+    )?;
+    write_json(&mut zip, "data/comments.json", &data.comments, deflated)?;
+    write_json(
+        &mut zip,
+        "data/comment_flags.json",
+        &data.comment_flags,
+        deflated,
+    )?;
+    write_json(
+        &mut zip,
+        "data/subscribers.json",
+        &data.subscribers,
+        deflated,
+    )?;
+
+    for (key, bytes) in &archive.media_files {
+        zip.start_file(format!("{}{}", MEDIA_PREFIX, key), stored)
+            .map_err(|err| ArchiveError::Invalid(err.to_string()))?;
+        zip.write_all(bytes)
+            .map_err(|err| ArchiveError::Invalid(err.to_string()))?;
+    }
+
+    let cursor = zip
+        .finish()
+        .map_err(|err| ArchiveError::Invalid(err.to_string()))?;
+    Ok(cursor.into_inner())
+}
+
+pub fn read(bytes: &[u8]) -> Result<Archive, ArchiveError> {
+    let mut zip = ZipArchive::new(Cursor::new(bytes))
+        .map_err(|err| ArchiveError::Invalid(err.to_string()))?;
+
+    let mut expanded: u64 = 0;
+    let mut files: BTreeMap<String, Vec<u8>> = BTreeMap::new();
+    for index in 0..zip.len() {
+        let entry = zip
+            .by_index(index)
+            .map_err(|err| ArchiveError::Invalid(err.to_string()))?;
+        if entry.is_dir() {
+            continue;
+        }
+        let Some(path) = entry.enclosed_name() else {
+            continue;
+        };
+        let path = path.to_string_lossy().replace('\\', "/");
+
+        let remaining = MAX_EXPANDED - expanded;
+        let mut buf = Vec::new();
+        entry
+            .take(remaining + 1)
+            .read_to_end(&mut buf)
+            .map_err(|err| ArchiveError::Invalid(err.to_string()))?;
+        expanded += buf.len() as u64;
+        if expanded > MAX_EXPANDED {
+            return Err(ArchiveError::TooLarge);
+        }
+        files.insert(path, buf);
+    }
+
+    let manifest: Manifest = take_json(&mut files, MANIFEST_PATH)?
+        .ok_or_else(|| ArchiveError::Invalid("manifest.json is missing".to_string()))?;
+    if manifest.format != FORMAT {
+        return Err(ArchiveError::Unsupported(format!(
+            "Unknown archive format '{}'",
+            manifest.format
+        )));
+    }
+    if manifest.format_version > FORMAT_VERSION {
+        return Err(ArchiveError::Unsupported(format!(
+            "Archive format version {} is newer than supported version {}",
+            manifest.format_version, FORMAT_VERSION
+        )));
+    }
+
+    let data = BackupData {
+        users: take_json(&mut files, "data/users.json")?.unwrap_or_default(),
+        media: take_json(&mut files, "data/media.json")?.unwrap_or_default(),
+        media_variants: take_json(&mut files, "data/media_variants.json")?.unwrap_or_default(),
+        categories: take_json(&mut files, "data/categories.json")?.unwrap_or_default(),
+        tags: take_json(&mut files, "data/tags.json")?.unwrap_or_default(),
+        posts: take_json(&mut files, "data/posts.json")?.unwrap_or_default(),
+        post_authors: take_json(&mut files, "data/post_authors.json")?.unwrap_or_default(),
+        post_revisions: take_json(&mut files, "data/post_revisions.json")?.unwrap_or_default(),
+        post_series: take_json(&mut files, "data/post_series.json")?.unwrap_or_default(),
+        post_series_posts: take_json(&mut files, "data/post_series_posts.json")?
+            .unwrap_or_default(),
+        comments: take_json(&mut files, "data/comments.json")?.unwrap_or_default(),
+        comment_flags: take_json(&mut files, "data/comment_flags.json")?.unwrap_or_default(),
+        subscribers: take_json(&mut files, "data/subscribers.json")?.unwrap_or_default(),
+    };
+
+    let media_files = files
+        .into_iter()
+        .filter_map(|(path, bytes)| {
+            path.strip_prefix(MEDIA_PREFIX)
+                .map(|key| (key.to_string(), Bytes::from(bytes)))
+        })
+        .collect();
+
+    Ok(Archive {
+        manifest,
+        data,
+        media_files,
+    })
+}
+
+fn write_json<W, T>(
+    zip: &mut ZipWriter<W>,
+    path: &str,
+    value: &T,
+    options: SimpleFileOptions,
+) -> Result<(), ArchiveError>
+where
+    W: Write + std::io::Seek,
+    T: Serialize + ?Sized,
+{
+    zip.start_file(path, options)
+        .map_err(|err| ArchiveError::Invalid(err.to_string()))?;
+    serde_json::to_writer(zip, value).map_err(|err| ArchiveError::Invalid(err.to_string()))
+}
+
+fn take_json<T: DeserializeOwned>(
+    files: &mut BTreeMap<String, Vec<u8>>,
+    path: &str,
+) -> Result<Option<T>, ArchiveError> {
+    match files.remove(path) {
+        Some(bytes) => serde_json::from_slice(&bytes)
+            .map(Some)
+            .map_err(|err| ArchiveError::Invalid(format!("{}: {}", path, err))),
+        None => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn archive_round_trip() {
+        let now = chrono::Utc::now().fixed_offset();
+        let data = BackupData {
+            tags: vec![tag::Model {
+                id: 7,
+                name: "Rust".to_string(),
+                slug: "rust".to_string(),
+                description: None,
+                color: "#000000".to_string(),
+                text_color: "#ffffff".to_string(),
+                is_active: true,
+                locale: None,
+                created_at: now,
+                updated_at: now,
+            }],
+            ..Default::default()
+        };
+        let archive = Archive {
+            manifest: Manifest {
+                format: FORMAT.to_string(),
+                format_version: FORMAT_VERSION,
+                created_at: now,
+                app_version: "test".to_string(),
+                include_media: true,
+                counts: data.counts(),
+            },
+            data,
+            media_files: BTreeMap::from([(
+                "media/2025/01/a.png".to_string(),
+                Bytes::from_static(b"png"),
+            )]),
+        };
+
+        let bytes = write(&archive).unwrap();
+        let restored = read(&bytes).unwrap();
+
+        assert_eq!(restored.data.tags, archive.data.tags);
+        assert_eq!(restored.manifest.counts["tags"], 1);
+        assert_eq!(
+            restored
+                .media_files
+                .get("media/2025/01/a.png")
+                .map(|b| &b[..]),
+            Some(&b"png"[..])
+        );
+    }
+
+    #[test]
+    fn newer_format_is_rejected() {
+        let archive = Archive {
+            manifest: Manifest {
+                format: FORMAT.to_string(),
+                format_version: FORMAT_VERSION + 1,
+                created_at: chrono::Utc::now().fixed_offset(),
+                app_version: "test".to_string(),
+                include_media: false,
+                counts: BTreeMap::new(),
+            },
+            data: BackupData::default(),
+            media_files: BTreeMap::new(),
+        };
+        let bytes = write(&archive).unwrap();
+        assert!(matches!(read(&bytes), Err(ArchiveError::Unsupported(_))));
+    }
+}
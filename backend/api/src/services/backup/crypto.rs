@@ -0,0 +1,74 @@
+//! Passphrase encryption for backup archives.
+//!
+//! Layout: `RUXBAK01` magic, 16-byte salt, 12-byte nonce, then the
+//! AES-256-GCM ciphertext (tag included). The key is derived with Argon2id
+//! using the crate defaults.
+
+use aes_gcm::{
+    aead::{Aead, KeyInit},
+    Aes256Gcm, Nonce,
+};
+use argon2::Argon2;
+use rand::RngCore;
+
+const MAGIC: &[u8; 8] = b"RUXBAK01";
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+const HEADER_LEN: usize = MAGIC.len() + SALT_LEN + NONCE_LEN;
+
+pub fn is_encrypted(bytes: &[u8]) -> bool {
+    bytes.starts_with(MAGIC)
+}
+
+pub fn encrypt(passphrase: &str, plaintext: &[u8]) -> Result<Vec<u8>, String> {
+    let mut salt = [0u8; SALT_LEN];
+    let mut nonce = [0u8; NONCE_LEN];
+    rand::rng().fill_bytes(&mut salt);
+    rand::rng().fill_bytes(&mut nonce);
+
+    let cipher = cipher_for(passphrase, &salt)?;
+    let ciphertext = cipher
+        .encrypt(Nonce::from_slice(&nonce), plaintext)
+        .map_err(|_| "Encryption failed".to_string())?;
+
+    let mut out = Vec::with_capacity(HEADER_LEN + ciphertext.len());
+    out.extend_from_slice(MAGIC);
+    out.extend_from_slice(&salt);
+    out.extend_from_slice(&nonce);
+    out.extend_from_slice(&ciphertext);
+    Ok(out)
+}
+
+pub fn decrypt(passphrase: &str, bytes: &[u8]) -> Result<Vec<u8>, String> {
+    if !is_encrypted(bytes) || bytes.len() < HEADER_LEN {
+        return Err("Not an encrypted backup".to_string());
+    }
+    let salt = &bytes[MAGIC.len()..MAGIC.len() + SALT_LEN];
+    let nonce = &bytes[MAGIC.len() + SALT_LEN..HEADER_LEN];
+
+    let cipher = cipher_for(passphrase, salt)?;
+    cipher
+        .decrypt(Nonce::from_slice(nonce), &bytes[HEADER_LEN..])
+        .map_err(|_| "Wrong passphrase or corrupted archive".to_string())
+}
+
+fn cipher_for(passphrase: &str, salt: &[u8]) -> Result<Aes256Gcm, String> {
+    let mut key = [0u8; 32];
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|err| format!("Key derivation failed: {}", err))?;
+    Aes256Gcm::new_from_slice(&key).map_err(|err| err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip_and_wrong_passphrase() {
+        let sealed = encrypt("correct horse", b"archive bytes").unwrap();
+        assert!(is_encrypted(&sealed));
+        assert_eq!(decrypt("correct horse", &sealed).unwrap(), b"archive bytes");
+        assert!(decrypt("battery staple", &sealed).is_err());
+    }
+}
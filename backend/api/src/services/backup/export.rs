@@ -0,0 +1,233 @@
+//! Export: dump the site into an archive and store it in object storage.
+
+use std::collections::BTreeMap;
+
+use bytes::Bytes;
+use chrono::{Datelike, Utc};
+use sea_orm::{EntityTrait, QueryOrder};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use tracing::{info, warn};
+
+use crate::{
+    db::sea_models::{
+        backup_job::{self, CompletedExport},
+        category, comment_flag, media, media_variant, newsletter_subscriber, post, post_author,
+        post_comment, post_revision, post_series, post_series_post, tag, user,
+    },
+    error::{ErrorCode, ErrorResponse},
+    services::media_storage,
+    AppState,
+};
+
+use super::{
+    archive::{self, Archive, ArchiveError, BackupData, Manifest},
+    crypto,
+};
+
+/// Build, optionally encrypt, and upload an archive for `job`.
+pub async fn run(
+    state: &AppState,
+    job: &backup_job::Model,
+    passphrase: Option<String>,
+) -> Result<backup_job::Model, ErrorResponse> {
+    let data = collect(state).await?;
+
+    let mut warnings = Vec::new();
+    let media_files = if job.include_media {
+        collect_media_files(state, &data, &mut warnings).await
+    } else {
+        BTreeMap::new()
+    };
+    let media_file_count = media_files.len();
+
+    let counts = data.counts();
+    let archive = Archive {
+        manifest: Manifest {
+            format: archive::FORMAT.to_string(),
+            format_version: archive::FORMAT_VERSION,
+            created_at: Utc::now().fixed_offset(),
+            app_version: env!("CARGO_PKG_VERSION").to_string(),
+            include_media: job.include_media,
+            counts: counts.clone(),
+        },
+        data,
+        media_files,
+    };
+
+    let bytes = tokio::task::spawn_blocking(move || -> Result<Vec<u8>, ArchiveError> {
+        let zipped = archive::write(&archive)?;
+        match passphrase {
+            Some(passphrase) => {
+                crypto::encrypt(&passphrase, &zipped).map_err(ArchiveError::Encrypt)
+            }
+            None => Ok(zipped),
+        }
+    })
+    .await
+    .map_err(|err| {
+        ErrorResponse::new(ErrorCode::InternalServerError)
+            .with_message("Backup task panicked")
+            .with_details(err.to_string())
+    })??;
+
+    let checksum = format!("{:x}", Sha256::digest(&bytes));
+    let size_bytes = i64::try_from(bytes.len()).unwrap_or(i64::MAX);
+    let object_key = object_key(job);
+
+    let content_type = if job.encrypted {
+        "application/octet-stream"
+    } else {
+        "application/zip"
+    };
+    media_storage::put_object(state, &object_key, Bytes::from(bytes), content_type).await?;
+
+    info!(job_id = job.id, size_bytes, object_key = %object_key, "Backup archive stored");
+
+    let completed = backup_job::Entity::mark_exported(
+        &state.sea_db,
+        job.id,
+        CompletedExport {
+            object_key,
+            size_bytes,
+            checksum,
+            report: json!({
+                "counts": counts,
+                "media_files": media_file_count,
+                "warnings": warnings,
+            }),
+        },
+    )
+    .await?;
+
+    Ok(completed)
+}
+
+/// File name offered when downloading the archive of `job`.
+pub fn file_name(job: &backup_job::Model) -> String {
+    format!(
+        "ruxlog-backup-{}-{}.{}",
+        job.id,
+        job.created_at.format("%Y%m%d%H%M%S"),
+        extension(job)
+    )
+}
+
+fn extension(job: &backup_job::Model) -> &'static str {
+    if job.encrypted {
+        "ruxbak"
+    } else {
+        "zip"
+    }
+}
+
+fn object_key(job: &backup_job::Model) -> String {
+    let now = Utc::now();
+    format!(
+        "backups/{}/{:02}/backup-{}-{}.{}",
+        now.year(),
+        now.month(),
+        job.id,
+        now.format("%Y%m%d%H%M%S"),
+        extension(job)
+    )
+}
+
+async fn collect(state: &AppState) -> Result<BackupData, ErrorResponse> {
+    let db = &state.sea_db;
+
+    let users = user::Entity::find()
+        .order_by_asc(user::Column::Id)
+        .all(db)
+        .await?
+        .into_iter()
+        .map(Into::into)
+        .collect();
+    let subscribers = newsletter_subscriber::Entity::find()
+        .order_by_asc(newsletter_subscriber::Column::Id)
+        .all(db)
+        .await?
+        .into_iter()
+        .map(Into::into)
+        .collect();
+
+    Ok(BackupData {
+        users,
+        media: media::Entity::find()
+            .order_by_asc(media::Column::Id)
+            .all(db)
+            .await?,
+        media_variants: media_variant::Entity::find()
+            .order_by_asc(media_variant::Column::Id)
+            .all(db)
+            .await?,
+        categories: category::Entity::find()
+            .order_by_asc(category::Column::Id)
+            .all(db)
+            .await?,
+        tags: tag::Entity::find()
+            .order_by_asc(tag::Column::Id)
+            .all(db)
+            .await?,
+        posts: post::Entity::find()
+            .order_by_asc(post::Column::Id)
+            .all(db)
+            .await?,
+        post_authors: post_author::Entity::find()
+            .order_by_asc(post_author::Column::Id)
+            .all(db)
+            .await?,
+        post_revisions: post_revision::Entity::find()
+            .order_by_asc(post_revision::Column::Id)
+            .all(db)
+            .await?,
+        post_series: post_series::Entity::find()
+            .order_by_asc(post_series::Column::Id)
+            .all(db)
+            .await?,
+        post_series_posts: post_series_post::Entity::find()
+            .order_by_asc(post_series_post::Column::Id)
+            .all(db)
+            .await?,
+        comments: post_comment::Entity::find()
+            .order_by_asc(post_comment::Column::Id)
+            .all(db)
+            .await?,
+        comment_flags: comment_flag::Entity::find()
+            .order_by_asc(comment_flag::Column::Id)
+            .all(db)
+            .await?,
+        subscribers,
+    })
+}
+
+/// Fetch originals and variants from storage. Missing objects are reported
+/// as warnings rather than failing the whole export.
+async fn collect_media_files(
+    state: &AppState,
+    data: &BackupData,
+    warnings: &mut Vec<String>,
+) -> BTreeMap<String, Bytes> {
+    let keys = data
+        .media
+        .iter()
+        .map(|m| m.object_key.as_str())
+        .chain(data.media_variants.iter().map(|v| v.object_key.as_str()));
+
+    let mut files = BTreeMap::new();
+    for key in keys {
+        match media_storage::get_object(state, key).await {
+            Ok(bytes) => {
+                files.insert(key.to_string(), bytes);
+            }
+            Err(err) => {
+                warn!(object_key = %key, "Skipping media object in backup");
+                warnings.push(format!(
+                    "Media object '{}' not exported: {}",
+                    key, err.message
+                ));
+            }
+        }
+    }
+    files
+}
@@ -0,0 +1,448 @@
+//! Full-site backup and restore.
+//!
+//! Exports and restores are tracked in `backup_jobs` and run on the job
+//! queue as [`BackupExport`] and [`BackupRestore`], one at a time. Archives
+//! are written to object storage under `backups/`; when the bucket is
+//! publicly readable, encrypt them with a passphrase. Job payloads are
+//! visible to admins, so passphrases travel sealed with the master key.
+//! Uploaded archives are staged under `backups/uploads/` until their restore
+//! has run, whether it succeeded or not. Periodic exports are the
+//! `backup.schedule` job schedule ([`BackupSchedule`]), pruned to its
+//! retention count.
+
+pub mod archive;
+pub mod crypto;
+pub mod export;
+pub mod restore;
+
+use bytes::Bytes;
+use sea_orm::TransactionTrait;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tracing::{error, info, warn};
+
+use crate::{
+    db::sea_models::{
+        backup_job::{self, BackupJobKind, BackupJobStatus, NewBackupJob},
+        job::{self, NewJob},
+    },
+    error::{ErrorCode, ErrorResponse},
+    services::{
+        jobs::{self, queues, Job, JobError, JobResult},
+        media_storage,
+        settings::{self, crypto::MasterKey},
+    },
+    AppState,
+};
+
+/// Name of the periodic export in `job_schedules`.
+pub const SCHEDULE_NAME: &str = "backup.schedule";
+/// Held by the queued export or restore; only one runs at a time.
+const UNIQUE_KEY: &str = "backup";
+const UPLOAD_PREFIX: &str = "backups/uploads";
+
+#[derive(Debug, Clone, Default)]
+pub struct ExportOptions {
+    pub include_media: bool,
+    pub passphrase: Option<String>,
+    pub requested_by: Option<i32>,
+}
+
+/// Where a restore reads its archive from.
+#[derive(Debug, Clone)]
+pub enum RestoreSource {
+    Upload(Bytes),
+    Job(backup_job::Model),
+}
+
+/// Archive passphrase as it appears in a job payload.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Passphrase {
+    /// Sealed with `APP_CONSTANTS_MASTER_KEY`.
+    Sealed(String),
+    /// The `BACKUP_PASSPHRASE` setting, read when the job runs.
+    Setting,
+}
+
+impl Passphrase {
+    fn seal(master: &MasterKey, plaintext: &str) -> Result<Self, String> {
+        settings::crypto::seal(master, plaintext).map(Self::Sealed)
+    }
+
+    async fn reveal(&self, state: &AppState) -> Result<String, ErrorResponse> {
+        match self {
+            // The previous key still opens payloads queued before a rotation.
+            Self::Sealed(sealed) => [
+                settings::crypto::master_key(),
+                settings::crypto::previous_master_key(),
+            ]
+            .into_iter()
+            .flatten()
+            .find_map(|key| settings::crypto::open(&key, sealed).ok())
+            .ok_or_else(|| {
+                ErrorResponse::new(ErrorCode::ConfigurationError)
+                    .with_message("The master key can't open the backup passphrase")
+            }),
+            Self::Setting => settings::BACKUP_PASSPHRASE
+                .value(state)
+                .await
+                .filter(|value| !value.is_empty())
+                .ok_or_else(|| {
+                    ErrorResponse::new(ErrorCode::ConfigurationError).with_message(format!(
+                        "{} is not set for an encrypted schedule",
+                        settings::BACKUP_PASSPHRASE.key
+                    ))
+                }),
+        }
+    }
+}
+
+/// Record an export and queue it.
+pub async fn start_export(
+    state: &AppState,
+    options: ExportOptions,
+) -> Result<backup_job::Model, ErrorResponse> {
+    let passphrase = match options.passphrase.as_deref() {
+        Some(plaintext) => {
+            let master = settings::crypto::master_key().ok_or_else(missing_master_key)?;
+            Some(Passphrase::seal(&master, plaintext).map_err(seal_failed)?)
+        }
+        None => None,
+    };
+
+    let new_job = NewBackupJob {
+        kind: BackupJobKind::Export,
+        include_media: options.include_media,
+        encrypted: passphrase.is_some(),
+        scheduled: false,
+        format_version: archive::FORMAT_VERSION,
+        source_job_id: None,
+        requested_by: options.requested_by,
+    };
+    queue(state, new_job, |backup_job_id| {
+        jobs::job(&BackupExport {
+            backup_job_id,
+            passphrase,
+            retain: None,
+        })
+    })
+    .await
+}
+
+/// Record a restore and queue it. Uploads are staged in object storage
+/// for the job.
+pub async fn start_restore(
+    state: &AppState,
+    source: RestoreSource,
+    passphrase: Option<String>,
+    requested_by: Option<i32>,
+) -> Result<backup_job::Model, ErrorResponse> {
+    let passphrase = match passphrase.as_deref() {
+        Some(plaintext) => {
+            let master = settings::crypto::master_key().ok_or_else(missing_master_key)?;
+            Some(Passphrase::seal(&master, plaintext).map_err(seal_failed)?)
+        }
+        None => None,
+    };
+
+    let (source_job, upload_key) = match source {
+        RestoreSource::Job(job) => (Some(job), None),
+        RestoreSource::Upload(bytes) => {
+            let key = format!("{}/{}", UPLOAD_PREFIX, uuid::Uuid::new_v4().simple());
+            media_storage::put_object(state, &key, bytes, "application/octet-stream").await?;
+            (None, Some(key))
+        }
+    };
+
+    let new_job = NewBackupJob {
+        kind: BackupJobKind::Restore,
+        include_media: source_job.as_ref().is_some_and(|j| j.include_media),
+        encrypted: passphrase.is_some(),
+        scheduled: false,
+        format_version: archive::FORMAT_VERSION,
+        source_job_id: source_job.as_ref().map(|j| j.id),
+        requested_by,
+    };
+    let queued = queue(state, new_job, |backup_job_id| {
+        jobs::job(&BackupRestore {
+            backup_job_id,
+            upload_key: upload_key.clone(),
+            passphrase,
+        })
+    })
+    .await;
+
+    if queued.is_err() {
+        if let Some(key) = upload_key.as_deref() {
+            discard_upload(state, key).await;
+        }
+    }
+    queued
+}
+
+fn missing_master_key() -> ErrorResponse {
+    ErrorResponse::new(ErrorCode::ConfigurationError).with_message(format!(
+        "{} must be set to queue passphrase-protected backups",
+        settings::crypto::MASTER_KEY_ENV
+    ))
+}
+
+fn seal_failed(err: String) -> ErrorResponse {
+    ErrorResponse::new(ErrorCode::InternalServerError)
+        .with_message("Could not seal the backup passphrase")
+        .with_details(err)
+}
+
+/// Insert the `backup_jobs` row together with the job that runs it.
+async fn queue(
+    state: &AppState,
+    new_backup_job: NewBackupJob,
+    new_job: impl FnOnce(i32) -> NewJob,
+) -> Result<backup_job::Model, ErrorResponse> {
+    let requested_by = new_backup_job.requested_by;
+    let txn = state.sea_db.begin().await?;
+    let backup_job = backup_job::Entity::create(&txn, new_backup_job).await?;
+
+    let mut new_job = new_job(backup_job.id).unique(UNIQUE_KEY);
+    if let Some(user_id) = requested_by {
+        new_job = new_job.created_by(user_id);
+    }
+    let Some(queued) = job::Entity::enqueue(&txn, new_job).await? else {
+        txn.rollback().await?;
+        return Err(ErrorResponse::new(ErrorCode::ResourceConflict)
+            .with_message("Another backup job is already running"));
+    };
+    txn.commit().await?;
+    jobs::notify_enqueued(Some(&queued));
+    Ok(backup_job)
+}
+
+/// Build and store the archive of a `backup_jobs` export.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BackupExport {
+    pub backup_job_id: i32,
+    pub passphrase: Option<Passphrase>,
+    /// Scheduled exports to keep; older ones are pruned once this one is
+    /// stored.
+    pub retain: Option<i32>,
+}
+
+#[async_trait::async_trait]
+impl Job for BackupExport {
+    const KIND: &'static str = "backup.export";
+    const QUEUE: &'static str = queues::MAINTENANCE;
+    const MAX_ATTEMPTS: i32 = 3;
+
+    async fn run(self, state: &AppState) -> JobResult {
+        let Some(job) = begin_attempt(state, self.backup_job_id).await? else {
+            return Ok(Some(json!({ "skipped": "backup job finished or deleted" })));
+        };
+
+        let exported = async {
+            let passphrase = match &self.passphrase {
+                Some(passphrase) => Some(passphrase.reveal(state).await?),
+                None => None,
+            };
+            export::run(state, &job, passphrase).await
+        }
+        .await;
+        let exported = settle(state, job.id, exported).await?;
+
+        if let Some(retain) = self.retain {
+            prune_scheduled_exports(state, retain).await;
+        }
+        Ok(Some(json!({
+            "backup_job_id": exported.id,
+            "object_key": exported.object_key,
+        })))
+    }
+}
+
+/// Restore a `backup_jobs` restore from a staged upload or its source
+/// export.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BackupRestore {
+    pub backup_job_id: i32,
+    pub upload_key: Option<String>,
+    pub passphrase: Option<Passphrase>,
+}
+
+#[async_trait::async_trait]
+impl Job for BackupRestore {
+    const KIND: &'static str = "backup.restore";
+    const QUEUE: &'static str = queues::MAINTENANCE;
+    // Rewrites the site; rerun it deliberately from the jobs admin.
+    const MAX_ATTEMPTS: i32 = 1;
+
+    async fn run(self, state: &AppState) -> JobResult {
+        let outcome = self.restore(state).await;
+        // Single attempt, so no later run reads the upload again, whatever
+        // the outcome. A run stopped at shutdown never gets here and keeps
+        // it for its requeued attempt.
+        if let Some(key) = self.upload_key.as_deref() {
+            discard_upload(state, key).await;
+        }
+        outcome
+    }
+}
+
+impl BackupRestore {
+    async fn restore(&self, state: &AppState) -> JobResult {
+        let Some(job) = begin_attempt(state, self.backup_job_id).await? else {
+            return Ok(Some(json!({ "skipped": "backup job finished or deleted" })));
+        };
+
+        let restored = run_restore(state, &job, self).await;
+        settle(state, job.id, restored).await?;
+        Ok(Some(json!({ "backup_job_id": job.id })))
+    }
+}
+
+/// Periodic export; the payload of the `backup.schedule` job schedule.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BackupSchedule {
+    pub include_media: bool,
+    /// Encrypt with the `BACKUP_PASSPHRASE` setting.
+    pub encrypt: bool,
+    pub retain: i32,
+}
+
+#[async_trait::async_trait]
+impl Job for BackupSchedule {
+    const KIND: &'static str = "backup.schedule";
+    const QUEUE: &'static str = queues::MAINTENANCE;
+    const MAX_ATTEMPTS: i32 = 3;
+
+    async fn run(self, state: &AppState) -> JobResult {
+        if self.encrypt {
+            Passphrase::Setting
+                .reveal(state)
+                .await
+                .map_err(|err| JobError::permanent(err.message))?;
+        }
+
+        let new_job = NewBackupJob {
+            kind: BackupJobKind::Export,
+            include_media: self.include_media,
+            encrypted: self.encrypt,
+            scheduled: true,
+            format_version: archive::FORMAT_VERSION,
+            source_job_id: None,
+            requested_by: None,
+        };
+        let queued = queue(state, new_job, |backup_job_id| {
+            jobs::job(&BackupExport {
+                backup_job_id,
+                passphrase: self.encrypt.then_some(Passphrase::Setting),
+                retain: Some(self.retain.max(1)),
+            })
+        })
+        .await;
+        match queued {
+            Ok(job) => Ok(Some(json!({ "backup_job_id": job.id }))),
+            Err(err) if err.code == ErrorCode::ResourceConflict => {
+                info!("Scheduled backup skipped: another backup job is running");
+                Ok(Some(json!({ "skipped": "another backup job is running" })))
+            }
+            Err(err) => Err(err.into()),
+        }
+    }
+}
+
+/// Mark the job running unless it already completed or was deleted.
+async fn begin_attempt(
+    state: &AppState,
+    job_id: i32,
+) -> Result<Option<backup_job::Model>, JobError> {
+    let job = backup_job::Entity::find_by_id(&state.sea_db, job_id)
+        .await?
+        .filter(|job| job.status != BackupJobStatus::Completed);
+    if job.is_some() {
+        backup_job::Entity::mark_running(&state.sea_db, job_id).await?;
+    }
+    Ok(job)
+}
+
+/// Record a failed attempt on the backup job before handing it back to
+/// the runner.
+async fn settle<T>(
+    state: &AppState,
+    job_id: i32,
+    result: Result<T, ErrorResponse>,
+) -> Result<T, JobError> {
+    let err = match result {
+        Ok(value) => return Ok(value),
+        Err(err) => err,
+    };
+    let message = err.message.clone();
+    error!(job_id, error = %message, "Backup job failed");
+    if let Err(db_err) = backup_job::Entity::mark_failed(&state.sea_db, job_id, message).await {
+        error!(job_id, error = %db_err, "Failed to record backup job failure");
+    }
+    Err(err.into())
+}
+
+async fn run_restore(
+    state: &AppState,
+    job: &backup_job::Model,
+    payload: &BackupRestore,
+) -> Result<(), ErrorResponse> {
+    let key = match (&payload.upload_key, job.source_job_id) {
+        (Some(key), _) => key.clone(),
+        (None, Some(source_job_id)) => backup_job::Entity::find_by_id(&state.sea_db, source_job_id)
+            .await?
+            .and_then(|source| source.object_key)
+            .ok_or_else(|| {
+                ErrorResponse::new(ErrorCode::FileNotFound)
+                    .with_message("Backup job has no stored archive")
+            })?,
+        (None, None) => {
+            return Err(ErrorResponse::new(ErrorCode::FileNotFound)
+                .with_message("Restore has no archive to read"))
+        }
+    };
+    let bytes = media_storage::get_object(state, &key).await?;
+
+    let passphrase = match &payload.passphrase {
+        Some(passphrase) => Some(passphrase.reveal(state).await?),
+        None => None,
+    };
+    let archive = restore::open(bytes, passphrase).await?;
+    let report = restore::run(state, archive).await?;
+    let report = serde_json::to_value(report).unwrap_or_else(|_| json!({}));
+    backup_job::Entity::mark_restored(&state.sea_db, job.id, report).await?;
+    Ok(())
+}
+
+async fn discard_upload(state: &AppState, key: &str) {
+    if let Err(err) = media_storage::delete_object(state, key).await {
+        warn!(object_key = %key, error = %err.message, "Failed to delete staged backup upload");
+    }
+}
+
+/// Delete completed scheduled exports beyond the `retain` newest.
+async fn prune_scheduled_exports(state: &AppState, retain: i32) {
+    let retain = retain.max(1) as u64;
+    let expired = match backup_job::Entity::expired_scheduled_exports(&state.sea_db, retain).await {
+        Ok(expired) => expired,
+        Err(err) => {
+            warn!(error = %err, "Could not list expired backups");
+            return;
+        }
+    };
+
+    for job in expired {
+        if let Some(key) = job.object_key.as_deref() {
+            if let Err(err) = media_storage::delete_object(state, key).await {
+                warn!(job_id = job.id, error = %err.message, "Failed to delete expired backup");
+                continue;
+            }
+        }
+        if let Err(err) = backup_job::Entity::delete_by_id(&state.sea_db, job.id).await {
+            warn!(job_id = job.id, error = %err, "Failed to delete expired backup job");
+        } else {
+            info!(job_id = job.id, "Expired scheduled backup removed");
+        }
+    }
+}
@@ -0,0 +1,653 @@
+//! Restore: load an archive into the current database.
+//!
+//! Rows are inserted with fresh IDs inside a single transaction, in
+//! dependency order, and every foreign key is rewritten through the ID maps
+//! built along the way. Existing rows are matched by natural key (email,
+//! slug, content hash) and reused instead of duplicated; each match is
+//! reported as a conflict with its resolution.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use bytes::Bytes;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::NotSet, ColumnTrait, DatabaseTransaction, EntityTrait,
+    IntoActiveModel, PaginatorTrait, QueryFilter, Set, TransactionTrait,
+};
+use serde::Serialize;
+use serde_json::Value;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::{
+    db::sea_models::{
+        category, media, media_variant, newsletter_subscriber, post, post_author, post_series,
+        post_series_post, post_translation_group, tag, user,
+    },
+    error::{ErrorCode, ErrorResponse},
+    services::media_storage,
+    AppState,
+};
+
+use super::{
+    archive::{self, Archive, ArchiveError},
+    crypto,
+};
+
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct EntityCounts {
+    pub created: usize,
+    pub mapped: usize,
+    pub skipped: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Resolution {
+    /// The archived row was linked to an existing row.
+    Mapped,
+    /// The archived row was not restored.
+    Skipped,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Conflict {
+    pub entity: &'static str,
+    pub source_id: i32,
+    pub resolution: Resolution,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RestoreReport {
+    pub format_version: i32,
+    pub entities: BTreeMap<&'static str, EntityCounts>,
+    pub conflicts: Vec<Conflict>,
+    pub warnings: Vec<String>,
+}
+
+impl RestoreReport {
+    fn created(&mut self, entity: &'static str) {
+        self.entities.entry(entity).or_default().created += 1;
+    }
+
+    fn mapped(&mut self, entity: &'static str, source_id: i32, reason: impl Into<String>) {
+        self.entities.entry(entity).or_default().mapped += 1;
+        self.conflicts.push(Conflict {
+            entity,
+            source_id,
+            resolution: Resolution::Mapped,
+            reason: reason.into(),
+        });
+    }
+
+    fn skipped(&mut self, entity: &'static str, source_id: i32, reason: impl Into<String>) {
+        self.entities.entry(entity).or_default().skipped += 1;
+        self.conflicts.push(Conflict {
+            entity,
+            source_id,
+            resolution: Resolution::Skipped,
+            reason: reason.into(),
+        });
+    }
+}
+
+/// Decrypt (when needed) and parse an archive off the async runtime.
+pub async fn open(bytes: Bytes, passphrase: Option<String>) -> Result<Archive, ErrorResponse> {
+    tokio::task::spawn_blocking(move || -> Result<Archive, ArchiveError> {
+        if !crypto::is_encrypted(&bytes) {
+            return archive::read(&bytes);
+        }
+        let passphrase = passphrase.ok_or(ArchiveError::PassphraseRequired)?;
+        let plain = crypto::decrypt(&passphrase, &bytes).map_err(ArchiveError::Decrypt)?;
+        archive::read(&plain)
+    })
+    .await
+    .map_err(|err| {
+        ErrorResponse::new(ErrorCode::InternalServerError)
+            .with_message("Restore task panicked")
+            .with_details(err.to_string())
+    })?
+    .map_err(ErrorResponse::from)
+}
+
+/// ID maps from archive IDs to IDs in this database.
+#[derive(Default)]
+struct IdMaps {
+    users: HashMap<i32, i32>,
+    media: HashMap<i32, i32>,
+    /// Archived `file_url` -> current `file_url`, for content references.
+    media_urls: HashMap<String, String>,
+    /// New media ID -> current `file_url`.
+    media_url_by_id: HashMap<i32, String>,
+    categories: HashMap<i32, i32>,
+    tags: HashMap<i32, i32>,
+    posts: HashMap<i32, i32>,
+    series: HashMap<i32, i32>,
+    comments: HashMap<i32, i32>,
+    translation_groups: HashMap<i32, i32>,
+    created_posts: HashSet<i32>,
+    created_comments: HashSet<i32>,
+}
+
+pub async fn run(state: &AppState, archive: Archive) -> Result<RestoreReport, ErrorResponse> {
+    let Archive {
+        manifest,
+        data,
+        mut media_files,
+    } = archive;
+
+    let mut report = RestoreReport {
+        format_version: manifest.format_version,
+        ..Default::default()
+    };
+    let mut ids = IdMaps::default();
+    let mut uploads: Vec<(String, Bytes, String)> = Vec::new();
+    let public_url = state
+        .object_storage
+        .public_url
+        .trim_end_matches('/')
+        .to_string();
+
+    let txn = state.sea_db.begin().await?;
+
+    // Users: matched by email. Restored users have no password and must use
+    // the password reset flow; 2FA has to be set up again.
+    let mut created_users = Vec::new();
+    for u in &data.users {
+        if let Some(existing) = user::Entity::find()
+            .filter(user::Column::Email.eq(u.email.clone()))
+            .one(&txn)
+            .await?
+        {
+            ids.users.insert(u.id, existing.id);
+            report.mapped("users", u.id, format!("Email '{}' already exists", u.email));
+            continue;
+        }
+
+        let created = user::ActiveModel {
+            name: Set(u.name.clone()),
+            email: Set(u.email.clone()),
+            password: Set(None),
+            avatar_id: Set(None),
+            is_verified: Set(u.is_verified),
+            role: Set(u.role),
+            two_fa_enabled: Set(false),
+            two_fa_secret: Set(None),
+            two_fa_backup_codes: Set(None),
+            google_id: Set(None),
+            oauth_provider: Set(None),
+            created_at: Set(u.created_at),
+            updated_at: Set(u.updated_at),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await?;
+        ids.users.insert(u.id, created.id);
+        created_users.push((created, u.avatar_id));
+        report.created("users");
+    }
+
+    // Media: matched by content hash, then object key.
+    let mut created_media = HashSet::new();
+    let mut missing_bytes = 0usize;
+    for m in &data.media {
+        let mut existing = None;
+        if let Some(hash) = &m.content_hash {
+            existing = media::Entity::find()
+                .filter(media::Column::ContentHash.eq(hash.clone()))
+                .one(&txn)
+                .await?;
+        }
+        if existing.is_none() {
+            existing = media::Entity::find()
+                .filter(media::Column::ObjectKey.eq(m.object_key.clone()))
+                .one(&txn)
+                .await?;
+        }
+        if let Some(existing) = existing {
+            ids.media.insert(m.id, existing.id);
+            ids.media_urls
+                .insert(m.file_url.clone(), existing.file_url.clone());
+            ids.media_url_by_id
+                .insert(existing.id, existing.file_url.clone());
+            report.mapped("media", m.id, "Identical media already exists");
+            continue;
+        }
+
+        let file_url = format!("{}/{}", public_url, m.object_key);
+        let mut active = m.clone().into_active_model().reset_all();
+        active.id = NotSet;
+        active.file_url = Set(file_url.clone());
+        active.uploader_id = Set(m.uploader_id.and_then(|id| ids.users.get(&id).copied()));
+        let created = active.insert(&txn).await?;
+
+        match media_files.remove(&m.object_key) {
+            Some(bytes) => uploads.push((m.object_key.clone(), bytes, m.mime_type.clone())),
+            None => missing_bytes += 1,
+        }
+
+        ids.media.insert(m.id, created.id);
+        ids.media_urls.insert(m.file_url.clone(), file_url.clone());
+        ids.media_url_by_id.insert(created.id, file_url);
+        created_media.insert(m.id);
+        report.created("media");
+    }
+    if missing_bytes > 0 {
+        report.warnings.push(format!(
+            "{} media record(s) restored without file contents; they rely on the objects still being present in storage",
+            missing_bytes
+        ));
+    }
+
+    for v in &data.media_variants {
+        if !created_media.contains(&v.media_id) {
+            continue;
+        }
+        let Some(&media_id) = ids.media.get(&v.media_id) else {
+            continue;
+        };
+        let key_taken = media_variant::Entity::find()
+            .filter(media_variant::Column::ObjectKey.eq(v.object_key.clone()))
+            .count(&txn)
+            .await?
+            > 0;
+        if key_taken {
+            report.skipped("media_variants", v.id, "Object key already in use");
+            continue;
+        }
+
+        let mut active = v.clone().into_active_model().reset_all();
+        active.id = NotSet;
+        active.media_id = Set(media_id);
+        active.insert(&txn).await?;
+        if let Some(bytes) = media_files.remove(&v.object_key) {
+            uploads.push((v.object_key.clone(), bytes, v.mime_type.clone()));
+        }
+        report.created("media_variants");
+    }
+
+    for (created, avatar_id) in created_users {
+        if let Some(&avatar_id) = avatar_id.as_ref().and_then(|id| ids.media.get(id)) {
+            let mut active: user::ActiveModel = created.into();
+            active.avatar_id = Set(Some(avatar_id));
+            active.update(&txn).await?;
+        }
+    }
+
+    // Categories: matched by slug; parents are linked in a second pass.
+    let mut created_categories = Vec::new();
+    for c in &data.categories {
+        if let Some(existing) = category::Entity::find()
+            .filter(category::Column::Slug.eq(c.slug.clone()))
+            .one(&txn)
+            .await?
+        {
+            ids.categories.insert(c.id, existing.id);
+            report.mapped(
+                "categories",
+                c.id,
+                format!("Slug '{}' already exists", c.slug),
+            );
+            continue;
+        }
+
+        let mut active = c.clone().into_active_model().reset_all();
+        active.id = NotSet;
+        active.parent_id = Set(None);
+        active.cover_id = Set(c.cover_id.and_then(|id| ids.media.get(&id).copied()));
+        active.logo_id = Set(c.logo_id.and_then(|id| ids.media.get(&id).copied()));
+        let created = active.insert(&txn).await?;
+        ids.categories.insert(c.id, created.id);
+        created_categories.push((created, c.parent_id));
+        report.created("categories");
+    }
+    for (created, parent_id) in created_categories {
+        if let Some(&parent_id) = parent_id.as_ref().and_then(|id| ids.categories.get(id)) {
+            let mut active: category::ActiveModel = created.into();
+            active.parent_id = Set(Some(parent_id));
+            active.update(&txn).await?;
+        }
+    }
+
+    for t in &data.tags {
+        if let Some(existing) = tag::Entity::find()
+            .filter(tag::Column::Slug.eq(t.slug.clone()))
+            .one(&txn)
+            .await?
+        {
+            ids.tags.insert(t.id, existing.id);
+            report.mapped("tags", t.id, format!("Slug '{}' already exists", t.slug));
+            continue;
+        }
+
+        let mut active = t.clone().into_active_model().reset_all();
+        active.id = NotSet;
+        let created = active.insert(&txn).await?;
+        ids.tags.insert(t.id, created.id);
+        report.created("tags");
+    }
+
+    // Posts: matched by slug and locale. A matched post is left untouched, and
+    // its revisions and comments from the archive are not merged into it.
+    // Restored posts get fresh translation groups; matched posts are not
+    // linked into them.
+    let mut group_members: HashMap<i32, Vec<i32>> = HashMap::new();
+    for p in &data.posts {
+        if let Some(existing) = post::Entity::find()
+            .filter(post::Column::Slug.eq(p.slug.clone()))
+            .filter(post::Column::Locale.eq(p.locale.clone()))
+            .one(&txn)
+            .await?
+        {
+            ids.posts.insert(p.id, existing.id);
+            report.mapped("posts", p.id, format!("Slug '{}' already exists", p.slug));
+            continue;
+        }
+
+        let Some(&author_id) = ids.users.get(&p.author_id) else {
+            report.skipped("posts", p.id, "Author missing from archive");
+            continue;
+        };
+        let Some(&category_id) = ids.categories.get(&p.category_id) else {
+            report.skipped("posts", p.id, "Category missing from archive");
+            continue;
+        };
+
+        let mut content = p.content.clone();
+        remap_content(&mut content, &ids);
+
+        let mut active = p.clone().into_active_model().reset_all();
+        active.id = NotSet;
+        active.content = Set(content);
+        active.author_id = Set(author_id);
+        active.category_id = Set(category_id);
+        active.featured_image_id = Set(p
+            .featured_image_id
+            .and_then(|id| ids.media.get(&id).copied()));
+        active.og_image_id = Set(p.og_image_id.and_then(|id| ids.media.get(&id).copied()));
+        active.tag_ids = Set(p
+            .tag_ids
+            .iter()
+            .filter_map(|id| ids.tags.get(id).copied())
+            .collect());
+        let translation_group_id = match p.translation_group_id {
+            Some(source_id) => match ids.translation_groups.get(&source_id) {
+                Some(&group_id) => Some(group_id),
+                None => {
+                    let group = post_translation_group::Entity::create(&txn).await?;
+                    ids.translation_groups.insert(source_id, group.id);
+                    Some(group.id)
+                }
+            },
+            None => None,
+        };
+        active.translation_group_id = Set(translation_group_id);
+        // Likes are not part of the archive; keep the counter consistent.
+        active.likes_count = Set(0);
+        let created = active.insert(&txn).await?;
+        if let Some(group_id) = translation_group_id {
+            group_members.entry(group_id).or_default().push(created.id);
+        }
+        ids.posts.insert(p.id, created.id);
+        ids.created_posts.insert(p.id);
+        report.created("posts");
+    }
+
+    // A group with a single restored post links nothing.
+    for (group_id, members) in group_members {
+        if let [post_id] = members[..] {
+            post::Entity::update_many()
+                .col_expr(
+                    post::Column::TranslationGroupId,
+                    sea_orm::sea_query::Expr::value(Option::<i32>::None),
+                )
+                .filter(post::Column::Id.eq(post_id))
+                .exec(&txn)
+                .await?;
+            post_translation_group::Entity::delete_by_id(group_id)
+                .exec(&txn)
+                .await?;
+        }
+    }
+
+    // Contributors of restored posts. Archives from before co-authors carry
+    // only `author_id`, which is credited when no row covers it.
+    for link in &data.post_authors {
+        if !ids.created_posts.contains(&link.post_id) {
+            continue;
+        }
+        let Some(&user_id) = ids.users.get(&link.user_id) else {
+            report.skipped("post_authors", link.id, "Contributor missing from archive");
+            continue;
+        };
+        let mut active = link.clone().into_active_model().reset_all();
+        active.id = NotSet;
+        active.post_id = Set(ids.posts[&link.post_id]);
+        active.user_id = Set(user_id);
+        active.insert(&txn).await?;
+        report.created("post_authors");
+    }
+    for p in &data.posts {
+        if ids.created_posts.contains(&p.id) {
+            post_author::Entity::add_owner(&txn, ids.posts[&p.id], ids.users[&p.author_id]).await?;
+        }
+    }
+
+    for r in &data.post_revisions {
+        if !ids.created_posts.contains(&r.post_id) {
+            continue;
+        }
+        let mut active = r.clone().into_active_model().reset_all();
+        active.id = NotSet;
+        active.post_id = Set(ids.posts[&r.post_id]);
+        active.created_by = Set(r.created_by.and_then(|id| ids.users.get(&id).copied()));
+        // A snapshot whose category is not in the archive can't be restored
+        active.fields = Set(r.snapshot_fields().and_then(|mut fields| {
+            fields.category_id = *ids.categories.get(&fields.category_id)?;
+            fields.featured_image_id = fields
+                .featured_image_id
+                .and_then(|id| ids.media.get(&id).copied());
+            fields.tag_ids = fields
+                .tag_ids
+                .iter()
+                .filter_map(|id| ids.tags.get(id).copied())
+                .collect();
+            serde_json::to_value(fields).ok()
+        }));
+        active.insert(&txn).await?;
+        report.created("post_revisions");
+    }
+
+    for s in &data.post_series {
+        if let Some(existing) = post_series::Entity::find()
+            .filter(post_series::Column::Slug.eq(s.slug.clone()))
+            .one(&txn)
+            .await?
+        {
+            ids.series.insert(s.id, existing.id);
+            report.mapped(
+                "post_series",
+                s.id,
+                format!("Slug '{}' already exists", s.slug),
+            );
+            continue;
+        }
+
+        let mut active = s.clone().into_active_model().reset_all();
+        active.id = NotSet;
+        let created = active.insert(&txn).await?;
+        ids.series.insert(s.id, created.id);
+        report.created("post_series");
+    }
+
+    for link in &data.post_series_posts {
+        let (Some(&series_id), Some(&post_id)) = (
+            ids.series.get(&link.series_id),
+            ids.posts.get(&link.post_id),
+        ) else {
+            report.skipped(
+                "post_series_posts",
+                link.id,
+                "Series or post was not restored",
+            );
+            continue;
+        };
+        let exists = post_series_post::Entity::find()
+            .filter(post_series_post::Column::SeriesId.eq(series_id))
+            .filter(post_series_post::Column::PostId.eq(post_id))
+            .count(&txn)
+            .await?
+            > 0;
+        if exists {
+            continue;
+        }
+
+        let mut active = link.clone().into_active_model().reset_all();
+        active.id = NotSet;
+        active.series_id = Set(series_id);
+        active.post_id = Set(post_id);
+        active.insert(&txn).await?;
+        report.created("post_series_posts");
+    }
+
+    for c in &data.comments {
+        if !ids.created_posts.contains(&c.post_id) {
+            continue;
+        }
+        let Some(&user_id) = ids.users.get(&c.user_id) else {
+            report.skipped("comments", c.id, "Comment author missing from archive");
+            continue;
+        };
+        let mut active = c.clone().into_active_model().reset_all();
+        active.id = NotSet;
+        active.post_id = Set(ids.posts[&c.post_id]);
+        active.user_id = Set(user_id);
+        // Archives list comments oldest first, so parents are already in.
+        active.parent_id = Set(c
+            .parent_id
+            .and_then(|parent| ids.comments.get(&parent).copied()));
+        active.likes_count = Set(0);
+        let created = active.insert(&txn).await?;
+        ids.comments.insert(c.id, created.id);
+        ids.created_comments.insert(c.id);
+        report.created("comments");
+    }
+
+    for f in &data.comment_flags {
+        if !ids.created_comments.contains(&f.comment_id) {
+            continue;
+        }
+        let Some(&user_id) = ids.users.get(&f.user_id) else {
+            report.skipped("comment_flags", f.id, "Flagging user missing from archive");
+            continue;
+        };
+        let mut active = f.clone().into_active_model().reset_all();
+        active.id = NotSet;
+        active.comment_id = Set(ids.comments[&f.comment_id]);
+        active.user_id = Set(user_id);
+        active.insert(&txn).await?;
+        report.created("comment_flags");
+    }
+
+    for s in &data.subscribers {
+        let exists = newsletter_subscriber::Entity::find()
+            .filter(newsletter_subscriber::Column::Email.eq(s.email.clone()))
+            .count(&txn)
+            .await?
+            > 0;
+        if exists {
+            report.mapped(
+                "subscribers",
+                s.id,
+                format!("Email '{}' already subscribed", s.email),
+            );
+            continue;
+        }
+        newsletter_subscriber::ActiveModel {
+            email: Set(s.email.clone()),
+            status: Set(s.status),
+            token: Set(Uuid::new_v4().to_string()),
+            created_at: Set(s.created_at),
+            updated_at: Set(s.updated_at),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await?;
+        report.created("subscribers");
+    }
+
+    upload_media(state, uploads, &mut report).await;
+    commit(txn).await?;
+
+    info!(
+        conflicts = report.conflicts.len(),
+        warnings = report.warnings.len(),
+        "Backup restore committed"
+    );
+    Ok(report)
+}
+
+/// Objects are written before the transaction commits so that a failed
+/// upload is reported alongside the rows it belongs to. If the commit fails
+/// afterwards the objects are orphaned but harmless.
+async fn upload_media(
+    state: &AppState,
+    uploads: Vec<(String, Bytes, String)>,
+    report: &mut RestoreReport,
+) {
+    for (key, bytes, mime_type) in uploads {
+        if let Err(err) = media_storage::put_object(state, &key, bytes, &mime_type).await {
+            warn!(object_key = %key, "Failed to upload restored media object");
+            report.warnings.push(format!(
+                "Media object '{}' not uploaded: {}",
+                key, err.message
+            ));
+        }
+    }
+}
+
+async fn commit(txn: DatabaseTransaction) -> Result<(), ErrorResponse> {
+    txn.commit().await.map_err(|err| {
+        ErrorResponse::new(ErrorCode::TransactionError)
+            .with_message("Failed to commit restored data")
+            .with_details(err.to_string())
+    })
+}
+
+/// Point image blocks at the restored media rows.
+fn remap_content(content: &mut Value, ids: &IdMaps) {
+    let Some(blocks) = content.get_mut("blocks").and_then(Value::as_array_mut) else {
+        return;
+    };
+    for block in blocks {
+        if block["type"] != "image" {
+            continue;
+        }
+        let Some(file) = block
+            .get_mut("data")
+            .and_then(|d| d.get_mut("file"))
+            .and_then(Value::as_object_mut)
+        else {
+            continue;
+        };
+
+        let new_id = file
+            .get("media_id")
+            .and_then(Value::as_i64)
+            .and_then(|id| i32::try_from(id).ok())
+            .and_then(|id| ids.media.get(&id).copied());
+        if let Some(new_id) = new_id {
+            file.insert("media_id".to_string(), Value::from(new_id));
+            if let Some(url) = ids.media_url_by_id.get(&new_id) {
+                file.insert("url".to_string(), Value::from(url.clone()));
+            }
+        } else if let Some(url) = file
+            .get("url")
+            .and_then(Value::as_str)
+            .and_then(|url| ids.media_urls.get(url))
+        {
+            let url = url.clone();
+            file.insert("url".to_string(), Value::from(url));
+        }
+    }
+}
@@ -0,0 +1,841 @@
+//! Plan and apply a parsed blog against the database.
+//!
+//! The same code path runs for dry runs and real imports; a dry run only
+//! performs lookups, so the report shows what would be created, what matches
+//! existing rows and what was already imported by an earlier run.
+
+use std::collections::{BTreeMap, HashMap};
+
+use sea_orm::{DatabaseConnection, EntityTrait};
+use serde::Serialize;
+use serde_json::{json, Value};
+use tracing::{info, warn};
+use validator::Validate;
+
+use super::{
+    html,
+    source::{CommentState, SourceBlog, SourceComment, SourcePost, SourceStatus, SourceTerm},
+};
+use crate::{
+    db::sea_models::{
+        category::{self, NewCategory},
+        import_job::ImportSource,
+        import_mapping::{self, SourceItem},
+        media, post,
+        post_comment::{self, ImportedComment},
+        tag::{self, NewTag},
+        user::{self, UserRole},
+    },
+    error::ErrorResponse,
+    modules::{
+        import_v1::validator::V1BlogImportOptions,
+        post_v1::validator::{EditorJsDocument, V1CreatePostPayload},
+    },
+    services::{
+        content_render::{inline_to_text, sanitize_inline},
+        media_storage,
+        post_markdown::EDITOR_JS_VERSION,
+    },
+    utils::slugify,
+    AppState,
+};
+
+const FALLBACK_CATEGORY: &str = "Uncategorized";
+const MAX_TITLE: usize = 255;
+const MAX_EXCERPT: usize = 500;
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct EntityCounts {
+    /// Created by this run (or would be, for a dry run).
+    pub created: usize,
+    /// Reused an existing row with the same slug or email.
+    pub matched: usize,
+    /// Brought in by an earlier run and left untouched.
+    pub existing: usize,
+    pub skipped: usize,
+    pub failed: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Conflict {
+    pub entity: &'static str,
+    pub source_id: String,
+    pub wanted_slug: String,
+    pub used_slug: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PostOutcome {
+    Created,
+    Existing,
+    Skipped,
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PostItem {
+    pub source_id: String,
+    pub title: String,
+    pub outcome: PostOutcome,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub post_id: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub slug: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ImportReport {
+    pub dry_run: bool,
+    pub authors: EntityCounts,
+    pub categories: EntityCounts,
+    pub tags: EntityCounts,
+    pub posts: EntityCounts,
+    pub comments: EntityCounts,
+    pub media: EntityCounts,
+    /// Source items that aren't posts (pages, menu items...), by kind.
+    pub ignored: BTreeMap<String, usize>,
+    pub conflicts: Vec<Conflict>,
+    pub warnings: Vec<String>,
+    pub items: Vec<PostItem>,
+}
+
+/// Local row for a source item. `None` inside a dry run means "would be
+/// created".
+type Target = Option<i32>;
+
+struct Importer<'a> {
+    state: &'a AppState,
+    source: ImportSource,
+    scope: String,
+    job_id: i32,
+    /// Owner of posts whose author can't be mapped, and uploader of media.
+    fallback_author: i32,
+    options: &'a V1BlogImportOptions,
+    report: ImportReport,
+    authors: HashMap<String, Target>,
+    categories: HashMap<String, Target>,
+    tags: HashMap<String, Target>,
+    commenters: HashMap<String, Target>,
+    media_cache: HashMap<String, Option<media::Model>>,
+    fallback_category: Option<Target>,
+}
+
+pub async fn run(
+    state: &AppState,
+    source: ImportSource,
+    blog: SourceBlog,
+    job_id: i32,
+    fallback_author: i32,
+    options: &V1BlogImportOptions,
+) -> Result<ImportReport, ErrorResponse> {
+    let mut importer = Importer {
+        state,
+        source,
+        scope: blog.scope.clone(),
+        job_id,
+        fallback_author,
+        options,
+        report: ImportReport {
+            dry_run: options.dry_run,
+            ..ImportReport::default()
+        },
+        authors: HashMap::new(),
+        categories: HashMap::new(),
+        tags: HashMap::new(),
+        commenters: HashMap::new(),
+        media_cache: HashMap::new(),
+        fallback_category: None,
+    };
+
+    for kind in &blog.ignored {
+        *importer.report.ignored.entry(kind.clone()).or_default() += 1;
+    }
+
+    for author in &blog.authors {
+        let target = importer
+            .author(&author.id, &author.name, author.email.as_deref())
+            .await?;
+        importer.authors.insert(author.id.clone(), target);
+    }
+    for term in &blog.categories {
+        let target = importer.term(TermKind::Category, term).await?;
+        importer.categories.insert(term.id.clone(), target);
+    }
+    for term in &blog.tags {
+        let target = importer.term(TermKind::Tag, term).await?;
+        importer.tags.insert(term.id.clone(), target);
+    }
+
+    for source_post in &blog.posts {
+        let mut item = PostItem {
+            source_id: source_post.id.clone(),
+            title: source_post.title.clone(),
+            outcome: PostOutcome::Failed,
+            post_id: None,
+            slug: None,
+            error: None,
+            warnings: Vec::new(),
+        };
+
+        let target = match importer.post(source_post, &mut item).await {
+            Ok(target) => target,
+            Err(message) => {
+                warn!(source_id = %source_post.id, error = %message, "Post import failed");
+                item.outcome = PostOutcome::Failed;
+                item.error = Some(message);
+                None
+            }
+        };
+
+        match item.outcome {
+            PostOutcome::Created => importer.report.posts.created += 1,
+            PostOutcome::Existing => importer.report.posts.existing += 1,
+            PostOutcome::Skipped => importer.report.posts.skipped += 1,
+            PostOutcome::Failed => importer.report.posts.failed += 1,
+        }
+
+        let has_post = matches!(item.outcome, PostOutcome::Created | PostOutcome::Existing);
+        importer.report.items.push(item);
+
+        if has_post && options.import_comments {
+            for comment in &source_post.comments {
+                if let Err(err) = importer.comment(target, comment).await {
+                    importer.report.comments.failed += 1;
+                    importer.report.warnings.push(format!(
+                        "Comment {} on post {}: {}",
+                        comment.id, source_post.id, err.message
+                    ));
+                }
+            }
+        }
+    }
+
+    let report = importer.report;
+    info!(
+        job_id,
+        dry_run = report.dry_run,
+        posts_created = report.posts.created,
+        posts_existing = report.posts.existing,
+        posts_failed = report.posts.failed,
+        "Blog import finished"
+    );
+    Ok(report)
+}
+
+#[derive(Clone, Copy)]
+enum TermKind {
+    Category,
+    Tag,
+}
+
+impl TermKind {
+    fn entity(self) -> &'static str {
+        match self {
+            Self::Category => "category",
+            Self::Tag => "tag",
+        }
+    }
+}
+
+impl Importer<'_> {
+    fn db(&self) -> &DatabaseConnection {
+        &self.state.sea_db
+    }
+
+    fn item(&self, entity: &str, source_id: &str) -> SourceItem {
+        SourceItem {
+            source: self.source,
+            scope: self.scope.clone(),
+            entity: entity.to_string(),
+            source_id: source_id.to_string(),
+        }
+    }
+
+    /// Target of an earlier import of this item, if it still exists.
+    async fn mapped(&self, item: &SourceItem) -> Result<Option<i32>, ErrorResponse> {
+        let Some(mapping) = import_mapping::Entity::find_target(self.db(), item).await? else {
+            return Ok(None);
+        };
+        let id = mapping.target_id;
+        let db = self.db();
+        let exists = match item.entity.as_str() {
+            "author" => <user::Entity as EntityTrait>::find_by_id(id)
+                .one(db)
+                .await?
+                .is_some(),
+            "category" => <category::Entity as EntityTrait>::find_by_id(id)
+                .one(db)
+                .await?
+                .is_some(),
+            "tag" => <tag::Entity as EntityTrait>::find_by_id(id)
+                .one(db)
+                .await?
+                .is_some(),
+            "post" => <post::Entity as EntityTrait>::find_by_id(id)
+                .one(db)
+                .await?
+                .is_some(),
+            "comment" => <post_comment::Entity as EntityTrait>::find_by_id(id)
+                .one(db)
+                .await?
+                .is_some(),
+            "media" => <media::Entity as EntityTrait>::find_by_id(id)
+                .one(db)
+                .await?
+                .is_some(),
+            _ => false,
+        };
+        // A stale mapping is overwritten when the item is imported again.
+        Ok(exists.then_some(id))
+    }
+
+    async fn record(&self, item: SourceItem, target_id: i32) -> Result<(), ErrorResponse> {
+        import_mapping::Entity::record(self.db(), item, target_id, Some(self.job_id)).await?;
+        Ok(())
+    }
+
+    async fn author(
+        &mut self,
+        source_id: &str,
+        name: &str,
+        email: Option<&str>,
+    ) -> Result<Target, ErrorResponse> {
+        let item = self.item("author", source_id);
+        if let Some(id) = self.mapped(&item).await? {
+            self.report.authors.existing += 1;
+            return Ok(Some(id));
+        }
+
+        let Some(email) = email
+            .map(|e| e.trim().to_lowercase())
+            .filter(|e| e.contains('@'))
+        else {
+            self.report.authors.skipped += 1;
+            self.report.warnings.push(format!(
+                "Author '{}' has no email; their posts are assigned to the importing user",
+                source_id
+            ));
+            return Ok(Some(self.fallback_author));
+        };
+
+        if let Some(existing) = user::Entity::find_by_email(self.db(), email.clone()).await? {
+            self.report.authors.matched += 1;
+            if !self.options.dry_run {
+                self.record(item, existing.id).await?;
+            }
+            return Ok(Some(existing.id));
+        }
+
+        self.report.authors.created += 1;
+        if self.options.dry_run {
+            return Ok(None);
+        }
+        let name = non_empty(name).unwrap_or(&email).to_string();
+        let created =
+            user::Entity::create_invited(self.db(), name, email, UserRole::Author).await?;
+        self.record(item, created.id).await?;
+        Ok(Some(created.id))
+    }
+
+    async fn term(&mut self, kind: TermKind, term: &SourceTerm) -> Result<Target, ErrorResponse> {
+        let item = self.item(kind.entity(), &term.id);
+        if let Some(id) = self.mapped(&item).await? {
+            self.term_counts(kind).existing += 1;
+            return Ok(Some(id));
+        }
+
+        let name = non_empty(&term.name)
+            .unwrap_or(&term.slug)
+            .trim()
+            .to_string();
+        let slug = slugify(non_empty(&term.slug).unwrap_or(&name));
+        if slug.is_empty() {
+            self.term_counts(kind).skipped += 1;
+            self.report.warnings.push(format!(
+                "{} '{}' has no usable slug",
+                kind.entity(),
+                term.id
+            ));
+            return Ok(None);
+        }
+
+        // Terms are shared across the site: reuse a same-slug row instead of
+        // creating a "-2" duplicate.
+        let existing = match kind {
+            TermKind::Category => category::Entity::find_by_slug(self.db(), slug.clone())
+                .await?
+                .map(|c| c.id),
+            TermKind::Tag => tag::Entity::find_by_slug(self.db(), slug.clone())
+                .await?
+                .map(|t| t.id),
+        };
+        if let Some(id) = existing {
+            self.term_counts(kind).matched += 1;
+            if !self.options.dry_run {
+                self.record(item, id).await?;
+            }
+            return Ok(Some(id));
+        }
+
+        self.term_counts(kind).created += 1;
+        if self.options.dry_run {
+            return Ok(None);
+        }
+        let id = self
+            .create_term(kind, name, slug, term.description.clone())
+            .await?;
+        self.record(item, id).await?;
+        Ok(Some(id))
+    }
+
+    fn term_counts(&mut self, kind: TermKind) -> &mut EntityCounts {
+        match kind {
+            TermKind::Category => &mut self.report.categories,
+            TermKind::Tag => &mut self.report.tags,
+        }
+    }
+
+    async fn create_term(
+        &self,
+        kind: TermKind,
+        name: String,
+        slug: String,
+        description: Option<String>,
+    ) -> Result<i32, ErrorResponse> {
+        let id = match kind {
+            TermKind::Category => {
+                category::Entity::create(
+                    self.db(),
+                    NewCategory {
+                        name,
+                        slug,
+                        parent_id: None,
+                        description,
+                        cover_id: None,
+                        logo_id: None,
+                        color: None,
+                        text_color: None,
+                        is_active: None,
+                        locale: None,
+                    },
+                )
+                .await?
+                .id
+            }
+            TermKind::Tag => {
+                tag::Entity::create(
+                    self.db(),
+                    NewTag {
+                        name,
+                        slug,
+                        description,
+                        color: None,
+                        text_color: None,
+                        is_active: None,
+                        locale: None,
+                    },
+                )
+                .await?
+                .id
+            }
+        };
+        Ok(id)
+    }
+
+    /// Category for posts that have none: the `category_id` option, else an
+    /// "Uncategorized" category (created on first use).
+    async fn fallback_category(&mut self) -> Result<Target, ErrorResponse> {
+        if let Some(target) = self.fallback_category {
+            return Ok(target);
+        }
+
+        let target = match self.options.category_id {
+            Some(id) => Some(id),
+            None => {
+                let slug = slugify(FALLBACK_CATEGORY);
+                match category::Entity::find_by_slug(self.db(), slug.clone()).await? {
+                    Some(found) => Some(found.id),
+                    None => {
+                        self.report.categories.created += 1;
+                        if self.options.dry_run {
+                            None
+                        } else {
+                            Some(
+                                self.create_term(
+                                    TermKind::Category,
+                                    FALLBACK_CATEGORY.to_string(),
+                                    slug,
+                                    None,
+                                )
+                                .await?,
+                            )
+                        }
+                    }
+                }
+            }
+        };
+        self.fallback_category = Some(target);
+        Ok(target)
+    }
+
+    async fn post(
+        &mut self,
+        source_post: &SourcePost,
+        item: &mut PostItem,
+    ) -> Result<Target, String> {
+        let mapping = self.item("post", &source_post.id);
+        if let Some(id) = self.mapped(&mapping).await.map_err(|err| err.message)? {
+            item.outcome = PostOutcome::Existing;
+            item.post_id = Some(id);
+            return Ok(Some(id));
+        }
+
+        let mut blocks = html::html_to_blocks(&source_post.html);
+        self.resolve_images(&mut blocks, &mut item.warnings).await;
+        if blocks.is_empty() {
+            item.outcome = PostOutcome::Skipped;
+            item.warnings.push("Post has no importable content".into());
+            return Ok(None);
+        }
+
+        let title = match non_empty(&source_post.title) {
+            Some(title) if title.chars().count() >= 3 => truncate(title, MAX_TITLE),
+            Some(title) => {
+                item.warnings
+                    .push(format!("Title '{}' is too short; padded", title));
+                format!("{} …", title)
+            }
+            None => {
+                item.warnings.push("Post has no title".into());
+                "Untitled".to_string()
+            }
+        };
+
+        let wanted = slugify(non_empty(&source_post.slug).unwrap_or(&title));
+        let wanted = if wanted.len() < 3 {
+            format!("post-{}", slugify(&source_post.id))
+        } else {
+            truncate(&wanted, MAX_TITLE)
+        };
+        let slug = self.free_slug(&wanted).await.map_err(|err| err.message)?;
+        if slug != wanted {
+            self.report.conflicts.push(Conflict {
+                entity: "post",
+                source_id: source_post.id.clone(),
+                wanted_slug: wanted,
+                used_slug: slug.clone(),
+            });
+        }
+        item.slug = Some(slug.clone());
+
+        let author_id = match source_post.author_id.as_ref() {
+            Some(id) => match self.authors.get(id) {
+                Some(target) => *target,
+                None => self.author(id, id, None).await.map_err(|err| err.message)?,
+            },
+            None => Some(self.fallback_author),
+        };
+
+        let mut category_id = None;
+        for id in &source_post.category_ids {
+            if let Some(target) = self.categories.get(id) {
+                category_id = Some(*target);
+                break;
+            }
+        }
+        let category_id = match category_id {
+            Some(target) => target,
+            None => self.fallback_category().await.map_err(|err| err.message)?,
+        };
+
+        let tag_ids: Vec<Target> = source_post
+            .tag_ids
+            .iter()
+            .filter_map(|id| self.tags.get(id).copied())
+            .collect();
+
+        let featured_image_id = match source_post.featured_image.as_deref() {
+            Some(url) => self.media(url, &mut item.warnings).await.map(|m| m.id),
+            None => None,
+        };
+
+        let is_published = match source_post.status {
+            SourceStatus::Published => true,
+            SourceStatus::Draft => false,
+            SourceStatus::Other => {
+                item.warnings.push(format!(
+                    "Status '{}' is not supported; imported as draft",
+                    source_post.raw_status
+                ));
+                false
+            }
+        };
+
+        let excerpt = source_post
+            .excerpt
+            .as_deref()
+            .map(|e| inline_to_text(&sanitize_inline(e)).trim().to_string())
+            .filter(|e| !e.is_empty())
+            .map(|e| truncate(&e, MAX_EXCERPT));
+
+        item.outcome = PostOutcome::Created;
+        if self.options.dry_run {
+            return Ok(None);
+        }
+
+        let (Some(author_id), Some(category_id)) = (author_id, category_id) else {
+            return Err("Author or category could not be resolved".into());
+        };
+
+        let document: EditorJsDocument = serde_json::from_value(json!({
+            "time": chrono::Utc::now().timestamp_millis(),
+            "blocks": blocks,
+            "version": EDITOR_JS_VERSION,
+        }))
+        .map_err(|err| format!("Could not build post content: {}", err))?;
+
+        let payload = V1CreatePostPayload {
+            title,
+            content: document,
+            published_at: if is_published {
+                source_post.published_at
+            } else {
+                None
+            },
+            is_published,
+            slug,
+            excerpt,
+            featured_image_id,
+            category_id,
+            tag_ids: tag_ids.into_iter().flatten().collect(),
+            visibility: post::PostVisibility::Public,
+            min_role: None,
+            password: None,
+            locale: None,
+            meta_title: None,
+            meta_description: None,
+            canonical_url: None,
+            noindex: false,
+            og_image_id: None,
+            og_image_variant: None,
+        };
+        payload.validate().map_err(|err| err.to_string())?;
+
+        let created = post::Entity::create(self.db(), payload.into_new_post(author_id))
+            .await
+            .map_err(|err| err.message)?;
+        self.record(mapping, created.id)
+            .await
+            .map_err(|err| err.message)?;
+
+        item.post_id = Some(created.id);
+        Ok(Some(created.id))
+    }
+
+    /// `wanted`, or `wanted-2`, `wanted-3`... if a post already uses it.
+    async fn free_slug(&self, wanted: &str) -> Result<String, ErrorResponse> {
+        let mut candidate = wanted.to_string();
+        let mut suffix = 2;
+        while post::Entity::find_by_id_or_slug(self.db(), None, Some(candidate.clone()))
+            .await?
+            .is_some()
+        {
+            candidate = format!("{}-{}", wanted, suffix);
+            suffix += 1;
+        }
+        Ok(candidate)
+    }
+
+    /// Point image blocks at stored media. Images that can't be stored (dry
+    /// run, `import_media` off, download failure) become a link paragraph.
+    async fn resolve_images(&mut self, blocks: &mut [Value], warnings: &mut Vec<String>) {
+        for block in blocks.iter_mut() {
+            if block["type"] != "image" {
+                continue;
+            }
+            let src = block["data"]["file"]["url"]
+                .as_str()
+                .unwrap_or_default()
+                .to_string();
+            match self.media(&src, warnings).await {
+                Some(media) => {
+                    block["data"]["file"] = json!({
+                        "url": media.file_url,
+                        "media_id": media.id,
+                        "width": media.width,
+                        "height": media.height,
+                    });
+                }
+                None => *block = html::link_paragraph(&src),
+            }
+        }
+    }
+
+    async fn media(&mut self, url: &str, warnings: &mut Vec<String>) -> Option<media::Model> {
+        let url = self.absolute_url(url);
+        if let Some(cached) = self.media_cache.get(&url) {
+            return cached.clone();
+        }
+
+        let resolved = self.fetch_media(&url, warnings).await;
+        self.media_cache.insert(url, resolved.clone());
+        resolved
+    }
+
+    async fn fetch_media(&mut self, url: &str, warnings: &mut Vec<String>) -> Option<media::Model> {
+        if !(url.starts_with("http://") || url.starts_with("https://")) {
+            warnings.push(format!("Image '{}' skipped: not an absolute URL", url));
+            self.report.media.skipped += 1;
+            return None;
+        }
+
+        let item = self.item("media", url);
+        match self.mapped(&item).await {
+            Ok(Some(id)) => {
+                if let Ok(Some(found)) = <media::Entity as EntityTrait>::find_by_id(id)
+                    .one(self.db())
+                    .await
+                {
+                    self.report.media.existing += 1;
+                    return Some(found);
+                }
+            }
+            Ok(None) => {}
+            Err(err) => warn!(error = %err.message, "Media mapping lookup failed"),
+        }
+
+        if self.options.dry_run || !self.options.import_media {
+            if self.options.dry_run && self.options.import_media {
+                self.report.media.created += 1;
+            } else {
+                self.report.media.skipped += 1;
+            }
+            return None;
+        }
+
+        match media_storage::store_remote(self.state, url, Some(self.fallback_author)).await {
+            Ok(stored) => {
+                self.report.media.created += 1;
+                if let Err(err) = self.record(item, stored.media.id).await {
+                    warn!(error = %err.message, "Failed to record media mapping");
+                }
+                Some(stored.media)
+            }
+            Err(err) => {
+                self.report.media.failed += 1;
+                warnings.push(format!("Image '{}' not imported: {}", url, err.message));
+                None
+            }
+        }
+    }
+
+    /// Resolve root-relative URLs (`/wp-content/...`) against the site URL.
+    fn absolute_url(&self, url: &str) -> String {
+        let url = url.trim();
+        if let Some(rest) = url.strip_prefix("//") {
+            return format!("https://{}", rest);
+        }
+        if url.starts_with('/') && !self.scope.is_empty() {
+            return format!("{}{}", self.scope, url);
+        }
+        url.to_string()
+    }
+
+    async fn comment(
+        &mut self,
+        post_id: Target,
+        comment: &SourceComment,
+    ) -> Result<(), ErrorResponse> {
+        if comment.state == CommentState::Rejected {
+            self.report.comments.skipped += 1;
+            return Ok(());
+        }
+
+        let item = self.item("comment", &comment.id);
+        if self.mapped(&item).await?.is_some() {
+            self.report.comments.existing += 1;
+            return Ok(());
+        }
+
+        let content = inline_to_text(&sanitize_inline(&comment.content))
+            .trim()
+            .to_string();
+        let Some(email) = comment
+            .author_email
+            .as_deref()
+            .map(|e| e.trim().to_lowercase())
+            .filter(|e| e.contains('@'))
+        else {
+            self.report.comments.skipped += 1;
+            return Ok(());
+        };
+        if content.is_empty() {
+            self.report.comments.skipped += 1;
+            return Ok(());
+        }
+
+        let user_id = match self.commenters.get(&email) {
+            Some(target) => *target,
+            None => {
+                let target = match user::Entity::find_by_email(self.db(), email.clone()).await? {
+                    Some(found) => Some(found.id),
+                    None if self.options.dry_run => None,
+                    None => {
+                        let name = non_empty(&comment.author_name)
+                            .unwrap_or(&email)
+                            .to_string();
+                        Some(
+                            user::Entity::create_invited(
+                                self.db(),
+                                name,
+                                email.clone(),
+                                UserRole::User,
+                            )
+                            .await?
+                            .id,
+                        )
+                    }
+                };
+                self.commenters.insert(email, target);
+                target
+            }
+        };
+
+        self.report.comments.created += 1;
+        if self.options.dry_run {
+            return Ok(());
+        }
+        let (Some(post_id), Some(user_id)) = (post_id, user_id) else {
+            return Ok(());
+        };
+
+        let created = post_comment::Entity::create_imported(
+            self.db(),
+            ImportedComment {
+                post_id,
+                user_id,
+                content,
+                hidden: comment.state == CommentState::Pending,
+                created_at: comment
+                    .created_at
+                    .unwrap_or_else(|| chrono::Utc::now().fixed_offset()),
+            },
+        )
+        .await?;
+        self.record(item, created.id).await
+    }
+}
+
+fn non_empty(value: &str) -> Option<&str> {
+    let value = value.trim();
+    (!value.is_empty()).then_some(value)
+}
+
+fn truncate(value: &str, max_chars: usize) -> String {
+    value.chars().take(max_chars).collect()
+}
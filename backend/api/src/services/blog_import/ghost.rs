@@ -0,0 +1,265 @@
+//! Ghost JSON export parser.
+//!
+//! Reads the file from `Settings → Labs → Export content`. Ghost has no
+//! categories and its JSON export carries no comments, so only authors, tags
+//! and posts are produced. Internal tags (`#name`) are dropped. Ghost writes
+//! its own URLs as `__GHOST_URL__/...`; pass the site URL to resolve them.
+
+use std::collections::HashMap;
+
+use chrono::DateTime;
+use serde::Deserialize;
+
+use super::source::{SourceAuthor, SourceBlog, SourcePost, SourceStatus, SourceTerm};
+use crate::services::content_render::escape_html;
+
+const GHOST_URL: &str = "__GHOST_URL__";
+
+#[derive(Deserialize)]
+struct Export {
+    #[serde(default)]
+    db: Vec<Database>,
+    data: Option<Data>,
+}
+
+#[derive(Deserialize)]
+struct Database {
+    data: Data,
+}
+
+#[derive(Deserialize, Default)]
+struct Data {
+    #[serde(default)]
+    posts: Vec<Post>,
+    #[serde(default)]
+    users: Vec<User>,
+    #[serde(default)]
+    tags: Vec<Tag>,
+    #[serde(default)]
+    posts_tags: Vec<PostRelation>,
+    #[serde(default)]
+    posts_authors: Vec<PostRelation>,
+}
+
+#[derive(Deserialize)]
+struct Post {
+    id: String,
+    #[serde(default)]
+    title: String,
+    #[serde(default)]
+    slug: String,
+    html: Option<String>,
+    plaintext: Option<String>,
+    feature_image: Option<String>,
+    custom_excerpt: Option<String>,
+    #[serde(default)]
+    status: String,
+    published_at: Option<String>,
+    #[serde(rename = "type", default)]
+    kind: Option<String>,
+    /// Single-author exports from Ghost 1.x.
+    author_id: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct User {
+    id: String,
+    #[serde(default)]
+    name: String,
+    email: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Tag {
+    id: String,
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    slug: String,
+    description: Option<String>,
+    visibility: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct PostRelation {
+    post_id: String,
+    /// `tag_id` in `posts_tags`, `author_id` in `posts_authors`.
+    #[serde(alias = "tag_id", alias = "author_id")]
+    related_id: String,
+    #[serde(default)]
+    sort_order: i64,
+}
+
+pub fn parse(json: &str, site_url: Option<&str>) -> Result<SourceBlog, String> {
+    let export: Export =
+        serde_json::from_str(json).map_err(|err| format!("Invalid Ghost export: {}", err))?;
+    let data = export
+        .db
+        .into_iter()
+        .next()
+        .map(|db| db.data)
+        .or(export.data)
+        .ok_or_else(|| "Not a Ghost export: missing db[0].data".to_string())?;
+
+    let site_url = site_url.map(|url| url.trim().trim_end_matches('/').to_string());
+    let resolve_url = |value: String| match &site_url {
+        Some(base) => value.replace(GHOST_URL, base),
+        None => value,
+    };
+
+    let public_tags: HashMap<&str, &Tag> = data
+        .tags
+        .iter()
+        .filter(|t| t.visibility.as_deref() != Some("internal") && !t.name.starts_with('#'))
+        .map(|t| (t.id.as_str(), t))
+        .collect();
+
+    let mut tags_by_post: HashMap<&str, Vec<&PostRelation>> = HashMap::new();
+    for relation in &data.posts_tags {
+        if public_tags.contains_key(relation.related_id.as_str()) {
+            tags_by_post
+                .entry(relation.post_id.as_str())
+                .or_default()
+                .push(relation);
+        }
+    }
+    let mut authors_by_post: HashMap<&str, Vec<&PostRelation>> = HashMap::new();
+    for relation in &data.posts_authors {
+        authors_by_post
+            .entry(relation.post_id.as_str())
+            .or_default()
+            .push(relation);
+    }
+
+    let mut blog = SourceBlog {
+        scope: site_url.clone().unwrap_or_default(),
+        ..SourceBlog::default()
+    };
+
+    blog.authors = data
+        .users
+        .iter()
+        .map(|u| SourceAuthor {
+            id: u.id.clone(),
+            name: u.name.clone(),
+            email: u.email.clone().filter(|e| !e.trim().is_empty()),
+        })
+        .collect();
+
+    blog.tags = data
+        .tags
+        .iter()
+        .filter(|t| public_tags.contains_key(t.id.as_str()))
+        .map(|t| SourceTerm {
+            id: t.id.clone(),
+            name: t.name.clone(),
+            slug: t.slug.clone(),
+            description: t.description.clone().filter(|d| !d.trim().is_empty()),
+        })
+        .collect();
+
+    for post in &data.posts {
+        let kind = post.kind.as_deref().unwrap_or("post");
+        if kind != "post" {
+            blog.ignored.push(kind.to_string());
+            continue;
+        }
+
+        let html = match (&post.html, &post.plaintext) {
+            (Some(html), _) if !html.trim().is_empty() => resolve_url(html.clone()),
+            (_, Some(text)) => text
+                .split("\n\n")
+                .filter(|p| !p.trim().is_empty())
+                .map(|p| format!("<p>{}</p>", escape_html(p)))
+                .collect(),
+            _ => String::new(),
+        };
+
+        let mut tag_relations = tags_by_post.remove(post.id.as_str()).unwrap_or_default();
+        tag_relations.sort_by_key(|r| r.sort_order);
+        let mut author_relations = authors_by_post.remove(post.id.as_str()).unwrap_or_default();
+        author_relations.sort_by_key(|r| r.sort_order);
+
+        blog.posts.push(SourcePost {
+            id: post.id.clone(),
+            title: post.title.clone(),
+            slug: post.slug.clone(),
+            html,
+            excerpt: post.custom_excerpt.clone().filter(|e| !e.trim().is_empty()),
+            status: match post.status.as_str() {
+                "published" => SourceStatus::Published,
+                "draft" => SourceStatus::Draft,
+                _ => SourceStatus::Other,
+            },
+            raw_status: post.status.clone(),
+            published_at: post
+                .published_at
+                .as_deref()
+                .and_then(|value| DateTime::parse_from_rfc3339(value).ok()),
+            author_id: author_relations
+                .first()
+                .map(|r| r.related_id.clone())
+                .or_else(|| post.author_id.clone()),
+            category_ids: Vec::new(),
+            tag_ids: tag_relations.iter().map(|r| r.related_id.clone()).collect(),
+            featured_image: post
+                .feature_image
+                .clone()
+                .filter(|url| !url.trim().is_empty())
+                .map(resolve_url),
+            comments: Vec::new(),
+        });
+    }
+
+    Ok(blog)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_ghost_export() {
+        let json = r##"{
+            "db": [{
+                "meta": { "version": "5.0.0" },
+                "data": {
+                    "posts": [
+                        { "id": "p1", "title": "Hello", "slug": "hello", "type": "post",
+                          "html": "<p>Hi <img src=\"__GHOST_URL__/content/images/a.png\"></p>",
+                          "feature_image": "__GHOST_URL__/content/images/cover.png",
+                          "status": "published", "published_at": "2021-03-04T05:06:07.000Z" },
+                        { "id": "p2", "title": "About", "slug": "about", "type": "page", "status": "published" }
+                    ],
+                    "users": [{ "id": "u1", "name": "Ann", "email": "ann@example.com" }],
+                    "tags": [
+                        { "id": "t1", "name": "Rust", "slug": "rust", "visibility": "public" },
+                        { "id": "t2", "name": "#hidden", "slug": "hash-hidden", "visibility": "internal" }
+                    ],
+                    "posts_tags": [
+                        { "post_id": "p1", "tag_id": "t2", "sort_order": 0 },
+                        { "post_id": "p1", "tag_id": "t1", "sort_order": 1 }
+                    ],
+                    "posts_authors": [{ "post_id": "p1", "author_id": "u1", "sort_order": 0 }]
+                }
+            }]
+        }"##;
+
+        let blog = parse(json, Some("https://ghost.example.com/")).unwrap();
+        assert_eq!(blog.scope, "https://ghost.example.com");
+        assert_eq!(blog.tags.len(), 1);
+        assert_eq!(blog.ignored, ["page"]);
+
+        let post = &blog.posts[0];
+        assert_eq!(post.status, SourceStatus::Published);
+        assert_eq!(post.author_id.as_deref(), Some("u1"));
+        assert_eq!(post.tag_ids, ["t1"]);
+        assert!(post
+            .html
+            .contains("https://ghost.example.com/content/images/a.png"));
+        assert_eq!(
+            post.featured_image.as_deref(),
+            Some("https://ghost.example.com/content/images/cover.png")
+        );
+    }
+}
@@ -0,0 +1,698 @@
+//! Post HTML (as exported by WordPress and Ghost) to Editor.js blocks.
+//!
+//! Inline formatting is reduced to the subset the editor produces (`<b>`,
+//! `<i>`, `<u>`, `<s>`, `<code>`, `<mark>`, `<a>`, `<br>`). Images are hoisted
+//! out of paragraphs into their own blocks and keep the raw `src` in
+//! `file.url`; the importer resolves them to stored media afterwards.
+//! Anything without an Editor.js equivalent is flattened to paragraphs.
+
+use scraper::{ElementRef, Html, Node};
+use serde_json::{json, Value};
+
+use crate::services::content_render::{escape_html, inline_to_text, sanitize_inline};
+
+const BLOCK_LEVEL: &[&str] = &[
+    "address",
+    "article",
+    "aside",
+    "blockquote",
+    "details",
+    "div",
+    "dl",
+    "figure",
+    "footer",
+    "form",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "hr",
+    "li",
+    "main",
+    "nav",
+    "ol",
+    "p",
+    "pre",
+    "section",
+    "table",
+    "ul",
+];
+
+/// Convert an HTML fragment into Editor.js blocks.
+pub fn html_to_blocks(html: &str) -> Vec<Value> {
+    let fragment = Html::parse_fragment(html);
+    let mut converter = Converter::default();
+    converter.container(fragment.root_element());
+    converter.flush();
+    converter.blocks
+}
+
+/// Simplified `wpautop`: WordPress stores classic-editor posts with blank
+/// lines instead of `<p>` tags. Content that already has paragraphs (block
+/// editor, Ghost) is returned unchanged.
+pub fn autop(content: &str) -> String {
+    if content.to_ascii_lowercase().contains("<p") {
+        return content.to_string();
+    }
+
+    let normalized = content.replace("\r\n", "\n");
+    let mut out = String::with_capacity(normalized.len() + 64);
+    for chunk in normalized.split("\n\n") {
+        let chunk = chunk.trim();
+        if chunk.is_empty() {
+            continue;
+        }
+        if starts_with_block_tag(chunk) {
+            out.push_str(chunk);
+        } else {
+            out.push_str("<p>");
+            out.push_str(&chunk.replace('\n', "<br>\n"));
+            out.push_str("</p>");
+        }
+        out.push('\n');
+    }
+    out
+}
+
+fn starts_with_block_tag(chunk: &str) -> bool {
+    let Some(rest) = chunk.strip_prefix('<') else {
+        return false;
+    };
+    let name: String = rest
+        .chars()
+        .take_while(|c| c.is_ascii_alphanumeric())
+        .collect::<String>()
+        .to_ascii_lowercase();
+    BLOCK_LEVEL.contains(&name.as_str())
+}
+
+/// Map a video URL to the embed data of a known service.
+pub fn embed_block(url: &str, caption: &str) -> Option<Value> {
+    let url = url.trim();
+    let lower = url.to_ascii_lowercase();
+    let without_scheme = lower
+        .strip_prefix("https://")
+        .or_else(|| lower.strip_prefix("http://"))?;
+    let host_and_path = without_scheme
+        .strip_prefix("www.")
+        .unwrap_or(without_scheme);
+    // Keep the original casing for ids.
+    let offset = url.len() - host_and_path.len();
+    let original = &url[offset..];
+
+    let (service, embed) = if host_and_path.starts_with("youtube.com/")
+        || host_and_path.starts_with("m.youtube.com/")
+        || host_and_path.starts_with("youtu.be/")
+        || host_and_path.starts_with("youtube-nocookie.com/")
+    {
+        let id = youtube_id(original)?;
+        ("youtube", format!("https://www.youtube.com/embed/{}", id))
+    } else if host_and_path.starts_with("vimeo.com/")
+        || host_and_path.starts_with("player.vimeo.com/")
+    {
+        let id = original
+            .split(['?', '#'])
+            .next()
+            .unwrap_or_default()
+            .rsplit('/')
+            .find(|segment| !segment.is_empty() && segment.chars().all(|c| c.is_ascii_digit()))?;
+        ("vimeo", format!("https://player.vimeo.com/video/{}", id))
+    } else {
+        return None;
+    };
+
+    Some(block(
+        "embed",
+        json!({
+            "service": service,
+            "source": url,
+            "embed": embed,
+            "width": 580,
+            "height": 320,
+            "caption": caption,
+        }),
+    ))
+}
+
+fn youtube_id(url: &str) -> Option<&str> {
+    let (path, query) = url.split_once('?').unwrap_or((url, ""));
+    let query = query.split('#').next().unwrap_or_default();
+    let id = if path.contains("/watch") {
+        query.split('&').find_map(|pair| pair.strip_prefix("v="))?
+    } else {
+        path.rsplit('/').find(|segment| !segment.is_empty())?
+    };
+    let valid = !id.is_empty()
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    valid.then_some(id)
+}
+
+/// A paragraph holding a single link, used for media the editor can't embed.
+pub fn link_paragraph(url: &str) -> Value {
+    let url = escape_html(url.trim());
+    block(
+        "paragraph",
+        json!({ "text": format!("<a href=\"{}\">{}</a>", url, url) }),
+    )
+}
+
+fn block(kind: &str, data: Value) -> Value {
+    json!({ "type": kind, "data": data })
+}
+
+fn image_block(src: &str, caption: &str) -> Value {
+    block(
+        "image",
+        json!({
+            "file": { "url": src },
+            "caption": caption,
+            "withBorder": false,
+            "withBackground": false,
+            "stretched": false,
+        }),
+    )
+}
+
+fn has_text(fragment: &str) -> bool {
+    !inline_to_text(fragment).trim().is_empty()
+}
+
+#[derive(Default)]
+struct Converter {
+    blocks: Vec<Value>,
+    inline: String,
+    /// Inline tags currently open in `inline`; images found inside them are
+    /// queued and emitted after the enclosing block.
+    open_inline: usize,
+    pending: Vec<Value>,
+}
+
+impl Converter {
+    fn container(&mut self, el: ElementRef<'_>) {
+        for child in el.children() {
+            match child.value() {
+                Node::Text(text) => self.text(text),
+                Node::Element(_) => {
+                    if let Some(child) = ElementRef::wrap(child) {
+                        self.element(child);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn element(&mut self, el: ElementRef<'_>) {
+        let name = el.value().name();
+        match name {
+            "script" | "style" | "noscript" | "template" | "head" | "title" | "meta" | "link" => {}
+            "img" => self.image(el, ""),
+            "br" => self.inline.push_str("<br>"),
+            _ if self.open_inline > 0 => self.inline_element(el),
+            "p" => {
+                self.flush();
+                self.container(el);
+                self.flush();
+            }
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                self.flush();
+                let text = self.inline_html(el);
+                if has_text(&text) {
+                    let level = name[1..].parse::<u8>().unwrap_or(2);
+                    self.push(block("header", json!({ "text": text, "level": level })));
+                }
+            }
+            "ul" | "ol" => {
+                self.flush();
+                let mut items = Vec::new();
+                self.list_items(el, &mut items);
+                if !items.is_empty() {
+                    let style = if name == "ol" { "ordered" } else { "unordered" };
+                    self.push(block("list", json!({ "style": style, "items": items })));
+                }
+            }
+            "blockquote" => {
+                self.flush();
+                self.quote(el);
+            }
+            "pre" => {
+                self.flush();
+                self.code(el);
+            }
+            "figure" => {
+                self.flush();
+                self.figure(el);
+            }
+            "table" => {
+                self.flush();
+                self.table(el);
+            }
+            "hr" => {
+                self.flush();
+                self.push(block("delimiter", json!({})));
+            }
+            "iframe" | "video" | "audio" | "embed" => {
+                self.flush();
+                self.media_embed(el, "");
+            }
+            _ if BLOCK_LEVEL.contains(&name) => {
+                self.flush();
+                self.container(el);
+                self.flush();
+            }
+            _ => self.inline_element(el),
+        }
+    }
+
+    fn inline_element(&mut self, el: ElementRef<'_>) {
+        let tag = match el.value().name() {
+            "b" | "strong" => Some("b".to_string()),
+            "i" | "em" => Some("i".to_string()),
+            "s" | "del" | "strike" => Some("s".to_string()),
+            name @ ("u" | "mark" | "code" | "sub" | "sup" | "kbd") => Some(name.to_string()),
+            "a" => el
+                .attr("href")
+                .map(str::trim)
+                .filter(|href| !href.is_empty())
+                .map(|href| format!("a href=\"{}\"", escape_html(href))),
+            _ => None,
+        };
+
+        match tag {
+            Some(tag) => {
+                let close = tag.split(' ').next().unwrap_or_default().to_string();
+                self.inline.push('<');
+                self.inline.push_str(&tag);
+                self.inline.push('>');
+                self.open_inline += 1;
+                self.container(el);
+                self.open_inline -= 1;
+                self.inline.push_str("</");
+                self.inline.push_str(&close);
+                self.inline.push('>');
+            }
+            None => {
+                self.open_inline += 1;
+                self.container(el);
+                self.open_inline -= 1;
+            }
+        }
+    }
+
+    fn text(&mut self, text: &str) {
+        let mut collapsed = String::with_capacity(text.len());
+        let mut last_space =
+            self.inline.is_empty() || self.inline.ends_with(' ') || self.inline.ends_with("<br>");
+        for c in text.chars() {
+            if c.is_whitespace() {
+                if !last_space {
+                    collapsed.push(' ');
+                }
+                last_space = true;
+            } else {
+                collapsed.push(c);
+                last_space = false;
+            }
+        }
+        self.inline.push_str(&escape_html(&collapsed));
+    }
+
+    /// Render the children of `el` as a standalone inline fragment.
+    fn inline_html(&mut self, el: ElementRef<'_>) -> String {
+        let saved = std::mem::take(&mut self.inline);
+        self.open_inline += 1;
+        self.container(el);
+        self.open_inline -= 1;
+        let html = std::mem::replace(&mut self.inline, saved);
+        clean_fragment(&html)
+    }
+
+    fn image(&mut self, el: ElementRef<'_>, caption: &str) {
+        let src = ["src", "data-src", "data-lazy-src", "data-orig-file"]
+            .iter()
+            .filter_map(|attr| el.attr(attr))
+            .map(str::trim)
+            .find(|src| !src.is_empty() && !src.starts_with("data:"));
+        let Some(src) = src else {
+            return;
+        };
+
+        let image = image_block(src, caption);
+        if self.open_inline > 0 {
+            self.pending.push(image);
+        } else {
+            self.flush();
+            self.push(image);
+        }
+    }
+
+    fn list_items(&mut self, el: ElementRef<'_>, items: &mut Vec<String>) {
+        for li in el.child_elements().filter(|c| c.value().name() == "li") {
+            // The stored list format only accepts plain string items, so
+            // nested lists are flattened after their parent item.
+            let saved = std::mem::take(&mut self.inline);
+            self.open_inline += 1;
+            let mut nested = Vec::new();
+            for child in li.children() {
+                match child.value() {
+                    Node::Text(text) => self.text(text),
+                    Node::Element(_) => {
+                        let Some(child) = ElementRef::wrap(child) else {
+                            continue;
+                        };
+                        if matches!(child.value().name(), "ul" | "ol") {
+                            nested.push(child);
+                        } else {
+                            self.element(child);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            self.open_inline -= 1;
+            let text = clean_fragment(&std::mem::replace(&mut self.inline, saved));
+            if has_text(&text) {
+                items.push(text);
+            }
+            for list in nested {
+                self.list_items(list, items);
+            }
+        }
+    }
+
+    fn quote(&mut self, el: ElementRef<'_>) {
+        let mut caption = String::new();
+        let mut paragraphs = Vec::new();
+        let saved = std::mem::take(&mut self.inline);
+        self.open_inline += 1;
+        for child in el.children() {
+            match child.value() {
+                Node::Text(text) => self.text(text),
+                Node::Element(_) => {
+                    let Some(child) = ElementRef::wrap(child) else {
+                        continue;
+                    };
+                    match child.value().name() {
+                        "cite" | "footer" | "figcaption" => {
+                            let before = std::mem::take(&mut self.inline);
+                            self.container(child);
+                            caption = clean_fragment(&std::mem::replace(&mut self.inline, before));
+                        }
+                        "p" => {
+                            let text = clean_fragment(&std::mem::take(&mut self.inline));
+                            if has_text(&text) {
+                                paragraphs.push(text);
+                            }
+                            self.container(child);
+                            let text = clean_fragment(&std::mem::take(&mut self.inline));
+                            if has_text(&text) {
+                                paragraphs.push(text);
+                            }
+                        }
+                        _ => self.element(child),
+                    }
+                }
+                _ => {}
+            }
+        }
+        self.open_inline -= 1;
+        let rest = clean_fragment(&std::mem::replace(&mut self.inline, saved));
+        if has_text(&rest) {
+            paragraphs.push(rest);
+        }
+
+        if !paragraphs.is_empty() {
+            self.push(block(
+                "quote",
+                json!({
+                    "text": paragraphs.join("<br>"),
+                    "caption": caption,
+                    "alignment": "left",
+                }),
+            ));
+        }
+    }
+
+    fn code(&mut self, el: ElementRef<'_>) {
+        let code: String = el.text().collect();
+        let code = code.trim_matches('\n');
+        if code.trim().is_empty() {
+            return;
+        }
+
+        let language = std::iter::once(el)
+            .chain(el.child_elements().filter(|c| c.value().name() == "code"))
+            .flat_map(|e| e.value().classes())
+            .find_map(|class| {
+                class
+                    .strip_prefix("language-")
+                    .or_else(|| class.strip_prefix("lang-"))
+            })
+            .map(str::to_string);
+
+        let mut data = json!({ "code": code });
+        if let Some(language) = language {
+            data["language"] = json!(language);
+        }
+        self.push(block("code", data));
+    }
+
+    fn figure(&mut self, el: ElementRef<'_>) {
+        let caption = el
+            .child_elements()
+            .find(|c| c.value().name() == "figcaption")
+            .map(|c| self.inline_html(c))
+            .unwrap_or_default();
+
+        let classes: Vec<&str> = el.value().classes().collect();
+        if classes.iter().any(|c| c.starts_with("wp-block-embed")) {
+            let url: String = el
+                .child_elements()
+                .filter(|c| c.value().name() != "figcaption")
+                .flat_map(|c| c.text())
+                .collect();
+            let url = url.trim();
+            if let Some(embed) = embed_block(url, &caption) {
+                self.push(embed);
+                return;
+            }
+            if url.starts_with("http://") || url.starts_with("https://") {
+                self.push(link_paragraph(url));
+                return;
+            }
+        }
+
+        let descendants: Vec<ElementRef<'_>> = el.descendent_elements().collect();
+        let has = |name: &str| descendants.iter().any(|d| d.value().name() == name);
+
+        // Galleries nest a figure per image; treat them as plain containers.
+        let nested_figures = descendants
+            .iter()
+            .skip(1)
+            .any(|d| d.value().name() == "figure");
+        if !nested_figures && !has("table") && !has("blockquote") {
+            let images: Vec<&ElementRef<'_>> = descendants
+                .iter()
+                .filter(|d| d.value().name() == "img")
+                .collect();
+            if images.len() == 1 {
+                self.image(*images[0], &caption);
+                return;
+            }
+            if let Some(media) = descendants
+                .iter()
+                .find(|d| matches!(d.value().name(), "iframe" | "video" | "audio" | "embed"))
+            {
+                self.media_embed(*media, &caption);
+                return;
+            }
+        }
+
+        for child in el.children() {
+            match child.value() {
+                Node::Text(text) => self.text(text),
+                Node::Element(_) => {
+                    if let Some(child) = ElementRef::wrap(child) {
+                        if child.value().name() != "figcaption" {
+                            self.element(child);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        self.flush();
+        if has_text(&caption) {
+            self.push(block("paragraph", json!({ "text": caption })));
+        }
+    }
+
+    fn media_embed(&mut self, el: ElementRef<'_>, caption: &str) {
+        let src = el.attr("src").map(str::to_string).or_else(|| {
+            el.child_elements()
+                .find(|c| c.value().name() == "source")
+                .and_then(|c| c.attr("src"))
+                .map(str::to_string)
+        });
+        let Some(src) = src.filter(|s| !s.trim().is_empty()) else {
+            return;
+        };
+        let src = if src.starts_with("//") {
+            format!("https:{}", src)
+        } else {
+            src
+        };
+
+        match embed_block(&src, caption) {
+            Some(embed) => self.push(embed),
+            None => self.push(link_paragraph(&src)),
+        }
+    }
+
+    fn table(&mut self, el: ElementRef<'_>) {
+        let rows: Vec<ElementRef<'_>> = el
+            .descendent_elements()
+            .filter(|d| d.value().name() == "tr")
+            .collect();
+
+        let mut with_headings = false;
+        let mut content = Vec::new();
+        for (idx, row) in rows.iter().enumerate() {
+            let cells: Vec<ElementRef<'_>> = row
+                .child_elements()
+                .filter(|c| matches!(c.value().name(), "td" | "th"))
+                .collect();
+            if cells.is_empty() {
+                continue;
+            }
+            if idx == 0 {
+                with_headings = cells.iter().all(|c| c.value().name() == "th");
+            }
+            let cells: Vec<String> = cells.into_iter().map(|c| self.inline_html(c)).collect();
+            content.push(cells);
+        }
+
+        if !content.is_empty() {
+            self.push(block(
+                "table",
+                json!({ "withHeadings": with_headings, "content": content }),
+            ));
+        }
+    }
+
+    fn push(&mut self, block: Value) {
+        self.blocks.push(block);
+        self.blocks.append(&mut self.pending);
+    }
+
+    fn flush(&mut self) {
+        let text = clean_fragment(&std::mem::take(&mut self.inline));
+        if has_text(&text) {
+            self.push(block("paragraph", json!({ "text": text })));
+        } else {
+            self.blocks.append(&mut self.pending);
+        }
+    }
+}
+
+/// Trim whitespace and stray line breaks from both ends, then run the
+/// fragment through the editor's inline allowlist.
+fn clean_fragment(fragment: &str) -> String {
+    let mut s = fragment.trim();
+    loop {
+        let before = s.len();
+        s = s.trim_start_matches("<br>").trim_end_matches("<br>").trim();
+        if s.len() == before {
+            break;
+        }
+    }
+    sanitize_inline(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(blocks: &[Value]) -> Vec<&str> {
+        blocks
+            .iter()
+            .map(|b| b["type"].as_str().unwrap_or_default())
+            .collect()
+    }
+
+    #[test]
+    fn converts_common_blocks() {
+        let blocks = html_to_blocks(
+            "<h2>Intro</h2><p>Hello <strong>world</strong> and <a href=\"https://x.test\">link</a></p>\
+             <ul><li>one</li><li>two<ul><li>nested</li></ul></li></ul>\
+             <blockquote><p>Quoted</p><cite>Someone</cite></blockquote>\
+             <pre><code class=\"language-rust\">fn main() {}</code></pre><hr>\
+             <table><tr><th>A</th><th>B</th></tr><tr><td>1</td><td>2</td></tr></table>",
+        );
+
+        assert_eq!(
+            kinds(&blocks),
+            [
+                "header",
+                "paragraph",
+                "list",
+                "quote",
+                "code",
+                "delimiter",
+                "table"
+            ]
+        );
+        assert_eq!(blocks[0]["data"]["level"], 2);
+        assert!(blocks[1]["data"]["text"]
+            .as_str()
+            .unwrap()
+            .starts_with("Hello <b>world</b> and <a href=\"https://x.test\""));
+        assert_eq!(blocks[2]["data"]["items"], json!(["one", "two", "nested"]));
+        assert_eq!(blocks[3]["data"]["caption"], "Someone");
+        assert_eq!(blocks[4]["data"]["language"], "rust");
+        assert_eq!(blocks[6]["data"]["withHeadings"], true);
+    }
+
+    #[test]
+    fn hoists_images_and_figures() {
+        let blocks = html_to_blocks(
+            "<p>Before <a href=\"/full.jpg\"><img src=\"/a.jpg\"></a> after</p>\
+             <figure class=\"wp-block-image\"><img src=\"/b.jpg\" alt=\"\"><figcaption>Caption</figcaption></figure>",
+        );
+
+        assert_eq!(kinds(&blocks), ["paragraph", "image", "image"]);
+        assert_eq!(blocks[1]["data"]["file"]["url"], "/a.jpg");
+        assert_eq!(blocks[2]["data"]["caption"], "Caption");
+    }
+
+    #[test]
+    fn maps_known_embeds() {
+        let blocks = html_to_blocks(
+            "<figure class=\"wp-block-embed is-provider-youtube\"><div class=\"wp-block-embed__wrapper\">\n\
+             https://www.youtube.com/watch?v=dQw4w9WgXcQ\n</div></figure>\
+             <iframe src=\"https://player.vimeo.com/video/12345\"></iframe>\
+             <iframe src=\"https://example.com/widget\"></iframe>",
+        );
+
+        assert_eq!(kinds(&blocks), ["embed", "embed", "paragraph"]);
+        assert_eq!(
+            blocks[0]["data"]["embed"],
+            "https://www.youtube.com/embed/dQw4w9WgXcQ"
+        );
+        assert_eq!(blocks[1]["data"]["service"], "vimeo");
+    }
+
+    #[test]
+    fn autop_wraps_classic_content() {
+        let html = autop("First line\nsecond line\n\n<h2>Title</h2>\n\nLast");
+        let blocks = html_to_blocks(&html);
+        assert_eq!(kinds(&blocks), ["paragraph", "header", "paragraph"]);
+        assert_eq!(blocks[0]["data"]["text"], "First line<br>second line");
+        assert_eq!(autop("<p>kept</p>"), "<p>kept</p>");
+    }
+}
@@ -0,0 +1,210 @@
+//! Importing WordPress (WXR) and Ghost (JSON) exports.
+//!
+//! The upload is parsed up front so malformed files are rejected in the
+//! request, then staged in object storage under `imports/` for the
+//! [`BlogImportRun`] job, tracked in `import_jobs`. Every imported author,
+//! term, post, comment and image is recorded in `import_mappings`, so
+//! running the same export again (or retrying the job) only brings in what
+//! is new. Dry runs report the plan without writing.
+
+pub mod apply;
+pub mod ghost;
+pub mod html;
+pub mod source;
+pub mod wordpress;
+
+use bytes::Bytes;
+use sea_orm::TransactionTrait;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tracing::{error, warn};
+
+use crate::{
+    db::sea_models::{
+        import_job::{self, ImportJobStatus, ImportSource, NewImportJob},
+        job,
+    },
+    error::{ErrorCode, ErrorResponse},
+    modules::import_v1::validator::V1BlogImportOptions,
+    services::{
+        jobs::{self, Job, JobResult},
+        media_storage,
+    },
+    AppState,
+};
+
+use source::SourceBlog;
+
+/// Held by the queued import; dry runs don't take it.
+const UNIQUE_KEY: &str = "blog_import";
+const UPLOAD_PREFIX: &str = "imports";
+
+/// Parse an uploaded export, stage it and queue the import.
+pub async fn start(
+    state: &AppState,
+    source: ImportSource,
+    file_name: Option<String>,
+    bytes: Bytes,
+    options: V1BlogImportOptions,
+    requested_by: i32,
+) -> Result<import_job::Model, ErrorResponse> {
+    parse_blocking(source, bytes.clone(), options.site_url.clone()).await?;
+
+    let object_key = format!("{}/{}", UPLOAD_PREFIX, uuid::Uuid::new_v4().simple());
+    media_storage::put_object(state, &object_key, bytes, "application/octet-stream").await?;
+
+    let queued = queue(
+        state,
+        source,
+        file_name,
+        &options,
+        requested_by,
+        &object_key,
+    )
+    .await;
+    if queued.is_err() {
+        discard_upload(state, &object_key).await;
+    }
+    queued
+}
+
+/// Insert the `import_jobs` row together with the job that runs it.
+async fn queue(
+    state: &AppState,
+    source: ImportSource,
+    file_name: Option<String>,
+    options: &V1BlogImportOptions,
+    requested_by: i32,
+    object_key: &str,
+) -> Result<import_job::Model, ErrorResponse> {
+    let txn = state.sea_db.begin().await?;
+    let import = import_job::Entity::create(
+        &txn,
+        NewImportJob {
+            source,
+            dry_run: options.dry_run,
+            file_name,
+            options: serde_json::to_value(options).unwrap_or_else(|_| json!({})),
+            requested_by: Some(requested_by),
+        },
+    )
+    .await?;
+
+    let mut new_job = jobs::job(&BlogImportRun {
+        import_job_id: import.id,
+        object_key: object_key.to_string(),
+    })
+    .created_by(requested_by);
+    if !options.dry_run {
+        new_job = new_job.unique(UNIQUE_KEY);
+    }
+    let Some(queued) = job::Entity::enqueue(&txn, new_job).await? else {
+        txn.rollback().await?;
+        return Err(ErrorResponse::new(ErrorCode::ResourceConflict)
+            .with_message("Another import is already running"));
+    };
+    txn.commit().await?;
+    jobs::notify_enqueued(Some(&queued));
+    Ok(import)
+}
+
+/// Import a staged export for an `import_jobs` row.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BlogImportRun {
+    pub import_job_id: i32,
+    pub object_key: String,
+}
+
+#[async_trait::async_trait]
+impl Job for BlogImportRun {
+    const KIND: &'static str = "blog_import.run";
+    const MAX_ATTEMPTS: i32 = 3;
+
+    async fn run(self, state: &AppState) -> JobResult {
+        let import = import_job::Entity::find_by_id(&state.sea_db, self.import_job_id)
+            .await?
+            .filter(|import| import.status != ImportJobStatus::Completed);
+        let Some(import) = import else {
+            return Ok(Some(json!({ "skipped": "import job finished or deleted" })));
+        };
+        import_job::Entity::mark_running(&state.sea_db, import.id).await?;
+
+        let report = match run_import(state, &import, &self.object_key).await {
+            Ok(report) => report,
+            Err(err) => {
+                error!(job_id = import.id, error = %err.message, "Import job failed");
+                let message = err.message.clone();
+                if let Err(db_err) =
+                    import_job::Entity::mark_failed(&state.sea_db, import.id, message).await
+                {
+                    error!(job_id = import.id, error = %db_err, "Failed to record import job result");
+                }
+                return Err(err.into());
+            }
+        };
+        import_job::Entity::mark_completed(&state.sea_db, import.id, report).await?;
+
+        discard_upload(state, &self.object_key).await;
+        Ok(Some(json!({ "import_job_id": import.id })))
+    }
+}
+
+async fn run_import(
+    state: &AppState,
+    import: &import_job::Model,
+    object_key: &str,
+) -> Result<serde_json::Value, ErrorResponse> {
+    let options: V1BlogImportOptions = import
+        .options
+        .clone()
+        .and_then(|options| serde_json::from_value(options).ok())
+        .ok_or_else(|| {
+            ErrorResponse::new(ErrorCode::InvalidValue).with_message("Import job has no options")
+        })?;
+    let requested_by = import.requested_by.ok_or_else(|| {
+        ErrorResponse::new(ErrorCode::RecordNotFound)
+            .with_message("The user who requested the import is gone")
+    })?;
+
+    let bytes = media_storage::get_object(state, object_key).await?;
+    let blog = parse_blocking(import.source, bytes, options.site_url.clone()).await?;
+    let report = apply::run(
+        state,
+        import.source,
+        blog,
+        import.id,
+        requested_by,
+        &options,
+    )
+    .await?;
+    Ok(serde_json::to_value(report).unwrap_or_else(|_| json!({})))
+}
+
+async fn discard_upload(state: &AppState, key: &str) {
+    if let Err(err) = media_storage::delete_object(state, key).await {
+        warn!(object_key = %key, error = %err.message, "Failed to delete staged import file");
+    }
+}
+
+async fn parse_blocking(
+    source: ImportSource,
+    bytes: Bytes,
+    site_url: Option<String>,
+) -> Result<SourceBlog, ErrorResponse> {
+    tokio::task::spawn_blocking(move || parse(source, &bytes, site_url.as_deref()))
+        .await
+        .map_err(|err| {
+            ErrorResponse::new(ErrorCode::InternalServerError)
+                .with_message("Import parser panicked")
+                .with_details(err.to_string())
+        })?
+        .map_err(|message| ErrorResponse::new(ErrorCode::InvalidFormat).with_message(message))
+}
+
+fn parse(source: ImportSource, bytes: &[u8], site_url: Option<&str>) -> Result<SourceBlog, String> {
+    let text = std::str::from_utf8(bytes).map_err(|_| "Export file is not valid UTF-8")?;
+    match source {
+        ImportSource::Wordpress => wordpress::parse(text),
+        ImportSource::Ghost => ghost::parse(text, site_url),
+    }
+}
@@ -0,0 +1,76 @@
+//! Platform-neutral view of an exported blog, produced by the WordPress and
+//! Ghost parsers and consumed by the importer.
+
+use chrono::{DateTime, FixedOffset};
+
+#[derive(Debug, Clone, Default)]
+pub struct SourceBlog {
+    /// Identifies the exported site so mappings from different blogs of the
+    /// same kind don't collide.
+    pub scope: String,
+    pub authors: Vec<SourceAuthor>,
+    pub categories: Vec<SourceTerm>,
+    pub tags: Vec<SourceTerm>,
+    pub posts: Vec<SourcePost>,
+    /// Items the parser skipped (pages, attachments, ...), by kind.
+    pub ignored: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SourceAuthor {
+    pub id: String,
+    pub name: String,
+    pub email: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SourceTerm {
+    pub id: String,
+    pub name: String,
+    pub slug: String,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SourceStatus {
+    Published,
+    Draft,
+    /// Scheduled, private, pending review...; imported as drafts.
+    Other,
+}
+
+#[derive(Debug, Clone)]
+pub struct SourcePost {
+    pub id: String,
+    pub title: String,
+    pub slug: String,
+    /// Post body as HTML, already passed through `autop` where needed.
+    pub html: String,
+    pub excerpt: Option<String>,
+    pub status: SourceStatus,
+    pub raw_status: String,
+    pub published_at: Option<DateTime<FixedOffset>>,
+    pub author_id: Option<String>,
+    pub category_ids: Vec<String>,
+    pub tag_ids: Vec<String>,
+    pub featured_image: Option<String>,
+    pub comments: Vec<SourceComment>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommentState {
+    Approved,
+    Pending,
+    /// Spam and trashed comments are never imported.
+    Rejected,
+}
+
+#[derive(Debug, Clone)]
+pub struct SourceComment {
+    pub id: String,
+    pub author_name: String,
+    pub author_email: Option<String>,
+    pub content: String,
+    pub state: CommentState,
+    pub created_at: Option<DateTime<FixedOffset>>,
+}
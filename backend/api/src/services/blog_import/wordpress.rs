@@ -0,0 +1,366 @@
+//! WordPress eXtended RSS (WXR) export parser.
+//!
+//! Reads the `Tools → Export` file produced by WordPress (WXR 1.0 to 1.2).
+//! Only `post` items are imported; pages, attachments, menu items and other
+//! post types are counted as ignored. Attachments are still used to resolve
+//! featured images (`_thumbnail_id`).
+
+use std::collections::{HashMap, HashSet};
+
+use chrono::{DateTime, FixedOffset, NaiveDateTime};
+use roxmltree::{Document, Node, ParsingOptions};
+
+use super::{
+    html,
+    source::{
+        CommentState, SourceAuthor, SourceBlog, SourceComment, SourcePost, SourceStatus, SourceTerm,
+    },
+};
+
+const WP_NS_PREFIX: &str = "http://wordpress.org/export/";
+const CONTENT_NS: &str = "http://purl.org/rss/1.0/modules/content/";
+const DC_NS: &str = "http://purl.org/dc/elements/1.1/";
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Ns {
+    None,
+    Wp,
+    Excerpt,
+    Content,
+    Dc,
+}
+
+fn ns_of(node: Node<'_, '_>) -> Ns {
+    match node.tag_name().namespace() {
+        None => Ns::None,
+        Some(uri)
+            if uri.starts_with(WP_NS_PREFIX) && uri.trim_end_matches('/').ends_with("excerpt") =>
+        {
+            Ns::Excerpt
+        }
+        Some(uri) if uri.starts_with(WP_NS_PREFIX) => Ns::Wp,
+        Some(CONTENT_NS) => Ns::Content,
+        Some(DC_NS) => Ns::Dc,
+        Some(_) => Ns::None,
+    }
+}
+
+fn is(node: Node<'_, '_>, ns: Ns, name: &str) -> bool {
+    node.is_element() && node.tag_name().name() == name && ns_of(node) == ns
+}
+
+fn children<'a, 'i>(
+    node: Node<'a, 'i>,
+    ns: Ns,
+    name: &'a str,
+) -> impl Iterator<Item = Node<'a, 'i>> + 'a {
+    node.children().filter(move |c| is(*c, ns, name))
+}
+
+fn child_text(node: Node<'_, '_>, ns: Ns, name: &str) -> Option<String> {
+    node.children()
+        .find(|c| is(*c, ns, name))
+        .map(|c| c.text().unwrap_or_default().trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+/// WXR dates are `YYYY-MM-DD HH:MM:SS`; unset dates are all zeros.
+fn parse_date(value: Option<String>) -> Option<DateTime<FixedOffset>> {
+    let value = value?;
+    if value.starts_with("0000") {
+        return None;
+    }
+    NaiveDateTime::parse_from_str(&value, "%Y-%m-%d %H:%M:%S")
+        .ok()
+        .map(|naive| naive.and_utc().fixed_offset())
+        .or_else(|| DateTime::parse_from_rfc2822(&value).ok())
+}
+
+pub fn parse(xml: &str) -> Result<SourceBlog, String> {
+    // WXR files never carry a DTD; refusing one also rules out entity bombs.
+    let doc = Document::parse_with_options(
+        xml,
+        ParsingOptions {
+            allow_dtd: false,
+            ..ParsingOptions::default()
+        },
+    )
+    .map_err(|err| format!("Invalid WXR XML: {}", err))?;
+
+    let channel = doc
+        .root_element()
+        .children()
+        .find(|c| is(*c, Ns::None, "channel"))
+        .ok_or_else(|| "Not a WordPress export: missing <channel>".to_string())?;
+
+    if !channel.children().any(|c| ns_of(c) == Ns::Wp) {
+        return Err("Not a WordPress export: no wp: elements found".to_string());
+    }
+
+    let mut blog = SourceBlog {
+        scope: child_text(channel, Ns::Wp, "base_site_url")
+            .or_else(|| child_text(channel, Ns::Wp, "base_blog_url"))
+            .or_else(|| child_text(channel, Ns::None, "link"))
+            .unwrap_or_default()
+            .trim_end_matches('/')
+            .to_string(),
+        ..SourceBlog::default()
+    };
+
+    for author in children(channel, Ns::Wp, "author") {
+        let Some(login) = child_text(author, Ns::Wp, "author_login") else {
+            continue;
+        };
+        blog.authors.push(SourceAuthor {
+            name: child_text(author, Ns::Wp, "author_display_name")
+                .unwrap_or_else(|| login.clone()),
+            email: child_text(author, Ns::Wp, "author_email"),
+            id: login,
+        });
+    }
+
+    for term in children(channel, Ns::Wp, "category") {
+        let Some(slug) = child_text(term, Ns::Wp, "category_nicename") else {
+            continue;
+        };
+        blog.categories.push(SourceTerm {
+            name: child_text(term, Ns::Wp, "cat_name").unwrap_or_else(|| slug.clone()),
+            description: child_text(term, Ns::Wp, "category_description"),
+            id: slug.clone(),
+            slug,
+        });
+    }
+
+    for term in children(channel, Ns::Wp, "tag") {
+        let Some(slug) = child_text(term, Ns::Wp, "tag_slug") else {
+            continue;
+        };
+        blog.tags.push(SourceTerm {
+            name: child_text(term, Ns::Wp, "tag_name").unwrap_or_else(|| slug.clone()),
+            description: child_text(term, Ns::Wp, "tag_description"),
+            id: slug.clone(),
+            slug,
+        });
+    }
+
+    let items: Vec<Node<'_, '_>> = children(channel, Ns::None, "item").collect();
+
+    let attachments: HashMap<String, String> = items
+        .iter()
+        .filter(|item| child_text(**item, Ns::Wp, "post_type").as_deref() == Some("attachment"))
+        .filter_map(|item| {
+            Some((
+                child_text(*item, Ns::Wp, "post_id")?,
+                child_text(*item, Ns::Wp, "attachment_url")?,
+            ))
+        })
+        .collect();
+
+    let mut known_categories: HashSet<String> =
+        blog.categories.iter().map(|c| c.id.clone()).collect();
+    let mut known_tags: HashSet<String> = blog.tags.iter().map(|t| t.id.clone()).collect();
+
+    for item in items {
+        let post_type = child_text(item, Ns::Wp, "post_type").unwrap_or_else(|| "post".into());
+        if post_type != "post" {
+            if post_type != "attachment" {
+                blog.ignored.push(post_type);
+            }
+            continue;
+        }
+
+        let Some(id) = child_text(item, Ns::Wp, "post_id") else {
+            continue;
+        };
+        let raw_status = child_text(item, Ns::Wp, "status").unwrap_or_else(|| "draft".into());
+        if raw_status == "trash" || raw_status == "auto-draft" || raw_status == "inherit" {
+            blog.ignored.push(format!("post ({})", raw_status));
+            continue;
+        }
+
+        let mut category_ids = Vec::new();
+        let mut tag_ids = Vec::new();
+        for term in children(item, Ns::None, "category") {
+            let Some(slug) = term.attribute("nicename").map(str::to_string) else {
+                continue;
+            };
+            let name = term.text().unwrap_or_default().trim().to_string();
+            let name = if name.is_empty() { slug.clone() } else { name };
+            match term.attribute("domain") {
+                Some("category") => {
+                    if known_categories.insert(slug.clone()) {
+                        blog.categories.push(SourceTerm {
+                            id: slug.clone(),
+                            name,
+                            slug: slug.clone(),
+                            description: None,
+                        });
+                    }
+                    category_ids.push(slug);
+                }
+                Some("post_tag") => {
+                    if known_tags.insert(slug.clone()) {
+                        blog.tags.push(SourceTerm {
+                            id: slug.clone(),
+                            name,
+                            slug: slug.clone(),
+                            description: None,
+                        });
+                    }
+                    tag_ids.push(slug);
+                }
+                _ => {}
+            }
+        }
+
+        let featured_image = children(item, Ns::Wp, "postmeta")
+            .find(|meta| child_text(*meta, Ns::Wp, "meta_key").as_deref() == Some("_thumbnail_id"))
+            .and_then(|meta| child_text(meta, Ns::Wp, "meta_value"))
+            .and_then(|attachment_id| attachments.get(&attachment_id).cloned());
+
+        let comments = children(item, Ns::Wp, "comment")
+            .filter_map(parse_comment)
+            .collect();
+
+        let title = child_text(item, Ns::None, "title").unwrap_or_default();
+        let content = item
+            .children()
+            .find(|c| is(*c, Ns::Content, "encoded"))
+            .and_then(|c| c.text())
+            .unwrap_or_default();
+
+        blog.posts.push(SourcePost {
+            slug: child_text(item, Ns::Wp, "post_name").unwrap_or_default(),
+            html: html::autop(content),
+            excerpt: child_text(item, Ns::Excerpt, "encoded"),
+            status: match raw_status.as_str() {
+                "publish" => SourceStatus::Published,
+                "draft" => SourceStatus::Draft,
+                _ => SourceStatus::Other,
+            },
+            raw_status,
+            published_at: parse_date(child_text(item, Ns::Wp, "post_date_gmt"))
+                .or_else(|| parse_date(child_text(item, Ns::Wp, "post_date"))),
+            author_id: child_text(item, Ns::Dc, "creator"),
+            category_ids,
+            tag_ids,
+            featured_image,
+            comments,
+            title,
+            id,
+        });
+    }
+
+    Ok(blog)
+}
+
+fn parse_comment(node: Node<'_, '_>) -> Option<SourceComment> {
+    // Pingbacks and trackbacks aren't reader comments.
+    if let Some(kind) = child_text(node, Ns::Wp, "comment_type") {
+        if kind != "comment" {
+            return None;
+        }
+    }
+
+    let state = match child_text(node, Ns::Wp, "comment_approved").as_deref() {
+        Some("1") => CommentState::Approved,
+        Some("0") => CommentState::Pending,
+        _ => CommentState::Rejected,
+    };
+
+    Some(SourceComment {
+        id: child_text(node, Ns::Wp, "comment_id")?,
+        author_name: child_text(node, Ns::Wp, "comment_author").unwrap_or_default(),
+        author_email: child_text(node, Ns::Wp, "comment_author_email"),
+        content: child_text(node, Ns::Wp, "comment_content").unwrap_or_default(),
+        state,
+        created_at: parse_date(child_text(node, Ns::Wp, "comment_date_gmt"))
+            .or_else(|| parse_date(child_text(node, Ns::Wp, "comment_date"))),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = r#"<?xml version="1.0" encoding="UTF-8" ?>
+<rss version="2.0"
+    xmlns:excerpt="http://wordpress.org/export/1.2/excerpt/"
+    xmlns:content="http://purl.org/rss/1.0/modules/content/"
+    xmlns:dc="http://purl.org/dc/elements/1.1/"
+    xmlns:wp="http://wordpress.org/export/1.2/">
+<channel>
+    <title>Old blog</title>
+    <link>https://old.example.com</link>
+    <wp:wxr_version>1.2</wp:wxr_version>
+    <wp:base_site_url>https://old.example.com/</wp:base_site_url>
+    <wp:author><wp:author_id>1</wp:author_id><wp:author_login><![CDATA[jane]]></wp:author_login><wp:author_email><![CDATA[jane@example.com]]></wp:author_email><wp:author_display_name><![CDATA[Jane Doe]]></wp:author_display_name></wp:author>
+    <wp:category><wp:term_id>2</wp:term_id><wp:category_nicename><![CDATA[news]]></wp:category_nicename><wp:category_parent><![CDATA[]]></wp:category_parent><wp:cat_name><![CDATA[News]]></wp:cat_name></wp:category>
+    <item>
+        <title>Hello world</title>
+        <dc:creator><![CDATA[jane]]></dc:creator>
+        <content:encoded><![CDATA[First paragraph.
+
+Second paragraph.]]></content:encoded>
+        <excerpt:encoded><![CDATA[Short]]></excerpt:encoded>
+        <wp:post_id>10</wp:post_id>
+        <wp:post_date_gmt><![CDATA[2020-05-01 10:00:00]]></wp:post_date_gmt>
+        <wp:post_name><![CDATA[hello-world]]></wp:post_name>
+        <wp:status><![CDATA[publish]]></wp:status>
+        <wp:post_type><![CDATA[post]]></wp:post_type>
+        <category domain="category" nicename="news"><![CDATA[News]]></category>
+        <category domain="post_tag" nicename="rust"><![CDATA[Rust]]></category>
+        <wp:postmeta><wp:meta_key><![CDATA[_thumbnail_id]]></wp:meta_key><wp:meta_value><![CDATA[11]]></wp:meta_value></wp:postmeta>
+        <wp:comment><wp:comment_id>5</wp:comment_id><wp:comment_author><![CDATA[Bob]]></wp:comment_author><wp:comment_author_email><![CDATA[bob@example.com]]></wp:comment_author_email><wp:comment_date_gmt><![CDATA[2020-05-02 08:00:00]]></wp:comment_date_gmt><wp:comment_content><![CDATA[Nice post]]></wp:comment_content><wp:comment_approved><![CDATA[1]]></wp:comment_approved><wp:comment_type><![CDATA[comment]]></wp:comment_type></wp:comment>
+        <wp:comment><wp:comment_id>6</wp:comment_id><wp:comment_content><![CDATA[Buy now]]></wp:comment_content><wp:comment_approved><![CDATA[spam]]></wp:comment_approved></wp:comment>
+    </item>
+    <item>
+        <title>Cover</title>
+        <wp:post_id>11</wp:post_id>
+        <wp:post_type><![CDATA[attachment]]></wp:post_type>
+        <wp:attachment_url><![CDATA[https://old.example.com/wp-content/uploads/cover.jpg]]></wp:attachment_url>
+    </item>
+    <item>
+        <title>About</title>
+        <wp:post_id>12</wp:post_id>
+        <wp:post_type><![CDATA[page]]></wp:post_type>
+    </item>
+</channel>
+</rss>"#;
+
+    #[test]
+    fn parses_wxr_export() {
+        let blog = parse(SAMPLE).unwrap();
+
+        assert_eq!(blog.scope, "https://old.example.com");
+        assert_eq!(blog.authors[0].id, "jane");
+        assert_eq!(blog.authors[0].email.as_deref(), Some("jane@example.com"));
+        assert_eq!(blog.categories.len(), 1);
+        assert_eq!(blog.tags[0].slug, "rust");
+        assert_eq!(blog.ignored, ["page"]);
+
+        let post = &blog.posts[0];
+        assert_eq!(post.slug, "hello-world");
+        assert_eq!(post.status, SourceStatus::Published);
+        assert_eq!(post.author_id.as_deref(), Some("jane"));
+        assert_eq!(post.excerpt.as_deref(), Some("Short"));
+        assert_eq!(
+            post.featured_image.as_deref(),
+            Some("https://old.example.com/wp-content/uploads/cover.jpg")
+        );
+        assert!(post.html.starts_with("<p>First paragraph.</p>"));
+        assert_eq!(
+            post.published_at.unwrap().to_rfc3339(),
+            "2020-05-01T10:00:00+00:00"
+        );
+        assert_eq!(post.comments.len(), 2);
+        assert_eq!(post.comments[0].state, CommentState::Approved);
+        assert_eq!(post.comments[1].state, CommentState::Rejected);
+    }
+
+    #[test]
+    fn rejects_non_wxr() {
+        assert!(parse("<rss><channel><title>x</title></channel></rss>").is_err());
+        assert!(parse("not xml").is_err());
+    }
+}
@@ -0,0 +1,294 @@
+use serde_json::Value;
+
+use super::inline::{escape_html, sanitize_inline, sanitize_raw};
+use super::{safe_url, table_cell, Block, ListItem};
+
+pub fn render_blocks(blocks: &[Value]) -> String {
+    let mut out = String::new();
+    for value in blocks {
+        if let Some(block) = Block::from_value(value) {
+            render_block(&block, &mut out);
+        }
+    }
+    out
+}
+
+fn render_block(block: &Block, out: &mut String) {
+    match block.kind {
+        "paragraph" => {
+            out.push_str(&format!(
+                "<p>{}</p>",
+                sanitize_inline(block.str_or_empty("text"))
+            ));
+        }
+        "header" => {
+            let level = block.header_level();
+            out.push_str(&format!(
+                "<h{level}>{}</h{level}>",
+                sanitize_inline(block.str_or_empty("text"))
+            ));
+        }
+        "list" => render_list(block.array("items"), block.is_ordered_list(), out),
+        "checklist" => {
+            out.push_str("<ul class=\"checklist\">");
+            for item in block.array("items") {
+                let text = item.get("text").and_then(|v| v.as_str()).unwrap_or("");
+                let checked = item
+                    .get("checked")
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(false);
+                out.push_str(&format!(
+                    "<li class=\"checklist-item{}\"><input type=\"checkbox\" disabled{}> {}</li>",
+                    if checked { " checked" } else { "" },
+                    if checked { " checked" } else { "" },
+                    sanitize_inline(text)
+                ));
+            }
+            out.push_str("</ul>");
+        }
+        "quote" => {
+            let alignment = match block.str("alignment") {
+                Some("center") => "center",
+                _ => "left",
+            };
+            out.push_str(&format!(
+                "<blockquote class=\"align-{}\"><p>{}</p>",
+                alignment,
+                sanitize_inline(block.str_or_empty("text"))
+            ));
+            if let Some(caption) = block.str("caption").filter(|c| !c.trim().is_empty()) {
+                out.push_str(&format!("<cite>{}</cite>", sanitize_inline(caption)));
+            }
+            out.push_str("</blockquote>");
+        }
+        "code" => {
+            let class = block
+                .str("language")
+                .filter(|l| {
+                    l.chars()
+                        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '+')
+                })
+                .filter(|l| !l.is_empty())
+                .map(|l| format!(" class=\"language-{}\"", l))
+                .unwrap_or_default();
+            out.push_str(&format!(
+                "<pre><code{}>{}</code></pre>",
+                class,
+                escape_html(block.str_or_empty("code"))
+            ));
+        }
+        "delimiter" => out.push_str("<hr>"),
+        "image" => {
+            let Some(url) = block.image_url().and_then(safe_url) else {
+                return;
+            };
+            let caption = block.str("caption").unwrap_or("");
+            let mut classes = vec!["image"];
+            if block.bool("stretched") {
+                classes.push("image-stretched");
+            }
+            if block.bool("withBorder") {
+                classes.push("image-bordered");
+            }
+            if block.bool("withBackground") {
+                classes.push("image-background");
+            }
+
+            let mut dimensions = String::new();
+            for field in ["width", "height"] {
+                if let Some(n) = block
+                    .data
+                    .get("file")
+                    .and_then(|f| f.get(field))
+                    .and_then(|v| v.as_i64())
+                {
+                    dimensions.push_str(&format!(" {}=\"{}\"", field, n));
+                }
+            }
+
+            out.push_str(&format!(
+                "<figure class=\"{}\"><img src=\"{}\" alt=\"{}\"{} loading=\"lazy\">",
+                classes.join(" "),
+                escape_html(url),
+                escape_html(&super::inline::to_text(caption)),
+                dimensions
+            ));
+            if !caption.trim().is_empty() {
+                out.push_str(&format!(
+                    "<figcaption>{}</figcaption>",
+                    sanitize_inline(caption)
+                ));
+            }
+            out.push_str("</figure>");
+        }
+        "embed" => {
+            let service = block
+                .str("service")
+                .unwrap_or("")
+                .chars()
+                .filter(|c| c.is_ascii_alphanumeric() || *c == '-')
+                .collect::<String>();
+            let source = block.str("source").and_then(safe_url);
+            let caption = block.str("caption").unwrap_or("");
+
+            out.push_str(&format!("<figure class=\"embed embed-{}\">", service));
+            match block
+                .str("embed")
+                .filter(|u| u.trim().to_ascii_lowercase().starts_with("https://"))
+            {
+                Some(embed_url) => {
+                    let width = block.data.get("width").and_then(|v| v.as_i64());
+                    let height = block.data.get("height").and_then(|v| v.as_i64());
+                    out.push_str(&format!(
+                        "<iframe src=\"{}\"{}{} frameborder=\"0\" allowfullscreen loading=\"lazy\"></iframe>",
+                        escape_html(embed_url.trim()),
+                        width.map(|w| format!(" width=\"{}\"", w)).unwrap_or_default(),
+                        height.map(|h| format!(" height=\"{}\"", h)).unwrap_or_default(),
+                    ));
+                }
+                None => {
+                    if let Some(source) = source {
+                        out.push_str(&format!(
+                            "<a href=\"{}\" rel=\"noopener noreferrer nofollow\">{}</a>",
+                            escape_html(source),
+                            escape_html(source)
+                        ));
+                    }
+                }
+            }
+            if !caption.trim().is_empty() {
+                out.push_str(&format!(
+                    "<figcaption>{}</figcaption>",
+                    sanitize_inline(caption)
+                ));
+            }
+            out.push_str("</figure>");
+        }
+        "linktool" => {
+            let Some(link) = block.str("link").and_then(safe_url) else {
+                return;
+            };
+            let title = block.nested_str("meta", "title").unwrap_or(link);
+            out.push_str(&format!(
+                "<a class=\"link-card\" href=\"{}\" rel=\"noopener noreferrer nofollow\"><strong>{}</strong>",
+                escape_html(link),
+                escape_html(title)
+            ));
+            if let Some(description) = block
+                .nested_str("meta", "description")
+                .filter(|d| !d.trim().is_empty())
+            {
+                out.push_str(&format!("<span>{}</span>", escape_html(description)));
+            }
+            out.push_str("</a>");
+        }
+        "attaches" => {
+            let Some(url) = block.nested_str("file", "url").and_then(safe_url) else {
+                return;
+            };
+            let name = block
+                .nested_str("file", "name")
+                .or_else(|| block.str("title"))
+                .unwrap_or(url);
+            out.push_str(&format!(
+                "<a class=\"attachment\" href=\"{}\" download>{}</a>",
+                escape_html(url),
+                escape_html(name)
+            ));
+        }
+        "raw" => out.push_str(&sanitize_raw(block.str_or_empty("html"))),
+        "table" => {
+            let rows = block.array("content");
+            let with_headings = block.bool("withHeadings");
+            out.push_str("<table>");
+            for (idx, row) in rows.iter().enumerate() {
+                let cells = row.as_array().map(|c| c.as_slice()).unwrap_or(&[]);
+                let heading = with_headings && idx == 0;
+                if heading {
+                    out.push_str("<thead>");
+                } else if idx == 0 || (with_headings && idx == 1) {
+                    out.push_str("<tbody>");
+                }
+                out.push_str("<tr>");
+                let tag = if heading { "th" } else { "td" };
+                for cell in cells {
+                    out.push_str(&format!(
+                        "<{tag}>{}</{tag}>",
+                        sanitize_inline(&table_cell(cell))
+                    ));
+                }
+                out.push_str("</tr>");
+                if heading {
+                    out.push_str("</thead>");
+                }
+            }
+            if rows.len() > usize::from(with_headings) {
+                out.push_str("</tbody>");
+            }
+            out.push_str("</table>");
+        }
+        "warning" => {
+            out.push_str(&format!(
+                "<aside class=\"warning\"><strong>{}</strong><p>{}</p></aside>",
+                sanitize_inline(block.str_or_empty("title")),
+                sanitize_inline(block.str_or_empty("message"))
+            ));
+        }
+        "alert" => {
+            let kind = block
+                .str("type")
+                .filter(|t| matches!(*t, "info" | "warning" | "success" | "error"))
+                .unwrap_or("info");
+            let align = block
+                .str("align")
+                .filter(|a| matches!(*a, "left" | "center" | "right"))
+                .unwrap_or("left");
+            out.push_str(&format!(
+                "<div class=\"alert alert-{} align-{}\" role=\"alert\">{}</div>",
+                kind,
+                align,
+                sanitize_inline(block.str_or_empty("message"))
+            ));
+        }
+        "button" => {
+            let text = block
+                .str("text")
+                .or_else(|| block.str("buttonText"))
+                .unwrap_or("");
+            let Some(link) = block
+                .str("link")
+                .or_else(|| block.str("buttonLink"))
+                .and_then(safe_url)
+            else {
+                return;
+            };
+            let style = block
+                .str("style")
+                .unwrap_or("primary")
+                .chars()
+                .filter(|c| c.is_ascii_alphanumeric() || *c == '-')
+                .collect::<String>();
+            out.push_str(&format!(
+                "<a class=\"button button-{}\" href=\"{}\" rel=\"noopener noreferrer nofollow\">{}</a>",
+                style,
+                escape_html(link),
+                escape_html(text)
+            ));
+        }
+        _ => {}
+    }
+}
+
+fn render_list(items: &[Value], ordered: bool, out: &mut String) {
+    let tag = if ordered { "ol" } else { "ul" };
+    out.push_str(&format!("<{}>", tag));
+    for item in items.iter().filter_map(ListItem::from_value) {
+        out.push_str("<li>");
+        out.push_str(&sanitize_inline(item.content));
+        if !item.children.is_empty() {
+            render_list(item.children, ordered, out);
+        }
+        out.push_str("</li>");
+    }
+    out.push_str(&format!("</{}>", tag));
+}
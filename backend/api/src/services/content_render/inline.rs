@@ -0,0 +1,196 @@
+//! Inline formatting inside Editor.js text fields.
+//!
+//! Editor.js stores inline markup (bold, italic, links, inline code, marker)
+//! as HTML fragments inside block strings. Everything goes through a strict
+//! ammonia allowlist first; the Markdown and plain-text renderers then walk
+//! the sanitized fragment with a small tokenizer.
+
+use std::collections::HashSet;
+use std::sync::OnceLock;
+
+use ammonia::Builder;
+
+const INLINE_TAGS: &[&str] = &[
+    "a", "b", "strong", "i", "em", "u", "s", "del", "mark", "code", "br", "sub", "sup", "kbd",
+];
+
+static INLINE_SANITIZER: OnceLock<Builder<'static>> = OnceLock::new();
+static RAW_SANITIZER: OnceLock<Builder<'static>> = OnceLock::new();
+
+fn inline_sanitizer() -> &'static Builder<'static> {
+    INLINE_SANITIZER.get_or_init(|| {
+        let mut builder = Builder::empty();
+        builder
+            .add_tags(INLINE_TAGS)
+            .add_tag_attributes("a", &["href", "title"])
+            .url_schemes(HashSet::from(["http", "https", "mailto"]))
+            .link_rel(Some("noopener noreferrer nofollow"));
+        builder
+    })
+}
+
+fn raw_sanitizer() -> &'static Builder<'static> {
+    RAW_SANITIZER.get_or_init(|| {
+        let mut builder = Builder::default();
+        builder
+            .url_schemes(HashSet::from(["http", "https", "mailto"]))
+            .link_rel(Some("noopener noreferrer nofollow"));
+        builder
+    })
+}
+
+/// Sanitize an inline Editor.js fragment, keeping only basic formatting tags.
+pub fn sanitize_inline(fragment: &str) -> String {
+    inline_sanitizer().clean(fragment).to_string()
+}
+
+/// Sanitize the HTML of a `raw` block with ammonia's default block allowlist.
+pub fn sanitize_raw(html: &str) -> String {
+    raw_sanitizer().clean(html).to_string()
+}
+
+pub fn escape_html(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Decode the named and numeric entities that Editor.js and ammonia emit.
+pub fn decode_entities(s: &str) -> String {
+    if !s.contains('&') {
+        return s.to_string();
+    }
+
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(pos) = rest.find('&') {
+        out.push_str(&rest[..pos]);
+        rest = &rest[pos..];
+
+        let decoded = rest.find(';').filter(|end| *end <= 10).and_then(|end| {
+            let entity = &rest[1..end];
+            let ch = match entity {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some('\u{a0}'),
+                _ if entity.starts_with("#x") || entity.starts_with("#X") => {
+                    u32::from_str_radix(&entity[2..], 16)
+                        .ok()
+                        .and_then(char::from_u32)
+                }
+                _ if entity.starts_with('#') => {
+                    entity[1..].parse::<u32>().ok().and_then(char::from_u32)
+                }
+                _ => None,
+            };
+            ch.map(|c| (c, end))
+        });
+
+        match decoded {
+            Some((c, end)) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InlineToken {
+    Text(String),
+    Open { tag: String, href: Option<String> },
+    Close { tag: String },
+    LineBreak,
+}
+
+/// Tokenize an inline fragment. The input is sanitized first, so the walker
+/// only ever sees allowlisted, well-formed tags.
+pub fn tokenize(fragment: &str) -> Vec<InlineToken> {
+    let clean = sanitize_inline(fragment);
+    let mut tokens = Vec::new();
+    let mut rest = clean.as_str();
+
+    while !rest.is_empty() {
+        match rest.find('<') {
+            Some(0) => {
+                let end = match rest.find('>') {
+                    Some(end) => end,
+                    None => {
+                        tokens.push(InlineToken::Text(decode_entities(rest)));
+                        break;
+                    }
+                };
+                let inner = &rest[1..end];
+                rest = &rest[end + 1..];
+
+                if let Some(name) = inner.strip_prefix('/') {
+                    tokens.push(InlineToken::Close {
+                        tag: name.trim().to_ascii_lowercase(),
+                    });
+                    continue;
+                }
+
+                let inner = inner.trim_end_matches('/');
+                let tag = inner
+                    .split_whitespace()
+                    .next()
+                    .unwrap_or("")
+                    .to_ascii_lowercase();
+                if tag == "br" {
+                    tokens.push(InlineToken::LineBreak);
+                } else {
+                    let href = attribute(inner, "href").map(|v| decode_entities(&v));
+                    tokens.push(InlineToken::Open { tag, href });
+                }
+            }
+            Some(pos) => {
+                tokens.push(InlineToken::Text(decode_entities(&rest[..pos])));
+                rest = &rest[pos..];
+            }
+            None => {
+                tokens.push(InlineToken::Text(decode_entities(rest)));
+                break;
+            }
+        }
+    }
+
+    tokens
+}
+
+fn attribute(tag_inner: &str, name: &str) -> Option<String> {
+    let needle = format!("{}=\"", name);
+    let start = tag_inner.find(&needle)? + needle.len();
+    let end = tag_inner[start..].find('"')?;
+    Some(tag_inner[start..start + end].to_string())
+}
+
+/// Inline fragment reduced to plain text.
+pub fn to_text(fragment: &str) -> String {
+    let mut out = String::new();
+    for token in tokenize(fragment) {
+        match token {
+            InlineToken::Text(text) => out.push_str(&text),
+            InlineToken::LineBreak => out.push('\n'),
+            _ => {}
+        }
+    }
+    out.replace('\u{a0}', " ")
+}
@@ -0,0 +1,318 @@
+use serde_json::Value;
+
+use super::inline::{self, sanitize_raw, InlineToken};
+use super::{safe_url, table_cell, Block, ListItem};
+
+pub fn render_blocks(blocks: &[Value]) -> String {
+    let chunks: Vec<String> = blocks
+        .iter()
+        .filter_map(Block::from_value)
+        .filter_map(|block| render_block(&block))
+        .filter(|chunk| !chunk.is_empty())
+        .collect();
+
+    let mut out = chunks.join("\n\n");
+    if !out.is_empty() {
+        out.push('\n');
+    }
+    out
+}
+
+fn render_block(block: &Block) -> Option<String> {
+    let rendered = match block.kind {
+        "paragraph" => inline_to_markdown(block.str_or_empty("text")),
+        "header" => format!(
+            "{} {}",
+            "#".repeat(block.header_level()),
+            inline_to_markdown(block.str_or_empty("text"))
+        ),
+        "list" => {
+            let mut out = String::new();
+            render_list(block.array("items"), block.is_ordered_list(), 0, &mut out);
+            out.trim_end().to_string()
+        }
+        "checklist" => block
+            .array("items")
+            .iter()
+            .map(|item| {
+                let text = item.get("text").and_then(|v| v.as_str()).unwrap_or("");
+                let checked = item
+                    .get("checked")
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(false);
+                format!(
+                    "- [{}] {}",
+                    if checked { "x" } else { " " },
+                    inline_to_markdown(text)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+        "quote" => {
+            let mut out = blockquote(&inline_to_markdown(block.str_or_empty("text")));
+            if let Some(caption) = block.str("caption").filter(|c| !c.trim().is_empty()) {
+                out.push_str(&format!("\n>\n> — {}", inline_to_markdown(caption)));
+            }
+            out
+        }
+        "code" => {
+            let code = block.str_or_empty("code");
+            let fence = "`".repeat(longest_backtick_run(code).max(2) + 1);
+            let language = block.str("language").unwrap_or("");
+            format!(
+                "{fence}{}\n{}\n{fence}",
+                language,
+                code.trim_end_matches('\n')
+            )
+        }
+        "delimiter" => "---".to_string(),
+        "image" => {
+            let url = block.image_url().and_then(safe_url)?;
+            let caption = inline::to_text(block.str_or_empty("caption"));
+            format!("![{}]({})", escape_link_text(&caption), escape_url(url))
+        }
+        "embed" => {
+            let source = block
+                .str("source")
+                .or_else(|| block.str("embed"))
+                .and_then(safe_url)?;
+            let caption = inline::to_text(block.str_or_empty("caption"));
+            let label = if caption.trim().is_empty() {
+                source.to_string()
+            } else {
+                caption
+            };
+            format!("[{}]({})", escape_link_text(&label), escape_url(source))
+        }
+        "linktool" => {
+            let link = block.str("link").and_then(safe_url)?;
+            let title = block.nested_str("meta", "title").unwrap_or(link);
+            let mut out = format!("[{}]({})", escape_link_text(title), escape_url(link));
+            if let Some(description) = block
+                .nested_str("meta", "description")
+                .filter(|d| !d.trim().is_empty())
+            {
+                out.push_str(&format!("\n{}", escape_text(description)));
+            }
+            out
+        }
+        "attaches" => {
+            let url = block.nested_str("file", "url").and_then(safe_url)?;
+            let name = block
+                .nested_str("file", "name")
+                .or_else(|| block.str("title"))
+                .unwrap_or(url);
+            format!("[{}]({})", escape_link_text(name), escape_url(url))
+        }
+        "raw" => sanitize_raw(block.str_or_empty("html")),
+        "table" => render_table(block.array("content"), block.bool("withHeadings")),
+        "warning" => blockquote(&format!(
+            "**{}**\n\n{}",
+            inline_to_markdown(block.str_or_empty("title")),
+            inline_to_markdown(block.str_or_empty("message"))
+        )),
+        "alert" => {
+            let label = match block.str("type") {
+                Some("warning") => "Warning",
+                Some("success") => "Success",
+                Some("error") => "Error",
+                _ => "Info",
+            };
+            blockquote(&format!(
+                "**{}:** {}",
+                label,
+                inline_to_markdown(block.str_or_empty("message"))
+            ))
+        }
+        "button" => {
+            let text = block
+                .str("text")
+                .or_else(|| block.str("buttonText"))
+                .unwrap_or("");
+            let link = block
+                .str("link")
+                .or_else(|| block.str("buttonLink"))
+                .and_then(safe_url)?;
+            format!("[{}]({})", escape_link_text(text), escape_url(link))
+        }
+        _ => return None,
+    };
+    Some(rendered)
+}
+
+fn render_list(items: &[Value], ordered: bool, depth: usize, out: &mut String) {
+    let indent = "   ".repeat(depth);
+    for (idx, item) in items.iter().filter_map(ListItem::from_value).enumerate() {
+        let marker = if ordered {
+            format!("{}.", idx + 1)
+        } else {
+            "-".to_string()
+        };
+        out.push_str(&format!(
+            "{}{} {}\n",
+            indent,
+            marker,
+            inline_to_markdown(item.content)
+        ));
+        if !item.children.is_empty() {
+            render_list(item.children, ordered, depth + 1, out);
+        }
+    }
+}
+
+fn render_table(rows: &[Value], with_headings: bool) -> String {
+    let rows: Vec<Vec<String>> = rows
+        .iter()
+        .map(|row| {
+            row.as_array()
+                .map(|cells| {
+                    cells
+                        .iter()
+                        .map(|c| inline_to_markdown(&table_cell(c)).replace('|', "\\|"))
+                        .collect()
+                })
+                .unwrap_or_default()
+        })
+        .collect();
+
+    let columns = rows.iter().map(|r| r.len()).max().unwrap_or(0);
+    if columns == 0 {
+        return String::new();
+    }
+
+    let format_row = |cells: &[String]| {
+        let mut padded: Vec<String> = cells.to_vec();
+        padded.resize(columns, String::new());
+        format!("| {} |", padded.join(" | "))
+    };
+
+    // GFM tables always need a header row; use an empty one when the
+    // block doesn't mark its first row as headings.
+    let (header, body) = if with_headings {
+        (rows[0].clone(), &rows[1..])
+    } else {
+        (vec![String::new(); columns], &rows[..])
+    };
+
+    let mut lines = vec![
+        format_row(&header),
+        format!("| {} |", vec!["---"; columns].join(" | ")),
+    ];
+    lines.extend(body.iter().map(|r| format_row(r)));
+    lines.join("\n")
+}
+
+fn blockquote(text: &str) -> String {
+    text.lines()
+        .map(|line| {
+            if line.is_empty() {
+                ">".to_string()
+            } else {
+                format!("> {}", line)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn longest_backtick_run(s: &str) -> usize {
+    let mut longest = 0;
+    let mut current = 0;
+    for c in s.chars() {
+        if c == '`' {
+            current += 1;
+            longest = longest.max(current);
+        } else {
+            current = 0;
+        }
+    }
+    longest
+}
+
+/// Convert a sanitized inline fragment to CommonMark inline syntax.
+pub fn inline_to_markdown(fragment: &str) -> String {
+    let mut out = String::new();
+    // Stack of pending link targets so nested formatting inside links works.
+    let mut links: Vec<Option<String>> = Vec::new();
+    let mut in_code = false;
+
+    for token in inline::tokenize(fragment) {
+        match token {
+            InlineToken::Text(text) => {
+                let text = text.replace('\u{a0}', " ");
+                if in_code {
+                    out.push_str(&text);
+                } else {
+                    out.push_str(&escape_text(&text));
+                }
+            }
+            InlineToken::LineBreak => out.push_str("\\\n"),
+            InlineToken::Open { tag, href } => match tag.as_str() {
+                "b" | "strong" => out.push_str("**"),
+                "i" | "em" => out.push('*'),
+                "s" | "del" => out.push_str("~~"),
+                "code" => {
+                    in_code = true;
+                    out.push('`');
+                }
+                "a" => {
+                    let target = href.as_deref().and_then(safe_url).map(str::to_string);
+                    if target.is_some() {
+                        out.push('[');
+                    }
+                    links.push(target);
+                }
+                _ => {}
+            },
+            InlineToken::Close { tag } => match tag.as_str() {
+                "b" | "strong" => out.push_str("**"),
+                "i" | "em" => out.push('*'),
+                "s" | "del" => out.push_str("~~"),
+                "code" => {
+                    in_code = false;
+                    out.push('`');
+                }
+                "a" => {
+                    if let Some(Some(target)) = links.pop() {
+                        out.push_str(&format!("]({})", escape_url(&target)));
+                    }
+                }
+                _ => {}
+            },
+        }
+    }
+
+    out
+}
+
+fn escape_text(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut line_start = true;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        let needs_escape = match c {
+            '\\' | '*' | '_' | '`' | '[' | ']' | '<' | '~' => true,
+            '#' | '>' => line_start,
+            '-' | '+' => line_start && chars.peek() == Some(&' '),
+            _ => false,
+        };
+        if needs_escape {
+            out.push('\\');
+        }
+        out.push(c);
+        line_start = c == '\n' || (line_start && c == ' ');
+    }
+    out
+}
+
+fn escape_link_text(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('[', "\\[")
+        .replace(']', "\\]")
+}
+
+fn escape_url(url: &str) -> String {
+    url.replace(' ', "%20")
+        .replace('(', "%28")
+        .replace(')', "%29")
+}
@@ -0,0 +1,267 @@
+//! Server-side rendering of Editor.js documents.
+//!
+//! Posts are stored as Editor.js JSON (`posts.content`). This module turns that
+//! document into sanitized HTML, CommonMark Markdown or plain text so feeds,
+//! exports and API consumers don't need to re-implement block rendering.
+//!
+//! Every block type accepted by `post_v1::validator::EditorJsDocument` is
+//! handled; unknown blocks are skipped rather than failing the whole render.
+
+mod html;
+mod inline;
+mod markdown;
+mod text;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+pub use inline::{decode_entities, escape_html, sanitize_inline, to_text as inline_to_text};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum RenderFormat {
+    Html,
+    #[serde(alias = "markdown")]
+    Md,
+    #[serde(alias = "plain")]
+    Text,
+}
+
+impl RenderFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::Html => "text/html; charset=utf-8",
+            Self::Md => "text/markdown; charset=utf-8",
+            Self::Text => "text/plain; charset=utf-8",
+        }
+    }
+}
+
+/// Render a stored Editor.js document into the requested format.
+pub fn render(content: &Value, format: RenderFormat) -> String {
+    match format {
+        RenderFormat::Html => to_html(content),
+        RenderFormat::Md => to_markdown(content),
+        RenderFormat::Text => to_plain_text(content),
+    }
+}
+
+pub fn to_html(content: &Value) -> String {
+    html::render_blocks(blocks(content))
+}
+
+pub fn to_markdown(content: &Value) -> String {
+    markdown::render_blocks(blocks(content))
+}
+
+pub fn to_plain_text(content: &Value) -> String {
+    text::render_blocks(blocks(content))
+}
+
+/// Plain text of a single block, as `to_plain_text` would render it.
+pub fn block_text(block: &Value) -> String {
+    text::render_blocks(std::slice::from_ref(block))
+}
+
+/// Plain-text summary capped at `max_len` characters, cut on a word boundary
+/// when possible. Used for feed descriptions and excerpt fallbacks.
+pub fn summary(content: &Value, max_len: usize) -> String {
+    let flattened = to_plain_text(content)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    if flattened.chars().count() <= max_len {
+        return flattened;
+    }
+
+    let truncated: String = flattened.chars().take(max_len).collect();
+    match truncated.rfind(' ') {
+        Some(idx) if idx > max_len / 2 => format!("{}…", truncated[..idx].trim_end()),
+        _ => format!("{}…", truncated.trim_end()),
+    }
+}
+
+pub(crate) fn blocks(content: &Value) -> &[Value] {
+    content
+        .get("blocks")
+        .and_then(|b| b.as_array())
+        .map(|b| b.as_slice())
+        .unwrap_or(&[])
+}
+
+/// Shared accessors over a raw block's `data` object.
+pub(crate) struct Block<'a> {
+    pub kind: &'a str,
+    pub data: &'a Value,
+}
+
+impl<'a> Block<'a> {
+    pub fn from_value(value: &'a Value) -> Option<Self> {
+        let kind = value.get("type").and_then(|t| t.as_str())?;
+        let data = value.get("data").unwrap_or(&Value::Null);
+        Some(Self { kind, data })
+    }
+
+    pub fn str(&self, field: &str) -> Option<&'a str> {
+        self.data.get(field).and_then(|v| v.as_str())
+    }
+
+    pub fn str_or_empty(&self, field: &str) -> &'a str {
+        self.str(field).unwrap_or("")
+    }
+
+    pub fn nested_str(&self, parent: &str, field: &str) -> Option<&'a str> {
+        self.data
+            .get(parent)
+            .and_then(|v| v.get(field))
+            .and_then(|v| v.as_str())
+    }
+
+    pub fn bool(&self, field: &str) -> bool {
+        self.data
+            .get(field)
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false)
+    }
+
+    pub fn array(&self, field: &str) -> &'a [Value] {
+        self.data
+            .get(field)
+            .and_then(|v| v.as_array())
+            .map(|v| v.as_slice())
+            .unwrap_or(&[])
+    }
+
+    pub fn header_level(&self) -> usize {
+        self.data
+            .get("level")
+            .and_then(|v| v.as_u64())
+            .map(|l| l.clamp(1, 6) as usize)
+            .unwrap_or(2)
+    }
+
+    pub fn is_ordered_list(&self) -> bool {
+        self.str("style") == Some("ordered")
+    }
+
+    /// Image URL from either `file.url` (image tool) or a top-level `url`.
+    pub fn image_url(&self) -> Option<&'a str> {
+        self.nested_str("file", "url").or_else(|| self.str("url"))
+    }
+}
+
+/// A list item in either the flat (`["a", "b"]`) or nested
+/// (`[{ "content": "a", "items": [...] }]`) Editor.js list shape.
+pub(crate) struct ListItem<'a> {
+    pub content: &'a str,
+    pub children: &'a [Value],
+}
+
+impl<'a> ListItem<'a> {
+    pub fn from_value(value: &'a Value) -> Option<Self> {
+        match value {
+            Value::String(s) => Some(Self {
+                content: s,
+                children: &[],
+            }),
+            Value::Object(obj) => Some(Self {
+                content: obj.get("content").and_then(|v| v.as_str()).unwrap_or(""),
+                children: obj
+                    .get("items")
+                    .and_then(|v| v.as_array())
+                    .map(|v| v.as_slice())
+                    .unwrap_or(&[]),
+            }),
+            _ => None,
+        }
+    }
+}
+
+pub(crate) fn table_cell(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Number(n) => n.to_string(),
+        Value::Bool(b) => b.to_string(),
+        _ => String::new(),
+    }
+}
+
+/// Only allow absolute http(s), mailto and root/fragment-relative URLs through.
+pub(crate) fn safe_url(url: &str) -> Option<&str> {
+    let trimmed = url.trim();
+    if trimmed.is_empty() {
+        return None;
+    }
+    let lower = trimmed.to_ascii_lowercase();
+    if lower.starts_with("http://")
+        || lower.starts_with("https://")
+        || lower.starts_with("mailto:")
+        || (trimmed.starts_with('/') && !trimmed.starts_with("//"))
+        || trimmed.starts_with('#')
+    {
+        Some(trimmed)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn doc() -> Value {
+        json!({
+            "time": 0,
+            "version": "2.30.7",
+            "blocks": [
+                { "type": "header", "data": { "text": "Hello <b>world</b>", "level": 2 } },
+                { "type": "paragraph", "data": { "text": "Read <a href=\"https://example.com\" onclick=\"x()\">this</a> &amp; <i>that</i><script>alert(1)</script>" } },
+                { "type": "list", "data": { "style": "ordered", "items": ["one", "two"] } },
+                { "type": "checklist", "data": { "items": [{ "text": "done", "checked": true }, { "text": "todo", "checked": false }] } },
+                { "type": "code", "data": { "code": "let a = \"<b>\";\n```" } },
+                { "type": "table", "data": { "withHeadings": true, "content": [["a", "b|c"], ["1", "2"]] } },
+                { "type": "image", "data": { "file": { "url": "javascript:alert(1)", "media_id": 1 }, "caption": "bad" } },
+                { "type": "raw", "data": { "html": "<div onclick=\"x()\">raw</div>" } },
+                { "type": "mystery", "data": {} }
+            ]
+        })
+    }
+
+    #[test]
+    fn html_is_sanitized() {
+        let html = to_html(&doc());
+        assert!(html.contains("<h2>Hello <b>world</b></h2>"));
+        assert!(html.contains("href=\"https://example.com\""));
+        assert!(!html.contains("onclick"));
+        assert!(!html.contains("<script"));
+        assert!(!html.contains("javascript:"));
+        assert!(html.contains("<ol><li>one</li><li>two</li></ol>"));
+        assert!(html.contains("&lt;b&gt;"));
+        assert!(html.contains("<thead><tr><th>a</th><th>b|c</th></tr></thead>"));
+    }
+
+    #[test]
+    fn markdown_output() {
+        let md = to_markdown(&doc());
+        assert!(md.starts_with("## Hello **world**\n\n"));
+        assert!(md.contains("Read [this](https://example.com) & *that*"));
+        assert!(md.contains("1. one\n2. two"));
+        assert!(md.contains("- [x] done\n- [ ] todo"));
+        assert!(md.contains("````\nlet a = \"<b>\";\n```\n````"));
+        assert!(md.contains("| a | b\\|c |\n| --- | --- |\n| 1 | 2 |"));
+        assert!(!md.contains("javascript:"));
+    }
+
+    #[test]
+    fn plain_text_and_summary() {
+        let text = to_plain_text(&doc());
+        assert!(text.starts_with("Hello world\n\nRead this & that"));
+        assert!(!text.contains("alert(1)"));
+
+        let short = summary(&doc(), 15);
+        assert_eq!(short, "Hello world…");
+    }
+}
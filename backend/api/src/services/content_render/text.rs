@@ -0,0 +1,103 @@
+use serde_json::Value;
+
+use super::inline::{decode_entities, to_text};
+use super::{table_cell, Block, ListItem};
+
+pub fn render_blocks(blocks: &[Value]) -> String {
+    blocks
+        .iter()
+        .filter_map(Block::from_value)
+        .map(|block| render_block(&block))
+        .filter(|chunk| !chunk.trim().is_empty())
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+fn render_block(block: &Block) -> String {
+    match block.kind {
+        "paragraph" | "header" => to_text(block.str_or_empty("text")),
+        "list" => {
+            let mut out = Vec::new();
+            render_list(block.array("items"), block.is_ordered_list(), 0, &mut out);
+            out.join("\n")
+        }
+        "checklist" => block
+            .array("items")
+            .iter()
+            .map(|item| {
+                let text = item.get("text").and_then(|v| v.as_str()).unwrap_or("");
+                let checked = item
+                    .get("checked")
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(false);
+                format!("[{}] {}", if checked { "x" } else { " " }, to_text(text))
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+        "quote" => {
+            let text = to_text(block.str_or_empty("text"));
+            match block.str("caption").filter(|c| !c.trim().is_empty()) {
+                Some(caption) => format!("\"{}\" — {}", text, to_text(caption)),
+                None => format!("\"{}\"", text),
+            }
+        }
+        "code" => block.str_or_empty("code").to_string(),
+        "delimiter" => String::new(),
+        "image" | "embed" => to_text(block.str_or_empty("caption")),
+        "linktool" => block
+            .nested_str("meta", "title")
+            .or_else(|| block.str("link"))
+            .unwrap_or("")
+            .to_string(),
+        "attaches" => block
+            .nested_str("file", "name")
+            .or_else(|| block.str("title"))
+            .unwrap_or("")
+            .to_string(),
+        "raw" => to_text(block.str_or_empty("html")),
+        "table" => block
+            .array("content")
+            .iter()
+            .map(|row| {
+                row.as_array()
+                    .map(|cells| {
+                        cells
+                            .iter()
+                            .map(|c| to_text(&table_cell(c)))
+                            .collect::<Vec<_>>()
+                            .join("\t")
+                    })
+                    .unwrap_or_default()
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+        "warning" => format!(
+            "{}: {}",
+            to_text(block.str_or_empty("title")),
+            to_text(block.str_or_empty("message"))
+        ),
+        "alert" => to_text(block.str_or_empty("message")),
+        "button" => decode_entities(
+            block
+                .str("text")
+                .or_else(|| block.str("buttonText"))
+                .unwrap_or(""),
+        ),
+        _ => String::new(),
+    }
+}
+
+fn render_list(items: &[Value], ordered: bool, depth: usize, out: &mut Vec<String>) {
+    let indent = "  ".repeat(depth);
+    for (idx, item) in items.iter().filter_map(ListItem::from_value).enumerate() {
+        let marker = if ordered {
+            format!("{}.", idx + 1)
+        } else {
+            "-".to_string()
+        };
+        out.push(format!("{}{} {}", indent, marker, to_text(item.content)));
+        if !item.children.is_empty() {
+            render_list(item.children, ordered, depth + 1, out);
+        }
+    }
+}
@@ -0,0 +1,253 @@
+//! Job kinds run by the background workers.
+
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tracing::{info, warn};
+
+use crate::{
+    db::sea_models::{
+        job,
+        newsletter_subscriber::{
+            Column as SubscriberColumn, Entity as SubscriberEntity, SubscriberStatus,
+        },
+        post::{self, PostStatus, UpdatePost},
+        scheduled_post::{self, ScheduledPostStatus},
+    },
+    services::{
+        admin_events::{self, AdminEvent},
+        audit::AuditService,
+        mail,
+        route_blocker_service::RouteBlockerService,
+        settings, websub,
+    },
+    AppState,
+};
+
+use super::{queues, Job, JobError, JobResult};
+
+/// Mail a newsletter issue to every confirmed subscriber.
+///
+/// Only loading the subscribers is retried; a failed delivery is counted
+/// rather than failing the job, so nobody gets the issue twice.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct NewsletterSend {
+    pub subject: String,
+    pub text: String,
+    pub html: Option<String>,
+}
+
+#[async_trait::async_trait]
+impl Job for NewsletterSend {
+    const KIND: &'static str = "newsletter.send";
+    const QUEUE: &'static str = queues::MAIL;
+    const MAX_ATTEMPTS: i32 = 3;
+
+    async fn run(self, state: &AppState) -> JobResult {
+        let subscribers = SubscriberEntity::find()
+            .filter(SubscriberColumn::Status.eq(SubscriberStatus::Confirmed))
+            .all(&state.sea_db)
+            .await?;
+
+        let total = subscribers.len();
+        info!(count = total, subject = %self.subject, "Sending newsletter to subscribers");
+
+        let mut sent = 0u64;
+        let mut failed = 0u64;
+        for sub in subscribers {
+            match mail::send_newsletter_email(
+                &state.mailer,
+                &sub.email,
+                &self.subject,
+                self.html.as_deref(),
+                &self.text,
+            )
+            .await
+            {
+                Ok(_) => sent += 1,
+                Err(_) => failed += 1,
+            }
+        }
+
+        info!(sent, failed, total, "Newsletter send completed");
+        Ok(Some(
+            json!({ "sent": sent, "failed": failed, "total": total }),
+        ))
+    }
+}
+
+/// Write blocked routes from Postgres to Redis; enqueued on the
+/// `route_blocker.sync` schedule.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct RouteBlockerSync {}
+
+#[async_trait::async_trait]
+impl Job for RouteBlockerSync {
+    const KIND: &'static str = "route_blocker.sync";
+    const QUEUE: &'static str = queues::MAINTENANCE;
+    const MAX_ATTEMPTS: i32 = 3;
+
+    async fn run(self, state: &AppState) -> JobResult {
+        RouteBlockerService::initialize_redis_sync(state)
+            .await
+            .map_err(|err| JobError::new(err.to_string()))?;
+        Ok(None)
+    }
+}
+
+/// Delete audit log entries past `AUDIT_RETENTION_DAYS`.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct AuditPurge {}
+
+#[async_trait::async_trait]
+impl Job for AuditPurge {
+    const KIND: &'static str = "audit.purge";
+    const QUEUE: &'static str = queues::MAINTENANCE;
+
+    async fn run(self, state: &AppState) -> JobResult {
+        let deleted = AuditService::purge_expired(state).await?;
+        Ok(Some(json!({ "deleted": deleted })))
+    }
+}
+
+/// Delete succeeded and cancelled jobs past `JOB_RETENTION_DAYS`. Dead jobs
+/// stay until an admin retries them.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct JobPurge {}
+
+#[async_trait::async_trait]
+impl Job for JobPurge {
+    const KIND: &'static str = "jobs.purge";
+    const QUEUE: &'static str = queues::MAINTENANCE;
+
+    async fn run(self, state: &AppState) -> JobResult {
+        let days = settings::JOB_RETENTION_DAYS.int(state).await;
+        if days <= 0 {
+            return Ok(Some(json!({ "deleted": 0 })));
+        }
+        let cutoff = chrono::Utc::now() - chrono::Duration::days(days);
+        let deleted =
+            job::Entity::purge_finished_before(&state.sea_db, cutoff.fixed_offset()).await?;
+        Ok(Some(json!({ "deleted": deleted })))
+    }
+}
+
+/// Publish posts whose `scheduled_posts` entry has come due.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ScheduledPostPublish {}
+
+impl ScheduledPostPublish {
+    const BATCH: u64 = 50;
+}
+
+#[async_trait::async_trait]
+impl Job for ScheduledPostPublish {
+    const KIND: &'static str = "posts.publish_scheduled";
+    const QUEUE: &'static str = queues::MAINTENANCE;
+
+    async fn run(self, state: &AppState) -> JobResult {
+        let db = &state.sea_db;
+        let now = chrono::Utc::now().fixed_offset();
+        let due = scheduled_post::Entity::due_pending(db, now, Some(Self::BATCH)).await?;
+
+        let mut published = 0u64;
+        let mut failed = 0u64;
+        for entry in due {
+            // Claim it so an overlapping run doesn't publish it twice.
+            if !scheduled_post::Entity::set_status(
+                db,
+                entry.id,
+                ScheduledPostStatus::Pending,
+                ScheduledPostStatus::Published,
+            )
+            .await?
+            {
+                continue;
+            }
+
+            let Some(current) = post::Entity::find_by_id(entry.post_id).one(db).await? else {
+                warn!(
+                    schedule_id = entry.id,
+                    post_id = entry.post_id,
+                    "Scheduled post is gone"
+                );
+                scheduled_post::Entity::set_status(
+                    db,
+                    entry.id,
+                    ScheduledPostStatus::Published,
+                    ScheduledPostStatus::Failed,
+                )
+                .await?;
+                failed += 1;
+                continue;
+            };
+            if current.status == PostStatus::Published {
+                continue;
+            }
+
+            let update = UpdatePost {
+                title: None,
+                slug: None,
+                content: None,
+                excerpt: None,
+                featured_image_id: None,
+                status: Some(PostStatus::Published),
+                published_at: Some(current.published_at.unwrap_or(entry.publish_at)),
+                updated_at: now,
+                category_id: None,
+                view_count: None,
+                likes_count: None,
+                tag_ids: None,
+                visibility: None,
+                min_role: None,
+                password: None,
+                locale: None,
+                meta_title: None,
+                meta_description: None,
+                canonical_url: None,
+                noindex: None,
+                og_image_id: None,
+                og_image_variant: None,
+            };
+            let post = match post::Entity::update(db, entry.post_id, update).await {
+                Ok(Some(post)) => post,
+                outcome => {
+                    warn!(
+                        schedule_id = entry.id,
+                        post_id = entry.post_id,
+                        error = ?outcome.err(),
+                        "Scheduled publish failed"
+                    );
+                    scheduled_post::Entity::set_status(
+                        db,
+                        entry.id,
+                        ScheduledPostStatus::Published,
+                        ScheduledPostStatus::Failed,
+                    )
+                    .await?;
+                    failed += 1;
+                    continue;
+                }
+            };
+
+            info!(
+                post_id = post.id,
+                schedule_id = entry.id,
+                "Scheduled post published"
+            );
+            published += 1;
+            websub::notify_post_published(state, post.id);
+            admin_events::publish(
+                state,
+                AdminEvent::PostPublished {
+                    post_id: post.id,
+                    title: post.title,
+                    slug: post.slug,
+                },
+            )
+            .await;
+        }
+
+        Ok(Some(json!({ "published": published, "failed": failed })))
+    }
+}
@@ -0,0 +1,282 @@
+//! Background jobs.
+//!
+//! Work that shouldn't hold up a request goes through the `jobs` table
+//! rather than a bare `tokio::spawn`, so it survives restarts, is retried
+//! and shows up in `admin_job_v1`:
+//!
+//! ```ignore
+//! jobs::enqueue(&state, jobs::job(&NewsletterSend { .. }).created_by(user.id)).await?;
+//! ```
+//!
+//! A job type implements [`Job`] and is listed in [`HANDLERS`]. Each instance
+//! works on the queues in `JOB_QUEUES`, claiming due jobs with
+//! `FOR UPDATE SKIP LOCKED` up to the queue's concurrency. Failed attempts
+//! are retried with exponential backoff until `max_attempts`, then the job
+//! is `dead` until an admin retries it. Recurring jobs are rows in
+//! `job_schedules`, enqueued when their cron expression comes due.
+
+pub mod handlers;
+pub mod runner;
+
+use std::{fmt, future::Future, pin::Pin, time::Duration};
+
+use chrono::{DateTime, Utc};
+use croner::Cron;
+use rand::Rng;
+use sea_orm::prelude::DateTimeWithTimeZone;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use tokio::sync::Notify;
+
+use crate::{
+    db::sea_models::job::{self, NewJob},
+    error::DbResult,
+    AppState,
+};
+
+pub use runner::{notify_enqueued, run_schedule_now, schedule, start, JobRunner};
+
+/// Queue names; `JOB_QUEUES` sets which ones an instance works on.
+pub mod queues {
+    pub const DEFAULT: &str = "default";
+    pub const MAIL: &str = "mail";
+    pub const MAINTENANCE: &str = "maintenance";
+    pub const WEBHOOKS: &str = crate::db::sea_models::webhook_delivery::DELIVER_JOB_QUEUE;
+}
+
+const BACKOFF_BASE: Duration = Duration::from_secs(10);
+const BACKOFF_MAX: Duration = Duration::from_secs(60 * 60);
+
+/// Wakes this instance's workers when a job is enqueued locally.
+static ENQUEUED: Notify = Notify::const_new();
+
+/// Why an attempt failed.
+#[derive(Debug)]
+pub struct JobError {
+    pub message: String,
+    /// Retrying won't help; the job goes straight to `dead`.
+    pub permanent: bool,
+}
+
+impl JobError {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            permanent: false,
+        }
+    }
+
+    pub fn permanent(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            permanent: true,
+        }
+    }
+}
+
+impl fmt::Display for JobError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl From<sea_orm::DbErr> for JobError {
+    fn from(err: sea_orm::DbErr) -> Self {
+        Self::new(err.to_string())
+    }
+}
+
+impl From<crate::error::ErrorResponse> for JobError {
+    fn from(err: crate::error::ErrorResponse) -> Self {
+        Self::new(err.to_string())
+    }
+}
+
+/// What a successful attempt returns; stored as the job's `result`.
+pub type JobResult = Result<Option<Value>, JobError>;
+
+/// A kind of background job. The struct is the payload.
+#[async_trait::async_trait]
+pub trait Job: Serialize + DeserializeOwned + Send + Sized + 'static {
+    const KIND: &'static str;
+    const QUEUE: &'static str = queues::DEFAULT;
+    const MAX_ATTEMPTS: i32 = 5;
+
+    async fn run(self, state: &AppState) -> JobResult;
+}
+
+type BoxedRun = fn(AppState, Value) -> Pin<Box<dyn Future<Output = JobResult> + Send>>;
+
+/// A [`Job`] with its payload type erased, looked up by kind.
+#[derive(Clone, Copy)]
+pub struct Handler {
+    pub kind: &'static str,
+    pub queue: &'static str,
+    pub max_attempts: i32,
+    run: BoxedRun,
+}
+
+impl Handler {
+    pub const fn of<J: Job>() -> Self {
+        Self {
+            kind: J::KIND,
+            queue: J::QUEUE,
+            max_attempts: J::MAX_ATTEMPTS,
+            run: run_erased::<J>,
+        }
+    }
+
+    pub async fn run(&self, state: AppState, payload: Value) -> JobResult {
+        (self.run)(state, payload).await
+    }
+}
+
+fn run_erased<J: Job>(
+    state: AppState,
+    payload: Value,
+) -> Pin<Box<dyn Future<Output = JobResult> + Send>> {
+    Box::pin(async move {
+        let job: J = serde_json::from_value(payload)
+            .map_err(|err| JobError::permanent(format!("Invalid payload: {}", err)))?;
+        job.run(&state).await
+    })
+}
+
+/// Every job kind this build can run.
+pub const HANDLERS: &[Handler] = &[
+    Handler::of::<handlers::NewsletterSend>(),
+    Handler::of::<handlers::RouteBlockerSync>(),
+    Handler::of::<handlers::AuditPurge>(),
+    Handler::of::<handlers::JobPurge>(),
+    Handler::of::<handlers::ScheduledPostPublish>(),
+    Handler::of::<crate::services::webhooks::WebhookDeliver>(),
+    Handler::of::<crate::services::notifications::NotificationDigest>(),
+    Handler::of::<crate::services::backup::BackupExport>(),
+    Handler::of::<crate::services::backup::BackupRestore>(),
+    Handler::of::<crate::services::backup::BackupSchedule>(),
+    Handler::of::<crate::services::blog_import::BlogImportRun>(),
+];
+
+pub fn handler(kind: &str) -> Option<&'static Handler> {
+    HANDLERS.iter().find(|handler| handler.kind == kind)
+}
+
+/// New job for `payload`, on its type's queue, to run now.
+pub fn job<J: Job>(payload: &J) -> NewJob {
+    NewJob {
+        queue: J::QUEUE.to_string(),
+        kind: J::KIND.to_string(),
+        payload: serde_json::to_value(payload).unwrap_or(Value::Null),
+        max_attempts: J::MAX_ATTEMPTS,
+        unique_key: None,
+        run_at: None,
+        created_by: None,
+    }
+}
+
+/// Insert the job; `None` when an active job already holds its unique key.
+pub async fn enqueue(state: &AppState, new_job: NewJob) -> DbResult<Option<job::Model>> {
+    let queued = job::Entity::enqueue(&state.sea_db, new_job).await?;
+    runner::notify_enqueued(queued.as_ref());
+    Ok(queued)
+}
+
+/// Delay before the attempt after `attempts` failures: doubling from
+/// `BACKOFF_BASE`, capped at `BACKOFF_MAX`.
+pub fn backoff(attempts: i32) -> Duration {
+    let exponent = attempts.saturating_sub(1).clamp(0, 16) as u32;
+    BACKOFF_BASE
+        .saturating_mul(2u32.saturating_pow(exponent))
+        .min(BACKOFF_MAX)
+}
+
+/// [`backoff`] plus up to 10% jitter so failed batches don't retry in step.
+pub fn retry_delay(attempts: i32) -> Duration {
+    let delay = backoff(attempts);
+    let jitter = rand::rng().random_range(0..=delay.as_millis() as u64 / 10);
+    delay + Duration::from_millis(jitter)
+}
+
+/// `queue:concurrency` pairs from `JOB_QUEUES`; a missing or invalid
+/// concurrency counts as 1.
+pub fn parse_queues(entries: &[String]) -> Vec<(String, usize)> {
+    let mut queues: Vec<(String, usize)> = Vec::new();
+    for entry in entries {
+        let (name, concurrency) = match entry.split_once(':') {
+            Some((name, concurrency)) => (name.trim(), concurrency.trim().parse().unwrap_or(1)),
+            None => (entry.trim(), 1),
+        };
+        if name.is_empty() || queues.iter().any(|(queue, _)| queue == name) {
+            continue;
+        }
+        queues.push((name.to_string(), concurrency.max(1)));
+    }
+    queues
+}
+
+/// Next time `cron` fires strictly after `after`. Accepts 5-field
+/// expressions and an optional leading seconds field.
+pub fn next_run(cron: &str, after: DateTime<Utc>) -> Result<DateTime<Utc>, String> {
+    let cron = Cron::new(cron)
+        .with_seconds_optional()
+        .parse()
+        .map_err(|err| err.to_string())?;
+    cron.find_next_occurrence(&after, false)
+        .map_err(|err| err.to_string())
+}
+
+/// Next time `cron` fires after now.
+pub fn next_run_at(cron: &str) -> Result<DateTimeWithTimeZone, String> {
+    next_run(cron, Utc::now()).map(|next| next.fixed_offset())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn next_run_follows_cron() {
+        let after = DateTime::parse_from_rfc3339("2025-01-01T10:30:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let next = next_run("0 3 * * *", after).unwrap();
+        assert_eq!(next.to_rfc3339(), "2025-01-02T03:00:00+00:00");
+        assert!(next_run("not a cron", after).is_err());
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_the_cap() {
+        assert_eq!(backoff(1), Duration::from_secs(10));
+        assert_eq!(backoff(2), Duration::from_secs(20));
+        assert_eq!(backoff(4), Duration::from_secs(80));
+        assert_eq!(backoff(12), BACKOFF_MAX);
+        assert_eq!(backoff(i32::MAX), BACKOFF_MAX);
+
+        let delay = retry_delay(3);
+        assert!(delay >= backoff(3) && delay <= backoff(3) + backoff(3) / 10);
+    }
+
+    #[test]
+    fn queues_are_parsed_with_their_concurrency() {
+        let entries: Vec<String> = ["default:4", "mail", "bulk:x", "default:9", " :2"]
+            .map(String::from)
+            .into();
+        assert_eq!(
+            parse_queues(&entries),
+            vec![
+                ("default".to_string(), 4),
+                ("mail".to_string(), 1),
+                ("bulk".to_string(), 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn handler_kinds_are_unique() {
+        let mut kinds: Vec<&str> = HANDLERS.iter().map(|handler| handler.kind).collect();
+        kinds.sort();
+        kinds.dedup();
+        assert_eq!(kinds.len(), HANDLERS.len());
+        assert!(handler("newsletter.send").is_some());
+    }
+}
@@ -0,0 +1,560 @@
+//! Queue workers, the cron scheduler and shutdown draining.
+
+use std::{
+    future::Future,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use chrono::Utc;
+use opentelemetry::KeyValue;
+use sea_orm::{prelude::DateTimeWithTimeZone, ConnectionTrait};
+use serde_json::json;
+use tokio::{
+    sync::{watch, Semaphore},
+    task::JoinHandle,
+};
+use tracing::{error, info, warn, Instrument};
+
+use crate::{
+    db::sea_models::{
+        job::{self, NewJob},
+        job_schedule::{self, NewJobSchedule},
+    },
+    error::DbResult,
+    services::{
+        admin_events::{self, AdminEvent},
+        route_blocker_config, settings,
+    },
+    utils::telemetry,
+    AppState,
+};
+
+use super::{handler, handlers, next_run_at, parse_queues, retry_delay, Job, JobError, ENQUEUED};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+const SCHEDULER_TICK: Duration = Duration::from_secs(15);
+
+/// Seconds a running job may go without a heartbeat before it is assumed
+/// lost with its worker and requeued.
+fn stale_after_secs() -> i64 {
+    settings::JOB_STALE_AFTER_SECS.env_int().max(60)
+}
+
+/// Heartbeats land several times within the stale window, so one slow
+/// database round trip doesn't get a healthy job requeued.
+fn heartbeat_every(stale_after_secs: i64) -> Duration {
+    Duration::from_secs((stale_after_secs / 4).max(1) as u64)
+}
+
+/// Schedules registered at startup. Admins can change their cron or
+/// disable them; removing one here leaves its row in place.
+fn builtin_schedules() -> Vec<NewJobSchedule> {
+    vec![
+        schedule("audit.purge", "0 15 */6 * * *", &handlers::AuditPurge {}),
+        schedule("jobs.purge", "0 45 3 * * *", &handlers::JobPurge {}),
+        schedule(
+            "posts.publish_scheduled",
+            "0 * * * * *",
+            &handlers::ScheduledPostPublish {},
+        ),
+        schedule(
+            "notifications.digest",
+            "0 5 * * * *",
+            &crate::services::notifications::NotificationDigest {},
+        ),
+        schedule(
+            route_blocker_config::SYNC_SCHEDULE,
+            &route_blocker_config::interval_cron(
+                settings::ROUTE_BLOCKER_SYNC_INTERVAL_SECS.env_int().max(0) as u64,
+            )
+            .0,
+            &handlers::RouteBlockerSync {},
+        ),
+    ]
+}
+
+/// Schedule running `payload` on `cron`, on its type's queue.
+pub fn schedule<J: Job>(name: &str, cron: &str, payload: &J) -> NewJobSchedule {
+    NewJobSchedule {
+        name: name.to_string(),
+        kind: J::KIND.to_string(),
+        queue: J::QUEUE.to_string(),
+        payload: serde_json::to_value(payload).unwrap_or_else(|_| json!({})),
+        cron: cron.to_string(),
+    }
+}
+
+fn labels(kind: &str, queue: &str) -> [KeyValue; 2] {
+    [
+        KeyValue::new("job.kind", kind.to_string()),
+        KeyValue::new("job.queue", queue.to_string()),
+    ]
+}
+
+/// Handle to this instance's workers; see [`start`].
+pub struct JobRunner {
+    state: AppState,
+    worker_id: String,
+    shutdown: watch::Sender<bool>,
+    tasks: Vec<JoinHandle<()>>,
+}
+
+/// Start a worker per queue in `JOB_QUEUES` and the schedule loop.
+pub fn start(state: AppState) -> JobRunner {
+    let host = std::env::var("HOSTNAME").unwrap_or_else(|_| "local".to_string());
+    let worker_id = format!(
+        "{}-{}",
+        host,
+        &uuid::Uuid::new_v4().simple().to_string()[..8]
+    );
+    let grace = Duration::from_secs(settings::JOB_SHUTDOWN_GRACE_SECS.env_int().max(0) as u64);
+    let (shutdown, stop) = watch::channel(false);
+
+    let queues = parse_queues(&settings::parse_list(&settings::JOB_QUEUES.env_string()));
+    let mut tasks = Vec::with_capacity(queues.len() + 1);
+    for (queue, concurrency) in queues {
+        info!(queue = %queue, concurrency, worker = %worker_id, "Starting job worker");
+        tasks.push(tokio::spawn(work(
+            state.clone(),
+            worker_id.clone(),
+            queue,
+            concurrency,
+            grace,
+            stop.clone(),
+        )));
+    }
+    tasks.push(tokio::spawn(run_schedules(state.clone(), stop)));
+
+    JobRunner {
+        state,
+        worker_id,
+        shutdown,
+        tasks,
+    }
+}
+
+impl JobRunner {
+    /// Stop claiming jobs and wait up to `JOB_SHUTDOWN_GRACE_SECS` for the
+    /// running ones. Jobs still running after that are stopped, then go back
+    /// to the queue.
+    pub async fn shutdown(self) {
+        info!(worker = %self.worker_id, "Draining background jobs");
+        let _ = self.shutdown.send(true);
+        for task in self.tasks {
+            let _ = task.await;
+        }
+
+        match job::Entity::release(&self.state.sea_db, Some(&self.worker_id), None).await {
+            Ok(0) => info!("Background jobs drained"),
+            Ok(count) => warn!(count, "Requeued jobs still running at shutdown"),
+            Err(err) => error!(error = %err, "Failed to requeue unfinished jobs"),
+        }
+    }
+}
+
+async fn work(
+    state: AppState,
+    worker: String,
+    queue: String,
+    concurrency: usize,
+    grace: Duration,
+    mut stop: watch::Receiver<bool>,
+) {
+    let permits = Arc::new(Semaphore::new(concurrency));
+    let heartbeat = heartbeat_every(stale_after_secs());
+    let (kill, killed) = watch::channel(false);
+
+    loop {
+        if *stop.borrow() {
+            break;
+        }
+        let permit = tokio::select! {
+            permit = permits.clone().acquire_owned() => match permit {
+                Ok(permit) => permit,
+                Err(_) => break,
+            },
+            _ = stop.changed() => break,
+        };
+
+        match job::Entity::claim(&state.sea_db, &queue, &worker).await {
+            Ok(Some(job)) => {
+                let state = state.clone();
+                let worker = worker.clone();
+                let killed = killed.clone();
+                tokio::spawn(async move {
+                    execute(&state, &worker, job, heartbeat, killed).await;
+                    drop(permit);
+                });
+            }
+            Ok(None) => {
+                drop(permit);
+                tokio::select! {
+                    _ = tokio::time::sleep(POLL_INTERVAL) => {},
+                    _ = ENQUEUED.notified() => {},
+                    _ = stop.changed() => break,
+                }
+            }
+            Err(err) => {
+                drop(permit);
+                warn!(queue = %queue, error = %err, "Failed to claim job");
+                tokio::select! {
+                    _ = tokio::time::sleep(POLL_INTERVAL) => {},
+                    _ = stop.changed() => break,
+                }
+            }
+        }
+    }
+
+    // Every permit back means every job on this queue has finished.
+    let drained = tokio::time::timeout(grace, permits.acquire_many(concurrency as u32)).await;
+    if !matches!(drained, Ok(Ok(_))) {
+        // Stop the stragglers before `JobRunner::shutdown` requeues them, so
+        // they can't keep running next to their retry.
+        warn!(queue = %queue, "Jobs still running after the shutdown grace period; stopping them");
+        let _ = kill.send(true);
+        let _ = permits.acquire_many(concurrency as u32).await;
+    }
+}
+
+/// How a supervised job run ended.
+#[derive(Debug)]
+enum Supervised<T> {
+    Finished(T),
+    Panicked(String),
+    /// Another worker took the job over, or it was released as stale.
+    LockLost,
+    /// Shut down after the grace period.
+    Killed,
+}
+
+/// Wait for `run`, calling `beat` every `every` to keep the job's lock. The
+/// run is aborted once `beat` reports the job is no longer ours or `kill`
+/// fires; either way it has stopped when this returns.
+async fn supervise<T, F, Fut>(
+    mut run: JoinHandle<T>,
+    every: Duration,
+    mut beat: F,
+    mut kill: watch::Receiver<bool>,
+) -> Supervised<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = bool>,
+{
+    let mut ticker = tokio::time::interval_at(tokio::time::Instant::now() + every, every);
+    let ended = loop {
+        if *kill.borrow() {
+            break Supervised::Killed;
+        }
+        tokio::select! {
+            joined = &mut run => {
+                return match joined {
+                    Ok(value) => Supervised::Finished(value),
+                    Err(err) => Supervised::Panicked(err.to_string()),
+                };
+            }
+            _ = ticker.tick() => {
+                if !beat().await {
+                    break Supervised::LockLost;
+                }
+            }
+            Ok(()) = kill.changed() => {}
+        }
+    };
+    run.abort();
+    let _ = run.await;
+    ended
+}
+
+async fn execute(
+    state: &AppState,
+    worker: &str,
+    job: job::Model,
+    heartbeat: Duration,
+    kill: watch::Receiver<bool>,
+) {
+    let metrics = telemetry::job_metrics();
+    let labels = labels(&job.kind, &job.queue);
+    let span = tracing::info_span!(
+        "job",
+        job_id = job.id,
+        kind = %job.kind,
+        queue = %job.queue,
+        attempt = job.attempts
+    );
+
+    let started = Instant::now();
+    let outcome = match handler(&job.kind) {
+        // Run on its own task so a panic fails the attempt instead of
+        // leaving the job locked, and so it can be stopped.
+        Some(handler) => {
+            let run = handler
+                .run(state.clone(), job.payload.clone())
+                .instrument(span.clone());
+            let beat = || async {
+                match job::Entity::heartbeat(&state.sea_db, job.id, worker).await {
+                    Ok(held) => held,
+                    Err(err) => {
+                        warn!(job_id = job.id, error = %err, "Failed to refresh job lock");
+                        true
+                    }
+                }
+            };
+            match supervise(tokio::spawn(run), heartbeat, beat, kill).await {
+                Supervised::Finished(outcome) => outcome,
+                Supervised::Panicked(err) => Err(JobError::new(format!("Job panicked: {}", err))),
+                Supervised::LockLost => {
+                    let _guard = span.enter();
+                    warn!("Job lock lost to another worker; stopped this run");
+                    return;
+                }
+                Supervised::Killed => {
+                    let _guard = span.enter();
+                    warn!("Job stopped at shutdown; it will be requeued");
+                    return;
+                }
+            }
+        }
+        None => Err(JobError::permanent(format!(
+            "No handler for job kind {}",
+            job.kind
+        ))),
+    };
+    metrics
+        .duration
+        .record(started.elapsed().as_secs_f64() * 1000.0, &labels);
+
+    let _guard = span.enter();
+    let saved = match outcome {
+        Ok(result) => {
+            metrics.succeeded.add(1, &labels);
+            info!("Job succeeded");
+            job::Entity::complete(&state.sea_db, job.id, worker, result).await
+        }
+        Err(err) if !err.permanent && job.attempts < job.max_attempts => {
+            let delay = retry_delay(job.attempts);
+            metrics.retried.add(1, &labels);
+            warn!(error = %err, retry_in_secs = delay.as_secs(), "Job failed; will retry");
+            let retry_at = Utc::now() + chrono::Duration::from_std(delay).unwrap_or_default();
+            job::Entity::fail(
+                &state.sea_db,
+                job.id,
+                worker,
+                &err.message,
+                Some(retry_at.fixed_offset()),
+            )
+            .await
+        }
+        Err(err) => {
+            metrics.dead.add(1, &labels);
+            error!(error = %err, "Job failed for good");
+            admin_events::spawn_publish(
+                state,
+                AdminEvent::JobFailed {
+                    job_id: job.id,
+                    kind: job.kind.clone(),
+                    queue: job.queue.clone(),
+                    attempts: job.attempts,
+                    error: err.message.clone(),
+                },
+            );
+            job::Entity::fail(&state.sea_db, job.id, worker, &err.message, None).await
+        }
+    };
+    if let Err(err) = saved {
+        error!(error = %err, "Failed to record job outcome");
+    }
+}
+
+/// Register the built-in schedules, then enqueue schedules as they come
+/// due and requeue jobs whose worker went away.
+async fn run_schedules(state: AppState, mut stop: watch::Receiver<bool>) {
+    for schedule in builtin_schedules() {
+        let next = match next_run_at(&schedule.cron) {
+            Ok(next) => next,
+            Err(err) => {
+                error!(name = %schedule.name, error = %err, "Invalid built-in job schedule");
+                continue;
+            }
+        };
+        if let Err(err) = job_schedule::Entity::ensure(&state.sea_db, schedule, next).await {
+            error!(error = %err, "Failed to register job schedule");
+        }
+    }
+
+    let stale_after = stale_after_secs();
+    loop {
+        let cutoff = Utc::now() - chrono::Duration::seconds(stale_after);
+        match job::Entity::release(&state.sea_db, None, Some(cutoff.fixed_offset())).await {
+            Ok(0) => {}
+            Ok(count) => warn!(count, "Requeued jobs left running by a lost worker"),
+            Err(err) => warn!(error = %err, "Failed to requeue stale jobs"),
+        }
+
+        loop {
+            match run_due_schedule(&state).await {
+                Ok(true) => continue,
+                Ok(false) => break,
+                Err(err) => {
+                    warn!(error = %err, "Failed to run job schedule");
+                    break;
+                }
+            }
+        }
+
+        tokio::select! {
+            _ = tokio::time::sleep(SCHEDULER_TICK) => {},
+            _ = stop.changed() => break,
+        }
+    }
+}
+
+/// Enqueue one due schedule, if any; `false` when none was due.
+async fn run_due_schedule(state: &AppState) -> DbResult<bool> {
+    let Some((txn, schedule)) = job_schedule::Entity::claim_due(&state.sea_db).await? else {
+        return Ok(false);
+    };
+
+    let next = match next_run_at(&schedule.cron) {
+        Ok(next) => Some(next),
+        Err(err) => {
+            warn!(name = %schedule.name, cron = %schedule.cron, error = %err, "Invalid job schedule; pausing it");
+            None
+        }
+    };
+    let queued = enqueue_schedule(&txn, schedule, next).await?;
+    txn.commit().await?;
+    notify_enqueued(queued.as_ref());
+    Ok(true)
+}
+
+/// Enqueue a schedule's job outside its cron, keeping the next run as is.
+pub async fn run_schedule_now(
+    state: &AppState,
+    schedule: job_schedule::Model,
+) -> DbResult<Option<job::Model>> {
+    let next = schedule.next_run_at;
+    let queued = enqueue_schedule(&state.sea_db, schedule, next).await?;
+    notify_enqueued(queued.as_ref());
+    Ok(queued)
+}
+
+/// Skipped while the schedule's previous job is still pending or running.
+async fn enqueue_schedule<C: ConnectionTrait>(
+    conn: &C,
+    schedule: job_schedule::Model,
+    next_run_at: Option<DateTimeWithTimeZone>,
+) -> DbResult<Option<job::Model>> {
+    let max_attempts = handler(&schedule.kind)
+        .map(|handler| handler.max_attempts)
+        .unwrap_or(1);
+    let new_job = NewJob {
+        queue: schedule.queue.clone(),
+        kind: schedule.kind.clone(),
+        payload: schedule.payload.clone(),
+        max_attempts,
+        unique_key: Some(format!("schedule:{}", schedule.name)),
+        run_at: None,
+        created_by: None,
+    };
+    let queued = job::Entity::enqueue(conn, new_job).await?;
+    if queued.is_none() {
+        info!(name = %schedule.name, "Previous scheduled run still queued; skipping");
+    }
+    job_schedule::Entity::mark_run(
+        conn,
+        schedule,
+        queued.as_ref().map(|job| job.id),
+        next_run_at,
+    )
+    .await?;
+    Ok(queued)
+}
+
+/// Wake the workers for a job enqueued on a transaction, once it committed.
+pub fn notify_enqueued(queued: Option<&job::Model>) {
+    if let Some(job) = queued {
+        telemetry::job_metrics()
+            .enqueued
+            .add(1, &labels(&job.kind, &job.queue));
+        ENQUEUED.notify_waiters();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TICK: Duration = Duration::from_millis(10);
+
+    /// A dropped sender never fires.
+    fn never_killed() -> watch::Receiver<bool> {
+        watch::channel(false).1
+    }
+
+    #[test]
+    fn builtin_schedules_parse_and_have_unique_names() {
+        let schedules = builtin_schedules();
+        for schedule in &schedules {
+            assert!(next_run_at(&schedule.cron).is_ok(), "{}", schedule.name);
+            assert!(handler(&schedule.kind).is_some(), "{}", schedule.kind);
+        }
+        let mut names: Vec<_> = schedules.iter().map(|schedule| &schedule.name).collect();
+        names.sort();
+        names.dedup();
+        assert_eq!(names.len(), schedules.len());
+    }
+
+    #[test]
+    fn heartbeats_fit_several_times_in_the_stale_window() {
+        assert_eq!(heartbeat_every(3600), Duration::from_secs(900));
+        assert_eq!(heartbeat_every(60), Duration::from_secs(15));
+        assert_eq!(heartbeat_every(0), Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn supervised_run_finishes_with_its_heartbeat_held() {
+        let run = tokio::spawn(async {
+            tokio::time::sleep(TICK * 5).await;
+            7
+        });
+        let ended = supervise(run, TICK, || async { true }, never_killed()).await;
+        assert!(matches!(ended, Supervised::Finished(7)));
+    }
+
+    #[tokio::test]
+    async fn supervised_run_reports_a_panic() {
+        let run = tokio::spawn(async { panic!("boom") });
+        let ended: Supervised<()> = supervise(run, TICK, || async { true }, never_killed()).await;
+        assert!(matches!(ended, Supervised::Panicked(_)));
+    }
+
+    #[tokio::test]
+    async fn supervised_run_stops_when_the_lock_is_lost() {
+        let (alive, gone) = tokio::sync::oneshot::channel::<()>();
+        let run = tokio::spawn(async move {
+            let _alive = alive;
+            std::future::pending::<()>().await
+        });
+        let ended = supervise(run, TICK, || async { false }, never_killed()).await;
+        assert!(matches!(ended, Supervised::LockLost));
+        assert!(gone.await.is_err(), "run should have been dropped");
+    }
+
+    #[tokio::test]
+    async fn supervised_run_stops_when_killed() {
+        let (alive, gone) = tokio::sync::oneshot::channel::<()>();
+        let run = tokio::spawn(async move {
+            let _alive = alive;
+            std::future::pending::<()>().await
+        });
+        let (kill, killed) = watch::channel(false);
+        tokio::spawn(async move {
+            tokio::time::sleep(TICK).await;
+            let _ = kill.send(true);
+        });
+        let ended = supervise(run, Duration::from_secs(60), || async { true }, killed).await;
+        assert!(matches!(ended, Supervised::Killed));
+        assert!(gone.await.is_err(), "run should have been dropped");
+    }
+}
@@ -55,3 +55,122 @@ pub fn email_otp_html(code: &str) -> String {
     </html>
     "#, code).to_string()
 }
+
+/// Editorial status change. All arguments are plain text and escaped here.
+pub fn post_status_html(headline: &str, post_title: &str, note: Option<&str>) -> String {
+    use crate::services::content_render::escape_html;
+
+    let note_html = match note {
+        Some(note) if !note.trim().is_empty() => format!(
+            r#"<div style="color:#868686;font-size:16px;font-weight:normal;text-align:left;padding:16px 24px 16px 24px;white-space:pre-wrap">{}</div>"#,
+            escape_html(note)
+        ),
+        _ => String::new(),
+    };
+
+    format!(
+        r#"
+    <!DOCTYPE html>
+    <html lang="en">
+      <body>
+        <div
+          style='background-color:#000000;color:#FFFFFF;font-family:"Iowan Old Style", "Palatino Linotype", "URW Palladio L", P052, serif;font-size:16px;font-weight:400;letter-spacing:0.15008px;line-height:1.5;margin:0;padding:32px 0;min-height:100%;width:100%'
+        >
+          <table
+            align="center"
+            width="100%"
+            style="margin:0 auto;max-width:600px;background-color:#000000"
+            role="presentation"
+            cellspacing="0"
+            cellpadding="0"
+            border="0"
+          >
+            <tbody>
+              <tr style="width:100%">
+                <td>
+                  <div
+                    style="color:#ffffff;font-size:16px;font-weight:normal;text-align:center;padding:16px 24px 16px 24px"
+                  >
+                    {}
+                  </div>
+                  <h1
+                    style="font-weight:bold;text-align:center;margin:0;font-size:24px;padding:16px 24px 16px 24px"
+                  >
+                    {}
+                  </h1>
+                  {}
+                </td>
+              </tr>
+            </tbody>
+          </table>
+        </div>
+      </body>
+    </html>
+    "#,
+        escape_html(headline),
+        escape_html(post_title),
+        note_html
+    )
+}
+
+/// Digest of unread notifications; each item is a line of text and an
+/// optional link.
+pub fn notification_digest_html(headline: &str, items: &[(String, Option<String>)]) -> String {
+    use crate::services::content_render::escape_html;
+
+    let items_html: String = items
+        .iter()
+        .map(|(text, link)| {
+            let text = match link {
+                Some(link) => format!(
+                    r#"<a href="{}" style="color:#ffffff">{}</a>"#,
+                    escape_html(link),
+                    escape_html(text)
+                ),
+                None => escape_html(text),
+            };
+            format!(
+                r#"<div style="color:#ffffff;font-size:16px;font-weight:normal;text-align:left;padding:8px 24px 8px 24px">{}</div>"#,
+                text
+            )
+        })
+        .collect();
+
+    format!(
+        r#"
+    <!DOCTYPE html>
+    <html lang="en">
+      <body>
+        <div
+          style='background-color:#000000;color:#FFFFFF;font-family:"Iowan Old Style", "Palatino Linotype", "URW Palladio L", P052, serif;font-size:16px;font-weight:400;letter-spacing:0.15008px;line-height:1.5;margin:0;padding:32px 0;min-height:100%;width:100%'
+        >
+          <table
+            align="center"
+            width="100%"
+            style="margin:0 auto;max-width:600px;background-color:#000000"
+            role="presentation"
+            cellspacing="0"
+            cellpadding="0"
+            border="0"
+          >
+            <tbody>
+              <tr style="width:100%">
+                <td>
+                  <h1
+                    style="font-weight:bold;text-align:center;margin:0;font-size:24px;padding:16px 24px 16px 24px"
+                  >
+                    {}
+                  </h1>
+                  {}
+                </td>
+              </tr>
+            </tbody>
+          </table>
+        </div>
+      </body>
+    </html>
+    "#,
+        escape_html(headline),
+        items_html
+    )
+}
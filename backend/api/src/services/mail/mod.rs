@@ -100,3 +100,63 @@ pub async fn send_forgot_password_email(
 
     send_email(mailer, email, &no_reply, subject, body).await
 }
+
+#[instrument(
+    skip(mailer, headline, post_title, note),
+    fields(email_type = "post_status")
+)]
+pub async fn send_post_status_email(
+    mailer: &AsyncSmtpTransport<lettre::Tokio1Executor>,
+    email: &str,
+    headline: &str,
+    post_title: &str,
+    note: Option<&str>,
+) -> Result<(), String> {
+    info!(to = %email, "Sending post status email");
+
+    let no_reply = format!("No reply <no-reply@{}>", DOMAIN);
+    let subject = format!("{}: {}", headline, post_title);
+    let body = html_templates::post_status_html(headline, post_title, note);
+
+    send_email(mailer, email, &no_reply, &subject, body).await
+}
+
+/// Digest of unread notifications, as `(text, link)` items.
+#[instrument(skip(mailer, items), fields(email_type = "notification_digest"))]
+pub async fn send_notification_digest_email(
+    mailer: &AsyncSmtpTransport<lettre::Tokio1Executor>,
+    email: &str,
+    headline: &str,
+    items: &[(String, Option<String>)],
+) -> Result<(), String> {
+    info!(to = %email, count = items.len(), "Sending notification digest");
+
+    let no_reply = format!("No reply <no-reply@{}>", DOMAIN);
+    let body = html_templates::notification_digest_html(headline, items);
+
+    send_email(mailer, email, &no_reply, headline, body).await
+}
+
+/// Newsletter issue; plain text is escaped and sent as preformatted HTML
+/// when there's no HTML body.
+#[instrument(skip(mailer, html, text), fields(email_type = "newsletter"))]
+pub async fn send_newsletter_email(
+    mailer: &AsyncSmtpTransport<lettre::Tokio1Executor>,
+    email: &str,
+    subject: &str,
+    html: Option<&str>,
+    text: &str,
+) -> Result<(), String> {
+    use crate::services::content_render::escape_html;
+
+    let no_reply = format!("No reply <no-reply@{}>", DOMAIN);
+    let body = match html {
+        Some(html) => html.to_string(),
+        None => format!(
+            r#"<div style="white-space:pre-wrap">{}</div>"#,
+            escape_html(text)
+        ),
+    };
+
+    send_email(mailer, email, &no_reply, subject, body).await
+}
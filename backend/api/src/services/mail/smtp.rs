@@ -1,13 +1,19 @@
-use std::env;
-
 use lettre::{transport::smtp::authentication::Credentials, AsyncSmtpTransport, Tokio1Executor};
 use tracing::{info, instrument};
 
+use crate::services::settings;
+
 #[instrument(name = "smtp_connection_init")]
 pub async fn create_connection() -> AsyncSmtpTransport<Tokio1Executor> {
-    let host = env::var("SMTP_HOST").expect("SMTP_HOST must be set");
-    let username = env::var("SMTP_USERNAME").expect("SMTP_USERNAME must be set");
-    let password = env::var("SMTP_PASSWORD").expect("SMTP_PASSWORD must be set");
+    let host = settings::SMTP_HOST
+        .env_value()
+        .expect("SMTP_HOST must be set");
+    let username = settings::SMTP_USERNAME
+        .env_value()
+        .expect("SMTP_USERNAME must be set");
+    let password = settings::SMTP_PASSWORD
+        .env_value()
+        .expect("SMTP_PASSWORD must be set");
 
     info!(smtp_host = %host, smtp_user = %username, "Initializing SMTP connection");
 
@@ -0,0 +1,478 @@
+//! Persisting uploaded bytes as `media` rows.
+//!
+//! This is the pipeline behind `POST /media/v1/create`: content-hash
+//! deduplication, dimension detection, optional image optimization, the S3
+//! upload of the original plus variants, and the database records. It lives
+//! here so importers and background jobs can store files the same way.
+
+use aws_sdk_s3::primitives::ByteStream;
+use bytes::Bytes;
+use chrono::{Datelike, Utc};
+use sha2::{Digest, Sha256};
+use tracing::{debug, info, instrument, warn};
+use uuid::Uuid;
+
+use crate::{
+    config,
+    db::sea_models::{
+        media::{self, Entity as Media, NewMedia},
+        media_variant::{Entity as MediaVariant, NewMediaVariant},
+    },
+    error::{ErrorCode, ErrorResponse},
+    modules::media_v1::validator::MediaUploadMetadata,
+    services::image_optimizer,
+    AppState,
+};
+
+#[derive(Debug, Clone)]
+pub struct UploadedFile {
+    pub bytes: Bytes,
+    pub original_name: Option<String>,
+    pub mime_type: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct StoredMedia {
+    pub media: media::Model,
+    /// `true` when an identical file already existed and was reused.
+    pub duplicate: bool,
+}
+
+#[instrument(
+    skip(state, file, metadata),
+    fields(file_size = file.bytes.len(), content_hash, is_duplicate, is_optimized)
+)]
+pub async fn store(
+    state: &AppState,
+    file: UploadedFile,
+    mut metadata: MediaUploadMetadata,
+    uploader_id: Option<i32>,
+) -> Result<StoredMedia, ErrorResponse> {
+    let UploadedFile {
+        bytes: file_bytes,
+        original_name,
+        mime_type,
+    } = file;
+
+    if file_bytes.len() > config::body_limits::MEDIA {
+        warn!(
+            file_size = file_bytes.len(),
+            max_size = config::body_limits::MEDIA,
+            "Upload exceeds size limit"
+        );
+        return Err(
+            ErrorResponse::new(ErrorCode::FileTooLarge).with_message(format!(
+                "File size exceeds the {}MiB upload limit",
+                config::body_limits::MEDIA / 1024 / 1024
+            )),
+        );
+    }
+
+    let mut hasher = Sha256::new();
+    hasher.update(&file_bytes);
+    let content_hash = format!("{:x}", hasher.finalize());
+
+    debug!(content_hash = %content_hash, "File hash calculated");
+    tracing::Span::current().record("content_hash", &content_hash);
+
+    if let Some(existing) = Media::find_by_hash(&state.sea_db, &content_hash).await? {
+        info!(
+            media_id = existing.id,
+            content_hash = %content_hash,
+            "Duplicate file detected, returning existing media"
+        );
+        tracing::Span::current().record("is_duplicate", true);
+        return Ok(StoredMedia {
+            media: existing,
+            duplicate: true,
+        });
+    }
+
+    tracing::Span::current().record("is_duplicate", false);
+
+    // Derive useful metadata if it was not supplied
+    if metadata.width.is_none() || metadata.height.is_none() {
+        if let Ok(dimensions) = imagesize::blob_size(&file_bytes) {
+            debug!(
+                width = dimensions.width,
+                height = dimensions.height,
+                "Image dimensions detected"
+            );
+            metadata.width = metadata
+                .width
+                .or_else(|| i32::try_from(dimensions.width).ok());
+            metadata.height = metadata
+                .height
+                .or_else(|| i32::try_from(dimensions.height).ok());
+        }
+    }
+
+    let mut extension = infer_extension(original_name.as_deref(), mime_type.as_deref());
+    let mut content_type = mime_type
+        .clone()
+        .unwrap_or_else(|| "application/octet-stream".to_string());
+    let mut final_bytes = file_bytes.clone();
+    let mut variants_to_upload = Vec::new();
+    let mut is_optimized = false;
+    let mut optimized_at = None;
+    struct PreparedVariant {
+        object_key: String,
+        mime_type: String,
+        width: Option<i32>,
+        height: Option<i32>,
+        size: i64,
+        extension: Option<String>,
+        quality: Option<i32>,
+        variant_type: String,
+    }
+    let mut prepared_variants: Vec<PreparedVariant> = Vec::new();
+
+    if content_type.starts_with("image/") {
+        let optimization_request = image_optimizer::OptimizationRequest {
+            bytes: &file_bytes,
+            metadata: &metadata,
+            reference: metadata.reference_type,
+            original_mime: mime_type.as_deref(),
+            original_extension: extension.as_deref(),
+        };
+
+        let optimization_outcome =
+            match image_optimizer::optimize(&state.optimizer, optimization_request) {
+                Ok(outcome) => outcome,
+                Err(err) => {
+                    warn!("image optimizer error: {}", err);
+                    image_optimizer::OptimizationOutcome::Skipped(
+                        image_optimizer::SkipReason::DecodeFailed,
+                    )
+                }
+            };
+
+        if let image_optimizer::OptimizationOutcome::Optimized(result) = optimization_outcome {
+            final_bytes = result.original.bytes.clone();
+            content_type = result.original.mime_type.clone();
+            extension = Some(result.original.extension.clone());
+
+            if let Ok(width) = i32::try_from(result.original.width) {
+                metadata.width = Some(width);
+            }
+            if let Ok(height) = i32::try_from(result.original.height) {
+                metadata.height = Some(height);
+            }
+
+            variants_to_upload = result.variants;
+            is_optimized = true;
+            optimized_at = Some(Utc::now().fixed_offset());
+        }
+    }
+
+    tracing::Span::current().record("is_optimized", is_optimized);
+
+    let size_bytes = i64::try_from(final_bytes.len()).map_err(|_| {
+        ErrorResponse::new(ErrorCode::InvalidValue)
+            .with_message("File size exceeds supported range")
+    })?;
+
+    let object_key = build_object_key(extension.as_deref());
+    let base_object_key = object_key
+        .rsplit_once('.')
+        .map(|(prefix, _)| prefix.to_string())
+        .unwrap_or_else(|| object_key.clone());
+
+    let byte_stream = ByteStream::from(final_bytes.clone().to_vec());
+
+    state
+        .s3_client
+        .put_object()
+        .bucket(&state.object_storage.bucket)
+        .key(&object_key)
+        .body(byte_stream)
+        .content_type(&content_type)
+        .send()
+        .await
+        .map_err(|err| {
+            ErrorResponse::new(ErrorCode::StorageError)
+                .with_message("Failed to persist media to storage")
+                .with_details(err.to_string())
+        })?;
+
+    for variant in variants_to_upload {
+        let suffix = match variant.label {
+            image_optimizer::VariantLabel::Width(width) => format!("@{}w", width),
+            image_optimizer::VariantLabel::Lqip => "@lqip".to_string(),
+            image_optimizer::VariantLabel::Original => continue,
+        };
+
+        let variant_key = format!(
+            "{}{}{}",
+            base_object_key,
+            suffix,
+            if variant.extension.is_empty() {
+                String::new()
+            } else {
+                format!(".{}", variant.extension)
+            }
+        );
+
+        let size_bytes = i64::try_from(variant.bytes.len()).map_err(|_| {
+            ErrorResponse::new(ErrorCode::InvalidValue)
+                .with_message("Variant size exceeds supported range")
+        })?;
+
+        prepared_variants.push(PreparedVariant {
+            object_key: variant_key.clone(),
+            mime_type: variant.mime_type.clone(),
+            width: i32::try_from(variant.width).ok(),
+            height: i32::try_from(variant.height).ok(),
+            size: size_bytes,
+            extension: if variant.extension.is_empty() {
+                None
+            } else {
+                Some(variant.extension.clone())
+            },
+            quality: variant.quality.map(i32::from),
+            variant_type: label_to_variant_type(&variant.label),
+        });
+
+        if let Err(err) = state
+            .s3_client
+            .put_object()
+            .bucket(&state.object_storage.bucket)
+            .key(&variant_key)
+            .body(ByteStream::from(variant.bytes.to_vec()))
+            .content_type(&variant.mime_type)
+            .send()
+            .await
+        {
+            warn!(
+                "failed to upload optimized variant {}: {}",
+                variant_key, err
+            );
+        }
+    }
+
+    let public_url = format!(
+        "{}/{}",
+        state.object_storage.public_url.trim_end_matches('/'),
+        &object_key
+    );
+
+    let new_media = NewMedia {
+        object_key,
+        file_url: public_url,
+        mime_type: content_type,
+        width: metadata.width,
+        height: metadata.height,
+        size: size_bytes,
+        extension,
+        uploader_id,
+        reference_type: metadata.reference_type,
+        content_hash: Some(content_hash),
+        is_optimized,
+        optimized_at,
+    };
+
+    let stored = Media::create(&state.sea_db, new_media).await?;
+
+    if !prepared_variants.is_empty() {
+        let records = prepared_variants
+            .into_iter()
+            .map(|variant| NewMediaVariant {
+                media_id: stored.id,
+                object_key: variant.object_key,
+                mime_type: variant.mime_type,
+                width: variant.width,
+                height: variant.height,
+                size: variant.size,
+                extension: variant.extension,
+                quality: variant.quality,
+                variant_type: variant.variant_type,
+            })
+            .collect();
+
+        MediaVariant::create_many(&state.sea_db, records).await?;
+    }
+
+    Ok(StoredMedia {
+        media: stored,
+        duplicate: false,
+    })
+}
+
+/// Download a remote file (e.g. an image referenced by imported content) and
+/// store it as media. Responses larger than the media upload limit are rejected.
+#[instrument(skip(state), fields(url = %url))]
+pub async fn store_remote(
+    state: &AppState,
+    url: &str,
+    uploader_id: Option<i32>,
+) -> Result<StoredMedia, ErrorResponse> {
+    let response = reqwest::Client::new()
+        .get(url)
+        .timeout(std::time::Duration::from_secs(30))
+        .send()
+        .await
+        .and_then(|resp| resp.error_for_status())
+        .map_err(|err| {
+            ErrorResponse::new(ErrorCode::ExternalServiceError)
+                .with_message("Failed to download remote file")
+                .with_details(err.to_string())
+        })?;
+
+    if response
+        .content_length()
+        .is_some_and(|len| len as usize > config::body_limits::MEDIA)
+    {
+        return Err(ErrorResponse::new(ErrorCode::FileTooLarge)
+            .with_message("Remote file exceeds the media upload limit"));
+    }
+
+    let mime_type = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.split(';').next().unwrap_or(v).trim().to_string());
+
+    let bytes = response.bytes().await.map_err(|err| {
+        ErrorResponse::new(ErrorCode::ExternalServiceError)
+            .with_message("Failed to read remote file")
+            .with_details(err.to_string())
+    })?;
+
+    let original_name = url
+        .split(['?', '#'])
+        .next()
+        .and_then(|path| path.rsplit('/').next())
+        .filter(|name| !name.is_empty())
+        .map(|name| name.to_string());
+
+    store(
+        state,
+        UploadedFile {
+            bytes,
+            original_name,
+            mime_type,
+        },
+        MediaUploadMetadata::default(),
+        uploader_id,
+    )
+    .await
+}
+
+/// Write raw bytes to object storage without creating a media record
+/// (backup archives and other internal artifacts).
+pub async fn put_object(
+    state: &AppState,
+    key: &str,
+    bytes: Bytes,
+    content_type: &str,
+) -> Result<(), ErrorResponse> {
+    state
+        .s3_client
+        .put_object()
+        .bucket(&state.object_storage.bucket)
+        .key(key)
+        .body(ByteStream::from(bytes))
+        .content_type(content_type)
+        .send()
+        .await
+        .map_err(|err| {
+            ErrorResponse::new(ErrorCode::StorageError)
+                .with_message("Failed to write object to storage")
+                .with_details(err.to_string())
+        })?;
+    Ok(())
+}
+
+/// Read an object from storage into memory.
+pub async fn get_object(state: &AppState, key: &str) -> Result<Bytes, ErrorResponse> {
+    let output = state
+        .s3_client
+        .get_object()
+        .bucket(&state.object_storage.bucket)
+        .key(key)
+        .send()
+        .await
+        .map_err(|err| {
+            ErrorResponse::new(ErrorCode::StorageError)
+                .with_message("Failed to read object from storage")
+                .with_details(err.to_string())
+        })?;
+
+    let data = output.body.collect().await.map_err(|err| {
+        ErrorResponse::new(ErrorCode::StorageError)
+            .with_message("Failed to read object from storage")
+            .with_details(err.to_string())
+    })?;
+    Ok(data.into_bytes())
+}
+
+pub async fn delete_object(state: &AppState, key: &str) -> Result<(), ErrorResponse> {
+    state
+        .s3_client
+        .delete_object()
+        .bucket(&state.object_storage.bucket)
+        .key(key)
+        .send()
+        .await
+        .map_err(|err| {
+            ErrorResponse::new(ErrorCode::FileDeletionError)
+                .with_message("Failed to delete object from storage")
+                .with_details(err.to_string())
+        })?;
+    Ok(())
+}
+
+pub fn infer_extension(filename: Option<&str>, mime_type: Option<&str>) -> Option<String> {
+    if let Some(name) = filename {
+        if let Some((_, ext)) = name.rsplit_once('.') {
+            let ext = ext.trim().trim_matches('.');
+            if !ext.is_empty() {
+                return Some(ext.to_ascii_lowercase());
+            }
+        }
+    }
+
+    mime_type
+        .and_then(|mt| mt.rsplit_once('/'))
+        .map(|(_, ext)| ext.trim().to_ascii_lowercase())
+        .filter(|ext| !ext.is_empty())
+}
+
+/// Best-effort MIME type from a file name, for files that arrive without one
+/// (zip entries, archive members).
+pub fn guess_mime_type(filename: &str) -> Option<&'static str> {
+    let ext = filename.rsplit_once('.')?.1.to_ascii_lowercase();
+    let mime = match ext.as_str() {
+        "jpg" | "jpeg" => "image/jpeg",
+        "png" => "image/png",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "svg" => "image/svg+xml",
+        "avif" => "image/avif",
+        "tif" | "tiff" => "image/tiff",
+        "pdf" => "application/pdf",
+        "mp4" => "video/mp4",
+        "mp3" => "audio/mpeg",
+        _ => return None,
+    };
+    Some(mime)
+}
+
+fn build_object_key(extension: Option<&str>) -> String {
+    let now = Utc::now();
+    let prefix = format!("media/{}/{:02}", now.year(), now.month());
+    let base = format!("{}/{}", prefix, Uuid::new_v4());
+
+    match extension {
+        Some(ext) => format!("{}.{}", base, ext),
+        None => base,
+    }
+}
+
+fn label_to_variant_type(label: &image_optimizer::VariantLabel) -> String {
+    match label {
+        image_optimizer::VariantLabel::Width(width) => format!("{}w", width),
+        image_optimizer::VariantLabel::Lqip => "lqip".to_string(),
+        image_optimizer::VariantLabel::Original => "original".to_string(),
+    }
+}
@@ -1,10 +1,28 @@
 pub mod abuse_limiter;
 pub mod acl_service;
+pub mod admin_events;
+pub mod audit;
 pub mod auth;
+pub mod backup;
+pub mod blog_import;
+pub mod content_render;
 pub mod image_optimizer;
+pub mod jobs;
 pub mod mail;
+pub mod media_storage;
+pub mod notifications;
+pub mod permission_service;
+pub mod post_diff;
+pub mod post_lock;
+pub mod post_markdown;
+pub mod post_review;
 pub mod redis;
 pub mod route_blocker_config;
+pub mod route_blocker_rules;
 pub mod route_blocker_service;
 pub mod seed;
 pub mod seed_config;
+pub mod seo;
+pub mod settings;
+pub mod webhooks;
+pub mod websub;
@@ -0,0 +1,195 @@
+//! User notifications.
+//!
+//! Notifications are written by the db actions they describe (a reply in
+//! `post_comment::Entity::create`, a like in `comment_like`, a publish in
+//! `post`), through `notification::Entity`. Each user picks channels in
+//! `notification_preferences`: the in-app center (`notification_v1`) and an
+//! email digest, sent by [`NotificationDigest`] on the `mail` queue.
+
+use sea_orm::EntityTrait;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tracing::{info, warn};
+
+use crate::{
+    db::sea_models::{
+        notification::{self, NotificationType},
+        notification_preference, user,
+    },
+    services::{
+        jobs::{queues, Job, JobError, JobResult},
+        mail, seo,
+    },
+    AppState,
+};
+
+/// Most notifications listed in one digest.
+const DIGEST_LIMIT: u64 = 50;
+
+/// One line describing `notification`, e.g. "Ada and 2 others liked your
+/// comment on “Title”".
+pub fn summary(notification: &notification::Model) -> String {
+    let payload = &notification.payload;
+    let actor = payload["actor_name"].as_str().unwrap_or("Someone");
+    let actor = match notification.count {
+        1 => actor.to_string(),
+        2 => format!("{} and 1 other", actor),
+        count => format!("{} and {} others", actor, count - 1),
+    };
+    let title = payload["post_title"].as_str().unwrap_or("a post");
+
+    match notification.kind {
+        NotificationType::CommentReply => {
+            format!("{} replied to your comment on “{}”", actor, title)
+        }
+        NotificationType::CommentLike => {
+            format!("{} liked your comment on “{}”", actor, title)
+        }
+        NotificationType::AuthorPublished => format!("{} published “{}”", actor, title),
+    }
+}
+
+/// Public URL of the post the notification is about.
+pub fn link(notification: &notification::Model) -> Option<String> {
+    let payload = &notification.payload;
+    let slug = payload["post_slug"].as_str()?;
+    let locale = payload["post_locale"]
+        .as_str()
+        .unwrap_or(crate::utils::default_locale());
+    Some(seo::post_url(locale, slug))
+}
+
+/// Email each user whose digest is due their unread notifications; enqueued
+/// hourly.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct NotificationDigest {}
+
+#[async_trait::async_trait]
+impl Job for NotificationDigest {
+    const KIND: &'static str = "notifications.digest";
+    const QUEUE: &'static str = queues::MAIL;
+    const MAX_ATTEMPTS: i32 = 3;
+
+    async fn run(self, state: &AppState) -> JobResult {
+        let db = &state.sea_db;
+        let now = chrono::Utc::now().fixed_offset();
+        let due = notification_preference::Entity::due_for_digest(db, now).await?;
+
+        let mut sent = 0u64;
+        let mut failed = 0u64;
+        for preferences in due {
+            let pending =
+                notification::Entity::pending_digest(db, preferences.user_id, DIGEST_LIMIT).await?;
+            if pending.is_empty() {
+                notification_preference::Entity::set_last_digest_at(db, preferences.user_id, now)
+                    .await?;
+                continue;
+            }
+            let Some(recipient) = user::Entity::find_by_id(preferences.user_id)
+                .one(db)
+                .await?
+            else {
+                continue;
+            };
+
+            let items: Vec<(String, Option<String>)> = pending
+                .iter()
+                .map(|notification| (summary(notification), link(notification)))
+                .collect();
+            let headline = match items.len() {
+                1 => "You have 1 new notification".to_string(),
+                count => format!("You have {} new notifications", count),
+            };
+            if let Err(err) = mail::send_notification_digest_email(
+                &state.mailer,
+                &recipient.email,
+                &headline,
+                &items,
+            )
+            .await
+            {
+                // Left pending for the next run.
+                warn!(user_id = recipient.id, error = %err, "Notification digest failed");
+                failed += 1;
+                continue;
+            }
+
+            let ids = pending.iter().map(|notification| notification.id).collect();
+            notification::Entity::mark_emailed(db, ids, !preferences.in_app).await?;
+            notification_preference::Entity::set_last_digest_at(db, preferences.user_id, now)
+                .await?;
+            sent += 1;
+        }
+
+        info!(sent, failed, "Notification digests sent");
+        if sent == 0 && failed > 0 {
+            return Err(JobError::new(format!("{} digests failed", failed)));
+        }
+        Ok(Some(json!({ "sent": sent, "failed": failed })))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn notification(kind: NotificationType, count: i32) -> notification::Model {
+        let now = chrono::Utc::now().fixed_offset();
+        notification::Model {
+            id: 1,
+            user_id: 2,
+            kind,
+            actor_id: Some(3),
+            group_key: "comment_like:4".to_string(),
+            count,
+            payload: json!({
+                "actor_name": "Ada",
+                "post_title": "Hello",
+                "post_slug": "hello",
+                "post_locale": "fr",
+            }),
+            read_at: None,
+            emailed_at: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[test]
+    fn summaries_name_the_actor_and_group_size() {
+        assert_eq!(
+            summary(&notification(NotificationType::CommentReply, 1)),
+            "Ada replied to your comment on “Hello”"
+        );
+        assert_eq!(
+            summary(&notification(NotificationType::CommentLike, 2)),
+            "Ada and 1 other liked your comment on “Hello”"
+        );
+        assert_eq!(
+            summary(&notification(NotificationType::CommentLike, 5)),
+            "Ada and 4 others liked your comment on “Hello”"
+        );
+        assert_eq!(
+            summary(&notification(NotificationType::AuthorPublished, 1)),
+            "Ada published “Hello”"
+        );
+    }
+
+    #[test]
+    fn links_point_at_the_post() {
+        let link = link(&notification(NotificationType::CommentReply, 1)).unwrap();
+        assert!(link.ends_with("/fr/posts/hello") || link.ends_with("/posts/hello"));
+
+        let mut without_post = notification(NotificationType::CommentReply, 1);
+        without_post.payload = json!({});
+        assert_eq!(super::link(&without_post), None);
+        assert_eq!(
+            summary(&without_post),
+            "Someone replied to your comment on “a post”"
+        );
+        assert_eq!(
+            NotificationType::parse("comment_like"),
+            Some(NotificationType::CommentLike)
+        );
+    }
+}
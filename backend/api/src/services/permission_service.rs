@@ -0,0 +1,386 @@
+//! Named permissions on top of user roles.
+//!
+//! Each role holds a set of permissions (`role_permissions`); a user can be
+//! granted extra ones or have some of their role's revoked
+//! (`user_permissions`). Super admins hold every permission whatever the
+//! matrix says, so the matrix can't lock everyone out of editing it.
+//!
+//! Role sets and user overrides are cached in Redis hashes the same way
+//! `AclService` caches app constants, and dropped from the cache whenever they
+//! change. The catalog below is the source of truth for which permissions
+//! exist and which role held each one under the fixed role levels.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use sea_orm::DatabaseConnection;
+use serde::Serialize;
+use tower_sessions_redis_store::fred::prelude::{
+    HashesInterface, KeysInterface, Pool as RedisPool,
+};
+use tracing::warn;
+
+use crate::{
+    db::sea_models::{
+        role_permission,
+        user::{self, UserRole},
+        user_permission,
+    },
+    error::{DbResult, ErrorCode, ErrorResponse},
+    AppState,
+};
+
+pub const POST_VIEW_ANY: &str = "post.view_any";
+pub const POST_EDIT_ANY: &str = "post.edit_any";
+pub const POST_PUBLISH: &str = "post.publish";
+pub const POST_REVIEW: &str = "post.review";
+pub const COMMENT_MODERATE: &str = "comment.moderate";
+pub const MEDIA_DELETE_ANY: &str = "media.delete_any";
+pub const TAXONOMY_MANAGE: &str = "taxonomy.manage";
+pub const USER_MANAGE: &str = "user.manage";
+pub const NEWSLETTER_MANAGE: &str = "newsletter.manage";
+pub const ANALYTICS_VIEW: &str = "analytics.view";
+pub const REDIRECT_MANAGE: &str = "redirect.manage";
+pub const ROUTE_MANAGE: &str = "route.manage";
+pub const IMPORT_RUN: &str = "import.run";
+pub const BACKUP_MANAGE: &str = "backup.manage";
+pub const ACL_MANAGE: &str = "acl.manage";
+pub const PERMISSION_MANAGE: &str = "permission.manage";
+pub const AUDIT_VIEW: &str = "audit.view";
+pub const JOB_MANAGE: &str = "job.manage";
+pub const WEBHOOK_MANAGE: &str = "webhook.manage";
+
+#[derive(Clone, Copy, Debug, Serialize)]
+pub struct PermissionDef {
+    pub name: &'static str,
+    pub description: &'static str,
+    /// Lowest role holding it by default; every role above holds it too.
+    pub default_role: UserRole,
+}
+
+const fn def(
+    name: &'static str,
+    description: &'static str,
+    default_role: UserRole,
+) -> PermissionDef {
+    PermissionDef {
+        name,
+        description,
+        default_role,
+    }
+}
+
+pub const CATALOG: &[PermissionDef] = &[
+    def(
+        POST_VIEW_ANY,
+        "List and read every post, not only the ones you are credited on",
+        UserRole::Moderator,
+    ),
+    def(
+        POST_EDIT_ANY,
+        "Edit, delete and manage contributors of any post",
+        UserRole::Moderator,
+    ),
+    def(
+        POST_PUBLISH,
+        "Publish without an approved review",
+        UserRole::Moderator,
+    ),
+    def(
+        POST_REVIEW,
+        "Approve posts, request changes and manage other reviewers' notes",
+        UserRole::Moderator,
+    ),
+    def(
+        COMMENT_MODERATE,
+        "Hide, delete and clear flags on comments",
+        UserRole::Admin,
+    ),
+    def(
+        MEDIA_DELETE_ANY,
+        "Delete media uploaded by someone else",
+        UserRole::Admin,
+    ),
+    def(
+        TAXONOMY_MANAGE,
+        "Create, edit and delete categories and tags",
+        UserRole::Admin,
+    ),
+    def(USER_MANAGE, "Manage user accounts", UserRole::Admin),
+    def(
+        NEWSLETTER_MANAGE,
+        "Manage subscribers and send newsletters",
+        UserRole::Admin,
+    ),
+    def(ANALYTICS_VIEW, "View site analytics", UserRole::Admin),
+    def(REDIRECT_MANAGE, "Manage slug redirects", UserRole::Admin),
+    def(ROUTE_MANAGE, "Block and unblock routes", UserRole::Admin),
+    def(
+        IMPORT_RUN,
+        "Import content from other blogs",
+        UserRole::Admin,
+    ),
+    def(
+        BACKUP_MANAGE,
+        "Create, download and restore backups",
+        UserRole::SuperAdmin,
+    ),
+    def(ACL_MANAGE, "Edit app constants", UserRole::SuperAdmin),
+    def(
+        PERMISSION_MANAGE,
+        "Edit the permission matrix and user overrides",
+        UserRole::SuperAdmin,
+    ),
+    def(
+        AUDIT_VIEW,
+        "Read, export and purge the audit log",
+        UserRole::SuperAdmin,
+    ),
+    def(
+        JOB_MANAGE,
+        "Inspect, retry and cancel background jobs",
+        UserRole::Admin,
+    ),
+    def(
+        WEBHOOK_MANAGE,
+        "Manage outbound webhooks and their deliveries",
+        UserRole::Admin,
+    ),
+];
+
+pub const ROLES: [UserRole; 5] = [
+    UserRole::SuperAdmin,
+    UserRole::Admin,
+    UserRole::Moderator,
+    UserRole::Author,
+    UserRole::User,
+];
+
+pub fn is_known(permission: &str) -> bool {
+    CATALOG.iter().any(|p| p.name == permission)
+}
+
+/// What `role` holds out of the box.
+pub fn defaults_for(role: UserRole) -> Vec<String> {
+    CATALOG
+        .iter()
+        .filter(|p| role.to_i32() >= p.default_role.to_i32())
+        .map(|p| p.name.to_string())
+        .collect()
+}
+
+/// A user's permissions: their role's, plus granted overrides, minus revoked
+/// ones. Super admins hold everything.
+pub fn effective(
+    role: UserRole,
+    role_permissions: &BTreeSet<String>,
+    overrides: &BTreeMap<String, bool>,
+) -> BTreeSet<String> {
+    if role == UserRole::SuperAdmin {
+        return CATALOG.iter().map(|p| p.name.to_string()).collect();
+    }
+    let mut permissions = role_permissions.clone();
+    for (permission, granted) in overrides {
+        if *granted {
+            permissions.insert(permission.clone());
+        } else {
+            permissions.remove(permission);
+        }
+    }
+    permissions
+}
+
+pub struct PermissionService;
+
+impl PermissionService {
+    pub const ROLE_HASH: &'static str = "role_permissions";
+    pub const USER_HASH: &'static str = "user_permissions";
+
+    pub async fn role_permissions(
+        db: &DatabaseConnection,
+        redis: Option<&RedisPool>,
+        role: UserRole,
+    ) -> DbResult<BTreeSet<String>> {
+        let field = role.to_string();
+        if let Some(cached) = Self::read_cached(redis, Self::ROLE_HASH, &field).await {
+            return Ok(cached);
+        }
+
+        let permissions: BTreeSet<String> = role_permission::Entity::for_role(db, role)
+            .await?
+            .into_iter()
+            .collect();
+        Self::write_cached(redis, Self::ROLE_HASH, &field, &permissions).await;
+        Ok(permissions)
+    }
+
+    pub async fn user_overrides(
+        db: &DatabaseConnection,
+        redis: Option<&RedisPool>,
+        user_id: i32,
+    ) -> DbResult<BTreeMap<String, bool>> {
+        let field = user_id.to_string();
+        if let Some(cached) = Self::read_cached(redis, Self::USER_HASH, &field).await {
+            return Ok(cached);
+        }
+
+        let overrides: BTreeMap<String, bool> = user_permission::Entity::for_user(db, user_id)
+            .await?
+            .into_iter()
+            .map(|row| (row.permission, row.granted))
+            .collect();
+        Self::write_cached(redis, Self::USER_HASH, &field, &overrides).await;
+        Ok(overrides)
+    }
+
+    pub async fn effective_for(
+        db: &DatabaseConnection,
+        redis: Option<&RedisPool>,
+        user: &user::Model,
+    ) -> DbResult<BTreeSet<String>> {
+        if user.role == UserRole::SuperAdmin {
+            return Ok(effective(user.role, &BTreeSet::new(), &BTreeMap::new()));
+        }
+        let role_permissions = Self::role_permissions(db, redis, user.role).await?;
+        let overrides = Self::user_overrides(db, redis, user.id).await?;
+        Ok(effective(user.role, &role_permissions, &overrides))
+    }
+
+    pub async fn has_permission(
+        db: &DatabaseConnection,
+        redis: Option<&RedisPool>,
+        user: &user::Model,
+        permission: &str,
+    ) -> DbResult<bool> {
+        Ok(Self::effective_for(db, redis, user)
+            .await?
+            .contains(permission))
+    }
+
+    /// Whether `user` holds `permission`. Lookup failures are logged and
+    /// count as not holding it.
+    pub async fn allows(state: &AppState, user: &user::Model, permission: &str) -> bool {
+        match Self::has_permission(&state.sea_db, Some(&state.redis_pool), user, permission).await {
+            Ok(allowed) => allowed,
+            Err(err) => {
+                warn!(user_id = user.id, permission, error = ?err, "Permission lookup failed");
+                false
+            }
+        }
+    }
+
+    pub async fn require(
+        state: &AppState,
+        user: &user::Model,
+        permission: &str,
+    ) -> Result<(), ErrorResponse> {
+        if Self::allows(state, user, permission).await {
+            return Ok(());
+        }
+        Err(ErrorResponse::new(ErrorCode::OperationNotAllowed)
+            .with_message(format!("Missing permission: {}", permission))
+            .with_context(serde_json::json!({ "required_permission": permission })))
+    }
+
+    /// Roles holding `permission` through the matrix.
+    pub async fn roles_with(state: &AppState, permission: &str) -> DbResult<Vec<UserRole>> {
+        let mut roles = role_permission::Entity::roles_with(&state.sea_db, permission).await?;
+        if !roles.contains(&UserRole::SuperAdmin) {
+            roles.push(UserRole::SuperAdmin);
+        }
+        Ok(roles)
+    }
+
+    pub async fn forget_role(state: &AppState, role: UserRole) {
+        state
+            .redis_pool
+            .hdel::<(), _, _>(Self::ROLE_HASH, role.to_string())
+            .await
+            .ok();
+    }
+
+    pub async fn forget_user(state: &AppState, user_id: i32) {
+        state
+            .redis_pool
+            .hdel::<(), _, _>(Self::USER_HASH, user_id.to_string())
+            .await
+            .ok();
+    }
+
+    /// Drop every cached role set and override; they are reloaded on use.
+    pub async fn flush(state: &AppState) {
+        state
+            .redis_pool
+            .del::<(), _>(vec![Self::ROLE_HASH, Self::USER_HASH])
+            .await
+            .ok();
+    }
+
+    async fn read_cached<T: serde::de::DeserializeOwned>(
+        redis: Option<&RedisPool>,
+        hash: &str,
+        field: &str,
+    ) -> Option<T> {
+        let raw: Option<String> = redis?.hget(hash, field).await.unwrap_or(None);
+        serde_json::from_str(&raw?).ok()
+    }
+
+    async fn write_cached<T: Serialize>(
+        redis: Option<&RedisPool>,
+        hash: &str,
+        field: &str,
+        value: &T,
+    ) {
+        let (Some(redis), Ok(json)) = (redis, serde_json::to_string(value)) else {
+            return;
+        };
+        redis.hset::<(), _, _>(hash, vec![(field, json)]).await.ok();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defaults_follow_the_old_role_levels() {
+        assert!(defaults_for(UserRole::User).is_empty());
+        assert!(defaults_for(UserRole::Author).is_empty());
+
+        let moderator = defaults_for(UserRole::Moderator);
+        assert!(moderator.contains(&POST_EDIT_ANY.to_string()));
+        assert!(!moderator.contains(&COMMENT_MODERATE.to_string()));
+
+        let admin = defaults_for(UserRole::Admin);
+        assert!(admin.contains(&COMMENT_MODERATE.to_string()));
+        assert!(!admin.contains(&BACKUP_MANAGE.to_string()));
+
+        assert_eq!(defaults_for(UserRole::SuperAdmin).len(), CATALOG.len());
+    }
+
+    #[test]
+    fn overrides_grant_and_revoke_on_top_of_the_role() {
+        let role: BTreeSet<String> = [POST_EDIT_ANY, POST_REVIEW].map(String::from).into();
+        let overrides: BTreeMap<String, bool> = [
+            (POST_PUBLISH.to_string(), true),
+            (POST_REVIEW.to_string(), false),
+        ]
+        .into();
+
+        let permissions = effective(UserRole::Moderator, &role, &overrides);
+        assert!(permissions.contains(POST_EDIT_ANY));
+        assert!(permissions.contains(POST_PUBLISH));
+        assert!(!permissions.contains(POST_REVIEW));
+
+        let revoked: BTreeMap<String, bool> = [(PERMISSION_MANAGE.to_string(), false)].into();
+        assert!(
+            effective(UserRole::SuperAdmin, &BTreeSet::new(), &revoked).contains(PERMISSION_MANAGE)
+        );
+    }
+
+    #[test]
+    fn catalog_names_are_unique() {
+        let names: BTreeSet<&str> = CATALOG.iter().map(|p| p.name).collect();
+        assert_eq!(names.len(), CATALOG.len());
+        assert!(is_known(MEDIA_DELETE_ANY) && !is_known("media.delete_all"));
+    }
+}
@@ -0,0 +1,420 @@
+//! Diffs between two post revisions.
+//!
+//! Content is compared block by block: blocks are aligned by their Editor.js
+//! `id` when both sides have one and by type + data otherwise, so an edited
+//! paragraph shows up as `modified` (with a word-level text diff) instead of
+//! a removal followed by an insertion. Editable fields are compared one by
+//! one from the revision snapshots.
+
+use std::time::Duration;
+
+use serde::Serialize;
+use serde_json::{json, Value};
+use similar::{capture_diff_slices, Algorithm, ChangeTag, DiffOp, TextDiff};
+
+use crate::{
+    db::sea_models::post_revision::{RevisionField, RevisionFields},
+    services::content_render,
+};
+
+const TEXT_DIFF_TIMEOUT: Duration = Duration::from_millis(250);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TextOp {
+    Equal,
+    Insert,
+    Delete,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TextChange {
+    pub op: TextOp,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum BlockChange {
+    Added {
+        index: usize,
+        block: Value,
+    },
+    Removed {
+        index: usize,
+        block: Value,
+    },
+    Modified {
+        old_index: usize,
+        new_index: usize,
+        #[serde(rename = "type")]
+        kind: String,
+        before: Value,
+        after: Value,
+        text: Vec<TextChange>,
+    },
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BlockDiff {
+    pub changes: Vec<BlockChange>,
+    pub unchanged: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FieldChange {
+    pub field: RevisionField,
+    pub before: Value,
+    pub after: Value,
+    /// Word-level diff for text fields.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<Vec<TextChange>>,
+    /// Tag IDs gained / lost, for `tags`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub added: Option<Vec<i32>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub removed: Option<Vec<i32>>,
+}
+
+/// Word-level diff; adjacent segments with the same op are merged.
+pub fn diff_text(old: &str, new: &str) -> Vec<TextChange> {
+    let diff = TextDiff::configure()
+        .algorithm(Algorithm::Myers)
+        .timeout(TEXT_DIFF_TIMEOUT)
+        .diff_words(old, new);
+
+    let mut changes: Vec<TextChange> = Vec::new();
+    for change in diff.iter_all_changes() {
+        let op = match change.tag() {
+            ChangeTag::Equal => TextOp::Equal,
+            ChangeTag::Insert => TextOp::Insert,
+            ChangeTag::Delete => TextOp::Delete,
+        };
+        match changes.last_mut() {
+            Some(last) if last.op == op => last.text.push_str(change.value()),
+            _ => changes.push(TextChange {
+                op,
+                text: change.value().to_string(),
+            }),
+        }
+    }
+    changes
+}
+
+fn block_id(block: &Value) -> Option<&str> {
+    block.get("id").and_then(Value::as_str)
+}
+
+fn block_kind(block: &Value) -> &str {
+    block
+        .get("type")
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+}
+
+/// Whether two blocks hold the same content (ignoring `id`).
+fn same_content(a: &Value, b: &Value) -> bool {
+    block_kind(a) == block_kind(b) && a.get("data") == b.get("data")
+}
+
+fn block_key(block: &Value) -> String {
+    match block_id(block) {
+        Some(id) => format!("id:{}", id),
+        None => format!(
+            "{}:{}",
+            block_kind(block),
+            block.get("data").cloned().unwrap_or(Value::Null)
+        ),
+    }
+}
+
+fn modified(old_index: usize, new_index: usize, before: &Value, after: &Value) -> BlockChange {
+    BlockChange::Modified {
+        old_index,
+        new_index,
+        kind: block_kind(after).to_string(),
+        before: before.clone(),
+        after: after.clone(),
+        text: diff_text(
+            &content_render::block_text(before),
+            &content_render::block_text(after),
+        ),
+    }
+}
+
+/// Block-aware diff of two Editor.js documents.
+pub fn diff_blocks(old: &Value, new: &Value) -> BlockDiff {
+    let old_blocks = content_render::blocks(old);
+    let new_blocks = content_render::blocks(new);
+    let old_keys: Vec<String> = old_blocks.iter().map(block_key).collect();
+    let new_keys: Vec<String> = new_blocks.iter().map(block_key).collect();
+
+    let mut changes = Vec::new();
+    let mut unchanged = 0;
+
+    for op in capture_diff_slices(Algorithm::Myers, &old_keys, &new_keys) {
+        match op {
+            DiffOp::Equal {
+                old_index,
+                new_index,
+                len,
+            } => {
+                for k in 0..len {
+                    let (before, after) = (&old_blocks[old_index + k], &new_blocks[new_index + k]);
+                    // Same id, possibly edited
+                    if same_content(before, after) {
+                        unchanged += 1;
+                    } else {
+                        changes.push(modified(old_index + k, new_index + k, before, after));
+                    }
+                }
+            }
+            DiffOp::Delete {
+                old_index, old_len, ..
+            } => {
+                for (k, block) in old_blocks[old_index..old_index + old_len]
+                    .iter()
+                    .enumerate()
+                {
+                    changes.push(BlockChange::Removed {
+                        index: old_index + k,
+                        block: block.clone(),
+                    });
+                }
+            }
+            DiffOp::Insert {
+                new_index, new_len, ..
+            } => {
+                for (k, block) in new_blocks[new_index..new_index + new_len]
+                    .iter()
+                    .enumerate()
+                {
+                    changes.push(BlockChange::Added {
+                        index: new_index + k,
+                        block: block.clone(),
+                    });
+                }
+            }
+            DiffOp::Replace {
+                old_index,
+                old_len,
+                new_index,
+                new_len,
+            } => {
+                // Pair replaced blocks positionally when they look like the
+                // same block edited in place: same type, and not two
+                // different Editor.js ids.
+                for k in 0..old_len.max(new_len) {
+                    let before = (k < old_len).then(|| &old_blocks[old_index + k]);
+                    let after = (k < new_len).then(|| &new_blocks[new_index + k]);
+                    match (before, after) {
+                        (Some(b), Some(a))
+                            if block_kind(b) == block_kind(a)
+                                && (block_id(b).is_none() || block_id(a).is_none()) =>
+                        {
+                            changes.push(modified(old_index + k, new_index + k, b, a));
+                        }
+                        (b, a) => {
+                            if let Some(b) = b {
+                                changes.push(BlockChange::Removed {
+                                    index: old_index + k,
+                                    block: b.clone(),
+                                });
+                            }
+                            if let Some(a) = a {
+                                changes.push(BlockChange::Added {
+                                    index: new_index + k,
+                                    block: a.clone(),
+                                });
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    BlockDiff { changes, unchanged }
+}
+
+fn field_change(field: RevisionField, before: Value, after: Value) -> FieldChange {
+    FieldChange {
+        field,
+        before,
+        after,
+        text: None,
+        added: None,
+        removed: None,
+    }
+}
+
+fn text_change(field: RevisionField, before: Option<&str>, after: Option<&str>) -> FieldChange {
+    FieldChange {
+        text: Some(diff_text(
+            before.unwrap_or_default(),
+            after.unwrap_or_default(),
+        )),
+        ..field_change(field, json!(before), json!(after))
+    }
+}
+
+/// Field-level changes between two snapshots, in `RevisionField::ALL` order.
+pub fn diff_fields(old: &RevisionFields, new: &RevisionFields) -> Vec<FieldChange> {
+    let mut changes = Vec::new();
+
+    if old.title != new.title {
+        changes.push(text_change(
+            RevisionField::Title,
+            Some(&old.title),
+            Some(&new.title),
+        ));
+    }
+    if old.slug != new.slug {
+        changes.push(field_change(
+            RevisionField::Slug,
+            json!(old.slug),
+            json!(new.slug),
+        ));
+    }
+    if old.excerpt != new.excerpt {
+        changes.push(text_change(
+            RevisionField::Excerpt,
+            old.excerpt.as_deref(),
+            new.excerpt.as_deref(),
+        ));
+    }
+    if old.featured_image_id != new.featured_image_id {
+        changes.push(field_change(
+            RevisionField::FeaturedImage,
+            json!(old.featured_image_id),
+            json!(new.featured_image_id),
+        ));
+    }
+    if old.category_id != new.category_id {
+        changes.push(field_change(
+            RevisionField::Category,
+            json!(old.category_id),
+            json!(new.category_id),
+        ));
+    }
+    if old.tag_ids != new.tag_ids {
+        changes.push(FieldChange {
+            added: Some(
+                new.tag_ids
+                    .iter()
+                    .copied()
+                    .filter(|id| !old.tag_ids.contains(id))
+                    .collect(),
+            ),
+            removed: Some(
+                old.tag_ids
+                    .iter()
+                    .copied()
+                    .filter(|id| !new.tag_ids.contains(id))
+                    .collect(),
+            ),
+            ..field_change(RevisionField::Tags, json!(old.tag_ids), json!(new.tag_ids))
+        });
+    }
+
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn paragraph(id: Option<&str>, text: &str) -> Value {
+        let mut block = json!({ "type": "paragraph", "data": { "text": text } });
+        if let Some(id) = id {
+            block["id"] = json!(id);
+        }
+        block
+    }
+
+    #[test]
+    fn diffs_text_by_word() {
+        let changes = diff_text("the quick fox", "the slow fox");
+        assert_eq!(
+            changes,
+            vec![
+                TextChange {
+                    op: TextOp::Equal,
+                    text: "the ".into()
+                },
+                TextChange {
+                    op: TextOp::Delete,
+                    text: "quick".into()
+                },
+                TextChange {
+                    op: TextOp::Insert,
+                    text: "slow".into()
+                },
+                TextChange {
+                    op: TextOp::Equal,
+                    text: " fox".into()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn blocks_without_ids_pair_edits_as_modified() {
+        let old = json!({ "blocks": [paragraph(None, "one"), paragraph(None, "two"), paragraph(None, "three")] });
+        let new = json!({ "blocks": [paragraph(None, "one"), paragraph(None, "two!"), paragraph(None, "four"), paragraph(None, "three")] });
+
+        let diff = diff_blocks(&old, &new);
+        assert_eq!(diff.unchanged, 2);
+        assert!(matches!(
+            diff.changes.as_slice(),
+            [
+                BlockChange::Modified {
+                    old_index: 1,
+                    new_index: 1,
+                    ..
+                },
+                BlockChange::Added { index: 2, .. }
+            ]
+        ));
+    }
+
+    #[test]
+    fn blocks_with_ids_match_by_id() {
+        let old =
+            json!({ "blocks": [paragraph(Some("a"), "alpha"), paragraph(Some("b"), "beta")] });
+        let new = json!({ "blocks": [paragraph(Some("a"), "alpha beta"), paragraph(Some("c"), "gamma")] });
+
+        let diff = diff_blocks(&old, &new);
+        assert_eq!(diff.unchanged, 0);
+        assert!(matches!(
+            diff.changes.as_slice(),
+            [
+                BlockChange::Modified { .. },
+                BlockChange::Removed { index: 1, .. },
+                BlockChange::Added { index: 1, .. }
+            ]
+        ));
+    }
+
+    #[test]
+    fn field_diff_reports_tag_changes() {
+        let old = RevisionFields {
+            title: "Hello".into(),
+            slug: "hello".into(),
+            excerpt: None,
+            featured_image_id: None,
+            category_id: 1,
+            tag_ids: vec![1, 2],
+        };
+        let new = RevisionFields {
+            tag_ids: vec![2, 3],
+            ..old.clone()
+        };
+
+        let changes = diff_fields(&old, &new);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].field, RevisionField::Tags);
+        assert_eq!(changes[0].added.as_deref(), Some(&[3][..]));
+        assert_eq!(changes[0].removed.as_deref(), Some(&[1][..]));
+    }
+}
@@ -0,0 +1,222 @@
+//! Soft edit locks for posts.
+//!
+//! An editor opening a post takes the lock and renews it with a heartbeat
+//! (`POST /post/v1/lock/{post_id}`) every ~20 seconds; the lock expires on its
+//! own `LOCK_TTL_SECS` after the last heartbeat, so a closed tab never leaves
+//! a post locked. The lock only drives the "being edited by X" warning —
+//! saves are guarded by optimistic concurrency on `updated_at`, not by it.
+
+use chrono::Utc;
+use schemars::JsonSchema;
+use serde::Serialize;
+use tower_sessions_redis_store::fred::interfaces::LuaInterface;
+use tower_sessions_redis_store::fred::prelude::Pool as RedisPool;
+use tower_sessions_redis_store::fred::types::{FromValue, Value};
+use tracing::error;
+
+use crate::error::{ErrorCode, ErrorResponse};
+
+pub const LOCK_TTL_SECS: i64 = 60;
+
+/// Take (or renew) the lock unless someone else holds it; `force` takes it
+/// over. Returns {acquired, user_id, user_name, acquired_at, ttl} for the
+/// holder after the call.
+const ACQUIRE_SCRIPT: &str = r#"
+-- KEYS: lock_key
+-- ARGV: user_id, user_name, now, ttl, force
+local key = KEYS[1]
+local owner = redis.call('HGET', key, 'user_id')
+if (not owner) or owner == ARGV[1] or ARGV[5] == '1' then
+  if owner ~= ARGV[1] then
+    redis.call('DEL', key)
+    redis.call('HSET', key, 'user_id', ARGV[1], 'user_name', ARGV[2], 'acquired_at', ARGV[3])
+  end
+  redis.call('EXPIRE', key, tonumber(ARGV[4]))
+  local holder = redis.call('HMGET', key, 'user_id', 'user_name', 'acquired_at')
+  return {1, holder[1], holder[2], holder[3], redis.call('TTL', key)}
+end
+local holder = redis.call('HMGET', key, 'user_id', 'user_name', 'acquired_at')
+return {0, holder[1], holder[2], holder[3], redis.call('TTL', key)}
+"#;
+
+/// Delete the lock only if `user_id` holds it.
+const RELEASE_SCRIPT: &str = r#"
+-- KEYS: lock_key
+-- ARGV: user_id
+if redis.call('HGET', KEYS[1], 'user_id') == ARGV[1] then
+  return redis.call('DEL', KEYS[1])
+end
+return 0
+"#;
+
+const STATUS_SCRIPT: &str = r#"
+-- KEYS: lock_key
+local holder = redis.call('HMGET', KEYS[1], 'user_id', 'user_name', 'acquired_at')
+if not holder[1] then
+  return {}
+end
+return {0, holder[1], holder[2], holder[3], redis.call('TTL', KEYS[1])}
+"#;
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct PostLock {
+    pub post_id: i32,
+    pub user_id: i32,
+    pub user_name: String,
+    pub acquired_at: String,
+    pub expires_in: i64,
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct LockStatus {
+    /// Whether the caller holds the lock after this call.
+    pub acquired: bool,
+    /// Current holder, if any.
+    pub lock: Option<PostLock>,
+    pub ttl_secs: i64,
+}
+
+fn lock_key(post_id: i32) -> String {
+    format!("post_lock:{}", post_id)
+}
+
+fn redis_error(err: impl std::fmt::Debug) -> ErrorResponse {
+    error!(error = ?err, "Post lock Redis call failed");
+    ErrorResponse::new(ErrorCode::ServiceUnavailable).with_message("Post locks are unavailable")
+}
+
+/// Parse the {acquired, user_id, user_name, acquired_at, ttl} reply.
+fn parse_reply(post_id: i32, reply: Vec<Value>) -> (bool, Option<PostLock>) {
+    let field = |i: usize| reply.get(i).cloned().unwrap_or(Value::Null);
+    let acquired = i64::from_value(field(0)).unwrap_or(0) == 1;
+    let lock = match (
+        String::from_value(field(1))
+            .ok()
+            .and_then(|id| id.parse::<i32>().ok()),
+        String::from_value(field(2)),
+    ) {
+        (Some(user_id), Ok(user_name)) => Some(PostLock {
+            post_id,
+            user_id,
+            user_name,
+            acquired_at: String::from_value(field(3)).unwrap_or_default(),
+            expires_in: i64::from_value(field(4)).unwrap_or(0).max(0),
+        }),
+        _ => None,
+    };
+    (acquired, lock)
+}
+
+pub async fn acquire(
+    redis_pool: &RedisPool,
+    post_id: i32,
+    user_id: i32,
+    user_name: &str,
+    force: bool,
+) -> Result<LockStatus, ErrorResponse> {
+    let args: Vec<Value> = vec![
+        Value::from(user_id.to_string()),
+        Value::from(user_name.to_string()),
+        Value::from(Utc::now().to_rfc3339()),
+        Value::from(LOCK_TTL_SECS),
+        Value::from(if force { "1" } else { "0" }),
+    ];
+    let reply: Vec<Value> = redis_pool
+        .eval(ACQUIRE_SCRIPT, vec![lock_key(post_id)], args)
+        .await
+        .map_err(redis_error)?;
+
+    let (acquired, lock) = parse_reply(post_id, reply);
+    Ok(LockStatus {
+        acquired,
+        lock,
+        ttl_secs: LOCK_TTL_SECS,
+    })
+}
+
+pub async fn status(
+    redis_pool: &RedisPool,
+    post_id: i32,
+    user_id: i32,
+) -> Result<LockStatus, ErrorResponse> {
+    let reply: Vec<Value> = redis_pool
+        .eval(STATUS_SCRIPT, vec![lock_key(post_id)], Vec::<Value>::new())
+        .await
+        .map_err(redis_error)?;
+
+    let (_, lock) = parse_reply(post_id, reply);
+    Ok(LockStatus {
+        acquired: lock.as_ref().is_some_and(|l| l.user_id == user_id),
+        lock,
+        ttl_secs: LOCK_TTL_SECS,
+    })
+}
+
+/// Returns whether a lock held by `user_id` was removed.
+pub async fn release(
+    redis_pool: &RedisPool,
+    post_id: i32,
+    user_id: i32,
+) -> Result<bool, ErrorResponse> {
+    let deleted: i64 = redis_pool
+        .eval(
+            RELEASE_SCRIPT,
+            vec![lock_key(post_id)],
+            vec![Value::from(user_id.to_string())],
+        )
+        .await
+        .map_err(redis_error)?;
+    Ok(deleted > 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reply(acquired: i64, user_id: &str, ttl: i64) -> Vec<Value> {
+        vec![
+            Value::from(acquired),
+            Value::from(user_id),
+            Value::from("Ada"),
+            Value::from("2026-03-01T10:00:00+00:00"),
+            Value::from(ttl),
+        ]
+    }
+
+    #[test]
+    fn parses_a_lock_held_by_the_caller() {
+        let (acquired, lock) = parse_reply(4, reply(1, "7", 60));
+        assert!(acquired);
+        let lock = lock.unwrap();
+        assert_eq!((lock.post_id, lock.user_id), (4, 7));
+        assert_eq!(lock.user_name, "Ada");
+        assert_eq!(lock.acquired_at, "2026-03-01T10:00:00+00:00");
+        assert_eq!(lock.expires_in, 60);
+    }
+
+    #[test]
+    fn parses_a_lock_held_by_another_user() {
+        let (acquired, lock) = parse_reply(4, reply(0, "8", 42));
+        assert!(!acquired);
+        let lock = lock.unwrap();
+        assert_eq!(lock.user_id, 8);
+        assert_eq!(lock.expires_in, 42);
+    }
+
+    #[test]
+    fn expired_locks_have_no_holder() {
+        // STATUS_SCRIPT once the key expired
+        assert!(matches!(parse_reply(4, Vec::new()), (false, None)));
+        // HMGET on a key that expired between the calls
+        let gone = vec![
+            Value::from(0),
+            Value::Null,
+            Value::Null,
+            Value::Null,
+            Value::from(-2),
+        ];
+        assert!(matches!(parse_reply(4, gone), (false, None)));
+        // TTL of a key without expiry
+        assert_eq!(parse_reply(4, reply(0, "8", -1)).1.unwrap().expires_in, 0);
+    }
+}
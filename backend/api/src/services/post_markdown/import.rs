@@ -0,0 +1,666 @@
+//! Turning uploaded Markdown files (or a zip of them) into posts.
+//!
+//! Image references are resolved to media: relative paths are looked up among
+//! the uploaded assets or zip entries, remote URLs are downloaded. Missing
+//! tags and categories named in front matter are created. Each file gets its
+//! own entry in the report so one bad file doesn't fail the whole batch.
+
+use std::collections::HashMap;
+use std::io::{Cursor, Read};
+
+use bytes::Bytes;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tracing::{info, instrument, warn};
+use validator::Validate;
+
+use super::{parse_document, ParsedMarkdown};
+use crate::{
+    db::sea_models::{
+        category::{self, NewCategory},
+        media,
+        post::{self, PostStatus, PostVisibility},
+        post_author::{self, PostAccess},
+        post_status_transition::check_transition,
+        tag::{self, NewTag},
+        user,
+    },
+    error::{ErrorCode, ErrorResponse},
+    modules::post_v1::validator::{
+        EditorJsDocument, V1CreatePostPayload, V1MarkdownImportOptions, V1UpdatePostPayload,
+    },
+    services::{
+        media_storage::{self, UploadedFile},
+        permission_service::{self, PermissionService},
+        post_review,
+    },
+    utils::{default_locale, normalize_locale, slugify},
+    AppState,
+};
+
+/// Upper bound on the total uncompressed size of a zip upload.
+const MAX_ARCHIVE_EXPANDED: u64 = 100 * 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ConflictStrategy {
+    #[default]
+    Skip,
+    Update,
+}
+
+#[derive(Debug, Clone)]
+pub struct ImportFile {
+    pub name: String,
+    pub bytes: Bytes,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportStatus {
+    Created,
+    Updated,
+    Skipped,
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportItem {
+    pub file: String,
+    pub status: ImportStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub post_id: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub slug: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ImportReport {
+    pub created: usize,
+    pub updated: usize,
+    pub skipped: usize,
+    pub failed: usize,
+    pub items: Vec<ImportItem>,
+}
+
+impl ImportReport {
+    fn push(&mut self, item: ImportItem) {
+        match item.status {
+            ImportStatus::Created => self.created += 1,
+            ImportStatus::Updated => self.updated += 1,
+            ImportStatus::Skipped => self.skipped += 1,
+            ImportStatus::Failed => self.failed += 1,
+        }
+        self.items.push(item);
+    }
+}
+
+struct ImportContext<'a> {
+    state: &'a AppState,
+    user: &'a user::Model,
+    /// Moderators and above may overwrite any post; authors only the ones
+    /// they can edit.
+    overwrite_any: bool,
+    /// Whether `user` needs an approved review before publishing.
+    needs_approval: bool,
+    options: &'a V1MarkdownImportOptions,
+    assets: HashMap<String, Bytes>,
+    media_cache: HashMap<String, media::Model>,
+}
+
+#[instrument(skip(state, user, files, options), fields(author_id = user.id, file_count = files.len()))]
+pub async fn import(
+    state: &AppState,
+    user: &user::Model,
+    files: Vec<ImportFile>,
+    options: V1MarkdownImportOptions,
+) -> Result<ImportReport, ErrorResponse> {
+    let mut documents: Vec<(String, Bytes)> = Vec::new();
+    let mut assets: HashMap<String, Bytes> = HashMap::new();
+
+    for file in files {
+        if has_extension(&file.name, &["zip"]) {
+            let entries = tokio::task::spawn_blocking(move || expand_zip(file.bytes))
+                .await
+                .map_err(|err| {
+                    ErrorResponse::new(ErrorCode::InternalServerError).with_details(err.to_string())
+                })??;
+            for (path, bytes) in entries {
+                if is_markdown(&path) {
+                    documents.push((path, bytes));
+                } else {
+                    assets.insert(path, bytes);
+                }
+            }
+        } else if is_markdown(&file.name) {
+            documents.push((normalize_path(&file.name), file.bytes));
+        } else {
+            assets.insert(normalize_path(&file.name), file.bytes);
+        }
+    }
+
+    if documents.is_empty() {
+        return Err(ErrorResponse::new(ErrorCode::MissingRequiredField)
+            .with_message("No Markdown files (.md) found in upload"));
+    }
+
+    documents.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let mut ctx = ImportContext {
+        state,
+        user,
+        overwrite_any: PermissionService::allows(state, user, permission_service::POST_EDIT_ANY)
+            .await,
+        needs_approval: !post_review::may_publish_directly(state, user).await,
+        options: &options,
+        assets,
+        media_cache: HashMap::new(),
+    };
+
+    let mut report = ImportReport::default();
+    for (path, bytes) in documents {
+        let mut item = ImportItem {
+            file: path.clone(),
+            status: ImportStatus::Failed,
+            post_id: None,
+            slug: None,
+            error: None,
+            warnings: Vec::new(),
+        };
+
+        let result = match String::from_utf8(bytes.to_vec()) {
+            Ok(source) => import_document(&mut ctx, &path, &source, &mut item).await,
+            Err(_) => Err("File is not valid UTF-8".to_string()),
+        };
+
+        if let Err(message) = result {
+            warn!(file = %path, error = %message, "Markdown import failed");
+            item.status = ImportStatus::Failed;
+            item.error = Some(message);
+        }
+        report.push(item);
+    }
+
+    info!(
+        created = report.created,
+        updated = report.updated,
+        skipped = report.skipped,
+        failed = report.failed,
+        "Markdown import finished"
+    );
+
+    Ok(report)
+}
+
+async fn import_document(
+    ctx: &mut ImportContext<'_>,
+    path: &str,
+    source: &str,
+    item: &mut ImportItem,
+) -> Result<(), String> {
+    let ParsedMarkdown {
+        front_matter,
+        mut content,
+    } = parse_document(source)?;
+
+    let title = front_matter
+        .title
+        .clone()
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty())
+        .unwrap_or_else(|| file_stem(path).replace(['-', '_'], " "));
+
+    let slug = front_matter
+        .slug
+        .as_deref()
+        .map(slugify)
+        .filter(|s| !s.is_empty())
+        .unwrap_or_else(|| slugify(&title));
+    item.slug = Some(slug.clone());
+
+    let locale = front_matter.locale.as_deref().and_then(|raw| {
+        let locale = normalize_locale(raw);
+        if locale.is_none() {
+            item.warnings
+                .push(format!("Unknown locale '{}'; using the default", raw));
+        }
+        locale
+    });
+    let lookup_locale = locale.as_deref().unwrap_or(default_locale());
+
+    let existing = post::Entity::find_localized(&ctx.state.sea_db, slug.clone(), lookup_locale)
+        .await
+        .map_err(|err| err.message)?
+        .filter(|post| post.locale == lookup_locale);
+
+    if let Some(existing) = &existing {
+        if ctx.options.on_conflict == ConflictStrategy::Skip {
+            item.status = ImportStatus::Skipped;
+            item.post_id = Some(existing.id);
+            item.warnings
+                .push(format!("A post with slug '{}' already exists", slug));
+            return Ok(());
+        }
+        if !ctx.overwrite_any {
+            let role = post_author::Entity::role_of(&ctx.state.sea_db, existing.id, ctx.user.id)
+                .await
+                .map_err(|err| err.message)?;
+            if !role.is_some_and(|r| r.allows(PostAccess::Edit)) {
+                item.status = ImportStatus::Skipped;
+                item.post_id = Some(existing.id);
+                item.warnings.push(format!(
+                    "A post with slug '{}' already exists and you can't edit it",
+                    slug
+                ));
+                return Ok(());
+            }
+        }
+    }
+
+    resolve_images(ctx, path, &mut content, &mut item.warnings).await;
+
+    let featured_image_id = match front_matter.featured_image.as_deref() {
+        Some(src) if !src.trim().is_empty() => match resolve_media(ctx, path, src.trim()).await {
+            Ok(media) => Some(media.id),
+            Err(message) => {
+                item.warnings
+                    .push(format!("Featured image '{}' skipped: {}", src, message));
+                None
+            }
+        },
+        _ => None,
+    };
+
+    let category_id = match front_matter.category.as_deref() {
+        Some(name) => resolve_category(ctx, name).await?,
+        None => ctx.options.category_id.ok_or_else(|| {
+            "No category in front matter and no default category_id given".to_string()
+        })?,
+    };
+
+    let mut tag_ids = Vec::new();
+    for name in &front_matter.tags {
+        let id = resolve_tag(ctx, name).await?;
+        if !tag_ids.contains(&id) {
+            tag_ids.push(id);
+        }
+    }
+
+    let published_at = front_matter.published_at().unwrap_or_else(|message| {
+        item.warnings.push(message);
+        None
+    });
+    let mut status = front_matter.status().unwrap_or(PostStatus::Draft);
+
+    let document: EditorJsDocument = serde_json::from_value(content)
+        .map_err(|err| format!("Could not build post content: {}", err))?;
+    let excerpt = front_matter
+        .excerpt
+        .clone()
+        .filter(|e| !e.trim().is_empty());
+
+    match existing {
+        None => {
+            if status == PostStatus::Archived {
+                item.warnings
+                    .push("Archived status is not supported on create; imported as draft".into());
+            }
+            if status == PostStatus::Published && ctx.needs_approval {
+                item.warnings
+                    .push("Publishing needs an approved review; imported as draft".into());
+                status = PostStatus::Draft;
+            }
+            let payload = V1CreatePostPayload {
+                title,
+                content: document,
+                published_at,
+                is_published: status == PostStatus::Published,
+                slug,
+                excerpt,
+                featured_image_id,
+                category_id,
+                tag_ids,
+                visibility: PostVisibility::Public,
+                min_role: None,
+                password: None,
+                locale,
+                meta_title: None,
+                meta_description: None,
+                canonical_url: None,
+                noindex: false,
+                og_image_id: None,
+                og_image_variant: None,
+            };
+            payload.validate().map_err(|err| err.to_string())?;
+
+            let created =
+                post::Entity::create(&ctx.state.sea_db, payload.into_new_post(ctx.user.id))
+                    .await
+                    .map_err(|err| err.message)?;
+
+            item.status = ImportStatus::Created;
+            item.post_id = Some(created.id);
+        }
+        Some(existing) => {
+            if check_transition(existing.status, status, false, ctx.needs_approval).is_some() {
+                item.warnings.push(format!(
+                    "Publishing needs an approved review; status kept as {}",
+                    existing.status
+                ));
+                status = existing.status;
+            }
+            let payload = V1UpdatePostPayload {
+                title: Some(title),
+                content: Some(document),
+                published_at,
+                status: Some(status),
+                slug: None,
+                excerpt,
+                featured_image_id: featured_image_id.map(Some),
+                category_id: Some(category_id),
+                tag_ids: Some(tag_ids),
+                visibility: None,
+                min_role: None,
+                password: None,
+                locale: None,
+                meta_title: None,
+                meta_description: None,
+                canonical_url: None,
+                noindex: None,
+                og_image_id: None,
+                og_image_variant: None,
+                updated_at: existing.updated_at,
+            };
+            payload.validate().map_err(|err| err.to_string())?;
+
+            post::Entity::update(&ctx.state.sea_db, existing.id, payload.into_update_post())
+                .await
+                .map_err(|err| err.message)?;
+            post_review::record_transition(
+                ctx.state,
+                existing.id,
+                &existing.title,
+                existing.status,
+                status,
+                ctx.user,
+                None,
+            )
+            .await;
+
+            item.status = ImportStatus::Updated;
+            item.post_id = Some(existing.id);
+        }
+    }
+
+    Ok(())
+}
+
+/// Replace image block sources with stored media; blocks whose image can't be
+/// resolved are dropped with a warning.
+async fn resolve_images(
+    ctx: &mut ImportContext<'_>,
+    doc_path: &str,
+    content: &mut Value,
+    warnings: &mut Vec<String>,
+) {
+    let Some(blocks) = content.get_mut("blocks").and_then(|b| b.as_array_mut()) else {
+        return;
+    };
+
+    let mut resolved = Vec::with_capacity(blocks.len());
+    for mut block in std::mem::take(blocks) {
+        if block["type"] != "image" {
+            resolved.push(block);
+            continue;
+        }
+
+        let src = block["data"]["file"]["url"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string();
+        match resolve_media(ctx, doc_path, &src).await {
+            Ok(media) => {
+                block["data"]["file"] = json!({
+                    "url": media.file_url,
+                    "media_id": media.id,
+                    "width": media.width,
+                    "height": media.height,
+                });
+                resolved.push(block);
+            }
+            Err(message) => warnings.push(format!("Image '{}' skipped: {}", src, message)),
+        }
+    }
+    *blocks = resolved;
+}
+
+async fn resolve_media(
+    ctx: &mut ImportContext<'_>,
+    doc_path: &str,
+    src: &str,
+) -> Result<media::Model, String> {
+    let lower = src.to_ascii_lowercase();
+    let key = if lower.starts_with("http://") || lower.starts_with("https://") {
+        src.to_string()
+    } else if lower.starts_with("data:") {
+        return Err("inline data URIs are not supported".to_string());
+    } else {
+        resolve_asset_path(&ctx.assets, doc_path, src)
+            .ok_or_else(|| "file not found in upload".to_string())?
+    };
+
+    if let Some(media) = ctx.media_cache.get(&key) {
+        return Ok(media.clone());
+    }
+
+    let stored = if let Some(bytes) = ctx.assets.get(&key) {
+        media_storage::store(
+            ctx.state,
+            UploadedFile {
+                bytes: bytes.clone(),
+                original_name: Some(file_name(&key).to_string()),
+                mime_type: media_storage::guess_mime_type(&key).map(str::to_string),
+            },
+            Default::default(),
+            Some(ctx.user.id),
+        )
+        .await
+    } else {
+        media_storage::store_remote(ctx.state, &key, Some(ctx.user.id)).await
+    }
+    .map_err(|err| err.message)?;
+
+    ctx.media_cache.insert(key, stored.media.clone());
+    Ok(stored.media)
+}
+
+async fn resolve_category(ctx: &ImportContext<'_>, name: &str) -> Result<i32, String> {
+    let slug = slugify(name);
+    if slug.is_empty() {
+        return Err(format!("Invalid category '{}'", name));
+    }
+
+    let db = &ctx.state.sea_db;
+    if let Some(found) = category::Entity::find_by_slug(db, slug.clone())
+        .await
+        .map_err(|err| err.message)?
+    {
+        return Ok(found.id);
+    }
+
+    let created = category::Entity::create(
+        db,
+        NewCategory {
+            name: name.trim().to_string(),
+            slug,
+            parent_id: None,
+            description: None,
+            cover_id: None,
+            logo_id: None,
+            color: None,
+            text_color: None,
+            is_active: None,
+            locale: None,
+        },
+    )
+    .await
+    .map_err(|err| err.message)?;
+    Ok(created.id)
+}
+
+async fn resolve_tag(ctx: &ImportContext<'_>, name: &str) -> Result<i32, String> {
+    let slug = slugify(name);
+    if slug.is_empty() {
+        return Err(format!("Invalid tag '{}'", name));
+    }
+
+    let db = &ctx.state.sea_db;
+    if let Some(found) = tag::Entity::find_by_slug(db, slug.clone())
+        .await
+        .map_err(|err| err.message)?
+    {
+        return Ok(found.id);
+    }
+
+    let created = tag::Entity::create(
+        db,
+        NewTag {
+            name: name.trim().to_string(),
+            slug,
+            description: None,
+            color: None,
+            text_color: None,
+            is_active: None,
+            locale: None,
+        },
+    )
+    .await
+    .map_err(|err| err.message)?;
+    Ok(created.id)
+}
+
+enum ArchiveError {
+    Invalid(String),
+    TooLarge,
+}
+
+impl From<ArchiveError> for ErrorResponse {
+    fn from(err: ArchiveError) -> Self {
+        match err {
+            ArchiveError::Invalid(details) => ErrorResponse::new(ErrorCode::InvalidFormat)
+                .with_message("Could not read zip archive")
+                .with_details(details),
+            ArchiveError::TooLarge => {
+                ErrorResponse::new(ErrorCode::FileTooLarge).with_message(format!(
+                    "Archive expands beyond {}MiB",
+                    MAX_ARCHIVE_EXPANDED / 1024 / 1024
+                ))
+            }
+        }
+    }
+}
+
+fn expand_zip(bytes: Bytes) -> Result<Vec<(String, Bytes)>, ArchiveError> {
+    let invalid = |err: zip::result::ZipError| ArchiveError::Invalid(err.to_string());
+
+    let mut archive = zip::ZipArchive::new(Cursor::new(bytes)).map_err(invalid)?;
+    let mut entries = Vec::new();
+    let mut expanded: u64 = 0;
+
+    for idx in 0..archive.len() {
+        let entry = archive.by_index(idx).map_err(invalid)?;
+        if entry.is_dir() {
+            continue;
+        }
+        let Some(path) = entry.enclosed_name() else {
+            continue;
+        };
+        let path = normalize_path(&path.to_string_lossy());
+        if path.starts_with("__MACOSX/") || file_name(&path).starts_with('.') {
+            continue;
+        }
+
+        // Declared sizes can't be trusted; cap what is actually read.
+        let remaining = MAX_ARCHIVE_EXPANDED - expanded;
+        let mut buf = Vec::new();
+        entry
+            .take(remaining + 1)
+            .read_to_end(&mut buf)
+            .map_err(|err| ArchiveError::Invalid(err.to_string()))?;
+        expanded += buf.len() as u64;
+        if expanded > MAX_ARCHIVE_EXPANDED {
+            return Err(ArchiveError::TooLarge);
+        }
+        entries.push((path, Bytes::from(buf)));
+    }
+
+    Ok(entries)
+}
+
+/// Look up an image `src` among the uploaded assets: first relative to the
+/// document, then from the upload root, then by bare file name.
+fn resolve_asset_path(
+    assets: &HashMap<String, Bytes>,
+    doc_path: &str,
+    src: &str,
+) -> Option<String> {
+    let src = src.split(['?', '#']).next().unwrap_or(src);
+    let src = urlencoding::decode(src)
+        .map(|s| s.into_owned())
+        .unwrap_or_else(|_| src.to_string());
+
+    let dir = doc_path.rsplit_once('/').map(|(dir, _)| dir).unwrap_or("");
+    let candidates = [
+        normalize_path(&format!("{}/{}", dir, src)),
+        normalize_path(&src),
+    ];
+    if let Some(found) = candidates.into_iter().find(|c| assets.contains_key(c)) {
+        return Some(found);
+    }
+
+    let name = file_name(&src);
+    let mut matches = assets.keys().filter(|key| file_name(key) == name);
+    match (matches.next(), matches.next()) {
+        (Some(only), None) => Some(only.clone()),
+        _ => None,
+    }
+}
+
+/// Collapse `.`/`..` segments and leading slashes into a zip-style path.
+fn normalize_path(path: &str) -> String {
+    let mut parts: Vec<&str> = Vec::new();
+    for part in path.split(['/', '\\']) {
+        match part {
+            "" | "." => {}
+            ".." => {
+                parts.pop();
+            }
+            other => parts.push(other),
+        }
+    }
+    parts.join("/")
+}
+
+fn file_name(path: &str) -> &str {
+    path.rsplit('/').next().unwrap_or(path)
+}
+
+fn file_stem(path: &str) -> &str {
+    let name = file_name(path);
+    name.rsplit_once('.').map(|(stem, _)| stem).unwrap_or(name)
+}
+
+fn has_extension(name: &str, extensions: &[&str]) -> bool {
+    name.rsplit_once('.')
+        .map(|(_, ext)| extensions.iter().any(|e| ext.eq_ignore_ascii_case(e)))
+        .unwrap_or(false)
+}
+
+fn is_markdown(name: &str) -> bool {
+    has_extension(name, &["md", "markdown"])
+}
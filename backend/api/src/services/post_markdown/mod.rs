@@ -0,0 +1,345 @@
+//! Markdown import and export for posts.
+//!
+//! Exported files are CommonMark with a YAML front matter block carrying the
+//! post metadata; the body comes from `content_render::to_markdown`. Imports
+//! accept the same shape (plus the common Jekyll/Hugo/Ghost front matter
+//! aliases) and convert the body back into Editor.js blocks.
+
+pub mod import;
+mod parse;
+
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::{json, Value};
+
+use crate::db::sea_models::post::{PostStatus, PostWithRelations};
+use crate::services::content_render;
+
+pub use parse::markdown_to_blocks;
+
+pub(crate) const EDITOR_JS_VERSION: &str = "2.30.7";
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FrontMatter {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub slug: Option<String>,
+    #[serde(
+        default,
+        alias = "description",
+        alias = "summary",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub excerpt: Option<String>,
+    #[serde(
+        default,
+        alias = "categories",
+        deserialize_with = "first_of_string_or_list",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub category: Option<String>,
+    #[serde(
+        default,
+        alias = "tag",
+        deserialize_with = "string_or_list",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    #[serde(default, skip_serializing)]
+    pub draft: Option<bool>,
+    #[serde(
+        default,
+        alias = "date",
+        alias = "published",
+        deserialize_with = "scalar_string",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub published_at: Option<String>,
+    #[serde(
+        default,
+        alias = "cover",
+        alias = "image",
+        alias = "feature_image",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub featured_image: Option<String>,
+    #[serde(
+        default,
+        alias = "lang",
+        alias = "language",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub locale: Option<String>,
+}
+
+impl FrontMatter {
+    /// Status from either `status:` or the Hugo-style `draft:` flag.
+    pub fn status(&self) -> Option<PostStatus> {
+        match self
+            .status
+            .as_deref()
+            .map(|s| s.trim().to_ascii_lowercase())
+        {
+            Some(s) if s == "published" || s == "publish" => Some(PostStatus::Published),
+            Some(s) if s == "archived" => Some(PostStatus::Archived),
+            Some(s) if s == "draft" => Some(PostStatus::Draft),
+            _ => self.draft.map(|draft| {
+                if draft {
+                    PostStatus::Draft
+                } else {
+                    PostStatus::Published
+                }
+            }),
+        }
+    }
+
+    /// Accepts RFC 3339, `YYYY-MM-DD HH:MM[:SS]` (UTC) and bare dates.
+    pub fn published_at(&self) -> Result<Option<DateTime<FixedOffset>>, String> {
+        let Some(raw) = self.published_at.as_deref().map(str::trim) else {
+            return Ok(None);
+        };
+        if raw.is_empty() {
+            return Ok(None);
+        }
+        if let Ok(dt) = DateTime::parse_from_rfc3339(raw) {
+            return Ok(Some(dt));
+        }
+        for format in ["%Y-%m-%d %H:%M:%S", "%Y-%m-%d %H:%M", "%Y-%m-%dT%H:%M:%S"] {
+            if let Ok(naive) = NaiveDateTime::parse_from_str(raw, format) {
+                return Ok(Some(naive.and_utc().fixed_offset()));
+            }
+        }
+        if let Ok(date) = NaiveDate::parse_from_str(raw, "%Y-%m-%d") {
+            return Ok(date
+                .and_hms_opt(0, 0, 0)
+                .map(|naive| naive.and_utc().fixed_offset()));
+        }
+        Err(format!("Unrecognised date: {}", raw))
+    }
+}
+
+/// A Markdown file split into metadata and an Editor.js document.
+#[derive(Debug, Clone)]
+pub struct ParsedMarkdown {
+    pub front_matter: FrontMatter,
+    pub content: Value,
+}
+
+/// Parse a Markdown file with optional front matter. When the front matter
+/// has no title, a leading level-1 heading is used (and removed from the body).
+pub fn parse_document(source: &str) -> Result<ParsedMarkdown, String> {
+    let (mut front_matter, body) = split_front_matter(source)?;
+    let mut blocks = markdown_to_blocks(body);
+
+    let leading_h1 = blocks
+        .first()
+        .filter(|b| b["type"] == "header" && b["data"]["level"] == 1)
+        .and_then(|b| b["data"]["text"].as_str())
+        .map(content_render::inline_to_text);
+
+    if let Some(heading) = leading_h1 {
+        match front_matter.title.as_deref() {
+            None => {
+                front_matter.title = Some(heading);
+                blocks.remove(0);
+            }
+            Some(title) if title.trim() == heading => {
+                blocks.remove(0);
+            }
+            _ => {}
+        }
+    }
+
+    Ok(ParsedMarkdown {
+        front_matter,
+        content: json!({
+            "time": chrono::Utc::now().timestamp_millis(),
+            "blocks": blocks,
+            "version": EDITOR_JS_VERSION,
+        }),
+    })
+}
+
+fn split_front_matter(source: &str) -> Result<(FrontMatter, &str), String> {
+    let source = source.trim_start_matches('\u{feff}');
+    let Some(rest) = source
+        .strip_prefix("---\n")
+        .or_else(|| source.strip_prefix("---\r\n"))
+    else {
+        return Ok((FrontMatter::default(), source));
+    };
+
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        let trimmed = line.trim_end();
+        if trimmed == "---" || trimmed == "..." {
+            let yaml = &rest[..offset];
+            let body = &rest[offset + line.len()..];
+            let front_matter = if yaml.trim().is_empty() {
+                FrontMatter::default()
+            } else {
+                serde_yaml::from_str(yaml)
+                    .map_err(|err| format!("Invalid front matter: {}", err))?
+            };
+            return Ok((front_matter, body));
+        }
+        offset += line.len();
+    }
+
+    Err("Front matter block is not closed".to_string())
+}
+
+/// Render a post as a Markdown file with YAML front matter.
+pub fn export(post: &PostWithRelations) -> String {
+    let front_matter = FrontMatter {
+        title: Some(post.title.clone()),
+        slug: Some(post.slug.clone()),
+        excerpt: post.excerpt.clone().filter(|e| !e.trim().is_empty()),
+        category: Some(post.category.slug.clone()),
+        tags: post.tags.iter().map(|t| t.slug.clone()).collect(),
+        status: Some(post.status.to_string()),
+        draft: None,
+        published_at: post.published_at.map(|dt| dt.to_rfc3339()),
+        featured_image: post.featured_image.as_ref().map(|m| m.file_url.clone()),
+        locale: Some(post.locale.clone()),
+    };
+
+    let yaml = serde_yaml::to_string(&front_matter).unwrap_or_default();
+    let body = content_render::to_markdown(&post.content);
+
+    format!("---\n{}---\n\n{}\n", yaml, body.trim_end())
+}
+
+fn string_or_list<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum StringOrList {
+        One(String),
+        Many(Vec<String>),
+    }
+
+    Ok(match Option::<StringOrList>::deserialize(deserializer)? {
+        None => Vec::new(),
+        Some(StringOrList::One(s)) => s
+            .split(',')
+            .map(|t| t.trim().to_string())
+            .filter(|t| !t.is_empty())
+            .collect(),
+        Some(StringOrList::Many(list)) => list
+            .into_iter()
+            .map(|t| t.trim().to_string())
+            .filter(|t| !t.is_empty())
+            .collect(),
+    })
+}
+
+fn first_of_string_or_list<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(string_or_list(deserializer)?.into_iter().next())
+}
+
+/// YAML parses unquoted dates and numbers as non-strings; accept any scalar.
+fn scalar_string<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(
+        match Option::<serde_yaml::Value>::deserialize(deserializer)? {
+            Some(serde_yaml::Value::String(s)) => Some(s),
+            Some(serde_yaml::Value::Number(n)) => Some(n.to_string()),
+            Some(serde_yaml::Value::Bool(b)) => Some(b.to_string()),
+            _ => None,
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn front_matter_and_body() {
+        let source = "---\ntitle: Hello\ntags: rust, web\ncategories: [notes]\ndate: 2024-05-01\ndraft: false\n---\n\nSome **bold** and `code`.\n\n![A cat](images/cat.png)\n\n- [x] done\n- [ ] todo\n";
+        let parsed = parse_document(source).unwrap();
+
+        assert_eq!(parsed.front_matter.title.as_deref(), Some("Hello"));
+        assert_eq!(parsed.front_matter.tags, vec!["rust", "web"]);
+        assert_eq!(parsed.front_matter.category.as_deref(), Some("notes"));
+        assert_eq!(parsed.front_matter.status(), Some(PostStatus::Published));
+        assert!(parsed.front_matter.published_at().unwrap().is_some());
+
+        let blocks = parsed.content["blocks"].as_array().unwrap();
+        assert_eq!(
+            blocks[0]["data"]["text"],
+            "Some <b>bold</b> and <code>code</code>."
+        );
+        assert_eq!(blocks[1]["type"], "image");
+        assert_eq!(blocks[1]["data"]["file"]["url"], "images/cat.png");
+        assert_eq!(blocks[2]["type"], "checklist");
+    }
+
+    #[test]
+    fn leading_heading_becomes_title() {
+        let parsed = parse_document("# My Post\n\nBody text").unwrap();
+        assert_eq!(parsed.front_matter.title.as_deref(), Some("My Post"));
+        assert_eq!(parsed.content["blocks"].as_array().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn exported_blocks_round_trip() {
+        let original = json!({
+            "blocks": [
+                { "type": "header", "data": { "text": "Intro", "level": 2 } },
+                { "type": "paragraph", "data": { "text": "Read <a href=\"https://example.com\">this</a> <i>now</i>" } },
+                { "type": "list", "data": { "style": "ordered", "items": ["one", "two"] } },
+                { "type": "quote", "data": { "text": "Be brief", "caption": "Someone" } },
+                { "type": "alert", "data": { "type": "warning", "message": "Careful" } },
+                { "type": "warning", "data": { "title": "Heads up", "message": "Mind the gap" } },
+                { "type": "code", "data": { "code": "fn main() {}\n```", "language": "rust" } },
+                { "type": "table", "data": { "withHeadings": false, "content": [["a", "b"], ["1", "2"]] } },
+                { "type": "delimiter", "data": {} }
+            ]
+        });
+        let markdown = content_render::to_markdown(&original);
+        let blocks = markdown_to_blocks(&markdown);
+
+        let kinds: Vec<&str> = blocks.iter().map(|b| b["type"].as_str().unwrap()).collect();
+        assert_eq!(
+            kinds,
+            [
+                "header",
+                "paragraph",
+                "list",
+                "quote",
+                "alert",
+                "warning",
+                "code",
+                "table",
+                "delimiter"
+            ]
+        );
+        assert_eq!(
+            blocks[1]["data"]["text"],
+            original["blocks"][1]["data"]["text"]
+        );
+        assert_eq!(blocks[2]["data"]["items"], json!(["one", "two"]));
+        assert_eq!(blocks[3]["data"]["caption"], "Someone");
+        assert_eq!(blocks[4]["data"]["type"], "warning");
+        assert_eq!(blocks[4]["data"]["message"], "Careful");
+        assert_eq!(blocks[5]["data"]["title"], "Heads up");
+        assert_eq!(blocks[6]["data"]["code"], "fn main() {}\n```");
+        assert_eq!(blocks[7]["data"]["withHeadings"], false);
+        assert_eq!(
+            blocks[7]["data"]["content"],
+            json!([["a", "b"], ["1", "2"]])
+        );
+    }
+}
@@ -0,0 +1,528 @@
+use pulldown_cmark::{
+    BlockQuoteKind, CodeBlockKind, Event, HeadingLevel, Options, Parser, Tag, TagEnd,
+};
+use serde_json::{json, Value};
+
+use crate::services::content_render::escape_html;
+
+/// Convert a Markdown body into Editor.js blocks.
+///
+/// Inline formatting is emitted as the same small HTML subset the editor
+/// produces (`<b>`, `<i>`, `<s>`, `<code>`, `<a>`, `<br>`). Image blocks keep
+/// the raw `src` in `file.url` and have no `media_id`; the importer resolves
+/// them to stored media afterwards.
+pub fn markdown_to_blocks(markdown: &str) -> Vec<Value> {
+    let options = Options::ENABLE_TABLES
+        | Options::ENABLE_STRIKETHROUGH
+        | Options::ENABLE_TASKLISTS
+        | Options::ENABLE_GFM;
+
+    let mut converter = Converter::default();
+    for event in Parser::new_ext(markdown, options) {
+        converter.handle(event);
+    }
+    converter.blocks
+}
+
+enum Container {
+    Quote {
+        kind: Option<BlockQuoteKind>,
+        paragraphs: Vec<String>,
+    },
+    List {
+        ordered: bool,
+        items: Vec<ListEntry>,
+    },
+    Item(ListEntry),
+}
+
+#[derive(Default)]
+struct ListEntry {
+    text: String,
+    checked: Option<bool>,
+    /// Nested list items, already flattened. The stored list format only
+    /// accepts plain string items, so nesting is not preserved.
+    children: Vec<ListEntry>,
+}
+
+struct TableState {
+    rows: Vec<Vec<String>>,
+    header_rows: usize,
+}
+
+#[derive(Default)]
+struct Converter {
+    blocks: Vec<Value>,
+    stack: Vec<Container>,
+    inline: String,
+    /// Inline tags currently open in `inline`; images can only be hoisted
+    /// into their own block when this is zero.
+    open_inline: usize,
+    code: Option<(Option<String>, String)>,
+    html: Option<String>,
+    table: Option<TableState>,
+    image: Option<(String, String)>,
+    in_heading: bool,
+}
+
+impl Converter {
+    fn handle(&mut self, event: Event) {
+        if let Some((_, code)) = self.code.as_mut() {
+            match event {
+                Event::Text(text) => code.push_str(&text),
+                Event::End(TagEnd::CodeBlock) => self.finish_code(),
+                _ => {}
+            }
+            return;
+        }
+
+        if let Some((_, alt)) = self.image.as_mut() {
+            match event {
+                Event::Text(text) | Event::Code(text) => alt.push_str(&text),
+                Event::End(TagEnd::Image) => self.finish_image(),
+                _ => {}
+            }
+            return;
+        }
+
+        match event {
+            Event::Start(tag) => self.start(tag),
+            Event::End(tag) => self.end(tag),
+            Event::Text(text) => {
+                if let Some(html) = self.html.as_mut() {
+                    html.push_str(&text);
+                } else {
+                    self.inline.push_str(&escape_html(&text));
+                }
+            }
+            Event::Code(text) => {
+                self.inline
+                    .push_str(&format!("<code>{}</code>", escape_html(&text)));
+            }
+            Event::Html(html) => match self.html.as_mut() {
+                Some(buffer) => buffer.push_str(&html),
+                None => self.inline.push_str(&html),
+            },
+            Event::InlineHtml(html) => self.inline.push_str(&html),
+            Event::SoftBreak => self.inline.push(' '),
+            Event::HardBreak => self.inline.push_str("<br>"),
+            Event::Rule if self.stack.is_empty() => {
+                self.blocks.push(block("delimiter", json!({})));
+            }
+            Event::TaskListMarker(checked) => {
+                if let Some(Container::Item(entry)) = self.stack.last_mut() {
+                    entry.checked = Some(checked);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn start(&mut self, tag: Tag) {
+        match tag {
+            Tag::BlockQuote(kind) => {
+                self.flush_into_item();
+                self.stack.push(Container::Quote {
+                    kind,
+                    paragraphs: Vec::new(),
+                });
+            }
+            Tag::List(start) => {
+                self.flush_into_item();
+                self.stack.push(Container::List {
+                    ordered: start.is_some(),
+                    items: Vec::new(),
+                });
+            }
+            Tag::Item => self.stack.push(Container::Item(ListEntry::default())),
+            Tag::CodeBlock(kind) => {
+                let language = match kind {
+                    CodeBlockKind::Fenced(info) => info
+                        .split_whitespace()
+                        .next()
+                        .filter(|l| !l.is_empty())
+                        .map(|l| l.to_string()),
+                    CodeBlockKind::Indented => None,
+                };
+                self.code = Some((language, String::new()));
+            }
+            Tag::Heading { .. } => self.in_heading = true,
+            Tag::HtmlBlock => self.html = Some(String::new()),
+            Tag::Table(_) => {
+                self.table = Some(TableState {
+                    rows: Vec::new(),
+                    header_rows: 0,
+                })
+            }
+            Tag::TableHead | Tag::TableRow => {
+                if let Some(table) = self.table.as_mut() {
+                    table.rows.push(Vec::new());
+                }
+            }
+            Tag::Emphasis => self.open("<i>"),
+            Tag::Strong => self.open("<b>"),
+            Tag::Strikethrough => self.open("<s>"),
+            Tag::Superscript => self.open("<sup>"),
+            Tag::Subscript => self.open("<sub>"),
+            Tag::Link {
+                dest_url, title, ..
+            } => {
+                let title = if title.is_empty() {
+                    String::new()
+                } else {
+                    format!(" title=\"{}\"", escape_html(&title))
+                };
+                self.open(&format!("<a href=\"{}\"{}>", escape_html(&dest_url), title));
+            }
+            Tag::Image { dest_url, .. } => self.image = Some((dest_url.to_string(), String::new())),
+            _ => {}
+        }
+    }
+
+    fn end(&mut self, tag: TagEnd) {
+        match tag {
+            TagEnd::Paragraph => {
+                let text = self.take_inline();
+                self.push_text(text, "paragraph", |text| json!({ "text": text }));
+            }
+            TagEnd::Heading(level) => {
+                self.in_heading = false;
+                let text = self.take_inline();
+                let level = heading_level(level);
+                self.push_text(
+                    text,
+                    "header",
+                    |text| json!({ "text": text, "level": level }),
+                );
+            }
+            TagEnd::BlockQuote(_) => self.finish_quote(),
+            TagEnd::Item => {
+                self.flush_into_item();
+                let Some(Container::Item(entry)) = self.stack.pop() else {
+                    return;
+                };
+                if let Some(Container::List { items, .. }) = self.stack.last_mut() {
+                    items.push(entry);
+                }
+            }
+            TagEnd::List(_) => self.finish_list(),
+            TagEnd::HtmlBlock => {
+                if let Some(html) = self.html.take().filter(|h| !h.trim().is_empty()) {
+                    let html = html.trim().to_string();
+                    self.push_text(html, "raw", |html| json!({ "html": html }));
+                }
+            }
+            TagEnd::TableHead => {
+                if let Some(table) = self.table.as_mut() {
+                    table.header_rows = table.rows.len();
+                }
+            }
+            TagEnd::TableCell => {
+                let cell = self.take_inline();
+                if let Some(row) = self.table.as_mut().and_then(|t| t.rows.last_mut()) {
+                    row.push(cell);
+                }
+            }
+            TagEnd::Table => self.finish_table(),
+            TagEnd::Emphasis => self.close("</i>"),
+            TagEnd::Strong => self.close("</b>"),
+            TagEnd::Strikethrough => self.close("</s>"),
+            TagEnd::Superscript => self.close("</sup>"),
+            TagEnd::Subscript => self.close("</sub>"),
+            TagEnd::Link => self.close("</a>"),
+            _ => {}
+        }
+    }
+
+    fn open(&mut self, html: &str) {
+        self.open_inline += 1;
+        self.inline.push_str(html);
+    }
+
+    fn close(&mut self, html: &str) {
+        self.open_inline = self.open_inline.saturating_sub(1);
+        self.inline.push_str(html);
+    }
+
+    fn take_inline(&mut self) -> String {
+        self.open_inline = 0;
+        std::mem::take(&mut self.inline).trim().to_string()
+    }
+
+    /// Route a finished run of inline text to the innermost container, or emit
+    /// it as a top-level block.
+    fn push_text(&mut self, text: String, kind: &str, data: impl FnOnce(String) -> Value) {
+        if text.is_empty() {
+            return;
+        }
+        match self.stack.last_mut() {
+            Some(Container::Quote { paragraphs, .. }) => paragraphs.push(text),
+            Some(Container::Item(entry)) => append_line(&mut entry.text, &text),
+            Some(Container::List { items, .. }) => items.push(ListEntry {
+                text,
+                ..Default::default()
+            }),
+            None => self.blocks.push(block(kind, data(text))),
+        }
+    }
+
+    /// Tight list items carry their text without paragraph events; move any
+    /// pending inline text into the open item before a nested block starts.
+    fn flush_into_item(&mut self) {
+        if matches!(self.stack.last(), Some(Container::Item(_))) {
+            let text = self.take_inline();
+            if let Some(Container::Item(entry)) = self.stack.last_mut() {
+                if !text.is_empty() {
+                    append_line(&mut entry.text, &text);
+                }
+            }
+        }
+    }
+
+    fn finish_code(&mut self) {
+        let Some((language, code)) = self.code.take() else {
+            return;
+        };
+        let code = code.strip_suffix('\n').unwrap_or(&code).to_string();
+        if code.is_empty() {
+            return;
+        }
+
+        if self.stack.is_empty() {
+            let mut data = json!({ "code": code });
+            if let Some(language) = language {
+                data["language"] = json!(language);
+            }
+            self.blocks.push(block("code", data));
+        } else {
+            let text = format!("<code>{}</code>", escape_html(&code).replace('\n', "<br>"));
+            self.push_text(text, "paragraph", |text| json!({ "text": text }));
+        }
+    }
+
+    fn finish_image(&mut self) {
+        let Some((url, alt)) = self.image.take() else {
+            return;
+        };
+        let alt = alt.trim().to_string();
+
+        if !self.stack.is_empty() || self.open_inline > 0 || self.in_heading || self.table.is_some()
+        {
+            // Images only stand alone at the top level; elsewhere keep a link.
+            let label = if alt.is_empty() { url.clone() } else { alt };
+            self.inline.push_str(&format!(
+                "<a href=\"{}\">{}</a>",
+                escape_html(&url),
+                escape_html(&label)
+            ));
+            return;
+        }
+
+        let before = self.take_inline();
+        if !before.is_empty() {
+            self.blocks
+                .push(block("paragraph", json!({ "text": before })));
+        }
+        self.blocks.push(block(
+            "image",
+            json!({
+                "file": { "url": url },
+                "caption": escape_html(&alt),
+                "withBorder": false,
+                "withBackground": false,
+                "stretched": false,
+            }),
+        ));
+    }
+
+    fn finish_quote(&mut self) {
+        self.flush_into_item();
+        let Some(Container::Quote { kind, paragraphs }) = self.stack.pop() else {
+            return;
+        };
+        if paragraphs.is_empty() {
+            return;
+        }
+
+        if !self.stack.is_empty() {
+            for paragraph in paragraphs {
+                self.push_text(paragraph, "paragraph", |text| json!({ "text": text }));
+            }
+            return;
+        }
+
+        let converted = quote_to_block(kind, paragraphs);
+        self.blocks.push(converted);
+    }
+
+    fn finish_list(&mut self) {
+        let Some(Container::List { ordered, items }) = self.stack.pop() else {
+            return;
+        };
+        if items.is_empty() {
+            return;
+        }
+
+        match self.stack.last_mut() {
+            Some(Container::Item(parent)) => parent.children.extend(items),
+            Some(Container::Quote { paragraphs, .. }) => {
+                paragraphs.extend(flatten(items).into_iter().map(|entry| entry.text))
+            }
+            Some(Container::List { items: outer, .. }) => outer.extend(items),
+            None => {
+                let flat = flatten(items);
+                if flat.iter().all(|entry| entry.checked.is_some()) {
+                    let items = flat
+                        .into_iter()
+                        .map(|entry| {
+                            json!({ "text": entry.text, "checked": entry.checked.unwrap_or(false) })
+                        })
+                        .collect::<Vec<_>>();
+                    self.blocks
+                        .push(block("checklist", json!({ "items": items })));
+                } else {
+                    let items = flat
+                        .into_iter()
+                        .map(|entry| Value::String(entry.text))
+                        .collect::<Vec<_>>();
+                    self.blocks.push(block(
+                        "list",
+                        json!({
+                            "style": if ordered { "ordered" } else { "unordered" },
+                            "items": items,
+                        }),
+                    ));
+                }
+            }
+        }
+    }
+
+    fn finish_table(&mut self) {
+        let Some(mut table) = self.table.take() else {
+            return;
+        };
+
+        // Exported tables without headings carry an empty header row.
+        let mut with_headings = table.header_rows > 0;
+        if with_headings
+            && table
+                .rows
+                .first()
+                .is_some_and(|row| row.iter().all(|cell| cell.is_empty()))
+        {
+            table.rows.remove(0);
+            with_headings = false;
+        }
+        if table.rows.is_empty() {
+            return;
+        }
+
+        self.blocks.push(block(
+            "table",
+            json!({ "withHeadings": with_headings, "content": table.rows }),
+        ));
+    }
+}
+
+fn block(kind: &str, data: Value) -> Value {
+    json!({ "type": kind, "data": data })
+}
+
+fn append_line(target: &mut String, text: &str) {
+    if !target.is_empty() {
+        target.push_str("<br>");
+    }
+    target.push_str(text);
+}
+
+fn flatten(items: Vec<ListEntry>) -> Vec<ListEntry> {
+    let mut flat = Vec::new();
+    for mut item in items {
+        let children = std::mem::take(&mut item.children);
+        if !item.text.is_empty() {
+            flat.push(item);
+        }
+        flat.extend(flatten(children));
+    }
+    flat
+}
+
+fn heading_level(level: HeadingLevel) -> u8 {
+    match level {
+        HeadingLevel::H1 => 1,
+        HeadingLevel::H2 => 2,
+        HeadingLevel::H3 => 3,
+        HeadingLevel::H4 => 4,
+        HeadingLevel::H5 => 5,
+        HeadingLevel::H6 => 6,
+    }
+}
+
+const ALERT_PREFIXES: [(&str, &str); 4] = [
+    ("<b>Info:</b>", "info"),
+    ("<b>Warning:</b>", "warning"),
+    ("<b>Success:</b>", "success"),
+    ("<b>Error:</b>", "error"),
+];
+
+/// Map a blockquote back onto the richest block the exporter would have
+/// produced it from: GitHub-style alerts and `**Kind:**` prefixes become
+/// alerts, a bold title line becomes a warning, and a trailing `— source`
+/// line becomes a quote caption.
+fn quote_to_block(kind: Option<BlockQuoteKind>, mut paragraphs: Vec<String>) -> Value {
+    if let Some(kind) = kind {
+        let alert_type = match kind {
+            BlockQuoteKind::Note | BlockQuoteKind::Tip | BlockQuoteKind::Important => "info",
+            BlockQuoteKind::Warning => "warning",
+            BlockQuoteKind::Caution => "error",
+        };
+        return block(
+            "alert",
+            json!({ "type": alert_type, "message": paragraphs.join("<br>"), "align": "left" }),
+        );
+    }
+
+    for (prefix, alert_type) in ALERT_PREFIXES {
+        if let Some(rest) = paragraphs[0].strip_prefix(prefix) {
+            paragraphs[0] = rest.trim_start().to_string();
+            return block(
+                "alert",
+                json!({ "type": alert_type, "message": paragraphs.join("<br>"), "align": "left" }),
+            );
+        }
+    }
+
+    if paragraphs.len() >= 2 {
+        let first = &paragraphs[0];
+        if let Some(title) = first
+            .strip_prefix("<b>")
+            .and_then(|t| t.strip_suffix("</b>"))
+            .filter(|t| !t.contains('<'))
+        {
+            return block(
+                "warning",
+                json!({ "title": title, "message": paragraphs[1..].join("<br>") }),
+            );
+        }
+    }
+
+    let caption = match paragraphs.last() {
+        Some(last) if paragraphs.len() >= 2 => last
+            .strip_prefix('—')
+            .or_else(|| last.strip_prefix("--"))
+            .map(|c| c.trim().to_string()),
+        _ => None,
+    };
+    if caption.is_some() {
+        paragraphs.pop();
+    }
+
+    block(
+        "quote",
+        json!({
+            "text": paragraphs.join("<br>"),
+            "caption": caption.unwrap_or_default(),
+            "alignment": "left",
+        }),
+    )
+}
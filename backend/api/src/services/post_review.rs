@@ -0,0 +1,234 @@
+//! Editorial review for `post_v1`.
+//!
+//! Status changes made by contributors are checked against the review rules
+//! (`post_status_transition::check_transition`), recorded in the post's
+//! history and announced by email: submissions go to everyone holding
+//! `post.review`, every other change to the post's contributors. Mail is sent in the
+//! background; failures are only logged.
+//!
+//! Whether authors need an approved review before publishing is the
+//! `POST_REQUIRE_APPROVAL` app constant, falling back to the environment
+//! variable of the same name. It is off by default.
+
+use sea_orm::{ColumnTrait, Condition, EntityTrait, QueryFilter};
+use serde_json::json;
+use tracing::{info, info_span, warn, Instrument};
+
+use crate::{
+    db::sea_models::{
+        post::{self, PostStatus},
+        post_author,
+        post_status_transition::{
+            self, check_transition, status_after_unrequested_edit, TransitionDenied,
+        },
+        user::{self, UserRole},
+        user_permission,
+    },
+    error::{ErrorCode, ErrorResponse},
+    services::{
+        mail,
+        permission_service::{self, PermissionService},
+        settings,
+    },
+    AppState,
+};
+
+pub async fn approval_required(state: &AppState) -> bool {
+    settings::POST_REQUIRE_APPROVAL.bool(state).await
+}
+
+/// Whether `user` may publish a post that hasn't been approved.
+pub async fn may_publish_directly(state: &AppState, user: &user::Model) -> bool {
+    PermissionService::allows(state, user, permission_service::POST_PUBLISH).await
+        || !approval_required(state).await
+}
+
+pub fn needs_approval_error() -> ErrorResponse {
+    ErrorResponse::new(ErrorCode::OperationNotAllowed)
+        .with_message("This post needs an approved review before it can be published")
+        .with_context(json!({ "required_status": PostStatus::Approved }))
+}
+
+/// Refuse a status change `user` isn't allowed to make.
+pub async fn ensure_transition(
+    state: &AppState,
+    user: &user::Model,
+    from: PostStatus,
+    to: PostStatus,
+) -> Result<(), ErrorResponse> {
+    let approval = to == PostStatus::Published && !may_publish_directly(state, user).await;
+    // Reviewers may move a post anywhere, but still need `post.publish` to
+    // skip approval.
+    let reviewer =
+        !approval && PermissionService::allows(state, user, permission_service::POST_REVIEW).await;
+    match check_transition(from, to, reviewer, approval) {
+        None => Ok(()),
+        Some(TransitionDenied::ModeratorOnly) => {
+            Err(ErrorResponse::new(ErrorCode::OperationNotAllowed)
+                .with_message("Only moderators and admins can approve posts or request changes"))
+        }
+        Some(TransitionDenied::NeedsApproval) => Err(needs_approval_error()),
+    }
+}
+
+/// The status an edit leaves `post` in. A requested change is checked; an
+/// edit to an approved post without one sends it back to review unless the
+/// editor may publish directly.
+pub async fn status_after_edit(
+    state: &AppState,
+    user: &user::Model,
+    post: &post::Model,
+    requested: Option<PostStatus>,
+) -> Result<Option<PostStatus>, ErrorResponse> {
+    match requested {
+        Some(to) => {
+            ensure_transition(state, user, post.status, to).await?;
+            Ok(Some(to))
+        }
+        None if post.status == PostStatus::Approved => Ok(status_after_unrequested_edit(
+            post.status,
+            may_publish_directly(state, user).await,
+        )),
+        None => Ok(None),
+    }
+}
+
+/// Record a status change in the post's history and email the people it
+/// concerns. A failed insert is logged but doesn't fail the change itself.
+pub async fn record_transition(
+    state: &AppState,
+    post_id: i32,
+    post_title: &str,
+    from: PostStatus,
+    to: PostStatus,
+    actor: &user::Model,
+    note: Option<String>,
+) {
+    if from == to {
+        return;
+    }
+    if let Err(err) = post_status_transition::Entity::record(
+        &state.sea_db,
+        post_id,
+        from,
+        to,
+        Some(actor.id),
+        note.clone(),
+    )
+    .await
+    {
+        warn!(error = ?err, post_id, "Failed to record post status transition");
+    }
+
+    notify(
+        state,
+        post_id,
+        post_title.to_string(),
+        to,
+        actor.clone(),
+        note,
+    );
+}
+
+fn headline(to: PostStatus, actor: &str) -> String {
+    match to {
+        PostStatus::InReview => format!("{} submitted a post for review", actor),
+        PostStatus::ChangesRequested => format!("{} requested changes", actor),
+        PostStatus::Approved => format!("{} approved a post", actor),
+        PostStatus::Published => format!("{} published a post", actor),
+        PostStatus::Draft => format!("{} moved a post back to draft", actor),
+        PostStatus::Archived => format!("{} archived a post", actor),
+    }
+}
+
+fn notify(
+    state: &AppState,
+    post_id: i32,
+    post_title: String,
+    to: PostStatus,
+    actor: user::Model,
+    note: Option<String>,
+) {
+    let state = state.clone();
+    tokio::spawn(
+        async move {
+            let emails = match recipients(&state, post_id, to, actor.id).await {
+                Ok(emails) => emails,
+                Err(err) => {
+                    warn!(post_id, error = ?err, "Failed to resolve review notification recipients");
+                    return;
+                }
+            };
+            let headline = headline(to, &actor.name);
+            for email in &emails {
+                if let Err(err) = mail::send_post_status_email(
+                    &state.mailer,
+                    email,
+                    &headline,
+                    &post_title,
+                    note.as_deref(),
+                )
+                .await
+                {
+                    warn!(post_id, error = %err, "Failed to send review notification");
+                }
+            }
+            info!(post_id, %to, recipients = emails.len(), "Review notifications sent");
+        }
+        .instrument(info_span!("post_review_notify", post_id)),
+    );
+}
+
+/// Submissions go to reviewers (`post.review` by role or override);
+/// everything else to the post's contributors. The actor is never mailed
+/// about their own change.
+async fn recipients(
+    state: &AppState,
+    post_id: i32,
+    to: PostStatus,
+    actor_id: i32,
+) -> Result<Vec<String>, ErrorResponse> {
+    let db = &state.sea_db;
+    let users = if to == PostStatus::InReview {
+        let roles = PermissionService::roles_with(state, permission_service::POST_REVIEW).await?;
+        let overrides =
+            user_permission::Entity::for_permission(db, permission_service::POST_REVIEW).await?;
+        let granted: Vec<i32> = overrides
+            .iter()
+            .filter(|o| o.granted)
+            .map(|o| o.user_id)
+            .collect();
+        let revoked: Vec<i32> = overrides
+            .iter()
+            .filter(|o| !o.granted)
+            .map(|o| o.user_id)
+            .collect();
+        user::Entity::find()
+            .filter(
+                Condition::any()
+                    .add(user::Column::Role.is_in(roles))
+                    .add(user::Column::Id.is_in(granted)),
+            )
+            .all(db)
+            .await?
+            .into_iter()
+            .filter(|u| u.role == UserRole::SuperAdmin || !revoked.contains(&u.id))
+            .collect()
+    } else {
+        let ids: Vec<i32> = post_author::Entity::list_for_post(db, post_id)
+            .await?
+            .into_iter()
+            .map(|c| c.user_id)
+            .collect();
+        user::Entity::find()
+            .filter(user::Column::Id.is_in(ids))
+            .all(db)
+            .await?
+    };
+
+    Ok(users
+        .into_iter()
+        .filter(|u| u.id != actor_id && u.is_verified)
+        .map(|u| u.email)
+        .collect())
+}
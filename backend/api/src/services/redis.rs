@@ -1,6 +1,6 @@
 use std::{env, time::Duration};
 
-use tower_sessions_redis_store::fred::prelude::*;
+use tower_sessions_redis_store::fred::{clients::SubscriberClient, prelude::*};
 
 use tokio::task::JoinHandle;
 use tracing::{error, info, instrument, warn};
@@ -79,3 +79,23 @@ pub async fn init_redis_pool_only() -> Result<Pool, Error> {
     let (pool, _handle) = init_redis_store().await?;
     Ok(pool)
 }
+
+/// Connect a dedicated pub/sub client; it resubscribes to its channels
+/// after a reconnect.
+#[instrument(name = "redis_subscriber_init")]
+pub async fn init_subscriber() -> Result<SubscriberClient, Error> {
+    let subscriber = Builder::from_config(redis_config())
+        .with_connection_config(|config| {
+            config.reconnect_on_auth_error = true;
+            config.connection_timeout = Duration::from_millis(1500);
+        })
+        .set_policy(ReconnectPolicy::new_linear(0, 1000 * 600, 500))
+        .build_subscriber_client()?;
+    subscriber.init().await.map_err(|e| {
+        error!(error = ?e, "Failed to connect Redis subscriber");
+        e
+    })?;
+    subscriber.manage_subscriptions();
+    info!("Redis subscriber connected");
+    Ok(subscriber)
+}
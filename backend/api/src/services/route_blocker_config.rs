@@ -1,93 +1,121 @@
-use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
-use std::sync::RwLock;
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::{Arc, RwLock};
 
-use chrono::{DateTime, Utc};
 use lazy_static::lazy_static;
-use tokio::sync::Notify;
 
-const DEFAULT_SYNC_INTERVAL_SECS: u64 = 60 * 30; // 30 minutes
+use crate::services::{jobs, route_blocker_rules::RouteSnapshot};
+
+/// Job schedule running `jobs::handlers::RouteBlockerSync`.
+pub const SYNC_SCHEDULE: &str = "route_blocker.sync";
+
 const MIN_SYNC_INTERVAL_SECS: u64 = 60; // 1 minute
 const MAX_SYNC_INTERVAL_SECS: u64 = 60 * 60 * 24; // 24 hours
 
 lazy_static! {
-    static ref SYNC_INTERVAL_SECS: AtomicU64 = AtomicU64::new(DEFAULT_SYNC_INTERVAL_SECS);
-    static ref SYNC_PAUSED: AtomicBool = AtomicBool::new(false);
-    static ref FORCE_SYNC: AtomicBool = AtomicBool::new(false);
-    static ref SYNC_NOTIFY: Notify = Notify::new();
-    static ref LAST_SYNC_AT: RwLock<Option<DateTime<Utc>>> = RwLock::new(None);
-    static ref NEXT_SYNC_AT: RwLock<Option<DateTime<Utc>>> = RwLock::new(None);
-    static ref SYNC_RUNNING: AtomicBool = AtomicBool::new(false);
-}
-
-pub fn get_sync_interval_secs() -> u64 {
-    SYNC_INTERVAL_SECS.load(Ordering::Relaxed)
-}
-
-pub fn set_sync_interval_secs(secs: u64) {
-    let clamped = secs.clamp(MIN_SYNC_INTERVAL_SECS, MAX_SYNC_INTERVAL_SECS);
-    SYNC_INTERVAL_SECS.store(clamped, Ordering::Relaxed);
-    SYNC_NOTIFY.notify_waiters();
-}
-
-pub fn pause_sync() {
-    SYNC_PAUSED.store(true, Ordering::Relaxed);
-    SYNC_NOTIFY.notify_waiters();
-}
-
-pub fn resume_sync() {
-    let was_paused = SYNC_PAUSED.swap(false, Ordering::Relaxed);
-    if was_paused {
-        FORCE_SYNC.store(true, Ordering::Relaxed);
+    static ref SNAPSHOT: RwLock<Arc<RouteSnapshot>> =
+        RwLock::new(Arc::new(RouteSnapshot::default()));
+    static ref KNOWN_PATTERNS: RwLock<HashSet<String>> = RwLock::new(HashSet::new());
+    static ref LAST_REFRESH_AT: AtomicI64 = AtomicI64::new(0);
+}
+
+/// Cron running every `secs`, rounded to an interval cron can express:
+/// minutes dividing an hour, hours dividing a day, or daily. Returns the
+/// cron and the interval it actually runs at.
+pub fn interval_cron(secs: u64) -> (String, u64) {
+    let secs = secs.clamp(MIN_SYNC_INTERVAL_SECS, MAX_SYNC_INTERVAL_SECS);
+    let minutes = ((secs + 30) / 60).max(1);
+    if minutes < 60 {
+        let step = (1..=minutes).rev().find(|step| 60 % step == 0).unwrap_or(1);
+        return (format!("0 */{step} * * * *"), step * 60);
     }
-    SYNC_NOTIFY.notify_waiters();
-}
-
-pub fn is_paused() -> bool {
-    SYNC_PAUSED.load(Ordering::Relaxed)
+    let hours = (minutes + 30) / 60;
+    if hours < 24 {
+        let step = (1..=hours).rev().find(|step| 24 % step == 0).unwrap_or(1);
+        return (format!("0 0 */{step} * * *"), step * 3600);
+    }
+    ("0 0 0 * * *".to_string(), 86400)
 }
 
-pub fn request_immediate_sync() {
-    FORCE_SYNC.store(true, Ordering::Relaxed);
-    SYNC_NOTIFY.notify_waiters();
+/// Gap between the next two runs of `cron`; `None` if it doesn't parse.
+pub fn cron_interval_secs(cron: &str) -> Option<u64> {
+    let first = jobs::next_run(cron, chrono::Utc::now()).ok()?;
+    let second = jobs::next_run(cron, first).ok()?;
+    u64::try_from((second - first).num_seconds()).ok()
 }
 
-pub fn take_force_sync_flag() -> bool {
-    FORCE_SYNC.swap(false, Ordering::Relaxed)
+/// Whether this instance should check Redis for newer rules, at most once
+/// every `every_secs`. Only one caller wins each window.
+pub fn take_refresh_slot(now: i64, every_secs: i64) -> bool {
+    let last = LAST_REFRESH_AT.load(Ordering::Relaxed);
+    now - last >= every_secs
+        && LAST_REFRESH_AT
+            .compare_exchange(last, now, Ordering::Relaxed, Ordering::Relaxed)
+            .is_ok()
 }
 
-pub fn notifier() -> &'static Notify {
-    &SYNC_NOTIFY
+/// Rules the middleware evaluates; empty until the first load.
+pub fn snapshot() -> Arc<RouteSnapshot> {
+    SNAPSHOT
+        .read()
+        .map(|guard| Arc::clone(&guard))
+        .unwrap_or_default()
 }
 
-pub fn set_last_sync_at(timestamp: DateTime<Utc>) {
-    if let Ok(mut last) = LAST_SYNC_AT.write() {
-        *last = Some(timestamp);
+pub fn install_snapshot(snapshot: RouteSnapshot) {
+    if let Ok(mut known) = KNOWN_PATTERNS.write() {
+        known.extend(snapshot.known.iter().cloned());
+    }
+    if let Ok(mut current) = SNAPSHOT.write() {
+        *current = Arc::new(snapshot);
     }
 }
 
-pub fn get_last_sync_at() -> Option<DateTime<Utc>> {
-    LAST_SYNC_AT.read().ok().and_then(|guard| *guard)
+pub fn is_known_pattern(pattern: &str) -> bool {
+    KNOWN_PATTERNS
+        .read()
+        .map(|known| known.contains(pattern))
+        .unwrap_or(false)
 }
 
-pub fn set_next_sync_at(timestamp: DateTime<Utc>) {
-    if let Ok(mut next) = NEXT_SYNC_AT.write() {
-        *next = Some(timestamp);
+pub fn mark_known_pattern(pattern: &str) {
+    if let Ok(mut known) = KNOWN_PATTERNS.write() {
+        known.insert(pattern.to_string());
     }
 }
 
-pub fn get_next_sync_at() -> Option<DateTime<Utc>> {
-    NEXT_SYNC_AT.read().ok().and_then(|guard| *guard)
-}
-
-pub fn set_sync_running(running: bool) {
-    SYNC_RUNNING.store(running, Ordering::Relaxed);
-}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn intervals_round_to_a_cron_step() {
+        assert_eq!(interval_cron(0), ("0 */1 * * * *".to_string(), 60));
+        assert_eq!(interval_cron(1800), ("0 */30 * * * *".to_string(), 1800));
+        assert_eq!(interval_cron(7 * 60), ("0 */6 * * * *".to_string(), 360));
+        assert_eq!(interval_cron(3600), ("0 0 */1 * * *".to_string(), 3600));
+        assert_eq!(
+            interval_cron(5 * 3600),
+            ("0 0 */4 * * *".to_string(), 4 * 3600)
+        );
+        assert_eq!(interval_cron(86400), ("0 0 0 * * *".to_string(), 86400));
+        assert_eq!(interval_cron(u64::MAX), ("0 0 0 * * *".to_string(), 86400));
+    }
 
-pub fn is_sync_running() -> bool {
-    SYNC_RUNNING.load(Ordering::Relaxed)
-}
+    #[test]
+    fn interval_is_read_back_from_the_cron() {
+        for secs in [60, 360, 1800, 3600, 4 * 3600, 86400] {
+            let (cron, interval) = interval_cron(secs);
+            assert_eq!(cron_interval_secs(&cron), Some(interval));
+        }
+        assert_eq!(cron_interval_secs("not a cron"), None);
+    }
 
-pub fn calculate_next_sync() -> DateTime<Utc> {
-    let interval = get_sync_interval_secs();
-    Utc::now() + chrono::Duration::seconds(interval as i64)
+    #[test]
+    fn refresh_slot_is_taken_once_per_window() {
+        let now = 1_000_000;
+        assert!(take_refresh_slot(now, 5));
+        assert!(!take_refresh_slot(now + 4, 5));
+        assert!(take_refresh_slot(now + 5, 5));
+    }
 }
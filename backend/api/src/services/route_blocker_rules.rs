@@ -0,0 +1,346 @@
+//! Rule evaluation for `RouteBlockerMiddleware`.
+//!
+//! A [`RouteSnapshot`] holds every blocked rule, the recorded route patterns
+//! and the maintenance mode settings. It is built from the database, shared
+//! through Redis and evaluated in process, so checking a request never leaves
+//! the process.
+//!
+//! Rules are tried most specific first (exact, then prefix, then glob, longer
+//! patterns first); the first rule in effect for the request decides it.
+
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::db::sea_models::{
+    route_status::{self, RouteMatch},
+    user::UserRole,
+};
+
+pub const DEFAULT_BLOCK_STATUS: u16 = 403;
+/// Statuses a rule may answer with.
+pub const BLOCK_STATUSES: [u16; 4] = [403, 404, 429, 503];
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RouteRuleEntry {
+    pub pattern: String,
+    pub match_type: RouteMatch,
+    pub methods: Vec<String>,
+    pub exempt_role: Option<UserRole>,
+    pub active_from: Option<DateTime<Utc>>,
+    pub active_until: Option<DateTime<Utc>>,
+    pub status: u16,
+    pub message: Option<String>,
+    pub retry_after_secs: Option<u64>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct Maintenance {
+    pub enabled: bool,
+    pub message: String,
+    pub retry_after_secs: u64,
+    /// Lowest role that keeps full access.
+    pub exempt_role: UserRole,
+    /// Path prefixes that stay reachable, e.g. login.
+    pub allow: Vec<String>,
+}
+
+impl Default for Maintenance {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            message: String::new(),
+            retry_after_secs: 0,
+            exempt_role: UserRole::Admin,
+            allow: Vec::new(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct RouteSnapshot {
+    /// Redis version the snapshot was loaded from; 0 before the first load.
+    #[serde(default)]
+    pub version: u64,
+    pub rules: Vec<RouteRuleEntry>,
+    /// Route patterns already recorded in `route_status`.
+    pub known: Vec<String>,
+    pub maintenance: Maintenance,
+}
+
+/// The request being checked.
+#[derive(Clone, Copy, Debug)]
+pub struct RouteRequest<'a> {
+    pub method: &'a str,
+    pub path: &'a str,
+    /// Axum's `MatchedPath`, when the request hit a route.
+    pub matched: Option<&'a str>,
+    pub now: DateTime<Utc>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Block {
+    /// Rule pattern, or `None` for maintenance mode.
+    pub pattern: Option<String>,
+    pub status: u16,
+    pub message: Option<String>,
+    pub retry_after_secs: Option<u64>,
+    pub exempt_role: Option<UserRole>,
+}
+
+impl Block {
+    pub fn is_maintenance(&self) -> bool {
+        self.pattern.is_none()
+    }
+
+    /// Whether a user with `role` gets through. Super admins always do, so a
+    /// rule can't lock everyone out of the admin API.
+    pub fn exempts(&self, role: UserRole) -> bool {
+        role == UserRole::SuperAdmin
+            || self
+                .exempt_role
+                .is_some_and(|exempt| role.to_i32() >= exempt.to_i32())
+    }
+}
+
+impl RouteRuleEntry {
+    pub fn from_model(model: &route_status::Model) -> Self {
+        Self {
+            pattern: model.route_pattern.clone(),
+            match_type: RouteMatch::parse(&model.match_type),
+            methods: model
+                .methods
+                .iter()
+                .map(|m| m.to_ascii_uppercase())
+                .collect(),
+            exempt_role: model.exempt_role,
+            active_from: model.active_from.map(|t| t.with_timezone(&Utc)),
+            active_until: model.active_until.map(|t| t.with_timezone(&Utc)),
+            status: model
+                .status_code
+                .and_then(|code| u16::try_from(code).ok())
+                .filter(|code| BLOCK_STATUSES.contains(code))
+                .unwrap_or(DEFAULT_BLOCK_STATUS),
+            message: model.message.clone(),
+            retry_after_secs: model
+                .retry_after_secs
+                .and_then(|secs| u64::try_from(secs).ok()),
+        }
+    }
+
+    fn matches_path(&self, path: &str, matched: Option<&str>) -> bool {
+        let mut candidates = std::iter::once(path).chain(matched);
+        match self.match_type {
+            RouteMatch::Exact => candidates.any(|c| c == self.pattern),
+            RouteMatch::Prefix => candidates.any(|c| c.starts_with(&self.pattern)),
+            RouteMatch::Glob => candidates.any(|c| glob_match(&self.pattern, c)),
+        }
+    }
+
+    fn matches_method(&self, method: &str) -> bool {
+        self.methods.is_empty()
+            || self.methods.iter().any(|m| m == method)
+            || (method == "HEAD" && self.methods.iter().any(|m| m == "GET"))
+    }
+
+    fn in_effect(&self, now: DateTime<Utc>) -> bool {
+        self.active_from.is_none_or(|from| now >= from)
+            && self.active_until.is_none_or(|until| now < until)
+    }
+
+    fn specificity(&self) -> (u8, std::cmp::Reverse<usize>) {
+        let rank = match self.match_type {
+            RouteMatch::Exact => 0,
+            RouteMatch::Prefix => 1,
+            RouteMatch::Glob => 2,
+        };
+        (rank, std::cmp::Reverse(self.pattern.len()))
+    }
+}
+
+impl RouteSnapshot {
+    pub fn from_rows(rows: &[route_status::Model], maintenance: Maintenance) -> Self {
+        let mut rules: Vec<RouteRuleEntry> = rows
+            .iter()
+            .filter(|row| row.is_blocked)
+            .map(RouteRuleEntry::from_model)
+            .collect();
+        rules.sort_by_key(RouteRuleEntry::specificity);
+
+        Self {
+            version: 0,
+            rules,
+            known: rows.iter().map(|row| row.route_pattern.clone()).collect(),
+            maintenance,
+        }
+    }
+
+    /// The block that applies to `req`, before role exemptions.
+    pub fn check(&self, req: &RouteRequest<'_>) -> Option<Block> {
+        let maintenance = &self.maintenance;
+        if maintenance.enabled && !maintenance.allow.iter().any(|p| req.path.starts_with(p)) {
+            return Some(Block {
+                pattern: None,
+                status: 503,
+                message: Some(maintenance.message.clone()).filter(|m| !m.is_empty()),
+                retry_after_secs: Some(maintenance.retry_after_secs).filter(|secs| *secs > 0),
+                exempt_role: Some(maintenance.exempt_role),
+            });
+        }
+
+        let rule = self.rules.iter().find(|rule| {
+            rule.in_effect(req.now)
+                && rule.matches_method(req.method)
+                && rule.matches_path(req.path, req.matched)
+        })?;
+
+        let retry_after_secs = rule.retry_after_secs.or_else(|| {
+            rule.active_until
+                .map(|until| (until - req.now).num_seconds().max(1) as u64)
+        });
+        Some(Block {
+            pattern: Some(rule.pattern.clone()),
+            status: rule.status,
+            message: rule.message.clone(),
+            retry_after_secs,
+            exempt_role: rule.exempt_role,
+        })
+    }
+}
+
+/// `*` matches any run of characters except `/`, `**` matches anything.
+pub fn glob_match(pattern: &str, path: &str) -> bool {
+    if let Some(rest) = pattern.strip_prefix("**") {
+        return (0..=path.len())
+            .filter(|i| path.is_char_boundary(*i))
+            .any(|i| glob_match(rest, &path[i..]));
+    }
+    if let Some(rest) = pattern.strip_prefix('*') {
+        let segment_end = path.find('/').unwrap_or(path.len());
+        return (0..=segment_end)
+            .filter(|i| path.is_char_boundary(*i))
+            .any(|i| glob_match(rest, &path[i..]));
+    }
+    match (pattern.chars().next(), path.chars().next()) {
+        (None, None) => true,
+        (Some(p), Some(c)) if p == c => glob_match(&pattern[p.len_utf8()..], &path[c.len_utf8()..]),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(pattern: &str, match_type: RouteMatch) -> RouteRuleEntry {
+        RouteRuleEntry {
+            pattern: pattern.to_string(),
+            match_type,
+            methods: Vec::new(),
+            exempt_role: None,
+            active_from: None,
+            active_until: None,
+            status: DEFAULT_BLOCK_STATUS,
+            message: None,
+            retry_after_secs: None,
+        }
+    }
+
+    fn request<'a>(method: &'a str, path: &'a str) -> RouteRequest<'a> {
+        RouteRequest {
+            method,
+            path,
+            matched: None,
+            now: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn globs_respect_segments() {
+        assert!(glob_match("/post/v1/*", "/post/v1/list"));
+        assert!(!glob_match("/post/v1/*", "/post/v1/view/1"));
+        assert!(glob_match("/admin/**", "/admin/route/v1/list"));
+        assert!(glob_match("/*/v1/list", "/tag/v1/list"));
+        assert!(!glob_match("/admin/**", "/auth/v1/log_in"));
+    }
+
+    #[test]
+    fn most_specific_rule_wins() {
+        let mut prefix = rule("/post/", RouteMatch::Prefix);
+        prefix.status = 503;
+        let mut exact = rule("/post/v1/list", RouteMatch::Exact);
+        exact.status = 404;
+        let mut snapshot = RouteSnapshot {
+            rules: vec![prefix, exact],
+            ..Default::default()
+        };
+        snapshot.rules.sort_by_key(RouteRuleEntry::specificity);
+
+        assert_eq!(
+            snapshot
+                .check(&request("GET", "/post/v1/list"))
+                .unwrap()
+                .status,
+            404
+        );
+        assert_eq!(
+            snapshot
+                .check(&request("GET", "/post/v1/view/1"))
+                .unwrap()
+                .status,
+            503
+        );
+        assert!(snapshot.check(&request("GET", "/tag/v1/list")).is_none());
+    }
+
+    #[test]
+    fn methods_and_windows_limit_a_rule() {
+        let now = Utc::now();
+        let mut writes = rule("/post/**", RouteMatch::Glob);
+        writes.methods = vec!["POST".into()];
+        writes.active_until = Some(now + chrono::Duration::seconds(120));
+        let snapshot = RouteSnapshot {
+            rules: vec![writes],
+            ..Default::default()
+        };
+
+        assert!(snapshot.check(&request("GET", "/post/v1/list")).is_none());
+        let block = snapshot.check(&request("POST", "/post/v1/create")).unwrap();
+        assert!((119..=120).contains(&block.retry_after_secs.unwrap()));
+
+        let later = RouteRequest {
+            now: now + chrono::Duration::seconds(121),
+            ..request("POST", "/post/v1/create")
+        };
+        assert!(snapshot.check(&later).is_none());
+    }
+
+    #[test]
+    fn maintenance_blocks_all_but_allowed_paths_and_exempt_roles() {
+        let snapshot = RouteSnapshot {
+            maintenance: Maintenance {
+                enabled: true,
+                message: "Back soon".into(),
+                retry_after_secs: 600,
+                exempt_role: UserRole::Admin,
+                allow: vec!["/auth/".into()],
+            },
+            ..Default::default()
+        };
+
+        assert!(snapshot
+            .check(&request("POST", "/auth/v1/log_in"))
+            .is_none());
+        let block = snapshot.check(&request("GET", "/post/v1/list")).unwrap();
+        assert!(block.is_maintenance());
+        assert_eq!((block.status, block.retry_after_secs), (503, Some(600)));
+        assert!(block.exempts(UserRole::Admin));
+        assert!(!block.exempts(UserRole::Moderator));
+
+        let locked = Block {
+            exempt_role: None,
+            ..block
+        };
+        assert!(locked.exempts(UserRole::SuperAdmin));
+    }
+}
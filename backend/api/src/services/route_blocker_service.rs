@@ -1,77 +1,153 @@
-use crate::db::sea_models::route_status::Entity as RouteStatus;
+use crate::db::sea_models::route_status::{Entity as RouteStatus, RouteRule};
+use crate::db::sea_models::user::UserRole;
 use crate::error::ErrorResponse;
+use crate::services::acl_service::{AclService, UpsertConstantPayload};
+use crate::services::route_blocker_config;
+use crate::services::route_blocker_rules::{Maintenance, RouteSnapshot};
+use crate::services::settings::{self, Setting};
 use crate::state::AppState;
 use axum::extract::State;
 use serde_json::json;
 use std::error::Error;
 use tower_sessions_redis_store::fred::prelude::*;
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
 
 pub struct RouteBlockerService;
 
 impl RouteBlockerService {
-    pub const BLOCKED_ROUTES_KEY: &'static str = "blocked_routes";
-    pub const KNOWN_ROUTES_KEY: &'static str = "known_routes";
-
+    /// Serialized [`RouteSnapshot`] shared by every instance.
+    pub const SNAPSHOT_KEY: &'static str = "route_blocker:snapshot";
+    /// Bumped on every publish; instances reload the snapshot when it moves.
+    pub const VERSION_KEY: &'static str = "route_blocker:version";
+    /// Sets used before rules were evaluated in process.
+    const LEGACY_KEYS: [&'static str; 2] = ["blocked_routes", "known_routes"];
+
+    /// Add a matched route pattern to `route_status` the first time this
+    /// process sees it.
     pub async fn record_route_pattern(
         state: &AppState,
         pattern: &str,
     ) -> Result<(), Box<dyn Error + Send + Sync>> {
-        let already_cached: bool = state
-            .redis_pool
-            .sismember(Self::KNOWN_ROUTES_KEY, pattern)
-            .await?;
-
-        if already_cached {
-            debug!(pattern, "Route pattern already cached in known_routes set");
+        if route_blocker_config::is_known_pattern(pattern) {
             return Ok(());
         }
 
         RouteStatus::ensure_exists(&state.sea_db, pattern)
             .await
             .map_err(|e| Box::new(e) as Box<dyn Error + Send + Sync>)?;
+        route_blocker_config::mark_known_pattern(pattern);
 
-        state
-            .redis_pool
-            .sadd::<(), _, _>(Self::KNOWN_ROUTES_KEY, pattern)
-            .await?;
+        debug!(pattern, "Recorded route pattern");
+        Ok(())
+    }
 
-        info!(pattern, "Recorded route pattern in valkey known_routes set");
+    pub async fn maintenance(state: &AppState) -> Maintenance {
+        let exempt_role = settings::MAINTENANCE_EXEMPT_ROLE.string(state).await;
+        Maintenance {
+            enabled: settings::MAINTENANCE_MODE.bool(state).await,
+            message: settings::MAINTENANCE_MESSAGE.string(state).await,
+            retry_after_secs: settings::MAINTENANCE_RETRY_AFTER
+                .string(state)
+                .await
+                .parse()
+                .unwrap_or(0),
+            exempt_role: UserRole::from_str(&exempt_role).unwrap_or(UserRole::Admin),
+            allow: settings::MAINTENANCE_ALLOW.list(state).await,
+        }
+    }
 
-        Ok(())
+    /// Write maintenance settings through `AclService`, which records each
+    /// change and republishes the rules.
+    pub async fn update_maintenance(
+        state: &AppState,
+        updates: Vec<(Setting, String)>,
+        updated_by: Option<i32>,
+    ) -> Result<Maintenance, ErrorResponse> {
+        for (setting, value) in updates {
+            AclService::upsert_constant(
+                State(state.clone()),
+                UpsertConstantPayload {
+                    key: setting.key.to_string(),
+                    value,
+                    value_type: None,
+                    description: None,
+                    is_sensitive: false,
+                    source: Some("manual".to_string()),
+                },
+                updated_by,
+            )
+            .await?;
+        }
+        Ok(Self::maintenance(state).await)
     }
 
-    pub async fn is_route_blocked(
-        State(state): State<AppState>,
-        path: &str,
-    ) -> Result<bool, Box<dyn Error + Send + Sync>> {
-        let is_blocked: bool = state
+    /// Rebuild the snapshot from the database and settings, share it through
+    /// Redis and install it in this process.
+    pub async fn publish(state: &AppState) -> Result<u64, Box<dyn Error + Send + Sync>> {
+        let rows = RouteStatus::all(&state.sea_db).await?;
+        let mut snapshot = RouteSnapshot::from_rows(&rows, Self::maintenance(state).await);
+
+        snapshot.version = state.redis_pool.incr(Self::VERSION_KEY).await?;
+        state
             .redis_pool
-            .sismember(Self::BLOCKED_ROUTES_KEY, path)
+            .set::<(), _, _>(
+                Self::SNAPSHOT_KEY,
+                serde_json::to_string(&snapshot)?,
+                None,
+                None,
+                false,
+            )
             .await?;
 
-        Ok(is_blocked)
+        let version = snapshot.version;
+        info!(
+            version,
+            rules = snapshot.rules.len(),
+            "Published route blocker rules"
+        );
+        route_blocker_config::install_snapshot(snapshot);
+        Ok(version)
+    }
+
+    /// Load the shared snapshot if another instance published a newer one.
+    /// Returns whether anything changed.
+    pub async fn refresh(state: &AppState) -> Result<bool, Box<dyn Error + Send + Sync>> {
+        let version: Option<u64> = state.redis_pool.get(Self::VERSION_KEY).await?;
+        let Some(version) = version else {
+            Self::publish(state).await?;
+            return Ok(true);
+        };
+        if version == route_blocker_config::snapshot().version {
+            return Ok(false);
+        }
+
+        let raw: Option<String> = state.redis_pool.get(Self::SNAPSHOT_KEY).await?;
+        let Some(snapshot) = raw.and_then(|raw| serde_json::from_str::<RouteSnapshot>(&raw).ok())
+        else {
+            warn!("Route blocker snapshot missing or unreadable; rebuilding it");
+            Self::publish(state).await?;
+            return Ok(true);
+        };
+
+        debug!(version = snapshot.version, "Loaded route blocker rules");
+        route_blocker_config::install_snapshot(snapshot);
+        Ok(true)
     }
 
     pub async fn block_route(
         State(state): State<AppState>,
         pattern: String,
         reason: Option<String>,
+        rule: Option<RouteRule>,
     ) -> Result<serde_json::Value, ErrorResponse> {
-        let route = RouteStatus::create_or_update(&state.sea_db, pattern.clone(), true, reason)
-            .await
-            .map_err(|e| {
-                ErrorResponse::new(crate::error::ErrorCode::InternalServerError)
-                    .with_message(e.to_string())
-            })?;
-
-        Self::sync_route_to_redis(&state, &pattern, true)
+        let route = RouteStatus::create_or_update(&state.sea_db, pattern, true, reason, rule)
             .await
             .map_err(|e| {
                 ErrorResponse::new(crate::error::ErrorCode::InternalServerError)
                     .with_message(e.to_string())
             })?;
 
+        Self::publish_or_error(&state).await?;
         Ok(json!(route))
     }
 
@@ -79,20 +155,14 @@ impl RouteBlockerService {
         State(state): State<AppState>,
         pattern: String,
     ) -> Result<serde_json::Value, ErrorResponse> {
-        let route = RouteStatus::create_or_update(&state.sea_db, pattern.clone(), false, None)
-            .await
-            .map_err(|e| {
-                ErrorResponse::new(crate::error::ErrorCode::InternalServerError)
-                    .with_message(e.to_string())
-            })?;
-
-        Self::sync_route_to_redis(&state, &pattern, false)
+        let route = RouteStatus::create_or_update(&state.sea_db, pattern, false, None, None)
             .await
             .map_err(|e| {
                 ErrorResponse::new(crate::error::ErrorCode::InternalServerError)
                     .with_message(e.to_string())
             })?;
 
+        Self::publish_or_error(&state).await?;
         Ok(json!(route))
     }
 
@@ -107,13 +177,7 @@ impl RouteBlockerService {
                     .with_message(e.to_string())
             })?;
 
-        Self::remove_route_from_redis(&state, &pattern)
-            .await
-            .map_err(|e| {
-                ErrorResponse::new(crate::error::ErrorCode::InternalServerError)
-                    .with_message(e.to_string())
-            })?;
-
+        Self::publish_or_error(&state).await?;
         Ok(json!({ "message": "Route deleted successfully" }))
     }
 
@@ -131,67 +195,24 @@ impl RouteBlockerService {
     pub async fn sync_all_routes_to_redis(
         State(state): State<AppState>,
     ) -> Result<serde_json::Value, ErrorResponse> {
-        RouteStatus::sync_all_to_redis(
-            &state.sea_db,
-            state.redis_pool,
-            Self::KNOWN_ROUTES_KEY,
-            Self::BLOCKED_ROUTES_KEY,
-        )
-        .await
-        .map_err(|e| {
-            let err_str = e.to_string();
-            if let Some(redis_err) = err_str.strip_prefix("Redis error: ") {
-                ErrorResponse::new(crate::error::ErrorCode::InternalServerError)
-                    .with_message(format!("Redis sync failed: {}", redis_err))
-            } else {
-                ErrorResponse::new(crate::error::ErrorCode::InternalServerError)
-                    .with_message(format!("Database sync failed: {}", err_str))
-            }
-        })?;
-
-        Ok(json!({ "message": "All routes synced to Redis successfully" }))
-    }
-
-    async fn sync_route_to_redis(
-        state: &AppState,
-        pattern: &str,
-        is_blocked: bool,
-    ) -> Result<(), Box<dyn Error + Send + Sync>> {
         state
             .redis_pool
-            .sadd::<(), _, _>(Self::KNOWN_ROUTES_KEY, pattern)
-            .await?;
-
-        if is_blocked {
-            info!(pattern, "Adding route to blocked_routes set in valkey");
-            state
-                .redis_pool
-                .sadd::<(), _, _>(Self::BLOCKED_ROUTES_KEY, pattern)
-                .await?;
-        } else {
-            info!(pattern, "Removing route from blocked_routes set in valkey");
-            state
-                .redis_pool
-                .srem::<(), _, _>(Self::BLOCKED_ROUTES_KEY, pattern)
-                .await?;
-        }
+            .del::<(), _>(Self::LEGACY_KEYS.to_vec())
+            .await
+            .ok();
+        let version = Self::publish_or_error(&state).await?;
 
-        Ok(())
+        Ok(json!({
+            "message": "All routes synced to Redis successfully",
+            "version": version,
+        }))
     }
 
-    async fn remove_route_from_redis(
-        state: &AppState,
-        pattern: &str,
-    ) -> Result<(), Box<dyn Error + Send + Sync>> {
-        state
-            .redis_pool
-            .srem::<(), _, _>(Self::BLOCKED_ROUTES_KEY, pattern)
-            .await?;
-        state
-            .redis_pool
-            .srem::<(), _, _>(Self::KNOWN_ROUTES_KEY, pattern)
-            .await?;
-        Ok(())
+    async fn publish_or_error(state: &AppState) -> Result<u64, ErrorResponse> {
+        Self::publish(state).await.map_err(|e| {
+            ErrorResponse::new(crate::error::ErrorCode::InternalServerError)
+                .with_message(format!("Route blocker sync failed: {}", e))
+        })
     }
 
     pub async fn initialize_redis_sync(
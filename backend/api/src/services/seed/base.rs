@@ -220,6 +220,7 @@ pub async fn seed_all_with_progress(
             color: Some("#3b82f6".to_string()),
             text_color: None,
             is_active: Some(true),
+            locale: None,
         };
 
         match category::Entity::create(db, new_category).await {
@@ -250,6 +251,7 @@ pub async fn seed_all_with_progress(
             color: Some("#3b82f6".to_string()),
             text_color: None,
             is_active: Some(true),
+            locale: None,
         };
 
         match tag::Entity::create(db, new_tag).await {
@@ -313,6 +315,16 @@ pub async fn seed_all_with_progress(
                     view_count: 0,
                     likes_count: 0,
                     tag_ids,
+                    visibility: post::PostVisibility::Public,
+                    min_role: None,
+                    password: None,
+                    locale: None,
+                    meta_title: None,
+                    meta_description: None,
+                    canonical_url: None,
+                    noindex: false,
+                    og_image_id: None,
+                    og_image_variant: None,
                 };
 
                 match post::Entity::create(db, new_post).await {
@@ -345,6 +357,7 @@ pub async fn seed_all_with_progress(
                 let new_comment = post_comment::NewComment {
                     post_id: post.id,
                     user_id: user.id,
+                    parent_id: None,
                     content: content.clone(),
                     likes_count: Some(0),
                 };
@@ -770,6 +783,8 @@ async fn seed_post_revisions(db: &DatabaseConnection) -> SeedResult<()> {
                 post_id: post.id,
                 content: revision_content.clone(),
                 metadata: None,
+                fields: None,
+                created_by: None,
                 created_at: chrono::Utc::now().fixed_offset(),
             };
 
@@ -778,6 +793,8 @@ async fn seed_post_revisions(db: &DatabaseConnection) -> SeedResult<()> {
                 post_id: Set(revision.post_id),
                 content: Set(revision.content),
                 metadata: Set(revision.metadata),
+                fields: Set(revision.fields),
+                created_by: Set(revision.created_by),
                 created_at: Set(revision.created_at),
             };
 
@@ -1140,6 +1157,14 @@ async fn seed_route_status(db: &DatabaseConnection) -> SeedResult<()> {
             route_pattern: route.to_string(),
             is_blocked: false,
             reason: None,
+            match_type: "exact".to_string(),
+            methods: Vec::new(),
+            exempt_role: None,
+            active_from: None,
+            active_until: None,
+            status_code: None,
+            message: None,
+            retry_after_secs: None,
             created_at: chrono::Utc::now().fixed_offset(),
             updated_at: chrono::Utc::now().fixed_offset(),
         };
@@ -1149,6 +1174,14 @@ async fn seed_route_status(db: &DatabaseConnection) -> SeedResult<()> {
             route_pattern: Set(route_status_entry.route_pattern),
             is_blocked: Set(route_status_entry.is_blocked),
             reason: Set(route_status_entry.reason),
+            match_type: Set(route_status_entry.match_type),
+            methods: Set(route_status_entry.methods),
+            exempt_role: Set(route_status_entry.exempt_role),
+            active_from: Set(route_status_entry.active_from),
+            active_until: Set(route_status_entry.active_until),
+            status_code: Set(route_status_entry.status_code),
+            message: Set(route_status_entry.message),
+            retry_after_secs: Set(route_status_entry.retry_after_secs),
             created_at: Set(route_status_entry.created_at),
             updated_at: Set(route_status_entry.updated_at),
         };
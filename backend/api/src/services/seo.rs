@@ -0,0 +1,392 @@
+//! Search engine metadata for posts, the XML sitemaps and `robots.txt`.
+//!
+//! Public URLs are built from `SITE_URL`: `/posts/{slug}` for posts in the
+//! default locale and `/{locale}/posts/{slug}` for the others, with
+//! `/categories/{slug}`, `/tags/{slug}` and `/series/{slug}` for the
+//! listing pages. A post's own `canonical_url` takes precedence.
+//!
+//! `robots.txt` is built from app constants, each falling back to the
+//! environment variable of the same name:
+//! - `ROBOTS_DISALLOW_ALL`: `true` blocks every crawler (staging sites)
+//! - `ROBOTS_DISALLOW` / `ROBOTS_ALLOW`: comma or newline separated paths
+//! - `ROBOTS_EXTRA`: raw lines appended as is
+
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::{
+    db::sea_models::{
+        media, media_variant,
+        post::{PostStatus, PostVisibility, PostWithRelations},
+        post_author::PostAuthorRole,
+    },
+    error::DbResult,
+    services::{content_render, settings, websub},
+    utils::default_locale,
+    AppState,
+};
+
+/// URLs per child sitemap, the limit of the sitemap protocol.
+pub const SITEMAP_CHUNK: u64 = 50_000;
+
+/// Width Open Graph and Twitter cards are displayed at.
+const SOCIAL_IMAGE_WIDTH: i32 = 1200;
+
+const DESCRIPTION_LEN: usize = 160;
+
+pub fn post_url(locale: &str, slug: &str) -> String {
+    if locale == default_locale() {
+        format!("{}/posts/{}", websub::site_url(), slug)
+    } else {
+        format!("{}/{}/posts/{}", websub::site_url(), locale, slug)
+    }
+}
+
+pub fn category_url(slug: &str) -> String {
+    format!("{}/categories/{}", websub::site_url(), slug)
+}
+
+pub fn tag_url(slug: &str) -> String {
+    format!("{}/tags/{}", websub::site_url(), slug)
+}
+
+pub fn series_url(slug: &str) -> String {
+    format!("{}/series/{}", websub::site_url(), slug)
+}
+
+pub fn sitemap_index_url() -> String {
+    format!("{}/seo/v1/sitemap.xml", websub::site_url())
+}
+
+pub fn sitemap_url(kind: &str, page: u64) -> String {
+    format!(
+        "{}/seo/v1/sitemaps/{}-{}.xml",
+        websub::site_url(),
+        kind,
+        page
+    )
+}
+
+/// Image for link previews.
+#[derive(Clone, Debug, Serialize)]
+pub struct SocialImage {
+    pub url: String,
+    pub width: Option<i32>,
+    pub height: Option<i32>,
+    pub mime_type: String,
+}
+
+/// Metadata for the `<head>` of a post page.
+#[derive(Clone, Debug, Serialize)]
+pub struct PostSeo {
+    pub title: String,
+    pub description: String,
+    pub canonical_url: String,
+    /// Value of the robots meta tag.
+    pub robots: &'static str,
+    pub twitter_card: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image: Option<SocialImage>,
+    /// `BlogPosting` structured data.
+    pub json_ld: Value,
+}
+
+/// The variant to share: the one asked for, else the smallest at least
+/// `SOCIAL_IMAGE_WIDTH` wide. `None` means the original should be used.
+pub fn pick_variant<'a>(
+    variants: &'a [media_variant::Model],
+    wanted: Option<&str>,
+) -> Option<&'a media_variant::Model> {
+    if let Some(found) =
+        wanted.and_then(|wanted| variants.iter().find(|v| v.variant_type == wanted))
+    {
+        return Some(found);
+    }
+    variants
+        .iter()
+        .filter(|v| v.variant_type != "lqip" && v.width.unwrap_or(0) >= SOCIAL_IMAGE_WIDTH)
+        .min_by_key(|v| v.width)
+}
+
+/// `og_image_id` or the featured image, at the variant picked for sharing.
+async fn social_image(state: &AppState, post: &PostWithRelations) -> DbResult<Option<SocialImage>> {
+    let Some(media_id) = post
+        .og_image_id
+        .or(post.featured_image.as_ref().map(|image| image.id))
+    else {
+        return Ok(None);
+    };
+    let Some(media) = media::Entity::find_by_id(&state.sea_db, media_id).await? else {
+        return Ok(None);
+    };
+    let variants = media_variant::Entity::find()
+        .filter(media_variant::Column::MediaId.eq(media_id))
+        .all(&state.sea_db)
+        .await?;
+
+    let image = match pick_variant(&variants, post.og_image_variant.as_deref()) {
+        Some(variant) => SocialImage {
+            url: format!(
+                "{}/{}",
+                state.object_storage.public_url.trim_end_matches('/'),
+                variant.object_key
+            ),
+            width: variant.width,
+            height: variant.height,
+            mime_type: variant.mime_type.clone(),
+        },
+        None => SocialImage {
+            url: media.file_url,
+            width: media.width,
+            height: media.height,
+            mime_type: media.mime_type,
+        },
+    };
+    Ok(Some(image))
+}
+
+/// Structured data for a post page.
+pub fn blog_posting(
+    post: &PostWithRelations,
+    canonical_url: &str,
+    description: &str,
+    image: Option<&str>,
+) -> Value {
+    let authors: Vec<Value> = post
+        .contributors
+        .iter()
+        .filter(|c| c.role == PostAuthorRole::Author)
+        .map(|c| json!({ "@type": "Person", "name": c.name }))
+        .collect();
+    let authors = if authors.is_empty() {
+        vec![json!({ "@type": "Person", "name": post.author.name })]
+    } else {
+        authors
+    };
+
+    let mut data = json!({
+        "@context": "https://schema.org",
+        "@type": "BlogPosting",
+        "headline": post.meta_title.as_deref().unwrap_or(&post.title),
+        "description": description,
+        "url": canonical_url,
+        "mainEntityOfPage": { "@type": "WebPage", "@id": canonical_url },
+        "inLanguage": post.locale,
+        "author": authors,
+        "articleSection": post.category.name,
+        "dateModified": post.updated_at.to_rfc3339(),
+    });
+    if let Some(published_at) = post.published_at {
+        data["datePublished"] = json!(published_at.to_rfc3339());
+    }
+    if !post.tags.is_empty() {
+        let keywords: Vec<&str> = post.tags.iter().map(|t| t.name.as_str()).collect();
+        data["keywords"] = json!(keywords.join(", "));
+    }
+    if let Some(image) = image {
+        data["image"] = json!([image]);
+    }
+    if post.visibility != PostVisibility::Public {
+        data["isAccessibleForFree"] = json!(false);
+    }
+    data
+}
+
+/// Page metadata for `post` as served to the viewer.
+pub async fn post_metadata(state: &AppState, post: &PostWithRelations) -> DbResult<PostSeo> {
+    let canonical_url = post
+        .canonical_url
+        .clone()
+        .unwrap_or_else(|| post_url(&post.locale, &post.slug));
+    let description = match (&post.meta_description, &post.excerpt) {
+        (Some(description), _) => description.clone(),
+        (None, Some(excerpt)) if !excerpt.trim().is_empty() => excerpt.clone(),
+        _ => content_render::summary(&post.content, DESCRIPTION_LEN),
+    };
+    let image = social_image(state, post).await?;
+    let json_ld = blog_posting(
+        post,
+        &canonical_url,
+        &description,
+        image.as_ref().map(|i| i.url.as_str()),
+    );
+
+    Ok(PostSeo {
+        title: post
+            .meta_title
+            .clone()
+            .unwrap_or_else(|| post.title.clone()),
+        description,
+        canonical_url,
+        robots: if post.noindex || post.status != PostStatus::Published {
+            "noindex, follow"
+        } else {
+            "index, follow"
+        },
+        twitter_card: if image.is_some() {
+            "summary_large_image"
+        } else {
+            "summary"
+        },
+        image,
+        json_ld,
+    })
+}
+
+pub fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('\"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// One `<url>` of a child sitemap.
+#[derive(Clone, Debug)]
+pub struct SitemapUrl {
+    pub loc: String,
+    pub lastmod: Option<chrono::DateTime<chrono::FixedOffset>>,
+    /// `(hreflang, href)` alternates.
+    pub alternates: Vec<(String, String)>,
+}
+
+pub fn render_urlset(urls: &[SitemapUrl]) -> String {
+    let mut xml = String::new();
+    xml.push_str(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+    xml.push_str(
+        r#"<urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9" xmlns:xhtml="http://www.w3.org/1999/xhtml">"#,
+    );
+    for url in urls {
+        xml.push_str("<url>");
+        xml.push_str(&format!("<loc>{}</loc>", xml_escape(&url.loc)));
+        if let Some(lastmod) = url.lastmod {
+            xml.push_str(&format!("<lastmod>{}</lastmod>", lastmod.to_rfc3339()));
+        }
+        for (hreflang, href) in &url.alternates {
+            xml.push_str(&format!(
+                r#"<xhtml:link rel="alternate" hreflang="{}" href="{}" />"#,
+                xml_escape(hreflang),
+                xml_escape(href)
+            ));
+        }
+        xml.push_str("</url>");
+    }
+    xml.push_str("</urlset>");
+    xml
+}
+
+pub fn render_index(sitemaps: &[String]) -> String {
+    let mut xml = String::new();
+    xml.push_str(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+    xml.push_str(r#"<sitemapindex xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">"#);
+    for loc in sitemaps {
+        xml.push_str(&format!(
+            "<sitemap><loc>{}</loc></sitemap>",
+            xml_escape(loc)
+        ));
+    }
+    xml.push_str("</sitemapindex>");
+    xml
+}
+
+/// Rules for `robots.txt`.
+#[derive(Clone, Debug, Default)]
+pub struct RobotsRules {
+    pub disallow_all: bool,
+    pub disallow: Vec<String>,
+    pub allow: Vec<String>,
+    pub extra: Option<String>,
+}
+
+pub fn render_robots(rules: &RobotsRules, sitemap_url: &str) -> String {
+    let mut lines = vec!["User-agent: *".to_string()];
+    if rules.disallow_all {
+        lines.push("Disallow: /".to_string());
+    } else {
+        lines.extend(rules.allow.iter().map(|path| format!("Allow: {}", path)));
+        lines.extend(
+            rules
+                .disallow
+                .iter()
+                .map(|path| format!("Disallow: {}", path)),
+        );
+        if rules.allow.is_empty() && rules.disallow.is_empty() {
+            lines.push("Disallow:".to_string());
+        }
+    }
+    if let Some(extra) = rules.extra.as_deref().filter(|e| !e.trim().is_empty()) {
+        lines.push(String::new());
+        lines.extend(extra.lines().map(|line| line.trim_end().to_string()));
+    }
+    lines.push(String::new());
+    lines.push(format!("Sitemap: {}", sitemap_url));
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+pub async fn robots_rules(state: &AppState) -> RobotsRules {
+    RobotsRules {
+        disallow_all: settings::ROBOTS_DISALLOW_ALL.bool(state).await,
+        disallow: settings::ROBOTS_DISALLOW.list(state).await,
+        allow: settings::ROBOTS_ALLOW.list(state).await,
+        extra: settings::ROBOTS_EXTRA.value(state).await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn variant(variant_type: &str, width: i32) -> media_variant::Model {
+        let now = chrono::Utc::now().fixed_offset();
+        media_variant::Model {
+            id: width,
+            media_id: 1,
+            object_key: format!("media/1-{}", variant_type),
+            mime_type: "image/webp".into(),
+            width: Some(width),
+            height: Some(width / 2),
+            size: 1,
+            extension: Some("webp".into()),
+            quality: None,
+            variant_type: variant_type.into(),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[test]
+    fn pick_variant_prefers_the_smallest_wide_enough() {
+        let variants = vec![
+            variant("lqip", 32),
+            variant("640w", 640),
+            variant("1600w", 1600),
+            variant("2400w", 2400),
+        ];
+        assert_eq!(pick_variant(&variants, None).unwrap().variant_type, "1600w");
+        assert_eq!(
+            pick_variant(&variants, Some("640w")).unwrap().variant_type,
+            "640w"
+        );
+        assert!(pick_variant(&variants[..2], Some("missing")).is_none());
+    }
+
+    #[test]
+    fn robots_lists_rules_and_the_sitemap() {
+        let rules = RobotsRules {
+            disallow: settings::parse_list("/admin, /drafts\n/api"),
+            ..Default::default()
+        };
+        let robots = render_robots(&rules, "https://example.com/seo/v1/sitemap.xml");
+        assert!(robots
+            .starts_with("User-agent: *\nDisallow: /admin\nDisallow: /drafts\nDisallow: /api\n"));
+        assert!(robots.ends_with("Sitemap: https://example.com/seo/v1/sitemap.xml\n"));
+
+        let blocked = RobotsRules {
+            disallow_all: true,
+            ..Default::default()
+        };
+        assert!(render_robots(&blocked, "x").contains("Disallow: /\n"));
+    }
+}
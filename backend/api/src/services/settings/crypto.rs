@@ -0,0 +1,138 @@
+//! Envelope encryption for sensitive app constants.
+//!
+//! Every value gets its own random data key. The value is sealed with the
+//! data key, and the data key with the master key from
+//! `APP_CONSTANTS_MASTER_KEY` (32 bytes, base64), both with AES-256-GCM.
+//! Stored as `v1.<wrapped key>.<sealed value>`, each part base64 with its
+//! 12-byte nonce in front. Rotating the master key only rewraps data keys.
+
+use aes_gcm::{
+    aead::{Aead, KeyInit},
+    Aes256Gcm, Nonce,
+};
+use base64::prelude::*;
+use rand::RngCore;
+
+pub const MASTER_KEY_ENV: &str = "APP_CONSTANTS_MASTER_KEY";
+/// The key being rotated away from; values still sealed under it are
+/// rewrapped at startup.
+pub const PREVIOUS_MASTER_KEY_ENV: &str = "APP_CONSTANTS_MASTER_KEY_PREVIOUS";
+
+const VERSION: &str = "v1";
+const KEY_LEN: usize = 32;
+const NONCE_LEN: usize = 12;
+
+pub type MasterKey = [u8; KEY_LEN];
+
+fn decode_key(value: &str) -> Option<MasterKey> {
+    BASE64_STANDARD
+        .decode(value.trim())
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+}
+
+fn key_from_env(name: &str) -> Option<MasterKey> {
+    let value = std::env::var(name).ok()?;
+    let key = decode_key(&value);
+    if key.is_none() {
+        tracing::warn!("{} is not 32 base64-encoded bytes; ignoring it", name);
+    }
+    key
+}
+
+pub fn master_key() -> Option<MasterKey> {
+    key_from_env(MASTER_KEY_ENV)
+}
+
+pub fn previous_master_key() -> Option<MasterKey> {
+    key_from_env(PREVIOUS_MASTER_KEY_ENV)
+}
+
+fn encrypt(key: &[u8], plaintext: &[u8]) -> Result<String, String> {
+    let mut nonce = [0u8; NONCE_LEN];
+    rand::rng().fill_bytes(&mut nonce);
+    let cipher = Aes256Gcm::new_from_slice(key).map_err(|err| err.to_string())?;
+    let ciphertext = cipher
+        .encrypt(Nonce::from_slice(&nonce), plaintext)
+        .map_err(|_| "Encryption failed".to_string())?;
+
+    let mut out = nonce.to_vec();
+    out.extend_from_slice(&ciphertext);
+    Ok(BASE64_STANDARD.encode(out))
+}
+
+fn decrypt(key: &[u8], part: &str) -> Result<Vec<u8>, String> {
+    let bytes = BASE64_STANDARD
+        .decode(part)
+        .map_err(|_| "Malformed encrypted value".to_string())?;
+    if bytes.len() < NONCE_LEN {
+        return Err("Malformed encrypted value".to_string());
+    }
+    let (nonce, ciphertext) = bytes.split_at(NONCE_LEN);
+    let cipher = Aes256Gcm::new_from_slice(key).map_err(|err| err.to_string())?;
+    cipher
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| "Wrong master key or corrupted value".to_string())
+}
+
+fn split(sealed: &str) -> Result<(&str, &str), String> {
+    match sealed.split('.').collect::<Vec<_>>().as_slice() {
+        [VERSION, wrapped, value] => Ok((wrapped, value)),
+        _ => Err("Unsupported encrypted value".to_string()),
+    }
+}
+
+pub fn seal(master: &MasterKey, plaintext: &str) -> Result<String, String> {
+    let mut data_key = [0u8; KEY_LEN];
+    rand::rng().fill_bytes(&mut data_key);
+    let value = encrypt(&data_key, plaintext.as_bytes())?;
+    let wrapped = encrypt(master, &data_key)?;
+    Ok(format!("{}.{}.{}", VERSION, wrapped, value))
+}
+
+pub fn open(master: &MasterKey, sealed: &str) -> Result<String, String> {
+    let (wrapped, value) = split(sealed)?;
+    let data_key = decrypt(master, wrapped)?;
+    let plaintext = decrypt(&data_key, value)?;
+    String::from_utf8(plaintext).map_err(|_| "Decrypted value is not UTF-8".to_string())
+}
+
+/// Re-seal the data key of `sealed` under `new`; the value itself is untouched.
+pub fn rewrap(old: &MasterKey, new: &MasterKey, sealed: &str) -> Result<String, String> {
+    let (wrapped, value) = split(sealed)?;
+    let data_key = decrypt(old, wrapped)?;
+    Ok(format!(
+        "{}.{}.{}",
+        VERSION,
+        encrypt(new, &data_key)?,
+        value
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seal_open_and_rewrap() {
+        let old = [7u8; KEY_LEN];
+        let new = [9u8; KEY_LEN];
+
+        let sealed = seal(&old, "smtp-password").unwrap();
+        assert!(!sealed.contains("smtp-password"));
+        assert_eq!(open(&old, &sealed).unwrap(), "smtp-password");
+        assert!(open(&new, &sealed).is_err());
+
+        let rewrapped = rewrap(&old, &new, &sealed).unwrap();
+        assert_eq!(open(&new, &rewrapped).unwrap(), "smtp-password");
+        assert!(open(&old, &rewrapped).is_err());
+        assert!(open(&old, "plain value").is_err());
+    }
+
+    #[test]
+    fn master_key_must_be_32_bytes() {
+        assert!(decode_key(&BASE64_STANDARD.encode([1u8; 32])).is_some());
+        assert!(decode_key(&BASE64_STANDARD.encode([1u8; 16])).is_none());
+        assert!(decode_key("not base64!").is_none());
+    }
+}
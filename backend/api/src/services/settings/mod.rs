@@ -0,0 +1,522 @@
+//! Declared application settings.
+//!
+//! Every setting the app reads is listed in [`REGISTRY`] with its type,
+//! default, sensitivity and whether it may change at runtime:
+//! - runtime settings resolve to their `app_constants` row (decrypted), then
+//!   the environment, then the default
+//! - startup settings resolve to the environment, then the default, and
+//!   can't be edited through the API
+//!
+//! Connection settings read before the database is up (Postgres, Redis,
+//! telemetry, CORS) stay plain environment variables.
+
+pub mod crypto;
+
+use axum::extract::State;
+use serde::Serialize;
+
+use crate::{services::acl_service::AclService, AppState};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SettingKind {
+    String,
+    Bool,
+    Int,
+    Url,
+    /// Comma or newline separated values.
+    List,
+}
+
+impl SettingKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::String => "string",
+            Self::Bool => "bool",
+            Self::Int => "int",
+            Self::Url => "url",
+            Self::List => "list",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Serialize)]
+pub struct Setting {
+    pub key: &'static str,
+    pub kind: SettingKind,
+    pub default: Option<&'static str>,
+    /// Stored encrypted and never returned by the API.
+    pub sensitive: bool,
+    /// Editable through `admin_acl_v1`; otherwise read from the environment
+    /// at startup only.
+    pub runtime: bool,
+    pub description: &'static str,
+    /// Allowed values for `String` settings; empty allows any.
+    pub choices: &'static [&'static str],
+}
+
+pub fn parse_bool(value: &str) -> Option<bool> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "1" | "true" | "yes" | "on" => Some(true),
+        "0" | "false" | "no" | "off" | "" => Some(false),
+        _ => None,
+    }
+}
+
+pub fn parse_list(value: &str) -> Vec<String> {
+    value
+        .split([',', '\n'])
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+impl Setting {
+    /// Check `raw` against the setting's type and return it in canonical
+    /// form: booleans as `true`/`false`, URLs without a trailing slash, lists
+    /// comma separated.
+    pub fn normalize(&self, raw: &str) -> Result<String, String> {
+        match self.kind {
+            SettingKind::String if self.choices.is_empty() => Ok(raw.to_string()),
+            SettingKind::String => {
+                let value = raw.trim();
+                if self.choices.contains(&value) {
+                    Ok(value.to_string())
+                } else {
+                    Err(format!(
+                        "{} must be one of: {}",
+                        self.key,
+                        self.choices.join(", ")
+                    ))
+                }
+            }
+            SettingKind::Bool => parse_bool(raw)
+                .map(|value| value.to_string())
+                .ok_or_else(|| format!("{} must be true or false", self.key)),
+            SettingKind::Int => raw
+                .trim()
+                .parse::<i64>()
+                .map(|value| value.to_string())
+                .map_err(|_| format!("{} must be a whole number", self.key)),
+            SettingKind::Url => {
+                let url = raw.trim().trim_end_matches('/');
+                if url.starts_with("http://") || url.starts_with("https://") {
+                    Ok(url.to_string())
+                } else {
+                    Err(format!("{} must be an http(s) URL", self.key))
+                }
+            }
+            SettingKind::List => Ok(parse_list(raw).join(",")),
+        }
+    }
+
+    /// Environment value, or the default. Values that don't fit the type
+    /// fall back to the default.
+    pub fn env_value(&self) -> Option<String> {
+        let from_env = std::env::var(self.key)
+            .ok()
+            .filter(|value| !value.is_empty())
+            .and_then(|value| match self.normalize(&value) {
+                Ok(value) => Some(value),
+                Err(err) => {
+                    tracing::warn!("{}; using the default", err);
+                    None
+                }
+            });
+        from_env.or_else(|| self.default.map(str::to_string))
+    }
+
+    pub fn env_string(&self) -> String {
+        self.env_value().unwrap_or_default()
+    }
+
+    pub fn env_bool(&self) -> bool {
+        self.env_value()
+            .and_then(|value| parse_bool(&value))
+            .unwrap_or(false)
+    }
+
+    /// Environment integer; unparsable values fall back to the default.
+    pub fn env_int(&self) -> i64 {
+        self.env_value()
+            .and_then(|value| value.trim().parse().ok())
+            .or_else(|| self.default.and_then(|value| value.parse().ok()))
+            .unwrap_or(0)
+    }
+
+    /// Current value: the stored constant for runtime settings, then the
+    /// environment and the default.
+    pub async fn value(&self, state: &AppState) -> Option<String> {
+        if self.runtime {
+            if let Ok(constant) = AclService::get_constant(State(state.clone()), self.key).await {
+                return Some(constant.value);
+            }
+        }
+        self.env_value()
+    }
+
+    pub async fn string(&self, state: &AppState) -> String {
+        self.value(state).await.unwrap_or_default()
+    }
+
+    pub async fn bool(&self, state: &AppState) -> bool {
+        self.value(state)
+            .await
+            .and_then(|value| parse_bool(&value))
+            .unwrap_or(false)
+    }
+
+    /// Integer value; unparsable values fall back to the default.
+    pub async fn int(&self, state: &AppState) -> i64 {
+        self.value(state)
+            .await
+            .and_then(|value| value.trim().parse().ok())
+            .or_else(|| self.default.and_then(|value| value.parse().ok()))
+            .unwrap_or(0)
+    }
+
+    pub async fn list(&self, state: &AppState) -> Vec<String> {
+        self.value(state)
+            .await
+            .map(|value| parse_list(&value))
+            .unwrap_or_default()
+    }
+}
+
+const fn startup(
+    key: &'static str,
+    kind: SettingKind,
+    default: Option<&'static str>,
+    description: &'static str,
+) -> Setting {
+    Setting {
+        key,
+        kind,
+        default,
+        sensitive: false,
+        runtime: false,
+        description,
+        choices: &[],
+    }
+}
+
+const fn runtime(
+    key: &'static str,
+    kind: SettingKind,
+    default: Option<&'static str>,
+    description: &'static str,
+) -> Setting {
+    Setting {
+        key,
+        kind,
+        default,
+        sensitive: false,
+        runtime: true,
+        description,
+        choices: &[],
+    }
+}
+
+const fn one_of(setting: Setting, choices: &'static [&'static str]) -> Setting {
+    Setting { choices, ..setting }
+}
+
+const fn secret(setting: Setting) -> Setting {
+    Setting {
+        sensitive: true,
+        ..setting
+    }
+}
+
+pub const SITE_URL: Setting = startup(
+    "SITE_URL",
+    SettingKind::Url,
+    Some("http://localhost:8888"),
+    "Public base URL used in feeds, sitemaps and emails",
+);
+pub const SITE_NAME: Setting = runtime(
+    "SITE_NAME",
+    SettingKind::String,
+    Some("Ruxlog"),
+    "Site name shown in feeds",
+);
+pub const FRONTEND_URL: Setting = startup(
+    "FRONTEND_URL",
+    SettingKind::Url,
+    Some("http://localhost:3000"),
+    "Where Google sign-in sends the browser back to",
+);
+pub const DEFAULT_LOCALE: Setting = startup(
+    "DEFAULT_LOCALE",
+    SettingKind::String,
+    Some("en"),
+    "Locale of content saved without one",
+);
+pub const APP_ENV: Setting = startup(
+    "APP_ENV",
+    SettingKind::String,
+    Some("development"),
+    "`development` disables the route blocker",
+);
+pub const CSRF_KEY: Setting = secret(startup(
+    "CSRF_KEY",
+    SettingKind::String,
+    Some("ultra-instinct-goku"),
+    "Static CSRF token key",
+));
+pub const SMTP_HOST: Setting = startup("SMTP_HOST", SettingKind::String, None, "SMTP relay host");
+pub const SMTP_USERNAME: Setting = startup("SMTP_USERNAME", SettingKind::String, None, "SMTP user");
+pub const SMTP_PASSWORD: Setting = secret(startup(
+    "SMTP_PASSWORD",
+    SettingKind::String,
+    None,
+    "SMTP password",
+));
+pub const GOOGLE_CLIENT_ID: Setting = startup(
+    "GOOGLE_CLIENT_ID",
+    SettingKind::String,
+    None,
+    "Google OAuth client ID",
+);
+pub const GOOGLE_CLIENT_SECRET: Setting = secret(startup(
+    "GOOGLE_CLIENT_SECRET",
+    SettingKind::String,
+    None,
+    "Google OAuth client secret",
+));
+pub const GOOGLE_REDIRECT_URI: Setting = startup(
+    "GOOGLE_REDIRECT_URI",
+    SettingKind::Url,
+    None,
+    "Google OAuth callback URL",
+);
+pub const BACKUP_PASSPHRASE: Setting = secret(runtime(
+    "BACKUP_PASSPHRASE",
+    SettingKind::String,
+    None,
+    "Passphrase for encrypted backups",
+));
+pub const WEBSUB_HUBS: Setting = runtime(
+    "WEBSUB_HUBS",
+    SettingKind::List,
+    None,
+    "WebSub hubs pinged when a post is published",
+);
+pub const POST_REQUIRE_APPROVAL: Setting = runtime(
+    "POST_REQUIRE_APPROVAL",
+    SettingKind::Bool,
+    Some("false"),
+    "Authors need an approved review before publishing",
+);
+pub const ROBOTS_DISALLOW_ALL: Setting = runtime(
+    "ROBOTS_DISALLOW_ALL",
+    SettingKind::Bool,
+    Some("false"),
+    "Ask crawlers to stay off the whole site",
+);
+pub const ROBOTS_DISALLOW: Setting = runtime(
+    "ROBOTS_DISALLOW",
+    SettingKind::List,
+    None,
+    "Paths disallowed in robots.txt",
+);
+pub const ROBOTS_ALLOW: Setting = runtime(
+    "ROBOTS_ALLOW",
+    SettingKind::List,
+    None,
+    "Paths allowed in robots.txt",
+);
+pub const ROBOTS_EXTRA: Setting = runtime(
+    "ROBOTS_EXTRA",
+    SettingKind::String,
+    None,
+    "Raw lines appended to robots.txt",
+);
+pub const MAINTENANCE_MODE: Setting = runtime(
+    "MAINTENANCE_MODE",
+    SettingKind::Bool,
+    Some("false"),
+    "Answer every request with 503 except for exempt users and paths",
+);
+pub const MAINTENANCE_MESSAGE: Setting = runtime(
+    "MAINTENANCE_MESSAGE",
+    SettingKind::String,
+    Some("We'll be back shortly"),
+    "Message shown during maintenance",
+);
+pub const MAINTENANCE_RETRY_AFTER: Setting = runtime(
+    "MAINTENANCE_RETRY_AFTER",
+    SettingKind::Int,
+    Some("300"),
+    "Retry-After seconds sent during maintenance",
+);
+pub const MAINTENANCE_EXEMPT_ROLE: Setting = one_of(
+    runtime(
+        "MAINTENANCE_EXEMPT_ROLE",
+        SettingKind::String,
+        Some("admin"),
+        "Lowest role that keeps access during maintenance",
+    ),
+    &["super-admin", "admin", "moderator", "author", "user"],
+);
+pub const MAINTENANCE_ALLOW: Setting = runtime(
+    "MAINTENANCE_ALLOW",
+    SettingKind::List,
+    Some("/auth/,/csrf/"),
+    "Path prefixes reachable during maintenance",
+);
+pub const AUDIT_RETENTION_DAYS: Setting = runtime(
+    "AUDIT_RETENTION_DAYS",
+    SettingKind::Int,
+    Some("365"),
+    "Days audit log entries are kept; 0 keeps them forever",
+);
+pub const AUDIT_EXPORT_MAX_ROWS: Setting = runtime(
+    "AUDIT_EXPORT_MAX_ROWS",
+    SettingKind::Int,
+    Some("10000"),
+    "Most audit log entries returned by one export",
+);
+pub const JOB_QUEUES: Setting = startup(
+    "JOB_QUEUES",
+    SettingKind::List,
+    Some("default:4,mail:2,maintenance:1,webhooks:4"),
+    "Job queues this instance works on, as queue:concurrency",
+);
+pub const JOB_SHUTDOWN_GRACE_SECS: Setting = startup(
+    "JOB_SHUTDOWN_GRACE_SECS",
+    SettingKind::Int,
+    Some("30"),
+    "Seconds running jobs get to finish on shutdown",
+);
+pub const JOB_STALE_AFTER_SECS: Setting = startup(
+    "JOB_STALE_AFTER_SECS",
+    SettingKind::Int,
+    Some("3600"),
+    "Seconds without a heartbeat after which a running job is assumed lost and requeued",
+);
+pub const JOB_RETENTION_DAYS: Setting = runtime(
+    "JOB_RETENTION_DAYS",
+    SettingKind::Int,
+    Some("14"),
+    "Days succeeded and cancelled jobs are kept; 0 keeps them forever",
+);
+pub const ROUTE_BLOCKER_SYNC_INTERVAL_SECS: Setting = startup(
+    "ROUTE_BLOCKER_SYNC_INTERVAL_SECS",
+    SettingKind::Int,
+    Some("1800"),
+    "Initial interval of the route blocker's Postgres to Redis sync schedule",
+);
+pub const ROUTE_BLOCKER_REFRESH_SECS: Setting = startup(
+    "ROUTE_BLOCKER_REFRESH_SECS",
+    SettingKind::Int,
+    Some("5"),
+    "Seconds between checks for route blocker rules published by other instances",
+);
+pub const WEBHOOK_DISABLE_AFTER: Setting = runtime(
+    "WEBHOOK_DISABLE_AFTER",
+    SettingKind::Int,
+    Some("5"),
+    "Failed deliveries in a row before a webhook is disabled; 0 never disables",
+);
+pub const COMMENT_FLAG_ALERT_THRESHOLD: Setting = runtime(
+    "COMMENT_FLAG_ALERT_THRESHOLD",
+    SettingKind::Int,
+    Some("3"),
+    "Flags on a comment that raise a live alert for moderators; 0 never alerts",
+);
+pub const API_DOCS_ENABLED: Setting = runtime(
+    "API_DOCS_ENABLED",
+    SettingKind::Bool,
+    Some("true"),
+    "Serve the OpenAPI document and Swagger UI at /docs",
+);
+
+pub const REGISTRY: &[Setting] = &[
+    SITE_URL,
+    SITE_NAME,
+    FRONTEND_URL,
+    DEFAULT_LOCALE,
+    APP_ENV,
+    CSRF_KEY,
+    SMTP_HOST,
+    SMTP_USERNAME,
+    SMTP_PASSWORD,
+    GOOGLE_CLIENT_ID,
+    GOOGLE_CLIENT_SECRET,
+    GOOGLE_REDIRECT_URI,
+    BACKUP_PASSPHRASE,
+    WEBSUB_HUBS,
+    POST_REQUIRE_APPROVAL,
+    ROBOTS_DISALLOW_ALL,
+    ROBOTS_DISALLOW,
+    ROBOTS_ALLOW,
+    ROBOTS_EXTRA,
+    MAINTENANCE_MODE,
+    MAINTENANCE_MESSAGE,
+    MAINTENANCE_RETRY_AFTER,
+    MAINTENANCE_EXEMPT_ROLE,
+    MAINTENANCE_ALLOW,
+    AUDIT_RETENTION_DAYS,
+    AUDIT_EXPORT_MAX_ROWS,
+    JOB_QUEUES,
+    JOB_SHUTDOWN_GRACE_SECS,
+    JOB_STALE_AFTER_SECS,
+    JOB_RETENTION_DAYS,
+    ROUTE_BLOCKER_SYNC_INTERVAL_SECS,
+    ROUTE_BLOCKER_REFRESH_SECS,
+    WEBHOOK_DISABLE_AFTER,
+    COMMENT_FLAG_ALERT_THRESHOLD,
+    API_DOCS_ENABLED,
+];
+
+pub fn find(key: &str) -> Option<&'static Setting> {
+    REGISTRY.iter().find(|setting| setting.key == key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn values_are_normalized_by_kind() {
+        assert_eq!(POST_REQUIRE_APPROVAL.normalize(" Yes ").unwrap(), "true");
+        assert!(POST_REQUIRE_APPROVAL.normalize("maybe").is_err());
+        assert_eq!(
+            SITE_URL.normalize("https://blog.example.com/").unwrap(),
+            "https://blog.example.com"
+        );
+        assert!(SITE_URL.normalize("blog.example.com").is_err());
+        assert_eq!(
+            ROBOTS_DISALLOW.normalize("/admin, /drafts\n/api").unwrap(),
+            "/admin,/drafts,/api"
+        );
+        assert_eq!(
+            MAINTENANCE_EXEMPT_ROLE.normalize(" moderator").unwrap(),
+            "moderator"
+        );
+        assert!(MAINTENANCE_EXEMPT_ROLE.normalize("owner").is_err());
+    }
+
+    #[test]
+    fn booleans_accept_common_spellings() {
+        for value in ["1", "true", "TRUE", " yes ", "on"] {
+            assert_eq!(parse_bool(value), Some(true), "{value}");
+        }
+        for value in ["", "0", "false", "off"] {
+            assert_eq!(parse_bool(value), Some(false), "{value}");
+        }
+        assert_eq!(parse_bool("maybe"), None);
+    }
+
+    #[test]
+    fn registry_keys_are_unique() {
+        let mut keys: Vec<&str> = REGISTRY.iter().map(|s| s.key).collect();
+        keys.sort();
+        keys.dedup();
+        assert_eq!(keys.len(), REGISTRY.len());
+        assert!(find("SMTP_PASSWORD").is_some_and(|s| s.sensitive && !s.runtime));
+        assert!(find("PATH").is_none());
+    }
+}
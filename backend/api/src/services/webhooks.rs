@@ -0,0 +1,272 @@
+//! Outbound webhooks.
+//!
+//! Actions in the db layer call `webhook::Entity::emit` for the events in
+//! `webhook::EVENTS`; every enabled webhook subscribed to the event gets a
+//! `webhook_deliveries` row and a [`WebhookDeliver`] job on the `webhooks`
+//! queue. The job POSTs the stored JSON body with:
+//!
+//! - `X-Ruxlog-Event`: event name
+//! - `X-Ruxlog-Event-Id`: shared by every delivery of the event
+//! - `X-Ruxlog-Delivery`: delivery id
+//! - `X-Ruxlog-Signature`: `t=<unix seconds>,v1=<hex HMAC-SHA256>` of
+//!   `"<t>.<body>"` keyed with the webhook secret
+//!
+//! Any non-2xx answer, redirect or timeout is retried with the job backoff.
+//! A delivery that fails every attempt counts against its webhook, which is
+//! disabled after `WEBHOOK_DISABLE_AFTER` such failures in a row.
+
+use std::{sync::OnceLock, time::Duration};
+
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use sea_orm::EntityTrait;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sha2::Sha256;
+use tracing::{info, warn};
+
+use crate::{
+    db::sea_models::{
+        job,
+        webhook::{self, events},
+        webhook_delivery::{
+            self, DeliveryAttempt, DeliveryStatus, DELIVER_JOB_KIND, DELIVER_JOB_QUEUE,
+            DELIVER_MAX_ATTEMPTS, RESPONSE_BODY_LIMIT,
+        },
+    },
+    services::{
+        jobs::{Job, JobError, JobResult},
+        settings,
+    },
+    AppState,
+};
+
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(10);
+const SECRET_PREFIX: &str = "whsec_";
+
+static CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+
+fn client() -> &'static reqwest::Client {
+    CLIENT.get_or_init(|| {
+        reqwest::Client::builder()
+            .timeout(DELIVERY_TIMEOUT)
+            .redirect(reqwest::redirect::Policy::none())
+            .user_agent(concat!("Ruxlog-Webhooks/", env!("CARGO_PKG_VERSION")))
+            .build()
+            .unwrap_or_default()
+    })
+}
+
+/// New random signing secret.
+pub fn generate_secret() -> String {
+    let mut bytes = [0u8; 32];
+    rand::rng().fill_bytes(&mut bytes);
+    format!("{}{}", SECRET_PREFIX, hex::encode(bytes))
+}
+
+/// `X-Ruxlog-Signature` value for `body` sent at `timestamp`.
+pub fn sign(secret: &str, timestamp: i64, body: &str) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body.as_bytes());
+    format!(
+        "t={},v1={}",
+        timestamp,
+        hex::encode(mac.finalize().into_bytes())
+    )
+}
+
+/// Data of the `webhook.ping` event sent by the admin test endpoint.
+pub fn ping_data(webhook: &webhook::Model) -> serde_json::Value {
+    json!({ "webhook_id": webhook.id, "name": webhook.name, "event": events::PING })
+}
+
+/// Cut `body` to `RESPONSE_BODY_LIMIT` bytes on a char boundary.
+fn truncate_body(mut body: String) -> String {
+    if body.len() > RESPONSE_BODY_LIMIT {
+        let mut end = RESPONSE_BODY_LIMIT;
+        while !body.is_char_boundary(end) {
+            end -= 1;
+        }
+        body.truncate(end);
+    }
+    body
+}
+
+/// Send one webhook delivery; enqueued by `webhook_delivery::Entity::queue`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WebhookDeliver {
+    pub delivery_id: i64,
+}
+
+#[async_trait::async_trait]
+impl Job for WebhookDeliver {
+    const KIND: &'static str = DELIVER_JOB_KIND;
+    const QUEUE: &'static str = DELIVER_JOB_QUEUE;
+    const MAX_ATTEMPTS: i32 = DELIVER_MAX_ATTEMPTS;
+
+    async fn run(self, state: &AppState) -> JobResult {
+        let db = &state.sea_db;
+        // Gone along with its webhook.
+        let Some(delivery) = webhook_delivery::Entity::get_by_id(db, self.delivery_id).await?
+        else {
+            return Ok(Some(json!({ "skipped": "delivery deleted" })));
+        };
+        let Some(hook) = webhook::Entity::get_by_id(db, delivery.webhook_id).await? else {
+            return Ok(Some(json!({ "skipped": "webhook deleted" })));
+        };
+        if !hook.enabled {
+            webhook_delivery::Entity::record_attempt(
+                db,
+                delivery.id,
+                DeliveryAttempt {
+                    error: Some("Webhook is disabled".to_string()),
+                    ..Default::default()
+                },
+                DeliveryStatus::Failed,
+            )
+            .await?;
+            return Ok(Some(json!({ "skipped": "webhook disabled" })));
+        }
+
+        let body = serde_json::to_string(&delivery.payload)
+            .map_err(|err| JobError::permanent(err.to_string()))?;
+        let timestamp = chrono::Utc::now().timestamp();
+        let started = std::time::Instant::now();
+        let sent = client()
+            .post(&hook.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header("X-Ruxlog-Event", &delivery.event)
+            .header("X-Ruxlog-Event-Id", &delivery.event_id)
+            .header("X-Ruxlog-Delivery", delivery.id.to_string())
+            .header("X-Ruxlog-Signature", sign(&hook.secret, timestamp, &body))
+            .body(body)
+            .send()
+            .await;
+
+        let mut attempt = DeliveryAttempt::default();
+        let failure = match sent {
+            Ok(response) => {
+                let status = response.status();
+                attempt.response_status = Some(status.as_u16() as i16);
+                attempt.response_body = response.text().await.ok().map(truncate_body);
+                (!status.is_success()).then(|| format!("Endpoint answered {}", status))
+            }
+            Err(err) => Some(err.to_string()),
+        };
+        attempt.duration_ms = Some(started.elapsed().as_millis().min(i32::MAX as u128) as i32);
+
+        let Some(message) = failure else {
+            webhook_delivery::Entity::record_attempt(
+                db,
+                delivery.id,
+                attempt.clone(),
+                DeliveryStatus::Succeeded,
+            )
+            .await?;
+            webhook::Entity::record_success(db, hook.id).await?;
+            return Ok(Some(json!({
+                "webhook_id": hook.id,
+                "status": attempt.response_status,
+            })));
+        };
+
+        attempt.error = Some(message.clone());
+        // The job row knows whether the runner will try again.
+        let last_attempt = match delivery.job_id {
+            Some(job_id) => job::Entity::find_by_id(job_id)
+                .one(db)
+                .await?
+                .is_none_or(|job| job.attempts >= job.max_attempts),
+            None => true,
+        };
+        let status = if last_attempt {
+            DeliveryStatus::Failed
+        } else {
+            DeliveryStatus::Pending
+        };
+        webhook_delivery::Entity::record_attempt(db, delivery.id, attempt, status).await?;
+
+        if last_attempt {
+            let disable_after = settings::WEBHOOK_DISABLE_AFTER.int(state).await;
+            let updated =
+                webhook::Entity::record_failure(db, hook.id, disable_after as i32).await?;
+            if updated.is_some_and(|updated| !updated.enabled) {
+                warn!(
+                    webhook_id = hook.id,
+                    url = %hook.url,
+                    "Webhook disabled after repeated failed deliveries"
+                );
+            } else {
+                info!(
+                    webhook_id = hook.id,
+                    delivery_id = delivery.id,
+                    "Webhook delivery failed"
+                );
+            }
+        }
+        Err(JobError::new(message))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signature_covers_timestamp_and_body() {
+        let signature = sign("whsec_test", 1_700_000_000, r#"{"event":"post.created"}"#);
+        let (t, v1) = signature.split_once(",v1=").unwrap();
+        assert_eq!(t, "t=1700000000");
+        assert_eq!(v1.len(), 64);
+        assert_eq!(
+            signature,
+            sign("whsec_test", 1_700_000_000, r#"{"event":"post.created"}"#)
+        );
+        assert_ne!(
+            signature,
+            sign("whsec_test", 1_700_000_001, r#"{"event":"post.created"}"#)
+        );
+        assert_ne!(
+            signature,
+            sign("whsec_other", 1_700_000_000, r#"{"event":"post.created"}"#)
+        );
+    }
+
+    #[test]
+    fn events_match_names_and_prefixes() {
+        let patterns = |list: &[&str]| list.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        assert!(webhook::subscribes(&[], events::USER_DELETED));
+        assert!(webhook::subscribes(
+            &patterns(&["post.*"]),
+            events::POST_PUBLISHED
+        ));
+        assert!(webhook::subscribes(
+            &patterns(&["comment.created", "user.created"]),
+            events::USER_CREATED
+        ));
+        assert!(!webhook::subscribes(
+            &patterns(&["post.*"]),
+            events::COMMENT_CREATED
+        ));
+        assert!(!webhook::subscribes(
+            &patterns(&["po.*"]),
+            events::POST_CREATED
+        ));
+        assert!(!webhook::subscribes(
+            &patterns(&["post"]),
+            events::POST_CREATED
+        ));
+    }
+
+    #[test]
+    fn response_body_is_truncated_on_a_char_boundary() {
+        let body = "é".repeat(RESPONSE_BODY_LIMIT);
+        let truncated = truncate_body(body);
+        assert!(truncated.len() <= RESPONSE_BODY_LIMIT);
+        assert!(truncated.chars().all(|c| c == 'é'));
+        assert!(generate_secret().starts_with(SECRET_PREFIX));
+    }
+}
@@ -0,0 +1,209 @@
+//! WebSub publishing for `feed_v1`.
+//!
+//! Hubs are configured with the `WEBSUB_HUBS` setting (comma separated). When a post is
+//! published every feed it appears in (site, category, tags, author, series,
+//! each also narrowed to the post's locale, in each format) is announced to
+//! every hub with a `hub.mode=publish` ping. Locale feeds with fallback are
+//! not pinged.
+//! Pings are fire-and-forget; failures are only logged.
+
+use std::time::Duration;
+
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+use tracing::{info, info_span, warn, Instrument};
+
+use crate::{
+    db::sea_models::{category, post, post_series, post_series_post, tag},
+    services::settings,
+    AppState,
+};
+
+const PING_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeedFormat {
+    Rss,
+    Atom,
+    Json,
+}
+
+impl FeedFormat {
+    pub const ALL: [FeedFormat; 3] = [FeedFormat::Rss, FeedFormat::Atom, FeedFormat::Json];
+
+    pub fn path(self) -> &'static str {
+        match self {
+            Self::Rss => "rss",
+            Self::Atom => "atom",
+            Self::Json => "json",
+        }
+    }
+}
+
+pub fn site_url() -> String {
+    settings::SITE_URL.env_string()
+}
+
+/// Canonical scope query string, parameters in a fixed order.
+pub fn scope_query(
+    category: Option<&str>,
+    tag: Option<&str>,
+    author: Option<i32>,
+    series: Option<&str>,
+    locale: Option<&str>,
+) -> String {
+    let mut params = Vec::new();
+    if let Some(slug) = category {
+        params.push(format!("category={}", urlencoding::encode(slug)));
+    }
+    if let Some(slug) = tag {
+        params.push(format!("tag={}", urlencoding::encode(slug)));
+    }
+    if let Some(id) = author {
+        params.push(format!("author={}", id));
+    }
+    if let Some(slug) = series {
+        params.push(format!("series={}", urlencoding::encode(slug)));
+    }
+    if let Some(locale) = locale {
+        params.push(format!("locale={}", urlencoding::encode(locale)));
+    }
+    params.join("&")
+}
+
+/// Public URL of a feed; this is the WebSub topic.
+pub fn feed_url(format: FeedFormat, scope_query: &str) -> String {
+    let mut url = format!("{}/feed/v1/{}", site_url(), format.path());
+    if !scope_query.is_empty() {
+        url.push('?');
+        url.push_str(scope_query);
+    }
+    url
+}
+
+/// Ping the configured hubs for every feed containing the post, if it is
+/// published. Runs in the background.
+pub fn notify_post_published(state: &AppState, post_id: i32) {
+    let state = state.clone();
+    tokio::spawn(
+        async move {
+            let hubs = settings::WEBSUB_HUBS.list(&state).await;
+            if hubs.is_empty() {
+                return;
+            }
+            let topics = match post_topics(&state, post_id).await {
+                Ok(Some(topics)) => topics,
+                Ok(None) => return,
+                Err(err) => {
+                    warn!(post_id, error = ?err, "Failed to resolve WebSub topics");
+                    return;
+                }
+            };
+            ping(&hubs, &topics).await;
+        }
+        .instrument(info_span!("websub_publish", post_id)),
+    );
+}
+
+async fn post_topics(
+    state: &AppState,
+    post_id: i32,
+) -> Result<Option<Vec<String>>, sea_orm::DbErr> {
+    let db = &state.sea_db;
+    let Some(post) = post::Entity::find_by_id(post_id).one(db).await? else {
+        return Ok(None);
+    };
+    if post.status != post::PostStatus::Published {
+        return Ok(None);
+    }
+
+    let category = category::Entity::find_by_id(post.category_id)
+        .one(db)
+        .await?;
+    let tags = if post.tag_ids.is_empty() {
+        Vec::new()
+    } else {
+        tag::Entity::find()
+            .filter(tag::Column::Id.is_in(post.tag_ids.clone()))
+            .all(db)
+            .await?
+    };
+    let series = post_series::Entity::find()
+        .inner_join(post_series_post::Entity)
+        .filter(post_series_post::Column::PostId.eq(post.id))
+        .all(db)
+        .await?;
+
+    let mut scopes = Vec::new();
+    for locale in [None, Some(post.locale.as_str())] {
+        scopes.push(scope_query(None, None, None, None, locale));
+        scopes.push(scope_query(None, None, Some(post.author_id), None, locale));
+        if let Some(category) = &category {
+            scopes.push(scope_query(Some(&category.slug), None, None, None, locale));
+        }
+        scopes.extend(
+            tags.iter()
+                .map(|t| scope_query(None, Some(&t.slug), None, None, locale)),
+        );
+        scopes.extend(
+            series
+                .iter()
+                .map(|s| scope_query(None, None, None, Some(&s.slug), locale)),
+        );
+    }
+
+    Ok(Some(
+        scopes
+            .iter()
+            .flat_map(|scope| FeedFormat::ALL.map(|format| feed_url(format, scope)))
+            .collect(),
+    ))
+}
+
+async fn ping(hubs: &[String], topics: &[String]) {
+    let client = match reqwest::Client::builder().timeout(PING_TIMEOUT).build() {
+        Ok(client) => client,
+        Err(err) => {
+            warn!(error = ?err, "Failed to build WebSub client");
+            return;
+        }
+    };
+
+    for hub in hubs {
+        for topic in topics {
+            let result = client
+                .post(hub)
+                .form(&[("hub.mode", "publish"), ("hub.url", topic.as_str())])
+                .send()
+                .await
+                .and_then(|response| response.error_for_status());
+            match result {
+                Ok(_) => info!(hub = %hub, topic = %topic, "WebSub hub notified"),
+                Err(err) => warn!(hub = %hub, topic = %topic, error = %err, "WebSub ping failed"),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scope_query_is_canonical() {
+        assert_eq!(scope_query(None, None, None, None, None), "");
+        assert_eq!(
+            scope_query(
+                Some("rust lang"),
+                Some("async"),
+                Some(3),
+                Some("intro"),
+                None
+            ),
+            "category=rust%20lang&tag=async&author=3&series=intro"
+        );
+        assert_eq!(
+            scope_query(None, Some("async"), None, None, Some("pt-br")),
+            "tag=async&locale=pt-br"
+        );
+    }
+}
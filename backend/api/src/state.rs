@@ -39,6 +39,6 @@ pub struct AppState {
 
 impl FromRef<AppState> for AuthBackend {
     fn from_ref(state: &AppState) -> Self {
-        AuthBackend::new(&state.sea_db)
+        AuthBackend::new(&state.sea_db).with_redis(&state.redis_pool)
     }
 }
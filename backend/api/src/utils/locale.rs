@@ -0,0 +1,64 @@
+use std::sync::OnceLock;
+
+use validator::ValidationError;
+
+use crate::services::settings;
+
+/// Locale of content saved without one: the `DEFAULT_LOCALE` setting, `en`
+/// when unset or invalid.
+pub fn default_locale() -> &'static str {
+    static DEFAULT: OnceLock<String> = OnceLock::new();
+    DEFAULT.get_or_init(|| {
+        settings::DEFAULT_LOCALE
+            .env_value()
+            .and_then(|value| normalize_locale(&value))
+            .unwrap_or_else(|| "en".to_string())
+    })
+}
+
+/// Normalize a BCP 47-style tag (`pt_BR` -> `pt-br`): a 2-3 letter language
+/// followed by optional 2-8 character alphanumeric subtags. `None` when the
+/// tag doesn't have that shape.
+pub fn normalize_locale(input: &str) -> Option<String> {
+    let tag = input.trim().replace('_', "-").to_ascii_lowercase();
+    if tag.len() > 16 {
+        return None;
+    }
+    let mut parts = tag.split('-');
+    let language = parts.next()?;
+    if !(2..=3).contains(&language.len()) || !language.chars().all(|c| c.is_ascii_lowercase()) {
+        return None;
+    }
+    for part in parts {
+        if !(2..=8).contains(&part.len()) || !part.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return None;
+        }
+    }
+    Some(tag)
+}
+
+pub fn validate_locale(s: &str) -> Result<(), ValidationError> {
+    if normalize_locale(s).is_some() {
+        Ok(())
+    } else {
+        Err(ValidationError::new("locale"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_locale_accepts_language_and_region_tags() {
+        assert_eq!(normalize_locale("en").as_deref(), Some("en"));
+        assert_eq!(normalize_locale(" pt_BR ").as_deref(), Some("pt-br"));
+        assert_eq!(
+            normalize_locale("zh-Hant-TW").as_deref(),
+            Some("zh-hant-tw")
+        );
+        assert_eq!(normalize_locale("e"), None);
+        assert_eq!(normalize_locale("en-"), None);
+        assert_eq!(normalize_locale("en'; --"), None);
+    }
+}
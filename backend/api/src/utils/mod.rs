@@ -2,9 +2,13 @@
 
 pub mod color;
 pub mod cors;
+pub mod locale;
+pub mod slug;
 pub mod sort;
 pub mod telemetry;
 pub mod twofa;
 pub use color::*;
+pub use locale::*;
+pub use slug::*;
 pub use sort::*;
 pub use twofa::*;
@@ -0,0 +1,33 @@
+/// Turn arbitrary text into a URL slug: lowercase ASCII alphanumerics separated
+/// by single hyphens. Non-ASCII letters are dropped rather than transliterated.
+pub fn slugify(input: &str) -> String {
+    let mut slug = String::with_capacity(input.len());
+    let mut pending_dash = false;
+
+    for ch in input.chars() {
+        if ch.is_ascii_alphanumeric() {
+            if pending_dash && !slug.is_empty() {
+                slug.push('-');
+            }
+            pending_dash = false;
+            slug.push(ch.to_ascii_lowercase());
+        } else {
+            pending_dash = true;
+        }
+    }
+
+    slug
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slugify_collapses_separators() {
+        assert_eq!(slugify("Hello, World!"), "hello-world");
+        assert_eq!(slugify("  Rust & WASM -- 2024 "), "rust-wasm-2024");
+        assert_eq!(slugify("already-a-slug"), "already-a-slug");
+        assert_eq!(slugify("!!!"), "");
+    }
+}
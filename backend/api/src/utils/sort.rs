@@ -1,7 +1,8 @@
+use schemars::JsonSchema;
 use sea_orm::Order;
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, JsonSchema)]
 pub struct SortParam {
     pub field: String,
     #[serde(
@@ -9,6 +10,7 @@ pub struct SortParam {
         deserialize_with = "deserialize_order",
         serialize_with = "serialize_order"
     )]
+    #[schemars(schema_with = "order_schema")]
     pub order: Order,
 }
 
@@ -16,6 +18,10 @@ fn default_order() -> Order {
     Order::Desc
 }
 
+fn order_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+    schemars::json_schema!({ "type": "string", "enum": ["asc", "desc"], "default": "desc" })
+}
+
 fn serialize_order<S>(order: &Order, serializer: S) -> Result<S::Ok, S::Error>
 where
     S: serde::Serializer,
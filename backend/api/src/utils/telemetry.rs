@@ -499,6 +499,43 @@ impl MailMetrics {
     }
 }
 
+/// Shared background job metrics, labelled with `job.kind` and `job.queue`
+pub struct JobMetrics {
+    pub enqueued: Counter<u64>,
+    pub succeeded: Counter<u64>,
+    pub retried: Counter<u64>,
+    pub dead: Counter<u64>,
+    pub duration: Histogram<f64>,
+}
+
+impl JobMetrics {
+    pub fn new(meter: &Meter) -> Self {
+        Self {
+            enqueued: meter
+                .u64_counter("jobs.enqueued")
+                .with_description("Jobs added to a queue")
+                .build(),
+            succeeded: meter
+                .u64_counter("jobs.succeeded")
+                .with_description("Jobs that ran successfully")
+                .build(),
+            retried: meter
+                .u64_counter("jobs.retried")
+                .with_description("Failed job attempts scheduled for a retry")
+                .build(),
+            dead: meter
+                .u64_counter("jobs.dead")
+                .with_description("Jobs that used up their attempts")
+                .build(),
+            duration: meter
+                .f64_histogram("jobs.duration")
+                .with_description("Job attempt duration in milliseconds")
+                .with_unit("ms")
+                .build(),
+        }
+    }
+}
+
 impl HttpMetrics {
     pub fn new(meter: &Meter) -> Self {
         let request_duration = meter
@@ -531,6 +568,7 @@ static AUTH_METRICS: OnceLock<AuthMetrics> = OnceLock::new();
 static IMAGE_METRICS: OnceLock<ImageMetrics> = OnceLock::new();
 static LIMITER_METRICS: OnceLock<LimiterMetrics> = OnceLock::new();
 static MAIL_METRICS: OnceLock<MailMetrics> = OnceLock::new();
+static JOB_METRICS: OnceLock<JobMetrics> = OnceLock::new();
 
 pub fn http_metrics() -> &'static HttpMetrics {
     HTTP_METRICS.get_or_init(|| HttpMetrics::new(&global_meter()))
@@ -552,6 +590,10 @@ pub fn mail_metrics() -> &'static MailMetrics {
     MAIL_METRICS.get_or_init(|| MailMetrics::new(&global_meter()))
 }
 
+pub fn job_metrics() -> &'static JobMetrics {
+    JOB_METRICS.get_or_init(|| JobMetrics::new(&global_meter()))
+}
+
 pub fn init_pool_metrics() {
     POOL_METRICS.get_or_init(|| PoolMetrics::new(&global_meter()));
 }
@@ -0,0 +1,51 @@
+//! The DTOs in `crates/ruxlog-client` are generated from the OpenAPI
+//! document by `src/modules/docs_v1/client_codegen.rs`. The committed
+//! `dto/generated.rs` must match what the current schemas generate.
+
+use std::collections::BTreeSet;
+
+use ruxlog::{
+    error::ErrorCode,
+    modules::docs_v1::{client_codegen, spec},
+};
+use serde_json::json;
+
+#[test]
+fn client_dtos_are_up_to_date() {
+    let committed = include_str!("../crates/ruxlog-client/src/dto/generated.rs");
+    let generated = client_codegen::client_dtos(&spec::document());
+    assert!(
+        committed == generated,
+        "crates/ruxlog-client/src/dto/generated.rs is stale; run `cargo run --bin generate_client_dtos`"
+    );
+}
+
+#[test]
+fn client_knows_every_error_code() {
+    let backend: BTreeSet<String> = spec::document()["components"]["schemas"]["ErrorCode"]["enum"]
+        .as_array()
+        .expect("ErrorCode enum")
+        .iter()
+        .map(|code| code.as_str().unwrap().to_string())
+        .collect();
+    let client: BTreeSet<String> = ruxlog_client::ErrorCode::ALL
+        .iter()
+        .map(|code| code.as_str().to_string())
+        .collect();
+    assert_eq!(backend, client);
+}
+
+#[test]
+fn backend_error_bodies_decode() {
+    let code = ErrorCode::SlugAlreadyExists;
+    let body = ruxlog::error::ErrorResponse::new(code)
+        .with_context(json!({ "field": "slug" }))
+        .with_request_id("req-1");
+    let decoded: ruxlog_client::ErrorResponse =
+        serde_json::from_value(serde_json::to_value(&body).unwrap()).unwrap();
+
+    assert_eq!(decoded.code, ruxlog_client::ErrorCode::SlugAlreadyExists);
+    assert_eq!(decoded.status, code.status_code().as_u16());
+    assert_eq!(decoded.context, Some(json!({ "field": "slug" })));
+    assert_eq!(decoded.request_id.as_deref(), Some("req-1"));
+}
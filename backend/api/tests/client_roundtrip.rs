@@ -0,0 +1,195 @@
+//! `ruxlog-client` against a local server behind the real CSRF guard: the
+//! token header, the session cookie, multipart uploads, plain-text bodies and
+//! `ErrorResponse` decoding, without a database.
+
+use std::collections::HashMap;
+
+use axum::{
+    extract::{Multipart, Query},
+    http::{header, HeaderMap},
+    middleware,
+    response::IntoResponse,
+    routing::{get, post},
+    Json, Router,
+};
+use ruxlog::{
+    error::{ErrorCode, ErrorResponse},
+    middlewares::static_csrf::{csrf_guard, get_static_csrf_key},
+};
+use ruxlog_client::{
+    dto::{MediaReference, MediaUploadMetadata, RenderFormat, V1LoginPayload, V1PostUnlockPayload},
+    Client, Error, Upload,
+};
+use serde_json::{json, Value};
+
+fn user() -> Value {
+    json!({
+        "id": 7,
+        "name": "Ada",
+        "email": "ada@example.com",
+        "is_verified": true,
+        "role": "author",
+        "two_fa_enabled": false,
+        "created_at": "2026-01-01T00:00:00+00:00",
+        "updated_at": "2026-01-01T00:00:00+00:00",
+    })
+}
+
+async fn log_in(Json(payload): Json<Value>) -> impl IntoResponse {
+    assert_eq!(payload["email"], "ada@example.com");
+    ([(header::SET_COOKIE, "id=session-7; Path=/")], Json(user()))
+}
+
+async fn current_user(headers: HeaderMap) -> Result<Json<Value>, ErrorResponse> {
+    let cookie = headers
+        .get(header::COOKIE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default();
+    if cookie.contains("id=session-7") {
+        Ok(Json(user()))
+    } else {
+        Err(ErrorResponse::new(ErrorCode::Unauthorized))
+    }
+}
+
+async fn missing_tag() -> Result<Json<Value>, ErrorResponse> {
+    Err(ErrorResponse::new(ErrorCode::TagNotFound).with_request_id("req-42"))
+}
+
+async fn view_post(
+    Query(query): Query<HashMap<String, String>>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    match query.get("format").map(String::as_str) {
+        Some("html") => Ok((
+            [(header::CONTENT_TYPE, "text/html; charset=utf-8")],
+            "<p>Hello</p>",
+        )
+            .into_response()),
+        _ => Err(ErrorResponse::new(ErrorCode::RecordNotFound)),
+    }
+}
+
+async fn upload(mut multipart: Multipart) -> Json<Value> {
+    let mut file = None;
+    let mut reference_type = None;
+    while let Some(field) = multipart.next_field().await.unwrap() {
+        match field.name().unwrap_or_default() {
+            "file" => {
+                let name = field.file_name().unwrap().to_string();
+                let mime = field.content_type().unwrap().to_string();
+                let size = field.bytes().await.unwrap().len();
+                file = Some((name, mime, size));
+            }
+            "reference_type" => reference_type = Some(field.text().await.unwrap()),
+            _ => {}
+        }
+    }
+    let (name, mime, size) = file.expect("file field");
+    Json(json!({
+        "id": 1,
+        "object_key": name,
+        "file_url": format!("https://cdn.example.com/{}", name),
+        "mime_type": mime,
+        "size": size,
+        "reference_type": reference_type,
+        "is_optimized": false,
+        "created_at": "2026-01-01T00:00:00+00:00",
+        "updated_at": "2026-01-01T00:00:00+00:00",
+    }))
+}
+
+async fn serve() -> String {
+    let router = Router::new()
+        .route("/auth/v1/log_in", post(log_in))
+        .route("/user/v1/get", get(current_user))
+        .route("/tag/v1/view/{tag_id}", post(missing_tag))
+        .route("/post/v1/view/{id_or_slug}", post(view_post))
+        .route("/media/v1/create", post(upload))
+        .layer(middleware::from_fn(csrf_guard));
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+    format!("http://{}", address)
+}
+
+#[tokio::test]
+async fn decodes_api_errors() {
+    let base_url = serve().await;
+
+    let error = Client::new(&base_url).tags().view(3).await.unwrap_err();
+    assert_eq!(error.code(), Some(ruxlog_client::ErrorCode::InvalidToken));
+    match &error {
+        Error::Api(response) => assert_eq!(response.context, Some(json!({ "reason": "missing" }))),
+        other => panic!("expected Api error, got {:?}", other),
+    }
+
+    let client = Client::new(&base_url).with_csrf_key(&get_static_csrf_key());
+    let error = client.tags().view(3).await.unwrap_err();
+    assert_eq!(error.code(), Some(ruxlog_client::ErrorCode::TagNotFound));
+    assert_eq!(error.status(), Some(404));
+    match error {
+        Error::Api(response) => assert_eq!(response.request_id.as_deref(), Some("req-42")),
+        other => panic!("expected Api error, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn keeps_the_session_cookie() {
+    let client = Client::new(serve().await).with_csrf_key(&get_static_csrf_key());
+
+    let error = client.users().get().await.unwrap_err();
+    assert_eq!(error.code(), Some(ruxlog_client::ErrorCode::Unauthorized));
+
+    let logged_in = client
+        .auth()
+        .log_in(&V1LoginPayload {
+            email: "ada@example.com".to_string(),
+            password: "secret".to_string(),
+        })
+        .await
+        .unwrap();
+    let user = client.users().get().await.unwrap();
+    assert_eq!(user, logged_in);
+    assert_eq!(user.id, 7);
+}
+
+#[tokio::test]
+async fn uploads_multipart() {
+    let client = Client::new(serve().await).with_csrf_key(&get_static_csrf_key());
+
+    let media = client
+        .media()
+        .upload(
+            &Upload::new("cover.png", "image/png", vec![0u8; 128]),
+            &MediaUploadMetadata {
+                reference_type: Some(MediaReference::Post),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(media.object_key, "cover.png");
+    assert_eq!(media.mime_type, "image/png");
+    assert_eq!(media.size, 128);
+    assert_eq!(media.reference_type, Some(MediaReference::Post));
+}
+
+#[tokio::test]
+async fn returns_rendered_content_as_text() {
+    let client = Client::new(serve().await).with_csrf_key(&get_static_csrf_key());
+
+    let html = client
+        .posts()
+        .render(
+            "hello-world",
+            RenderFormat::Html,
+            None,
+            &V1PostUnlockPayload::default(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(html, "<p>Hello</p>");
+
+    let error = client.posts().view("hello-world").await.unwrap_err();
+    assert_eq!(error.code(), Some(ruxlog_client::ErrorCode::RecordNotFound));
+}
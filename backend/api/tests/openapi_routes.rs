@@ -0,0 +1,124 @@
+//! Every route mounted by `router::router()` must be documented in the
+//! OpenAPI spec (`docs_v1::spec::operations`), and the spec must not list
+//! routes that no longer exist. Routes come from the built router itself:
+//! axum has no API to list them, so paths are read from the router's `Debug`
+//! output and each method is confirmed by mounting it again, which axum
+//! rejects with an overlapping route panic.
+
+use std::{collections::BTreeSet, panic};
+
+use axum::{
+    routing::{on, MethodFilter},
+    Router,
+};
+use regex::Regex;
+use ruxlog::{modules::docs_v1::spec, router, AppState};
+
+const METHODS: [(&str, MethodFilter); 5] = [
+    ("GET", MethodFilter::GET),
+    ("POST", MethodFilter::POST),
+    ("PUT", MethodFilter::PUT),
+    ("PATCH", MethodFilter::PATCH),
+    ("DELETE", MethodFilter::DELETE),
+];
+
+/// Paths in the router's route table, without the fallbacks axum adds for
+/// nested routers.
+fn mounted_paths(router: &Router<AppState>) -> BTreeSet<String> {
+    let debug = format!("{:?}", router);
+    let path_router = debug
+        .split(", fallback_router: ")
+        .next()
+        .expect("router debug output");
+    Regex::new(r#"RouteId\(\d+\): "([^"]+)""#)
+        .unwrap()
+        .captures_iter(path_router)
+        .map(|found| found[1].to_string())
+        .filter(|path| !path.contains("__private__axum"))
+        .collect()
+}
+
+/// Whether `router` already answers `method` on `path`.
+fn mounts(router: &Router<AppState>, path: &str, method: MethodFilter) -> bool {
+    let router = router.clone();
+    let added = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+        router.route(path, on(method, || async {}))
+    }));
+    match added {
+        Ok(_) => false,
+        Err(payload) => {
+            let message = payload
+                .downcast_ref::<String>()
+                .map(String::as_str)
+                .or_else(|| payload.downcast_ref::<&str>().copied())
+                .unwrap_or_default();
+            assert!(
+                message.contains("Overlapping method route"),
+                "unexpected panic probing {} {:?}: {}",
+                path,
+                method,
+                message
+            );
+            true
+        }
+    }
+}
+
+fn mounted_routes() -> BTreeSet<(String, String)> {
+    let router = router::router();
+    let paths = mounted_paths(&router);
+
+    // Probing panics by design; keep them out of the test output.
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let mut routes = BTreeSet::new();
+    for path in &paths {
+        for (name, method) in METHODS {
+            if mounts(&router, path, method) {
+                routes.insert((name.to_string(), path.clone()));
+            }
+        }
+    }
+    panic::set_hook(hook);
+    routes
+}
+
+#[test]
+fn every_mounted_route_is_documented() {
+    let mounted = mounted_routes();
+    assert!(mounted.len() > 200, "only found {} routes", mounted.len());
+
+    let documented: BTreeSet<(String, String)> = spec::operations()
+        .iter()
+        .map(|operation| (operation.method.to_string(), operation.path.to_string()))
+        .collect();
+
+    let undocumented: Vec<_> = mounted.difference(&documented).collect();
+    assert!(
+        undocumented.is_empty(),
+        "routes missing from docs_v1::spec::operations: {:?}",
+        undocumented
+    );
+    let stale: Vec<_> = documented.difference(&mounted).collect();
+    assert!(
+        stale.is_empty(),
+        "documented routes that are not mounted: {:?}",
+        stale
+    );
+}
+
+#[test]
+fn document_describes_errors_and_itself() {
+    let document = spec::document();
+    assert_eq!(document["openapi"], "3.0.3");
+    assert!(document["components"]["schemas"]["ErrorCode"]["enum"]
+        .as_array()
+        .is_some_and(|codes| codes.iter().any(|code| code == "AUTH_001")));
+    assert!(document["components"]["schemas"]["ErrorResponse"].is_object());
+    assert!(document["paths"]["/docs/openapi.json"]["get"].is_object());
+    assert_eq!(
+        document["paths"]["/auth/v1/log_in"]["post"]["requestBody"]["content"]["application/json"]
+            ["schema"]["$ref"],
+        "#/components/schemas/V1LoginPayload"
+    );
+}
@@ -1,6 +1,6 @@
 use axum::{
     body::{Body, Bytes},
-    extract::Multipart,
+    extract::{DefaultBodyLimit, Multipart},
     http::{Request, StatusCode},
     routing::post,
     Router,
@@ -49,7 +49,28 @@ fn build_router() -> Router {
                 .route("/upload", post(accept_multipart))
                 .layer(RequestBodyLimitLayer::new(body_limits::MEDIA)),
         )
+        .nest(
+            "/post/v1/import",
+            Router::new()
+                .route("/markdown", post(accept_multipart))
+                .layer(RequestBodyLimitLayer::new(body_limits::IMPORT)),
+        )
         .layer(RequestBodyLimitLayer::new(body_limits::DEFAULT))
+        // Archive routes raise the extractor limit themselves (as backup_v1
+        // and import_v1 do), so they sit outside the default layer
+        .nest(
+            "/backup/v1",
+            Router::new()
+                .route("/restore", post(accept_multipart))
+                .layer(DefaultBodyLimit::max(body_limits::RESTORE)),
+        )
+        .nest(
+            "/import/v1",
+            Router::new()
+                .route("/wordpress", post(accept_multipart))
+                .route("/ghost", post(accept_multipart))
+                .layer(DefaultBodyLimit::max(body_limits::BLOG_IMPORT)),
+        )
 }
 
 fn multipart_body(boundary: &str, filename: &str, bytes: &[u8]) -> Vec<u8> {
@@ -69,6 +90,52 @@ fn multipart_body(boundary: &str, filename: &str, bytes: &[u8]) -> Vec<u8> {
     body
 }
 
+/// Streams a multipart body with `len` bytes of file content so the larger
+/// archive limits can be exercised without buffering the whole payload.
+fn streamed_multipart_body(boundary: &str, filename: &str, len: usize) -> Body {
+    const CHUNK: usize = 1024 * 1024;
+
+    let head = Bytes::from(format!(
+        "--{}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"{}\"\r\nContent-Type: application/octet-stream\r\n\r\n",
+        boundary, filename
+    ));
+    let tail = Bytes::from(format!("\r\n--{}--\r\n", boundary));
+    let chunk = Bytes::from(vec![b'a'; CHUNK]);
+
+    let mut parts = vec![head];
+    let mut remaining = len;
+    while remaining > 0 {
+        let size = remaining.min(CHUNK);
+        parts.push(chunk.slice(..size));
+        remaining -= size;
+    }
+    parts.push(tail);
+
+    Body::from_stream(tokio_stream::iter(
+        parts.into_iter().map(Ok::<_, std::io::Error>),
+    ))
+}
+
+async fn send_streamed_multipart(uri: &str, len: usize) -> StatusCode {
+    let boundary = "BOUNDARY";
+    let response = build_router()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri(uri)
+                .header(
+                    "content-type",
+                    format!("multipart/form-data; boundary={}", boundary),
+                )
+                .body(streamed_multipart_body(boundary, "archive.bin", len))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    response.status()
+}
+
 #[tokio::test]
 async fn default_payload_under_limit_is_accepted() {
     let app = build_router();
@@ -198,3 +265,62 @@ async fn media_payload_over_limit_is_rejected() {
 
     assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
 }
+
+#[tokio::test]
+async fn import_payload_over_limit_is_rejected() {
+    let app = build_router();
+    let bytes = vec![b'a'; body_limits::IMPORT + 1024];
+    let boundary = "BOUNDARY";
+    let body = multipart_body(boundary, "posts.zip", &bytes);
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/post/v1/import/markdown")
+                .header(
+                    "content-type",
+                    format!("multipart/form-data; boundary={}", boundary),
+                )
+                .body(Body::from(body))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+}
+
+#[tokio::test]
+async fn restore_payload_under_limit_is_accepted() {
+    // Larger than every other route limit, so only the restore override lets it through.
+    let status =
+        send_streamed_multipart("/backup/v1/restore", body_limits::BLOG_IMPORT + 1024).await;
+
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn restore_payload_over_limit_is_rejected() {
+    let status = send_streamed_multipart("/backup/v1/restore", body_limits::RESTORE + 1024).await;
+
+    assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+}
+
+#[tokio::test]
+async fn blog_import_payload_under_limit_is_accepted() {
+    for uri in ["/import/v1/wordpress", "/import/v1/ghost"] {
+        let status = send_streamed_multipart(uri, body_limits::IMPORT + 1024).await;
+
+        assert_eq!(status, StatusCode::OK, "{}", uri);
+    }
+}
+
+#[tokio::test]
+async fn blog_import_payload_over_limit_is_rejected() {
+    for uri in ["/import/v1/wordpress", "/import/v1/ghost"] {
+        let status = send_streamed_multipart(uri, body_limits::BLOG_IMPORT + 1024).await;
+
+        assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE, "{}", uri);
+    }
+}
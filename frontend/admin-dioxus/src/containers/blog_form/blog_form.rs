@@ -727,6 +727,16 @@ pub fn BlogFormContainer(post_id: Option<i32>) -> Element {
 
                         if let Some(id) = post_id {
                             // Edit existing post
+                            let Some(updated_at) = posts
+                                .view
+                                .read()
+                                .get(&id)
+                                .and_then(|frame| frame.data.as_ref())
+                                .map(|post| post.updated_at)
+                            else {
+                                tracing::error!("Post is not loaded yet");
+                                return;
+                            };
                             let payload = PostEditPayload {
                                         title: Some(form_data.data.title.clone()),
                                         content: Some(serde_json::from_str::<PostContent>(&form_data.data.content).unwrap()),
@@ -745,6 +755,7 @@ pub fn BlogFormContainer(post_id: Option<i32>) -> Element {
                                         category_id: form_data.data.category_id,
                                         tag_ids: Some(form_data.data.tag_ids.clone()),
                                         published_at: None,
+                                        updated_at,
                                     };
 
                                     spawn(async move {
@@ -383,6 +383,8 @@ pub struct PostEditPayload {
     pub featured_image_id: Option<i32>,
     pub category_id: Option<i32>,
     pub tag_ids: Option<Vec<i32>>,
+    /// The `updated_at` of the post as loaded; the save is rejected if it moved on
+    pub updated_at: DateTime<Utc>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]